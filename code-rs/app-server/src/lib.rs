@@ -144,6 +144,7 @@ pub async fn run_main_with_transport(
             })?
         }
     };
+    code_core::http_client::set_network_config(&config.network);
 
     let request_routes = Arc::new(tokio::sync::Mutex::new(HashMap::<RequestId, RequestRoute>::new()));
     let request_routes_for_outbound = Arc::clone(&request_routes);
@@ -261,6 +261,11 @@ impl ApplyPatchAction {
         &self.changes
     }
 
+    /// Drops every change for which `keep` returns false.
+    pub fn retain_changes(&mut self, keep: impl FnMut(&PathBuf, &mut ApplyPatchFileChange) -> bool) {
+        self.changes.retain(keep);
+    }
+
     /// Should be used exclusively for testing. (Not worth the overhead of
     /// creating a feature flag for this.)
     pub fn new_add_for_test(path: &Path, content: String) -> Self {
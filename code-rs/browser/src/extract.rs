@@ -0,0 +1,252 @@
+//! Text views of a page for the model: the rendered DOM text, or an outline
+//! of the accessibility tree. Both are far cheaper than a screenshot on
+//! text-heavy pages and can be read exactly.
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Which text view of the page to extract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtractFormat {
+    /// Rendered text with headings, list items, links and form fields marked.
+    #[default]
+    Text,
+    /// Indented outline of the accessibility tree: roles, names and states.
+    Accessibility,
+}
+
+/// A text view of a page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageExtract {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub format: ExtractFormat,
+    pub content: String,
+}
+
+/// Walks the rendered DOM (including open shadow roots) and returns the text
+/// a reader would see. Hidden elements, scripts and styles are skipped;
+/// password values are never read.
+pub(crate) const VISIBLE_TEXT_JS: &str = r#"(() => {
+  const SKIP = new Set(['SCRIPT', 'STYLE', 'NOSCRIPT', 'TEMPLATE', 'SVG', 'CANVAS', 'IFRAME']);
+  const BLOCK = /^(P|DIV|SECTION|ARTICLE|MAIN|HEADER|FOOTER|NAV|ASIDE|LI|UL|OL|DL|DT|DD|TABLE|TR|H[1-6]|PRE|BLOCKQUOTE|FORM|FIELDSET|FIGCAPTION|BR|HR|DETAILS|SUMMARY)$/;
+  const out = [];
+  const rendered = (el) => {
+    const style = getComputedStyle(el);
+    if (style.display === 'none' || style.visibility === 'hidden') return false;
+    if (el.getAttribute('aria-hidden') === 'true') return false;
+    return style.display === 'contents' || el.getClientRects().length > 0;
+  };
+  const walk = (node) => {
+    if (node.nodeType === Node.TEXT_NODE) {
+      const text = node.textContent.replace(/\s+/g, ' ');
+      if (text.trim()) out.push(text);
+      return;
+    }
+    if (node.nodeType !== Node.ELEMENT_NODE) return;
+    const el = node;
+    const tag = el.tagName;
+    if (SKIP.has(tag) || !rendered(el)) return;
+    const block = BLOCK.test(tag);
+    if (block) out.push('\n');
+    const heading = /^H([1-6])$/.exec(tag);
+    if (heading) out.push('#'.repeat(Number(heading[1])) + ' ');
+    if (tag === 'LI') out.push('- ');
+    if (tag === 'IMG') {
+      if (el.alt) out.push(`[image: ${el.alt}]`);
+      return;
+    }
+    if (tag === 'INPUT' || tag === 'TEXTAREA' || tag === 'SELECT') {
+      const type = (el.type || tag).toLowerCase();
+      if (type === 'hidden') return;
+      const label = el.getAttribute('aria-label') || el.placeholder || el.name || '';
+      let field = `[${type}${label ? ' ' + label : ''}`;
+      if (type === 'checkbox' || type === 'radio') field += el.checked ? ' ✓' : ' ☐';
+      else if (type !== 'password' && el.value) field += ` = ${String(el.value).slice(0, 200)}`;
+      out.push(field + '] ');
+      return;
+    }
+    if (tag === 'BUTTON') out.push('[button] ');
+    for (const child of el.childNodes) walk(child);
+    if (el.shadowRoot) for (const child of el.shadowRoot.childNodes) walk(child);
+    if (tag === 'A' && el.href && !el.href.startsWith('javascript:')) out.push(` (${el.href})`);
+    if (block) out.push('\n');
+  };
+  walk(document.body || document.documentElement);
+  const text = out.join('')
+    .replace(/[ \t]+\n/g, '\n')
+    .replace(/\n[ \t]+/g, '\n')
+    .replace(/\n{3,}/g, '\n\n')
+    .trim();
+  return { url: location.href, title: document.title, text };
+})()"#;
+
+/// Accessibility roles that only group other nodes; they are flattened into
+/// their children unless they carry a name.
+const STRUCTURAL_ROLES: [&str; 4] = ["generic", "none", "presentation", "LineBreak"];
+
+/// Node properties worth showing next to the role and name.
+const SHOWN_PROPERTIES: [&str; 8] = [
+    "focused", "checked", "selected", "expanded", "disabled", "required", "level", "url",
+];
+
+/// Formats the result of `Accessibility.getFullAXTree` as an indented
+/// outline, one `- role "name"` line per meaningful node.
+pub fn format_accessibility_tree(tree: &Value) -> String {
+    let nodes: Vec<&Value> = tree
+        .get("nodes")
+        .and_then(Value::as_array)
+        .map(|nodes| nodes.iter().collect())
+        .unwrap_or_default();
+    let by_id: HashMap<&str, &Value> = nodes
+        .iter()
+        .filter_map(|node| Some((node.get("nodeId")?.as_str()?, *node)))
+        .collect();
+
+    let mut out = String::new();
+    for root in nodes.iter().filter(|node| {
+        node.get("parentId")
+            .and_then(Value::as_str)
+            .is_none_or(|parent| !by_id.contains_key(parent))
+    }) {
+        write_ax_node(root, &by_id, 0, None, &mut out);
+    }
+    out.trim_end().to_string()
+}
+
+fn ax_value<'a>(node: &'a Value, key: &str) -> Option<&'a Value> {
+    node.get(key).and_then(|value| value.get("value"))
+}
+
+fn ax_string(node: &Value, key: &str) -> Option<String> {
+    match ax_value(node, key)? {
+        Value::String(text) => Some(text.trim().to_string()).filter(|text| !text.is_empty()),
+        Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
+
+fn write_ax_node(
+    node: &Value,
+    by_id: &HashMap<&str, &Value>,
+    depth: usize,
+    parent_name: Option<&str>,
+    out: &mut String,
+) {
+    let ignored = node.get("ignored").and_then(Value::as_bool).unwrap_or(false);
+    let role = ax_string(node, "role").unwrap_or_default();
+    // Inline text boxes split a text node into rendered lines.
+    if role == "InlineTextBox" {
+        return;
+    }
+    let name = ax_string(node, "name");
+    // Text nodes often just repeat the name of the link or heading around
+    // them; keep them only where they add text of their own.
+    let flatten = ignored
+        || role.is_empty()
+        || (STRUCTURAL_ROLES.contains(&role.as_str()) && name.is_none())
+        || (role == "StaticText" && (name.is_none() || name.as_deref() == parent_name));
+
+    let child_depth = if flatten {
+        depth
+    } else {
+        out.push_str(&"  ".repeat(depth));
+        out.push_str("- ");
+        out.push_str(&role);
+        if let Some(name) = &name {
+            out.push_str(&format!(" {name:?}"));
+        }
+        if let Some(value) = ax_string(node, "value") {
+            out.push_str(&format!(" = {value:?}"));
+        }
+        for property in node
+            .get("properties")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let Some(key) = property.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            if !SHOWN_PROPERTIES.contains(&key) {
+                continue;
+            }
+            match ax_value(property, "value") {
+                Some(Value::Bool(true)) => out.push_str(&format!(" [{key}]")),
+                Some(Value::Bool(false)) | None | Some(Value::Null) => {}
+                Some(Value::String(text)) => out.push_str(&format!(" [{key}={text}]")),
+                Some(other) => out.push_str(&format!(" [{key}={other}]")),
+            }
+        }
+        out.push('\n');
+        depth + 1
+    };
+
+    let child_parent_name = if flatten { parent_name } else { name.as_deref() };
+    for child_id in node
+        .get("childIds")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+    {
+        if let Some(child) = by_id.get(child_id) {
+            write_ax_node(child, by_id, child_depth, child_parent_name, out);
+        }
+    }
+}
+
+/// Cuts `text` to at most `max_chars` characters, reporting whether it did.
+pub fn truncate_extract(text: &str, max_chars: usize) -> (String, bool) {
+    match text.char_indices().nth(max_chars) {
+        Some((cut, _)) => (text[..cut].to_string(), true),
+        None => (text.to_string(), false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn accessibility_outline_flattens_structure_and_shows_states() {
+        let tree = json!({
+            "nodes": [
+                { "nodeId": "1", "role": { "value": "RootWebArea" }, "name": { "value": "Docs" }, "childIds": ["2"] },
+                { "nodeId": "2", "parentId": "1", "role": { "value": "generic" }, "childIds": ["3", "4", "6"] },
+                { "nodeId": "3", "parentId": "2", "role": { "value": "heading" }, "name": { "value": "Install" },
+                  "properties": [{ "name": "level", "value": { "type": "integer", "value": 2 } }], "childIds": ["7"] },
+                { "nodeId": "7", "parentId": "3", "role": { "value": "StaticText" }, "name": { "value": "Install" }, "childIds": ["8"] },
+                { "nodeId": "8", "parentId": "7", "role": { "value": "InlineTextBox" }, "name": { "value": "Install" } },
+                { "nodeId": "4", "parentId": "2", "role": { "value": "checkbox" }, "name": { "value": "Agree" },
+                  "properties": [
+                      { "name": "checked", "value": { "type": "tristate", "value": "true" } },
+                      { "name": "focusable", "value": { "type": "boolean", "value": true } }
+                  ] },
+                { "nodeId": "6", "parentId": "2", "ignored": true, "role": { "value": "none" }, "childIds": ["5"] },
+                { "nodeId": "5", "parentId": "6", "role": { "value": "textbox" }, "name": { "value": "Email" },
+                  "value": { "type": "string", "value": "a@b.c" },
+                  "properties": [{ "name": "focused", "value": { "type": "booleanOrUndefined", "value": true } }] }
+            ]
+        });
+        assert_eq!(
+            format_accessibility_tree(&tree),
+            "- RootWebArea \"Docs\"\n  - heading \"Install\" [level=2]\n  - checkbox \"Agree\" [checked=true]\n  - textbox \"Email\" = \"a@b.c\" [focused]"
+        );
+    }
+
+    #[test]
+    fn extract_format_defaults_to_text() {
+        assert_eq!(ExtractFormat::default(), ExtractFormat::Text);
+        assert_eq!(
+            serde_json::from_value::<ExtractFormat>(json!("accessibility")).ok(),
+            Some(ExtractFormat::Accessibility)
+        );
+        assert_eq!(truncate_extract("héllo", 2), ("hé".to_string(), true));
+    }
+}
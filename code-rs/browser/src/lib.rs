@@ -1,5 +1,6 @@
 pub mod assets;
 pub mod config;
+pub mod extract;
 pub mod global;
 pub mod hooks;
 pub mod manager;
@@ -10,6 +11,7 @@ pub use config::BrowserConfig;
 pub use config::ViewportConfig;
 pub use config::WaitStrategy;
 pub use manager::BrowserManager;
+pub use manager::BrowserTab;
 pub use page::Page;
 pub use page::ScreenshotMode;
 pub use page::ScreenshotRegion;
@@ -12,7 +12,10 @@ use fs2::FileExt;
 use futures::StreamExt;
 use once_cell::sync::Lazy;
 use reqwest::Client;
+use chromiumoxide::cdp::browser_protocol::target::TargetId;
+use chromiumoxide::page::Page as CdpPage;
 use serde::Deserialize;
+use serde::Serialize;
 use serde_json::Value;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -327,6 +330,39 @@ async fn scan_for_chrome_debug_port() -> Option<u16> {
     None
 }
 
+/// Whether a tab's URL is one we can inject into and evaluate on: web and
+/// file pages or `about:blank`, but not browser-internal pages.
+fn is_controllable_tab_url(url: &str) -> bool {
+    let lu = url.to_lowercase();
+    if lu.starts_with("chrome://")
+        || lu.starts_with("devtools://")
+        || lu.starts_with("edge://")
+        || lu.starts_with("chrome-extension://")
+        || lu.starts_with("brave://")
+        || lu.starts_with("vivaldi://")
+        || lu.starts_with("opera://")
+    {
+        return false;
+    }
+    lu.starts_with("http://")
+        || lu.starts_with("https://")
+        || lu.starts_with("file://")
+        || lu == "about:blank"
+}
+
+/// A controllable tab of the connected browser.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BrowserTab {
+    /// 1-based position in the tab list, as taken by `switch_tab`,
+    /// `close_tab` and `capture_tab_screenshot`.
+    pub index: usize,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Whether this is the tab browser actions operate on.
+    pub active: bool,
+}
+
 pub struct BrowserManager {
     pub config: Arc<RwLock<BrowserConfig>>,
     browser: Arc<Mutex<Option<Browser>>>,
@@ -346,6 +382,9 @@ pub struct BrowserManager {
     auto_viewport_correction_enabled: Arc<tokio::sync::RwLock<bool>>,
     /// Track last applied device metrics to avoid redundant overrides
     last_metrics_applied: LastAppliedMetricsSlot,
+    /// Tabs in the order they were first seen, so tab indexes stay stable
+    /// while other tabs open and close.
+    tab_order: Arc<Mutex<Vec<TargetId>>>,
 }
 
 #[derive(Debug)]
@@ -384,6 +423,7 @@ impl BrowserManager {
             viewport_monitor_handle: Arc::new(Mutex::new(None)),
             auto_viewport_correction_enabled: Arc::new(tokio::sync::RwLock::new(true)),
             last_metrics_applied: Arc::new(Mutex::new(None)),
+            tab_order: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -1114,33 +1154,13 @@ impl BrowserManager {
                 let mut first_visible: Option<chromiumoxide::page::Page> = None; // visible
                 let mut last_allowed: Option<chromiumoxide::page::Page> = None;  // allowed regardless of visibility
 
-                // Helper: determine if a URL is controllable (we can inject/evaluate)
-                let is_allowed = |u: &str| {
-                    let lu = u.to_lowercase();
-                    if lu.starts_with("chrome://")
-                        || lu.starts_with("devtools://")
-                        || lu.starts_with("edge://")
-                        || lu.starts_with("chrome-extension://")
-                        || lu.starts_with("brave://")
-                        || lu.starts_with("vivaldi://")
-                        || lu.starts_with("opera://")
-                    {
-                        return false;
-                    }
-                    // Allow http/https/file/about:blank
-                    lu.starts_with("http://")
-                        || lu.starts_with("https://")
-                        || lu.starts_with("file://")
-                        || lu == "about:blank"
-                };
-
                 for page in &pages {
                     // Quick URL check first to skip uninjectable pages
                     let url = match tokio::time::timeout(Duration::from_millis(200), page.url()).await {
                         Ok(Ok(Some(u))) => u,
                         _ => "unknown".to_string(),
                     };
-                    if !is_allowed(&url) {
+                    if !is_controllable_tab_url(&url) {
                         debug!("Skipping uncontrollable tab: {}", url);
                         continue;
                     } else {
@@ -1230,13 +1250,33 @@ impl BrowserManager {
             browser.new_page("about:blank").await?
         };
 
+        let page = self.prepare_page(cdp_page, &config).await?;
+        *page_guard = Some(Arc::clone(&page));
+
+        // Start navigation monitoring for this page
+        self.start_navigation_monitor(Arc::clone(&page)).await;
+        // Start viewport monitor (low-frequency, non-invasive)
+        self.start_viewport_monitor(Arc::clone(&page)).await;
+        // TEMP: disable auto-corrections post-initial set to validate no unintended resizes
+        // This affects both external and internal; explicit browser.setViewport still works
+        self.set_auto_viewport_correction(false).await;
+        info!(
+            "[bm] get_or_create_page: complete in {:?}",
+            overall_start.elapsed()
+        );
+
+        Ok(page)
+    }
+
+    /// Applies overrides to a CDP page and installs the virtual cursor and
+    /// console capture, without making it the active page.
+    async fn prepare_page(&self, cdp_page: CdpPage, config: &BrowserConfig) -> Result<Arc<Page>> {
         // Apply page overrides (UA, locale, timezone, viewport, etc.)
         let overrides_start = Instant::now();
         self.apply_page_overrides(&cdp_page).await?;
-        info!("[bm] get_or_create_page: overrides in {:?}", overrides_start.elapsed());
+        info!("[bm] prepare_page: overrides in {:?}", overrides_start.elapsed());
 
         let page = Arc::new(Page::new(cdp_page, config.clone()));
-        *page_guard = Some(Arc::clone(&page));
 
         // Inject the virtual cursor when page is created
         debug!("Injecting virtual cursor for new page");
@@ -1301,18 +1341,6 @@ impl BrowserManager {
             warn!("Failed to install console capture on page creation: {}", e);
         }
 
-        // Start navigation monitoring for this page
-        self.start_navigation_monitor(Arc::clone(&page)).await;
-        // Start viewport monitor (low-frequency, non-invasive)
-        self.start_viewport_monitor(Arc::clone(&page)).await;
-        // TEMP: disable auto-corrections post-initial set to validate no unintended resizes
-        // This affects both external and internal; explicit browser.setViewport still works
-        self.set_auto_viewport_correction(false).await;
-        info!(
-            "[bm] get_or_create_page: complete in {:?}",
-            overall_start.elapsed()
-        );
-
         Ok(page)
     }
 
@@ -1324,6 +1352,159 @@ impl BrowserManager {
         Ok(())
     }
 
+    /// Controllable tabs, in stable order (see `tab_order`).
+    async fn tab_pages(&self) -> Result<Vec<CdpPage>> {
+        self.ensure_browser().await?;
+        let pages = {
+            let browser_guard = self.browser.lock().await;
+            let browser = browser_guard.as_ref().ok_or(BrowserError::NotInitialized)?;
+            browser.pages().await?
+        };
+
+        let mut controllable = Vec::with_capacity(pages.len());
+        for page in pages {
+            // New tabs can report no URL yet; they are about:blank until they navigate.
+            let url = match tokio::time::timeout(Duration::from_millis(200), page.url()).await {
+                Ok(Ok(url)) => url.unwrap_or_else(|| "about:blank".to_string()),
+                _ => continue,
+            };
+            if is_controllable_tab_url(&url) {
+                controllable.push(page);
+            }
+        }
+
+        let mut order = self.tab_order.lock().await;
+        order.retain(|id| controllable.iter().any(|page| page.target_id() == id));
+        for page in &controllable {
+            if !order.contains(page.target_id()) {
+                order.push(page.target_id().clone());
+            }
+        }
+        controllable.sort_by_key(|page| order.iter().position(|id| id == page.target_id()));
+        Ok(controllable)
+    }
+
+    async fn tab_page(&self, index: usize) -> Result<CdpPage> {
+        let mut pages = self.tab_pages().await?;
+        if index == 0 || index > pages.len() {
+            return Err(BrowserError::ConfigError(format!(
+                "no tab {index}; {} tab(s) open",
+                pages.len()
+            )));
+        }
+        Ok(pages.swap_remove(index - 1))
+    }
+
+    async fn active_target_id(&self) -> Option<TargetId> {
+        self.page
+            .lock()
+            .await
+            .as_ref()
+            .map(|page| page.target_id().clone())
+    }
+
+    /// Lists the browser's controllable tabs, marking the active one.
+    pub async fn list_tabs(&self) -> Result<Vec<BrowserTab>> {
+        let pages = self.tab_pages().await?;
+        let active = self.active_target_id().await;
+        let mut tabs = Vec::with_capacity(pages.len());
+        for (idx, page) in pages.iter().enumerate() {
+            let url = match tokio::time::timeout(Duration::from_millis(200), page.url()).await {
+                Ok(Ok(Some(url))) => url,
+                _ => "about:blank".to_string(),
+            };
+            let title = match tokio::time::timeout(Duration::from_millis(200), page.get_title()).await {
+                Ok(Ok(title)) => title.filter(|title| !title.is_empty()),
+                _ => None,
+            };
+            tabs.push(BrowserTab {
+                index: idx + 1,
+                url,
+                title,
+                active: active.as_ref() == Some(page.target_id()),
+            });
+        }
+        Ok(tabs)
+    }
+
+    /// Makes `cdp_page` the page every browser action operates on.
+    async fn activate_cdp_page(&self, cdp_page: CdpPage) -> Result<Arc<Page>> {
+        if let Err(e) = cdp_page.bring_to_front().await {
+            debug!("Failed to bring tab to front: {}", e);
+        }
+        let config = self.config.read().await.clone();
+        let page = self.prepare_page(cdp_page, &config).await?;
+        *self.page.lock().await = Some(Arc::clone(&page));
+        self.start_navigation_monitor(Arc::clone(&page)).await;
+        self.start_viewport_monitor(Arc::clone(&page)).await;
+        self.update_activity().await;
+        Ok(page)
+    }
+
+    async fn tab_info(&self, page: &Page) -> Result<BrowserTab> {
+        let tabs = self.list_tabs().await?;
+        tabs.into_iter()
+            .find(|tab| tab.active)
+            .ok_or_else(|| BrowserError::CdpError(format!("tab {} is gone", page.target_id_debug())))
+    }
+
+    /// Opens `url` in a new tab and makes it the active tab.
+    pub async fn open_tab(&self, url: &str) -> Result<BrowserTab> {
+        self.ensure_browser().await?;
+        let cdp_page = {
+            let browser_guard = self.browser.lock().await;
+            let browser = browser_guard.as_ref().ok_or(BrowserError::NotInitialized)?;
+            browser.new_page(url).await?
+        };
+        let page = self.activate_cdp_page(cdp_page).await?;
+        self.tab_info(&page).await
+    }
+
+    /// Makes tab `index` (1-based) the active tab.
+    pub async fn switch_tab(&self, index: usize) -> Result<BrowserTab> {
+        let cdp_page = self.tab_page(index).await?;
+        if self.active_target_id().await.as_ref() == Some(cdp_page.target_id())
+            && let Some(page) = self.page.lock().await.clone()
+        {
+            return self.tab_info(&page).await;
+        }
+        let page = self.activate_cdp_page(cdp_page).await?;
+        self.tab_info(&page).await
+    }
+
+    /// Closes tab `index` (1-based). Closing the active tab activates the
+    /// last remaining one.
+    pub async fn close_tab(&self, index: usize) -> Result<()> {
+        let cdp_page = self.tab_page(index).await?;
+        let was_active = self.active_target_id().await.as_ref() == Some(cdp_page.target_id());
+        if was_active {
+            self.stop_navigation_monitor().await;
+            self.stop_viewport_monitor().await;
+            *self.page.lock().await = None;
+        }
+        cdp_page.close().await?;
+        if was_active
+            && let Some(next) = self.tab_pages().await?.pop()
+        {
+            self.activate_cdp_page(next).await?;
+        }
+        Ok(())
+    }
+
+    /// Screenshots tab `index` (1-based) without changing the active tab.
+    pub async fn capture_tab_screenshot(
+        &self,
+        index: usize,
+    ) -> Result<(Vec<std::path::PathBuf>, String)> {
+        let cdp_page = self.tab_page(index).await?;
+        if self.active_target_id().await.as_ref() == Some(cdp_page.target_id()) {
+            return self.capture_screenshot_regular().await;
+        }
+        let config = self.config.read().await.clone();
+        let page = Page::unattached(cdp_page, config);
+        self.capture_page_screenshot(&page).await
+    }
+
     pub async fn is_enabled(&self) -> bool {
         self.config.read().await.enabled
     }
@@ -1934,7 +2115,13 @@ impl BrowserManager {
     async fn capture_screenshot_regular(&self) -> Result<(Vec<std::path::PathBuf>, String)> {
         // For launched Chrome, use the regular approach since it's already isolated
         let page = self.get_or_create_page().await?;
+        self.capture_page_screenshot(&page).await
+    }
 
+    async fn capture_page_screenshot(
+        &self,
+        page: &Page,
+    ) -> Result<(Vec<std::path::PathBuf>, String)> {
         // Viewport correction is handled inside Page::screenshot for all connections
 
         // Initialize assets manager if needed
@@ -2155,6 +2342,50 @@ impl BrowserManager {
         page.inject_js(&script).await
     }
 
+    /// Text view of the active page: its rendered text or an outline of its
+    /// accessibility tree.
+    pub async fn extract_page(
+        &self,
+        format: crate::extract::ExtractFormat,
+    ) -> Result<crate::extract::PageExtract> {
+        use crate::extract::ExtractFormat;
+
+        let page = self.get_or_create_page().await?;
+        let (url, title, content) = match format {
+            ExtractFormat::Text => {
+                let result = page.inject_js(crate::extract::VISIBLE_TEXT_JS).await?;
+                let field = |key: &str| {
+                    result
+                        .get(key)
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                };
+                (
+                    field("url").unwrap_or_default(),
+                    field("title").filter(|title| !title.is_empty()),
+                    field("text").unwrap_or_default(),
+                )
+            }
+            ExtractFormat::Accessibility => {
+                let tree = page
+                    .execute_cdp_raw("Accessibility.getFullAXTree", serde_json::json!({}))
+                    .await?;
+                (
+                    page.get_current_url().await.unwrap_or_default(),
+                    page.get_title().await.filter(|title| !title.is_empty()),
+                    crate::extract::format_accessibility_tree(&tree),
+                )
+            }
+        };
+        self.update_activity().await;
+        Ok(crate::extract::PageExtract {
+            url,
+            title,
+            format,
+            content,
+        })
+    }
+
     /// Execute an arbitrary CDP command against the active page session
     pub async fn execute_cdp(
         &self,
@@ -2540,6 +2771,7 @@ pub struct BrowserStatus {
 #[cfg(test)]
 mod tests {
     use super::discover_ws_via_host_port;
+    use super::is_controllable_tab_url;
     use super::should_restart_handler;
     use super::should_stop_handler;
     use std::io::Read;
@@ -2562,6 +2794,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn only_web_and_blank_tabs_are_controllable() {
+        assert!(is_controllable_tab_url("https://example.com/"));
+        assert!(is_controllable_tab_url("file:///tmp/index.html"));
+        assert!(is_controllable_tab_url("about:blank"));
+        assert!(!is_controllable_tab_url("chrome://settings"));
+        assert!(!is_controllable_tab_url("DevTools://devtools/bundled/inspector.html"));
+        assert!(!is_controllable_tab_url("about:newtab"));
+    }
+
     #[test]
     fn handler_restarts_after_repeated_errors() {
         assert!(!should_restart_handler(0));
@@ -16,6 +16,7 @@ use chromiumoxide::cdp::browser_protocol::emulation::SetDeviceMetricsOverridePar
 use chromiumoxide::cdp::browser_protocol::page::AddScriptToEvaluateOnNewDocumentParams;
 use chromiumoxide::cdp::browser_protocol::page::CaptureScreenshotFormat;
 use chromiumoxide::cdp::browser_protocol::page::CaptureScreenshotParams;
+use chromiumoxide::cdp::browser_protocol::target::TargetId;
 use chromiumoxide::page::Page as CdpPage;
 use chromiumoxide::cdp::js_protocol::runtime as cdp_runtime;
 use chromiumoxide::cdp::browser_protocol::log as cdp_log;
@@ -111,22 +112,7 @@ async fn url_looks_loaded(cdp_page: &CdpPage, timeout: Duration) -> bool {
 
 impl Page {
     pub fn new(cdp_page: CdpPage, config: BrowserConfig) -> Self {
-        // Initialize cursor position (Updated)
-        let initial_cursor = CursorState {
-            x: (config.viewport.width as f64 / 2.0).floor(),
-            y: (config.viewport.height as f64 / 4.0).floor(),
-            button: MouseButton::None,
-            is_mouse_down: false,
-        };
-
-        let page = Self {
-            cdp_page: Arc::new(cdp_page),
-            config,
-            current_url: Arc::new(RwLock::new(None)),
-            cursor_state: Arc::new(Mutex::new(initial_cursor)),
-            preflight_cache: Arc::new(Mutex::new(None)),
-            console_logs: Arc::new(Mutex::new(Vec::new())),
-        };
+        let page = Self::unattached(cdp_page, config);
 
         // Register a unified bootstrap (runs on every new document):
         //  - Blocks _blank/tab opens
@@ -1554,6 +1540,31 @@ impl Page {
         }
     }
 
+    /// Wraps a CDP page without registering the bootstrap script or console
+    /// capture, for one-off use such as screenshotting a background tab.
+    pub(crate) fn unattached(cdp_page: CdpPage, config: BrowserConfig) -> Self {
+        // Initialize cursor position (Updated)
+        let initial_cursor = CursorState {
+            x: (config.viewport.width as f64 / 2.0).floor(),
+            y: (config.viewport.height as f64 / 4.0).floor(),
+            button: MouseButton::None,
+            is_mouse_down: false,
+        };
+
+        Self {
+            cdp_page: Arc::new(cdp_page),
+            config,
+            current_url: Arc::new(RwLock::new(None)),
+            cursor_state: Arc::new(Mutex::new(initial_cursor)),
+            preflight_cache: Arc::new(Mutex::new(None)),
+            console_logs: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn target_id(&self) -> &TargetId {
+        self.cdp_page.target_id()
+    }
+
     pub fn target_id_debug(&self) -> String {
         let target_id = self.cdp_page.target_id();
         format!("{target_id:?}")
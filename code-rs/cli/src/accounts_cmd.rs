@@ -0,0 +1,94 @@
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use clap::Subcommand;
+use clap_complete::engine::ArgValueCompleter;
+use code_core::auth;
+use code_core::auth_accounts;
+use code_core::auth_accounts::StoredAccount;
+use code_core::config::Config;
+use code_core::config::ConfigOverrides;
+use code_core::config::find_code_home;
+
+use crate::completion::account_candidates;
+
+#[derive(Debug, Parser)]
+pub struct AccountsCli {
+    #[command(subcommand)]
+    subcommand: AccountsSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum AccountsSubcommand {
+    /// List stored accounts; the active one is marked with `*`.
+    List,
+
+    /// Make a stored account the active one for new sessions.
+    Use(UseArgs),
+}
+
+#[derive(Debug, Parser)]
+struct UseArgs {
+    /// Account label (case-insensitive) or id.
+    #[arg(value_name = "ACCOUNT", add = ArgValueCompleter::new(account_candidates))]
+    account: String,
+}
+
+impl AccountsCli {
+    pub fn run(self) -> Result<()> {
+        let code_home = find_code_home().context("failed to resolve CODE_HOME")?;
+        let accounts = auth_accounts::list_accounts(&code_home)
+            .context("failed to read the account store")?;
+        match self.subcommand {
+            AccountsSubcommand::List => {
+                if accounts.is_empty() {
+                    println!("No accounts stored. Run `code login` to add one.");
+                    return Ok(());
+                }
+                let active = auth_accounts::get_active_account_id(&code_home)?;
+                for account in &accounts {
+                    let marker = if active.as_deref() == Some(account.id.as_str()) { "*" } else { " " };
+                    println!("{marker} {} ({:?}, id {})", account_name(account), account.mode, account.id);
+                }
+                Ok(())
+            }
+            AccountsSubcommand::Use(args) => {
+                let account = find_account(&accounts, &args.account)?;
+                let config = Config::load_with_cli_overrides(Vec::new(), ConfigOverrides::default())
+                    .context("failed to load configuration")?;
+                auth::activate_account_with_store_mode(
+                    &code_home,
+                    &account.id,
+                    config.cli_auth_credentials_store_mode,
+                )
+                .context("failed to activate the account")?;
+                println!("Active account: {}", account_name(account));
+                Ok(())
+            }
+        }
+    }
+}
+
+fn account_name(account: &StoredAccount) -> &str {
+    account
+        .label
+        .as_deref()
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+        .unwrap_or(account.id.as_str())
+}
+
+fn find_account<'a>(accounts: &'a [StoredAccount], needle: &str) -> Result<&'a StoredAccount> {
+    if let Some(account) = accounts.iter().find(|account| account.id == needle) {
+        return Ok(account);
+    }
+    let matches: Vec<&StoredAccount> = accounts
+        .iter()
+        .filter(|account| account_name(account).eq_ignore_ascii_case(needle))
+        .collect();
+    match matches.as_slice() {
+        [account] => Ok(account),
+        [] => anyhow::bail!("no account matches `{needle}`; see `code accounts list`"),
+        _ => anyhow::bail!("several accounts are labeled `{needle}`; pass the account id instead"),
+    }
+}
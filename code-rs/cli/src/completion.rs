@@ -0,0 +1,145 @@
+//! Shell completion for `code`.
+//!
+//! `code completion <shell>` prints a registration script that calls back
+//! into `COMPLETE=<shell> code ...` on every Tab press, so besides
+//! subcommands and flags the shell can offer values read from the local
+//! stores: session ids, skill names, account labels and MCP server names.
+//! `--static` prints clap's self-contained script instead.
+
+use std::ffi::OsStr;
+use std::io;
+use std::io::Write;
+
+use clap_complete::Shell;
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::env::EnvCompleter;
+use clap_complete::env::Shells;
+use code_core::SessionCatalog;
+use code_core::SessionQuery;
+use code_core::auth_accounts;
+use code_core::config::Config;
+use code_core::config::ConfigOverrides;
+use code_core::config::find_code_home;
+use code_core::config::load_global_mcp_servers;
+
+/// Environment variable that switches `code` into completion mode.
+pub(crate) const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Sessions offered for completion, newest first.
+const MAX_SESSION_CANDIDATES: usize = 50;
+
+/// Writes the script that registers dynamic completion for `bin` in `shell`.
+pub(crate) fn write_registration(shell: Shell, bin: &str, out: &mut dyn Write) -> io::Result<()> {
+    let name = shell.to_string();
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(&name)
+        .ok_or_else(|| io::Error::other(format!("dynamic completion is not available for {name}")))?;
+    completer.write_registration(COMPLETE_VAR, bin, bin, bin, out)
+}
+
+/// Recent session ids, described by their nickname or last prompt.
+pub(crate) fn session_candidates(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Ok(code_home) = find_code_home() else {
+        return Vec::new();
+    };
+    let Ok(runtime) = tokio::runtime::Builder::new_current_thread().enable_all().build() else {
+        return Vec::new();
+    };
+    let query = SessionQuery {
+        limit: Some(MAX_SESSION_CANDIDATES),
+        ..SessionQuery::default()
+    };
+    let entries = runtime
+        .block_on(SessionCatalog::new(code_home).query(&query))
+        .unwrap_or_default();
+    let prefix = current.to_string_lossy().to_ascii_lowercase();
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            let id = entry.session_id.to_string();
+            if !id.starts_with(&prefix) {
+                return None;
+            }
+            let help = entry
+                .nickname
+                .or(entry.last_user_snippet)
+                .map(|text| text.lines().next().unwrap_or_default().to_string())
+                .filter(|text| !text.trim().is_empty());
+            Some(with_help(id, help))
+        })
+        .collect()
+}
+
+/// Installed skill names with their descriptions.
+pub(crate) fn skill_candidates(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Ok(config) = Config::load_with_cli_overrides(Vec::new(), ConfigOverrides::default()) else {
+        return Vec::new();
+    };
+    let prefix = current.to_string_lossy();
+    code_core::load_skills(&config)
+        .skills
+        .into_iter()
+        .filter(|skill| skill.name.starts_with(prefix.as_ref()))
+        .map(|skill| with_help(skill.name, Some(skill.description)))
+        .collect()
+}
+
+/// Stored accounts by label, falling back to the id for unlabeled ones.
+pub(crate) fn account_candidates(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Ok(code_home) = find_code_home() else {
+        return Vec::new();
+    };
+    let prefix = current.to_string_lossy().to_lowercase();
+    auth_accounts::list_accounts(&code_home)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|account| {
+            let value = account
+                .label
+                .as_deref()
+                .map(str::trim)
+                .filter(|label| !label.is_empty())
+                .unwrap_or(account.id.as_str())
+                .to_string();
+            value
+                .to_lowercase()
+                .starts_with(&prefix)
+                .then(|| with_help(value, Some(format!("{:?} account", account.mode))))
+        })
+        .collect()
+}
+
+/// MCP server names configured in `config.toml`.
+pub(crate) fn mcp_server_candidates(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Ok(code_home) = find_code_home() else {
+        return Vec::new();
+    };
+    let prefix = current.to_string_lossy();
+    load_global_mcp_servers(&code_home)
+        .unwrap_or_default()
+        .into_keys()
+        .filter(|name| name.starts_with(prefix.as_ref()))
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+fn with_help(value: String, help: Option<String>) -> CompletionCandidate {
+    CompletionCandidate::new(value).help(help.map(Into::into))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registration_scripts_call_back_into_code() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let mut buf = Vec::new();
+            write_registration(shell, "code", &mut buf).expect("registration");
+            let script = String::from_utf8(buf).expect("utf-8");
+            assert!(script.contains(COMPLETE_VAR), "{shell} script should set {COMPLETE_VAR}");
+            assert!(script.contains("code"), "{shell} script should invoke code");
+        }
+    }
+}
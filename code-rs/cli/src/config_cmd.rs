@@ -3,7 +3,12 @@ use anyhow::Result;
 use clap::Parser;
 use clap::Subcommand;
 use clap::ValueEnum;
+use code_core::config::CONFIG_SECRETS_KEYRING_SERVICE;
+use code_core::config::CURRENT_CONFIG_VERSION;
 use code_core::config::find_code_home;
+use code_core::config::migrate_config_file;
+use code_keyring_store::DefaultKeyringStore;
+use code_keyring_store::KeyringStore;
 use jsonschema::Draft;
 use jsonschema::JSONSchema;
 use serde_json::Value as JsonValue;
@@ -24,6 +29,12 @@ enum ConfigSubcommand {
 
     /// Validate a config file against one or more schemas.
     Validate(ValidateArgs),
+
+    /// Store or remove a secret referenced as `${keyring:<entry>}` in config.
+    Secret(SecretArgs),
+
+    /// Upgrade `CODE_HOME/config.toml` to the current layout version.
+    Migrate(MigrateArgs),
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -63,11 +74,40 @@ struct ValidateArgs {
     path: Option<PathBuf>,
 }
 
+#[derive(Debug, Parser)]
+struct MigrateArgs {
+    /// Print the migrations that would run without rewriting the file.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Debug, Parser)]
+struct SecretArgs {
+    #[command(subcommand)]
+    action: SecretAction,
+}
+
+#[derive(Debug, Subcommand)]
+enum SecretAction {
+    /// Read the secret from stdin and save it in the OS keyring.
+    Set {
+        #[arg(value_name = "ENTRY")]
+        entry: String,
+    },
+    /// Delete the secret from the OS keyring.
+    Delete {
+        #[arg(value_name = "ENTRY")]
+        entry: String,
+    },
+}
+
 impl ConfigCli {
     pub async fn run(self) -> Result<()> {
         match self.subcommand {
             ConfigSubcommand::Schema(args) => run_schema(args),
             ConfigSubcommand::Validate(args) => run_validate(args),
+            ConfigSubcommand::Secret(args) => run_secret(args),
+            ConfigSubcommand::Migrate(args) => run_migrate(args),
         }
     }
 }
@@ -97,6 +137,55 @@ fn run_schema(args: SchemaArgs) -> Result<()> {
     Ok(())
 }
 
+fn run_migrate(args: MigrateArgs) -> Result<()> {
+    let code_home = find_code_home().context("failed to resolve CODE_HOME")?;
+    let Some(report) = migrate_config_file(&code_home, args.dry_run)? else {
+        println!("config.toml is up to date (version {CURRENT_CONFIG_VERSION}).");
+        return Ok(());
+    };
+    let verb = if args.dry_run { "Would upgrade" } else { "Upgraded" };
+    println!(
+        "{verb} config.toml from version {} to {}.",
+        report.from_version, report.to_version
+    );
+    for summary in &report.applied {
+        println!("  - {summary}");
+    }
+    if let Some(backup) = &report.backup_path {
+        println!("Previous file saved as {}.", backup.display());
+    }
+    Ok(())
+}
+
+fn run_secret(args: SecretArgs) -> Result<()> {
+    let store = DefaultKeyringStore;
+    match args.action {
+        SecretAction::Set { entry } => {
+            let mut value = String::new();
+            std::io::stdin()
+                .read_line(&mut value)
+                .context("failed to read secret from stdin")?;
+            let value = value.trim_end_matches(['\r', '\n']);
+            anyhow::ensure!(!value.is_empty(), "no secret provided on stdin");
+            store
+                .save(CONFIG_SECRETS_KEYRING_SERVICE, &entry, value)
+                .map_err(|err| anyhow::anyhow!("failed to save `{entry}`: {}", err.message()))?;
+            eprintln!("Saved `{entry}`; reference it as ${{keyring:{entry}}} in config.toml.");
+        }
+        SecretAction::Delete { entry } => {
+            let removed = store
+                .delete(CONFIG_SECRETS_KEYRING_SERVICE, &entry)
+                .map_err(|err| anyhow::anyhow!("failed to delete `{entry}`: {}", err.message()))?;
+            if removed {
+                eprintln!("Deleted `{entry}`.");
+            } else {
+                eprintln!("No secret named `{entry}`.");
+            }
+        }
+    }
+    Ok(())
+}
+
 fn run_validate(args: ValidateArgs) -> Result<()> {
     let path = match args.path {
         Some(path) => path,
@@ -146,6 +235,25 @@ fn validate_one(
     instance_json: &JsonValue,
     path: &Path,
 ) -> Result<bool> {
+    let violations = schema_violations(label, schema_json, instance_json)?;
+    if violations.is_empty() {
+        println!("Schema {label}: PASS ({path})", path = path.display());
+        return Ok(true);
+    }
+    println!("Schema {label}: FAIL ({path})", path = path.display());
+    for violation in violations {
+        println!("- {violation}");
+    }
+    Ok(false)
+}
+
+/// Validates `instance_json` against `schema_json`, returning one message per
+/// violation prefixed with the offending instance path.
+pub(crate) fn schema_violations(
+    label: &str,
+    schema_json: &[u8],
+    instance_json: &JsonValue,
+) -> Result<Vec<String>> {
     let schema_value: JsonValue = serde_json::from_slice(schema_json)
         .with_context(|| format!("failed to parse {label} schema JSON"))?;
 
@@ -154,22 +262,18 @@ fn validate_one(
         .compile(&schema_value)
         .map_err(|err| anyhow::anyhow!("failed to compile {label} schema: {err}"))?;
 
-    match compiled.validate(instance_json) {
-        Ok(()) => {
-            println!("Schema {label}: PASS ({path})", path = path.display());
-            Ok(true)
-        }
-        Err(errors) => {
-            println!("Schema {label}: FAIL ({path})", path = path.display());
-            for error in errors {
+    let violations = match compiled.validate(instance_json) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors
+            .map(|error| {
                 let instance_path = error.instance_path.to_string();
                 if instance_path.is_empty() {
-                    println!("- {error}");
+                    error.to_string()
                 } else {
-                    println!("- {instance_path}: {error}");
+                    format!("{instance_path}: {error}")
                 }
-            }
-            Ok(false)
-        }
-    }
+            })
+            .collect(),
+    };
+    Ok(violations)
 }
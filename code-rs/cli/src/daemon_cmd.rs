@@ -0,0 +1,221 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use clap::Subcommand;
+use code_common::CliConfigOverrides;
+use code_core::AuthManager;
+use code_core::ConversationManager;
+use code_core::NewConversation;
+use code_core::config::Config;
+use code_core::config::ConfigOverrides;
+use code_core::daemon;
+use code_core::protocol::Event;
+use code_core::protocol::EventMsg;
+use code_core::protocol::Op;
+use code_protocol::protocol::SessionSource;
+use tracing::info;
+
+/// How long `code daemon stop` waits for the session to shut down.
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Parser)]
+pub struct DaemonCli {
+    /// Socket to listen on (default: `$CODE_HOME/daemon.sock`).
+    #[arg(long, value_name = "PATH", global = true)]
+    socket: Option<PathBuf>,
+
+    /// Start in the background and return immediately. Output goes to
+    /// `daemon.log` in the Code log directory.
+    #[arg(long, default_value_t = false)]
+    detach: bool,
+
+    #[command(subcommand)]
+    action: Option<DaemonAction>,
+
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+}
+
+#[derive(Debug, Subcommand)]
+enum DaemonAction {
+    /// Report whether a daemon is listening on the socket.
+    Status,
+
+    /// Shut down the daemon's session and exit it.
+    Stop,
+}
+
+#[derive(Debug, Parser)]
+pub struct AttachCommand {
+    /// Socket of the daemon to attach to (default: `$CODE_HOME/daemon.sock`).
+    #[arg(long, value_name = "PATH")]
+    pub socket: Option<PathBuf>,
+}
+
+impl DaemonCli {
+    pub async fn run(self, code_linux_sandbox_exe: Option<PathBuf>) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(
+            overrides,
+            ConfigOverrides {
+                code_linux_sandbox_exe,
+                ..Default::default()
+            },
+        )?;
+        let socket = self
+            .socket
+            .unwrap_or_else(|| daemon::default_socket_path(&config.code_home));
+        match self.action {
+            Some(DaemonAction::Status) => run_status(&socket).await,
+            Some(DaemonAction::Stop) => run_stop(&socket).await,
+            None if self.detach => run_detached(&config),
+            None => run_foreground(config, &socket).await,
+        }
+    }
+}
+
+/// Resolves the socket `code attach` should use, failing early when no
+/// daemon is listening there.
+pub async fn attach_socket(command: &AttachCommand) -> Result<PathBuf> {
+    let socket = match &command.socket {
+        Some(socket) => socket.clone(),
+        None => {
+            let code_home =
+                code_core::config::find_code_home().context("failed to resolve CODE_HOME")?;
+            daemon::default_socket_path(&code_home)
+        }
+    };
+    anyhow::ensure!(
+        tokio::net::UnixStream::connect(&socket).await.is_ok(),
+        "no daemon is listening on {}; start one with `code daemon`",
+        socket.display()
+    );
+    Ok(socket)
+}
+
+async fn run_foreground(config: Config, socket: &Path) -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+
+    let auth_manager = AuthManager::shared_with_mode_and_originator(
+        config.code_home.clone(),
+        code_login::AuthMode::ApiKey,
+        config.responses_originator_header.clone(),
+        config.cli_auth_credentials_store_mode,
+    );
+    let conversation_manager = ConversationManager::new(auth_manager, SessionSource::Cli);
+    let NewConversation {
+        conversation_id,
+        conversation,
+        session_configured,
+    } = conversation_manager.new_conversation(config).await?;
+    let session_configured = Event {
+        id: conversation_id.to_string(),
+        event_seq: 0,
+        msg: EventMsg::SessionConfigured(session_configured),
+        order: None,
+    };
+
+    info!("session {conversation_id} listening on {}", socket.display());
+    let serve = daemon::serve(conversation.clone(), session_configured, socket);
+    tokio::pin!(serve);
+    tokio::select! {
+        result = &mut serve => return result.context("daemon socket failed"),
+        _ = termination_signal() => {}
+    }
+    // Shut the session down cleanly so attached clients see it end.
+    info!("shutting down");
+    conversation.submit(Op::Shutdown).await?;
+    serve.await.context("daemon socket failed")
+}
+
+async fn termination_signal() {
+    let mut sigterm =
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(signal) => signal,
+            Err(_) => {
+                let _ = tokio::signal::ctrl_c().await;
+                return;
+            }
+        };
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = sigterm.recv() => {}
+    }
+}
+
+/// Re-runs this command without `--detach` in its own process group, so
+/// closing the terminal (or the Termux session) does not hang it up.
+fn run_detached(config: &Config) -> Result<()> {
+    use std::os::unix::process::CommandExt;
+
+    let log_dir = code_core::config::log_dir(config)?;
+    std::fs::create_dir_all(&log_dir)?;
+    let log_path = log_dir.join("daemon.log");
+    let log = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .with_context(|| format!("failed to open {}", log_path.display()))?;
+    let args: Vec<_> = std::env::args_os()
+        .skip(1)
+        .filter(|arg| arg != "--detach")
+        .collect();
+    let child = std::process::Command::new(std::env::current_exe()?)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log)
+        .process_group(0)
+        .spawn()
+        .context("failed to start daemon")?;
+    println!(
+        "Daemon started (pid {}); logs in {}. Attach with `code attach`.",
+        child.id(),
+        log_path.display()
+    );
+    Ok(())
+}
+
+async fn run_status(socket: &Path) -> Result<()> {
+    anyhow::ensure!(
+        tokio::net::UnixStream::connect(socket).await.is_ok(),
+        "no daemon is listening on {}",
+        socket.display()
+    );
+    println!("Daemon listening on {}", socket.display());
+    Ok(())
+}
+
+async fn run_stop(socket: &Path) -> Result<()> {
+    let (mut events, mut ops) = daemon::connect(socket, None)
+        .await
+        .with_context(|| format!("no daemon is listening on {}", socket.display()))?;
+    ops.submit(Op::Shutdown).await?;
+    let wait_for_shutdown = async {
+        while let Some((_, event)) = events.next_event().await? {
+            if matches!(event.msg, EventMsg::ShutdownComplete) {
+                break;
+            }
+        }
+        anyhow::Ok(())
+    };
+    tokio::time::timeout(STOP_TIMEOUT, wait_for_shutdown)
+        .await
+        .context("timed out waiting for the daemon to shut down")??;
+    println!("Daemon stopped");
+    Ok(())
+}
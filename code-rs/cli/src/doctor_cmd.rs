@@ -0,0 +1,403 @@
+//! Configuration checks for `code doctor`.
+//!
+//! Each check inspects one piece of local setup (config file, `CODE_HOME`,
+//! keyring, MCP servers, accounts, skills) and reports a status plus, for
+//! anything that is not fine, the command or edit that fixes it.
+
+use std::path::Path;
+use std::time::Duration;
+
+use code_app_server_protocol::AuthMode;
+use code_core::auth_accounts;
+use code_core::config::Config;
+use code_core::config::ConfigOverrides;
+use code_core::config::find_code_home;
+use code_core::config_types::AuthCredentialsStoreMode;
+use code_core::config_types::McpServerTransportConfig;
+use code_keyring_store::DefaultKeyringStore;
+use code_keyring_store::KeyringStore;
+use serde_json::Value as JsonValue;
+
+/// Accounts whose tokens have not been refreshed for this long are refreshed
+/// on next use; older refresh tokens are more likely to have been revoked.
+const STALE_REFRESH_DAYS: i64 = 28;
+
+const MCP_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug)]
+struct Check {
+    status: CheckStatus,
+    label: String,
+    detail: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn ok(label: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            status: CheckStatus::Ok,
+            label: label.into(),
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(label: impl Into<String>, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            status: CheckStatus::Warn,
+            label: label.into(),
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(label: impl Into<String>, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            status: CheckStatus::Fail,
+            label: label.into(),
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn print(&self) {
+        let tag = match self.status {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warn => "warn",
+            CheckStatus::Fail => "FAIL",
+        };
+        println!("  [{tag}] {}: {}", self.label, self.detail);
+        if let Some(fix) = &self.fix {
+            println!("         fix: {fix}");
+        }
+    }
+}
+
+/// Runs every configuration check, prints the results, and returns the number
+/// of failed checks.
+pub(crate) async fn run_config_checks() -> usize {
+    let mut checks = Vec::new();
+
+    let code_home = match find_code_home() {
+        Ok(code_home) => code_home,
+        Err(err) => {
+            let check = Check::fail(
+                "code_home",
+                format!("could not be resolved: {err}"),
+                "set CODE_HOME to a writable directory",
+            );
+            check.print();
+            return 1;
+        }
+    };
+
+    checks.push(check_code_home(&code_home));
+    checks.extend(check_config_file(&code_home));
+    if code_core::env::is_termux() {
+        checks.push(check_android_storage());
+    }
+
+    match Config::load_with_cli_overrides(Vec::new(), ConfigOverrides::default()) {
+        Ok(config) => {
+            checks.push(Check::ok("config", "loaded with typed settings"));
+            checks.push(check_keyring(config.cli_auth_credentials_store_mode));
+            checks.extend(check_mcp_servers(&config).await);
+            checks.extend(check_accounts(&code_home));
+            checks.extend(check_skills(&config));
+        }
+        Err(err) => {
+            checks.push(Check::fail(
+                "config",
+                format!("failed to load: {err}"),
+                "fix the reported key in config.toml; keyring, MCP, and skill checks were skipped",
+            ));
+            checks.extend(check_accounts(&code_home));
+        }
+    }
+
+    println!("\nConfiguration ({}):", code_home.display());
+    for check in &checks {
+        check.print();
+    }
+    checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Fail)
+        .count()
+}
+
+fn check_code_home(code_home: &Path) -> Check {
+    if !code_home.exists() {
+        return Check::warn(
+            "code_home",
+            format!("{} does not exist yet", code_home.display()),
+            "run `code` once or create the directory",
+        );
+    }
+    if !code_home.is_dir() {
+        return Check::fail(
+            "code_home",
+            format!("{} is not a directory", code_home.display()),
+            "remove the file or point CODE_HOME elsewhere",
+        );
+    }
+    if let Err(err) = tempfile::NamedTempFile::new_in(code_home) {
+        return Check::fail(
+            "code_home",
+            format!("{} is not writable: {err}", code_home.display()),
+            format!("chmod u+rwx {}", code_home.display()),
+        );
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(metadata) = std::fs::metadata(code_home) {
+            let mode = metadata.permissions().mode() & 0o777;
+            if mode & 0o022 != 0 {
+                return Check::warn(
+                    "code_home",
+                    format!("{} is writable by other users (mode {mode:o})", code_home.display()),
+                    format!("chmod 700 {}", code_home.display()),
+                );
+            }
+        }
+    }
+    Check::ok("code_home", "exists and is writable")
+}
+
+fn check_config_file(code_home: &Path) -> Vec<Check> {
+    let path = code_home.join("config.toml");
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return vec![Check::ok("config.toml", "not present; using defaults")];
+        }
+        Err(err) => {
+            return vec![Check::fail(
+                "config.toml",
+                format!("unreadable: {err}"),
+                format!("check permissions on {}", path.display()),
+            )];
+        }
+    };
+    let toml_value: toml::Value = match toml::from_str(&contents) {
+        Ok(value) => value,
+        Err(err) => {
+            return vec![Check::fail(
+                "config.toml",
+                format!("invalid TOML: {err}"),
+                "fix the syntax error at the reported line",
+            )];
+        }
+    };
+    let instance: JsonValue = match serde_json::to_value(toml_value) {
+        Ok(value) => value,
+        Err(err) => {
+            return vec![Check::fail(
+                "config.toml",
+                format!("could not be converted for validation: {err}"),
+                "run `code config validate` for details",
+            )];
+        }
+    };
+    let violations = code_core::config::schema::config_schema_json()
+        .and_then(|schema| crate::config_cmd::schema_violations("code", &schema, &instance));
+    match violations {
+        Ok(violations) if violations.is_empty() => {
+            vec![Check::ok("config.toml", "matches the config schema")]
+        }
+        Ok(violations) => violations
+            .into_iter()
+            .map(|violation| {
+                Check::fail(
+                    "config.toml",
+                    violation,
+                    "edit the key or run `code config schema` to see allowed values",
+                )
+            })
+            .collect(),
+        Err(err) => vec![Check::warn(
+            "config.toml",
+            format!("schema validation unavailable: {err}"),
+            "run `code config validate` for details",
+        )],
+    }
+}
+
+fn check_android_storage() -> Check {
+    if code_core::android_storage::storage_permission_granted() {
+        Check::ok("android storage", "shared storage (/sdcard) is accessible")
+    } else {
+        Check::warn(
+            "android storage",
+            "no permission for shared storage; edits under /sdcard will be rejected",
+            "run `termux-setup-storage` and allow access",
+        )
+    }
+}
+
+fn check_keyring(mode: AuthCredentialsStoreMode) -> Check {
+    if matches!(
+        mode,
+        AuthCredentialsStoreMode::File | AuthCredentialsStoreMode::Ephemeral
+    ) {
+        return Check::ok("keyring", format!("not used (credentials store: {mode:?})"));
+    }
+    match DefaultKeyringStore.load("Code Doctor", "probe") {
+        Ok(_) => Check::ok("keyring", "available"),
+        Err(err) if mode == AuthCredentialsStoreMode::Auto => Check::warn(
+            "keyring",
+            format!("unavailable, falling back to files: {}", err.message()),
+            "start a secret service (e.g. gnome-keyring) or set cli_auth_credentials_store = \"file\"",
+        ),
+        Err(err) => Check::fail(
+            "keyring",
+            format!("unavailable: {}", err.message()),
+            "start a secret service or set cli_auth_credentials_store = \"auto\"",
+        ),
+    }
+}
+
+async fn check_mcp_servers(config: &Config) -> Vec<Check> {
+    let mut names: Vec<&String> = config.mcp_servers.keys().collect();
+    names.sort();
+    let mut checks = Vec::new();
+    for name in names {
+        let label = format!("mcp `{name}`");
+        let check = match &config.mcp_servers[name].transport {
+            McpServerTransportConfig::Stdio { command, .. } => {
+                let path = Path::new(command);
+                let looks_like_path = path.components().count() > 1 || path.is_absolute();
+                let found = if looks_like_path {
+                    path.is_file()
+                } else {
+                    which::which(command).is_ok()
+                };
+                if found {
+                    Check::ok(label, format!("command `{command}` found"))
+                } else {
+                    Check::fail(
+                        label,
+                        format!("command `{command}` not found"),
+                        "install it, use an absolute path, or set command = \"npx\" for npm packages",
+                    )
+                }
+            }
+            McpServerTransportConfig::StreamableHttp { url, .. } => probe_http(label, url).await,
+        };
+        checks.push(check);
+    }
+    checks
+}
+
+async fn probe_http(label: String, url: &str) -> Check {
+    let client = match reqwest::Client::builder().timeout(MCP_PROBE_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(err) => return Check::warn(label, format!("could not build HTTP client: {err}"), "retry"),
+    };
+    // Any HTTP response means the server is reachable; auth and method errors
+    // are expected for a bare GET.
+    match client.get(url).send().await {
+        Ok(response) => Check::ok(label, format!("{url} responded ({})", response.status())),
+        Err(err) => Check::fail(
+            label,
+            format!("{url} unreachable: {err}"),
+            "start the server or correct `url`",
+        ),
+    }
+}
+
+fn check_accounts(code_home: &Path) -> Vec<Check> {
+    let accounts = match auth_accounts::list_accounts(code_home) {
+        Ok(accounts) => accounts,
+        Err(err) => {
+            return vec![Check::fail(
+                "accounts",
+                format!("could not be read: {err}"),
+                "run `code login` to recreate the account store",
+            )];
+        }
+    };
+    if accounts.is_empty() {
+        return vec![Check::ok("accounts", "none stored")];
+    }
+
+    let now = chrono::Utc::now();
+    accounts
+        .iter()
+        .map(|account| {
+            let label = format!(
+                "account `{}`",
+                account.label.as_deref().unwrap_or(account.id.as_str())
+            );
+            match account.mode {
+                AuthMode::ApiKey => match account.openai_api_key.as_deref() {
+                    Some(key) if !key.trim().is_empty() => Check::ok(label, "API key present"),
+                    _ => Check::fail(label, "API key is empty", "run `code login --with-api-key`"),
+                },
+                AuthMode::Chatgpt | AuthMode::ChatgptAuthTokens => {
+                    let Some(tokens) = &account.tokens else {
+                        return Check::fail(label, "no ChatGPT tokens stored", "run `code login`");
+                    };
+                    if tokens.refresh_token.is_empty() {
+                        return Check::fail(label, "refresh token missing", "run `code login`");
+                    }
+                    match account.last_refresh {
+                        Some(last) if now - last > chrono::Duration::days(STALE_REFRESH_DAYS) => {
+                            Check::warn(
+                                label,
+                                format!("tokens last refreshed {}", last.format("%Y-%m-%d")),
+                                "start a session to refresh, or run `code login` if it fails",
+                            )
+                        }
+                        _ => Check::ok(label, "ChatGPT tokens present"),
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+fn check_skills(config: &Config) -> Vec<Check> {
+    let outcome = code_core::load_skills(config);
+    let mut checks = vec![Check::ok(
+        "skills",
+        format!("{} skill(s) loaded", outcome.skills.len()),
+    )];
+    checks.extend(outcome.errors.into_iter().map(|error| {
+        Check::warn(
+            "skills",
+            format!("{}: {}", error.path.display(), error.message),
+            "fix the SKILL.md frontmatter or remove the skill directory",
+        )
+    }));
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_file_checks_report_syntax_and_schema_errors() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert_eq!(check_config_file(dir.path())[0].status, CheckStatus::Ok);
+
+        std::fs::write(dir.path().join("config.toml"), "model = \"o3").expect("write");
+        let checks = check_config_file(dir.path());
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].status, CheckStatus::Fail);
+        assert!(checks[0].detail.starts_with("invalid TOML"));
+
+        std::fs::write(dir.path().join("config.toml"), "model = 3\n").expect("write");
+        let checks = check_config_file(dir.path());
+        assert!(checks.iter().any(|check| check.status == CheckStatus::Fail
+            && check.detail.starts_with("/model")));
+    }
+}
@@ -0,0 +1,85 @@
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use clap::Subcommand;
+use code_core::HistoryStore;
+use code_core::config::find_code_home;
+use uuid::Uuid;
+
+#[derive(Debug, Parser)]
+pub struct HistoryCli {
+    #[command(subcommand)]
+    subcommand: HistorySubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum HistorySubcommand {
+    /// Import existing JSONL session rollouts into the SQLite history store.
+    Migrate,
+
+    /// Search recorded messages and commands across sessions.
+    Search(SearchArgs),
+
+    /// Print record, command, and token totals.
+    Stats(StatsArgs),
+}
+
+#[derive(Debug, Parser)]
+struct SearchArgs {
+    /// Text to look for (case-insensitive substring match).
+    query: String,
+
+    /// Maximum number of matches to print.
+    #[arg(long, default_value_t = 20)]
+    limit: usize,
+}
+
+#[derive(Debug, Parser)]
+struct StatsArgs {
+    /// Restrict stats to one session id.
+    #[arg(long, value_name = "SESSION_ID")]
+    session: Option<Uuid>,
+}
+
+impl HistoryCli {
+    pub async fn run(self) -> Result<()> {
+        let code_home = find_code_home().context("failed to resolve CODE_HOME")?;
+        let mut store = HistoryStore::open(&code_home).context("failed to open history store")?;
+        match self.subcommand {
+            HistorySubcommand::Migrate => {
+                let report = store.migrate_rollouts()?;
+                println!(
+                    "Imported {} session(s); {} already up to date.",
+                    report.imported, report.unchanged
+                );
+                for (path, err) in &report.failed {
+                    eprintln!("skipped {}: {err}", path.display());
+                }
+                println!("Set `[history] store = \"sqlite\"` to keep the store updated.");
+            }
+            HistorySubcommand::Search(args) => {
+                for hit in store.search(&args.query, args.limit)? {
+                    let text = hit.text.lines().next().unwrap_or_default();
+                    println!("{}  {}  [{}] {text}", hit.timestamp, hit.session_id, hit.kind);
+                }
+            }
+            HistorySubcommand::Stats(args) => {
+                let stats = store.stats(args.session)?;
+                println!("sessions:        {}", stats.sessions);
+                println!("records:         {}", stats.records);
+                println!(
+                    "exec commands:   {} ({} failed)",
+                    stats.exec_commands, stats.failed_exec_commands
+                );
+                println!(
+                    "tokens:          {} total ({} input, {} cached, {} output)",
+                    stats.total_tokens,
+                    stats.input_tokens,
+                    stats.cached_input_tokens,
+                    stats.output_tokens
+                );
+            }
+        }
+        Ok(())
+    }
+}
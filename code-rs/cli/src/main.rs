@@ -2,7 +2,9 @@ use anyhow::anyhow;
 use anyhow::Context;
 use clap::CommandFactory;
 use clap::Parser;
+use clap_complete::CompleteEnv;
 use clap_complete::Shell;
+use clap_complete::engine::ArgValueCompleter;
 use clap_complete::generate;
 use code_arg0::arg0_dispatch_or_else;
 use code_chatgpt::apply_command::ApplyCommand;
@@ -27,6 +29,7 @@ use code_exec::Cli as ExecCli;
 use code_exec::Command as ExecCommand;
 use code_exec::ReviewArgs;
 use code_responses_api_proxy::Args as ResponsesApiProxyArgs;
+use code_serve::ServeCli;
 use code_tui::Cli as TuiCli;
 use code_tui::ExitSummary;
 use code_tui::resume_command_name;
@@ -37,11 +40,33 @@ use std::path::PathBuf;
 use std::process;
 use tokio::runtime::{Builder as TokioRuntimeBuilder, Handle as TokioHandle};
 
+mod accounts_cmd;
+mod completion;
 mod mcp_cmd;
 mod config_cmd;
-
+#[cfg(unix)]
+mod daemon_cmd;
+mod doctor_cmd;
+mod history_cmd;
+mod run_cmd;
+mod schedule_cmd;
+mod session_cmd;
+mod skills_cmd;
+mod watch_cmd;
+
+use crate::accounts_cmd::AccountsCli;
 use crate::mcp_cmd::McpCli;
 use crate::config_cmd::ConfigCli;
+#[cfg(unix)]
+use crate::daemon_cmd::AttachCommand;
+#[cfg(unix)]
+use crate::daemon_cmd::DaemonCli;
+use crate::history_cmd::HistoryCli;
+use crate::run_cmd::RunCommand;
+use crate::schedule_cmd::ScheduleCli;
+use crate::session_cmd::SessionCli;
+use crate::skills_cmd::SkillsCli;
+use crate::watch_cmd::WatchCommand;
 
 const CLI_COMMAND_NAME: &str = "code";
 pub(crate) const CODEX_SECURE_MODE_ENV_VAR: &str = "CODEX_SECURE_MODE";
@@ -112,6 +137,15 @@ enum Subcommand {
     #[clap(name = "auto")]
     Auto(ExecCli),
 
+    /// Run a YAML/TOML file of tasks non-interactively and write a JSON report.
+    Run(RunCommand),
+
+    /// Launch headless Auto Drive runs on a cron schedule.
+    Schedule(ScheduleCli),
+
+    /// Start an agent turn when a check command fails or watched files change.
+    Watch(WatchCommand),
+
     /// Manage login.
     Login(LoginCommand),
 
@@ -119,9 +153,12 @@ enum Subcommand {
     Logout(LogoutCommand),
 
     /// [experimental] Run Codex as an MCP server and manage MCP servers.
-    #[clap(visible_alias = "acp")]
     Mcp(McpCli),
 
+    /// [experimental] Serve the Agent Client Protocol over stdio so an editor
+    /// (Zed, Neovim plugins) can host the conversation.
+    Acp,
+
     /// [experimental] Run the Codex MCP server (stdio transport).
     McpServer,
 
@@ -129,6 +166,7 @@ enum Subcommand {
     AppServer,
 
     /// Generate shell completion scripts.
+    #[clap(visible_alias = "completions")]
     Completion(CompletionCommand),
 
     /// Internal debugging commands.
@@ -145,6 +183,17 @@ enum Subcommand {
     /// Resume a previous interactive session (picker by default; use --last to continue the most recent).
     Resume(ResumeCommand),
 
+    /// Run a session in the background that survives closing the terminal; connect with `code attach`.
+    #[cfg(unix)]
+    Daemon(DaemonCli),
+
+    /// Attach the TUI to the session run by `code daemon`.
+    #[cfg(unix)]
+    Attach(AttachCommand),
+
+    /// Serve the conversation API over HTTP and server-sent events.
+    Serve(ServeCli),
+
     /// Internal: generate TypeScript protocol bindings.
     #[clap(hide = true)]
     GenerateTs(GenerateTsCommand),
@@ -156,12 +205,24 @@ enum Subcommand {
     #[clap(hide = true)]
     ResponsesApiProxy(ResponsesApiProxyArgs),
 
-    /// Diagnose PATH, binary collisions, and versions.
+    /// Diagnose PATH, binary collisions, versions, and configuration problems.
     Doctor,
 
     /// Inspect and validate configuration files.
     Config(ConfigCli),
 
+    /// Migrate, search, and summarize session history in the SQLite store.
+    History(HistoryCli),
+
+    /// Inspect recorded sessions (e.g. dump a golden history transcript).
+    Session(SessionCli),
+
+    /// List installed skills and print their instructions.
+    Skills(SkillsCli),
+
+    /// List stored login accounts and switch the active one.
+    Accounts(AccountsCli),
+
     /// Download and run preview artifact by slug.
     Preview(PreviewArgs),
 
@@ -177,13 +238,19 @@ struct CompletionCommand {
     /// Shell to generate completions for
     #[clap(value_enum, default_value_t = Shell::Bash)]
     shell: Shell,
+
+    /// Print a self-contained script that does not call back into `code`;
+    /// it completes subcommands and flags but not session ids, skill names
+    /// or account labels.
+    #[clap(long = "static", default_value_t = false)]
+    static_script: bool,
 }
 
 #[derive(Debug, Parser)]
 struct ResumeCommand {
     /// Conversation/session id (UUID). When provided, resumes this session.
     /// If omitted, use --last to pick the most recent recorded session.
-    #[arg(value_name = "SESSION_ID")]
+    #[arg(value_name = "SESSION_ID", add = ArgValueCompleter::new(completion::session_candidates))]
     session_id: Option<String>,
 
     /// Continue the most recent session without showing the picker.
@@ -509,6 +576,13 @@ struct PreviewArgs {
 }
 
 fn main() -> anyhow::Result<()> {
+    // Answers `COMPLETE=<shell> code ...` requests from the registration
+    // script printed by `code completion`, then exits; a no-op otherwise.
+    CompleteEnv::with_factory(MultitoolCli::command)
+        .var(completion::COMPLETE_VAR)
+        .bin(CLI_COMMAND_NAME)
+        .complete();
+
     arg0_dispatch_or_else(|code_linux_sandbox_exe| async move {
         cli_main(code_linux_sandbox_exe).await?;
         Ok(())
@@ -602,9 +676,35 @@ async fn cli_main(code_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()>
             );
             code_exec::run_main(exec_cli, code_linux_sandbox_exe).await?;
         }
+        Some(Subcommand::Run(mut run_command)) => {
+            prepend_config_flags(
+                &mut run_command.config_overrides,
+                root_config_overrides.clone(),
+            );
+            run_command.run().await?;
+        }
+        Some(Subcommand::Schedule(mut schedule_cli)) => {
+            prepend_config_flags(
+                &mut schedule_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            schedule_cli.run().await?;
+        }
+        Some(Subcommand::Watch(mut watch_command)) => {
+            prepend_config_flags(
+                &mut watch_command.config_overrides,
+                root_config_overrides.clone(),
+            );
+            watch_command.run().await?;
+        }
         Some(Subcommand::McpServer) => {
             code_mcp_server::run_main(code_linux_sandbox_exe, root_config_overrides).await?;
         }
+        Some(Subcommand::Acp) => {
+            // The stdio server answers ACP requests (`initialize`,
+            // `session/new`, `session/prompt`, ...) alongside MCP ones.
+            code_mcp_server::run_main(code_linux_sandbox_exe, root_config_overrides).await?;
+        }
         Some(Subcommand::Mcp(mut mcp_cli)) => {
             // Propagate any root-level config overrides (e.g. `-c key=value`).
             prepend_config_flags(&mut mcp_cli.config_overrides, root_config_overrides.clone());
@@ -644,6 +744,38 @@ async fn cli_main(code_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()>
                 );
             }
         }
+        #[cfg(unix)]
+        Some(Subcommand::Daemon(mut daemon_cli)) => {
+            prepend_config_flags(
+                &mut daemon_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            daemon_cli.run(code_linux_sandbox_exe).await?;
+        }
+        Some(Subcommand::Serve(mut serve_cli)) => {
+            prepend_config_flags(
+                &mut serve_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            serve_cli.run(code_linux_sandbox_exe).await?;
+        }
+        #[cfg(unix)]
+        Some(Subcommand::Attach(attach_command)) => {
+            prepend_config_flags(
+                &mut interactive.config_overrides,
+                root_config_overrides.clone(),
+            );
+            interactive.attach_socket = Some(daemon_cmd::attach_socket(&attach_command).await?);
+            let ExitSummary { token_usage, .. } =
+                code_tui::run_main(interactive, code_linux_sandbox_exe).await?;
+            if !token_usage.is_zero() {
+                println!(
+                    "{}",
+                    code_core::protocol::FinalOutput::from(token_usage)
+                );
+            }
+            println!("Detached; the session keeps running. Reattach with `code attach`.");
+        }
         Some(Subcommand::Login(mut login_cli)) => {
             prepend_config_flags(
                 &mut login_cli.config_overrides,
@@ -683,7 +815,7 @@ async fn cli_main(code_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()>
             run_logout(logout_cli.config_overrides).await;
         }
         Some(Subcommand::Completion(completion_cli)) => {
-            print_completion(completion_cli);
+            print_completion(completion_cli)?;
         }
         Some(Subcommand::Cloud(mut cloud_cli)) => {
             prepend_config_flags(
@@ -739,6 +871,18 @@ async fn cli_main(code_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()>
         Some(Subcommand::Config(config_cli)) => {
             config_cli.run().await?;
         }
+        Some(Subcommand::History(history_cli)) => {
+            history_cli.run().await?;
+        }
+        Some(Subcommand::Session(session_cli)) => {
+            session_cli.run().await?;
+        }
+        Some(Subcommand::Skills(skills_cli)) => {
+            skills_cli.run()?;
+        }
+        Some(Subcommand::Accounts(accounts_cli)) => {
+            accounts_cli.run()?;
+        }
         Some(Subcommand::Preview(args)) => {
             preview_main(args).await?;
         }
@@ -1201,6 +1345,9 @@ fn merge_resume_cli_flags(interactive: &mut TuiCli, resume_cli: TuiCli) {
     if resume_cli.full_auto {
         interactive.full_auto = true;
     }
+    if resume_cli.plain {
+        interactive.plain = true;
+    }
     if resume_cli.dangerously_bypass_approvals_and_sandbox {
         interactive.dangerously_bypass_approvals_and_sandbox = true;
     }
@@ -1325,8 +1472,13 @@ fn write_completion<W: std::io::Write>(shell: Shell, out: &mut W) {
     generate(shell, &mut app, CLI_COMMAND_NAME, out);
 }
 
-fn print_completion(cmd: CompletionCommand) {
-    write_completion(cmd.shell, &mut std::io::stdout());
+fn print_completion(cmd: CompletionCommand) -> anyhow::Result<()> {
+    if cmd.static_script {
+        write_completion(cmd.shell, &mut std::io::stdout());
+        return Ok(());
+    }
+    completion::write_registration(cmd.shell, CLI_COMMAND_NAME, &mut std::io::stdout())?;
+    Ok(())
 }
 
 fn order_replay_main(args: OrderReplayArgs) -> anyhow::Result<()> {
@@ -1759,6 +1911,10 @@ async fn doctor_main() -> anyhow::Result<()> {
     println!("  - Homebrew: brew uninstall code");
     println!("  - Prefer using 'coder' to avoid conflicts with VS Code's 'code'.");
 
+    let failures = doctor_cmd::run_config_checks().await;
+    if failures > 0 {
+        anyhow::bail!("{failures} configuration check(s) failed");
+    }
     Ok(())
 }
 #[cfg(test)]
@@ -1793,6 +1949,26 @@ mod tests {
         assert!(!script.contains("_codex()"), "bash completion output should not use legacy codex prefix");
     }
 
+    #[test]
+    fn completions_alias_and_store_backed_subcommands_parse() {
+        let cli = MultitoolCli::try_parse_from(["code", "completions", "zsh", "--static"])
+            .expect("parse");
+        let Some(Subcommand::Completion(CompletionCommand { shell, static_script })) = cli.subcommand
+        else {
+            panic!("expected completion subcommand");
+        };
+        assert_eq!(shell, Shell::Zsh);
+        assert!(static_script);
+
+        for args in [
+            &["code", "skills", "show", "release-notes"][..],
+            &["code", "accounts", "use", "work@example.com"][..],
+            &["code", "session", "summarize", "1a2b"][..],
+        ] {
+            MultitoolCli::try_parse_from(args).expect("parse");
+        }
+    }
+
     #[test]
     fn review_subcommand_parses_base_scope() {
         let cli =
@@ -5,6 +5,7 @@ use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
 use anyhow::bail;
+use clap_complete::engine::ArgValueCompleter;
 use code_common::CliConfigOverrides;
 use code_core::config::Config;
 use code_core::config::ConfigOverrides;
@@ -23,6 +24,8 @@ use code_rmcp_client::delete_oauth_tokens;
 use code_rmcp_client::perform_oauth_login;
 use code_rmcp_client::supports_oauth_login;
 
+use crate::completion::mcp_server_candidates;
+
 /// Subcommands:
 /// - `list`   — list configured servers (with `--json`)
 /// - `get`    — show a single server (with `--json`)
@@ -42,18 +45,25 @@ pub struct McpCli {
 
 #[derive(Debug, clap::Subcommand)]
 pub enum McpSubcommand {
+    /// List configured MCP servers.
     List(ListArgs),
 
+    /// Show one configured MCP server.
     Get(GetArgs),
 
+    /// Start the configured servers and report their status, auth and tools.
     Status(StatusArgs),
 
+    /// Add an MCP server launcher entry to `config.toml`.
     Add(AddArgs),
 
+    /// Remove an MCP server entry from `config.toml`.
     Remove(RemoveArgs),
 
+    /// Authenticate with an MCP server using OAuth.
     Login(LoginArgs),
 
+    /// Remove stored OAuth credentials for an MCP server.
     Logout(LogoutArgs),
 }
 
@@ -67,6 +77,7 @@ pub struct ListArgs {
 #[derive(Debug, clap::Parser)]
 pub struct GetArgs {
     /// Name of the MCP server to display.
+    #[arg(value_name = "NAME", add = ArgValueCompleter::new(mcp_server_candidates))]
     pub name: String,
 
     /// Output the server configuration as JSON.
@@ -114,12 +125,14 @@ pub struct AddArgs {
 #[derive(Debug, clap::Parser)]
 pub struct RemoveArgs {
     /// Name of the MCP server configuration to remove.
+    #[arg(value_name = "NAME", add = ArgValueCompleter::new(mcp_server_candidates))]
     pub name: String,
 }
 
 #[derive(Debug, clap::Parser)]
 pub struct LoginArgs {
     /// Name of the MCP server to authenticate with OAuth.
+    #[arg(value_name = "NAME", add = ArgValueCompleter::new(mcp_server_candidates))]
     pub name: String,
 
     /// Comma-separated list of OAuth scopes to request.
@@ -130,6 +143,7 @@ pub struct LoginArgs {
 #[derive(Debug, clap::Parser)]
 pub struct LogoutArgs {
     /// Name of the MCP server to deauthenticate.
+    #[arg(value_name = "NAME", add = ArgValueCompleter::new(mcp_server_candidates))]
     pub name: String,
 }
 
@@ -0,0 +1,569 @@
+//! `code run`: execute a file of agent tasks unattended and write a report.
+//!
+//! Each task runs as its own `code exec` process so tasks cannot share state,
+//! optionally inside a fresh git worktree so parallel tasks never edit the
+//! same checkout. Every task gets a directory under the run's output
+//! directory holding its log, final message and copied artifacts, and the
+//! run ends with a machine-readable `report.json`.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Context;
+use anyhow::Result;
+use chrono::Local;
+use chrono::Utc;
+use clap::Parser;
+use code_common::CliConfigOverrides;
+use code_core::git_worktree;
+use code_core::protocol::AskForApproval;
+use code_protocol::config_types::SandboxMode;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::process::Command;
+
+/// Prompt separator understood by `code exec --prompts-file`.
+const PROMPT_SEPARATOR: &str = "\n---\n";
+
+#[derive(Debug, Parser)]
+pub struct RunCommand {
+    /// Task file (`.yaml`/`.yml`, otherwise TOML).
+    #[arg(value_name = "FILE")]
+    file: PathBuf,
+
+    /// Directory for task logs, artifacts and `report.json`
+    /// (default: `code-run/<file name>-<timestamp>` in the current directory).
+    #[arg(long = "out", value_name = "DIR")]
+    out_dir: Option<PathBuf>,
+
+    /// Run up to N tasks at once (overrides the file's `parallel`).
+    #[arg(long, value_name = "N")]
+    parallel: Option<usize>,
+
+    /// Only run the named task; repeat to select several.
+    #[arg(long = "only", value_name = "NAME")]
+    only: Vec<String>,
+
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+}
+
+#[derive(Debug, Deserialize)]
+struct TaskFile {
+    /// Tasks run at once (default 1).
+    #[serde(default)]
+    parallel: Option<usize>,
+    /// Settings every task inherits unless it sets its own.
+    #[serde(default)]
+    defaults: TaskSettings,
+    tasks: Vec<TaskSpec>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+struct TaskSettings {
+    /// Working directory, relative to the task file's directory.
+    #[serde(default)]
+    cwd: Option<PathBuf>,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    sandbox: Option<SandboxMode>,
+    #[serde(default)]
+    approval_policy: Option<AskForApproval>,
+    /// Run in a new git worktree (on its own branch) instead of `cwd` itself.
+    #[serde(default)]
+    worktree: Option<bool>,
+    #[serde(default)]
+    timeout_seconds: Option<u64>,
+}
+
+impl TaskSettings {
+    fn or(self, defaults: &TaskSettings) -> TaskSettings {
+        TaskSettings {
+            cwd: self.cwd.or_else(|| defaults.cwd.clone()),
+            model: self.model.or_else(|| defaults.model.clone()),
+            sandbox: self.sandbox.or(defaults.sandbox),
+            approval_policy: self.approval_policy.or(defaults.approval_policy),
+            worktree: self.worktree.or(defaults.worktree),
+            timeout_seconds: self.timeout_seconds.or(defaults.timeout_seconds),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TaskSpec {
+    name: String,
+    /// A single prompt; use `prompts` for a multi-turn script.
+    #[serde(default)]
+    prompt: Option<String>,
+    #[serde(default)]
+    prompts: Vec<String>,
+    /// Files or directories, relative to the task's working directory, to
+    /// copy into the task's output directory once it finishes.
+    #[serde(default)]
+    artifacts: Vec<PathBuf>,
+    #[serde(flatten)]
+    settings: TaskSettings,
+}
+
+/// A task ready to run: prompts collected, settings merged, paths resolved.
+#[derive(Debug)]
+struct PlannedTask {
+    name: String,
+    prompts: Vec<String>,
+    artifacts: Vec<PathBuf>,
+    cwd: PathBuf,
+    settings: TaskSettings,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum TaskStatus {
+    Succeeded,
+    Failed,
+    TimedOut,
+}
+
+#[derive(Debug, Serialize)]
+struct ArtifactReport {
+    path: PathBuf,
+    /// Where the artifact was copied; `None` when it did not exist.
+    saved_as: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+struct TaskReport {
+    name: String,
+    status: TaskStatus,
+    exit_code: Option<i32>,
+    duration_ms: u128,
+    cwd: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
+    log: PathBuf,
+    last_message: Option<String>,
+    artifacts: Vec<ArtifactReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct RunReport {
+    file: PathBuf,
+    started_at: String,
+    finished_at: String,
+    succeeded: usize,
+    failed: usize,
+    tasks: Vec<TaskReport>,
+}
+
+impl RunCommand {
+    pub async fn run(self) -> Result<()> {
+        let file = std::fs::canonicalize(&self.file)
+            .with_context(|| format!("cannot read task file {}", self.file.display()))?;
+        let text = std::fs::read_to_string(&file)
+            .with_context(|| format!("cannot read task file {}", file.display()))?;
+        let task_file = parse_task_file(&file, &text)?;
+        let base_dir = file.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let parallel = self.parallel.or(task_file.parallel).unwrap_or(1).max(1);
+        let tasks = plan_tasks(task_file, &base_dir, &self.only)?;
+        warn_about_shared_checkouts(&tasks, parallel);
+
+        let stem = file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "tasks".to_string());
+        let out_dir = match self.out_dir {
+            Some(dir) => dir,
+            None => PathBuf::from("code-run")
+                .join(format!("{stem}-{}", Local::now().format("%Y%m%d-%H%M%S"))),
+        };
+        std::fs::create_dir_all(&out_dir)
+            .with_context(|| format!("cannot create {}", out_dir.display()))?;
+        let out_dir = std::fs::canonicalize(&out_dir)?;
+
+        let started_at = Utc::now().to_rfc3339();
+        let total = tasks.len();
+        eprintln!(
+            "Running {total} task(s) from {} ({parallel} at a time); output in {}",
+            file.display(),
+            out_dir.display()
+        );
+        let overrides = &self.config_overrides.raw_overrides;
+        let mut reports: Vec<(usize, TaskReport)> = futures::stream::iter(tasks.into_iter().enumerate())
+            .map(|(index, task)| {
+                let out_dir = out_dir.clone();
+                async move { (index, run_task(task, &out_dir, overrides).await) }
+            })
+            .buffer_unordered(parallel)
+            .collect()
+            .await;
+        reports.sort_by_key(|(index, _)| *index);
+        let tasks: Vec<TaskReport> = reports.into_iter().map(|(_, report)| report).collect();
+
+        let succeeded = tasks
+            .iter()
+            .filter(|task| task.status == TaskStatus::Succeeded)
+            .count();
+        let report = RunReport {
+            file,
+            started_at,
+            finished_at: Utc::now().to_rfc3339(),
+            succeeded,
+            failed: total - succeeded,
+            tasks,
+        };
+        let report_path = out_dir.join("report.json");
+        std::fs::write(&report_path, serde_json::to_vec_pretty(&report)?)
+            .with_context(|| format!("cannot write {}", report_path.display()))?;
+        eprintln!(
+            "{succeeded} of {total} task(s) succeeded; report written to {}",
+            report_path.display()
+        );
+        anyhow::ensure!(
+            report.failed == 0,
+            "{} task(s) did not succeed",
+            report.failed
+        );
+        Ok(())
+    }
+}
+
+fn parse_task_file(path: &Path, text: &str) -> Result<TaskFile> {
+    let is_yaml = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"));
+    let parsed = if is_yaml {
+        serde_yaml::from_str(text).map_err(anyhow::Error::from)
+    } else {
+        toml::from_str(text).map_err(anyhow::Error::from)
+    };
+    parsed.with_context(|| format!("invalid task file {}", path.display()))
+}
+
+fn plan_tasks(task_file: TaskFile, base_dir: &Path, only: &[String]) -> Result<Vec<PlannedTask>> {
+    let TaskFile { defaults, tasks, .. } = task_file;
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut planned = Vec::new();
+    for task in tasks {
+        anyhow::ensure!(!task.name.trim().is_empty(), "every task needs a name");
+        anyhow::ensure!(
+            seen.insert(task.name.clone()),
+            "task `{}` is defined twice",
+            task.name
+        );
+        if !only.is_empty() && !only.contains(&task.name) {
+            continue;
+        }
+        let mut prompts: Vec<String> = task.prompt.into_iter().chain(task.prompts).collect();
+        prompts.retain(|prompt| !prompt.trim().is_empty());
+        anyhow::ensure!(
+            !prompts.is_empty(),
+            "task `{}` has no `prompt` or `prompts`",
+            task.name
+        );
+        let settings = task.settings.or(&defaults);
+        let cwd = match &settings.cwd {
+            Some(cwd) => base_dir.join(cwd),
+            None => base_dir.to_path_buf(),
+        };
+        anyhow::ensure!(
+            cwd.is_dir(),
+            "task `{}`: working directory {} does not exist",
+            task.name,
+            cwd.display()
+        );
+        planned.push(PlannedTask {
+            name: task.name,
+            prompts,
+            artifacts: task.artifacts,
+            cwd,
+            settings,
+        });
+    }
+    if let Some(missing) = only.iter().find(|name| !seen.contains(*name)) {
+        anyhow::bail!("no task named `{missing}`");
+    }
+    anyhow::ensure!(!planned.is_empty(), "no tasks to run");
+    Ok(planned)
+}
+
+/// Parallel tasks that edit the same checkout trample each other; point that
+/// out without refusing, since read-only tasks share a checkout just fine.
+fn warn_about_shared_checkouts(tasks: &[PlannedTask], parallel: usize) {
+    if parallel < 2 {
+        return;
+    }
+    let mut by_cwd: HashMap<&Path, Vec<&str>> = HashMap::new();
+    for task in tasks.iter().filter(|task| task.settings.worktree != Some(true)) {
+        by_cwd.entry(task.cwd.as_path()).or_default().push(&task.name);
+    }
+    for (cwd, names) in by_cwd.into_iter().filter(|(_, names)| names.len() > 1) {
+        eprintln!(
+            "warning: tasks {} may run at the same time in {}; set `worktree = true` to isolate them",
+            names.join(", "),
+            cwd.display()
+        );
+    }
+}
+
+/// Arguments for the `code exec` process that runs `task` in `cwd`.
+fn exec_args(
+    task: &PlannedTask,
+    cwd: &Path,
+    task_dir: &Path,
+    overrides: &[String],
+) -> Result<Vec<String>> {
+    let mut args = vec![
+        "exec".to_string(),
+        "--skip-git-repo-check".to_string(),
+        "--cd".to_string(),
+        cwd.to_string_lossy().to_string(),
+        "--output-last-message".to_string(),
+        task_dir.join("last-message.md").to_string_lossy().to_string(),
+    ];
+    for raw in overrides {
+        args.push("-c".to_string());
+        args.push(raw.clone());
+    }
+    if let Some(model) = &task.settings.model {
+        args.push("--model".to_string());
+        args.push(model.clone());
+    }
+    if let Some(sandbox) = task.settings.sandbox {
+        args.push("--sandbox".to_string());
+        args.push(sandbox.to_string());
+    }
+    if let Some(policy) = task.settings.approval_policy {
+        args.push("-c".to_string());
+        args.push(format!("approval_policy=\"{policy}\""));
+    }
+    if let [prompt] = task.prompts.as_slice() {
+        args.push("--".to_string());
+        args.push(prompt.clone());
+    } else {
+        let script = task_dir.join("prompts.txt");
+        std::fs::write(&script, task.prompts.join(PROMPT_SEPARATOR))
+            .with_context(|| format!("cannot write {}", script.display()))?;
+        args.push("--prompts-file".to_string());
+        args.push(script.to_string_lossy().to_string());
+    }
+    Ok(args)
+}
+
+async fn run_task(task: PlannedTask, out_dir: &Path, overrides: &[String]) -> TaskReport {
+    let task_dir = out_dir.join(git_worktree::sanitize_ref_component(&task.name));
+    let log = task_dir.join("output.log");
+    let started = Instant::now();
+    let mut report = TaskReport {
+        name: task.name.clone(),
+        status: TaskStatus::Failed,
+        exit_code: None,
+        duration_ms: 0,
+        cwd: task.cwd.clone(),
+        branch: None,
+        log: log.clone(),
+        last_message: None,
+        artifacts: Vec::new(),
+        error: None,
+    };
+    eprintln!("[{}] started", task.name);
+    if let Err(err) = run_task_inner(&task, &task_dir, overrides, &mut report).await {
+        report.error = Some(format!("{err:#}"));
+    }
+    report.duration_ms = started.elapsed().as_millis();
+    report.last_message = std::fs::read_to_string(task_dir.join("last-message.md"))
+        .ok()
+        .map(|message| message.trim().to_string())
+        .filter(|message| !message.is_empty());
+    let status = match report.status {
+        TaskStatus::Succeeded => "succeeded",
+        TaskStatus::Failed => "failed",
+        TaskStatus::TimedOut => "timed out",
+    };
+    eprintln!("[{}] {status} after {}s", task.name, report.duration_ms / 1000);
+    report
+}
+
+async fn run_task_inner(
+    task: &PlannedTask,
+    task_dir: &Path,
+    overrides: &[String],
+    report: &mut TaskReport,
+) -> Result<()> {
+    std::fs::create_dir_all(task_dir)
+        .with_context(|| format!("cannot create {}", task_dir.display()))?;
+    if task.settings.worktree == Some(true) {
+        let git_root = git_worktree::get_git_root_from(&task.cwd)
+            .await
+            .map_err(anyhow::Error::msg)?;
+        let branch = format!(
+            "code-run-{}-{}",
+            git_worktree::sanitize_ref_component(&task.name),
+            Local::now().format("%Y%m%d-%H%M%S")
+        );
+        let (worktree, branch) = git_worktree::setup_worktree(&git_root, &branch, None)
+            .await
+            .map_err(anyhow::Error::msg)?;
+        let relative = task.cwd.strip_prefix(&git_root).unwrap_or(Path::new(""));
+        report.cwd = worktree.join(relative);
+        report.branch = Some(branch);
+    }
+
+    let args = exec_args(task, &report.cwd, task_dir, overrides)?;
+    let log = std::fs::File::create(&report.log)
+        .with_context(|| format!("cannot create {}", report.log.display()))?;
+    let mut child = Command::new(std::env::current_exe()?)
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log)
+        .kill_on_drop(true)
+        .spawn()
+        .context("failed to start `code exec`")?;
+    let timeout = task
+        .settings
+        .timeout_seconds
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::MAX);
+    match tokio::time::timeout(timeout, child.wait()).await {
+        Ok(status) => {
+            let status = status?;
+            report.exit_code = status.code();
+            if status.success() {
+                report.status = TaskStatus::Succeeded;
+            }
+        }
+        Err(_) => {
+            let _ = child.kill().await;
+            report.status = TaskStatus::TimedOut;
+        }
+    }
+
+    let artifact_dir = task_dir.join("artifacts");
+    for artifact in &task.artifacts {
+        let source = report.cwd.join(artifact);
+        let saved_as = if source.exists() {
+            let target = artifact_dir.join(artifact_destination(artifact));
+            copy_recursively(&source, &target)
+                .with_context(|| format!("cannot copy artifact {}", source.display()))?;
+            Some(target)
+        } else {
+            None
+        };
+        report.artifacts.push(ArtifactReport {
+            path: artifact.clone(),
+            saved_as,
+        });
+    }
+    Ok(())
+}
+
+/// Keeps an artifact inside the artifacts directory even when its path is
+/// absolute or climbs out with `..`.
+fn artifact_destination(artifact: &Path) -> PathBuf {
+    artifact
+        .components()
+        .filter_map(|component| match component {
+            std::path::Component::Normal(part) => Some(part),
+            _ => None,
+        })
+        .collect()
+}
+
+fn copy_recursively(source: &Path, target: &Path) -> std::io::Result<()> {
+    if source.is_dir() {
+        std::fs::create_dir_all(target)?;
+        for entry in std::fs::read_dir(source)? {
+            let entry = entry?;
+            copy_recursively(&entry.path(), &target.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(source, target).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YAML: &str = r#"
+parallel: 2
+defaults:
+  sandbox: workspace-write
+  timeout_seconds: 600
+tasks:
+  - name: deps
+    prompt: Update dependencies
+    artifacts: [Cargo.lock]
+  - name: docs
+    prompts:
+      - Fix broken links
+      - Summarize what changed
+    sandbox: read-only
+    approval_policy: never
+    worktree: true
+"#;
+
+    #[test]
+    fn yaml_and_toml_task_files_merge_defaults() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let task_file = parse_task_file(Path::new("tasks.yaml"), YAML).expect("yaml");
+        assert_eq!(task_file.parallel, Some(2));
+        let tasks = plan_tasks(task_file, dir.path(), &[]).expect("plan");
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].settings.sandbox, Some(SandboxMode::WorkspaceWrite));
+        assert_eq!(tasks[1].settings.sandbox, Some(SandboxMode::ReadOnly));
+        assert_eq!(tasks[1].settings.timeout_seconds, Some(600));
+        assert_eq!(tasks[1].prompts.len(), 2);
+
+        let toml = "[[tasks]]\nname = \"lint\"\nprompt = \"Fix clippy warnings\"\n";
+        let task_file = parse_task_file(Path::new("tasks.toml"), toml).expect("toml");
+        let tasks = plan_tasks(task_file, dir.path(), &["lint".to_string()]).expect("plan");
+        assert_eq!(tasks[0].cwd, dir.path().to_path_buf());
+
+        let task_file = parse_task_file(Path::new("tasks.toml"), toml).expect("toml");
+        let err = plan_tasks(task_file, dir.path(), &["nope".to_string()]).expect_err("unknown");
+        assert_eq!(err.to_string(), "no task named `nope`");
+    }
+
+    #[test]
+    fn exec_args_pass_settings_and_prompt_scripts() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let task_file = parse_task_file(Path::new("tasks.yaml"), YAML).expect("yaml");
+        let tasks = plan_tasks(task_file, dir.path(), &[]).expect("plan");
+        let cwd = dir.path().to_path_buf();
+
+        let args = exec_args(&tasks[0], &cwd, dir.path(), &[]).expect("args");
+        assert_eq!(args[args.len() - 4..], ["--sandbox", "workspace-write", "--", "Update dependencies"]);
+
+        let args = exec_args(&tasks[1], &cwd, dir.path(), &["model=\"o3\"".to_string()]).expect("args");
+        assert!(args.windows(2).any(|pair| pair == ["-c", "model=\"o3\""]));
+        assert!(args.windows(2).any(|pair| pair == ["-c", "approval_policy=\"never\""]));
+        let script = std::fs::read_to_string(dir.path().join("prompts.txt")).expect("script");
+        assert_eq!(script, "Fix broken links\n---\nSummarize what changed");
+    }
+
+    #[test]
+    fn artifact_destinations_stay_inside_the_artifact_dir() {
+        assert_eq!(
+            artifact_destination(Path::new("../secrets/../report.html")),
+            PathBuf::from("secrets/report.html")
+        );
+        assert_eq!(
+            artifact_destination(Path::new("/tmp/out.txt")),
+            PathBuf::from("tmp/out.txt")
+        );
+    }
+}
@@ -0,0 +1,720 @@
+//! `code schedule`: recurring headless Auto Drive runs.
+//!
+//! Schedules live in `$CODE_HOME/schedules.toml`, each one a cron expression
+//! plus a prompt file and a working directory. `code schedule start` keeps
+//! running in the foreground and launches `code auto` whenever a schedule is
+//! due. Every run gets a directory under `$CODE_HOME/schedule-runs/<name>/`
+//! with its log and final message, is appended to
+//! `$CODE_HOME/schedule-runs/runs.jsonl` with the session id so it can be
+//! picked up with `code resume`, and ends with a notification.
+
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Local;
+use chrono::Datelike;
+use chrono::Timelike;
+use clap::Parser;
+use clap::Subcommand;
+use code_common::CliConfigOverrides;
+use code_core::config::Config;
+use code_core::config::ConfigOverrides;
+use code_core::config::find_code_home;
+use code_core::git_worktree;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::process::Command;
+use tokio::task::JoinHandle;
+
+const SCHEDULES_FILE: &str = "schedules.toml";
+const RUNS_DIR: &str = "schedule-runs";
+const RUNS_LOG: &str = "runs.jsonl";
+
+/// Longest the scheduler sleeps, so edits to `schedules.toml` apply within a
+/// minute without a restart.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Parser)]
+pub struct ScheduleCli {
+    #[command(subcommand)]
+    subcommand: ScheduleSubcommand,
+
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+}
+
+#[derive(Debug, Subcommand)]
+enum ScheduleSubcommand {
+    /// Add a recurring Auto Drive run, e.g. `add "0 6 * * *" --prompt-file nightly.md`.
+    Add(AddArgs),
+
+    /// List schedules with their next run and last result.
+    List,
+
+    /// Remove a schedule.
+    Remove(NameArgs),
+
+    /// Run the scheduler in the foreground, launching runs as they come due.
+    Start,
+
+    /// Run a schedule once now, in the foreground.
+    Run(NameArgs),
+
+    /// Show recent runs with their session ids.
+    History(HistoryArgs),
+}
+
+#[derive(Debug, Parser)]
+struct AddArgs {
+    /// Five-field cron expression (minute hour day-of-month month
+    /// day-of-week) or `@hourly`, `@daily`, `@weekly`, `@monthly`, `@yearly`.
+    #[arg(value_name = "CRON")]
+    cron: String,
+
+    /// File holding the prompt; it is read again before every run.
+    #[arg(long = "prompt-file", value_name = "FILE")]
+    prompt_file: PathBuf,
+
+    /// Schedule name (default: the prompt file's name without extension).
+    #[arg(long)]
+    name: Option<String>,
+
+    /// Working directory for the runs (default: the current directory).
+    #[arg(long = "cd", short = 'C', value_name = "DIR")]
+    cwd: Option<PathBuf>,
+
+    /// Model for the runs.
+    #[arg(long, short = 'm')]
+    model: Option<String>,
+
+    /// Wall-clock budget per run, passed to `code auto --max-seconds`.
+    #[arg(long = "max-seconds", value_name = "SECONDS")]
+    max_seconds: Option<u64>,
+}
+
+#[derive(Debug, Parser)]
+struct NameArgs {
+    #[arg(value_name = "NAME")]
+    name: String,
+}
+
+#[derive(Debug, Parser)]
+struct HistoryArgs {
+    /// Only show runs of this schedule.
+    #[arg(value_name = "NAME")]
+    name: Option<String>,
+
+    /// Number of runs to show.
+    #[arg(long, default_value_t = 20)]
+    limit: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ScheduleFile {
+    #[serde(default)]
+    schedules: Vec<ScheduleEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ScheduleEntry {
+    name: String,
+    cron: String,
+    prompt_file: PathBuf,
+    cwd: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RunStatus {
+    Succeeded,
+    Failed,
+}
+
+impl RunStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            RunStatus::Succeeded => "succeeded",
+            RunStatus::Failed => "failed",
+        }
+    }
+}
+
+/// One line of `runs.jsonl`.
+#[derive(Debug, Serialize, Deserialize)]
+struct RunRecord {
+    name: String,
+    started_at: String,
+    finished_at: String,
+    status: RunStatus,
+    exit_code: Option<i32>,
+    /// Session to pass to `code resume`.
+    session_id: Option<String>,
+    log: PathBuf,
+    last_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// A parsed cron expression. Each field is a bit set of the values it
+/// matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Day-of-month and day-of-week fields that were `*`. When both are
+    /// restricted, a day matching either one is enough, as in cron.
+    any_day: bool,
+    any_weekday: bool,
+}
+
+fn parse_cron_field(field: &str, min: u32, max: u32) -> Result<u64> {
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .with_context(|| format!("invalid step in `{part}`"))?;
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+        let value = |text: &str| -> Result<u32> {
+            let value: u32 = text
+                .parse()
+                .with_context(|| format!("invalid value `{text}` in `{part}`"))?;
+            anyhow::ensure!(
+                (min..=max).contains(&value),
+                "`{value}` is outside {min}-{max} in `{part}`"
+            );
+            Ok(value)
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (value(start)?, value(end)?),
+                None if step.is_some() => (value(range)?, max),
+                None => {
+                    let value = value(range)?;
+                    (value, value)
+                }
+            },
+        };
+        anyhow::ensure!(start <= end, "range `{range}` runs backwards");
+        let mut current = start;
+        while current <= end {
+            bits |= 1 << current;
+            current += step.unwrap_or(1);
+        }
+    }
+    Ok(bits)
+}
+
+impl CronSchedule {
+    fn parse(expression: &str) -> Result<Self> {
+        let expression = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields.as_slice() else {
+            anyhow::bail!("expected 5 cron fields (minute hour day month weekday), got `{expression}`");
+        };
+        let mut weekdays = parse_cron_field(weekday, 0, 7)?;
+        // Both 0 and 7 mean Sunday.
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            minutes: parse_cron_field(minute, 0, 59)?,
+            hours: parse_cron_field(hour, 0, 23)?,
+            days: parse_cron_field(day, 1, 31)?,
+            months: parse_cron_field(month, 1, 12)?,
+            weekdays,
+            any_day: *day == "*",
+            any_weekday: *weekday == "*",
+        })
+    }
+
+    fn matches(&self, time: &DateTime<Local>) -> bool {
+        let has = |bits: u64, value: u32| bits & (1 << value) != 0;
+        let day = has(self.days, time.day());
+        let weekday = has(self.weekdays, time.weekday().num_days_from_sunday());
+        let day_matches = match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => weekday,
+            (false, true) => day,
+            (false, false) => day || weekday,
+        };
+        has(self.minutes, time.minute())
+            && has(self.hours, time.hour())
+            && has(self.months, time.month())
+            && day_matches
+    }
+
+    /// First matching minute after `after`, looking up to a year ahead.
+    fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        let mut time = after.with_second(0)?.with_nanosecond(0)? + chrono::Duration::minutes(1);
+        for _ in 0..366 * 24 * 60 {
+            if self.matches(&time) {
+                return Some(time);
+            }
+            time += chrono::Duration::minutes(1);
+        }
+        None
+    }
+}
+
+impl ScheduleCli {
+    pub async fn run(self) -> Result<()> {
+        let code_home = find_code_home().context("failed to resolve CODE_HOME")?;
+        let overrides = self.config_overrides.raw_overrides;
+        match self.subcommand {
+            ScheduleSubcommand::Add(args) => run_add(&code_home, args),
+            ScheduleSubcommand::List => run_list(&code_home),
+            ScheduleSubcommand::Remove(args) => run_remove(&code_home, &args.name),
+            ScheduleSubcommand::Start => run_scheduler(&code_home, &overrides).await,
+            ScheduleSubcommand::Run(args) => {
+                let schedules = load_schedules(&code_home)?;
+                let entry = schedules
+                    .schedules
+                    .into_iter()
+                    .find(|entry| entry.name == args.name)
+                    .with_context(|| format!("no schedule named `{}`", args.name))?;
+                let record = run_schedule(&code_home, &entry, &overrides).await;
+                anyhow::ensure!(
+                    record.status == RunStatus::Succeeded,
+                    "run failed; see {}",
+                    record.log.display()
+                );
+                Ok(())
+            }
+            ScheduleSubcommand::History(args) => run_history(&code_home, args),
+        }
+    }
+}
+
+fn load_schedules(code_home: &Path) -> Result<ScheduleFile> {
+    let path = code_home.join(SCHEDULES_FILE);
+    match std::fs::read_to_string(&path) {
+        Ok(text) => toml::from_str(&text).with_context(|| format!("invalid {}", path.display())),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(ScheduleFile::default()),
+        Err(err) => Err(err).with_context(|| format!("cannot read {}", path.display())),
+    }
+}
+
+fn save_schedules(code_home: &Path, schedules: &ScheduleFile) -> Result<()> {
+    std::fs::create_dir_all(code_home)?;
+    let path = code_home.join(SCHEDULES_FILE);
+    std::fs::write(&path, toml::to_string_pretty(schedules)?)
+        .with_context(|| format!("cannot write {}", path.display()))
+}
+
+fn run_add(code_home: &Path, args: AddArgs) -> Result<()> {
+    let cron = CronSchedule::parse(&args.cron)?;
+    let prompt_file = std::fs::canonicalize(&args.prompt_file)
+        .with_context(|| format!("cannot read prompt file {}", args.prompt_file.display()))?;
+    let cwd = match args.cwd {
+        Some(cwd) => cwd,
+        None => std::env::current_dir()?,
+    };
+    let cwd = std::fs::canonicalize(&cwd)
+        .with_context(|| format!("working directory {} does not exist", cwd.display()))?;
+    let name = match args.name {
+        Some(name) => name.trim().to_string(),
+        None => prompt_file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    };
+    anyhow::ensure!(!name.is_empty(), "the schedule needs a name; pass --name");
+
+    let mut schedules = load_schedules(code_home)?;
+    anyhow::ensure!(
+        !schedules.schedules.iter().any(|entry| entry.name == name),
+        "a schedule named `{name}` already exists; remove it first"
+    );
+    schedules.schedules.push(ScheduleEntry {
+        name: name.clone(),
+        cron: args.cron.trim().to_string(),
+        prompt_file,
+        cwd,
+        model: args.model,
+        max_seconds: args.max_seconds,
+    });
+    save_schedules(code_home, &schedules)?;
+    match cron.next_after(Local::now()) {
+        Some(next) => println!("Added `{name}`; next run {}.", next.format("%Y-%m-%d %H:%M")),
+        None => println!("Added `{name}`, but `{}` never matches within a year.", args.cron.trim()),
+    }
+    println!("Runs happen while `code schedule start` is running.");
+    Ok(())
+}
+
+fn run_remove(code_home: &Path, name: &str) -> Result<()> {
+    let mut schedules = load_schedules(code_home)?;
+    let before = schedules.schedules.len();
+    schedules.schedules.retain(|entry| entry.name != name);
+    anyhow::ensure!(schedules.schedules.len() < before, "no schedule named `{name}`");
+    save_schedules(code_home, &schedules)?;
+    println!("Removed `{name}`.");
+    Ok(())
+}
+
+fn run_list(code_home: &Path) -> Result<()> {
+    let schedules = load_schedules(code_home)?;
+    if schedules.schedules.is_empty() {
+        println!("No schedules. Add one with `code schedule add \"0 6 * * *\" --prompt-file nightly.md`.");
+        return Ok(());
+    }
+    let runs = load_runs(code_home)?;
+    let now = Local::now();
+    for entry in &schedules.schedules {
+        let next = match CronSchedule::parse(&entry.cron) {
+            Ok(cron) => cron
+                .next_after(now)
+                .map(|next| next.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "never".to_string()),
+            Err(err) => format!("invalid cron: {err}"),
+        };
+        println!("{}  `{}`  next: {next}", entry.name, entry.cron);
+        println!("  prompt: {}", entry.prompt_file.display());
+        println!("  cwd:    {}", entry.cwd.display());
+        if let Some(last) = runs.iter().rev().find(|run| run.name == entry.name) {
+            println!("  last:   {} {}", last.started_at, last.status.as_str());
+        }
+    }
+    Ok(())
+}
+
+fn load_runs(code_home: &Path) -> Result<Vec<RunRecord>> {
+    let path = code_home.join(RUNS_DIR).join(RUNS_LOG);
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("cannot read {}", path.display())),
+    };
+    Ok(text
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+fn run_history(code_home: &Path, args: HistoryArgs) -> Result<()> {
+    let runs = load_runs(code_home)?;
+    let runs: Vec<&RunRecord> = runs
+        .iter()
+        .rev()
+        .filter(|run| args.name.as_ref().is_none_or(|name| &run.name == name))
+        .take(args.limit)
+        .collect();
+    if runs.is_empty() {
+        println!("No scheduled runs yet.");
+        return Ok(());
+    }
+    for run in runs {
+        println!("{}  {}  {}", run.started_at, run.name, run.status.as_str());
+        if let Some(session_id) = &run.session_id {
+            println!("  resume: code resume {session_id}");
+        }
+        println!("  log:    {}", run.log.display());
+        if let Some(error) = &run.error {
+            println!("  error:  {error}");
+        }
+    }
+    Ok(())
+}
+
+async fn run_scheduler(code_home: &Path, overrides: &[String]) -> Result<()> {
+    eprintln!(
+        "Scheduler running; schedules from {}. Press Ctrl-C to stop.",
+        code_home.join(SCHEDULES_FILE).display()
+    );
+    // Next due time per schedule, keyed by name and remembered with the cron
+    // expression it was computed from so edits take effect.
+    let mut due: HashMap<String, (String, Option<DateTime<Local>>)> = HashMap::new();
+    let mut running: HashMap<String, JoinHandle<()>> = HashMap::new();
+    loop {
+        let schedules = match load_schedules(code_home) {
+            Ok(schedules) => schedules.schedules,
+            Err(err) => {
+                eprintln!("warning: {err:#}");
+                Vec::new()
+            }
+        };
+        let now = Local::now();
+        due.retain(|name, _| schedules.iter().any(|entry| &entry.name == name));
+        running.retain(|_, handle| !handle.is_finished());
+
+        for entry in &schedules {
+            let cron = match CronSchedule::parse(&entry.cron) {
+                Ok(cron) => cron,
+                Err(err) => {
+                    if due.get(&entry.name).is_none_or(|(cron, _)| cron != &entry.cron) {
+                        eprintln!("warning: schedule `{}` has an invalid cron expression: {err:#}", entry.name);
+                        due.insert(entry.name.clone(), (entry.cron.clone(), None));
+                    }
+                    continue;
+                }
+            };
+            let slot = due
+                .entry(entry.name.clone())
+                .or_insert_with(|| (entry.cron.clone(), cron.next_after(now)));
+            if slot.0 != entry.cron {
+                *slot = (entry.cron.clone(), cron.next_after(now));
+            }
+            let Some(at) = slot.1.filter(|at| *at <= now) else {
+                continue;
+            };
+            slot.1 = cron.next_after(now);
+            if running.contains_key(&entry.name) {
+                eprintln!(
+                    "[{}] skipped the {} run; the previous run is still going",
+                    entry.name,
+                    at.format("%H:%M")
+                );
+                continue;
+            }
+            let code_home = code_home.to_path_buf();
+            let entry = entry.clone();
+            let overrides = overrides.to_vec();
+            let name = entry.name.clone();
+            running.insert(
+                name,
+                tokio::spawn(async move {
+                    run_schedule(&code_home, &entry, &overrides).await;
+                }),
+            );
+        }
+
+        let now = Local::now();
+        let sleep = due
+            .values()
+            .filter_map(|(_, at)| *at)
+            .map(|at| (at - now).to_std().unwrap_or(Duration::ZERO))
+            .min()
+            .unwrap_or(MAX_SLEEP)
+            .clamp(Duration::from_secs(1), MAX_SLEEP);
+        tokio::time::sleep(sleep).await;
+    }
+}
+
+/// Session id from the `codex session <id>` line `code exec` prints first.
+pub(crate) fn session_id_from_log(log: &str) -> Option<String> {
+    log.lines().find_map(|line| {
+        let (_, rest) = line.split_once("codex session ")?;
+        let id = rest.split_whitespace().next()?;
+        uuid::Uuid::parse_str(id).ok().map(|id| id.to_string())
+    })
+}
+
+/// Arguments for the `code auto` process that runs `entry`.
+fn auto_args(entry: &ScheduleEntry, prompt: String, run_dir: &Path, overrides: &[String]) -> Vec<String> {
+    let mut args = vec![
+        "auto".to_string(),
+        "--skip-git-repo-check".to_string(),
+        "--color".to_string(),
+        "never".to_string(),
+        "--cd".to_string(),
+        entry.cwd.to_string_lossy().to_string(),
+        "--output-last-message".to_string(),
+        run_dir.join("last-message.md").to_string_lossy().to_string(),
+    ];
+    for raw in overrides {
+        args.push("-c".to_string());
+        args.push(raw.clone());
+    }
+    if let Some(model) = &entry.model {
+        args.push("--model".to_string());
+        args.push(model.clone());
+    }
+    if let Some(seconds) = entry.max_seconds {
+        args.push("--max-seconds".to_string());
+        args.push(seconds.to_string());
+    }
+    args.push("--".to_string());
+    args.push(prompt);
+    args
+}
+
+async fn run_schedule(code_home: &Path, entry: &ScheduleEntry, overrides: &[String]) -> RunRecord {
+    let started = Local::now();
+    let run_dir = code_home
+        .join(RUNS_DIR)
+        .join(git_worktree::sanitize_ref_component(&entry.name))
+        .join(started.format("%Y%m%d-%H%M%S").to_string());
+    let mut record = RunRecord {
+        name: entry.name.clone(),
+        started_at: started.to_rfc3339(),
+        finished_at: String::new(),
+        status: RunStatus::Failed,
+        exit_code: None,
+        session_id: None,
+        log: run_dir.join("output.log"),
+        last_message: None,
+        error: None,
+    };
+    eprintln!("[{}] started", entry.name);
+    if let Err(err) = run_schedule_inner(entry, &run_dir, overrides, &mut record).await {
+        record.error = Some(format!("{err:#}"));
+    }
+    record.finished_at = Local::now().to_rfc3339();
+    record.session_id = std::fs::read_to_string(&record.log)
+        .ok()
+        .and_then(|log| session_id_from_log(&log));
+    record.last_message = std::fs::read_to_string(run_dir.join("last-message.md"))
+        .ok()
+        .map(|message| message.trim().to_string())
+        .filter(|message| !message.is_empty());
+    eprintln!("[{}] {}", entry.name, record.status.as_str());
+    if let Err(err) = append_run(code_home, &record) {
+        eprintln!("warning: cannot record the run: {err:#}");
+    }
+    notify_run_finished(&record);
+    record
+}
+
+async fn run_schedule_inner(
+    entry: &ScheduleEntry,
+    run_dir: &Path,
+    overrides: &[String],
+    record: &mut RunRecord,
+) -> Result<()> {
+    std::fs::create_dir_all(run_dir).with_context(|| format!("cannot create {}", run_dir.display()))?;
+    let prompt = std::fs::read_to_string(&entry.prompt_file)
+        .with_context(|| format!("cannot read prompt file {}", entry.prompt_file.display()))?;
+    anyhow::ensure!(
+        !prompt.trim().is_empty(),
+        "prompt file {} is empty",
+        entry.prompt_file.display()
+    );
+    let log = std::fs::File::create(&record.log)
+        .with_context(|| format!("cannot create {}", record.log.display()))?;
+    let status = Command::new(std::env::current_exe()?)
+        .args(auto_args(entry, prompt.trim().to_string(), run_dir, overrides))
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log)
+        .status()
+        .await
+        .context("failed to start `code auto`")?;
+    record.exit_code = status.code();
+    if status.success() {
+        record.status = RunStatus::Succeeded;
+    }
+    Ok(())
+}
+
+fn append_run(code_home: &Path, record: &RunRecord) -> Result<()> {
+    let dir = code_home.join(RUNS_DIR);
+    std::fs::create_dir_all(&dir)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(RUNS_LOG))?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    Ok(())
+}
+
+/// Passes the result to the configured `notify` program and, on Termux, to
+/// the Android notification shade.
+fn notify_run_finished(record: &RunRecord) {
+    let notify = Config::load_with_cli_overrides(Vec::new(), ConfigOverrides::default())
+        .ok()
+        .and_then(|config| config.notify)
+        .filter(|command| !command.is_empty());
+    if let Some(command) = notify {
+        let payload = serde_json::json!({
+            "type": "scheduled-run-complete",
+            "name": record.name,
+            "status": record.status.as_str(),
+            "session-id": record.session_id,
+            "last-assistant-message": record.last_message,
+        });
+        let mut notifier = std::process::Command::new(&command[0]);
+        notifier.args(&command[1..]).arg(payload.to_string());
+        if let Err(err) = code_core::spawn::spawn_std_command_with_retry(&mut notifier) {
+            eprintln!("warning: failed to run notifier `{}`: {err}", command[0]);
+        }
+    }
+
+    if code_core::env::is_termux() && which::which("termux-notification").is_ok() {
+        let content = match &record.session_id {
+            Some(session_id) => format!("{} · code resume {session_id}", record.status.as_str()),
+            None => record.status.as_str().to_string(),
+        };
+        let mut notifier = std::process::Command::new("termux-notification");
+        notifier
+            .arg("--title")
+            .arg(format!("code schedule: {}", record.name))
+            .arg("--content")
+            .arg(content);
+        if let Err(err) = code_core::spawn::spawn_std_command_with_retry(&mut notifier) {
+            eprintln!("warning: failed to run termux-notification: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .single()
+            .expect("unambiguous local time")
+    }
+
+    #[test]
+    fn cron_expressions_find_the_next_run() {
+        let daily = CronSchedule::parse("0 6 * * *").expect("daily");
+        assert_eq!(daily.next_after(at(2026, 3, 10, 5, 59)), Some(at(2026, 3, 10, 6, 0)));
+        assert_eq!(daily.next_after(at(2026, 3, 10, 6, 0)), Some(at(2026, 3, 11, 6, 0)));
+
+        // 2026-03-14 is a Saturday; weekdays 1-5 skip to Monday.
+        let weekdays = CronSchedule::parse("*/15 9-17 * * 1-5").expect("weekdays");
+        assert_eq!(weekdays.next_after(at(2026, 3, 13, 17, 50)), Some(at(2026, 3, 16, 9, 0)));
+
+        // Day-of-month and day-of-week both restricted: either one matches.
+        let either = CronSchedule::parse("0 0 1 * 7").expect("either");
+        assert_eq!(either.next_after(at(2026, 3, 2, 0, 0)), Some(at(2026, 3, 8, 0, 0)));
+
+        assert_eq!(CronSchedule::parse("@weekly"), CronSchedule::parse("0 0 * * 0"));
+        assert!(CronSchedule::parse("0 6 * *").is_err());
+        assert!(CronSchedule::parse("60 6 * * *").is_err());
+        assert_eq!(CronSchedule::parse("0 0 30 2 *").expect("feb 30").next_after(at(2026, 1, 1, 0, 0)), None);
+    }
+
+    #[test]
+    fn finds_the_session_id_in_exec_output() {
+        let log = "[2026-10-16T06:00:01] codex session 0199f4c2-8b1e-7c33-9a55-1d2e3f405162\nmodel: gpt-5.1\n";
+        assert_eq!(
+            session_id_from_log(log).as_deref(),
+            Some("0199f4c2-8b1e-7c33-9a55-1d2e3f405162")
+        );
+        assert_eq!(session_id_from_log("model: gpt-5.1\n"), None);
+    }
+}
@@ -0,0 +1,209 @@
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use clap::Subcommand;
+use clap::ValueEnum;
+use clap_complete::engine::ArgValueCompleter;
+use code_core::SessionCatalog;
+use code_core::SessionIndexEntry;
+use code_core::audit_rollout_order;
+use code_core::config::find_code_home;
+use code_core::entry_to_rollout_path;
+use code_core::history::HistorySnapshot;
+use code_core::history::golden::golden_transcript;
+use code_core::summarize_rollout;
+use std::path::PathBuf;
+
+use crate::completion::session_candidates;
+
+#[derive(Debug, Parser)]
+pub struct SessionCli {
+    #[command(subcommand)]
+    subcommand: SessionSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum SessionSubcommand {
+    /// Print a recorded session's history snapshot.
+    Dump(DumpArgs),
+
+    /// Check that a rollout's recorded events are in monotonic order.
+    AuditOrder(AuditOrderArgs),
+
+    /// Summarize what a session did: files changed, commands run, tokens
+    /// used and the final answer.
+    Summarize(SummarizeArgs),
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum DumpFormat {
+    /// Stable text transcript suitable for golden-file diffs.
+    Golden,
+    /// The raw snapshot JSON, pretty-printed.
+    Json,
+}
+
+#[derive(Debug, Parser)]
+struct DumpArgs {
+    /// Session id (prefix allowed) or path to a `.snapshot.json` file.
+    #[arg(value_name = "SESSION", add = ArgValueCompleter::new(session_candidates))]
+    session: String,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = DumpFormat::Golden)]
+    format: DumpFormat,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SummaryFormat {
+    /// Short human-readable report.
+    Text,
+    /// Structured JSON for scripts and dashboards.
+    Json,
+}
+
+#[derive(Debug, Parser)]
+struct SummarizeArgs {
+    /// Session id (prefix allowed) or path to a rollout `.jsonl` file.
+    #[arg(value_name = "SESSION", add = ArgValueCompleter::new(session_candidates))]
+    session: String,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = SummaryFormat::Text)]
+    format: SummaryFormat,
+}
+
+#[derive(Debug, Parser)]
+struct AuditOrderArgs {
+    /// Session id (prefix allowed) or path to a rollout `.jsonl` file.
+    #[arg(value_name = "SESSION", add = ArgValueCompleter::new(session_candidates))]
+    session: String,
+}
+
+impl SessionCli {
+    pub async fn run(self) -> Result<()> {
+        match self.subcommand {
+            SessionSubcommand::Dump(args) => run_dump(args).await,
+            SessionSubcommand::AuditOrder(args) => run_audit_order(args).await,
+            SessionSubcommand::Summarize(args) => run_summarize(args).await,
+        }
+    }
+}
+
+async fn run_dump(args: DumpArgs) -> Result<()> {
+    let path = resolve_snapshot_path(&args.session).await?;
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read snapshot {}", path.display()))?;
+    let snapshot: HistorySnapshot = serde_json::from_str(&contents)
+        .with_context(|| format!("failed to parse snapshot {}", path.display()))?;
+    match args.format {
+        DumpFormat::Golden => print!("{}", golden_transcript(&snapshot)),
+        DumpFormat::Json => println!("{}", serde_json::to_string_pretty(&snapshot)?),
+    }
+    Ok(())
+}
+
+async fn run_audit_order(args: AuditOrderArgs) -> Result<()> {
+    let path = resolve_rollout_path(&args.session).await?;
+    let audit = audit_rollout_order(&path)
+        .with_context(|| format!("failed to read rollout {}", path.display()))?;
+    for violation in &audit.violations {
+        println!(
+            "line {}: [{}] {}",
+            violation.line, violation.submission_id, violation.message
+        );
+    }
+    println!(
+        "{} event(s), {} with order metadata, {} violation(s)",
+        audit.events,
+        audit.ordered_events,
+        audit.violations.len()
+    );
+    if audit.unparsed_lines > 0 {
+        eprintln!("skipped {} unparseable line(s)", audit.unparsed_lines);
+    }
+    anyhow::ensure!(audit.is_clean(), "rollout ordering is not monotonic");
+    Ok(())
+}
+
+async fn run_summarize(args: SummarizeArgs) -> Result<()> {
+    let path = resolve_rollout_path(&args.session).await?;
+    let summary = summarize_rollout(&path)
+        .with_context(|| format!("failed to read rollout {}", path.display()))?;
+    if let SummaryFormat::Json = args.format {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        return Ok(());
+    }
+
+    if let Some(id) = &summary.session_id {
+        println!("session:  {id}");
+    }
+    if let Some(cwd) = &summary.cwd {
+        println!("cwd:      {}", cwd.display());
+    }
+    if let Some(model) = &summary.model {
+        println!("model:    {model}");
+    }
+    println!("messages: {}", summary.user_messages);
+    println!(
+        "tokens:   {} total ({} input, {} cached, {} output)",
+        summary.tokens.total, summary.tokens.input, summary.tokens.cached_input, summary.tokens.output
+    );
+    println!("files changed ({}):", summary.files_changed.len());
+    for file in &summary.files_changed {
+        println!("  {}", file.display());
+    }
+    let failed = summary
+        .commands
+        .iter()
+        .filter(|command| command.exit_code != 0)
+        .count();
+    println!("commands ({}, {failed} failed):", summary.commands.len());
+    for command in &summary.commands {
+        println!("  [{}] {}", command.exit_code, command.command);
+    }
+    if let Some(answer) = &summary.final_answer {
+        println!("final answer:\n{answer}");
+    }
+    if summary.unparsed_lines > 0 {
+        eprintln!("skipped {} unparseable line(s)", summary.unparsed_lines);
+    }
+    Ok(())
+}
+
+async fn find_session(session: &str) -> Result<(PathBuf, SessionIndexEntry)> {
+    let code_home = find_code_home().context("failed to resolve CODE_HOME")?;
+    let catalog = SessionCatalog::new(code_home.clone());
+    let entry = catalog
+        .find_by_id(session)
+        .await?
+        .with_context(|| format!("no session matches `{session}`"))?;
+    Ok((code_home, entry))
+}
+
+async fn resolve_rollout_path(session: &str) -> Result<PathBuf> {
+    let direct = PathBuf::from(session);
+    if direct.is_file() {
+        return Ok(direct);
+    }
+    let (code_home, entry) = find_session(session).await?;
+    Ok(entry_to_rollout_path(&code_home, &entry))
+}
+
+async fn resolve_snapshot_path(session: &str) -> Result<PathBuf> {
+    let direct = PathBuf::from(session);
+    if direct.is_file() {
+        return Ok(direct);
+    }
+    let (code_home, entry) = find_session(session).await?;
+    let path = match &entry.snapshot_path {
+        Some(snapshot) => code_home.join(snapshot),
+        None => entry_to_rollout_path(&code_home, &entry).with_extension("snapshot.json"),
+    };
+    anyhow::ensure!(
+        path.is_file(),
+        "session {} has no history snapshot (only sessions recorded by the TUI have one)",
+        entry.session_id
+    );
+    Ok(path)
+}
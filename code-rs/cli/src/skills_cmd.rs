@@ -0,0 +1,87 @@
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use clap::Subcommand;
+use clap_complete::engine::ArgValueCompleter;
+use code_core::config::Config;
+use code_core::config::ConfigOverrides;
+use serde_json::json;
+
+use crate::completion::skill_candidates;
+
+#[derive(Debug, Parser)]
+pub struct SkillsCli {
+    #[command(subcommand)]
+    subcommand: SkillsSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum SkillsSubcommand {
+    /// List the skills available to new sessions.
+    List(ListArgs),
+
+    /// Print a skill's instructions.
+    Show(ShowArgs),
+}
+
+#[derive(Debug, Parser)]
+struct ListArgs {
+    /// Output the skills as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Parser)]
+struct ShowArgs {
+    /// Name of the skill to print.
+    #[arg(value_name = "NAME", add = ArgValueCompleter::new(skill_candidates))]
+    name: String,
+}
+
+impl SkillsCli {
+    pub fn run(self) -> Result<()> {
+        let config = Config::load_with_cli_overrides(Vec::new(), ConfigOverrides::default())
+            .context("failed to load configuration")?;
+        let outcome = code_core::load_skills(&config);
+        match self.subcommand {
+            SkillsSubcommand::List(args) => {
+                if args.json {
+                    let skills: Vec<_> = outcome
+                        .skills
+                        .iter()
+                        .map(|skill| {
+                            json!({
+                                "name": skill.name,
+                                "description": skill.description,
+                                "scope": format!("{:?}", skill.scope).to_lowercase(),
+                                "path": skill.path,
+                            })
+                        })
+                        .collect();
+                    println!("{}", serde_json::to_string_pretty(&skills)?);
+                    return Ok(());
+                }
+                if outcome.skills.is_empty() {
+                    println!("No skills installed.");
+                }
+                for skill in &outcome.skills {
+                    let scope = format!("{:?}", skill.scope).to_lowercase();
+                    println!("{} [{scope}] {}", skill.name, skill.description);
+                }
+                for error in &outcome.errors {
+                    eprintln!("skipped {}: {}", error.path.display(), error.message);
+                }
+                Ok(())
+            }
+            SkillsSubcommand::Show(args) => {
+                let skill = outcome
+                    .skills
+                    .iter()
+                    .find(|skill| skill.name == args.name)
+                    .with_context(|| format!("no skill named `{}`", args.name))?;
+                println!("{}", skill.content);
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,429 @@
+//! `code watch`: start an agent turn when a check fails or files change.
+//!
+//! The watcher polls the working directory. After a burst of changes settles
+//! it runs the `--on` command, and when that fails it sends `--prompt` with
+//! the command's output to `code exec`. Without `--on`, changes to the
+//! `--glob` files trigger a turn that lists them. Every turn continues the
+//! session the first one started, and `--max-runs-per-hour` bounds how often
+//! the agent runs.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use code_common::CliConfigOverrides;
+use regex::Regex;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::process::Command;
+
+use crate::schedule_cmd::session_id_from_log;
+
+/// How often the working directory is scanned for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Tail of the check's output attached to the prompt.
+const MAX_OUTPUT_CHARS: usize = 12_000;
+
+/// Directories never scanned: VCS metadata and build output.
+const SKIPPED_DIRS: &[&str] = &[".git", "target", "node_modules"];
+
+const DEFAULT_PROMPT: &str = "Fix the failure below.";
+
+#[derive(Debug, Parser)]
+pub struct WatchCommand {
+    /// Shell command to run after changes (and once at start); a non-zero
+    /// exit starts a turn with its output attached.
+    #[arg(long = "on", value_name = "COMMAND")]
+    on: Option<String>,
+
+    /// Files to watch, relative to the working directory; repeat for
+    /// several. Default: every file outside `.git`, `target` and
+    /// `node_modules`.
+    #[arg(long = "glob", value_name = "GLOB")]
+    globs: Vec<String>,
+
+    /// Instructions sent with the failure output or the changed files.
+    #[arg(long, value_name = "TEXT", default_value = DEFAULT_PROMPT)]
+    prompt: String,
+
+    /// Seconds without changes before the check runs.
+    #[arg(long = "debounce", value_name = "SECONDS", default_value_t = 2)]
+    debounce_secs: u64,
+
+    /// Most agent turns in any hour (0 = unlimited).
+    #[arg(long = "max-runs-per-hour", value_name = "N", default_value_t = 6)]
+    max_runs_per_hour: usize,
+
+    /// Directory to watch and run in (default: the current directory).
+    #[arg(long = "cd", short = 'C', value_name = "DIR")]
+    cwd: Option<PathBuf>,
+
+    /// Model for the agent turns.
+    #[arg(long, short = 'm')]
+    model: Option<String>,
+
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+}
+
+/// Size and modification time of every watched file.
+type Snapshot = HashMap<PathBuf, (SystemTime, u64)>;
+
+/// Matches a path relative to the watched directory. `**` spans
+/// directories, `*` and `?` stay within one; a pattern without `/` also
+/// matches the file name alone.
+fn glob_regex(pattern: &str) -> Result<Regex> {
+    let pattern = pattern.trim_start_matches("./");
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            other => regex.push_str(&regex::escape(&other.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).with_context(|| format!("invalid glob `{pattern}`"))
+}
+
+fn is_watched(relative: &str, globs: &[(Regex, bool)]) -> bool {
+    if globs.is_empty() {
+        return true;
+    }
+    let name = relative.rsplit('/').next().unwrap_or(relative);
+    globs
+        .iter()
+        .any(|(regex, has_slash)| regex.is_match(relative) || (!has_slash && regex.is_match(name)))
+}
+
+fn scan(root: &Path, globs: &[(Regex, bool)]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let name = entry.file_name();
+                if !SKIPPED_DIRS.iter().any(|skipped| name == *skipped) {
+                    dirs.push(path);
+                }
+                continue;
+            }
+            let relative = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            if !is_watched(&relative, globs) {
+                continue;
+            }
+            if let Ok(metadata) = entry.metadata() {
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                snapshot.insert(path, (modified, metadata.len()));
+            }
+        }
+    }
+    snapshot
+}
+
+/// Files added, modified or removed between two snapshots.
+fn changed_files(before: &Snapshot, after: &Snapshot) -> BTreeSet<PathBuf> {
+    let mut changed: BTreeSet<PathBuf> = after
+        .iter()
+        .filter(|(path, stamp)| before.get(*path) != Some(*stamp))
+        .map(|(path, _)| path.clone())
+        .collect();
+    changed.extend(before.keys().filter(|path| !after.contains_key(*path)).cloned());
+    changed
+}
+
+/// The last `max_chars` characters of `text`, cut at a line start.
+fn output_tail(text: &str, max_chars: usize) -> String {
+    let total = text.chars().count();
+    if total <= max_chars {
+        return text.to_string();
+    }
+    let tail: String = text.chars().skip(total - max_chars).collect();
+    let tail = tail.split_once('\n').map(|(_, rest)| rest).unwrap_or(&tail);
+    format!("… (earlier output omitted)\n{tail}")
+}
+
+/// Agent turns in the last hour, to enforce `--max-runs-per-hour`.
+struct RunCap {
+    max_per_hour: usize,
+    started: VecDeque<Instant>,
+}
+
+impl RunCap {
+    fn allows(&mut self, now: Instant) -> bool {
+        while self
+            .started
+            .front()
+            .is_some_and(|started| now.duration_since(*started) >= Duration::from_secs(3600))
+        {
+            self.started.pop_front();
+        }
+        self.max_per_hour == 0 || self.started.len() < self.max_per_hour
+    }
+}
+
+impl WatchCommand {
+    pub async fn run(self) -> Result<()> {
+        anyhow::ensure!(
+            self.on.is_some() || !self.globs.is_empty(),
+            "pass --on COMMAND, --glob PATTERN or both"
+        );
+        let cwd = match &self.cwd {
+            Some(cwd) => cwd.clone(),
+            None => std::env::current_dir()?,
+        };
+        let cwd = std::fs::canonicalize(&cwd)
+            .with_context(|| format!("working directory {} does not exist", cwd.display()))?;
+        let globs = self
+            .globs
+            .iter()
+            .map(|glob| Ok((glob_regex(glob)?, glob.contains('/'))))
+            .collect::<Result<Vec<_>>>()?;
+        let debounce = Duration::from_secs(self.debounce_secs);
+        let mut cap = RunCap {
+            max_per_hour: self.max_runs_per_hour,
+            started: VecDeque::new(),
+        };
+        let mut session_id: Option<String> = None;
+
+        let mut snapshot = scan(&cwd, &globs);
+        eprintln!(
+            "Watching {} ({} file(s)); press Ctrl-C to stop.",
+            cwd.display(),
+            snapshot.len()
+        );
+        if self.on.is_some() {
+            self.trigger(&cwd, &BTreeSet::new(), &mut cap, &mut session_id).await?;
+        }
+
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let current = scan(&cwd, &globs);
+            let mut changed = changed_files(&snapshot, &current);
+            if changed.is_empty() {
+                continue;
+            }
+            snapshot = current;
+            // Wait for the burst of writes (a save, a checkout) to settle.
+            loop {
+                tokio::time::sleep(debounce.max(POLL_INTERVAL)).await;
+                let current = scan(&cwd, &globs);
+                let more = changed_files(&snapshot, &current);
+                if more.is_empty() {
+                    break;
+                }
+                changed.extend(more);
+                snapshot = current;
+            }
+            self.trigger(&cwd, &changed, &mut cap, &mut session_id).await?;
+            if self.on.is_none() {
+                // Without a check, the agent's own edits would trigger the
+                // next turn. With one, they re-run the check instead.
+                snapshot = scan(&cwd, &globs);
+            }
+        }
+    }
+
+    /// Runs the check (when there is one) and, if it fails or there is no
+    /// check, an agent turn.
+    async fn trigger(
+        &self,
+        cwd: &Path,
+        changed: &BTreeSet<PathBuf>,
+        cap: &mut RunCap,
+        session_id: &mut Option<String>,
+    ) -> Result<()> {
+        let prompt = match &self.on {
+            Some(check) => {
+                let output = shell_command(check)
+                    .current_dir(cwd)
+                    .stdin(Stdio::null())
+                    .output()
+                    .await
+                    .with_context(|| format!("failed to run `{check}`"))?;
+                if output.status.success() {
+                    eprintln!("[watch] `{check}` passed");
+                    return Ok(());
+                }
+                let code = output
+                    .status
+                    .code()
+                    .map(|code| code.to_string())
+                    .unwrap_or_else(|| "signal".to_string());
+                eprintln!("[watch] `{check}` failed (exit {code})");
+                let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+                text.push_str(&String::from_utf8_lossy(&output.stderr));
+                format!(
+                    "{}\n\n`{check}` failed with exit code {code}:\n```\n{}\n```",
+                    self.prompt.trim(),
+                    output_tail(text.trim_end(), MAX_OUTPUT_CHARS)
+                )
+            }
+            None => {
+                let files: Vec<String> = changed
+                    .iter()
+                    .map(|path| path.strip_prefix(cwd).unwrap_or(path).display().to_string())
+                    .collect();
+                eprintln!("[watch] {} file(s) changed", files.len());
+                format!("{}\n\nChanged files:\n- {}", self.prompt.trim(), files.join("\n- "))
+            }
+        };
+
+        let now = Instant::now();
+        if !cap.allows(now) {
+            eprintln!(
+                "[watch] skipped the agent turn: {} turn(s) in the last hour (--max-runs-per-hour)",
+                cap.started.len()
+            );
+            return Ok(());
+        }
+        cap.started.push_back(now);
+        self.run_agent_turn(cwd, prompt, session_id).await
+    }
+
+    /// Sends `prompt` through `code exec`, continuing the watch's session
+    /// once there is one.
+    async fn run_agent_turn(&self, cwd: &Path, prompt: String, session_id: &mut Option<String>) -> Result<()> {
+        let mut args = vec![
+            "exec".to_string(),
+            "--skip-git-repo-check".to_string(),
+            "--full-auto".to_string(),
+            "--color".to_string(),
+            "never".to_string(),
+            "--cd".to_string(),
+            cwd.to_string_lossy().to_string(),
+        ];
+        for raw in &self.config_overrides.raw_overrides {
+            args.push("-c".to_string());
+            args.push(raw.clone());
+        }
+        if let Some(model) = &self.model {
+            args.push("--model".to_string());
+            args.push(model.clone());
+        }
+        if let Some(id) = session_id.as_ref() {
+            args.push("resume".to_string());
+            args.push(id.clone());
+        }
+        args.push("-".to_string());
+
+        eprintln!("[watch] starting an agent turn");
+        let mut child = Command::new(std::env::current_exe()?)
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .context("failed to start `code exec`")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(prompt.as_bytes()).await?;
+        }
+        if let Some(stderr) = child.stderr.take() {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Some(line) = lines.next_line().await? {
+                if session_id.is_none() {
+                    *session_id = session_id_from_log(&line);
+                }
+                eprintln!("{line}");
+            }
+        }
+        let status = child.wait().await?;
+        match session_id.as_deref() {
+            Some(id) if status.success() => eprintln!("[watch] agent turn finished (session {id})"),
+            _ if status.success() => eprintln!("[watch] agent turn finished"),
+            _ => eprintln!("[watch] agent turn failed ({status})"),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn globs(patterns: &[&str]) -> Vec<(Regex, bool)> {
+        patterns
+            .iter()
+            .map(|glob| (glob_regex(glob).expect("glob"), glob.contains('/')))
+            .collect()
+    }
+
+    #[test]
+    fn globs_match_relative_paths_and_file_names() {
+        let rust = globs(&["*.rs"]);
+        assert!(is_watched("src/main.rs", &rust));
+        assert!(!is_watched("src/main.rs.orig", &rust));
+        let tests = globs(&["tests/**/*.py", "src/?.c"]);
+        assert!(is_watched("tests/unit/test_api.py", &tests));
+        assert!(is_watched("tests/test_api.py", &tests));
+        assert!(is_watched("src/a.c", &tests));
+        assert!(!is_watched("app/tests/test_api.py", &tests));
+        assert!(!is_watched("src/ab.c", &tests));
+        assert!(is_watched("anything", &[]));
+    }
+
+    #[test]
+    fn detects_changes_and_caps_runs() {
+        let t0 = SystemTime::UNIX_EPOCH;
+        let t1 = t0 + Duration::from_secs(1);
+        let before: Snapshot = [(PathBuf::from("a"), (t0, 1)), (PathBuf::from("b"), (t0, 1))].into();
+        let after: Snapshot = [(PathBuf::from("a"), (t1, 1)), (PathBuf::from("c"), (t0, 1))].into();
+        let changed: Vec<PathBuf> = changed_files(&before, &after).into_iter().collect();
+        assert_eq!(changed, ["a", "b", "c"].map(PathBuf::from));
+
+        let start = Instant::now();
+        let mut cap = RunCap {
+            max_per_hour: 2,
+            started: VecDeque::from([start, start]),
+        };
+        assert!(!cap.allows(start + Duration::from_secs(60)));
+        assert!(cap.allows(start + Duration::from_secs(3600)));
+        assert_eq!(output_tail("one\ntwo\nthree", 7), "… (earlier output omitted)\nthree");
+    }
+}
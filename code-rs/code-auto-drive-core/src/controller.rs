@@ -317,6 +317,9 @@ pub struct AutoDriveController {
     pub countdown_decision_seq: u64,
     pub seconds_remaining: u8,
     pub countdown_override: Option<u8>,
+    /// Set when a guardrail paused the run: the next prompt waits for the
+    /// user whatever the continue mode.
+    pub guardrail_hold: bool,
     pub last_broadcast_summary: Option<String>,
     pub last_decision_summary: Option<String>,
     pub last_decision_status_sent_to_user: Option<String>,
@@ -385,6 +388,7 @@ impl AutoDriveController {
 
     pub fn on_prompt_submitted(&mut self) {
         self.countdown_override = None;
+        self.guardrail_hold = false;
         self.apply_phase(AutoRunPhase::AwaitingDiagnostics { coordinator_waiting: true });
     }
 
@@ -825,6 +829,9 @@ impl AutoDriveController {
     }
 
     pub fn countdown_seconds(&self) -> Option<u8> {
+        if self.guardrail_hold {
+            return None;
+        }
         self.countdown_override.or_else(|| self.continue_mode.seconds())
     }
 
@@ -138,33 +138,10 @@ pub(crate) async fn request_permission(
     session_id: Uuid,
     mcp_connection_manager: &McpConnectionManager,
 ) -> Result<ReviewDecision> {
-    let approve_for_session_id = acp::PermissionOptionId("approve_for_session".into());
-    let approve_id = acp::PermissionOptionId("approve".into());
-    let deny_id = acp::PermissionOptionId("deny".into());
-
     let arguments = acp::RequestPermissionRequest {
         session_id: acp::SessionId(session_id.to_string().into()),
         tool_call,
-        options: vec![
-            acp::PermissionOption {
-                id: approve_for_session_id.clone(),
-                name: "Approve for Session".into(),
-                kind: acp::PermissionOptionKind::AllowAlways,
-                meta: None,
-            },
-            acp::PermissionOption {
-                id: approve_id.clone(),
-                name: "Approve".into(),
-                kind: acp::PermissionOptionKind::AllowOnce,
-                meta: None,
-            },
-            acp::PermissionOption {
-                id: deny_id.clone(),
-                name: "Deny".into(),
-                kind: acp::PermissionOptionKind::RejectOnce,
-                meta: None,
-            },
-        ],
+        options: permission_options(),
         meta: None,
     };
 
@@ -182,23 +159,51 @@ pub(crate) async fn request_permission(
     let result = structured_content.context("No output from permission tool")?;
     let result = serde_json::from_value::<acp::RequestPermissionResponse>(result)?;
 
-    use acp::RequestPermissionOutcome::*;
-    let decision = match result.outcome {
-        Selected { option_id } => {
-            if option_id == approve_id {
-                ReviewDecision::Approved
-            } else if option_id == approve_for_session_id {
-                ReviewDecision::ApprovedForSession
-            } else if option_id == deny_id {
-                ReviewDecision::Denied
-            } else {
-                anyhow::bail!("Unexpected permission option: {option_id}");
-            }
-        }
-        Cancelled => ReviewDecision::Abort,
-    };
+    review_decision_for_outcome(result.outcome)
+}
+
+const APPROVE_FOR_SESSION_OPTION: &str = "approve_for_session";
+const APPROVE_OPTION: &str = "approve";
+const DENY_OPTION: &str = "deny";
+
+/// Choices offered with every `session/request_permission` request.
+pub fn permission_options() -> Vec<acp::PermissionOption> {
+    vec![
+        acp::PermissionOption {
+            id: acp::PermissionOptionId(APPROVE_FOR_SESSION_OPTION.into()),
+            name: "Approve for Session".into(),
+            kind: acp::PermissionOptionKind::AllowAlways,
+            meta: None,
+        },
+        acp::PermissionOption {
+            id: acp::PermissionOptionId(APPROVE_OPTION.into()),
+            name: "Approve".into(),
+            kind: acp::PermissionOptionKind::AllowOnce,
+            meta: None,
+        },
+        acp::PermissionOption {
+            id: acp::PermissionOptionId(DENY_OPTION.into()),
+            name: "Deny".into(),
+            kind: acp::PermissionOptionKind::RejectOnce,
+            meta: None,
+        },
+    ]
+}
 
-    Ok(decision)
+/// Maps the client's answer to a [`permission_options`] request onto the
+/// decision core expects for the pending approval.
+pub fn review_decision_for_outcome(
+    outcome: acp::RequestPermissionOutcome,
+) -> Result<ReviewDecision> {
+    match outcome {
+        acp::RequestPermissionOutcome::Selected { option_id } => match &*option_id.0 {
+            APPROVE_FOR_SESSION_OPTION => Ok(ReviewDecision::ApprovedForSession),
+            APPROVE_OPTION => Ok(ReviewDecision::Approved),
+            DENY_OPTION => Ok(ReviewDecision::Denied),
+            _ => anyhow::bail!("Unexpected permission option: {option_id}"),
+        },
+        acp::RequestPermissionOutcome::Cancelled => Ok(ReviewDecision::Abort),
+    }
 }
 
 pub fn new_execute_tool_call(
@@ -0,0 +1,102 @@
+//! Android shared storage as seen from Termux.
+//!
+//! Shared storage (`/sdcard`, `/storage/emulated/0`) is only reachable after
+//! `termux-setup-storage` has granted the storage permission; that command
+//! also creates the `~/storage/<name>` symlinks. Without the permission, writes
+//! fail with a bare EACCES, so callers check paths up front with
+//! [`storage_access_error`] and surface an actionable message instead.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Primary shared storage volume.
+const SHARED_ROOT: &str = "/storage/emulated/0";
+
+/// Aliases Android and Termux use for [`SHARED_ROOT`].
+const SHARED_ROOT_ALIASES: &[&str] = &["/sdcard", "/storage/self/primary", "/mnt/sdcard"];
+
+/// `~/storage/<link>` symlinks created by `termux-setup-storage`, with the
+/// shared-storage directory each points at.
+const TERMUX_STORAGE_LINKS: &[(&str, &str)] = &[
+    ("shared", ""),
+    ("downloads", "Download"),
+    ("dcim", "DCIM"),
+    ("pictures", "Pictures"),
+    ("music", "Music"),
+    ("movies", "Movies"),
+    ("documents", "Documents"),
+];
+
+/// Maps `path` to its location under `/storage/emulated/0` when it lives in
+/// shared storage under any of the usual spellings. Returns `None` for paths
+/// outside shared storage (including removable volumes such as
+/// `~/storage/external-1`, which stay app-private).
+pub fn shared_storage_path(path: &Path) -> Option<PathBuf> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    shared_storage_path_with_home(path, home.as_deref())
+}
+
+fn shared_storage_path_with_home(path: &Path, home: Option<&Path>) -> Option<PathBuf> {
+    if path.starts_with(SHARED_ROOT) {
+        return Some(path.to_path_buf());
+    }
+    for alias in SHARED_ROOT_ALIASES {
+        if let Ok(rest) = path.strip_prefix(alias) {
+            return Some(Path::new(SHARED_ROOT).join(rest));
+        }
+    }
+    let storage = home?.join("storage");
+    let rest = path.strip_prefix(&storage).ok()?;
+    let mut components = rest.components();
+    let link = components.next()?.as_os_str().to_str()?;
+    let (_, target) = TERMUX_STORAGE_LINKS.iter().find(|(name, _)| *name == link)?;
+    Some(Path::new(SHARED_ROOT).join(target).join(components.as_path()))
+}
+
+/// True once `termux-setup-storage` has been granted access.
+pub fn storage_permission_granted() -> bool {
+    std::fs::read_dir(SHARED_ROOT).is_ok()
+}
+
+/// Returns a user-facing error when `path` is in shared storage and Termux
+/// lacks the storage permission. Always `None` outside Termux.
+pub fn storage_access_error(path: &Path) -> Option<String> {
+    if !crate::env::is_termux() {
+        return None;
+    }
+    shared_storage_path(path)?;
+    if storage_permission_granted() {
+        return None;
+    }
+    Some(format!(
+        "{} requires Android storage permission: run `termux-setup-storage`, allow access, then retry",
+        path.display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn maps_termux_spellings_of_shared_storage() {
+        let home = Path::new("/data/data/com.termux/files/home");
+        let map = |path: &str| shared_storage_path_with_home(Path::new(path), Some(home));
+
+        assert_eq!(
+            map("/sdcard/Notes/todo.md"),
+            Some(PathBuf::from("/storage/emulated/0/Notes/todo.md"))
+        );
+        assert_eq!(
+            map("/data/data/com.termux/files/home/storage/downloads/a.txt"),
+            Some(PathBuf::from("/storage/emulated/0/Download/a.txt"))
+        );
+        assert_eq!(
+            map("/data/data/com.termux/files/home/storage/shared"),
+            Some(PathBuf::from("/storage/emulated/0"))
+        );
+        assert_eq!(map("/data/data/com.termux/files/home/storage/external-1/x"), None);
+        assert_eq!(map("/data/data/com.termux/files/home/project/src/main.rs"), None);
+    }
+}
@@ -19,6 +19,7 @@ use serde_json::json;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
+use wildmatch::WildMatch;
 
 pub const CODEX_APPLY_PATCH_ARG1: &str = "--codex-run-as-apply-patch";
 
@@ -43,6 +44,10 @@ pub(crate) async fn apply_patch(
     output_index: Option<u32>,
     action: ApplyPatchAction,
 ) -> ApplyPatchResult {
+    // Snapshot what each updated file looked like when the patch was prepared so
+    // edits made on disk while approval is pending get merged, not overwritten.
+    let prepared_contents = prepared_update_contents(&action);
+
     let (harness_summary_json, harness_status_message) = {
         let mut summary_json: Option<String> = None;
         let mut status_message: Option<String> = None;
@@ -137,6 +142,26 @@ pub(crate) async fn apply_patch(
             .await;
     }
 
+    // Shared storage without the Android permission fails with a bare EACCES;
+    // report it before asking for approval.
+    let storage_error = action.changes().iter().find_map(|(path, change)| {
+        let move_path = match change {
+            ApplyPatchFileChange::Update { move_path, .. } => move_path.as_deref(),
+            _ => None,
+        };
+        std::iter::once(path.as_path())
+            .chain(move_path)
+            .find_map(crate::android_storage::storage_access_error)
+    });
+    if let Some(message) = storage_error {
+        return ApplyPatchResult::Reply(ResponseInputItem::FunctionCallOutput {
+            call_id: call_id.to_owned(),
+            output: FunctionCallOutputPayload {
+                body: code_protocol::models::FunctionCallOutputBody::Text(format!("patch rejected: {message}")),
+                success: Some(false)},
+        });
+    }
+
     let auto_approved = match assess_patch_safety(
         &action,
         sess.get_approval_policy(),
@@ -145,16 +170,39 @@ pub(crate) async fn apply_patch(
     ) {
         SafetyCheck::AutoApprove { .. } => true,
         SafetyCheck::AskUser => {
+            let lint_note = match sess.lint_gate() {
+                Some(gate) if gate.applies_to(&action) => {
+                    let order = sess.next_background_order(sub_id, attempt_req, output_index);
+                    sess.notify_background_event_with_order(
+                        sub_id,
+                        order,
+                        "Linting the patch before approval…".to_string(),
+                    )
+                    .await;
+                    gate.check(&action).await
+                }
+                _ => None,
+            };
             let rx = sess
-                .request_patch_approval(sub_id.to_owned(), call_id.to_owned(), &action, None, None)
+                .request_patch_approval(
+                    sub_id.to_owned(),
+                    call_id.to_owned(),
+                    &action,
+                    lint_note.clone(),
+                    None,
+                )
                 .await;
             match rx.await.unwrap_or_default() {
                 ReviewDecision::Approved | ReviewDecision::ApprovedForSession => false,
                 ReviewDecision::Denied | ReviewDecision::Abort => {
+                    let message = match lint_note {
+                        Some(note) => format!("patch rejected by user\n{note}"),
+                        None => "patch rejected by user".to_string(),
+                    };
                     return ApplyPatchResult::Reply(ResponseInputItem::FunctionCallOutput {
                         call_id: call_id.to_owned(),
                         output: FunctionCallOutputPayload {
-                            body: code_protocol::models::FunctionCallOutputBody::Text("patch rejected by user".to_string()),
+                            body: code_protocol::models::FunctionCallOutputBody::Text(message),
                             success: Some(false)},
                     });
                 }
@@ -173,9 +221,23 @@ pub(crate) async fn apply_patch(
     let mut stderr = Vec::new();
     let result = if let Some(client_tools) = sess.client_tools() {
         let fs = AcpFileSystem::new(sess.session_uuid(), client_tools, sess.mcp_connection_manager());
-        apply_changes_from_apply_patch_and_report(&action, &mut stdout, &mut stderr, &fs).await
+        apply_changes_from_apply_patch_and_report(
+            &action,
+            &prepared_contents,
+            &mut stdout,
+            &mut stderr,
+            &fs,
+        )
+        .await
     } else {
-        apply_changes_from_apply_patch_and_report(&action, &mut stdout, &mut stderr, &StdFileSystem).await
+        apply_changes_from_apply_patch_and_report(
+            &action,
+            &prepared_contents,
+            &mut stdout,
+            &mut stderr,
+            &StdFileSystem,
+        )
+        .await
     };
 
     let stdout = String::from_utf8_lossy(&stdout).to_string();
@@ -237,15 +299,136 @@ pub(crate) fn get_writable_roots(cwd: &Path) -> Vec<PathBuf> {
     writable_roots
 }
 
+/// Removes changes whose source or rename destination matches one of the
+/// `[patches] protected` globs and returns the paths that were dropped.
+pub(crate) fn strip_protected_changes(
+    action: &mut ApplyPatchAction,
+    patterns: &[String],
+) -> Vec<PathBuf> {
+    if patterns.is_empty() {
+        return Vec::new();
+    }
+    let cwd = action.cwd.clone();
+    let mut stripped: Vec<PathBuf> = Vec::new();
+    action.retain_changes(|path, change| {
+        let dest = match change {
+            ApplyPatchFileChange::Update {
+                move_path: Some(dest),
+                ..
+            } => Some(dest),
+            _ => None,
+        };
+        let protected = std::iter::once(path)
+            .chain(dest)
+            .any(|candidate| matches_patch_glob(&cwd, candidate, patterns));
+        if protected {
+            stripped.push(path.clone());
+        }
+        !protected
+    });
+    stripped.sort();
+    stripped
+}
+
+/// Message shown to the user and returned to the model when protected paths
+/// were dropped from a patch.
+pub(crate) fn protected_paths_note(cwd: &Path, stripped: &[PathBuf]) -> Option<String> {
+    if stripped.is_empty() {
+        return None;
+    }
+    let list = stripped
+        .iter()
+        .map(|path| path.strip_prefix(cwd).unwrap_or(path).display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(format!(
+        "Protected paths: skipped changes to {list}. These files match `[patches] protected` \
+         and cannot be modified by apply_patch; leave them unchanged."
+    ))
+}
+
+/// Matches `path` (made relative to `cwd`) against gitignore-style globs: a
+/// pattern without `/` matches the file name at any depth, and a leading
+/// `**/` also matches at the top level.
+pub(crate) fn matches_patch_glob(cwd: &Path, path: &Path, patterns: &[String]) -> bool {
+    let rel = path
+        .strip_prefix(cwd)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/");
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.trim_start_matches("./");
+        WildMatch::new(pattern).matches(&rel)
+            || pattern
+                .strip_prefix("**/")
+                .is_some_and(|rest| WildMatch::new(rest).matches(&rel))
+            || (!pattern.contains('/') && WildMatch::new(pattern).matches(&name))
+    })
+}
+
+fn prepared_update_contents(action: &ApplyPatchAction) -> HashMap<PathBuf, String> {
+    action
+        .changes()
+        .iter()
+        .filter(|(_, change)| matches!(change, ApplyPatchFileChange::Update { .. }))
+        .filter_map(|(path, _)| {
+            std::fs::read_to_string(path)
+                .ok()
+                .map(|content| (path.clone(), content))
+        })
+        .collect()
+}
+
+/// Returns the content to write for an updated file. If the file changed on
+/// disk after the patch was prepared, the patch is 3-way merged onto the
+/// current contents; overlapping edits fail with a merge conflict.
+fn merge_with_disk_edits(
+    path: &Path,
+    prepared: Option<&String>,
+    new_content: &str,
+) -> Result<Option<String>> {
+    let Some(prepared) = prepared else {
+        return Ok(None);
+    };
+    let Ok(current) = std::fs::read_to_string(path) else {
+        return Ok(None);
+    };
+    if current == *prepared {
+        return Ok(None);
+    }
+    match diffy::merge(prepared, &current, new_content) {
+        Ok(merged) => Ok(Some(merged)),
+        Err(conflicted) => {
+            let regions = conflicted
+                .lines()
+                .filter(|line| line.starts_with("<<<<<<<"))
+                .count();
+            anyhow::bail!(
+                "Merge conflict in {}: the file changed on disk after the patch was prepared \
+                 and {regions} region(s) overlap with the patch. Re-read the file and regenerate the patch.",
+                path.display()
+            )
+        }
+    }
+}
+
 async fn apply_changes_from_apply_patch_and_report(
     action: &ApplyPatchAction,
+    prepared_contents: &HashMap<PathBuf, String>,
     stdout: &mut impl std::io::Write,
     stderr: &mut impl std::io::Write,
     fs: &impl FileSystem,
 ) -> std::io::Result<()> {
-    match apply_changes_from_apply_patch(action, fs).await {
-        Ok(affected_paths) => {
+    match apply_changes_from_apply_patch(action, prepared_contents, fs).await {
+        Ok((affected_paths, merged)) => {
             print_summary(&affected_paths, stdout)?;
+            for path in merged {
+                writeln!(stdout, "Merged with on-disk edits: {}", path.display())?;
+            }
         }
         Err(err) => {
             writeln!(stderr, "{err:#}")?;
@@ -257,11 +440,25 @@ async fn apply_changes_from_apply_patch_and_report(
 
 async fn apply_changes_from_apply_patch(
     action: &ApplyPatchAction,
+    prepared_contents: &HashMap<PathBuf, String>,
     fs: &impl FileSystem,
-) -> Result<AffectedPaths> {
+) -> Result<(AffectedPaths, Vec<PathBuf>)> {
     let mut added: Vec<PathBuf> = Vec::new();
     let mut modified: Vec<PathBuf> = Vec::new();
     let mut deleted: Vec<PathBuf> = Vec::new();
+    let mut merged: Vec<PathBuf> = Vec::new();
+
+    // Resolve merges up front so a conflict leaves every file untouched.
+    let mut merged_contents: HashMap<&PathBuf, String> = HashMap::new();
+    for (path, change) in action.changes() {
+        if let ApplyPatchFileChange::Update { new_content, .. } = change
+            && let Some(content) =
+                merge_with_disk_edits(path, prepared_contents.get(path), new_content)?
+        {
+            merged_contents.insert(path, content);
+            merged.push(path.clone());
+        }
+    }
 
     for (path, change) in action.changes() {
         match change {
@@ -287,6 +484,7 @@ async fn apply_changes_from_apply_patch(
                 new_content,
                 ..
             } => {
+                let new_content = merged_contents.get(path).unwrap_or(new_content);
                 if let Some(move_path) = move_path {
                     if let Some(parent) = move_path.parent()
                         && !parent.as_os_str().is_empty() {
@@ -308,9 +506,72 @@ async fn apply_changes_from_apply_patch(
         }
     }
 
-    Ok(AffectedPaths {
-        added,
-        modified,
-        deleted,
-    })
+    Ok((
+        AffectedPaths {
+            added,
+            modified,
+            deleted,
+        },
+        merged,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protected_globs_match_relative_paths_and_names() {
+        let cwd = Path::new("/repo");
+        let patterns = vec![
+            "**/*.lock".to_string(),
+            ".env".to_string(),
+            "secrets/**".to_string(),
+        ];
+        let protected = |rel: &str| matches_patch_glob(cwd, &cwd.join(rel), &patterns);
+        assert!(protected("Cargo.lock"));
+        assert!(protected("web/yarn.lock"));
+        assert!(protected(".env"));
+        assert!(protected("services/api/.env"));
+        assert!(protected("secrets/prod/key.pem"));
+        assert!(!protected("src/secrets.rs"));
+        assert!(!protected(".env.example"));
+    }
+
+    #[test]
+    fn strips_protected_changes_from_action() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut action = ApplyPatchAction::new_add_for_test(&dir.path().join(".env"), "A=1\n".to_string());
+        let stripped = strip_protected_changes(&mut action, &[".env".to_string()]);
+        assert_eq!(stripped, vec![dir.path().join(".env")]);
+        assert!(action.is_empty());
+        assert!(protected_paths_note(dir.path(), &stripped).is_some_and(|note| note.contains(".env")));
+    }
+
+    #[test]
+    fn merges_patch_onto_unrelated_disk_edits() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("notes.txt");
+        let prepared = "one\ntwo\nthree\nfour\nfive\n".to_string();
+        std::fs::write(&path, "ONE\ntwo\nthree\nfour\nfive\n").unwrap();
+
+        let merged = merge_with_disk_edits(&path, Some(&prepared), "one\ntwo\nthree\nfour\nFIVE\n")
+            .expect("clean merge");
+        assert_eq!(merged.as_deref(), Some("ONE\ntwo\nthree\nfour\nFIVE\n"));
+    }
+
+    #[test]
+    fn reports_conflict_when_edits_overlap() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("notes.txt");
+        let prepared = "alpha\n".to_string();
+        std::fs::write(&path, "beta\n").unwrap();
+
+        let err = merge_with_disk_edits(&path, Some(&prepared), "gamma\n").unwrap_err();
+        assert!(err.to_string().starts_with("Merge conflict in"));
+        assert_eq!(
+            merge_with_disk_edits(&path, Some(&"beta\n".to_string()), "gamma\n").unwrap(),
+            None
+        );
+    }
 }
@@ -0,0 +1,160 @@
+//! Files the agent produces for the user, such as reports, generated images
+//! or CSV exports. The `save_artifact` tool writes them under
+//! `<code_home>/artifacts/<session id>/`, outside the workspace, so they
+//! outlive the session without cluttering the repository.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use base64::Engine;
+use serde::Deserialize;
+use serde::Serialize;
+
+pub const ARTIFACTS_DIR: &str = "artifacts";
+
+/// Largest artifact `save_artifact` writes.
+const MAX_ARTIFACT_BYTES: u64 = 50 * 1024 * 1024;
+
+/// A saved artifact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactInfo {
+    pub name: String,
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// Where an artifact's content comes from.
+#[derive(Debug, Clone, Copy)]
+pub enum ArtifactSource<'a> {
+    Text(&'a str),
+    Base64(&'a str),
+    /// An existing file to copy, such as an image a script wrote.
+    File(&'a Path),
+}
+
+/// `<code_home>/artifacts/<session_id>`.
+pub fn session_artifacts_dir(code_home: &Path, session_id: &str) -> PathBuf {
+    code_home.join(ARTIFACTS_DIR).join(session_id)
+}
+
+/// Checks that `name` is a plain file name: no directories, no leading dot
+/// and nothing that could step outside the artifacts folder.
+pub fn validate_artifact_name(name: &str) -> Result<&str, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("artifact name is empty".to_string());
+    }
+    if name.starts_with('.')
+        || name.contains(['/', '\\', '\0'])
+        || name.chars().any(char::is_control)
+    {
+        return Err(format!(
+            "`{name}` is not a plain file name (no directories or leading dots)"
+        ));
+    }
+    Ok(name)
+}
+
+/// Writes an artifact into `dir`, replacing an earlier one of the same name.
+pub fn save_artifact(
+    dir: &Path,
+    name: &str,
+    source: ArtifactSource<'_>,
+) -> Result<ArtifactInfo, String> {
+    let name = validate_artifact_name(name)?;
+    let bytes = match source {
+        ArtifactSource::Text(text) => text.as_bytes().to_vec(),
+        ArtifactSource::Base64(data) => base64::engine::general_purpose::STANDARD
+            .decode(data.trim())
+            .map_err(|err| format!("content is not valid base64: {err}"))?,
+        ArtifactSource::File(path) => {
+            let len = std::fs::metadata(path)
+                .map_err(|err| format!("cannot read {}: {err}", path.display()))?
+                .len();
+            if len > MAX_ARTIFACT_BYTES {
+                return Err(too_large(len));
+            }
+            std::fs::read(path).map_err(|err| format!("cannot read {}: {err}", path.display()))?
+        }
+    };
+    if bytes.len() as u64 > MAX_ARTIFACT_BYTES {
+        return Err(too_large(bytes.len() as u64));
+    }
+
+    std::fs::create_dir_all(dir)
+        .map_err(|err| format!("cannot create {}: {err}", dir.display()))?;
+    let path = dir.join(name);
+    std::fs::write(&path, &bytes).map_err(|err| format!("cannot write {}: {err}", path.display()))?;
+    Ok(ArtifactInfo {
+        name: name.to_string(),
+        path,
+        bytes: bytes.len() as u64,
+    })
+}
+
+fn too_large(bytes: u64) -> String {
+    format!(
+        "artifact is {} MiB; the limit is {} MiB",
+        bytes / (1024 * 1024),
+        MAX_ARTIFACT_BYTES / (1024 * 1024)
+    )
+}
+
+/// Artifacts in `dir`, by name. A missing folder has none.
+pub fn list_artifacts(dir: &Path) -> Vec<ArtifactInfo> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut artifacts: Vec<ArtifactInfo> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            metadata.is_file().then(|| ArtifactInfo {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                path: entry.path(),
+                bytes: metadata.len(),
+            })
+        })
+        .collect();
+    artifacts.sort_by(|a, b| a.name.cmp(&b.name));
+    artifacts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn saves_lists_and_replaces_artifacts() {
+        let home = TempDir::new().expect("tempdir");
+        let dir = session_artifacts_dir(home.path(), "session-1");
+
+        let report = save_artifact(&dir, "report.md", ArtifactSource::Text("# Report\n"))
+            .expect("save report");
+        assert_eq!(report.path, home.path().join("artifacts/session-1/report.md"));
+        assert_eq!(report.bytes, 9);
+        save_artifact(&dir, "data.bin", ArtifactSource::Base64("AAEC")).expect("save base64");
+        save_artifact(&dir, "report.md", ArtifactSource::Text("# Report v2\n"))
+            .expect("replace report");
+
+        let listed: Vec<(String, u64)> = list_artifacts(&dir)
+            .into_iter()
+            .map(|artifact| (artifact.name, artifact.bytes))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![("data.bin".to_string(), 3), ("report.md".to_string(), 12)]
+        );
+        assert!(list_artifacts(&home.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn rejects_names_that_leave_the_folder() {
+        for name in ["", "../escape.txt", "nested/file.txt", ".hidden", "a\\b"] {
+            assert!(validate_artifact_name(name).is_err(), "{name:?} was accepted");
+        }
+        assert_eq!(validate_artifact_name(" chart.png "), Ok("chart.png"));
+    }
+}
@@ -0,0 +1,136 @@
+//! Prompt and finding filters for background auto-review, driven by
+//! `[auto_review]` (see [`AutoReviewConfig`]).
+
+use std::path::Path;
+
+use crate::apply_patch::matches_patch_glob;
+use crate::config_types::AutoReviewConfig;
+use crate::config_types::ReviewSeverity;
+use crate::protocol::ReviewFinding;
+
+/// Review instructions used when `[auto_review] prompt` is unset.
+pub const DEFAULT_AUTO_REVIEW_PROMPT: &str = "Analyze only changes made in commit {commit}. Identify critical bugs, regressions, security/performance/concurrency risks or incorrect assumptions. Provide actionable feedback and references to the changed code; ignore minor style or formatting nits.";
+
+/// Instructions for the auto-review agent: the configured template with
+/// `{commit}` filled in, followed by the file scope and severity gate.
+pub fn auto_review_prompt(config: &AutoReviewConfig, commit: &str) -> String {
+    let template = config
+        .prompt
+        .as_deref()
+        .map(str::trim)
+        .filter(|prompt| !prompt.is_empty())
+        .unwrap_or(DEFAULT_AUTO_REVIEW_PROMPT);
+    let mut prompt = template.replace("{commit}", commit);
+    if !template.contains("{commit}") {
+        prompt.push_str(&format!("\n\nReview commit {commit}."));
+    }
+    if !config.include.is_empty() {
+        prompt.push_str(&format!(
+            "\n\nOnly review files matching: {}.",
+            config.include.join(", ")
+        ));
+    }
+    if !config.exclude.is_empty() {
+        prompt.push_str(&format!(
+            "\n\nIgnore files matching: {}.",
+            config.exclude.join(", ")
+        ));
+    }
+    let min_severity = config.min_severity();
+    if min_severity > ReviewSeverity::Info {
+        prompt.push_str(&format!(
+            "\n\nOnly report findings of priority {} or more severe.",
+            min_severity.priority_label()
+        ));
+    }
+    prompt
+}
+
+/// Whether a finding passes the severity gate and file scope. Paths are
+/// matched relative to the first of `roots` that contains them.
+pub fn keeps_finding(config: &AutoReviewConfig, roots: &[&Path], finding: &ReviewFinding) -> bool {
+    if ReviewSeverity::from_priority(finding.priority) < config.min_severity() {
+        return false;
+    }
+    let path = finding.code_location.absolute_file_path.as_path();
+    let root = roots
+        .iter()
+        .copied()
+        .find(|root| path.starts_with(root))
+        .unwrap_or(Path::new(""));
+    if !config.include.is_empty() && !matches_patch_glob(root, path, &config.include) {
+        return false;
+    }
+    !matches_patch_glob(root, path, &config.exclude)
+}
+
+/// Drops findings [`keeps_finding`] rejects and returns how many were dropped.
+pub fn gate_findings(
+    config: &AutoReviewConfig,
+    roots: &[&Path],
+    findings: &mut Vec<ReviewFinding>,
+) -> usize {
+    let before = findings.len();
+    findings.retain(|finding| keeps_finding(config, roots, finding));
+    before - findings.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::ReviewCodeLocation;
+    use crate::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn finding(path: &str, priority: i32) -> ReviewFinding {
+        ReviewFinding {
+            title: format!("[P{priority}] {path}"),
+            body: String::new(),
+            confidence_score: 0.9,
+            priority,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: PathBuf::from(path),
+                line_range: ReviewLineRange { start: 1, end: 1 },
+            },
+        }
+    }
+
+    #[test]
+    fn gate_drops_low_severity_and_out_of_scope_findings() {
+        let config = AutoReviewConfig {
+            include: vec!["src/**".to_string()],
+            exclude: vec!["*.snap".to_string()],
+            min_severity: Some(ReviewSeverity::Warning),
+            ..Default::default()
+        };
+        let mut findings = vec![
+            finding("/wt/src/lib.rs", 1),
+            finding("/wt/src/style.rs", 3),
+            finding("/wt/docs/guide.md", 0),
+            finding("/wt/src/snapshots/out.snap", 2),
+        ];
+        let dropped = gate_findings(&config, &[Path::new("/wt")], &mut findings);
+        assert_eq!(dropped, 3);
+        let kept: Vec<&str> = findings.iter().map(|f| f.title.as_str()).collect();
+        assert_eq!(kept, vec!["[P1] /wt/src/lib.rs"]);
+    }
+
+    #[test]
+    fn prompt_fills_template_and_appends_scope() {
+        let config = AutoReviewConfig {
+            prompt: Some("Check {commit} for data races.".to_string()),
+            exclude: vec!["vendor/**".to_string()],
+            min_severity: Some(ReviewSeverity::Error),
+            ..Default::default()
+        };
+        assert_eq!(
+            auto_review_prompt(&config, "abc123"),
+            "Check abc123 for data races.\n\nIgnore files matching: vendor/**.\n\nOnly report findings of priority P1 or more severe."
+        );
+        assert_eq!(
+            auto_review_prompt(&AutoReviewConfig::default(), "abc123"),
+            DEFAULT_AUTO_REVIEW_PROMPT.replace("{commit}", "abc123")
+        );
+    }
+}
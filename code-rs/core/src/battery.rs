@@ -0,0 +1,190 @@
+//! Battery and thermal state on Android (Termux).
+//!
+//! Readings come from `termux-battery-status` (Termux:API) and are cached
+//! briefly because each call round-trips through an Android service. Other
+//! platforms report no battery, so nothing is ever throttled there.
+//!
+//! Frontends call [`set_low_battery_threshold`] once config is loaded; until
+//! then [`DEFAULT_LOW_BATTERY_THRESHOLD`] applies.
+
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+use serde::Deserialize;
+
+/// Battery percentage below which background work is deferred while
+/// discharging. `0` disables battery throttling.
+pub const DEFAULT_LOW_BATTERY_THRESHOLD: u8 = 20;
+
+/// Battery temperature (°C) at which background work is deferred even when
+/// charging.
+const HOT_BATTERY_CELSIUS: f64 = 45.0;
+
+const STATUS_CACHE_TTL: Duration = Duration::from_secs(60);
+const STATUS_TIMEOUT: Duration = Duration::from_secs(5);
+
+static LOW_BATTERY_THRESHOLD: AtomicU8 = AtomicU8::new(DEFAULT_LOW_BATTERY_THRESHOLD);
+static STATUS_CACHE: Mutex<Option<(Instant, Option<BatteryStatus>)>> = Mutex::new(None);
+static REFRESH_PENDING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatteryStatus {
+    pub percentage: u8,
+    pub charging: bool,
+    pub temperature_celsius: Option<f64>,
+}
+
+/// Why background work should wait.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PowerConstraint {
+    LowBattery { percentage: u8 },
+    Hot { temperature_celsius: f64 },
+}
+
+impl std::fmt::Display for PowerConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PowerConstraint::LowBattery { percentage } => {
+                write!(f, "battery at {percentage}% and not charging")
+            }
+            PowerConstraint::Hot {
+                temperature_celsius,
+            } => write!(f, "battery temperature {temperature_celsius:.0}°C"),
+        }
+    }
+}
+
+pub fn set_low_battery_threshold(threshold: u8) {
+    LOW_BATTERY_THRESHOLD.store(threshold.min(100), Ordering::Relaxed);
+}
+
+/// Current battery reading, or `None` off Termux or when Termux:API is not
+/// installed.
+pub fn battery_status() -> Option<BatteryStatus> {
+    if !crate::env::is_termux() {
+        return None;
+    }
+    let mut cache = STATUS_CACHE.lock().ok()?;
+    if let Some((read_at, status)) = cache.as_ref()
+        && read_at.elapsed() < STATUS_CACHE_TTL
+    {
+        return *status;
+    }
+    let status = read_termux_battery_status();
+    *cache = Some((Instant::now(), status));
+    status
+}
+
+/// Returns the reason deferrable background work (housekeeping, session
+/// index refresh, auto review) should be skipped right now, if any.
+pub fn background_work_constraint() -> Option<PowerConstraint> {
+    constraint_for(
+        battery_status()?,
+        LOW_BATTERY_THRESHOLD.load(Ordering::Relaxed),
+    )
+}
+
+/// Like [`background_work_constraint`] but never blocks, for UI threads: it
+/// answers from the last reading and refreshes a stale one in the background.
+pub fn cached_background_work_constraint() -> Option<PowerConstraint> {
+    if !crate::env::is_termux() {
+        return None;
+    }
+    // A held lock means a reading is in flight; treat that as unconstrained.
+    let (fresh, status) = match STATUS_CACHE.try_lock() {
+        Ok(cache) => match cache.as_ref() {
+            Some((read_at, status)) => (read_at.elapsed() < STATUS_CACHE_TTL, *status),
+            None => (false, None),
+        },
+        Err(_) => (true, None),
+    };
+    if !fresh && !REFRESH_PENDING.swap(true, Ordering::AcqRel) {
+        let spawned = std::thread::Builder::new()
+            .name("battery-status".to_string())
+            .spawn(|| {
+                let _ = battery_status();
+                REFRESH_PENDING.store(false, Ordering::Release);
+            });
+        if spawned.is_err() {
+            REFRESH_PENDING.store(false, Ordering::Release);
+        }
+    }
+    constraint_for(status?, LOW_BATTERY_THRESHOLD.load(Ordering::Relaxed))
+}
+
+fn constraint_for(status: BatteryStatus, threshold: u8) -> Option<PowerConstraint> {
+    if let Some(temperature_celsius) = status.temperature_celsius
+        && temperature_celsius >= HOT_BATTERY_CELSIUS
+    {
+        return Some(PowerConstraint::Hot {
+            temperature_celsius,
+        });
+    }
+    (!status.charging && status.percentage < threshold).then_some(PowerConstraint::LowBattery {
+        percentage: status.percentage,
+    })
+}
+
+#[derive(Deserialize)]
+struct TermuxBatteryStatus {
+    percentage: u8,
+    #[serde(default)]
+    plugged: String,
+    #[serde(default)]
+    status: String,
+    temperature: Option<f64>,
+}
+
+fn parse_termux_battery_status(json: &[u8]) -> Option<BatteryStatus> {
+    let raw: TermuxBatteryStatus = serde_json::from_slice(json).ok()?;
+    let charging = raw.status.eq_ignore_ascii_case("CHARGING")
+        || raw.status.eq_ignore_ascii_case("FULL")
+        || (!raw.plugged.is_empty() && !raw.plugged.eq_ignore_ascii_case("UNPLUGGED"));
+    Some(BatteryStatus {
+        percentage: raw.percentage.min(100),
+        charging,
+        temperature_celsius: raw.temperature,
+    })
+}
+
+/// Runs `termux-battery-status`; see [`crate::env::termux_api_output`].
+fn read_termux_battery_status() -> Option<BatteryStatus> {
+    let output = crate::env::termux_api_output("termux-battery-status", STATUS_TIMEOUT)?;
+    parse_termux_battery_status(&output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_termux_output_and_applies_threshold() {
+        let discharging = parse_termux_battery_status(
+            br#"{"health":"GOOD","percentage":15,"plugged":"UNPLUGGED","status":"DISCHARGING","temperature":31.2,"current":-410000}"#,
+        )
+        .expect("parse");
+        assert!(!discharging.charging);
+        assert_eq!(
+            constraint_for(discharging, 20),
+            Some(PowerConstraint::LowBattery { percentage: 15 })
+        );
+        assert_eq!(constraint_for(discharging, 0), None);
+
+        let charging = parse_termux_battery_status(
+            br#"{"health":"GOOD","percentage":15,"plugged":"PLUGGED_AC","status":"CHARGING","temperature":46.0}"#,
+        )
+        .expect("parse");
+        assert!(charging.charging);
+        assert_eq!(
+            constraint_for(charging, 20),
+            Some(PowerConstraint::Hot {
+                temperature_celsius: 46.0
+            })
+        );
+    }
+}
@@ -25,6 +25,7 @@ use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::client_common::ResponseStream;
 use crate::client_common::replace_image_payloads_for_model;
+use crate::config_types::ModelOverrides;
 use crate::debug_logger::DebugLogger;
 use crate::error::CodexErr;
 use crate::error::Result;
@@ -42,6 +43,7 @@ pub(crate) struct ChatCompletionsRequest<'a> {
     pub(crate) prompt: &'a Prompt,
     pub(crate) model_family: &'a ModelFamily,
     pub(crate) model_slug: &'a str,
+    pub(crate) model_overrides: Option<&'a ModelOverrides>,
     pub(crate) client: &'a reqwest::Client,
     pub(crate) provider: &'a ModelProviderInfo,
     pub(crate) debug_logger: &'a Arc<Mutex<DebugLogger>>,
@@ -58,6 +60,7 @@ pub(crate) async fn stream_chat_completions(
         prompt,
         model_family,
         model_slug,
+        model_overrides,
         client,
         provider,
         debug_logger,
@@ -74,7 +77,10 @@ pub(crate) async fn stream_chat_completions(
     // Build messages array
     let mut messages = Vec::<serde_json::Value>::new();
 
-    let full_instructions = prompt.get_full_instructions(model_family);
+    let mut full_instructions = prompt.get_full_instructions(model_family);
+    if let Some(overrides) = model_overrides {
+        full_instructions = overrides.apply_instructions(full_instructions);
+    }
     messages.push(json!({"role": "system", "content": full_instructions}));
 
     let mut input = prompt.get_formatted_input();
@@ -310,6 +316,12 @@ pub(crate) async fn stream_chat_completions(
         "tools": tools_json,
     });
 
+    if let Some(stop) = provider.stop.as_ref().filter(|stop| !stop.is_empty())
+        && let Some(obj) = payload.as_object_mut()
+    {
+        obj.insert("stop".to_string(), json!(stop));
+    }
+
     if let Some(openrouter_cfg) = provider.openrouter_config()
         && let Some(obj) = payload.as_object_mut() {
             if let Some(provider_cfg) = &openrouter_cfg.provider {
@@ -326,6 +338,11 @@ pub(crate) async fn stream_chat_completions(
             }
         }
 
+    if let Some(overrides) = model_overrides {
+        overrides.apply_to_payload(&mut payload, "max_tokens");
+    }
+    prompt.apply_temperature_override(&mut payload);
+
     // If an Ollama context override is present, propagate it. Some Ollama
     // builds honor `num_ctx` directly in OpenAI-compatible Chat Completions,
     // and others accept it under an `options` object – include both.
@@ -352,7 +369,10 @@ pub(crate) async fn stream_chat_completions(
     loop {
         attempt += 1;
 
-        let auth = auth_manager.as_ref().and_then(|m| m.auth());
+        let auth = auth_manager
+            .as_ref()
+            .and_then(|m| m.auth())
+            .filter(|_| !provider.local);
         let mut req_builder = provider.create_request_builder(client, &auth).await?;
 
         if let Some(auth) = auth.as_ref()
@@ -469,11 +489,15 @@ pub(crate) async fn stream_chat_completions(
                     }
                     if is_connectivity {
                         let req_id = (!request_id.is_empty()).then(|| request_id.clone());
-                        return Err(CodexErr::Stream(
-                            format!("[transport] network unavailable: {e}"),
-                            None,
-                            req_id,
-                        ));
+                        let message = if provider.local {
+                            format!(
+                                "[transport] local model server at {} is not reachable: {e}",
+                                provider.base_url_for_probe()
+                            )
+                        } else {
+                            format!("[transport] network unavailable: {e}")
+                        };
+                        return Err(CodexErr::Stream(message, None, req_id));
                     }
                     return Err(e.into());
                 }
@@ -776,7 +800,11 @@ async fn process_chat_sse<S>(
             // Forward any reasoning/thinking deltas if present.
             // Some providers stream `reasoning` as a plain string while others
             // nest the text under an object (e.g. `{ "reasoning": { "text": "…" } }`).
-            if let Some(reasoning_val) = choice.get("delta").and_then(|d| d.get("reasoning")) {
+            // llama.cpp and vLLM use `reasoning_content` instead.
+            if let Some(reasoning_val) = choice
+                .get("delta")
+                .and_then(|d| d.get("reasoning").or_else(|| d.get("reasoning_content")))
+            {
                 let mut maybe_text = reasoning_val
                     .as_str()
                     .map(str::to_string)
@@ -878,59 +906,58 @@ async fn process_chat_sse<S>(
                 }
 
             // Emit end-of-turn when finish_reason signals completion.
-            if let Some(finish_reason) = choice.get("finish_reason").and_then(|v| v.as_str()) {
-                match finish_reason {
-                    "tool_calls" if fn_call_state.active => {
-                        // First, flush the terminal raw reasoning so UIs can finalize
-                        // the reasoning stream before any exec/tool events begin.
-                        if !reasoning_text.is_empty() {
-                            let item = ResponseItem::Reasoning {
-                                id: current_item_id.clone().unwrap_or_default(),
-                                summary: Vec::new(),
-                                content: Some(vec![ReasoningItemContent::ReasoningText {
-                                    text: std::mem::take(&mut reasoning_text),
-                                }]),
-                                encrypted_content: None,
-                            };
-                            let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone { item, sequence_number: None, output_index: None })).await;
-                        }
-
-                        // Then emit the FunctionCall response item.
-                        let item = ResponseItem::FunctionCall {
-                            id: current_item_id.clone(),
-                            name: fn_call_state.name.clone().unwrap_or_else(|| "".to_string()),
-                            arguments: fn_call_state.arguments.clone(),
-                            call_id: fn_call_state.call_id.clone().unwrap_or_else(String::new),
+            if choice.get("finish_reason").and_then(|v| v.as_str()).is_some() {
+                // Local servers do not agree on the reason: Ollama reports
+                // "stop" after tool calls, llama.cpp "length" or "eos" when it
+                // runs out of tokens. Emit whatever the stream built up.
+                if fn_call_state.active {
+                    // First, flush the terminal raw reasoning so UIs can finalize
+                    // the reasoning stream before any exec/tool events begin.
+                    if !reasoning_text.is_empty() {
+                        let item = ResponseItem::Reasoning {
+                            id: current_item_id.clone().unwrap_or_default(),
+                            summary: Vec::new(),
+                            content: Some(vec![ReasoningItemContent::ReasoningText {
+                                text: std::mem::take(&mut reasoning_text),
+                            }]),
+                            encrypted_content: None,
                         };
+                        let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone { item, sequence_number: None, output_index: None })).await;
+                    }
 
+                    // Then emit the FunctionCall response item.
+                    let item = ResponseItem::FunctionCall {
+                        id: current_item_id.clone(),
+                        name: fn_call_state.name.clone().unwrap_or_else(|| "".to_string()),
+                        arguments: fn_call_state.arguments.clone(),
+                        call_id: fn_call_state.call_id.clone().unwrap_or_else(String::new),
+                    };
+
+                    let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone { item, sequence_number: None, output_index: None })).await;
+                } else {
+                    // Regular turn without tool-call. Emit the final assistant message
+                    // as a single OutputItemDone so non-delta consumers see the result.
+                    if !assistant_text.is_empty() {
+                        let item = ResponseItem::Message {
+                            role: "assistant".to_string(),
+                            content: vec![ContentItem::OutputText {
+                                text: std::mem::take(&mut assistant_text),
+                            }],
+                            id: current_item_id.clone(), end_turn: None, phase: None};
                         let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone { item, sequence_number: None, output_index: None })).await;
                     }
-                    "stop" => {
-                        // Regular turn without tool-call. Emit the final assistant message
-                        // as a single OutputItemDone so non-delta consumers see the result.
-                        if !assistant_text.is_empty() {
-                            let item = ResponseItem::Message {
-                                role: "assistant".to_string(),
-                                content: vec![ContentItem::OutputText {
-                                    text: std::mem::take(&mut assistant_text),
-                                }],
-                                id: current_item_id.clone(), end_turn: None, phase: None};
-                            let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone { item, sequence_number: None, output_index: None })).await;
-                        }
-                        // Also emit a terminal Reasoning item so UIs can finalize raw reasoning.
-                        if !reasoning_text.is_empty() {
-                            let item = ResponseItem::Reasoning {
-                                id: current_item_id.clone().unwrap_or_default(),
-                                summary: Vec::new(),
-                                content: Some(vec![ReasoningItemContent::ReasoningText {
-                                    text: std::mem::take(&mut reasoning_text),
-                                }]),
-                                encrypted_content: None,
-                            };
-                            let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone { item, sequence_number: None, output_index: None })).await;
-                        }
+                    // Also emit a terminal Reasoning item so UIs can finalize raw reasoning.
+                    if !reasoning_text.is_empty() {
+                        let item = ResponseItem::Reasoning {
+                            id: current_item_id.clone().unwrap_or_default(),
+                            summary: Vec::new(),
+                            content: Some(vec![ReasoningItemContent::ReasoningText {
+                                text: std::mem::take(&mut reasoning_text),
+                            }]),
+                            encrypted_content: None,
+                        };
+                        let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone { item, sequence_number: None, output_index: None })).await;
                     }
-                    _ => {}
                 }
 
                 // Emit Completed regardless of reason so the agent can advance.
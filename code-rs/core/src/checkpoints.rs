@@ -0,0 +1,144 @@
+//! Commit checkpoints recorded after each applied patch (`[git] auto_checkpoint`).
+//!
+//! Every successful apply_patch snapshots the working tree into a commit on
+//! `refs/code/checkpoints/<session>`, chained onto the previous checkpoint of
+//! the session (the first one hangs off `HEAD`). Neither the branch, the index
+//! nor the working tree is touched, so the ref is a private undo history:
+//! `git log -p refs/code/checkpoints/<session>` shows each patch and
+//! `git restore --source <checkpoint> -- <path>` rolls a file back.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+use code_git_tooling::CreateGhostCommitOptions;
+use code_git_tooling::create_ghost_commit;
+use uuid::Uuid;
+
+const CHECKPOINT_REF_PREFIX: &str = "refs/code/checkpoints/";
+
+/// Ref holding the checkpoints of `session_id`.
+pub(crate) fn checkpoint_ref(session_id: Uuid) -> String {
+    format!("{CHECKPOINT_REF_PREFIX}{session_id}")
+}
+
+/// Commit message for the checkpoint of one apply_patch call, with the
+/// session, turn and call recorded as trailers.
+pub(crate) fn checkpoint_message(
+    session_id: Uuid,
+    sub_id: &str,
+    call_id: &str,
+    files: &[String],
+) -> String {
+    let subject = match files {
+        [] => "apply_patch".to_string(),
+        [file] => format!("apply_patch: {file}"),
+        [file, rest @ ..] => format!("apply_patch: {file} and {} more", rest.len()),
+    };
+    let mut message = format!("{subject}\n\n");
+    for file in files {
+        message.push_str(&format!("  {file}\n"));
+    }
+    if !files.is_empty() {
+        message.push('\n');
+    }
+    message.push_str(&format!(
+        "Code-Session: {session_id}\nCode-Turn: {sub_id}\nCode-Call: {call_id}\n"
+    ));
+    message
+}
+
+/// Snapshots the whole repository containing `cwd` onto the session's
+/// checkpoint ref. Returns the new checkpoint commit id, or `None` when `cwd`
+/// is not inside a git repository.
+pub(crate) fn record_checkpoint(
+    cwd: &Path,
+    session_id: Uuid,
+    message: &str,
+) -> Result<Option<String>, String> {
+    let Some(toplevel) = git_stdout(cwd, &["rev-parse", "--show-toplevel"]) else {
+        return Ok(None);
+    };
+    let repo = PathBuf::from(toplevel);
+    let reference = checkpoint_ref(session_id);
+    let previous = git_stdout(&repo, &["rev-parse", "--verify", "--quiet", &format!("{reference}^{{commit}}")]);
+
+    let mut options = CreateGhostCommitOptions::new(&repo).message(message);
+    if let Some(previous) = previous.as_deref() {
+        options = options.parent(previous);
+    }
+    let commit = create_ghost_commit(&options).map_err(|err| err.to_string())?;
+
+    let mut update = vec!["update-ref", "-m", "code checkpoint", reference.as_str(), commit.id()];
+    if let Some(previous) = previous.as_deref() {
+        update.push(previous);
+    }
+    let output = Command::new("git")
+        .current_dir(&repo)
+        .args(&update)
+        .output()
+        .map_err(|err| format!("failed to run git update-ref: {err}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git update-ref failed: {}", stderr.trim()));
+    }
+    Ok(Some(commit.id().to_string()))
+}
+
+fn git_stdout(cwd: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git").current_dir(cwd).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn git(repo: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .current_dir(repo)
+            .args(args)
+            .output()
+            .expect("git command");
+        assert!(output.status.success(), "git {args:?} failed");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn checkpoints_chain_on_a_private_ref() {
+        let temp = TempDir::new().expect("tempdir");
+        let repo = temp.path();
+        git(repo, &["init", "-q"]);
+        git(repo, &["config", "user.email", "test@example.com"]);
+        git(repo, &["config", "user.name", "Test User"]);
+        std::fs::write(repo.join("a.txt"), "one").expect("write");
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-qm", "init"]);
+        let head = git(repo, &["rev-parse", "HEAD"]);
+        let session_id = Uuid::new_v4();
+
+        std::fs::write(repo.join("a.txt"), "two").expect("write");
+        let message = checkpoint_message(session_id, "1", "call_1", &["a.txt".to_string()]);
+        let first = record_checkpoint(repo, session_id, &message)
+            .expect("first checkpoint")
+            .expect("inside a repository");
+        std::fs::write(repo.join("a.txt"), "three").expect("write");
+        let second = record_checkpoint(repo, session_id, "second")
+            .expect("second checkpoint")
+            .expect("inside a repository");
+
+        let reference = checkpoint_ref(session_id);
+        assert_eq!(git(repo, &["rev-parse", &reference]), second);
+        assert_eq!(git(repo, &["rev-parse", &format!("{second}^")]), first);
+        assert_eq!(git(repo, &["rev-parse", &format!("{first}^")]), head);
+        assert_eq!(git(repo, &["show", &format!("{first}:a.txt")]), "two");
+        assert_eq!(git(repo, &["rev-parse", "HEAD"]), head);
+        assert!(git(repo, &["log", "-1", "--format=%B", &first]).contains("Code-Call: call_1"));
+    }
+}
@@ -320,6 +320,14 @@ impl ModelClient {
         &self.config
     }
 
+    /// A copy of this client that sends its requests to `provider`.
+    pub(crate) fn with_provider(&self, provider: ModelProviderInfo) -> Self {
+        Self {
+            provider,
+            ..self.clone()
+        }
+    }
+
     /// Get the reasoning effort configuration
     pub fn get_reasoning_effort(&self) -> ReasoningEffortConfig {
         self.effort
@@ -343,11 +351,12 @@ impl ModelClient {
             return None;
         }
 
-        create_reasoning_param_for_request(
-            family,
-            Some(effort),
-            self.summary,
-        )
+        let summary = if self.provider.supports_reasoning_summaries() {
+            self.summary
+        } else {
+            ReasoningSummaryConfig::None
+        };
+        create_reasoning_param_for_request(family, Some(effort), summary)
     }
 
     fn disable_reasoning_summary(&self) {
@@ -417,6 +426,11 @@ impl ModelClient {
         tools_config.web_search_allowed_domains = self.config.tools_web_search_allowed_domains.clone();
         tools_config.web_search_external = self.config.tools_web_search_external;
         tools_config.search_tool = self.config.tools_search_tool;
+        tools_config.fetch_url = self.config.tools_fetch_url;
+        tools_config.fetch_url_allowed_domains =
+            self.config.tools_fetch_url_allowed_domains.clone();
+        tools_config.artifacts = self.config.tools_artifacts;
+        tools_config.run_tests = self.config.tools_run_tests;
 
         let auth_mode = self
             .auth_manager
@@ -555,6 +569,7 @@ impl ModelClient {
                     prompt,
                     model_family: effective_family,
                     model_slug,
+                    model_overrides: self.config.model_overrides_for(model_slug),
                     client: &self.client,
                     provider: &self.provider,
                     debug_logger: &self.debug_logger,
@@ -618,7 +633,11 @@ impl ModelClient {
             .or_else(|| find_family_for_model(request_model))
             .unwrap_or_else(|| self.config.model_family.clone());
 
-        let full_instructions = prompt.get_full_instructions(&request_family);
+        let model_overrides = self.config.model_overrides_for(request_model);
+        let mut full_instructions = prompt.get_full_instructions(&request_family);
+        if let Some(overrides) = model_overrides {
+            full_instructions = overrides.apply_instructions(full_instructions);
+        }
         let mut tools_json = create_tools_json_for_responses_api(&prompt.tools)?;
         if matches!(effective_effort, ReasoningEffortConfig::Minimal) {
             tools_json.retain(|tool| {
@@ -700,6 +719,10 @@ impl ModelClient {
             if self.provider.is_azure_responses_endpoint() {
                 attach_item_ids(&mut payload_json, &input_with_instructions);
             }
+            if let Some(overrides) = model_overrides {
+                overrides.apply_to_payload(&mut payload_json, "max_output_tokens");
+            }
+            prompt.apply_temperature_override(&mut payload_json);
             if let Some(openrouter_cfg) = self.provider.openrouter_config()
                 && let Some(obj) = payload_json.as_object_mut() {
                     if let Some(provider) = &openrouter_cfg.provider {
@@ -713,7 +736,10 @@ impl ModelClient {
                     }
                 }
 
-            let auth = auth_manager.as_ref().and_then(|m| m.auth());
+            let auth = auth_manager
+                .as_ref()
+                .and_then(|m| m.auth())
+                .filter(|_| !self.provider.local);
             let endpoint = self.provider.get_full_url(&auth);
 
             let url = reqwest::Url::parse(&endpoint).map_err(|err| {
@@ -1016,7 +1042,11 @@ impl ModelClient {
             .or_else(|| find_family_for_model(request_model))
             .unwrap_or_else(|| self.config.model_family.clone());
 
-        let full_instructions = prompt.get_full_instructions(&request_family);
+        let model_overrides = self.config.model_overrides_for(request_model);
+        let mut full_instructions = prompt.get_full_instructions(&request_family);
+        if let Some(overrides) = model_overrides {
+            full_instructions = overrides.apply_instructions(full_instructions);
+        }
         let mut tools_json = create_tools_json_for_responses_api(&prompt.tools)?;
         if matches!(effective_effort, ReasoningEffortConfig::Minimal) {
             tools_json.retain(|tool| {
@@ -1126,6 +1156,10 @@ impl ModelClient {
             if azure_workaround {
                 attach_item_ids(&mut payload_json, &input_with_instructions);
             }
+            if let Some(overrides) = model_overrides {
+                overrides.apply_to_payload(&mut payload_json, "max_output_tokens");
+            }
+            prompt.apply_temperature_override(&mut payload_json);
             if let Some(openrouter_cfg) = self.provider.openrouter_config()
                 && let Some(obj) = payload_json.as_object_mut() {
                     if let Some(provider) = &openrouter_cfg.provider {
@@ -1146,7 +1180,10 @@ impl ModelClient {
             let mut auth_refresh_error: Option<RefreshTokenError> = None;
 
             // Always fetch the latest auth in case a prior attempt refreshed the token.
-            let auth = auth_manager.as_ref().and_then(|m| m.auth());
+            let auth = auth_manager
+                .as_ref()
+                .and_then(|m| m.auth())
+                .filter(|_| !self.provider.local);
 
             trace!(
                 "POST to {}: {}",
@@ -1763,7 +1800,10 @@ impl ModelClient {
         let mut request_id = String::new();
 
         loop {
-            let auth = auth_manager.as_ref().and_then(|m| m.auth());
+            let auth = auth_manager
+                .as_ref()
+                .and_then(|m| m.auth())
+                .filter(|_| !self.provider.local);
             let mut request = self
                 .provider
                 .create_compact_request_builder(&self.client, &auth)
@@ -2934,6 +2974,9 @@ mod tests {
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
 
         let client = reqwest::Client::builder()
@@ -2982,6 +3025,9 @@ mod tests {
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
 
         let client = reqwest::Client::builder()
@@ -3032,6 +3078,9 @@ mod tests {
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
 
         let client = reqwest::Client::builder()
@@ -3179,6 +3228,9 @@ mod tests {
             stream_idle_timeout_ms: Some(1000),
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
 
         let events = collect_events(
@@ -3245,6 +3297,9 @@ mod tests {
             stream_idle_timeout_ms: Some(1000),
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
 
         let events = collect_events(&[sse1.as_bytes()], provider).await;
@@ -3297,6 +3352,9 @@ mod tests {
             stream_idle_timeout_ms: Some(1000),
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
 
         let events = collect_events(&[sse1.as_bytes()], provider).await;
@@ -3392,6 +3450,9 @@ mod tests {
             stream_idle_timeout_ms: Some(1000),
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
 
         let events = collect_events(&[sse1.as_bytes()], provider).await;
@@ -3499,6 +3560,9 @@ mod tests {
                 stream_idle_timeout_ms: Some(1000),
                 requires_openai_auth: false,
                 openrouter: None,
+                local: false,
+                supports_reasoning_summaries: None,
+                stop: None,
             };
 
             let out = run_sse(evs, provider).await;
@@ -3739,6 +3803,9 @@ mod tests {
             stream_idle_timeout_ms: Some(1000),
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
 
         let events = collect_events(&[sse1.as_bytes()], provider).await;
@@ -3770,6 +3837,9 @@ mod tests {
             stream_idle_timeout_ms: Some(1000),
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
 
         let events = collect_events(&[sse1.as_bytes()], provider).await;
@@ -3804,6 +3874,9 @@ mod tests {
             stream_idle_timeout_ms: Some(1000),
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
 
         let events = collect_events(&[sse1.as_bytes()], provider).await;
@@ -3835,6 +3908,9 @@ mod tests {
             stream_idle_timeout_ms: Some(1000),
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
 
         let events = collect_events(&[sse1.as_bytes()], provider).await;
@@ -3866,6 +3942,9 @@ mod tests {
             stream_idle_timeout_ms: Some(1000),
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
 
         let events = collect_events(&[sse1.as_bytes()], provider).await;
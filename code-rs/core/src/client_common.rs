@@ -83,6 +83,9 @@ pub struct Prompt {
     pub model_family_override: Option<ModelFamily>,
     /// Optional the output schema for the model's response.
     pub output_schema: Option<Value>,
+    /// Optional sampling temperature for this request, taking precedence over
+    /// `[models.<slug>] temperature`.
+    pub temperature_override: Option<f64>,
     /// Optional tag used to route debug logs into helper-specific directories.
     pub log_tag: Option<String>,
     /// Optional override for session/conversation identifiers used for caching.
@@ -108,6 +111,7 @@ impl Default for Prompt {
             model_override: None,
             model_family_override: None,
             output_schema: None,
+            temperature_override: None,
             log_tag: None,
             session_id_override: None,
             model_descriptions: None,
@@ -116,6 +120,16 @@ impl Default for Prompt {
 }
 
 impl Prompt {
+    /// Writes `temperature_override` into a request payload. Applied after
+    /// the per-model overrides so a retry's temperature wins.
+    pub(crate) fn apply_temperature_override(&self, payload: &mut Value) {
+        if let Some(temperature) = self.temperature_override
+            && let Some(obj) = payload.as_object_mut()
+        {
+            obj.insert("temperature".to_string(), serde_json::json!(temperature));
+        }
+    }
+
     pub(crate) fn get_full_instructions<'a>(&'a self, model: &'a ModelFamily) -> Cow<'a, str> {
         let effective_model = self.model_family_override.as_ref().unwrap_or(model);
         Cow::Borrowed(
@@ -2,6 +2,7 @@
 #![allow(clippy::unwrap_used)]
 
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
@@ -55,6 +56,7 @@ use serde::Deserialize;
 use serde_json::json;
 use tokio::sync::oneshot;
 use tokio::task::AbortHandle;
+use tracing::Instrument;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
@@ -68,6 +70,9 @@ use crate::agent_tool::AgentStatusUpdatePayload;
 use crate::remote_models::RemoteModelsManager;
 use crate::split_command_and_args;
 use crate::git_worktree;
+use crate::patch_format;
+use crate::script_hooks::ScriptHookPoint;
+use crate::script_hooks::ScriptHookVerdict;
 use crate::protocol::ApprovedCommandMatchKind;
 use crate::protocol::WebSearchBeginEvent;
 use crate::protocol::WebSearchCompleteEvent;
@@ -88,9 +93,12 @@ use chrono::Utc;
 
 pub mod compact;
 pub mod compact_remote;
+mod cost_budget;
+mod dlp_gate;
 mod events;
 mod exec;
 mod mcp_access;
+mod retry;
 mod session;
 mod streaming;
 
@@ -260,7 +268,13 @@ pub(crate) struct TurnContext {
     pub(crate) shell_environment_policy: ShellEnvironmentPolicy,
     pub(crate) collaboration_mode: crate::protocol::CollaborationModeKind,
     pub(crate) is_review_mode: bool,
+    /// Specialized passes a review turn runs after its main pass.
+    pub(crate) review_passes: Vec<ReviewPassConfig>,
     pub(crate) text_format_override: Option<TextFormat>,
+    /// Model for this turn only, from [`Op::SetNextTurnModel`].
+    pub(crate) model_override: Option<String>,
+    /// Sampling temperature for this turn only, from [`Op::RetryLastTurn`].
+    pub(crate) temperature_override: Option<f64>,
     pub(crate) final_output_json_schema: Option<Value>,
 }
 
@@ -679,7 +693,8 @@ async fn build_turn_status_items_v2(sess: &Session) -> Vec<ResponseItem> {
         Some(sess.approval_policy),
         Some(sess.sandbox_policy.clone()),
         Some(sess.user_shell.clone()),
-    );
+    )
+    .with_providers(sess.environment_providers.facts(&sess.cwd));
 
     if let Some(mut env_items) = sess.maybe_emit_env_ctx_messages(
         &env_context,
@@ -726,6 +741,8 @@ async fn build_turn_status_items_v2(sess: &Session) -> Vec<ResponseItem> {
             let (viewport_width, viewport_height) = browser_manager.get_viewport_size().await;
             let cursor_position = browser_manager.get_cursor_position().await.ok();
 
+            let tabs = browser_manager.list_tabs().await.unwrap_or_default();
+
             let mut metadata = HashMap::new();
             metadata.insert("browser_type".to_string(), browser_type.clone());
             if let Some((x, y)) = cursor_position {
@@ -774,15 +791,18 @@ async fn build_turn_status_items_v2(sess: &Session) -> Vec<ResponseItem> {
                 if !metadata.is_empty() {
                     snapshot.metadata = Some(metadata);
                 }
+                if tabs.len() > 1 {
+                    snapshot.tabs = tabs;
+                }
 
                 match snapshot.to_response_item_with_id(Some(&browser_stream_id)) {
                     Ok(item) => items.push(item),
                     Err(err) => warn!("env_ctx_v2: failed to serialize browser_snapshot JSON: {err}"),
                 }
 
-                if *crate::flags::CTX_UI {
-                    sess.emit_browser_snapshot_event(&browser_stream_id, &snapshot);
-                }
+                // Always sent: besides the context UI, the TUI's browser
+                // overlay builds its screenshot timeline from these events.
+                sess.emit_browser_snapshot_event(&browser_stream_id, &snapshot, &path);
 
                 match std::fs::read(&path) {
                     Ok(bytes) => {
@@ -845,6 +865,8 @@ use crate::agent_tool::normalize_agent_name;
 use crate::agent_tool::RunAgentParams;
 use crate::agent_tool::WaitForAgentParams;
 use crate::apply_patch::convert_apply_patch_to_protocol;
+use crate::apply_patch::protected_paths_note;
+use crate::apply_patch::strip_protected_changes;
 use crate::apply_patch::get_writable_roots;
 use crate::apply_patch::{self, ApplyPatchResult};
 use crate::bridge_client::{
@@ -869,6 +891,7 @@ use crate::timeboxed_exec_guidance::{
     AUTO_EXEC_TIMEBOXED_REVIEW_GUIDANCE,
 };
 use crate::config_types::ProjectHookEvent;
+use crate::config_types::ReviewPassConfig;
 use crate::config_types::ShellEnvironmentPolicy;
 use crate::conversation_history::ConversationHistory;
 use crate::error::{CodexErr, RetryAfter};
@@ -1133,6 +1156,8 @@ mod tests {
             shell: None,
             git_branch: Some("main".to_string()),
             reasoning_effort: None,
+            shell_session: None,
+            providers: BTreeMap::new(),
         }
     }
 
@@ -1,4 +1,5 @@
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use super::streaming::AgentTask;
@@ -224,6 +225,8 @@ pub(super) async fn perform_compaction(
     // Convert core InputItem -> ResponseInputItem using the same logic as the main turn flow
     let initial_input_for_turn: ResponseInputItem = response_input_from_core_items(input);
     let mut turn_input = sess.turn_input_with_history(vec![initial_input_for_turn.clone().into()]);
+    let compacted_messages =
+        count_compacted_messages(&turn_input[..turn_input.len().saturating_sub(1)]);
 
     turn_input = sanitize_items_for_compact(turn_input);
 
@@ -361,7 +364,7 @@ pub(super) async fn perform_compaction(
     // state bookkeeping stays centralized.
     sess.replace_history(new_history);
 
-    send_compaction_checkpoint_warning(&sess, &sub_id).await;
+    send_compaction_checkpoint_warning(&sess, &sub_id, compacted_messages).await;
 
     let rollout_item = RolloutItem::Compacted(CompactedItem {
         message: summary_text.clone(),
@@ -394,6 +397,8 @@ async fn run_compact_task_inner_inline(
     // Convert core InputItem -> ResponseInputItem and build prompt
     let initial_input_for_turn: ResponseInputItem = response_input_from_core_items(input);
     let mut turn_input = sess.turn_input_with_history(vec![initial_input_for_turn.clone().into()]);
+    let compacted_messages =
+        count_compacted_messages(&turn_input[..turn_input.len().saturating_sub(1)]);
 
     turn_input = sanitize_items_for_compact(turn_input);
 
@@ -529,7 +534,7 @@ async fn run_compact_task_inner_inline(
         state.token_usage_info = None;
     }
 
-    send_compaction_checkpoint_warning(&sess, &sub_id).await;
+    send_compaction_checkpoint_warning(&sess, &sub_id, compacted_messages).await;
 
     let rollout_item = RolloutItem::Compacted(CompactedItem {
         message: summary_text.clone(),
@@ -748,17 +753,49 @@ pub fn prune_orphan_tool_outputs(items: &mut Vec<ResponseItem>) -> usize {
     removed
 }
 
-fn compaction_checkpoint_warning_event() -> EventMsg {
+fn compaction_checkpoint_warning_event(
+    compacted_messages: Option<u32>,
+    rollout_path: Option<PathBuf>,
+) -> EventMsg {
     EventMsg::CompactionCheckpointWarning(CompactionCheckpointWarningEvent {
         message: COMPACTION_CHECKPOINT_MESSAGE.to_string(),
+        compacted_messages,
+        rollout_path,
     })
 }
 
-pub(super) async fn send_compaction_checkpoint_warning(sess: &Arc<Session>, sub_id: &str) {
-    let event = sess.make_event(sub_id, compaction_checkpoint_warning_event());
+/// Announces the compaction with how many messages it folded away and the
+/// rollout that keeps them.
+pub(super) async fn send_compaction_checkpoint_warning(
+    sess: &Arc<Session>,
+    sub_id: &str,
+    compacted_messages: u32,
+) {
+    let rollout_path = sess.clone_rollout_recorder().map(|recorder| recorder.rollout_path);
+    let event = sess.make_event(
+        sub_id,
+        compaction_checkpoint_warning_event(Some(compacted_messages), rollout_path),
+    );
     sess.send_event(event).await;
 }
 
+/// User and assistant messages of `items` that a compaction folds into its
+/// summary. The session prefix is rebuilt rather than summarized, so it does
+/// not count.
+pub(crate) fn count_compacted_messages(items: &[ResponseItem]) -> u32 {
+    let count = items
+        .iter()
+        .filter(|item| match item {
+            ResponseItem::Message { role, content, .. } => {
+                (role == "user" || role == "assistant")
+                    && !content_items_to_text(content).is_some_and(|text| is_session_prefix_message(&text))
+            }
+            _ => false,
+        })
+        .count();
+    u32::try_from(count).unwrap_or(u32::MAX)
+}
+
 #[cfg(test)]
 pub(crate) fn collect_user_messages(items: &[ResponseItem]) -> Vec<String> {
     collect_compaction_snippets(items)
@@ -1143,9 +1180,10 @@ mod tests {
 
     #[test]
     fn compaction_checkpoint_warning_event_has_copy() {
-        match compaction_checkpoint_warning_event() {
+        match compaction_checkpoint_warning_event(Some(34), None) {
             EventMsg::CompactionCheckpointWarning(payload) => {
                 assert!(payload.message.contains("checkpoint"));
+                assert_eq!(payload.compacted_messages, Some(34));
             }
             other => panic!("unexpected variant: {other:?}"),
         }
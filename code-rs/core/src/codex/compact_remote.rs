@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use super::compact::{
+    count_compacted_messages,
     is_context_overflow_error,
     prune_orphan_tool_outputs,
     response_input_from_core_items,
@@ -77,6 +78,7 @@ async fn run_remote_compact_task_inner(
     sub_id: &str,
     extra_input: Vec<InputItem>,
 ) -> CodexResult<Vec<ResponseItem>> {
+    let extra_items = usize::from(!extra_input.is_empty());
     let mut turn_items = sess.turn_input_with_history({
         if extra_input.is_empty() {
             Vec::new()
@@ -86,6 +88,8 @@ async fn run_remote_compact_task_inner(
         }
     });
 
+    let compacted_messages =
+        count_compacted_messages(&turn_items[..turn_items.len().saturating_sub(extra_items)]);
     turn_items = sanitize_items_for_compact(turn_items);
     let mut truncated_count = 0usize;
     let max_retries = turn_context.client.get_provider().stream_max_retries();
@@ -169,7 +173,7 @@ async fn run_remote_compact_task_inner(
         state.token_usage_info = None;
     }
 
-    send_compaction_checkpoint_warning(sess, sub_id).await;
+    send_compaction_checkpoint_warning(sess, sub_id, compacted_messages).await;
 
     let rollout_item = RolloutItem::Compacted(CompactedItem {
         message: "Conversation history compacted.".to_string(),
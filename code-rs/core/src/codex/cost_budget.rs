@@ -0,0 +1,160 @@
+use super::*;
+
+const BUDGET_QUESTION_ID: &str = "session_cost_budget";
+const STOP_LABEL: &str = "Stop here";
+
+impl Session {
+    /// Budget in effect: the one raised from the prompt, else `[limits]`.
+    fn session_cost_budget(&self) -> Option<f64> {
+        let state = self.state.lock().unwrap();
+        state.session_cost_budget.or(self.max_session_cost)
+    }
+}
+
+/// Checks the estimated session cost against `[limits] max_session_cost`
+/// before the next model request, assuming it costs as much as the last one.
+///
+/// When the request would run past the budget the user is asked to raise it
+/// or stop; sessions that never prompt for approval stop outright. Returns
+/// `false` when the turn should end.
+pub(super) async fn confirm_session_cost_budget(
+    sess: &Arc<Session>,
+    turn_context: &TurnContext,
+    sub_id: &str,
+) -> bool {
+    use code_protocol::request_user_input::RequestUserInputEvent;
+
+    let Some(budget) = sess.session_cost_budget() else {
+        return true;
+    };
+    let (spent, next_estimate) = {
+        let state = sess.state.lock().unwrap();
+        (state.session_cost_usd, state.last_request_cost_usd)
+    };
+    let Some(spent) = spent else {
+        return true;
+    };
+    if spent + next_estimate <= budget {
+        return true;
+    }
+
+    let attempt_req = sess.current_request_ordinal();
+    let summary = format!(
+        "Estimated session cost ${spent:.2} is about to pass the ${budget:.2} budget (`limits.max_session_cost`)."
+    );
+    if turn_context.approval_policy == AskForApproval::Never {
+        let order = sess.next_background_order(sub_id, attempt_req, None);
+        sess.notify_background_event_with_order(sub_id, order, format!("{summary} Stopping the turn."))
+            .await;
+        return false;
+    }
+
+    let increment = sess.max_session_cost.unwrap_or(budget).max(next_estimate);
+    let raised = budget + increment;
+    let rx_response = match sess.register_pending_user_input(sub_id.to_string()) {
+        Ok(rx) => rx,
+        Err(err) => {
+            tracing::warn!("cannot prompt for the session cost budget: {err}");
+            return false;
+        }
+    };
+    let order = sess.next_background_order(sub_id, attempt_req, None);
+    let event = sess.make_event_with_order(
+        sub_id,
+        EventMsg::RequestUserInput(RequestUserInputEvent {
+            call_id: format!("{BUDGET_QUESTION_ID}:{sub_id}"),
+            turn_id: sub_id.to_string(),
+            questions: vec![budget_question(summary, raised)],
+        }),
+        order,
+        None,
+    );
+    sess.send_event(event).await;
+
+    let Ok(response) = rx_response.await else {
+        return false;
+    };
+    let answer = response
+        .answers
+        .get(BUDGET_QUESTION_ID)
+        .and_then(|answer| answer.answers.first())
+        .map(|value| value.trim().to_string())
+        .unwrap_or_default();
+    let Some(new_budget) = parse_budget_answer(&answer, raised).filter(|new_budget| *new_budget > spent) else {
+        let order = sess.next_background_order(sub_id, attempt_req, None);
+        sess.notify_background_event_with_order(
+            sub_id,
+            order,
+            format!("Stopped at an estimated ${spent:.2} to stay within the session budget."),
+        )
+        .await;
+        return false;
+    };
+
+    sess.state.lock().unwrap().session_cost_budget = Some(new_budget);
+    let order = sess.next_background_order(sub_id, attempt_req, None);
+    sess.notify_background_event_with_order(
+        sub_id,
+        order,
+        format!("Session cost budget raised to ${new_budget:.2}."),
+    )
+    .await;
+    true
+}
+
+fn budget_question(
+    question: String,
+    raised: f64,
+) -> code_protocol::request_user_input::RequestUserInputQuestion {
+    use code_protocol::request_user_input::RequestUserInputQuestion;
+    use code_protocol::request_user_input::RequestUserInputQuestionOption;
+
+    RequestUserInputQuestion {
+        id: BUDGET_QUESTION_ID.to_string(),
+        header: "Cost budget".to_string(),
+        question: format!("{question}\n\nContinue with a higher budget, or type a new amount in USD."),
+        is_other: true,
+        is_secret: false,
+        options: Some(vec![
+            RequestUserInputQuestionOption {
+                label: format!("Raise budget to ${raised:.2}"),
+                description: "Continue this turn with the higher budget.".to_string(),
+            },
+            RequestUserInputQuestionOption {
+                label: STOP_LABEL.to_string(),
+                description: "End the turn; the next message starts a new one.".to_string(),
+            },
+        ]),
+    }
+}
+
+/// New budget picked in the prompt: the offered raise or a typed amount.
+fn parse_budget_answer(answer: &str, raised: f64) -> Option<f64> {
+    if answer.starts_with("Raise budget") {
+        return Some(raised);
+    }
+    if answer == STOP_LABEL {
+        return None;
+    }
+    answer
+        .trim_start_matches('$')
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|amount| amount.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn budget_answers_accept_the_offer_or_a_typed_amount() {
+        assert_eq!(parse_budget_answer("Raise budget to $4.00", 4.0), Some(4.0));
+        assert_eq!(parse_budget_answer("$7.50", 4.0), Some(7.5));
+        assert_eq!(parse_budget_answer("12", 4.0), Some(12.0));
+        assert_eq!(parse_budget_answer(STOP_LABEL, 4.0), None);
+        assert_eq!(parse_budget_answer("keep going", 4.0), None);
+    }
+}
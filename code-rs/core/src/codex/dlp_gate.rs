@@ -0,0 +1,239 @@
+use super::*;
+use crate::config_types::DlpAction;
+use crate::dlp::DlpViolation;
+
+const DLP_QUESTION_ID: &str = "dlp_outbound_content";
+const SEND_LABEL: &str = "Send once";
+const STOP_LABEL: &str = "Don't send";
+
+/// Checks the user's message, including any `@path` attachments, against
+/// `[[dlp.rules]]` before it is recorded and sent. Returns `false` when the
+/// turn should end without sending it.
+pub(super) async fn gate_user_input(
+    sess: &Arc<Session>,
+    turn_context: &TurnContext,
+    sub_id: &str,
+    input: &[InputItem],
+) -> bool {
+    if sess.dlp.is_empty() {
+        return true;
+    }
+    let violations: Vec<DlpViolation> = input
+        .iter()
+        .filter_map(|item| match item {
+            InputItem::Text { text } => sess.dlp.check(text, &[]),
+            _ => None,
+        })
+        .collect();
+    confirm_outbound_content(sess, turn_context, sub_id, "your message", &violations).await
+}
+
+/// Drops messages typed while the turn runs whose content breaks a rule the
+/// user does not confirm.
+pub(super) async fn gate_queued_messages(
+    sess: &Arc<Session>,
+    turn_context: &TurnContext,
+    sub_id: &str,
+    pending_input: &mut Vec<ResponseItem>,
+) {
+    if sess.dlp.is_empty() {
+        return;
+    }
+    let mut kept = Vec::with_capacity(pending_input.len());
+    for item in pending_input.drain(..) {
+        let violation = match &item {
+            ResponseItem::Message { role, content, .. } if role == "user" => {
+                content.iter().find_map(|content| match content {
+                    ContentItem::InputText { text } => sess.dlp.check(text, &[]),
+                    _ => None,
+                })
+            }
+            _ => None,
+        };
+        let allowed = match violation {
+            Some(violation) => {
+                confirm_outbound_content(sess, turn_context, sub_id, "a queued message", &[violation])
+                    .await
+            }
+            None => true,
+        };
+        if allowed {
+            kept.push(item);
+        }
+    }
+    *pending_input = kept;
+}
+
+/// Checks tool outputs (command output, file reads, browser snapshots)
+/// before they are recorded and sent. When the send is not allowed, each
+/// offending output is replaced with a note saying it was withheld and
+/// `true` is returned so the caller ends the turn.
+pub(super) async fn withhold_tool_outputs(
+    sess: &Arc<Session>,
+    turn_context: &TurnContext,
+    sub_id: &str,
+    items: &mut [ResponseItem],
+) -> bool {
+    if sess.dlp.is_empty() {
+        return false;
+    }
+    let mut read_paths: HashMap<String, Vec<String>> = HashMap::new();
+    let mut flagged: Vec<(usize, DlpViolation)> = Vec::new();
+    for (idx, item) in items.iter().enumerate() {
+        match item {
+            ResponseItem::LocalShellCall {
+                call_id: Some(call_id),
+                action: LocalShellAction::Exec(action),
+                ..
+            } => {
+                read_paths.insert(call_id.clone(), crate::dlp::read_paths(&action.command));
+            }
+            ResponseItem::FunctionCall { name, arguments, call_id, .. }
+                if matches!(name.as_str(), "shell" | "container.exec") =>
+            {
+                if let Ok(params) = serde_json::from_str::<ShellToolCallParams>(arguments) {
+                    read_paths.insert(call_id.clone(), crate::dlp::read_paths(&params.command));
+                }
+            }
+            ResponseItem::FunctionCallOutput { call_id, output } => {
+                let text = output.body.to_text().unwrap_or_default();
+                let paths = read_paths.get(call_id).map(Vec::as_slice).unwrap_or_default();
+                if let Some(violation) = sess.dlp.check(&text, paths) {
+                    flagged.push((idx, violation));
+                }
+            }
+            ResponseItem::CustomToolCallOutput { output, .. } => {
+                if let Some(violation) = sess.dlp.check(output, &[]) {
+                    flagged.push((idx, violation));
+                }
+            }
+            _ => {}
+        }
+    }
+    if flagged.is_empty() {
+        return false;
+    }
+
+    let violations: Vec<DlpViolation> = flagged.iter().map(|(_, violation)| violation.clone()).collect();
+    if confirm_outbound_content(sess, turn_context, sub_id, "tool output", &violations).await {
+        return false;
+    }
+    for (idx, violation) in flagged {
+        let note = format!("Output withheld from the model: {}.", violation.describe());
+        match &mut items[idx] {
+            ResponseItem::FunctionCallOutput { output, .. } => {
+                *output = FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(note),
+                    success: Some(false),
+                };
+            }
+            ResponseItem::CustomToolCallOutput { output, .. } => *output = note,
+            _ => {}
+        }
+    }
+    true
+}
+
+/// Decides whether content that broke `violations` may be sent. Blocking
+/// rules, and any rule in a session that never prompts, refuse with an
+/// explanation; otherwise the user is asked once. Returns `true` when the
+/// content may be sent.
+async fn confirm_outbound_content(
+    sess: &Arc<Session>,
+    turn_context: &TurnContext,
+    sub_id: &str,
+    source: &str,
+    violations: &[DlpViolation],
+) -> bool {
+    use code_protocol::request_user_input::RequestUserInputEvent;
+
+    let Some(first) = violations.first() else {
+        return true;
+    };
+    let attempt_req = sess.current_request_ordinal();
+    let blocking = violations
+        .iter()
+        .find(|violation| violation.action == DlpAction::Block);
+    if let Some(violation) = blocking.or((turn_context.approval_policy == AskForApproval::Never).then_some(first)) {
+        let order = sess.next_background_order(sub_id, attempt_req, None);
+        sess.notify_background_event_with_order(
+            sub_id,
+            order,
+            format!(
+                "Not sending {source} to the model: {}.",
+                violation.describe()
+            ),
+        )
+        .await;
+        return false;
+    }
+
+    let rx_response = match sess.register_pending_user_input(sub_id.to_string()) {
+        Ok(rx) => rx,
+        Err(err) => {
+            tracing::warn!("cannot prompt for DLP confirmation: {err}");
+            return false;
+        }
+    };
+    let reasons: Vec<String> = violations.iter().map(DlpViolation::describe).collect();
+    let order = sess.next_background_order(sub_id, attempt_req, None);
+    let event = sess.make_event_with_order(
+        sub_id,
+        EventMsg::RequestUserInput(RequestUserInputEvent {
+            call_id: format!("{DLP_QUESTION_ID}:{sub_id}"),
+            turn_id: sub_id.to_string(),
+            questions: vec![dlp_question(source, &reasons)],
+        }),
+        order,
+        None,
+    );
+    sess.send_event(event).await;
+
+    let Ok(response) = rx_response.await else {
+        return false;
+    };
+    let send = response
+        .answers
+        .get(DLP_QUESTION_ID)
+        .and_then(|answer| answer.answers.first())
+        .is_some_and(|value| value.trim() == SEND_LABEL);
+    if !send {
+        let order = sess.next_background_order(sub_id, attempt_req, None);
+        sess.notify_background_event_with_order(
+            sub_id,
+            order,
+            format!("Did not send {source} to the model."),
+        )
+        .await;
+    }
+    send
+}
+
+fn dlp_question(
+    source: &str,
+    reasons: &[String],
+) -> code_protocol::request_user_input::RequestUserInputQuestion {
+    use code_protocol::request_user_input::RequestUserInputQuestion;
+    use code_protocol::request_user_input::RequestUserInputQuestionOption;
+
+    RequestUserInputQuestion {
+        id: DLP_QUESTION_ID.to_string(),
+        header: "Outbound content".to_string(),
+        question: format!(
+            "{}.\n\nSend {source} to the model anyway?",
+            reasons.join(".\n")
+        ),
+        is_other: false,
+        is_secret: false,
+        options: Some(vec![
+            RequestUserInputQuestionOption {
+                label: SEND_LABEL.to_string(),
+                description: "Send this content; the rules still apply to later content.".to_string(),
+            },
+            RequestUserInputQuestionOption {
+                label: STOP_LABEL.to_string(),
+                description: "Do not send it.".to_string(),
+            },
+        ]),
+    }
+}
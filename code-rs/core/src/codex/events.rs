@@ -39,6 +39,22 @@ impl Session {
         }
     }
 
+    /// Keep the in-flight turn checkpoint in step with the event.
+    fn checkpoint_turn(&self, event: &Event) {
+        if let Some(checkpoint) = self.turn_checkpoint.as_ref()
+            && let Ok(mut checkpoint) = checkpoint.lock()
+        {
+            checkpoint.observe(&event.id, &event.msg);
+        }
+    }
+
+    /// Queue the event for any `[[webhooks]]` that report on it.
+    fn dispatch_webhooks(&self, event: &Event) {
+        if let Some(webhooks) = self.webhooks.as_ref() {
+            webhooks.dispatch(self.id, &self.cwd, event);
+        }
+    }
+
     /// Create a stamped Event with a per-turn sequence number.
     fn stamp_event(&self, sub_id: &str, msg: EventMsg) -> Event {
         let mut state = self.state.lock().unwrap();
@@ -87,6 +103,8 @@ impl Session {
     pub(crate) fn make_event(&self, sub_id: &str, msg: EventMsg) -> Event {
         let event = self.stamp_event(sub_id, msg);
         self.persist_event(&event);
+        self.checkpoint_turn(&event);
+        self.dispatch_webhooks(&event);
         event
     }
 
@@ -103,6 +121,8 @@ impl Session {
     ) -> Event {
         let event = self.stamp_event(sub_id, msg);
         self.persist_event(&event);
+        self.checkpoint_turn(&event);
+        self.dispatch_webhooks(&event);
         event
     }
 
@@ -116,6 +136,8 @@ impl Session {
         let mut ev = self.stamp_event(sub_id, msg);
         ev.order = Some(order);
         self.persist_event(&ev);
+        self.checkpoint_turn(&ev);
+        self.dispatch_webhooks(&ev);
         ev
     }
 
@@ -76,6 +76,7 @@ impl Drop for ExecDropGuard {
             stderr,
             exit_code,
             duration: Duration::ZERO,
+            redacted_values: 0,
         });
 
         if let Some(session) = self.session.upgrade() {
@@ -341,6 +342,7 @@ impl Session {
         sub_id: &str,
         call_id: &str,
         output: &ExecToolCallOutput,
+        redacted_values: usize,
         is_apply_patch: bool,
         order_ctx: ExecOrderContext,
     ) {
@@ -373,6 +375,7 @@ impl Session {
                 stderr,
                 exit_code: *exit_code,
                 duration: *duration,
+                redacted_values,
             })
         };
         let order = crate::protocol::OrderMeta {
@@ -408,6 +411,13 @@ impl Session {
         output_index: Option<u32>,
         attempt_req: u64,
     ) -> crate::error::Result<ExecToolCallOutput> {
+        let exec_span = tracing::info_span!(
+            target: code_otel::TRACE_TARGET,
+            "exec",
+            call_id = %begin_ctx.call_id,
+            program = begin_ctx.command_for_display.first().map(String::as_str).unwrap_or_default(),
+            apply_patch = begin_ctx.apply_patch.is_some(),
+        );
         self
             .run_exec_with_events_inner(
                 turn_diff_tracker,
@@ -420,6 +430,7 @@ impl Session {
                 },
                 true,
             )
+            .instrument(exec_span)
             .await
     }
 
@@ -502,6 +513,7 @@ impl Session {
                     stderr,
                     exit_code,
                     duration: Duration::ZERO,
+                    redacted_values: 0,
                 });
                 let event = self.make_event_with_order(sub_id, msg, order_meta.clone(), order_meta.sequence_number);
                 let _ = self.tx_event.send(event).await;
@@ -579,8 +591,17 @@ impl Session {
         )
         .await;
 
-        let result = process_exec_tool_call(params, sandbox_type, sandbox_policy, sandbox_cwd, code_linux_sandbox_exe, stdout_stream)
+        let mut result = process_exec_tool_call(params, sandbox_type, sandbox_policy, sandbox_cwd, code_linux_sandbox_exe, stdout_stream)
         .await;
+        let redacted_values = match &mut result {
+            Ok(output) => self.redactor.redact_exec_output(output),
+            Err(CodexErr::Sandbox(
+                SandboxErr::Timeout { output }
+                | SandboxErr::Denied { output }
+                | SandboxErr::OutOfMemory { output, .. },
+            )) => self.redactor.redact_exec_output(output),
+            Err(_) => 0,
+        };
 
         let output_stderr;
         let borrowed: &ExecToolCallOutput = match &result {
@@ -603,6 +624,7 @@ impl Session {
             &sub_id,
             &call_id,
             borrowed,
+            redacted_values,
             is_apply_patch,
             ExecOrderContext {
                 seq_hint: order_ctx.seq_hint.map(|h| h.saturating_add(1)),
@@ -716,6 +738,54 @@ impl Session {
         }
     }
 
+    /// Runs the `[hooks]` scripts for `point`. Annotations, vetoes and hooks
+    /// that failed open are reported to the user as background events; the
+    /// caller decides what a veto or annotation means for its action.
+    pub(super) async fn run_script_hooks(
+        &self,
+        sub_id: &str,
+        attempt_req: u64,
+        point: ScriptHookPoint,
+        mut payload: Value,
+    ) -> ScriptHookVerdict {
+        let hooks = match point {
+            ScriptHookPoint::PreTurn => &self.script_hooks.pre_turn,
+            ScriptHookPoint::PreExec => &self.script_hooks.pre_exec,
+            ScriptHookPoint::PostPatch => &self.script_hooks.post_patch,
+        };
+        if hooks.is_empty() {
+            return ScriptHookVerdict::default();
+        }
+        if let Some(obj) = payload.as_object_mut() {
+            obj.insert("hook".to_string(), json!(point.as_str()));
+            obj.insert("sub_id".to_string(), json!(sub_id));
+            obj.insert("session_cwd".to_string(), json!(self.cwd.to_string_lossy()));
+        }
+        let verdict =
+            crate::script_hooks::run_script_hooks(hooks, point, self.get_cwd(), &payload).await;
+
+        let point_label = point.as_str();
+        let mut notes: Vec<String> = verdict
+            .failures
+            .iter()
+            .map(|(label, error)| format!("Hook `{label}` ({point_label}) failed: {error}"))
+            .collect();
+        notes.extend(
+            verdict
+                .annotations
+                .iter()
+                .map(|(label, text)| format!("Hook `{label}` ({point_label}): {text}")),
+        );
+        if let Some(denial) = verdict.denial() {
+            notes.push(format!("{point_label} {denial}"));
+        }
+        for note in notes {
+            let order = self.next_background_order(sub_id, attempt_req, None);
+            self.notify_background_event_with_order(sub_id, order, note).await;
+        }
+        verdict
+    }
+
     pub(super) async fn run_session_hooks(&self, event: ProjectHookEvent) {
         if self.project_hooks.is_empty() {
             return;
@@ -943,4 +1013,222 @@ impl Session {
                 .await;
         }
     }
+
+    /// Re-run a command that previously executed in this session. Unlike
+    /// project commands, reruns go through the same safety assessment as model
+    /// issued commands so the session approval policy still applies.
+    pub(super) async fn rerun_command(
+        &self,
+        turn_diff_tracker: &mut TurnDiffTracker,
+        sub_id: &str,
+        call_id: String,
+        command: Vec<String>,
+        cwd: Option<PathBuf>,
+        attempt_req: u64,
+    ) {
+        let cwd = cwd.unwrap_or_else(|| self.get_cwd().to_path_buf());
+        let label = crate::util::strip_bash_lc_and_escape(&command);
+        let safety = {
+            let state = self.state.lock().unwrap();
+            let command_safety_context =
+                crate::command_safety::context::CommandSafetyContext::from_shell(&self.user_shell)
+                    .with_command_shell(&command);
+            let safety_config = crate::safety::CommandSafetyEvaluationConfig {
+                context: command_safety_context,
+                safe_rules: self.safe_command_rules,
+                dangerous_rules: self.dangerous_command_rules,
+                dangerous_command_detection_enabled: self.dangerous_command_detection_enabled,
+            };
+            assess_command_safety(
+                &command,
+                safety_config,
+                self.approval_policy,
+                &self.sandbox_policy,
+                &state.approved_commands,
+                false,
+            )
+        };
+
+        let sandbox_type = match safety {
+            SafetyCheck::AutoApprove { sandbox_type, .. } => sandbox_type,
+            SafetyCheck::AskUser => {
+                let rx_approve = self
+                    .request_command_approval(
+                        sub_id.to_string(),
+                        call_id.clone(),
+                        command.clone(),
+                        cwd.clone(),
+                        Some("Rerun of a command from history".to_string()),
+                    )
+                    .await;
+                match rx_approve.await.unwrap_or_default() {
+                    ReviewDecision::Approved => {}
+                    ReviewDecision::ApprovedForSession => {
+                        self.add_approved_command(ApprovedCommandPattern::new(
+                            command.clone(),
+                            ApprovedCommandMatchKind::Exact,
+                            None,
+                        ));
+                    }
+                    ReviewDecision::Denied | ReviewDecision::Abort => {
+                        let order = self.next_background_order(sub_id, attempt_req, None);
+                        self
+                            .notify_background_event_with_order(
+                                sub_id,
+                                order,
+                                format!("Rerun of `{label}` was declined."),
+                            )
+                            .await;
+                        return;
+                    }
+                }
+                SandboxType::None
+            }
+            SafetyCheck::Reject { reason } => {
+                let order = self.next_background_order(sub_id, attempt_req, None);
+                self
+                    .notify_background_event_with_order(
+                        sub_id,
+                        order,
+                        format!("Rerun of `{label}` rejected: {reason}"),
+                    )
+                    .await;
+                return;
+            }
+        };
+
+        let exec_params = ExecParams {
+            command: command.clone(),
+            cwd: cwd.clone(),
+            timeout_ms: None,
+            env: create_env(&self.shell_environment_policy),
+            with_escalated_permissions: Some(false),
+            justification: None,
+        };
+        let exec_params = maybe_run_with_user_profile(exec_params, self);
+
+        let exec_ctx = ExecCommandContext {
+            sub_id: sub_id.to_string(),
+            call_id,
+            command_for_display: command,
+            cwd: exec_params.cwd.clone(),
+            apply_patch: None,
+        };
+
+        let exec_args = ExecInvokeArgs {
+            params: exec_params,
+            sandbox_type,
+            sandbox_policy: &self.sandbox_policy,
+            sandbox_cwd: self.get_cwd(),
+            code_linux_sandbox_exe: &self.code_linux_sandbox_exe,
+            stdout_stream: None,
+        };
+
+        if let Err(err) = self
+            .run_exec_with_events(turn_diff_tracker, exec_ctx, exec_args, None, None, attempt_req)
+            .await
+        {
+            let order = self.next_background_order(sub_id, attempt_req, None);
+            self
+                .notify_background_event_with_order(
+                    sub_id,
+                    order,
+                    format!("Rerun of `{label}` failed: {}", get_error_message_ui(&err)),
+                )
+                .await;
+        }
+    }
+
+    /// Runs a `run_tests` command. Like reruns it goes through the safety
+    /// assessment, so the approval policy applies; the output is collected
+    /// rather than streamed since the tool reports parsed results.
+    pub(super) async fn run_test_command(
+        &self,
+        sub_id: &str,
+        call_id: &str,
+        command: Vec<String>,
+        cwd: PathBuf,
+        timeout_ms: u64,
+    ) -> Result<ExecToolCallOutput, String> {
+        let label = crate::util::strip_bash_lc_and_escape(&command);
+        let safety = {
+            let state = self.state.lock().unwrap();
+            let command_safety_context =
+                crate::command_safety::context::CommandSafetyContext::from_shell(&self.user_shell)
+                    .with_command_shell(&command);
+            let safety_config = crate::safety::CommandSafetyEvaluationConfig {
+                context: command_safety_context,
+                safe_rules: self.safe_command_rules,
+                dangerous_rules: self.dangerous_command_rules,
+                dangerous_command_detection_enabled: self.dangerous_command_detection_enabled,
+            };
+            assess_command_safety(
+                &command,
+                safety_config,
+                self.approval_policy,
+                &self.sandbox_policy,
+                &state.approved_commands,
+                false,
+            )
+        };
+
+        let sandbox_type = match safety {
+            SafetyCheck::AutoApprove { sandbox_type, .. } => sandbox_type,
+            SafetyCheck::AskUser => {
+                let rx_approve = self
+                    .request_command_approval(
+                        sub_id.to_string(),
+                        call_id.to_string(),
+                        command.clone(),
+                        cwd.clone(),
+                        Some("Run the project's tests".to_string()),
+                    )
+                    .await;
+                match rx_approve.await.unwrap_or_default() {
+                    ReviewDecision::Approved => {}
+                    ReviewDecision::ApprovedForSession => {
+                        self.add_approved_command(ApprovedCommandPattern::new(
+                            command.clone(),
+                            ApprovedCommandMatchKind::Exact,
+                            None,
+                        ));
+                    }
+                    ReviewDecision::Denied | ReviewDecision::Abort => {
+                        return Err(format!("The user declined `{label}`."));
+                    }
+                }
+                SandboxType::None
+            }
+            SafetyCheck::Reject { reason } => {
+                return Err(format!("`{label}` was rejected: {reason}"));
+            }
+        };
+
+        let exec_params = ExecParams {
+            command,
+            cwd,
+            timeout_ms: Some(timeout_ms),
+            env: create_env(&self.shell_environment_policy),
+            with_escalated_permissions: Some(false),
+            justification: None,
+        };
+        let exec_params = maybe_run_with_user_profile(exec_params, self);
+        match crate::exec::process_exec_tool_call(
+            exec_params,
+            sandbox_type,
+            &self.sandbox_policy,
+            self.get_cwd(),
+            &self.code_linux_sandbox_exe,
+            None,
+        )
+        .await
+        {
+            Ok(output) => Ok(output),
+            // Partial output still says which tests ran and failed.
+            Err(CodexErr::Sandbox(SandboxErr::Timeout { output } | SandboxErr::Denied { output })) => {
+                Ok(*output)
+            }
+            Err(err) => Err(format!("`{label}` failed to run: {}", get_error_message_ui(&err))),
+        }
+    }
 }
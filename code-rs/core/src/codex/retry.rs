@@ -0,0 +1,123 @@
+use super::*;
+use super::streaming::AgentTask;
+use code_protocol::protocol::InputMessageKind;
+
+/// Position of the last message the user typed. User-role messages that
+/// carry instructions, environment context or browser snapshots are skipped.
+fn last_user_turn_index(history: &[ResponseItem]) -> Option<usize> {
+    history.iter().rposition(|item| match item {
+        ResponseItem::Message { role, content, .. } if role == "user" => {
+            let text: String = content
+                .iter()
+                .filter_map(|part| match part {
+                    ContentItem::InputText { text } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect();
+            matches!(InputMessageKind::from(("user", text.as_str())), InputMessageKind::User)
+        }
+        _ => false,
+    })
+}
+
+impl Session {
+    /// Cuts the history back to just before the last user turn and returns
+    /// that turn's message so it can be sent again. The rollout is
+    /// append-only, so the dropped answer stays in the session log.
+    fn take_last_user_turn(&self) -> Option<ResponseInputItem> {
+        let mut state = self.state.lock().unwrap();
+        let mut history = state.history.contents();
+        let idx = last_user_turn_index(&history)?;
+        let removed = history.drain(idx..).next();
+        let Some(ResponseItem::Message { role, content, .. }) = removed else {
+            return None;
+        };
+        state.history.replace(history);
+        Some(ResponseInputItem::Message { role, content })
+    }
+}
+
+/// Handles [`Op::RetryLastTurn`]: resends the last user turn as a new task,
+/// on `model` and at `temperature` when given.
+pub(super) async fn retry_last_turn(
+    sess: &Arc<Session>,
+    sub_id: String,
+    model: Option<String>,
+    temperature: Option<f64>,
+) {
+    let attempt_req = sess.current_request_ordinal();
+    let notice = if sess.has_running_task() {
+        Err("Wait for the current turn to finish before retrying it.")
+    } else {
+        sess.take_last_user_turn().ok_or("There is no turn to retry yet.")
+    };
+    let user_turn = match notice {
+        Ok(user_turn) => user_turn,
+        Err(message) => {
+            let order = sess.next_background_order(&sub_id, attempt_req, None);
+            sess.notify_background_event_with_order(&sub_id, order, message.to_string())
+                .await;
+            return;
+        }
+    };
+
+    let mut how = String::new();
+    if let Some(model) = model.as_deref() {
+        how.push_str(&format!(" on {model}"));
+    }
+    if let Some(temperature) = temperature {
+        how.push_str(&format!(" at temperature {temperature}"));
+    }
+    let order = sess.next_background_order(&sub_id, attempt_req, None);
+    sess.notify_background_event_with_order(
+        &sub_id,
+        order,
+        format!("Retrying the last turn{how}; the previous answer stays in the session log."),
+    )
+    .await;
+
+    *sess.next_turn_model.lock().unwrap() = model;
+    *sess.next_turn_temperature.lock().unwrap() = temperature;
+    sess.add_pending_input(user_turn);
+    sess.cleanup_old_status_items().await;
+    let turn_context = sess.make_turn_context();
+    let sentinel_input = vec![InputItem::Text {
+        text: PENDING_ONLY_SENTINEL.to_string(),
+    }];
+    let agent = AgentTask::spawn(Arc::clone(sess), turn_context, sub_id, sentinel_input);
+    sess.set_task(agent);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(role: &str, text: &str) -> ResponseItem {
+        let part = if role == "user" {
+            ContentItem::InputText { text: text.to_string() }
+        } else {
+            ContentItem::OutputText { text: text.to_string() }
+        };
+        ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![part],
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    #[test]
+    fn finds_last_typed_user_message() {
+        let history = vec![
+            message("user", "<environment_context>cwd</environment_context>"),
+            message("user", "first question"),
+            message("assistant", "first answer"),
+            message("user", "second question"),
+            message("assistant", "second answer"),
+            message("user", "<environment_context>changed</environment_context>"),
+        ];
+        assert_eq!(last_user_turn_index(&history), Some(3));
+        assert_eq!(last_user_turn_index(&history[..1]), None);
+    }
+}
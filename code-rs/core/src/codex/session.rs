@@ -143,6 +143,12 @@ pub(super) struct State {
     pub(super) running_execs: HashMap<String, RunningExecMeta>,
     pub(super) next_internal_sub_id: u64,
     pub(super) token_usage_info: Option<TokenUsageInfo>,
+    /// Estimated session cost in USD, priced from `[model_pricing]`.
+    pub(super) session_cost_usd: Option<f64>,
+    /// Estimated cost of the latest priced request, used to predict the next.
+    pub(super) last_request_cost_usd: f64,
+    /// Budget the user raised `[limits] max_session_cost` to from the prompt.
+    pub(super) session_cost_budget: Option<f64>,
     pub(super) latest_rate_limits: Option<RateLimitSnapshotEvent>,
     pub(super) pending_manual_compacts: VecDeque<String>,
     pub(super) wait_interrupt_epoch: u64,
@@ -355,6 +361,9 @@ pub(crate) struct Session {
     /// Optional rollout recorder for persisting the conversation transcript so
     /// sessions can be replayed or inspected later.
     pub(super) rollout: Mutex<Option<RolloutRecorder>>,
+    /// Streamed text of the running turn, kept next to the rollout so a
+    /// crash mid-turn can be recovered.
+    pub(super) turn_checkpoint: Option<Mutex<crate::rollout::interrupted::TurnCheckpointWriter>>,
     pub(super) state: Mutex<State>,
     pub(super) code_linux_sandbox_exe: Option<PathBuf>,
     pub(super) user_shell: shell::Shell,
@@ -372,6 +381,10 @@ pub(crate) struct Session {
     pub(super) last_screenshot_info: Mutex<LastScreenshotInfo>, // (path, phash, dhash)
     pub(super) time_budget: Mutex<Option<RunTimeBudget>>,
     pub(super) confirm_guard: ConfirmGuardRuntime,
+    /// Masks secrets in command output before it reaches history or the model.
+    pub(super) redactor: Arc<crate::redaction::SecretRedactor>,
+    /// `[[dlp.rules]]` checked before content is sent to the model.
+    pub(super) dlp: crate::dlp::DlpPolicy,
     pub(super) project_hooks: ProjectHooks,
     pub(super) project_commands: Vec<ProjectCommand>,
     pub(super) tool_output_max_bytes: usize,
@@ -381,10 +394,39 @@ pub(crate) struct Session {
     pub(super) self_handle: Weak<Session>,
     pub(super) active_review: Mutex<Option<ReviewRequest>>,
     pub(super) next_turn_text_format: Mutex<Option<TextFormat>>,
+    pub(super) next_turn_model: Mutex<Option<String>>,
+    pub(super) next_turn_temperature: Mutex<Option<f64>>,
+    /// Facts from `/context pin`, sent with every request.
+    pub(super) pinned_facts: Mutex<BTreeMap<String, String>>,
+    /// Developer message for the `/scope` subproject, sent with every request.
+    pub(super) scope_message: Mutex<Option<String>>,
     pub(super) env_ctx_v2: bool,
     pub(super) retention_config: crate::config_types::RetentionConfig,
     pub(super) model_descriptions: Option<String>,
     pub(super) mcp_access: StdRwLock<McpAccessState>,
+    /// Shell state carried between exec calls when `[tools] persistent_shell` is on.
+    pub(super) persistent_shell: Option<crate::persistent_shell::PersistentShell>,
+    /// `[patches] protected` globs that apply_patch may never touch.
+    pub(super) protected_patch_paths: Vec<String>,
+    /// `[patches] format_command` formatters run after a successful apply.
+    pub(super) patch_format_commands: std::collections::BTreeMap<String, String>,
+    /// `[git] auto_checkpoint`: commit each applied patch to the session's checkpoint ref.
+    pub(super) auto_checkpoint: bool,
+    /// `[lsp]` language servers checked after each applied patch.
+    pub(super) lsp: Option<crate::lsp::LspBridge>,
+    /// `[patches.lint]` linters run on a patch before it is sent for approval.
+    pub(super) lint_gate: Option<crate::patch_lint::LintGate>,
+    /// `[repo_map] auto`: repository map added to the initial context.
+    pub(super) repo_map: Option<String>,
+    /// `[environment_context] providers`: toolchain, compose and virtualenv
+    /// facts added to the environment context.
+    pub(super) environment_providers: crate::environment_providers::EnvironmentProviders,
+    /// `[hooks]` user scripts that can veto or annotate turns, commands and patches.
+    pub(super) script_hooks: crate::config_types::HooksConfig,
+    /// `[[webhooks]]` endpoints notified of turn completion, patch apply and errors.
+    pub(super) webhooks: Option<crate::webhooks::WebhookEmitter>,
+    /// `[limits] max_session_cost`, the estimated spend that needs confirmation.
+    pub(super) max_session_cost: Option<f64>,
 }
 pub(super) struct HookGuard<'a> {
     flag: &'a AtomicBool,
@@ -566,6 +608,10 @@ impl Session {
         &self.cwd
     }
 
+    pub(crate) fn lint_gate(&self) -> Option<&crate::patch_lint::LintGate> {
+        self.lint_gate.as_ref()
+    }
+
     pub(super) async fn apply_remote_model_overrides(&self, prompt: &mut Prompt) -> bool {
         let configured_model = self.client.get_model();
 
@@ -713,6 +759,32 @@ impl Session {
             .map_or_else(|| self.cwd.clone(), |p| self.cwd.join(p))
     }
 
+    /// Like `resolve_path`, but a missing `workdir` falls back to the directory
+    /// the persistent shell was left in by the previous command.
+    pub(super) fn resolve_exec_cwd(&self, path: Option<String>) -> PathBuf {
+        let base = self
+            .persistent_shell
+            .as_ref()
+            .and_then(|shell| shell.cwd())
+            .unwrap_or_else(|| self.cwd.clone());
+        path.as_ref()
+            .map(PathBuf::from)
+            .map_or(base.clone(), |p| base.join(p))
+    }
+
+    pub(super) fn wrap_for_persistent_shell(&self, mut params: ExecParams) -> ExecParams {
+        if let Some(shell) = self.persistent_shell.as_ref() {
+            params.command = shell.wrap_command(params.command);
+        }
+        params
+    }
+
+    pub(super) fn shell_session_context(
+        &self,
+    ) -> Option<crate::persistent_shell::ShellSessionContext> {
+        self.persistent_shell.as_ref().and_then(|shell| shell.context())
+    }
+
     pub(crate) async fn maybe_parse_apply_patch_verified(
         &self,
         argv: &[String],
@@ -1183,11 +1255,27 @@ impl Session {
             shell_environment_policy: self.shell_environment_policy.clone(),
             collaboration_mode: self.collaboration_mode,
             is_review_mode: false,
+            review_passes: Vec::new(),
             text_format_override: self.next_turn_text_format.lock().unwrap().take(),
+            model_override: self.next_turn_model.lock().unwrap().take(),
+            temperature_override: self.next_turn_temperature.lock().unwrap().take(),
             final_output_json_schema,
         })
     }
 
+    /// Developer message listing the pinned facts, if there are any.
+    pub(super) fn pinned_facts_message(&self) -> Option<String> {
+        let facts = self.pinned_facts.lock().unwrap();
+        if facts.is_empty() {
+            return None;
+        }
+        let mut message = "Facts the user pinned for this session. Treat them as true unless the user says otherwise:".to_string();
+        for (key, value) in facts.iter() {
+            message.push_str(&format!("\n- {key}: {value}"));
+        }
+        Some(message)
+    }
+
     pub(super) fn compact_prompt_text(&self) -> String {
         crate::codex::compact::resolve_compact_prompt_text(
             self.compact_prompt_override.as_deref(),
@@ -1599,6 +1687,13 @@ impl Session {
 
         debug_history("turn_input_with_history", &result);
 
+        if crate::metered::is_metered() {
+            let omitted = crate::metered::omit_images(&mut result);
+            if omitted > 0 {
+                debug!("Metered connection: left {omitted} images out of the request");
+            }
+        }
+
         // Count total images in result for debugging
         let total_images = result
             .iter()
@@ -1637,7 +1732,9 @@ impl Session {
             Some(turn_context.approval_policy),
             Some(turn_context.sandbox_policy.clone()),
             Some(self.user_shell.clone()),
-        );
+        )
+        .with_shell_session(self.shell_session_context())
+        .with_providers(self.environment_providers.facts(&turn_context.cwd));
 
         if let Some(mut env_ctx_items) = self.maybe_emit_env_ctx_messages(
             &env_context,
@@ -1651,6 +1748,19 @@ impl Session {
             // Legacy XML payload remains so behaviour is unchanged when the feature flag is off.
             items.push(ResponseItem::from(env_context));
         }
+
+        // Optional context; skipped to save data on a metered connection.
+        if let Some(repo_map) = self.repo_map.as_ref().filter(|_| !crate::metered::is_metered()) {
+            items.push(ResponseItem::Message {
+                id: None,
+                role: "developer".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: repo_map.clone(),
+                }],
+                end_turn: None,
+                phase: None,
+            });
+        }
         items
     }
 
@@ -1846,7 +1956,12 @@ impl Session {
         }
     }
 
-    pub(super) fn emit_browser_snapshot_event(&self, stream_id: &str, snapshot: &BrowserSnapshot) {
+    pub(super) fn emit_browser_snapshot_event(
+        &self,
+        stream_id: &str,
+        snapshot: &BrowserSnapshot,
+        screenshot_path: &Path,
+    ) {
         use crate::protocol::OrderMeta;
 
         let Ok(snapshot_json) = serde_json::to_value(snapshot) else {
@@ -1864,6 +1979,7 @@ impl Session {
             snapshot: snapshot_json,
             url: Some(snapshot.url.clone()),
             captured_at: Some(snapshot.captured_at.clone()),
+            screenshot_path: Some(screenshot_path.to_path_buf()),
         });
 
         let event = self.make_event_with_order(stream_id, msg, order, None);
@@ -2470,6 +2586,10 @@ impl State {
             environment_context_seq: self.environment_context_seq,
             last_environment_snapshot: self.last_environment_snapshot.clone(),
             context_stream_ids: self.context_stream_ids.clone(),
+            // Cost estimates cover the whole session, across reconfigurations.
+            session_cost_usd: self.session_cost_usd,
+            last_request_cost_usd: self.last_request_cost_usd,
+            session_cost_budget: self.session_cost_budget,
             ..Default::default()
         }
     }
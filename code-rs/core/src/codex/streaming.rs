@@ -749,13 +749,18 @@ pub(super) async fn submission_loop(
                 };
 
                 // Create debug logger based on config
+                let wire_log = crate::wire_log::WireLog::from_config(
+                    &config.wire_log,
+                    &config.code_home,
+                    &config.redaction,
+                );
                 let debug_logger = match crate::debug_logger::DebugLogger::new(config.debug) {
-                    Ok(logger) => std::sync::Arc::new(std::sync::Mutex::new(logger)),
+                    Ok(logger) => std::sync::Arc::new(std::sync::Mutex::new(logger.with_wire_log(wire_log))),
                     Err(e) => {
                         warn!("Failed to create debug logger: {}", e);
                         // Create a disabled logger as fallback
                         std::sync::Arc::new(std::sync::Mutex::new(
-                            crate::debug_logger::DebugLogger::new(false).unwrap(),
+                            crate::debug_logger::DebugLogger::new(false).unwrap().with_wire_log(wire_log),
                         ))
                     }
                 };
@@ -981,6 +986,31 @@ pub(super) async fn submission_loop(
                         remote.refresh_remote_models().await;
                     });
                 }
+                let persistent_shell = if config.tools_persistent_shell {
+                    match crate::persistent_shell::PersistentShell::new(session_id) {
+                        Ok(shell) => Some(shell),
+                        Err(err) => {
+                            warn!("failed to set up persistent shell state: {err}");
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+                let lsp = crate::lsp::LspBridge::new(&config.lsp, &cwd);
+                let lint_gate =
+                    crate::patch_lint::LintGate::new(&config.patches.lint, &cwd, session_id);
+                let repo_map = if config.repo_map.auto {
+                    let root = cwd.clone();
+                    let max_tokens = config.repo_map.max_tokens();
+                    tokio::task::spawn_blocking(move || crate::repo_map::build_repo_map(&root, max_tokens))
+                        .await
+                        .ok()
+                        .filter(|map| map.files > 0)
+                        .map(|map| map.to_context_message())
+                } else {
+                    None
+                };
                 let mut new_session = Arc::new(Session {
                     id: session_id,
                     client,
@@ -1006,6 +1036,11 @@ pub(super) async fn submission_loop(
                     model_reasoning_effort: config.model_reasoning_effort,
                     notify,
                     state: Mutex::new(state),
+                    turn_checkpoint: rollout_recorder.as_ref().map(|rec| {
+                        Mutex::new(crate::rollout::interrupted::TurnCheckpointWriter::new(
+                            &rec.rollout_path,
+                        ))
+                    }),
                     rollout: Mutex::new(rollout_recorder),
                     code_linux_sandbox_exe: config.code_linux_sandbox_exe.clone(),
                     disable_response_storage,
@@ -1027,6 +1062,8 @@ pub(super) async fn submission_loop(
                         RunTimeBudget::new(deadline, total)
                     })),
                     confirm_guard: ConfirmGuardRuntime::from_config(&config.confirm_guard),
+                    redactor: Arc::new(crate::redaction::SecretRedactor::from_config(&config.redaction)),
+                    dlp: crate::dlp::DlpPolicy::from_config(&config.dlp),
                     project_hooks: config.project_hooks.clone(),
                     project_commands: config.project_commands.clone(),
                     tool_output_max_bytes: config.tool_output_max_bytes,
@@ -1036,6 +1073,10 @@ pub(super) async fn submission_loop(
                     self_handle: Weak::new(),
                     active_review: Mutex::new(None),
                     next_turn_text_format: Mutex::new(None),
+                    next_turn_model: Mutex::new(None),
+                    next_turn_temperature: Mutex::new(None),
+                    pinned_facts: Mutex::new(BTreeMap::new()),
+                    scope_message: Mutex::new(None),
                     env_ctx_v2: config.env_ctx_v2,
                     retention_config: config.retention.clone(),
                     model_descriptions,
@@ -1049,6 +1090,19 @@ pub(super) async fn submission_loop(
                         turn_id: None,
                         turn_allow_servers: HashSet::new(),
                     }),
+                    persistent_shell,
+                    protected_patch_paths: config.patches.protected.clone(),
+                    patch_format_commands: config.patches.format_command.clone(),
+                    auto_checkpoint: config.git.auto_checkpoint,
+                    lsp,
+                    lint_gate,
+                    repo_map,
+                    environment_providers: crate::environment_providers::EnvironmentProviders::new(
+                        &config.environment_context,
+                    ),
+                    script_hooks: config.hooks.clone(),
+                    webhooks: crate::webhooks::WebhookEmitter::new(&config.webhooks),
+                    max_session_cost: config.limits.max_session_cost,
                 });
                 let weak_handle = Arc::downgrade(&new_session);
                 if let Some(inner) = Arc::get_mut(&mut new_session) {
@@ -1365,6 +1419,25 @@ pub(super) async fn submission_loop(
                     .await;
             }
 
+            Op::RerunCommand { call_id, command, cwd } => {
+                let sess = match sess.as_ref() {
+                    Some(sess) => Arc::clone(sess),
+                    None => {
+                        send_no_session_event(sub.id).await;
+                        continue;
+                    }
+                };
+                // Run off the submission loop: the rerun may wait on an
+                // ExecApproval op that this loop has to deliver.
+                let sub_id = sub.id.clone();
+                tokio::spawn(async move {
+                    let mut tracker = TurnDiffTracker::new();
+                    let attempt_req = sess.current_request_ordinal();
+                    sess.rerun_command(&mut tracker, &sub_id, call_id, command, cwd, attempt_req)
+                        .await;
+                });
+            }
+
             Op::GetHistoryEntryRequest { offset, log_id } => {
                 let config = config.clone();
                 let tx_event = tx_event.clone();
@@ -1717,6 +1790,50 @@ pub(super) async fn submission_loop(
                 };
                 *sess_arc.next_turn_text_format.lock().unwrap() = Some(format);
             }
+            Op::SetNextTurnModel { model } => {
+                let sess_arc = match sess.as_ref() {
+                    Some(sess) => Arc::clone(sess),
+                    None => {
+                        send_no_session_event(sub.id).await;
+                        continue;
+                    }
+                };
+                *sess_arc.next_turn_model.lock().unwrap() = Some(model);
+            }
+            Op::RetryLastTurn { model, temperature } => {
+                let sess = match sess.as_ref() {
+                    Some(sess) => sess,
+                    None => {
+                        send_no_session_event(sub.id).await;
+                        continue;
+                    }
+                };
+                super::retry::retry_last_turn(sess, sub.id, model, temperature).await;
+            }
+            Op::SetPinnedFacts { facts } => {
+                let sess_arc = match sess.as_ref() {
+                    Some(sess) => Arc::clone(sess),
+                    None => {
+                        send_no_session_event(sub.id).await;
+                        continue;
+                    }
+                };
+                *sess_arc.pinned_facts.lock().unwrap() = facts;
+            }
+            Op::SetScope { path } => {
+                let sess_arc = match sess.as_ref() {
+                    Some(sess) => Arc::clone(sess),
+                    None => {
+                        send_no_session_event(sub.id).await;
+                        continue;
+                    }
+                };
+                let message = path.map(|path| {
+                    let workspace = crate::monorepo::detect_workspace(&path);
+                    crate::monorepo::scope_message(&path, workspace.as_ref())
+                });
+                *sess_arc.scope_message.lock().unwrap() = message;
+            }
             Op::Shutdown => {
                 info!("Shutting down Codex instance");
 
@@ -1854,12 +1971,17 @@ async fn spawn_review_thread(
     }
     let review_config = Arc::new(review_config);
 
+    let review_wire_log = crate::wire_log::WireLog::from_config(
+        &review_config.wire_log,
+        &review_config.code_home,
+        &review_config.redaction,
+    );
     let review_debug_logger = match crate::debug_logger::DebugLogger::new(review_config.debug) {
-        Ok(logger) => Arc::new(Mutex::new(logger)),
+        Ok(logger) => Arc::new(Mutex::new(logger.with_wire_log(review_wire_log))),
         Err(err) => {
             warn!("failed to create review debug logger: {err}");
             Arc::new(Mutex::new(
-                crate::debug_logger::DebugLogger::new(false).unwrap(),
+                crate::debug_logger::DebugLogger::new(false).unwrap().with_wire_log(review_wire_log),
             ))
         }
     };
@@ -1899,7 +2021,10 @@ async fn spawn_review_thread(
         shell_environment_policy: parent_turn_context.shell_environment_policy.clone(),
         collaboration_mode: parent_turn_context.collaboration_mode,
         is_review_mode: true,
+        review_passes: config.review_passes.clone(),
         text_format_override: None,
+        model_override: None,
+        temperature_override: None,
         final_output_json_schema: None,
     });
 
@@ -2008,6 +2133,21 @@ async fn capture_review_snapshot(session: &Session) -> Option<ReviewSnapshotInfo
     })
 }
 
+/// Parses and clears the reviewer messages collected for the pass that just
+/// finished, falling back to its last assistant message.
+fn take_review_output(messages: &mut Vec<String>, fallback: Option<&str>) -> Option<ReviewOutputEvent> {
+    let combined = if messages.is_empty() {
+        fallback.unwrap_or_default().to_string()
+    } else {
+        std::mem::take(messages).join("\n\n")
+    };
+    if combined.trim().is_empty() {
+        None
+    } else {
+        Some(parse_review_output_event(&combined))
+    }
+}
+
 fn parse_review_output_event(text: &str) -> ReviewOutputEvent {
     if let Ok(parsed) = serde_json::from_str::<ReviewOutputEvent>(text) {
         return parsed;
@@ -2044,7 +2184,7 @@ fn parse_review_output_event(text: &str) -> ReviewOutputEvent {
 ///   back to the model in the next turn.
 /// - If the model sends only an assistant message, we record it in the
 ///   conversation history and consider the agent complete.
-async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>, sub_id: String, input: Vec<InputItem>) {
+async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>, sub_id: String, mut input: Vec<InputItem>) {
     if input.is_empty() {
         return;
     }
@@ -2057,6 +2197,10 @@ async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>, sub_id: S
     let is_review_mode = turn_context.is_review_mode;
     let mut review_history: Vec<ResponseItem> = Vec::new();
     let mut review_messages: Vec<String> = Vec::new();
+    let mut review_passes = turn_context.review_passes.iter();
+    // `None` while the main review pass runs.
+    let mut review_pass_name: Option<String> = None;
+    let mut review_pass_outputs: Vec<(Option<String>, ReviewOutputEvent)> = Vec::new();
     let mut review_exit_emitted = false;
 
     let pending_only_turn = input.len() == 1
@@ -2080,6 +2224,31 @@ async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>, sub_id: S
 
     let mut initial_response_item: Option<ResponseItem> = None;
 
+    if !pending_only_turn && !is_review_mode {
+        let attempt_req = sess.current_request_ordinal();
+        let verdict = sess
+            .run_script_hooks(
+                &sub_id,
+                attempt_req,
+                ScriptHookPoint::PreTurn,
+                build_pre_turn_hook_payload(&input),
+            )
+            .await;
+        if verdict.denied.is_some() {
+            finish_agent_task(sess, sub_id, None).await;
+            return;
+        }
+        if let Some(notes) = verdict.annotation_text() {
+            input.push(InputItem::Text {
+                text: format!("Notes from pre_turn hooks:\n{notes}"),
+            });
+        }
+        if !super::dlp_gate::gate_user_input(&sess, &turn_context, &sub_id, &input).await {
+            finish_agent_task(sess, sub_id, None).await;
+            return;
+        }
+    }
+
     if !pending_only_turn {
         // Convert input to ResponseInputItem
         let mut response_input = response_input_from_core_items(input.clone());
@@ -2111,6 +2280,10 @@ async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>, sub_id: S
     let mut auto_compact_pending = false;
 
     loop {
+        if !super::cost_budget::confirm_session_cost_budget(&sess, &turn_context, &sub_id).await {
+            break;
+        }
+
         // Note that pending_input would be something like a message the user
         // submitted through the UI while the model was running. Though the UI
         // may support this, the model might not.
@@ -2119,7 +2292,7 @@ async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>, sub_id: S
         // review model, causing loops. Only include queued user inputs when not in
         // review mode. They will be picked up after TaskComplete via
         // pop_next_queued_user_input.
-        let pending_input = if is_review_mode {
+        let mut pending_input = if is_review_mode {
             sess.get_pending_input_filtered(false)
         } else {
             sess.get_pending_input()
@@ -2127,6 +2300,10 @@ async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>, sub_id: S
         .into_iter()
         .map(ResponseItem::from)
         .collect::<Vec<ResponseItem>>();
+        if !is_review_mode {
+            super::dlp_gate::gate_queued_messages(&sess, &turn_context, &sub_id, &mut pending_input)
+                .await;
+        }
         let mut pending_input_tail = pending_input.clone();
 
         if initial_response_item.is_none() {
@@ -2228,6 +2405,13 @@ async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>, sub_id: S
                 })
             })
             .collect();
+        let turn_span = tracing::info_span!(
+            target: code_otel::TRACE_TARGET,
+            "turn",
+            session_id = %sess.id,
+            sub_id = %sub_id,
+            model = %turn_context.client.get_model(),
+        );
         match run_turn(
             &sess,
             &turn_context,
@@ -2237,6 +2421,7 @@ async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>, sub_id: S
             pending_input_tail,
             turn_input,
         )
+        .instrument(turn_span)
         .await
         {
             Ok(turn_output) => {
@@ -2336,6 +2521,15 @@ async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>, sub_id: S
                     }
                 }
 
+                let outputs_withheld = !is_review_mode
+                    && super::dlp_gate::withhold_tool_outputs(
+                        &sess,
+                        &turn_context,
+                        &sub_id,
+                        &mut items_to_record_in_conversation_history,
+                    )
+                    .await;
+
                 // Only attempt to take the lock if there is something to record.
                 if !items_to_record_in_conversation_history.is_empty() {
                     if is_review_mode {
@@ -2348,6 +2542,11 @@ async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>, sub_id: S
                             .await;
                     }
                 }
+                if outputs_withheld {
+                    // The calls are answered with a withheld note; end the
+                    // turn rather than let the model work around the rule.
+                    break;
+                }
 
                 // Check whether we should proactively compact before queuing follow-up work.
                 // Upstream codex-rs compacts as soon as usage hits the configured threshold,
@@ -2432,6 +2631,36 @@ async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>, sub_id: S
                     if let Some(m) = last_task_message.as_ref() {
                         tracing::info!("core.turn completed: last_assistant_message.len={}", m.len());
                     }
+                    if is_review_mode && let Some(pass) = review_passes.next() {
+                        if let Some(output) =
+                            take_review_output(&mut review_messages, last_task_message.as_deref())
+                        {
+                            review_pass_outputs.push((review_pass_name.take(), output));
+                        }
+                        review_pass_name = Some(pass.name.trim().to_string());
+                        review_history.push(ResponseItem::Message {
+                            id: None,
+                            role: "user".to_string(),
+                            content: vec![ContentItem::InputText {
+                                text: crate::review_passes::review_pass_request(pass),
+                            }],
+                            end_turn: None,
+                            phase: None,
+                        });
+                        continue;
+                    }
+                    if let Some(schema) = turn_context.final_output_json_schema.as_ref()
+                        && let Some(warning) = crate::output_schema::check_final_answer(
+                            schema,
+                            last_task_message.as_deref(),
+                        )
+                    {
+                        let event = sess.make_event(
+                            &sub_id,
+                            EventMsg::Warning(crate::protocol::WarningEvent { message: warning }),
+                        );
+                        sess.send_event(event).await;
+                    }
                     sess.maybe_notify(UserNotification::AgentTurnComplete {
                         turn_id: sub_id.clone(),
                         input_messages: turn_input_messages,
@@ -2448,7 +2677,11 @@ async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>, sub_id: S
                 );
                 sess.tx_event.send(event).await.ok();
                 if is_review_mode && !review_exit_emitted {
-                    exit_review_mode(sess.clone(), sub_id.clone(), None).await;
+                    // Keep the findings of passes that already finished.
+                    let output = crate::review_passes::merge_review_passes(std::mem::take(
+                        &mut review_pass_outputs,
+                    ));
+                    exit_review_mode(sess.clone(), sub_id.clone(), output).await;
                     review_exit_emitted = true;
                 }
                 // let the user continue the conversation
@@ -2457,19 +2690,19 @@ async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>, sub_id: S
         }
     }
     if is_review_mode && !review_exit_emitted {
-        let combined = if !review_messages.is_empty() {
-            review_messages.join("\n\n")
-        } else {
-            last_task_message.clone().unwrap_or_default()
-        };
-        let output = if combined.trim().is_empty() {
-            None
-        } else {
-            Some(parse_review_output_event(&combined))
-        };
+        if let Some(output) = take_review_output(&mut review_messages, last_task_message.as_deref()) {
+            review_pass_outputs.push((review_pass_name.take(), output));
+        }
+        let output = crate::review_passes::merge_review_passes(review_pass_outputs);
         exit_review_mode(sess.clone(), sub_id.clone(), output).await;
     }
 
+    finish_agent_task(sess, sub_id, last_task_message).await;
+}
+
+/// Completes the task for `sub_id`, then starts any compaction or queued
+/// user input that was waiting for it.
+async fn finish_agent_task(sess: Arc<Session>, sub_id: String, last_task_message: Option<String>) {
     sess.remove_task(&sub_id);
     let event = sess.make_event(
         &sub_id,
@@ -2509,6 +2742,25 @@ async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>, sub_id: S
     }
 }
 
+/// The text and image count of a user turn, for `pre_turn` hooks.
+fn build_pre_turn_hook_payload(input: &[InputItem]) -> Value {
+    let text: Vec<&str> = input
+        .iter()
+        .filter_map(|item| match item {
+            InputItem::Text { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    let images = input
+        .iter()
+        .filter(|item| !matches!(item, InputItem::Text { .. }))
+        .count();
+    json!({
+        "text": text.join("\n"),
+        "images": images,
+    })
+}
+
 fn strip_skill_contents(
     skills: &[crate::skills::model::SkillMetadata],
 ) -> Vec<crate::skills::model::SkillMetadata> {
@@ -2544,6 +2796,9 @@ async fn run_turn(
     };
 
     let mut retries = 0;
+    // Client and model from `[model_fallback]` once the active model has kept
+    // failing; used for the rest of this turn only.
+    let mut fallback: Option<(ModelClient, String)> = None;
     let mut rate_limit_switch_state = RateLimitSwitchState::default();
     let collaboration_mode_instructions =
         render_collaboration_mode_instructions(tc.collaboration_mode);
@@ -2598,6 +2853,8 @@ async fn run_turn(
         if should_inject_html_sanitizer_guardrails(&attempt_input) {
             prepend_developer_messages.push(HTML_SANITIZER_GUARDRAILS_MESSAGE.to_string());
         }
+        prepend_developer_messages.extend(sess.pinned_facts_message());
+        prepend_developer_messages.extend(sess.scope_message.lock().unwrap().clone());
 
         let mut prompt = Prompt {
             input: attempt_input.clone(),
@@ -2608,7 +2865,8 @@ async fn run_turn(
                 Some(tc.approval_policy),
                 Some(tc.sandbox_policy.clone()),
                 Some(sess.user_shell.clone()),
-            )),
+            )
+            .with_shell_session(sess.shell_session_context())),
             tools: Vec::new(),
             status_items, // Include status items with this request
             base_instructions_override: tc.base_instructions.clone(),
@@ -2618,11 +2876,17 @@ async fn run_turn(
             model_override: None,
             model_family_override: None,
             output_schema: tc.final_output_json_schema.clone(),
+            temperature_override: tc.temperature_override,
             log_tag: Some("codex/turn".to_string()),
             session_id_override: None,
             model_descriptions: sess.model_descriptions.clone(),
         };
 
+        if let Some((_, model)) = fallback.as_ref() {
+            prompt.model_override = Some(model.clone());
+        } else if let Some(model) = tc.model_override.as_ref() {
+            prompt.model_override = Some(model.clone());
+        }
         sess.apply_remote_model_overrides(&mut prompt).await;
 
         let effective_family = prompt
@@ -2663,7 +2927,18 @@ async fn run_turn(
         // Start a new scratchpad for this HTTP attempt
         sess.begin_attempt_scratchpad();
 
-        match try_run_turn(sess, turn_diff_tracker, &sub_id, &prompt, attempt_req).await {
+        let stream_span = tracing::info_span!(
+            target: code_otel::TRACE_TARGET,
+            "model_stream",
+            attempt = attempt_req,
+            input_items = prompt.input.len(),
+        );
+        let client = fallback.as_ref().map_or(&sess.client, |(client, _)| client);
+        let provider_is_local = client.get_provider().local;
+        match try_run_turn(sess, client, turn_diff_tracker, &sub_id, &prompt, attempt_req)
+            .instrument(stream_span)
+            .await
+        {
             Ok(output) => {
                 // Record status items to conversation history after successful turn
                 // This ensures they persist for future requests in the right chronological order
@@ -2872,7 +3147,9 @@ async fn run_turn(
                     CodexErr::Stream(_, _, req) => req.clone(),
                     _ => None,
                 };
-                let is_connectivity = is_connectivity_error(&e);
+                // A local server that is down will not come back by waiting
+                // for the network.
+                let is_connectivity = is_connectivity_error(&e) && !provider_is_local;
                 let drain_scratchpad_into_attempt = |attempt_input: &mut Vec<ResponseItem>| {
                     if let Some(sp) = sess.take_scratchpad() {
                         // Build a set of call_ids we have already included to avoid duplicate calls
@@ -2968,6 +3245,42 @@ async fn run_turn(
                     continue;
                 }
 
+                let fallback_config = tc.client.config().model_fallback.as_ref();
+                if fallback.is_none()
+                    && !is_connectivity
+                    && let Some(fallback_config) = fallback_config
+                    && retries >= fallback_config.after_errors.map_or(max_retries, u64::from)
+                {
+                    let current_model = prompt
+                        .model_override
+                        .clone()
+                        .unwrap_or_else(|| tc.client.get_model());
+                    let provider = fallback_config
+                        .provider
+                        .as_ref()
+                        .and_then(|id| tc.client.config().model_providers.get(id))
+                        .cloned()
+                        .unwrap_or_else(|| sess.client.get_provider());
+                    let notice = format!(
+                        "`{current_model}` failed {retries} times in a row ({e}); switching to `{}` via {} for the rest of this turn. The next turn goes back to `{current_model}`; press Esc to stop this turn and retry it there now.",
+                        fallback_config.model, provider.name,
+                    );
+                    warn!(
+                        error = %e,
+                        fallback_model = fallback_config.model.as_str(),
+                        "model keeps failing - switching to fallback model for this turn"
+                    );
+                    let order = sess.next_background_order(&sub_id, attempt_req, None);
+                    sess.notify_background_event_with_order(&sub_id, order, notice).await;
+                    drain_scratchpad_into_attempt(&mut attempt_input);
+                    fallback = Some((
+                        sess.client.with_provider(provider),
+                        fallback_config.model.clone(),
+                    ));
+                    retries = 0;
+                    continue;
+                }
+
                 if retries < max_retries {
                     retries += 1;
                     let (delay, retry_eta) = match e {
@@ -3228,6 +3541,7 @@ impl Drop for TurnLatencyGuard<'_> {
 
 async fn try_run_turn(
     sess: &Session,
+    client: &ModelClient,
     turn_diff_tracker: &mut TurnDiffTracker,
     sub_id: &str,
     prompt: &Prompt,
@@ -3289,7 +3603,7 @@ async fn try_run_turn(
     };
 
     let mut turn_latency_guard = TurnLatencyGuard::new(sess, attempt_req, prompt.as_ref());
-    let mut stream = match sess.client.clone().stream(&prompt).await {
+    let mut stream = match client.clone().stream(&prompt).await {
         Ok(stream) => stream,
         Err(e) => {
             turn_latency_guard.mark_failed(Some(format!("stream_init_failed: {e}")));
@@ -3304,6 +3618,8 @@ async fn try_run_turn(
     };
 
     let mut output = Vec::new();
+    // Model the server reports answering with, kept for the usage stats.
+    let mut response_model: Option<String> = None;
     loop {
         // Poll the next item from the model stream. We must inspect *both* Ok and Err
         // cases so that transient stream failures (e.g., dropped SSE connection before
@@ -3332,7 +3648,14 @@ async fn try_run_turn(
         };
 
         match event {
-            ResponseEvent::Created { .. } => {}
+            ResponseEvent::Created {
+                response_model: model,
+                ..
+            } => {
+                if model.is_some() {
+                    response_model = model;
+                }
+            }
             ResponseEvent::ServerReasoningIncluded(_included) => {}
             ResponseEvent::OutputItemDone { item, sequence_number, output_index } => {
                 let response =
@@ -3376,19 +3699,39 @@ async fn try_run_turn(
                 response_id: _,
                 token_usage,
             } => {
-                let (new_info, rate_limits, should_emit);
+                let request_model = prompt
+                    .model_override
+                    .clone()
+                    .unwrap_or_else(|| sess.client.get_model());
+                let request_cost = token_usage.as_ref().and_then(|usage| {
+                    sess.client
+                        .config()
+                        .model_pricing_for(&request_model)
+                        .map(|pricing| pricing.cost_usd(usage))
+                });
+                let (new_info, rate_limits, session_cost_usd, should_emit);
                 {
                     let mut state = sess.state.lock().unwrap();
                     let info = TokenUsageInfo::new_or_append(
                         &state.token_usage_info,
                         &token_usage,
                         sess.client.get_model_context_window(),
-                    );
+                    )
+                    .map(|mut info| {
+                        info.requested_model = Some(request_model);
+                        info.latest_response_model = response_model.take();
+                        info
+                    });
+                    if let Some(cost) = request_cost {
+                        state.session_cost_usd = Some(state.session_cost_usd.unwrap_or(0.0) + cost);
+                        state.last_request_cost_usd = cost;
+                    }
                     let limits = state.latest_rate_limits.clone();
                     let emit = info.is_some() || limits.is_some();
                     state.token_usage_info = info.clone();
                     new_info = info;
                     rate_limits = limits;
+                    session_cost_usd = state.session_cost_usd;
                     should_emit = emit;
                 }
 
@@ -3396,6 +3739,7 @@ async fn try_run_turn(
                     let payload = TokenCountEvent {
                         info: new_info,
                         rate_limits,
+                        session_cost_usd,
                     };
                     sess.tx_event
                         .send(sess.make_event(sub_id, EventMsg::TokenCount(payload)))
@@ -3901,6 +4245,9 @@ async fn handle_function_call(
         // unified browser tool
         "browser" => handle_browser_tool(sess, &ctx, arguments).await,
         "web_fetch" => handle_web_fetch(sess, &ctx, arguments).await,
+        "fetch_url" => handle_fetch_url(sess, &ctx, arguments).await,
+        "save_artifact" => handle_save_artifact(sess, &ctx, arguments).await,
+        "run_tests" => handle_run_tests(sess, &ctx, arguments).await,
         "image_view" => handle_image_view(sess, &ctx, arguments).await,
         "wait" => handle_wait(sess, &ctx, arguments).await,
         "gh_run_wait" => handle_gh_run_wait(sess, &ctx, arguments).await,
@@ -4711,7 +5058,7 @@ async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String)
                 timeout: Duration,
                 extra_headers: Option<&[(reqwest::header::HeaderName, &'static str)]>,
             ) -> Result<reqwest::Response, reqwest::Error> {
-                let client = reqwest::Client::builder()
+                let client = crate::http_client::apply_network_config(reqwest::Client::builder())
                     .timeout(timeout)
                     .user_agent(ua)
                     .build()?;
@@ -5435,6 +5782,203 @@ async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String)
     ).await
 }
 
+async fn handle_fetch_url(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
+    let params_for_event = serde_json::from_str::<serde_json::Value>(&arguments).ok();
+    let allowed_domains = sess.tools_config.fetch_url_allowed_domains.clone();
+    let call_id = ctx.call_id.clone();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "fetch_url".to_string(),
+        params_for_event,
+        || async move {
+            #[derive(serde::Deserialize)]
+            struct FetchUrlParams {
+                url: String,
+            }
+
+            let (body, success) = match serde_json::from_str::<FetchUrlParams>(&arguments) {
+                Ok(params) => match crate::url_fetch::fetch_url(
+                    &params.url,
+                    allowed_domains.as_deref(),
+                    crate::url_fetch::DEFAULT_FETCH_MAX_CHARS,
+                )
+                .await
+                {
+                    Ok(page) => (page.to_tool_output().to_string(), true),
+                    Err(err) => (err, false),
+                },
+                Err(e) => (format!("Invalid fetch_url arguments: {e}"), false),
+            };
+            ResponseInputItem::FunctionCallOutput {
+                call_id,
+                output: FunctionCallOutputPayload {
+                    body: FunctionCallOutputBody::Text(body),
+                    success: Some(success),
+                },
+            }
+        },
+    )
+    .await
+}
+
+async fn handle_save_artifact(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
+    use crate::artifacts::ArtifactSource;
+
+    #[derive(serde::Deserialize)]
+    struct SaveArtifactParams {
+        name: String,
+        #[serde(default)]
+        content: Option<String>,
+        #[serde(default)]
+        encoding: Option<String>,
+        #[serde(default)]
+        source_path: Option<String>,
+    }
+
+    let params_for_event = serde_json::from_str::<serde_json::Value>(&arguments).ok();
+    let dir = crate::artifacts::session_artifacts_dir(sess.client.code_home(), &sess.id.to_string());
+    let call_id = ctx.call_id.clone();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "save_artifact".to_string(),
+        params_for_event,
+        || async move {
+            let saved = match serde_json::from_str::<SaveArtifactParams>(&arguments) {
+                Err(e) => Err(format!("Invalid save_artifact arguments: {e}")),
+                Ok(params) => match (params.content.as_deref(), params.source_path) {
+                    (Some(_), Some(_)) => Err("Pass either `content` or `source_path`, not both".to_string()),
+                    (None, None) => Err("save_artifact needs `content` or `source_path`".to_string()),
+                    (None, Some(source_path)) => {
+                        let source = sess.resolve_path(Some(source_path));
+                        crate::artifacts::save_artifact(&dir, &params.name, ArtifactSource::File(&source))
+                    }
+                    (Some(content), None) => match params.encoding.as_deref() {
+                        None | Some("utf8") => crate::artifacts::save_artifact(&dir, &params.name, ArtifactSource::Text(content)),
+                        Some("base64") => crate::artifacts::save_artifact(&dir, &params.name, ArtifactSource::Base64(content)),
+                        Some(other) => Err(format!("Unknown encoding `{other}`; use `utf8` or `base64`")),
+                    },
+                },
+            };
+            let (body, success) = match saved {
+                Ok(artifact) => (
+                    serde_json::json!({
+                        "name": artifact.name,
+                        "path": artifact.path,
+                        "bytes": artifact.bytes,
+                        "dir": dir,
+                    })
+                    .to_string(),
+                    true,
+                ),
+                Err(err) => (err, false),
+            };
+            ResponseInputItem::FunctionCallOutput {
+                call_id,
+                output: FunctionCallOutputPayload {
+                    body: FunctionCallOutputBody::Text(body),
+                    success: Some(success),
+                },
+            }
+        },
+    )
+    .await
+}
+
+async fn handle_run_tests(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
+    use crate::test_runner::TestReport;
+    use crate::test_runner::TestRunner;
+
+    /// Runs stop after this long unless the call sets `timeout_ms`.
+    const DEFAULT_TEST_TIMEOUT_MS: u64 = 20 * 60 * 1000;
+
+    #[derive(serde::Deserialize)]
+    struct RunTestsParams {
+        #[serde(default)]
+        runner: Option<TestRunner>,
+        #[serde(default)]
+        filter: Option<String>,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default)]
+        workdir: Option<String>,
+        #[serde(default)]
+        timeout_ms: Option<u64>,
+    }
+
+    let params_for_event = serde_json::from_str::<serde_json::Value>(&arguments).ok();
+    let call_id = ctx.call_id.clone();
+    let sub_id = ctx.sub_id.clone();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "run_tests".to_string(),
+        params_for_event,
+        || async move {
+            let report = match serde_json::from_str::<RunTestsParams>(&arguments) {
+                Err(e) => Err(format!("Invalid run_tests arguments: {e}")),
+                Ok(params) => {
+                    let workdir = params.workdir.clone().map(|dir| sess.resolve_exec_cwd(Some(dir)));
+                    let detected = crate::test_runner::detect_runner(
+                        workdir.as_deref().unwrap_or_else(|| sess.get_cwd()),
+                    );
+                    let runner_and_dir = match (params.runner, detected) {
+                        (Some(runner), _) => Ok((runner, workdir.unwrap_or_else(|| sess.get_cwd().to_path_buf()))),
+                        (None, Some((runner, dir))) => Ok((runner, workdir.unwrap_or(dir))),
+                        (None, None) => Err(
+                            "No Cargo.toml, jest package.json or pytest configuration found; pass `runner` (cargo, pytest or jest)".to_string(),
+                        ),
+                    };
+                    match runner_and_dir {
+                        Err(err) => Err(err),
+                        Ok((runner, dir)) => {
+                            let command = runner.command(params.filter.as_deref(), &params.args);
+                            sess.run_test_command(
+                                &sub_id,
+                                &call_id,
+                                command.clone(),
+                                dir.clone(),
+                                params.timeout_ms.unwrap_or(DEFAULT_TEST_TIMEOUT_MS),
+                            )
+                            .await
+                            .map(|output| {
+                                let text = sess.redactor.redact(&output.aggregated_output.text).0.into_owned();
+                                TestReport::from_output(
+                                    runner,
+                                    &command,
+                                    dir,
+                                    output.exit_code,
+                                    output.timed_out,
+                                    &text,
+                                )
+                            })
+                        }
+                    }
+                }
+            };
+            let (body, success) = match report {
+                Ok(report) => match serde_json::to_string(&report) {
+                    Ok(json) => (json, report.succeeded()),
+                    Err(err) => (format!("Failed to encode the test report: {err}"), false),
+                },
+                Err(err) => (err, false),
+            };
+            ResponseInputItem::FunctionCallOutput {
+                call_id,
+                output: FunctionCallOutputPayload {
+                    body: FunctionCallOutputBody::Text(body),
+                    success: Some(success),
+                },
+            }
+        },
+    )
+    .await
+}
+
 async fn handle_image_view(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
     use crate::protocol::ViewImageToolCallEvent;
     use serde::Deserialize;
@@ -5810,6 +6354,7 @@ async fn handle_wait(
                             .as_ref()
                             .map(|arc| String::from_utf8_lossy(&arc.lock().unwrap()).to_string())
                             .unwrap_or_default();
+                        let tail_text = sess.redactor.redact(&tail_text).0.into_owned();
                         let msg = if tail_text.is_empty() {
                             format!("Background job {call_id} still running...")
                         } else {
@@ -6779,6 +7324,7 @@ async fn handle_kill(
                 stderr: output.stderr.text.clone(),
                 exit_code: output.exit_code,
                 duration: output.duration,
+                redacted_values: 0,
             });
             let event = Event {
                 id: sub_id_for_end.clone(),
@@ -6814,7 +7360,7 @@ fn to_exec_params(params: ShellToolCallParams, sess: &Session) -> ExecParams {
         .and_then(|p| p.requires_escalated_permissions().then_some(true));
     ExecParams {
         command: params.command,
-        cwd: sess.resolve_path(params.workdir.clone()),
+        cwd: sess.resolve_exec_cwd(params.workdir.clone()),
         timeout_ms,
         env: create_env(&sess.shell_environment_policy),
         with_escalated_permissions,
@@ -9279,8 +9825,33 @@ async fn handle_container_exec_with_params(
                     };
                 }
 
+            let mut action = action;
+            let protected = strip_protected_changes(&mut action, &sess.protected_patch_paths);
+            let protected_note = protected_paths_note(&action.cwd, &protected);
+            if let Some(note) = protected_note.as_ref() {
+                let event = sess.make_event(
+                    &sub_id,
+                    EventMsg::Warning(crate::protocol::WarningEvent { message: note.clone() }),
+                );
+                sess.send_event(event).await;
+                if action.is_empty() {
+                    return ResponseInputItem::FunctionCallOutput {
+                        call_id,
+                        output: FunctionCallOutputPayload {
+                            body: FunctionCallOutputBody::Text(note.clone()),
+                            success: Some(false),
+                        },
+                    };
+                }
+            }
+
             let changes = convert_apply_patch_to_protocol(&action);
             turn_diff_tracker.on_patch_begin(&changes);
+            let mut patched_files: Vec<String> = changes
+                .keys()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect();
+            patched_files.sort();
 
             let mut hook_ctx = ExecCommandContext {
                 sub_id: sub_id.clone(),
@@ -9326,6 +9897,12 @@ async fn handle_container_exec_with_params(
                         output_index,
                         sequence_number: seq_hint,
                     };
+                    let formattable = patch_format::formattable_paths(&changes);
+                    let lsp_files = if sess.lsp.is_some() {
+                        crate::lsp::TouchedFile::from_changes(&changes)
+                    } else {
+                        Vec::new()
+                    };
                     let begin_event = EventMsg::PatchApplyBegin(PatchApplyBeginEvent {
                         call_id: call_id.clone(),
                         auto_approved: run.auto_approved,
@@ -9353,6 +9930,51 @@ async fn handle_container_exec_with_params(
                     );
                     let _ = sess.tx_event.send(event).await;
 
+                    let mut format_reports: Vec<String> = Vec::new();
+                    if run.success && !sess.patch_format_commands.is_empty() {
+                        let outcomes = patch_format::run_format_commands(
+                            &params.cwd,
+                            &sess.patch_format_commands,
+                            &formattable,
+                        )
+                        .await;
+                        for outcome in outcomes {
+                            let report = patch_format::format_report(&params.cwd, &outcome);
+                            if outcome.success {
+                                let order = sess.next_background_order(&sub_id, attempt_req, output_index);
+                                sess
+                                    .notify_background_event_with_order(&sub_id, order, report.clone())
+                                    .await;
+                            } else {
+                                let event = sess.make_event(
+                                    &sub_id,
+                                    EventMsg::Warning(crate::protocol::WarningEvent { message: report.clone() }),
+                                );
+                                sess.send_event(event).await;
+                            }
+                            format_reports.push(report);
+                        }
+                    }
+
+                    if run.success && sess.auto_checkpoint {
+                        record_patch_checkpoint(
+                            sess,
+                            &sub_id,
+                            &call_id,
+                            &params.cwd,
+                            &patched_files,
+                            attempt_req,
+                            output_index,
+                        )
+                        .await;
+                    }
+
+                    let lsp_note = if run.success && !lsp_files.is_empty() {
+                        check_patch_diagnostics(sess, &sub_id, &call_id, &lsp_files).await
+                    } else {
+                        None
+                    };
+
                     let hook_output = ExecToolCallOutput {
                         exit_code: if run.success { 0 } else { 1 },
                         stdout: StreamOutput::new(run.stdout.clone()),
@@ -9381,6 +10003,22 @@ async fn handle_container_exec_with_params(
                         )
                         .await;
 
+                    let post_patch_verdict = if run.success {
+                        sess.run_script_hooks(
+                            &sub_id,
+                            attempt_req,
+                            ScriptHookPoint::PostPatch,
+                            json!({
+                                "call_id": call_id,
+                                "files": patched_files,
+                                "stdout": run.stdout,
+                            }),
+                        )
+                        .await
+                    } else {
+                        ScriptHookVerdict::default()
+                    };
+
                     if let Ok(Some(unified_diff)) = turn_diff_tracker.get_unified_diff() {
                         let diff_event = sess.make_event(
                             &sub_id,
@@ -9403,12 +10041,43 @@ async fn handle_container_exec_with_params(
                             }
                             content.push_str(&summary);
                         }
+                    for report in format_reports {
+                        if !content.is_empty() {
+                            content.push('\n');
+                        }
+                        content.push_str(&report);
+                    }
+                    if let Some(note) = lsp_note {
+                        if !content.is_empty() {
+                            content.push('\n');
+                        }
+                        content.push_str(&note);
+                    }
+                    if let Some(note) = protected_note {
+                        if !content.is_empty() {
+                            content.push('\n');
+                        }
+                        content.push_str(&note);
+                    }
+                    // The patch is already on disk, so a post_patch veto can
+                    // only report it as failed and ask the model to revise.
+                    let hook_notes = post_patch_verdict
+                        .denial()
+                        .map(|denial| format!("The applied patch was {denial}. Revise or revert the change."))
+                        .into_iter()
+                        .chain(post_patch_verdict.annotation_text());
+                    for note in hook_notes {
+                        if !content.is_empty() {
+                            content.push('\n');
+                        }
+                        content.push_str(&note);
+                    }
 
                     return ResponseInputItem::FunctionCallOutput {
                         call_id,
                         output: FunctionCallOutputPayload {
                             body: FunctionCallOutputBody::Text(content),
-                            success: Some(run.success),
+                            success: Some(run.success && post_patch_verdict.denied.is_none()),
                         },
                     };
                 }
@@ -9429,6 +10098,29 @@ async fn handle_container_exec_with_params(
         MaybeApplyPatchVerified::NotApplyPatch => {}
     }
 
+    let pre_exec_verdict = sess
+        .run_script_hooks(
+            &sub_id,
+            attempt_req,
+            ScriptHookPoint::PreExec,
+            json!({
+                "call_id": call_id,
+                "command": params.command,
+                "cwd": params.cwd.to_string_lossy(),
+                "justification": params.justification,
+            }),
+        )
+        .await;
+    if let Some(denial) = pre_exec_verdict.denial() {
+        return ResponseInputItem::FunctionCallOutput {
+            call_id,
+            output: FunctionCallOutputPayload {
+                body: FunctionCallOutputBody::Text(format!("exec command {denial}")),
+                success: None,
+            },
+        };
+    }
+
     let safety = {
         let state = sess.state.lock().unwrap();
         let command_safety_context =
@@ -9541,6 +10233,7 @@ async fn handle_container_exec_with_params(
     };
 
     let display_label = crate::util::strip_bash_lc_and_escape(&exec_command_context.command_for_display);
+    let params = sess.wrap_for_persistent_shell(params);
     let params = maybe_run_with_user_profile(params, sess);
 
     // ToolBefore hook for shell/container.exec commands
@@ -9630,6 +10323,7 @@ async fn handle_container_exec_with_params(
     let suppress_event_flag_task = suppress_event_flag.clone();
     let display_label_task = display_label.clone();
     let tool_output_max_bytes = sess.tool_output_max_bytes;
+    let redactor = sess.redactor.clone();
     let task_handle = tokio::spawn(async move {
         // Build stdout stream with tail capture. We cannot stamp via `Session` here,
         // but deltas will be delivered with neutral ordering which the UI tolerates.
@@ -9642,6 +10336,7 @@ async fn handle_container_exec_with_params(
                 tx_event: tx_event.clone(),
                 session: None,
                 tail_buf: Some(tail_buf_task.clone()),
+                redactor: Some(redactor.clone()),
                 order: Some(order_meta_for_deltas.clone()),
                 spool_dir: exec_spool_dir_for_task.clone(),
             })
@@ -9659,7 +10354,7 @@ async fn handle_container_exec_with_params(
         .await;
 
         // Normalize to ExecToolCallOutput
-        let (out, exit_code) = match res {
+        let (mut out, exit_code) = match res {
             Ok(o) => { let exit = o.exit_code; (o, exit) },
             Err(CodexErr::Sandbox(SandboxErr::Timeout { output })) => (output.as_ref().clone(), 124),
             Err(e) => {
@@ -9677,6 +10372,15 @@ async fn handle_container_exec_with_params(
                 )
             }
         };
+        let redacted_values = redactor.redact_exec_output(&mut out);
+
+        // Pick up the cwd/exports the command left behind before anyone
+        // waiting on the result issues the next exec.
+        if let Some(sess_arc) = sess_for_hooks.as_ref()
+            && let Some(shell) = sess_arc.persistent_shell.as_ref()
+        {
+            shell.refresh();
+        }
 
         // Emit END event directly
         let end_msg = EventMsg::ExecCommandEnd(ExecCommandEndEvent {
@@ -9685,6 +10389,7 @@ async fn handle_container_exec_with_params(
             stderr: out.stderr.text.clone(),
             exit_code,
             duration: out.duration,
+            redacted_values,
         });
         let ev = Event { id: sub_id_for_events.clone(), event_seq: 0, msg: end_msg, order: Some(order_meta_for_end) };
         let _ = tx_event.send(ev).await;
@@ -9821,6 +10526,7 @@ async fn handle_container_exec_with_params(
     // Still running: mark as backgrounded and return background notice + tail and instructions
     backgrounded.store(true, std::sync::atomic::Ordering::Relaxed);
     let tail = String::from_utf8_lossy(&tail_buf.lock().unwrap()).to_string();
+    let tail = sess.redactor.redact(&tail).0.into_owned();
     let header = format!(
         "Command running in background (call_id={call_id}).\nTo wait: wait(call_id=\"{call_id}\")\nYou can continue other work or wait. You'll be notified when the command completes."
     );
@@ -9832,6 +10538,86 @@ async fn handle_container_exec_with_params(
     ResponseInputItem::FunctionCallOutput { call_id: call_id.clone(), output: FunctionCallOutputPayload { body: FunctionCallOutputBody::Text(msg), success: Some(true) } }
 }
 
+/// Collects `[lsp]` diagnostics for the files a patch touched and shows them.
+/// Returns the errors the patch introduced, for the apply_patch output.
+async fn check_patch_diagnostics(
+    sess: &Session,
+    sub_id: &str,
+    call_id: &str,
+    files: &[crate::lsp::TouchedFile],
+) -> Option<String> {
+    let lsp = sess.lsp.as_ref()?;
+    let result = lsp.check(files).await;
+    if result.files.is_empty() && result.errors.is_empty() {
+        return None;
+    }
+    let note = crate::lsp::new_errors_note(&result.files, sess.get_cwd());
+    let event = sess.make_event(
+        sub_id,
+        EventMsg::LspDiagnostics(crate::protocol::LspDiagnosticsEvent {
+            call_id: call_id.to_string(),
+            files: result.files,
+            errors: result.errors,
+        }),
+    );
+    sess.send_event(event).await;
+    note
+}
+
+/// Commits the working tree to the session's checkpoint ref after a patch
+/// applies (`[git] auto_checkpoint`). Outside a git repository this is a no-op.
+async fn record_patch_checkpoint(
+    sess: &Session,
+    sub_id: &str,
+    call_id: &str,
+    cwd: &Path,
+    patched_files: &[String],
+    attempt_req: u64,
+    output_index: Option<u32>,
+) {
+    let files: Vec<String> = patched_files
+        .iter()
+        .map(|file| {
+            Path::new(file)
+                .strip_prefix(cwd)
+                .map(|relative| relative.to_string_lossy().to_string())
+                .unwrap_or_else(|_| file.clone())
+        })
+        .collect();
+    let message = crate::checkpoints::checkpoint_message(sess.id, sub_id, call_id, &files);
+    let session_id = sess.id;
+    let repo = cwd.to_path_buf();
+    let result = tokio::task::spawn_blocking(move || {
+        crate::checkpoints::record_checkpoint(&repo, session_id, &message)
+    })
+    .await
+    .unwrap_or_else(|err| Err(format!("checkpoint task failed: {err}")));
+
+    match result {
+        Ok(None) => {}
+        Ok(Some(commit)) => {
+            let short: String = commit.chars().take(8).collect();
+            let reference = crate::checkpoints::checkpoint_ref(session_id);
+            let order = sess.next_background_order(sub_id, attempt_req, output_index);
+            sess.notify_background_event_with_order(
+                sub_id,
+                order,
+                format!("Checkpoint {short} recorded on {reference}"),
+            )
+            .await;
+        }
+        Err(err) => {
+            let event = sess.make_event(
+                sub_id,
+                EventMsg::Warning(crate::protocol::WarningEvent {
+                    message: format!("Could not record a git checkpoint for this patch: {err}"),
+                }),
+            );
+            sess.send_event(event).await;
+        }
+    }
+}
+
 #[allow(dead_code)]
 async fn handle_sandbox_error(
     turn_diff_tracker: &mut TurnDiffTracker,
@@ -9999,6 +10785,7 @@ async fn handle_sandbox_error(
                         tx_event: sess.tx_event.clone(),
                         session: None,
                         tail_buf: None,
+                        redactor: Some(sess.redactor.clone()),
                         order: Some(crate::protocol::OrderMeta { request_ordinal: attempt_req, output_index: None, sequence_number: None }),
                         spool_dir: if sess.client.debug_enabled() {
                             Some(sess.client.code_home().join("debug_logs").join("exec"))
@@ -10158,6 +10945,9 @@ pub(super) async fn capture_browser_screenshot(
     if !browser_manager.is_enabled().await {
         return Err("Browser manager is not enabled".to_string());
     }
+    if crate::metered::is_metered() {
+        return Err("screenshots are off on a metered connection".to_string());
+    }
 
     // Get current URL first
     let url = browser_manager
@@ -10634,6 +11424,10 @@ async fn handle_browser_tool(sess: &Session, ctx: &ToolCallCtx, arguments: Strin
         "cdp" => handle_browser_cdp(sess, ctx, payload_string.clone()).await,
         "cleanup" => handle_browser_cleanup(sess, ctx).await,
         "fetch" => handle_web_fetch(sess, ctx, payload_string.clone()).await,
+        "read" => handle_browser_read(sess, ctx, payload_string.clone()).await,
+        "tabs" | "new_tab" | "switch_tab" | "close_tab" | "snapshot" => {
+            handle_browser_tabs(sess, ctx, &action_lower, payload_string.clone()).await
+        }
         _ => ResponseInputItem::FunctionCallOutput {
             call_id: ctx.call_id.clone(),
             output: FunctionCallOutputPayload {
@@ -10787,6 +11581,268 @@ async fn get_browser_manager_for_session(
     code_browser::global::get_browser_manager().await
 }
 
+/// Characters of extracted page text `read` returns unless `max_chars` says otherwise.
+const BROWSER_READ_DEFAULT_MAX_CHARS: usize = 20_000;
+
+/// `read`: the active page as text (rendered DOM text or an accessibility
+/// outline), optionally with a screenshot alongside.
+async fn handle_browser_read(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
+    let params: Option<Value> = serde_json::from_str(&arguments).ok();
+    let format = params
+        .as_ref()
+        .and_then(|json| json.get("format"))
+        .map(|value| serde_json::from_value::<code_browser::extract::ExtractFormat>(value.clone()));
+    let with_screenshot = params
+        .as_ref()
+        .and_then(|json| json.get("screenshot"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let max_chars = params
+        .as_ref()
+        .and_then(|json| json.get("max_chars"))
+        .and_then(Value::as_u64)
+        .map_or(BROWSER_READ_DEFAULT_MAX_CHARS, |max| max as usize);
+    let call_id = ctx.call_id.clone();
+
+    execute_custom_tool(sess, ctx, "browser_read".to_string(), params, || async move {
+        let output = |body: FunctionCallOutputBody, success: bool| ResponseInputItem::FunctionCallOutput {
+            call_id: call_id.clone(),
+            output: FunctionCallOutputPayload {
+                body,
+                success: Some(success),
+            },
+        };
+        let format = match format.transpose() {
+            Ok(format) => format.unwrap_or_default(),
+            Err(_) => {
+                return output(
+                    FunctionCallOutputBody::Text(
+                        "Unsupported format (expected 'text' or 'accessibility')".to_string(),
+                    ),
+                    false,
+                );
+            }
+        };
+        let Some(browser_manager) = get_browser_manager_for_session(sess).await else {
+            return output(
+                FunctionCallOutputBody::Text("Browser is not currently open.".to_string()),
+                false,
+            );
+        };
+
+        let extract = match browser_manager.extract_page(format).await {
+            Ok(extract) => extract,
+            Err(e) => {
+                return output(
+                    FunctionCallOutputBody::Text(format!("Failed to read page: {e}")),
+                    false,
+                );
+            }
+        };
+        let (content, truncated) =
+            code_browser::extract::truncate_extract(&extract.content, max_chars);
+        let mut text = format!("URL: {}\n", extract.url);
+        if let Some(title) = &extract.title {
+            text.push_str(&format!("Title: {title}\n"));
+        }
+        text.push('\n');
+        if content.trim().is_empty() {
+            text.push_str("(no readable text on this page)");
+        } else {
+            text.push_str(&content);
+        }
+        if truncated {
+            text.push_str(&format!(
+                "\n\n[truncated to {max_chars} of {} characters]",
+                extract.content.chars().count()
+            ));
+        }
+
+        if !with_screenshot {
+            return output(FunctionCallOutputBody::Text(text), true);
+        }
+        let mut items = vec![FunctionCallOutputContentItem::InputText { text }];
+        match capture_browser_screenshot(sess).await {
+            Ok((path, url)) => match std::fs::read(&path) {
+                Ok(bytes) => {
+                    add_pending_screenshot(sess, path.clone(), url);
+                    let mime = mime_guess::from_path(&path)
+                        .first()
+                        .map(|m| m.to_string())
+                        .unwrap_or_else(|| "image/png".to_string());
+                    let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+                    items.push(FunctionCallOutputContentItem::InputImage {
+                        image_url: format!("data:{mime};base64,{encoded}"),
+                    });
+                }
+                Err(e) => items.push(FunctionCallOutputContentItem::InputText {
+                    text: format!("(screenshot unavailable: {e})"),
+                }),
+            },
+            Err(e) => items.push(FunctionCallOutputContentItem::InputText {
+                text: format!("(screenshot unavailable: {e})"),
+            }),
+        }
+        output(FunctionCallOutputBody::ContentItems(items), true)
+    })
+    .await
+}
+
+/// `tabs`, `new_tab`, `switch_tab`, `close_tab` and `snapshot`. Tabs are
+/// addressed by the 1-based `tab` index that `tabs` lists; every other
+/// browser action works on the active tab.
+async fn handle_browser_tabs(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    action: &str,
+    arguments: String,
+) -> ResponseInputItem {
+    let params: Option<Value> = serde_json::from_str(&arguments).ok();
+    let tab = params
+        .as_ref()
+        .and_then(|json| json.get("tab"))
+        .and_then(Value::as_u64)
+        .map(|index| index as usize);
+    let url = params
+        .as_ref()
+        .and_then(|json| json.get("url"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let call_id = ctx.call_id.clone();
+    let action = action.to_string();
+
+    execute_custom_tool(sess, ctx, format!("browser_{action}"), params, || async move {
+        let output = |body: FunctionCallOutputBody, success: bool| ResponseInputItem::FunctionCallOutput {
+            call_id: call_id.clone(),
+            output: FunctionCallOutputPayload {
+                body,
+                success: Some(success),
+            },
+        };
+        let Some(browser_manager) = get_browser_manager_for_session(sess).await else {
+            return output(
+                FunctionCallOutputBody::Text("Browser is not currently open.".to_string()),
+                false,
+            );
+        };
+
+        let result = match (action.as_str(), tab) {
+            ("tabs", _) => Ok(String::new()),
+            ("new_tab", _) => browser_manager
+                .open_tab(url.as_deref().unwrap_or("about:blank"))
+                .await
+                .map(|opened| format!("Opened tab {}: {}", opened.index, opened.url))
+                .map_err(|e| format!("Failed to open tab: {e}")),
+            ("switch_tab", Some(index)) => browser_manager
+                .switch_tab(index)
+                .await
+                .map(|active| format!("Switched to tab {}: {}", active.index, active.url))
+                .map_err(|e| format!("Failed to switch tab: {e}")),
+            ("close_tab", Some(index)) => browser_manager
+                .close_tab(index)
+                .await
+                .map(|()| format!("Closed tab {index}"))
+                .map_err(|e| format!("Failed to close tab: {e}")),
+            ("snapshot", _) => {
+                return match browser_tab_snapshot(sess, &browser_manager, tab).await {
+                    Ok(items) => output(FunctionCallOutputBody::ContentItems(items), true),
+                    Err(err) => output(FunctionCallOutputBody::Text(err), false),
+                };
+            }
+            (other, _) => Err(format!("action={other} requires 'tab' (see action=tabs)")),
+        };
+
+        match result {
+            Ok(summary) => {
+                let tabs = match browser_manager.list_tabs().await {
+                    Ok(tabs) => format_browser_tabs(&tabs),
+                    Err(e) => format!("Failed to list tabs: {e}"),
+                };
+                let text = if summary.is_empty() {
+                    tabs
+                } else {
+                    format!("{summary}\n\n{tabs}")
+                };
+                output(FunctionCallOutputBody::Text(text), true)
+            }
+            Err(err) => output(FunctionCallOutputBody::Text(err), false),
+        }
+    })
+    .await
+}
+
+fn format_browser_tabs(tabs: &[code_browser::BrowserTab]) -> String {
+    if tabs.is_empty() {
+        return "No tabs open.".to_string();
+    }
+    let mut text = "Open tabs (* = active):".to_string();
+    for tab in tabs {
+        let marker = if tab.active { '*' } else { ' ' };
+        match tab.title.as_deref() {
+            Some(title) => text.push_str(&format!("\n{marker} {}. {title} — {}", tab.index, tab.url)),
+            None => text.push_str(&format!("\n{marker} {}. {}", tab.index, tab.url)),
+        }
+    }
+    text
+}
+
+/// Screenshots tab `tab` (the active tab when `None`) and returns it with a
+/// caption, also adding it to the TUI's screenshot timeline.
+async fn browser_tab_snapshot(
+    sess: &Session,
+    browser_manager: &code_browser::BrowserManager,
+    tab: Option<usize>,
+) -> Result<Vec<FunctionCallOutputContentItem>, String> {
+    let tabs = browser_manager.list_tabs().await.unwrap_or_default();
+    let index = tab.or_else(|| tabs.iter().find(|t| t.active).map(|t| t.index));
+    let (paths, url) = match index {
+        Some(index) => browser_manager.capture_tab_screenshot(index).await,
+        None => browser_manager
+            .capture_screenshot_with_url()
+            .await
+            .map(|(paths, url)| (paths, url.unwrap_or_default())),
+    }
+    .map_err(|e| format!("Failed to capture snapshot: {e}"))?;
+    let path = paths
+        .into_iter()
+        .next()
+        .ok_or_else(|| "Snapshot capture returned no image".to_string())?;
+    let bytes = std::fs::read(&path)
+        .map_err(|e| format!("Failed to read snapshot {}: {e}", path.display()))?;
+
+    let captured_at = OffsetDateTime::now_utc()
+        .format(&Rfc3339)
+        .unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string());
+    let mut snapshot = BrowserSnapshot::new(url.clone(), captured_at);
+    snapshot.title = index
+        .and_then(|index| tabs.iter().find(|t| t.index == index))
+        .and_then(|t| t.title.clone());
+    if tabs.len() > 1 {
+        snapshot.tabs = tabs;
+    }
+    let browser_stream_id = {
+        let mut state = sess.state.lock().unwrap();
+        state.context_stream_ids.browser_stream_id(sess.id)
+    };
+    sess.emit_browser_snapshot_event(&browser_stream_id, &snapshot, &path);
+
+    let caption = match index {
+        Some(index) => format!("Snapshot of tab {index}: {url}"),
+        None => format!("Snapshot of the active tab: {url}"),
+    };
+    let mime = mime_guess::from_path(&path)
+        .first()
+        .map(|m| m.to_string())
+        .unwrap_or_else(|| "image/png".to_string());
+    let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+    Ok(vec![
+        FunctionCallOutputContentItem::InputText { text: caption },
+        FunctionCallOutputContentItem::InputImage {
+            image_url: format!("data:{mime};base64,{encoded}"),
+        },
+    ])
+}
+
 async fn handle_browser_close(sess: &Session, ctx: &ToolCallCtx) -> ResponseInputItem {
     let sess_clone = sess;
     let call_id_clone = ctx.call_id.clone();
@@ -12807,6 +13863,8 @@ fn parse_legacy_status_snapshot(item: &ResponseItem) -> Option<EnvironmentContex
                     shell: None,
                     git_branch: branch,
                     reasoning_effort: None,
+                    shell_session: None,
+                    providers: BTreeMap::new(),
                 });
             }
         }
@@ -77,6 +77,157 @@ pub(crate) fn normalize_command_for_persistence(command: &[String]) -> Vec<Strin
     }
 }
 
+/// One pipeline stage of a PowerShell script, split out for the approval preview.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PowerShellPreviewStage {
+    pub text: String,
+    /// Whether the stage invokes a cmdlet (or alias) that deletes or overwrites data.
+    pub destructive: bool,
+}
+
+/// Splits the script of a PowerShell wrapper command into statements, each a
+/// list of pipeline stages. Returns `None` when `command` is not a PowerShell
+/// invocation.
+///
+/// This is a display helper, not a parser: it only understands quoting and
+/// bracket nesting well enough to avoid splitting inside strings and blocks.
+pub fn powershell_preview_statements(
+    command: &[String],
+) -> Option<Vec<Vec<PowerShellPreviewStage>>> {
+    let script = extract_powershell_script(command)?;
+    let statements = split_powershell_statements(&script)
+        .into_iter()
+        .map(|statement| {
+            statement
+                .into_iter()
+                .map(|text| PowerShellPreviewStage {
+                    destructive: is_destructive_powershell_stage(&text),
+                    text,
+                })
+                .collect()
+        })
+        .collect();
+    Some(statements)
+}
+
+fn split_powershell_statements(script: &str) -> Vec<Vec<String>> {
+    let mut statements: Vec<Vec<String>> = Vec::new();
+    let mut stages: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut depth = 0usize;
+    let mut chars = script.chars().peekable();
+
+    fn push_stage(stages: &mut Vec<String>, current: &mut String) {
+        let stage = current.trim();
+        if !stage.is_empty() {
+            stages.push(stage.to_string());
+        }
+        current.clear();
+    }
+    fn push_statement(statements: &mut Vec<Vec<String>>, stages: &mut Vec<String>) {
+        if !stages.is_empty() {
+            statements.push(std::mem::take(stages));
+        }
+    }
+
+    while let Some(ch) = chars.next() {
+        if let Some(q) = quote {
+            current.push(ch);
+            // Backtick escapes the next character inside double quotes.
+            if ch == '`' && q == '"' {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            } else if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+        match ch {
+            '\'' | '"' => {
+                quote = Some(ch);
+                current.push(ch);
+            }
+            '`' => {
+                current.push(ch);
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            '(' | '{' | '[' => {
+                depth += 1;
+                current.push(ch);
+            }
+            ')' | '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                current.push(ch);
+            }
+            '|' if depth == 0 && chars.peek() == Some(&'|') => {
+                chars.next();
+                push_stage(&mut stages, &mut current);
+                push_statement(&mut statements, &mut stages);
+            }
+            '&' if depth == 0 && chars.peek() == Some(&'&') => {
+                chars.next();
+                push_stage(&mut stages, &mut current);
+                push_statement(&mut statements, &mut stages);
+            }
+            '|' if depth == 0 => push_stage(&mut stages, &mut current),
+            ';' | '\n' | '\r' if depth == 0 => {
+                push_stage(&mut stages, &mut current);
+                push_statement(&mut statements, &mut stages);
+            }
+            _ => current.push(ch),
+        }
+    }
+    push_stage(&mut stages, &mut current);
+    push_statement(&mut statements, &mut stages);
+    statements
+}
+
+fn is_destructive_powershell_stage(stage: &str) -> bool {
+    let first = stage
+        .trim_start_matches(['&', '.', ' '])
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .trim_matches(['\'', '"'])
+        .to_ascii_lowercase();
+    matches!(
+        first.as_str(),
+        "remove-item"
+            | "ri"
+            | "rm"
+            | "rmdir"
+            | "rd"
+            | "del"
+            | "erase"
+            | "remove-itemproperty"
+            | "set-content"
+            | "sc"
+            | "clear-content"
+            | "clc"
+            | "out-file"
+            | "clear-item"
+            | "cli"
+            | "move-item"
+            | "mi"
+            | "mv"
+            | "move"
+            | "rename-item"
+            | "ren"
+            | "rni"
+            | "stop-process"
+            | "spps"
+            | "kill"
+            | "format-volume"
+            | "clear-disk"
+            | "stop-computer"
+            | "restart-computer"
+    )
+}
+
 fn parse_shell_lc_plain_commands(command: &[String]) -> Option<Vec<Vec<String>>> {
     let script = extract_shell_wrapper_script(command)?;
     let tree = bash::try_parse_bash(&script)?;
@@ -271,6 +422,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn powershell_preview_splits_statements_and_pipelines() {
+        let command = vec![
+            "pwsh".to_string(),
+            "-NoProfile".to_string(),
+            "-Command".to_string(),
+            "Get-ChildItem *.log | Remove-Item -Force; Write-Host 'a;b | c'".to_string(),
+        ];
+
+        let statements = powershell_preview_statements(&command).expect("powershell");
+        assert_eq!(
+            statements,
+            vec![
+                vec![
+                    PowerShellPreviewStage {
+                        text: "Get-ChildItem *.log".to_string(),
+                        destructive: false,
+                    },
+                    PowerShellPreviewStage {
+                        text: "Remove-Item -Force".to_string(),
+                        destructive: true,
+                    },
+                ],
+                vec![PowerShellPreviewStage {
+                    text: "Write-Host 'a;b | c'".to_string(),
+                    destructive: false,
+                }],
+            ]
+        );
+    }
+
+    #[test]
+    fn powershell_preview_keeps_script_blocks_intact() {
+        let command = vec![
+            "powershell.exe".to_string(),
+            "-Command".to_string(),
+            "if ($x) { Set-Content a.txt 1; Write-Host ok }\nSet-Content b.txt 2".to_string(),
+        ];
+
+        let statements = powershell_preview_statements(&command).expect("powershell");
+        assert_eq!(statements.len(), 2);
+        assert!(!statements[0][0].destructive);
+        assert!(statements[1][0].destructive);
+        assert_eq!(
+            powershell_preview_statements(&["bash".to_string(), "-lc".to_string(), "ls".to_string()]),
+            None
+        );
+    }
+
     #[test]
     fn preserves_non_shell_commands() {
         let command = vec!["cargo".to_string(), "fmt".to_string()];
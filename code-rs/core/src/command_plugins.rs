@@ -0,0 +1,235 @@
+//! External slash commands discovered under `$CODE_HOME/commands`.
+//!
+//! Every executable file in the directory becomes `/<file stem>`. An optional
+//! `<file stem>.toml` manifest next to it describes the command:
+//!
+//! ```toml
+//! description = "Pull a ticket into the conversation"
+//! argument_hint = "<ticket-id>"
+//! output = "message"   # or "composer"
+//! timeout_ms = 30000
+//! ```
+//!
+//! Running `/name args` executes the program with the shell-split `args` in
+//! the session's working directory. Its stdout is either sent as the user's
+//! message or placed in the composer for editing.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::Deserialize;
+use tokio::process::Command;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_ERROR_CHARS: usize = 2_000;
+
+/// Where a command's stdout goes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandPluginOutput {
+    /// Submitted as the user's message.
+    #[default]
+    Message,
+    /// Placed in the composer for the user to edit and send.
+    Composer,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandPlugin {
+    pub name: String,
+    pub program: PathBuf,
+    pub description: Option<String>,
+    pub argument_hint: Option<String>,
+    pub output: CommandPluginOutput,
+    pub timeout: Duration,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CommandPluginManifest {
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    argument_hint: Option<String>,
+    #[serde(default)]
+    output: CommandPluginOutput,
+    #[serde(default)]
+    timeout_ms: Option<u64>,
+}
+
+/// Return the default commands directory: `$CODE_HOME/commands`.
+/// If `CODE_HOME` cannot be resolved, returns `None`.
+pub fn default_commands_dir() -> Option<PathBuf> {
+    crate::config::find_code_home()
+        .ok()
+        .map(|home| crate::config::resolve_code_path_for_read(&home, Path::new("commands")))
+}
+
+/// Discover command plugins in `dir`, sorted by name. Missing or unreadable
+/// directories yield nothing; a malformed manifest hides only its command.
+/// Cheap enough to call whenever the command list is shown, so new commands
+/// show up without a restart.
+pub fn discover_command_plugins(dir: &Path) -> Vec<CommandPlugin> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut plugins: Vec<CommandPlugin> = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !entry.file_type().map(|ft| ft.is_file() || ft.is_symlink()).unwrap_or(false)
+            || !is_executable(&path)
+        {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if !is_valid_name(name) || plugins.iter().any(|plugin| plugin.name == name) {
+            continue;
+        }
+        let manifest_path = dir.join(format!("{name}.toml"));
+        let manifest = match std::fs::read_to_string(&manifest_path) {
+            Ok(text) => match toml::from_str::<CommandPluginManifest>(&text) {
+                Ok(manifest) => manifest,
+                Err(err) => {
+                    tracing::warn!("ignoring /{name}: invalid {}: {err}", manifest_path.display());
+                    continue;
+                }
+            },
+            Err(_) => CommandPluginManifest::default(),
+        };
+        plugins.push(CommandPlugin {
+            name: name.to_string(),
+            program: path,
+            description: manifest.description,
+            argument_hint: manifest.argument_hint,
+            output: manifest.output,
+            timeout: manifest
+                .timeout_ms
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_TIMEOUT),
+        });
+    }
+    plugins.sort_by(|a, b| a.name.cmp(&b.name));
+    plugins
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::metadata(path)
+        .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            ["exe", "cmd", "bat", "com"]
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+}
+
+/// Runs `plugin` with `args` (split like a shell would) and returns its
+/// stdout. A non-zero exit, a timeout or unparseable arguments are errors.
+pub async fn run_command_plugin(
+    plugin: &CommandPlugin,
+    args: &str,
+    cwd: &Path,
+) -> Result<String, String> {
+    let argv = shlex::split(args).ok_or_else(|| format!("could not parse arguments `{args}`"))?;
+    let mut cmd = Command::new(&plugin.program);
+    cmd.args(&argv)
+        .current_dir(cwd)
+        .env("CODE_COMMAND_NAME", &plugin.name)
+        .env("CODE_COMMAND_ARGS", args)
+        .env("CODE_SESSION_CWD", cwd)
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+    let output = match tokio::time::timeout(plugin.timeout, cmd.output()).await {
+        Err(_) => return Err(format!("timed out after {}ms", plugin.timeout.as_millis())),
+        Ok(Err(err)) => return Err(format!("failed to start: {err}")),
+        Ok(Ok(output)) => output,
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let mut stderr: String = stderr.trim().chars().take(MAX_ERROR_CHARS).collect();
+        if stderr.is_empty() {
+            stderr = "no error output".to_string();
+        }
+        return Err(match output.status.code() {
+            Some(code) => format!("exited with status {code}: {stderr}"),
+            None => format!("terminated by a signal: {stderr}"),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::tempdir;
+
+    fn write_script(dir: &Path, file: &str, body: &str) -> PathBuf {
+        let path = dir.join(file);
+        std::fs::write(&path, format!("#!/bin/sh\n{body}\n")).expect("write script");
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+        path
+    }
+
+    #[test]
+    fn discovers_executables_with_manifests() {
+        let tmp = tempdir().expect("tempdir");
+        let dir = tmp.path();
+        write_script(dir, "ticket.sh", "echo ticket");
+        write_script(dir, "standup", "echo standup");
+        write_script(dir, "bad name", "echo nope");
+        std::fs::write(dir.join("notes.txt"), "not executable").expect("write");
+        std::fs::write(
+            dir.join("ticket.toml"),
+            "description = \"Fetch a ticket\"\nargument_hint = \"<id>\"\noutput = \"composer\"\n",
+        )
+        .expect("write manifest");
+
+        let plugins = discover_command_plugins(dir);
+        let names: Vec<&str> = plugins.iter().map(|plugin| plugin.name.as_str()).collect();
+        assert_eq!(names, vec!["standup", "ticket"]);
+        assert_eq!(plugins[0].output, CommandPluginOutput::Message);
+        assert_eq!(plugins[1].description.as_deref(), Some("Fetch a ticket"));
+        assert_eq!(plugins[1].argument_hint.as_deref(), Some("<id>"));
+        assert_eq!(plugins[1].output, CommandPluginOutput::Composer);
+    }
+
+    #[tokio::test]
+    async fn runs_with_split_arguments_and_reports_failures() {
+        let tmp = tempdir().expect("tempdir");
+        let dir = tmp.path();
+        write_script(dir, "echoargs", "printf '%s|' \"$@\"; echo \"$CODE_COMMAND_NAME\"");
+        write_script(dir, "fail", "echo broken >&2; exit 3");
+        let plugins = discover_command_plugins(dir);
+
+        let output = run_command_plugin(&plugins[0], "one 'two words'", dir)
+            .await
+            .expect("run");
+        assert_eq!(output, "one|two words|echoargs");
+
+        let err = run_command_plugin(&plugins[1], "", dir)
+            .await
+            .expect_err("fails");
+        assert_eq!(err, "exited with status 3: broken");
+    }
+}
@@ -12,8 +12,21 @@ use crate::config_types::BrowserConfig;
 use crate::config_types::ClientTools;
 use crate::config_types::Notice;
 use crate::config_types::History;
+use crate::config_types::ModelOverrides;
+use crate::config_types::ModelFallbackConfig;
+use crate::config_types::ModelPricing;
+use crate::config_types::LimitsConfig;
 use crate::config_types::GithubConfig;
+use crate::config_types::GitConfig;
+use crate::config_types::LspConfig;
+use crate::config_types::RepoMapConfig;
+use crate::config_types::EnvironmentContextConfig;
+use crate::config_types::AutoReviewConfig;
+use crate::config_types::ReviewPassConfig;
 use crate::config_types::ValidationConfig;
+use crate::config_types::HooksConfig;
+use crate::config_types::WebhookConfig;
+use crate::config_types::PatchesConfig;
 use crate::config_types::McpServerConfig;
 use crate::config_types::Notifications;
 use crate::config_types::OtelConfig;
@@ -33,6 +46,11 @@ use crate::config_types::TextVerbosity;
 use crate::config_types::Tui;
 use crate::config_types::UriBasedFileOpener;
 use crate::config_types::ConfirmGuardConfig;
+use crate::config_types::RedactionConfig;
+use crate::config_types::DlpConfig;
+use crate::config_types::NetworkConfig;
+use crate::config_types::WireLogConfig;
+use crate::config_types::MeteredConnection;
 use crate::config_types::Personality;
 use crate::config_types::DEFAULT_OTEL_ENVIRONMENT;
 use crate::git_info::resolve_root_git_project_for_trust;
@@ -61,6 +79,8 @@ use toml::Value as TomlValue;
 
 mod builder;
 mod defaults;
+mod interpolation;
+mod migrations;
 pub mod schema;
 pub mod service;
 mod sources;
@@ -75,7 +95,9 @@ pub use sources::{
     add_project_allowed_command,
     find_code_home,
     list_mcp_servers,
+    load_auto_review_settings,
     load_config_as_toml,
+    load_config_profiles,
     load_global_mcp_servers,
     persist_model_selection,
     persist_shell,
@@ -96,6 +118,8 @@ pub use sources::{
     set_mcp_server_tool_enabled,
     set_planning_model,
     set_project_access_mode,
+    set_project_auto_review,
+    set_project_config_allowed,
     set_project_trusted,
     set_review_model,
     set_review_resolve_model,
@@ -113,6 +137,8 @@ pub use sources::{
     set_validation_tool_enabled,
     write_global_mcp_servers,
 };
+pub use interpolation::CONFIG_SECRETS_KEYRING_SERVICE;
+pub use migrations::{ConfigMigrationReport, CURRENT_CONFIG_VERSION, migrate_config_file};
 pub use sources::ShellStyleSkillMode;
 
 #[allow(deprecated)]
@@ -258,6 +284,20 @@ pub struct Config {
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Per-model request parameters from `[models.<slug>]`.
+    pub model_overrides: HashMap<String, ModelOverrides>,
+
+    /// Per-model token prices from `[model_pricing.<slug>]`, used to estimate
+    /// session cost.
+    pub model_pricing: HashMap<String, ModelPricing>,
+
+    /// Model and provider a turn switches to when the active model keeps
+    /// failing, from `[model_fallback]`.
+    pub model_fallback: Option<ModelFallbackConfig>,
+
+    /// Session guardrails such as the cost budget.
+    pub limits: LimitsConfig,
+
     /// Key into the model_providers map that specifies which provider to use.
     pub model_provider_id: String,
 
@@ -293,6 +333,19 @@ pub struct Config {
     /// Patterns requiring an explicit confirm prefix before running.
     pub confirm_guard: ConfirmGuardConfig,
 
+    /// Secret masking applied to command output and attached files.
+    pub redaction: RedactionConfig,
+
+    /// Outbound content rules checked before content is sent to the model.
+    pub dlp: DlpConfig,
+
+    /// Sanitized log of model requests and responses.
+    pub wire_log: WireLogConfig,
+
+    /// Proxy and custom CA settings for outbound HTTP traffic; `ca_bundle`
+    /// is already resolved against `CODE_HOME`.
+    pub network: NetworkConfig,
+
     /// When `true`, `AgentReasoning` events emitted by the backend will be
     /// suppressed from the frontend output. This can reduce visual noise when
     /// users are only interested in the final agent responses.
@@ -365,6 +418,14 @@ pub struct Config {
     /// Cadence (in requests) for running the Auto Drive observer thread.
     pub auto_drive_observer_cadence: u32,
 
+    /// On Android, battery percentage below which background work is
+    /// deferred and long agent runs warn before starting. `0` disables.
+    pub low_battery_threshold: u8,
+
+    /// Whether the connection is treated as metered: images and browser
+    /// screenshots are left out and optional context is trimmed.
+    pub metered_connection: MeteredConnection,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
@@ -470,10 +531,23 @@ pub struct Config {
     pub tools_search_tool: bool,
     /// Optional allow-list of domains for web_search filters.allowed_domains
     pub tools_web_search_allowed_domains: Option<Vec<String>>,
+    /// Enable the built-in `fetch_url` tool (plain HTTP, no browser).
+    pub tools_fetch_url: bool,
+    /// Domains `fetch_url` and `/fetch` may reach; `None` allows any.
+    pub tools_fetch_url_allowed_domains: Option<Vec<String>>,
+    /// Enable the `save_artifact` tool (files under `code_home/artifacts`).
+    pub tools_artifacts: bool,
+    /// Enable the `run_tests` tool (cargo test, pytest, jest).
+    pub tools_run_tests: bool,
+    /// Attach the failing tests' output of the last `run_tests` call to the
+    /// next prompt.
+    pub tools_run_tests_attach_failures: bool,
     /// Experimental: enable streamable shell tool selection (off by default).
     pub use_experimental_streamable_shell_tool: bool,
     /// Enable the `image_view` tool that lets the agent attach local images.
     pub include_view_image_tool: bool,
+    /// Carry `cd`/`export` state across shell commands within a session.
+    pub tools_persistent_shell: bool,
 
     /// Experimental: enable discovery and injection of skills.
     pub skills_enabled: bool,
@@ -501,9 +575,43 @@ pub struct Config {
     /// GitHub integration configuration.
     pub github: GithubConfig,
 
+    /// Local git integration such as per-patch checkpoints.
+    pub git: GitConfig,
+
+    /// Language servers checked after each patch.
+    pub lsp: LspConfig,
+
+    /// Repository map shared with the model (`/map`, `[repo_map] auto`).
+    pub repo_map: RepoMapConfig,
+
+    /// Environment context providers, with the current project's overrides
+    /// applied.
+    pub environment_context: EnvironmentContextConfig,
+
+    /// Auto-review prompt, file scope and severity gate, with the current
+    /// project's overrides applied.
+    pub auto_review: AutoReviewConfig,
+
+    /// Specialized passes `/review` runs after the main one.
+    pub review_passes: Vec<ReviewPassConfig>,
+
     /// Validation harness configuration.
     pub validation: ValidationConfig,
 
+    /// Patch safety settings, including protected paths.
+    pub patches: PatchesConfig,
+
+    /// User scripting hooks that can veto or annotate turns, commands and
+    /// patches.
+    pub hooks: HooksConfig,
+
+    /// Outbound webhooks fired on turn completion, patch apply and errors.
+    pub webhooks: Vec<WebhookConfig>,
+
+    /// Start interactive sessions in a fresh git worktree of the repository so
+    /// agent edits and commands never touch the user's checkout.
+    pub worktree_isolation: bool,
+
     /// Resolved subagent command configurations (including custom ones).
     /// If a command with name `plan|solve|code` exists here, it overrides
     /// the built-in defaults for that slash command.
@@ -533,6 +641,16 @@ pub struct Config {
 }
 
 impl Config {
+    /// `[models.<slug>]` overrides for `model`, if any.
+    pub fn model_overrides_for(&self, model: &str) -> Option<&ModelOverrides> {
+        ModelOverrides::find(&self.model_overrides, model)
+    }
+
+    /// `[model_pricing.<slug>]` prices for `model`, if any.
+    pub fn model_pricing_for(&self, model: &str) -> Option<ModelPricing> {
+        ModelPricing::find(&self.model_pricing, model)
+    }
+
     /// Load configuration with *generic* CLI overrides (`-c key=value`) applied
     /// **in between** the values parsed from `config.toml` and the
     /// strongly-typed overrides specified via [`ConfigOverrides`].
@@ -611,6 +729,9 @@ pub fn load_allowed_approval_policies(
 #[derive(Deserialize, Debug, Clone, Default, JsonSchema)]
 #[schemars(deny_unknown_fields)]
 pub struct ConfigToml {
+    /// Layout version of this file; older layouts are upgraded on startup.
+    pub config_version: Option<u32>,
+
     /// Optional override of model selection.
     pub model: Option<String>,
     /// Planning model override used when in Read Only (Plan Mode).
@@ -663,6 +784,20 @@ pub struct ConfigToml {
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Per-model request parameters, keyed by model slug.
+    #[serde(default)]
+    pub models: HashMap<String, ModelOverrides>,
+
+    /// Per-model token prices in USD per million tokens, keyed by model slug.
+    #[serde(default)]
+    pub model_pricing: HashMap<String, ModelPricing>,
+
+    /// Fallback model for turns whose model keeps failing.
+    pub model_fallback: Option<ModelFallbackConfig>,
+
+    /// Session guardrails (`[limits]`).
+    pub limits: Option<LimitsConfig>,
+
     /// Default approval policy for executing commands.
     pub approval_policy: Option<AskForApproval>,
 
@@ -686,6 +821,22 @@ pub struct ConfigToml {
     #[serde(default)]
     pub confirm_guard: Option<ConfirmGuardConfig>,
 
+    /// Secret masking for command output and attached files.
+    #[serde(default)]
+    pub redaction: Option<RedactionConfig>,
+
+    /// Outbound content rules (`[[dlp.rules]]`).
+    #[serde(default)]
+    pub dlp: Option<DlpConfig>,
+
+    /// Proxy and custom CA settings (`[network]`).
+    #[serde(default)]
+    pub network: Option<NetworkConfig>,
+
+    /// Sanitized request/response log (`[wire_log]`).
+    #[serde(default)]
+    pub wire_log: Option<WireLogConfig>,
+
     /// Disable server-side response storage (sends the full conversation
     /// context with every request). Currently necessary for OpenAI customers
     /// who have opted into Zero Data Retention (ZDR).
@@ -778,6 +929,14 @@ pub struct ConfigToml {
     #[serde(default)]
     pub auto_drive_observer_cadence: Option<u32>,
 
+    /// Battery percentage (Termux only) below which background work is
+    /// deferred while discharging. Defaults to 20; `0` disables.
+    pub low_battery_threshold: Option<u8>,
+
+    /// `auto` (default) treats the connection as metered when Termux reports
+    /// no Wi-Fi; `on` and `off` force the mode.
+    pub metered_connection: Option<MeteredConnection>,
+
     /// Browser configuration for integrated screenshot capabilities.
     pub browser: Option<BrowserConfig>,
 
@@ -855,9 +1014,41 @@ pub struct ConfigToml {
     /// GitHub integration configuration.
     pub github: Option<GithubConfig>,
 
+    /// Local git integration (`[git]`).
+    pub git: Option<GitConfig>,
+
+    /// Language servers checked after each patch (`[lsp]`).
+    pub lsp: Option<LspConfig>,
+
+    /// Repository map settings (`[repo_map]`).
+    pub repo_map: Option<RepoMapConfig>,
+
+    /// Environment context providers (`[environment_context]`).
+    pub environment_context: Option<EnvironmentContextConfig>,
+
+    /// Auto-review prompt, file scope and severity gate (`[auto_review]`).
+    pub auto_review: Option<AutoReviewConfig>,
+
+    /// Specialized review passes (`[[review_passes]]`).
+    #[serde(default)]
+    pub review_passes: Vec<ReviewPassConfig>,
+
     /// Validation harness configuration.
     pub validation: Option<ValidationConfig>,
 
+    /// Patch safety settings (`[patches]`).
+    pub patches: Option<PatchesConfig>,
+
+    /// User scripting hooks (`[hooks]`).
+    pub hooks: Option<HooksConfig>,
+
+    /// Outbound webhooks (`[[webhooks]]`).
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+
+    /// Run sessions in their own git worktree; `/merge` brings the work back.
+    pub worktree_isolation: Option<bool>,
+
     /// Configuration for subagent commands (built-ins and custom).
     #[serde(default)]
     pub subagents: Option<crate::config_types::SubagentsToml>,
@@ -908,6 +1099,17 @@ pub struct ProjectConfig {
     pub hooks: Vec<ProjectHookConfig>,
     #[serde(default)]
     pub commands: Vec<ProjectCommandConfig>,
+    /// Recorded answer to the project config prompt: whether the repository's
+    /// `.code/config.toml` may be merged over the user config.
+    pub allow_project_config: Option<bool>,
+    /// Auto-review settings for this project; fields set here replace the
+    /// global `[auto_review]` ones.
+    #[serde(default)]
+    pub auto_review: Option<AutoReviewConfig>,
+    /// Environment context providers for this project; replaces the global
+    /// `[environment_context]` list.
+    #[serde(default)]
+    pub environment_context: Option<EnvironmentContextConfig>,
 }
 
 #[derive(Deserialize, Debug, Clone, Default, JsonSchema)]
@@ -934,9 +1136,45 @@ pub struct ToolsToml {
     #[serde(default)]
     pub web_search_allowed_domains: Option<Vec<String>>,
 
+    /// Enable the `fetch_url` tool: a plain HTTP fetch that returns the
+    /// readable part of a page as markdown, without starting a browser.
+    #[serde(default)]
+    pub fetch_url: Option<bool>,
+
+    /// Domains (and their subdomains) `fetch_url` and `/fetch` may reach.
+    /// Example:
+    ///
+    /// [tools]
+    /// fetch_url = true
+    /// fetch_url_allowed_domains = ["docs.rs", "developer.mozilla.org"]
+    #[serde(default)]
+    pub fetch_url_allowed_domains: Option<Vec<String>>,
+
+    /// Enable the `save_artifact` tool, which writes files meant for the user
+    /// (reports, images, exports) to `<code_home>/artifacts/<session id>/`.
+    /// Defaults to true.
+    #[serde(default)]
+    pub artifacts: Option<bool>,
+
+    /// Enable the `run_tests` tool, which runs `cargo test`, pytest or jest
+    /// and reports failing tests with their location and output. Defaults to
+    /// true.
+    #[serde(default)]
+    pub run_tests: Option<bool>,
+
+    /// After a `run_tests` call with failures, attach only the failing tests'
+    /// output to the next prompt. Defaults to false.
+    #[serde(default)]
+    pub run_tests_attach_failures: Option<bool>,
+
     /// Enable the `image_view` tool that lets the agent attach local images.
     #[serde(default)]
     pub view_image: Option<bool>,
+
+    /// Carry the working directory and exported environment from one shell
+    /// command to the next within a session (off by default).
+    #[serde(default)]
+    pub persistent_shell: Option<bool>,
 }
 
 #[derive(Deserialize, Debug, Clone, Default, JsonSchema)]
@@ -1002,6 +1240,20 @@ impl ConfigToml {
         false
     }
 
+    /// Recorded project config decision for `resolved_cwd`, falling back to
+    /// the root git project the same way [`Self::is_cwd_trusted`] does.
+    pub fn project_config_decision(&self, resolved_cwd: &Path) -> Option<bool> {
+        let projects = self.projects.as_ref()?;
+        let decision_for = |path: &Path| {
+            projects
+                .get(path.to_string_lossy().as_ref())
+                .and_then(|project| project.allow_project_config)
+        };
+        decision_for(resolved_cwd).or_else(|| {
+            resolve_root_git_project_for_trust(resolved_cwd).and_then(|root| decision_for(&root))
+        })
+    }
+
     pub fn get_config_profile(
         &self,
         override_profile: Option<String>,
@@ -1139,6 +1391,17 @@ impl Config {
                 )
             })?
             .clone();
+        if let Some(provider) = cfg
+            .model_fallback
+            .as_ref()
+            .and_then(|fallback| fallback.provider.as_deref())
+            && !model_providers.contains_key(provider)
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("model_fallback provider `{provider}` not found"),
+            ));
+        }
 
         // Capture workspace-write details early to avoid borrow after partial moves
         let cfg_workspace = cfg.sandbox_workspace_write.clone();
@@ -1175,8 +1438,9 @@ impl Config {
             .as_ref()
             .and_then(|m| m.get(&project_key));
         // Resolve sandbox mode with correct precedence:
-        // CLI override > per-project override > global config.toml > default
+        // CLI override > profile override > per-project override > global config.toml > default
         let effective_sandbox_mode = sandbox_mode
+            .or(config_profile.sandbox_mode)
             .or(project_override.and_then(|p| p.sandbox_mode))
             .or(cfg.sandbox_mode)
             .unwrap_or_default();
@@ -1240,6 +1504,20 @@ impl Config {
         let project_commands = project_override
             .map(|cfg| load_project_commands(&cfg.commands, &resolved_cwd))
             .unwrap_or_default();
+        let auto_review = {
+            let global = cfg.auto_review.clone().unwrap_or_default();
+            match project_override.and_then(|project| project.auto_review.as_ref()) {
+                Some(project) => global.overridden_by(project),
+                None => global,
+            }
+        };
+        let environment_context = {
+            let global = cfg.environment_context.clone().unwrap_or_default();
+            match project_override.and_then(|project| project.environment_context.as_ref()) {
+                Some(project) => global.overridden_by(project),
+                None => global,
+            }
+        };
 
         let tools_web_search_request = override_tools_web_search_request
             .or(cfg.tools.as_ref().and_then(|t| t.web_search))
@@ -1258,6 +1536,35 @@ impl Config {
             .tools
             .as_ref()
             .and_then(|t| t.web_search_allowed_domains.clone());
+        let tools_fetch_url = cfg
+            .tools
+            .as_ref()
+            .and_then(|t| t.fetch_url)
+            .unwrap_or(false);
+        let tools_fetch_url_allowed_domains = cfg
+            .tools
+            .as_ref()
+            .and_then(|t| t.fetch_url_allowed_domains.clone());
+        let tools_artifacts = cfg
+            .tools
+            .as_ref()
+            .and_then(|t| t.artifacts)
+            .unwrap_or(true);
+        let tools_run_tests = cfg
+            .tools
+            .as_ref()
+            .and_then(|t| t.run_tests)
+            .unwrap_or(true);
+        let tools_run_tests_attach_failures = cfg
+            .tools
+            .as_ref()
+            .and_then(|t| t.run_tests_attach_failures)
+            .unwrap_or(false);
+        let tools_persistent_shell = cfg
+            .tools
+            .as_ref()
+            .and_then(|t| t.persistent_shell)
+            .unwrap_or(false);
         // View Image tool is enabled by default; can be disabled in config or overrides.
         let include_view_image_tool_flag = include_view_image_tool
             .or(cfg.tools.as_ref().and_then(|t| t.view_image))
@@ -1318,8 +1625,18 @@ impl Config {
             .or(config_profile.model_reasoning_effort)
             .or(cfg.model_reasoning_effort);
 
-        let requested_chat_effort =
-            preferred_model_reasoning_effort.unwrap_or(ReasoningEffort::Medium);
+        let model_overrides = cfg.models.clone();
+        // A `[models.<slug>]` default outranks the top-level effort, which
+        // was usually picked for a different model, but not the profile.
+        let model_default_effort = ModelOverrides::find(&model_overrides, &model)
+            .and_then(|overrides| overrides.model_reasoning_effort);
+        let requested_chat_effort = match model_default_effort {
+            Some(effort) => config_profile
+                .preferred_model_reasoning_effort
+                .or(config_profile.model_reasoning_effort)
+                .unwrap_or(effort),
+            None => preferred_model_reasoning_effort.unwrap_or(ReasoningEffort::Medium),
+        };
         let chat_reasoning_effort =
             clamp_reasoning_effort_for_model(&model, requested_chat_effort);
 
@@ -1388,6 +1705,39 @@ impl Config {
             }
         }
 
+        let redaction = cfg.redaction.unwrap_or_default();
+        for pattern in &redaction.patterns {
+            if let Err(err) = regex_lite::Regex::new(pattern) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("invalid redaction pattern `{pattern}`: {err}"),
+                ));
+            }
+        }
+
+        let dlp = cfg.dlp.unwrap_or_default();
+        for rule in &dlp.rules {
+            for pattern in &rule.patterns {
+                if let Err(err) = regex_lite::Regex::new(pattern) {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("invalid pattern `{pattern}` in DLP rule `{}`: {err}", rule.name),
+                    ));
+                }
+            }
+        }
+
+        let mut network = cfg.network.unwrap_or_default();
+        if let Some(ca_bundle) = network.ca_bundle.take() {
+            network.ca_bundle = Some(code_home.join(ca_bundle));
+        }
+        if let Err(err) = crate::http_client::NetworkSettings::from_config(&network) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid [network] config: {err}"),
+            ));
+        }
+
         // Default review model when not set in config; allow CLI override to take precedence.
         let review_model = override_review_model
             .or(config_profile.review_model.clone())
@@ -1599,6 +1949,10 @@ impl Config {
             model_family,
             model_context_window,
             model_max_output_tokens,
+            model_overrides,
+            model_pricing: cfg.model_pricing,
+            model_fallback: cfg.model_fallback,
+            limits: cfg.limits.unwrap_or_default(),
             model_auto_compact_token_limit,
             model_provider_id,
             model_provider,
@@ -1612,6 +1966,10 @@ impl Config {
             shell: cfg.shell,
             shell_style_profiles: cfg.shell_style_profiles,
             confirm_guard,
+            redaction,
+            dlp,
+            network,
+            wire_log: cfg.wire_log.unwrap_or_default(),
             disable_response_storage: config_profile
                 .disable_response_storage
                 .or(cfg.disable_response_storage)
@@ -1685,11 +2043,17 @@ impl Config {
             tools_web_search_external,
             tools_search_tool,
             tools_web_search_allowed_domains,
+            tools_fetch_url,
+            tools_fetch_url_allowed_domains,
+            tools_artifacts,
+            tools_run_tests,
+            tools_run_tests_attach_failures,
             // Honor upstream opt-in switch name for our experimental streamable shell tool.
             use_experimental_streamable_shell_tool: cfg
                 .experimental_use_exec_command_tool
                 .unwrap_or(false),
             include_view_image_tool: include_view_image_tool_flag,
+            tools_persistent_shell,
             skills_enabled,
             env_ctx_v2: env_ctx_v2_flag,
             retention: crate::config_types::RetentionConfig::default(),
@@ -1700,7 +2064,17 @@ impl Config {
             auto_switch_accounts_on_rate_limit,
             api_key_fallback_on_all_accounts_limited,
             github: cfg.github.unwrap_or_default(),
+            git: cfg.git.unwrap_or_default(),
+            lsp: cfg.lsp.unwrap_or_default(),
+            repo_map: cfg.repo_map.unwrap_or_default(),
+            environment_context,
+            auto_review,
+            review_passes: cfg.review_passes,
             validation: cfg.validation.unwrap_or_default(),
+            patches: cfg.patches.unwrap_or_default(),
+            hooks: cfg.hooks.unwrap_or_default(),
+            webhooks: cfg.webhooks,
+            worktree_isolation: cfg.worktree_isolation.unwrap_or(false),
             subagent_commands: cfg
                 .subagents
                 .map(|s| s.commands)
@@ -1712,6 +2086,11 @@ impl Config {
             // Surface TUI notifications preference from config when present.
             tui_notifications: tui_config.notifications,
             auto_drive_observer_cadence: cfg.auto_drive_observer_cadence.unwrap_or(5),
+            low_battery_threshold: cfg
+                .low_battery_threshold
+                .unwrap_or(crate::battery::DEFAULT_LOW_BATTERY_THRESHOLD)
+                .min(100),
+            metered_connection: cfg.metered_connection.unwrap_or_default(),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1719,10 +2098,14 @@ impl Config {
                     .environment
                     .unwrap_or(DEFAULT_OTEL_ENVIRONMENT.to_string());
                 let exporter = t.exporter.unwrap_or(OtelExporterKind::None);
+                let trace_exporter = t.trace_exporter.unwrap_or(OtelExporterKind::None);
+                let metrics_exporter = t.metrics_exporter.unwrap_or(OtelExporterKind::None);
                 OtelConfig {
                     log_user_prompt,
                     environment,
                     exporter,
+                    trace_exporter,
+                    metrics_exporter,
                 }
             },
         };
@@ -1870,7 +2253,9 @@ fn migrate_entry(path: &Path, target_dir: &Path) -> std::io::Result<()> {
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used, clippy::unwrap_used)]
+    use crate::config_types::HistoryMergePolicy;
     use crate::config_types::HistoryPersistence;
+    use crate::config_types::HistoryStoreKind;
     use crate::config_types::McpServerTransportConfig;
     use crate::config_types::Notifications;
 
@@ -1920,6 +2305,8 @@ persistence = "save-all"
             Some(History {
                 persistence: HistoryPersistence::SaveAll,
                 max_bytes: None,
+                store: HistoryStoreKind::Jsonl,
+                merge: HistoryMergePolicy::default(),
             }),
             history_with_persistence_cfg.history
         );
@@ -1935,6 +2322,8 @@ persistence = "none"
             Some(History {
                 persistence: HistoryPersistence::None,
                 max_bytes: None,
+                store: HistoryStoreKind::Jsonl,
+                merge: HistoryMergePolicy::default(),
             }),
             history_no_persistence_cfg.history
         );
@@ -1983,6 +2372,33 @@ persistence = "none"
         Ok(())
     }
 
+    #[test]
+    fn model_fallback_requires_a_known_provider() -> std::io::Result<()> {
+        let code_home = TempDir::new()?;
+
+        let cfg = toml::from_str::<ConfigToml>("[model_fallback]\nmodel = \"gpt-5-mini\"\nafter_errors = 2")
+            .expect("TOML should deserialize");
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            code_home.path().to_path_buf(),
+        )?;
+        let fallback = config.model_fallback.expect("fallback should be configured");
+        assert_eq!(fallback.model, "gpt-5-mini");
+        assert_eq!(fallback.after_errors, Some(2));
+
+        let cfg = toml::from_str::<ConfigToml>("[model_fallback]\nmodel = \"gpt-5-mini\"\nprovider = \"missing\"")
+            .expect("TOML should deserialize");
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            code_home.path().to_path_buf(),
+        )
+        .expect_err("unknown fallback provider should be rejected");
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+        Ok(())
+    }
+
     #[test]
     fn load_default_with_cli_overrides_applies_cli_model_override() -> std::io::Result<()> {
         let _code_home_guard = EnvVarGuard::new("CODE_HOME");
@@ -2423,6 +2839,9 @@ model_verbosity = "high"
             stream_idle_timeout_ms: Some(300_000),
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
         let model_provider_map = {
             let mut model_provider_map = built_in_model_providers();
@@ -3045,6 +3464,86 @@ model_verbosity = "high"
         assert_eq!(legacy.review_model.as_deref(), Some("gpt-5.1-codex"));
     }
 
+    #[test]
+    fn profile_sandbox_mode_overrides_global_sandbox_mode() -> std::io::Result<()> {
+        let fixture = create_test_fixture()?;
+        let mut cfg = fixture.cfg.clone();
+        cfg.sandbox_mode = Some(SandboxMode::DangerFullAccess);
+        cfg.profiles.insert(
+            "careful".to_string(),
+            ConfigProfile {
+                sandbox_mode: Some(SandboxMode::ReadOnly),
+                ..Default::default()
+            },
+        );
+
+        let overrides = ConfigOverrides {
+            cwd: Some(fixture.cwd()),
+            config_profile: Some("careful".to_string()),
+            ..Default::default()
+        };
+
+        let resolved = Config::load_from_base_config_with_overrides(
+            cfg,
+            overrides,
+            fixture.code_home(),
+        )?;
+
+        assert_eq!(resolved.sandbox_policy, SandboxPolicy::ReadOnly);
+        assert_eq!(resolved.active_profile.as_deref(), Some("careful"));
+        Ok(())
+    }
+
+    #[test]
+    fn model_overrides_supply_default_effort_below_profile() -> std::io::Result<()> {
+        let fixture = create_test_fixture()?;
+        let mut cfg = fixture.cfg.clone();
+        cfg.profile = None;
+        cfg.model = Some("o3".to_string());
+        cfg.model_reasoning_effort = Some(ReasoningEffort::Low);
+        cfg.models.insert(
+            "O3".to_string(),
+            ModelOverrides {
+                temperature: Some(0.2),
+                model_reasoning_effort: Some(ReasoningEffort::High),
+                ..Default::default()
+            },
+        );
+        cfg.profiles.insert(
+            "quick".to_string(),
+            ConfigProfile {
+                model_reasoning_effort: Some(ReasoningEffort::Medium),
+                ..Default::default()
+            },
+        );
+
+        let resolved = Config::load_from_base_config_with_overrides(
+            cfg.clone(),
+            ConfigOverrides {
+                cwd: Some(fixture.cwd()),
+                ..Default::default()
+            },
+            fixture.code_home(),
+        )?;
+        assert_eq!(resolved.model_reasoning_effort, ReasoningEffort::High);
+        assert_eq!(
+            resolved.model_overrides_for("o3").and_then(|o| o.temperature),
+            Some(0.2)
+        );
+
+        let with_profile = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                cwd: Some(fixture.cwd()),
+                config_profile: Some("quick".to_string()),
+                ..Default::default()
+            },
+            fixture.code_home(),
+        )?;
+        assert_eq!(with_profile.model_reasoning_effort, ReasoningEffort::Medium);
+        Ok(())
+    }
+
     #[test]
     fn test_compact_prompt_override_prefers_cli_string() -> std::io::Result<()> {
         let fixture = create_test_fixture()?;
@@ -3343,6 +3842,53 @@ script_style = "zsh"
         Ok(())
     }
 
+    #[test]
+    fn project_auto_review_overrides_global_settings() -> anyhow::Result<()> {
+        use crate::config_types::ReviewSeverity;
+
+        let code_home = TempDir::new()?;
+        let project_dir = TempDir::new()?;
+        let config_path = code_home.path().join(CONFIG_TOML_FILE);
+        std::fs::write(
+            &config_path,
+            "[auto_review]\nexclude = [\"*.lock\"]\nmin_severity = \"error\"\n",
+        )?;
+
+        set_project_auto_review(
+            code_home.path(),
+            project_dir.path(),
+            &AutoReviewConfig {
+                include: vec!["src/**".to_string()],
+                min_severity: Some(ReviewSeverity::Warning),
+                ..Default::default()
+            },
+        )?;
+
+        let cfg: ConfigToml = toml::from_str(&std::fs::read_to_string(&config_path)?)?;
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                cwd: Some(project_dir.path().to_path_buf()),
+                ..Default::default()
+            },
+            code_home.path().to_path_buf(),
+        )?;
+        assert_eq!(
+            config.auto_review,
+            AutoReviewConfig {
+                prompt: None,
+                include: vec!["src/**".to_string()],
+                exclude: vec!["*.lock".to_string()],
+                min_severity: Some(ReviewSeverity::Warning),
+            }
+        );
+
+        // Clearing the project settings drops the table entirely.
+        set_project_auto_review(code_home.path(), project_dir.path(), &AutoReviewConfig::default())?;
+        assert!(!std::fs::read_to_string(&config_path)?.contains("src/**"));
+        Ok(())
+    }
+
     #[test]
     fn shell_style_profile_skill_mode_updates_existing_alias_key() -> anyhow::Result<()> {
         let code_home = TempDir::new().unwrap();
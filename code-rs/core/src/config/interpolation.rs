@@ -0,0 +1,173 @@
+//! `${env:VAR}` and `${keyring:entry}` references in config string values.
+//!
+//! References are resolved once, after all config layers are merged and
+//! before the result is deserialized, so the on-disk files keep only the
+//! reference. `$${` escapes a literal `${`. Any other `${...}` text (for
+//! example a shell-style `${HOME}` in an env profile) is left untouched.
+
+use std::io;
+use std::io::ErrorKind;
+
+use code_keyring_store::DefaultKeyringStore;
+use code_keyring_store::KeyringStore;
+use toml::Value as TomlValue;
+
+/// Keyring service under which `${keyring:entry}` secrets are stored.
+pub const CONFIG_SECRETS_KEYRING_SERVICE: &str = "Code Config Secrets";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reference<'a> {
+    Env(&'a str),
+    Keyring(&'a str),
+}
+
+/// Resolves every reference in `root` from the process environment and the
+/// OS keyring.
+pub(crate) fn interpolate_config(root: &mut TomlValue) -> io::Result<()> {
+    interpolate_with(root, &mut |reference| match reference {
+        Reference::Env(name) => std::env::var(name)
+            .map_err(|_| format!("environment variable `{name}` is not set")),
+        Reference::Keyring(entry) => match DefaultKeyringStore.load(CONFIG_SECRETS_KEYRING_SERVICE, entry) {
+            Ok(Some(secret)) => Ok(secret),
+            Ok(None) => Err(format!(
+                "keyring entry `{entry}` not found (store it with `code config secret set {entry}`)"
+            )),
+            Err(err) => Err(format!("keyring entry `{entry}` unavailable: {}", err.message())),
+        },
+    })
+}
+
+fn interpolate_with(
+    root: &mut TomlValue,
+    resolve: &mut dyn FnMut(Reference<'_>) -> Result<String, String>,
+) -> io::Result<()> {
+    let mut path = Vec::new();
+    visit(root, &mut path, resolve)
+}
+
+fn visit(
+    value: &mut TomlValue,
+    path: &mut Vec<String>,
+    resolve: &mut dyn FnMut(Reference<'_>) -> Result<String, String>,
+) -> io::Result<()> {
+    match value {
+        TomlValue::String(text) => {
+            if text.contains("${") {
+                *text = interpolate_str(text, resolve).map_err(|message| {
+                    io::Error::new(
+                        ErrorKind::InvalidData,
+                        format!("config value `{}`: {message}", path.join(".")),
+                    )
+                })?;
+            }
+        }
+        TomlValue::Array(items) => {
+            for (idx, item) in items.iter_mut().enumerate() {
+                path.push(idx.to_string());
+                visit(item, path, resolve)?;
+                path.pop();
+            }
+        }
+        TomlValue::Table(table) => {
+            for (key, item) in table.iter_mut() {
+                path.push(key.clone());
+                visit(item, path, resolve)?;
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn interpolate_str(
+    text: &str,
+    resolve: &mut dyn FnMut(Reference<'_>) -> Result<String, String>,
+) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        if let Some(escaped) = tail.strip_prefix("$${") {
+            out.push_str("${");
+            rest = escaped;
+            continue;
+        }
+        let reference = tail
+            .strip_prefix("${")
+            .and_then(|body| body.find('}').map(|end| (&body[..end], &body[end + 1..])))
+            .and_then(|(inner, after)| parse_reference(inner).map(|reference| (reference, after)));
+        match reference {
+            Some((reference, after)) => {
+                out.push_str(&resolve(reference)?);
+                rest = after;
+            }
+            None => {
+                out.push('$');
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn parse_reference(inner: &str) -> Option<Reference<'_>> {
+    if let Some(name) = inner.strip_prefix("env:") {
+        return (!name.is_empty()).then_some(Reference::Env(name));
+    }
+    if let Some(entry) = inner.strip_prefix("keyring:") {
+        return (!entry.is_empty()).then_some(Reference::Keyring(entry));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve_fixture(reference: Reference<'_>) -> Result<String, String> {
+        match reference {
+            Reference::Env("HOST") => Ok("example.com".to_string()),
+            Reference::Keyring("mcp-token") => Ok("s3cret".to_string()),
+            other => Err(format!("unknown {other:?}")),
+        }
+    }
+
+    #[test]
+    fn resolves_env_and_keyring_references_in_nested_values() {
+        let mut root: TomlValue = toml::from_str(
+            r#"
+base_url = "https://${env:HOST}/v1"
+[mcp_servers.docs.http_headers]
+Authorization = "Bearer ${keyring:mcp-token}"
+[shell_environment_policy.set]
+PATH = "${HOME}/bin"
+LITERAL = "$${env:HOST}"
+"#,
+        )
+        .expect("parse");
+
+        interpolate_with(&mut root, &mut resolve_fixture).expect("interpolate");
+
+        assert_eq!(root["base_url"].as_str(), Some("https://example.com/v1"));
+        assert_eq!(
+            root["mcp_servers"]["docs"]["http_headers"]["Authorization"].as_str(),
+            Some("Bearer s3cret")
+        );
+        let set = &root["shell_environment_policy"]["set"];
+        assert_eq!(set["PATH"].as_str(), Some("${HOME}/bin"));
+        assert_eq!(set["LITERAL"].as_str(), Some("${env:HOST}"));
+    }
+
+    #[test]
+    fn unresolved_reference_names_the_config_key() {
+        let mut root: TomlValue =
+            toml::from_str("[model_providers.corp]\nbase_url = \"${env:MISSING}\"\n").expect("parse");
+
+        let err = interpolate_with(&mut root, &mut resolve_fixture).expect_err("should fail");
+
+        assert!(err.to_string().starts_with("config value `model_providers.corp.base_url`"));
+    }
+}
@@ -0,0 +1,209 @@
+//! Versioned upgrades for `CODE_HOME/config.toml`.
+//!
+//! `config_version` records the layout a file was written for; a missing key
+//! means the file predates versioning. On startup the file is upgraded one
+//! version at a time, the original is kept next to it as a backup, and the
+//! applied steps are reported so renamed or moved keys are not silently
+//! ignored.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use tempfile::NamedTempFile;
+use toml_edit::DocumentMut;
+use toml_edit::Item as TomlItem;
+
+use super::CONFIG_TOML_FILE;
+
+/// Layout version written by this build.
+pub const CURRENT_CONFIG_VERSION: u32 = 2;
+
+const CONFIG_VERSION_KEY: &str = "config_version";
+
+struct Migration {
+    /// Version this step upgrades from; it produces `from + 1`.
+    from: u32,
+    summary: &'static str,
+    /// Returns true when the document was changed.
+    apply: fn(&mut DocumentMut) -> bool,
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from: 0,
+        summary: "renamed [tools].web_search_request to [tools].web_search",
+        apply: rename_tools_web_search_request,
+    },
+    Migration {
+        from: 1,
+        summary: "moved [tui.auto_drive] to [auto_drive]",
+        apply: move_tui_auto_drive,
+    },
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigMigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    /// Summaries of the steps that changed the file, in order.
+    pub applied: Vec<&'static str>,
+    /// Copy of the pre-migration file; `None` for dry runs and for upgrades
+    /// that only stamped `config_version`.
+    pub backup_path: Option<PathBuf>,
+}
+
+/// Upgrades `CODE_HOME/config.toml` to [`CURRENT_CONFIG_VERSION`].
+///
+/// Returns `None` when there is no file or it is already current. With
+/// `dry_run`, reports what would change without touching the file.
+pub fn migrate_config_file(
+    code_home: &Path,
+    dry_run: bool,
+) -> anyhow::Result<Option<ConfigMigrationReport>> {
+    let config_path = code_home.join(CONFIG_TOML_FILE);
+    let contents = match std::fs::read_to_string(&config_path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let mut doc = contents.parse::<DocumentMut>()?;
+
+    let from_version = doc
+        .get(CONFIG_VERSION_KEY)
+        .and_then(TomlItem::as_integer)
+        .map_or(0, |version| version.clamp(0, i64::from(u32::MAX)) as u32);
+    if from_version > CURRENT_CONFIG_VERSION {
+        tracing::warn!(
+            "config.toml has config_version {from_version}, newer than this build supports ({CURRENT_CONFIG_VERSION}); leaving it unchanged"
+        );
+        return Ok(None);
+    }
+    if from_version == CURRENT_CONFIG_VERSION {
+        return Ok(None);
+    }
+
+    let applied = apply_migrations(&mut doc, from_version);
+    let mut report = ConfigMigrationReport {
+        from_version,
+        to_version: CURRENT_CONFIG_VERSION,
+        applied,
+        backup_path: None,
+    };
+    if dry_run {
+        return Ok(Some(report));
+    }
+
+    if !report.applied.is_empty() {
+        let backup_path = code_home.join(format!("{CONFIG_TOML_FILE}.v{from_version}.bak"));
+        std::fs::write(&backup_path, &contents)?;
+        report.backup_path = Some(backup_path);
+    }
+    let tmp_file = NamedTempFile::new_in(code_home)?;
+    std::fs::write(tmp_file.path(), doc.to_string())?;
+    tmp_file.persist(config_path)?;
+    Ok(Some(report))
+}
+
+fn apply_migrations(doc: &mut DocumentMut, from_version: u32) -> Vec<&'static str> {
+    let applied = MIGRATIONS
+        .iter()
+        .filter(|migration| migration.from >= from_version)
+        .filter(|migration| (migration.apply)(doc))
+        .map(|migration| migration.summary)
+        .collect();
+    doc[CONFIG_VERSION_KEY] = toml_edit::value(i64::from(CURRENT_CONFIG_VERSION));
+    applied
+}
+
+fn rename_tools_web_search_request(doc: &mut DocumentMut) -> bool {
+    let Some(tools) = doc.get_mut("tools").and_then(TomlItem::as_table_like_mut) else {
+        return false;
+    };
+    let Some(legacy) = tools.remove("web_search_request") else {
+        return false;
+    };
+    if tools.get("web_search").is_none() {
+        tools.insert("web_search", legacy);
+    }
+    true
+}
+
+fn move_tui_auto_drive(doc: &mut DocumentMut) -> bool {
+    let Some(tui) = doc.get_mut("tui").and_then(TomlItem::as_table_like_mut) else {
+        return false;
+    };
+    let Some(legacy) = tui.remove("auto_drive") else {
+        return false;
+    };
+    // The top-level table already took precedence at load time, so a legacy
+    // copy next to it is simply dropped.
+    if doc.get("auto_drive").is_none() {
+        doc.insert("auto_drive", legacy);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn upgrades_unversioned_file_with_backup() {
+        let code_home = tempfile::tempdir().expect("tempdir");
+        let original = r#"model = "o3"
+
+[tools]
+web_search_request = true
+
+[tui.auto_drive]
+model = "gpt-5"
+"#;
+        std::fs::write(code_home.path().join(CONFIG_TOML_FILE), original).expect("write");
+
+        let report = migrate_config_file(code_home.path(), false)
+            .expect("migrate")
+            .expect("report");
+
+        assert_eq!(report.from_version, 0);
+        assert_eq!(report.applied.len(), 2);
+        let backup = report.backup_path.expect("backup");
+        assert_eq!(std::fs::read_to_string(backup).expect("backup"), original);
+
+        let migrated: toml::Value = toml::from_str(
+            &std::fs::read_to_string(code_home.path().join(CONFIG_TOML_FILE)).expect("read"),
+        )
+        .expect("parse");
+        assert_eq!(migrated["config_version"].as_integer(), Some(2));
+        assert_eq!(migrated["tools"]["web_search"].as_bool(), Some(true));
+        assert!(migrated["tools"].get("web_search_request").is_none());
+        assert_eq!(migrated["auto_drive"]["model"].as_str(), Some("gpt-5"));
+        assert!(migrated["tui"].get("auto_drive").is_none());
+
+        assert_eq!(migrate_config_file(code_home.path(), false).expect("rerun"), None);
+    }
+
+    #[test]
+    fn dry_run_and_current_layout_leave_file_untouched() {
+        let code_home = tempfile::tempdir().expect("tempdir");
+        let path = code_home.path().join(CONFIG_TOML_FILE);
+        std::fs::write(&path, "[tools]\nweb_search_request = false\n").expect("write");
+
+        let report = migrate_config_file(code_home.path(), true)
+            .expect("dry run")
+            .expect("report");
+        assert_eq!(report.applied, vec![MIGRATIONS[0].summary]);
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read"),
+            "[tools]\nweb_search_request = false\n"
+        );
+
+        std::fs::write(&path, "model = \"o3\"\n").expect("write");
+        let report = migrate_config_file(code_home.path(), false)
+            .expect("stamp")
+            .expect("report");
+        assert!(report.applied.is_empty());
+        assert_eq!(report.backup_path, None);
+        assert!(std::fs::read_to_string(&path).expect("read").contains("config_version = 2"));
+    }
+}
@@ -1,7 +1,9 @@
 use crate::config_loader::{load_config_as_toml_blocking, LoaderOverrides};
+use crate::config_profile::ConfigProfile;
 use crate::config_types::{
     AutoDriveContinueMode,
     AutoDriveSettings,
+    AutoReviewConfig,
     CachedTerminalBackground,
     LimitsLayoutMode,
     McpServerConfig,
@@ -66,6 +68,20 @@ pub fn load_global_mcp_servers(
     Ok(servers)
 }
 
+/// Reads the `[profiles.*]` tables from `config.toml` so a running session can
+/// switch between them without reloading the whole configuration.
+pub fn load_config_profiles(code_home: &Path) -> std::io::Result<BTreeMap<String, ConfigProfile>> {
+    let root_value = load_config_as_toml(code_home)?;
+    let Some(profiles_value) = root_value.get("profiles") else {
+        return Ok(BTreeMap::new());
+    };
+
+    profiles_value
+        .clone()
+        .try_into()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
 pub fn write_global_mcp_servers(
     code_home: &Path,
     servers: &BTreeMap<String, McpServerConfig>,
@@ -911,6 +927,45 @@ pub fn set_project_trusted(code_home: &Path, project_path: &Path) -> anyhow::Res
 }
 
 fn set_project_trusted_inner(doc: &mut DocumentMut, project_path: &Path) -> anyhow::Result<()> {
+    let proj_tbl = ensure_project_table(doc, project_path)?;
+    proj_tbl["trust_level"] = toml_edit::value("trusted");
+
+    Ok(())
+}
+
+/// Record whether the repository containing `cwd` may apply its
+/// `.code/config.toml` over the user config. The decision is stored under the
+/// root git project so worktrees share it.
+pub fn set_project_config_allowed(
+    code_home: &Path,
+    cwd: &Path,
+    allowed: bool,
+) -> anyhow::Result<()> {
+    let config_path = code_home.join(CONFIG_TOML_FILE);
+    let read_path = resolve_code_path_for_read(code_home, Path::new(CONFIG_TOML_FILE));
+    let mut doc = match std::fs::read_to_string(&read_path) {
+        Ok(s) => s.parse::<DocumentMut>()?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => DocumentMut::new(),
+        Err(e) => return Err(e.into()),
+    };
+
+    let project_path = crate::git_info::resolve_root_git_project_for_trust(cwd)
+        .unwrap_or_else(|| cwd.to_path_buf());
+    let proj_tbl = ensure_project_table(&mut doc, &project_path)?;
+    proj_tbl["allow_project_config"] = toml_edit::value(allowed);
+
+    std::fs::create_dir_all(code_home)?;
+    let tmp_file = NamedTempFile::new_in(code_home)?;
+    std::fs::write(tmp_file.path(), doc.to_string())?;
+    tmp_file.persist(config_path)?;
+
+    Ok(())
+}
+
+fn ensure_project_table<'a>(
+    doc: &'a mut DocumentMut,
+    project_path: &Path,
+) -> anyhow::Result<&'a mut toml_edit::Table> {
     // Ensure we render a human-friendly structure:
     //
     // [projects]
@@ -965,9 +1020,8 @@ fn set_project_trusted_inner(doc: &mut DocumentMut, project_path: &Path) -> anyh
         return Err(anyhow::anyhow!("project table missing for {project_key}"));
     };
     proj_tbl.set_implicit(false);
-    proj_tbl["trust_level"] = toml_edit::value("trusted");
 
-    Ok(())
+    Ok(proj_tbl)
 }
 
 /// Persist the selected TUI theme into `CODEX_HOME/config.toml` at `[tui.theme].name`.
@@ -2077,6 +2131,101 @@ pub fn add_project_allowed_command(
     Ok(())
 }
 
+/// The global `[auto_review]` table and the one under
+/// `[projects."<path>"]`, as written in `config.toml` (not merged).
+pub fn load_auto_review_settings(
+    code_home: &Path,
+    project_path: &Path,
+) -> anyhow::Result<(AutoReviewConfig, AutoReviewConfig)> {
+    let root = load_config_as_toml(code_home)?;
+    let parse = |value: Option<&TomlValue>| -> anyhow::Result<AutoReviewConfig> {
+        match value {
+            Some(value) => Ok(value.clone().try_into()?),
+            None => Ok(AutoReviewConfig::default()),
+        }
+    };
+    let global = parse(root.get("auto_review"))?;
+    let project = parse(
+        root.get("projects")
+            .and_then(|projects| projects.get(project_path.to_string_lossy().as_ref()))
+            .and_then(|project| project.get("auto_review")),
+    )?;
+    Ok((global, project))
+}
+
+/// Replace `[projects."<path>".auto_review]` with `settings`; an empty
+/// `settings` removes the table so the global `[auto_review]` applies again.
+pub fn set_project_auto_review(
+    code_home: &Path,
+    project_path: &Path,
+    settings: &AutoReviewConfig,
+) -> anyhow::Result<()> {
+    let config_path = code_home.join(CONFIG_TOML_FILE);
+    let read_path = resolve_code_path_for_read(code_home, Path::new(CONFIG_TOML_FILE));
+    let mut doc = match std::fs::read_to_string(&read_path) {
+        Ok(s) => s.parse::<DocumentMut>()?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => DocumentMut::new(),
+        Err(e) => return Err(e.into()),
+    };
+
+    let project_key = project_path.to_string_lossy().to_string();
+    if doc
+        .as_table()
+        .get("projects")
+        .and_then(|i| i.as_table())
+        .is_none()
+    {
+        doc["projects"] = TomlItem::Table(TomlTable::new());
+    }
+    let Some(projects_tbl) = doc["projects"].as_table_mut() else {
+        return Err(anyhow::anyhow!("failed to prepare projects table"));
+    };
+    if projects_tbl
+        .get(project_key.as_str())
+        .and_then(|i| i.as_table())
+        .is_none()
+    {
+        projects_tbl.insert(project_key.as_str(), TomlItem::Table(TomlTable::new()));
+    }
+    let project_tbl = projects_tbl
+        .get_mut(project_key.as_str())
+        .and_then(|i| i.as_table_mut())
+        .ok_or_else(|| anyhow::anyhow!(format!("failed to create projects.{project_key} table")))?;
+
+    if *settings == AutoReviewConfig::default() {
+        project_tbl.remove("auto_review");
+    } else {
+        let globs = |patterns: &[String]| {
+            let mut array = TomlArray::new();
+            for pattern in patterns {
+                array.push(pattern.as_str());
+            }
+            TomlItem::Value(toml_edit::Value::Array(array))
+        };
+        let mut table = TomlTable::new();
+        if let Some(prompt) = settings.prompt.as_deref() {
+            table.insert("prompt", toml_edit::value(prompt));
+        }
+        if !settings.include.is_empty() {
+            table.insert("include", globs(&settings.include));
+        }
+        if !settings.exclude.is_empty() {
+            table.insert("exclude", globs(&settings.exclude));
+        }
+        if let Some(severity) = settings.min_severity {
+            table.insert("min_severity", toml_edit::value(severity.as_str()));
+        }
+        project_tbl.insert("auto_review", TomlItem::Table(table));
+    }
+
+    std::fs::create_dir_all(code_home)?;
+    let tmp = NamedTempFile::new_in(code_home)?;
+    std::fs::write(tmp.path(), doc.to_string())?;
+    tmp.persist(config_path)?;
+
+    Ok(())
+}
+
 /// List MCP servers from `CODEX_HOME/config.toml`.
 /// Returns `(enabled, disabled)` lists of `(name, McpServerConfig)`.
 type NamedMcpServer = (String, McpServerConfig);
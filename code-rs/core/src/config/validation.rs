@@ -108,7 +108,9 @@ pub(crate) fn deserialize_config_toml_with_cli_warnings(
     // reliably detect unknown fields via `serde_ignored`. Some deserializers
     // (including TOML implementations) may filter unknown struct fields before
     // they reach Serde's ignored-field machinery.
-    let deserializer = serde_json::to_value(root_value).map_err(|e| {
+    let mut root_value = root_value.clone();
+    super::interpolation::interpolate_config(&mut root_value)?;
+    let deserializer = serde_json::to_value(&root_value).map_err(|e| {
         tracing::error!("Failed to convert overridden config for deserialization: {e}");
         std::io::Error::new(ErrorKind::InvalidData, e)
     })?;
@@ -276,7 +276,7 @@ pub async fn load_config_layers_state_with_cwd(
     if let Some(cwd) = cwd {
         let resolved_cwd = resolve_cwd_for_config_layers(cwd)?;
 
-        let trusted = {
+        let gate = {
             let mut merged_so_far = default_empty_table();
             // Only use system + user layers to determine trust.
             merge_toml_values(&mut merged_so_far, &layers[0].config);
@@ -285,11 +285,13 @@ pub async fn load_config_layers_state_with_cwd(
                 merged_so_far.try_into().map_err(|err: toml::de::Error| {
                     io::Error::new(io::ErrorKind::InvalidData, err)
                 })?;
-            cfg.is_cwd_trusted(&resolved_cwd)
+            ProjectLayerGate::resolve(
+                cfg.project_config_decision(&resolved_cwd),
+                cfg.is_cwd_trusted(&resolved_cwd),
+            )
         };
 
-        let mut project_layers =
-            load_project_layers(&resolved_cwd, code_home, trusted).await?;
+        let mut project_layers = load_project_layers(&resolved_cwd, code_home, gate).await?;
         layers.append(&mut project_layers);
     }
 
@@ -607,10 +609,83 @@ fn resolve_cwd_for_config_layers(cwd: &Path) -> io::Result<PathBuf> {
     Ok(std::fs::canonicalize(&base).unwrap_or(base))
 }
 
+/// Whether `.code/config.toml` layers found between the project root and the
+/// cwd are merged into the effective config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProjectLayerGate {
+    Allowed,
+    Declined,
+    Pending,
+}
+
+impl ProjectLayerGate {
+    /// An explicit answer to the project config prompt wins; otherwise a
+    /// trusted project applies its config as before.
+    fn resolve(decision: Option<bool>, trusted: bool) -> Self {
+        match decision {
+            Some(true) => Self::Allowed,
+            Some(false) => Self::Declined,
+            None if trusted => Self::Allowed,
+            None => Self::Pending,
+        }
+    }
+
+    fn disabled_reason(self) -> Option<&'static str> {
+        match self {
+            Self::Allowed => None,
+            Self::Declined => Some(PROJECT_CONFIG_DECLINED),
+            Self::Pending => Some(PROJECT_CONFIG_PENDING),
+        }
+    }
+}
+
+const PROJECT_CONFIG_DECLINED: &str =
+    "Project config was declined for this repository; ignoring project config layer.";
+const PROJECT_CONFIG_PENDING: &str =
+    "Project directory is not trusted; ignoring project config layer.";
+
+/// Top-level keys a repository's `.code/config.toml` may not set: model
+/// selection stays with the user, and a repository cannot grant itself trust.
+const PROJECT_EXCLUDED_KEYS: &[&str] = &[
+    "model",
+    "model_provider",
+    "model_providers",
+    "review_model",
+    "review_resolve_model",
+    "planning_model",
+    "auto_review_model",
+    "auto_review_resolve_model",
+    "profile",
+    "profiles",
+    "projects",
+];
+
+/// Strips settings a project layer may not control and returns the dropped
+/// keys. Approval and sandbox settings may only tighten, so `never` and
+/// `danger-full-access` are dropped as well.
+fn restrict_project_layer(config: &mut TomlValue) -> Vec<String> {
+    let Some(table) = config.as_table_mut() else {
+        return Vec::new();
+    };
+    let mut dropped = Vec::new();
+    for key in PROJECT_EXCLUDED_KEYS {
+        if table.remove(*key).is_some() {
+            dropped.push((*key).to_string());
+        }
+    }
+    for (key, loosest) in [("approval_policy", "never"), ("sandbox_mode", "danger-full-access")] {
+        if table.get(key).and_then(TomlValue::as_str) == Some(loosest) {
+            table.remove(key);
+            dropped.push(key.to_string());
+        }
+    }
+    dropped
+}
+
 async fn load_project_layers(
     cwd: &Path,
     code_home: &Path,
-    trusted: bool,
+    gate: ProjectLayerGate,
 ) -> io::Result<Vec<ConfigLayerEntry>> {
     let project_root = crate::git_info::resolve_root_git_project_for_trust(cwd)
         .unwrap_or_else(|| cwd.to_path_buf());
@@ -650,19 +725,24 @@ async fn load_project_layers(
 
         match tokio::fs::read_to_string(&config_file).await {
             Ok(contents) => match toml::from_str::<TomlValue>(&contents) {
-                Ok(config) => {
-                    if trusted {
+                Ok(mut config) => match gate.disabled_reason() {
+                    None => {
+                        let dropped = restrict_project_layer(&mut config);
+                        if !dropped.is_empty() {
+                            tracing::warn!(
+                                "ignoring settings not allowed in project config {}: {}",
+                                config_file.display(),
+                                dropped.join(", ")
+                            );
+                        }
                         layers.push(ConfigLayerEntry::new(layer_source, config));
-                    } else {
-                        layers.push(ConfigLayerEntry::new_disabled(
-                            layer_source,
-                            config,
-                            "Project directory is not trusted; ignoring project config layer.",
-                        ));
                     }
-                }
+                    Some(reason) => {
+                        layers.push(ConfigLayerEntry::new_disabled(layer_source, config, reason));
+                    }
+                },
                 Err(err) => {
-                    if trusted {
+                    if gate == ProjectLayerGate::Allowed {
                         return Err(io::Error::new(
                             io::ErrorKind::InvalidData,
                             format!(
@@ -682,18 +762,17 @@ async fn load_project_layers(
             },
             Err(err) if err.kind() == io::ErrorKind::NotFound => {
                 // Record an empty layer entry when the folder exists, even if config.toml is missing.
-                if trusted {
-                    layers.push(ConfigLayerEntry::new(layer_source, default_empty_table()));
-                } else {
-                    layers.push(ConfigLayerEntry::new_disabled(
+                match gate.disabled_reason() {
+                    None => layers.push(ConfigLayerEntry::new(layer_source, default_empty_table())),
+                    Some(reason) => layers.push(ConfigLayerEntry::new_disabled(
                         layer_source,
                         default_empty_table(),
-                        "Project directory is not trusted; ignoring project config layer.",
-                    ));
+                        reason,
+                    )),
                 }
             }
             Err(err) => {
-                if trusted {
+                if gate == ProjectLayerGate::Allowed {
                     return Err(io::Error::new(
                         err.kind(),
                         format!(
@@ -716,6 +795,30 @@ async fn load_project_layers(
     Ok(layers)
 }
 
+/// Returns the project `.code/config.toml` files that apply to `cwd` but are
+/// ignored until the user answers the project config prompt.
+pub fn pending_project_config_files(code_home: &Path, cwd: &Path) -> io::Result<Vec<PathBuf>> {
+    let stack = load_config_layers_state_blocking_with_cwd(
+        code_home,
+        Some(cwd),
+        &[],
+        LoaderOverrides::default(),
+    )?;
+    let mut files: Vec<PathBuf> = stack
+        .layers_high_to_low()
+        .filter(|layer| layer.disabled_reason.as_deref() == Some(PROJECT_CONFIG_PENDING))
+        .filter_map(|layer| match &layer.name {
+            ConfigLayerSource::Project { dot_codex_folder } => {
+                Some(dot_codex_folder.as_path().join(CONFIG_TOML_FILE))
+            }
+            _ => None,
+        })
+        .filter(|file| file.is_file())
+        .collect();
+    files.reverse();
+    Ok(files)
+}
+
 fn block_on_loader<F, T>(future: F) -> io::Result<T>
 where
     F: std::future::Future<Output = io::Result<T>> + Send + 'static,
@@ -822,6 +925,58 @@ extra = true
         assert!(table.is_empty(), "expected empty table when configs missing");
     }
 
+    #[tokio::test]
+    async fn project_layer_follows_recorded_decision_and_drops_restricted_keys() {
+        let home = tempdir().expect("code home");
+        let project = tempdir().expect("project");
+        std::fs::create_dir(project.path().join(".code")).expect("create .code");
+        std::fs::write(
+            project.path().join(".code").join(CONFIG_TOML_FILE),
+            r#"model = "repo-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+hide_agent_reasoning = true
+
+[projects."/elsewhere"]
+trust_level = "trusted"
+"#,
+        )
+        .expect("write project config");
+        let overrides = || LoaderOverrides {
+            system_config_path: Some(home.path().join("system_config.toml")),
+            managed_config_path: Some(home.path().join("managed_config.toml")),
+            requirements_path: None,
+            #[cfg(target_os = "macos")]
+            managed_preferences_base64: None,
+        };
+
+        let pending =
+            load_config_layers_state_with_cwd(home.path(), Some(project.path()), &[], overrides())
+                .await
+                .expect("load layers");
+        assert!(pending.effective_config().get("sandbox_mode").is_none());
+
+        std::fs::write(
+            home.path().join(CONFIG_TOML_FILE),
+            format!(
+                "[projects.\"{}\"]\nallow_project_config = true\n",
+                project.path().display()
+            ),
+        )
+        .expect("write user config");
+        let allowed =
+            load_config_layers_state_with_cwd(home.path(), Some(project.path()), &[], overrides())
+                .await
+                .expect("load layers");
+        let effective = allowed.effective_config();
+        assert_eq!(effective.get("sandbox_mode").and_then(TomlValue::as_str), Some("read-only"));
+        assert_eq!(effective.get("hide_agent_reasoning").and_then(TomlValue::as_bool), Some(true));
+        assert!(effective.get("model").is_none());
+        assert!(effective.get("approval_policy").is_none());
+        let projects = effective.get("projects").and_then(TomlValue::as_table).expect("projects");
+        assert!(!projects.contains_key("/elsewhere"));
+    }
+
     #[cfg(target_os = "macos")]
     #[tokio::test]
     async fn managed_preferences_take_highest_precedence() {
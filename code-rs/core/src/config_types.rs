@@ -3,6 +3,8 @@
 // Note this file should generally be restricted to simple struct/enum
 // definitions that do not contain business logic.
 
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -59,7 +61,13 @@ pub enum OtelExporterKind {
 pub struct OtelConfigToml {
     pub log_user_prompt: Option<bool>,
     pub environment: Option<String>,
+    /// Where log events are exported.
     pub exporter: Option<OtelExporterKind>,
+    /// Where spans for turns, model streams, exec commands and MCP calls
+    /// are exported.
+    pub trace_exporter: Option<OtelExporterKind>,
+    /// Where token and approval counters are exported.
+    pub metrics_exporter: Option<OtelExporterKind>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -67,6 +75,8 @@ pub struct OtelConfig {
     pub log_user_prompt: bool,
     pub environment: String,
     pub exporter: OtelExporterKind,
+    pub trace_exporter: OtelExporterKind,
+    pub metrics_exporter: OtelExporterKind,
 }
 
 impl Default for OtelConfig {
@@ -75,6 +85,8 @@ impl Default for OtelConfig {
             log_user_prompt: false,
             environment: DEFAULT_OTEL_ENVIRONMENT.to_owned(),
             exporter: OtelExporterKind::None,
+            trace_exporter: OtelExporterKind::None,
+            metrics_exporter: OtelExporterKind::None,
         }
     }
 }
@@ -476,6 +488,124 @@ impl Default for ConfirmGuardConfig {
     }
 }
 
+/// Masking of secrets in command output and attached file content.
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RedactionConfig {
+    /// Mask values matching the built-in secret patterns (AWS keys, bearer
+    /// tokens, API keys, private keys) and `patterns`. Defaults to true.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Additional regex patterns to mask. When a pattern has a capture group,
+    /// only the first group is masked.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self { enabled: true, patterns: Vec::new() }
+    }
+}
+
+/// Outbound content rules (`[[dlp.rules]]`) checked before file content,
+/// command output or browser snapshots are sent to the model.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
+pub struct DlpConfig {
+    #[serde(default)]
+    pub rules: Vec<DlpRule>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct DlpRule {
+    /// Name shown when the rule stops or pauses a turn.
+    pub name: String,
+    /// Globs for files whose content may not be sent. A glob without a `/`
+    /// matches the file name anywhere.
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Regexes for text that may not be sent.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    #[serde(default)]
+    pub action: DlpAction,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DlpAction {
+    /// End the turn and explain which rule matched.
+    Block,
+    /// Ask once before sending; sessions that never prompt block instead.
+    #[default]
+    Confirm,
+}
+
+/// `[wire_log]`: opt-in log of model requests and responses, sanitized by
+/// the `[redaction]` rules.
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct WireLogConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Size at which `wire.jsonl` is rotated. Defaults to 10 MiB.
+    #[serde(default = "default_wire_log_max_file_bytes")]
+    pub max_file_bytes: u64,
+    /// Rotated files kept besides the current one. Defaults to 3.
+    #[serde(default = "default_wire_log_max_files")]
+    pub max_files: usize,
+}
+
+impl Default for WireLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_file_bytes: default_wire_log_max_file_bytes(),
+            max_files: default_wire_log_max_files(),
+        }
+    }
+}
+
+const fn default_wire_log_max_file_bytes() -> u64 {
+    10 * 1024 * 1024
+}
+
+const fn default_wire_log_max_files() -> usize {
+    3
+}
+
+/// `[network]`: proxy and TLS settings for the model client, MCP HTTP
+/// servers and the URL fetch tool.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
+pub struct NetworkConfig {
+    /// Proxy URL for all outbound HTTP(S) traffic: `http://`, `https://`,
+    /// `socks5://` or `socks5h://` (DNS resolved by the proxy), optionally
+    /// with `user:password@`. When unset, `HTTPS_PROXY`/`ALL_PROXY` apply.
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// Hosts reached without the proxy: domain names (their subdomains
+    /// match too; a leading `.` or `*.` is accepted), IP addresses, CIDR
+    /// ranges, or `*` for every host.
+    #[serde(default)]
+    pub no_proxy: Vec<String>,
+    /// PEM bundle (or single DER certificate) trusted in addition to the
+    /// system roots. Relative paths are resolved against `CODE_HOME`.
+    #[serde(default)]
+    pub ca_bundle: Option<PathBuf>,
+}
+
+/// `metered_connection`: whether to save data on this connection.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MeteredConnection {
+    /// Treat the connection as metered when Termux reports no Wi-Fi.
+    #[default]
+    Auto,
+    /// Always save data.
+    On,
+    /// Never save data.
+    Off,
+}
+
 #[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
 pub struct ConfirmGuardPattern {
     /// ECMA-style regular expression matched against the command string.
@@ -706,6 +836,510 @@ pub struct GithubConfig {
     pub actionlint_strict: bool,
 }
 
+/// Local git integration settings (`[git]`).
+#[derive(Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+pub struct GitConfig {
+    /// Commit the working tree to `refs/code/checkpoints/<session>` after
+    /// every successful patch apply, leaving the branch and index untouched.
+    #[serde(default)]
+    pub auto_checkpoint: bool,
+
+    /// Command `/pr` runs to open the pull request, e.g. `"gh pr create"`.
+    /// When unset, `glab mr create` is used for GitLab remotes and
+    /// `gh pr create` otherwise.
+    #[serde(default)]
+    pub pr_command: Option<String>,
+}
+
+/// Repository map settings (`[repo_map]`).
+#[derive(Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+pub struct RepoMapConfig {
+    /// Put the repository map into the context of every new session.
+    #[serde(default)]
+    pub auto: bool,
+
+    /// Approximate size limit of the map in tokens. Default: 4096.
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+}
+
+impl RepoMapConfig {
+    pub fn max_tokens(&self) -> usize {
+        self.max_tokens
+            .unwrap_or(crate::repo_map::DEFAULT_REPO_MAP_MAX_TOKENS)
+    }
+}
+
+/// Language servers whose diagnostics are collected after each patch (`[lsp]`).
+#[derive(Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+pub struct LspConfig {
+    /// Servers by name, e.g. `[lsp.servers.rust] command = "rust-analyzer"`.
+    /// The bridge is off while this is empty.
+    #[serde(default)]
+    pub servers: BTreeMap<String, LspServerConfig>,
+
+    /// Milliseconds to wait for a server to publish diagnostics after a
+    /// change. Default: 5000.
+    #[serde(default)]
+    pub wait_ms: Option<u64>,
+
+    /// Start a fix-up turn when a turn ends with errors that its patches
+    /// introduced.
+    #[serde(default)]
+    pub auto_fix: bool,
+}
+
+impl LspConfig {
+    pub fn wait_ms(&self) -> u64 {
+        self.wait_ms.unwrap_or(crate::lsp::DEFAULT_LSP_WAIT_MS)
+    }
+}
+
+/// One language server started over stdio.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+pub struct LspServerConfig {
+    pub command: String,
+
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// File extensions (without the dot) sent to this server, e.g. `["rs"]`.
+    pub extensions: Vec<String>,
+
+    /// LSP language id for the files; derived from the extension when unset.
+    #[serde(default)]
+    pub language_id: Option<String>,
+}
+
+/// A source of environment context facts besides git.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EnvironmentProviderKind {
+    /// Rust, Node and Python versions pinned or installed for the project.
+    Toolchains,
+    /// Services of the project's docker compose file that are running.
+    DockerCompose,
+    /// The active or project-local Python virtualenv.
+    Virtualenv,
+}
+
+impl EnvironmentProviderKind {
+    pub const ALL: [EnvironmentProviderKind; 3] = [
+        EnvironmentProviderKind::Toolchains,
+        EnvironmentProviderKind::DockerCompose,
+        EnvironmentProviderKind::Virtualenv,
+    ];
+}
+
+/// Environment context settings (`[environment_context]`, or per project
+/// under `[projects."<path>".environment_context]`).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct EnvironmentContextConfig {
+    /// Providers whose facts are added to the environment context.
+    /// Default: all of them; `[]` turns them off.
+    #[serde(default)]
+    pub providers: Option<Vec<EnvironmentProviderKind>>,
+}
+
+impl EnvironmentContextConfig {
+    /// Fields set in `project` replace the matching fields of `self`.
+    pub fn overridden_by(&self, project: &EnvironmentContextConfig) -> EnvironmentContextConfig {
+        EnvironmentContextConfig {
+            providers: project.providers.clone().or_else(|| self.providers.clone()),
+        }
+    }
+
+    pub fn providers(&self) -> Vec<EnvironmentProviderKind> {
+        self.providers
+            .clone()
+            .unwrap_or_else(|| EnvironmentProviderKind::ALL.to_vec())
+    }
+}
+
+/// Severity of a review finding, derived from its `[P0]`–`[P3]` priority.
+#[derive(
+    Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewSeverity {
+    /// P3: nice to have.
+    #[default]
+    Info,
+    /// P2: should be fixed eventually.
+    Warning,
+    /// P1: should be fixed soon.
+    Error,
+    /// P0: blocking.
+    Critical,
+}
+
+impl ReviewSeverity {
+    pub const ALL: [ReviewSeverity; 4] = [
+        ReviewSeverity::Info,
+        ReviewSeverity::Warning,
+        ReviewSeverity::Error,
+        ReviewSeverity::Critical,
+    ];
+
+    /// Maps a finding priority (0 = P0) to a severity. Findings without a
+    /// recognised priority count as warnings.
+    pub fn from_priority(priority: i32) -> Self {
+        match priority {
+            0 => ReviewSeverity::Critical,
+            1 => ReviewSeverity::Error,
+            3 => ReviewSeverity::Info,
+            _ => ReviewSeverity::Warning,
+        }
+    }
+
+    /// Lowest priority tag (`P0`–`P3`) with at least this severity.
+    pub fn priority_label(self) -> &'static str {
+        match self {
+            ReviewSeverity::Info => "P3",
+            ReviewSeverity::Warning => "P2",
+            ReviewSeverity::Error => "P1",
+            ReviewSeverity::Critical => "P0",
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReviewSeverity::Info => "info",
+            ReviewSeverity::Warning => "warning",
+            ReviewSeverity::Error => "error",
+            ReviewSeverity::Critical => "critical",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|severity| severity.as_str().eq_ignore_ascii_case(value.trim()))
+    }
+}
+
+/// Background auto-review settings (`[auto_review]`, or per project under
+/// `[projects."<path>".auto_review]`).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct AutoReviewConfig {
+    /// Review instructions sent to the auto-review agent. `{commit}` is
+    /// replaced with the snapshot commit under review.
+    #[serde(default)]
+    pub prompt: Option<String>,
+
+    /// Only review files matching these globs (default: all files).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+
+    /// Never review files matching these globs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+
+    /// Hide findings below this severity. Default: info (show everything).
+    #[serde(default)]
+    pub min_severity: Option<ReviewSeverity>,
+}
+
+impl AutoReviewConfig {
+    /// Fields set in `project` replace the matching fields of `self`.
+    pub fn overridden_by(&self, project: &AutoReviewConfig) -> AutoReviewConfig {
+        AutoReviewConfig {
+            prompt: project.prompt.clone().or_else(|| self.prompt.clone()),
+            include: if project.include.is_empty() {
+                self.include.clone()
+            } else {
+                project.include.clone()
+            },
+            exclude: if project.exclude.is_empty() {
+                self.exclude.clone()
+            } else {
+                project.exclude.clone()
+            },
+            min_severity: project.min_severity.or(self.min_severity),
+        }
+    }
+
+    pub fn min_severity(&self) -> ReviewSeverity {
+        self.min_severity.unwrap_or_default()
+    }
+}
+
+/// A specialized reviewer that runs after the main `/review` pass
+/// (`[[review_passes]]`). Its findings are merged into the same review.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ReviewPassConfig {
+    /// Short label shown on the pass's section, e.g. `security`.
+    pub name: String,
+
+    /// What this pass looks for. Optional for the built-in `security`,
+    /// `performance` and `tests` passes.
+    #[serde(default)]
+    pub prompt: Option<String>,
+}
+
+/// Per-model request parameters (`[models.<slug>]`), applied to every request
+/// that targets the model, whichever flow (chat, review, planning) sends it.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelOverrides {
+    /// Sampling temperature sent with each request.
+    #[serde(default)]
+    pub temperature: Option<f64>,
+
+    /// Upper bound on generated tokens per response.
+    #[serde(default)]
+    pub max_output_tokens: Option<u64>,
+
+    /// Reasoning effort used when this is the session model and neither the
+    /// active profile nor the CLI picks one.
+    #[serde(default)]
+    pub model_reasoning_effort: Option<ReasoningEffort>,
+
+    /// Text appended to the system instructions.
+    #[serde(default)]
+    pub system_prompt_suffix: Option<String>,
+}
+
+impl ModelOverrides {
+    /// Looks up the overrides for `model`, preferring an exact key and falling
+    /// back to a case-insensitive match.
+    pub fn find<'a>(
+        models: &'a HashMap<String, ModelOverrides>,
+        model: &str,
+    ) -> Option<&'a ModelOverrides> {
+        models.get(model).or_else(|| {
+            models
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(model))
+                .map(|(_, overrides)| overrides)
+        })
+    }
+
+    /// Short labels for the settings that are set, in display order.
+    pub fn summary(&self) -> Vec<String> {
+        let mut parts = Vec::new();
+        if let Some(temperature) = self.temperature {
+            parts.push(format!("temperature {temperature}"));
+        }
+        if let Some(max) = self.max_output_tokens {
+            parts.push(format!("max output {max}"));
+        }
+        if let Some(effort) = self.model_reasoning_effort {
+            parts.push(format!("default effort {}", effort.to_string().to_ascii_lowercase()));
+        }
+        if self
+            .system_prompt_suffix
+            .as_deref()
+            .is_some_and(|suffix| !suffix.trim().is_empty())
+        {
+            parts.push("prompt suffix".to_string());
+        }
+        parts
+    }
+
+    /// Appends `system_prompt_suffix` to the request instructions.
+    pub fn apply_instructions<'a>(&self, instructions: Cow<'a, str>) -> Cow<'a, str> {
+        match self.system_prompt_suffix.as_deref().map(str::trim) {
+            Some(suffix) if !suffix.is_empty() => {
+                Cow::Owned(format!("{}\n\n{suffix}", instructions.trim_end()))
+            }
+            _ => instructions,
+        }
+    }
+
+    /// Writes `temperature` and the output-token cap into a request payload.
+    /// `max_tokens_field` is the wire name of the cap (`max_output_tokens` for
+    /// Responses, `max_tokens` for Chat Completions).
+    pub fn apply_to_payload(&self, payload: &mut serde_json::Value, max_tokens_field: &str) {
+        let Some(obj) = payload.as_object_mut() else {
+            return;
+        };
+        if let Some(temperature) = self.temperature {
+            obj.insert("temperature".to_string(), serde_json::json!(temperature));
+        }
+        if let Some(max) = self.max_output_tokens {
+            obj.insert(max_tokens_field.to_string(), serde_json::json!(max));
+        }
+    }
+}
+
+/// Model a turn falls back to when the active one keeps failing
+/// (`[model_fallback]`).
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelFallbackConfig {
+    /// Model slug to switch to.
+    pub model: String,
+
+    /// Key into `model_providers` to send the fallback requests to. Defaults
+    /// to the session's provider.
+    #[serde(default)]
+    pub provider: Option<String>,
+
+    /// Failed attempts in a row before switching. Defaults to the provider's
+    /// `stream_max_retries`.
+    #[serde(default)]
+    pub after_errors: Option<u32>,
+}
+
+/// Token prices for one model (`[model_pricing.<slug>]`), in USD per million
+/// tokens. Used only to estimate session cost; nothing is billed from it.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelPricing {
+    /// Price of uncached input tokens.
+    pub input: f64,
+
+    /// Price of cached input tokens. Defaults to `input`.
+    #[serde(default)]
+    pub cached_input: Option<f64>,
+
+    /// Price of output tokens, reasoning included.
+    pub output: f64,
+}
+
+impl ModelPricing {
+    /// Looks up the pricing for `model`, preferring an exact key and falling
+    /// back to a case-insensitive match.
+    pub fn find(pricing: &HashMap<String, ModelPricing>, model: &str) -> Option<ModelPricing> {
+        pricing.get(model).copied().or_else(|| {
+            pricing
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(model))
+                .map(|(_, price)| *price)
+        })
+    }
+
+    /// Estimated cost in USD of one request's token usage.
+    pub fn cost_usd(&self, usage: &crate::protocol::TokenUsage) -> f64 {
+        let cached = usage.cached_input() as f64 * self.cached_input.unwrap_or(self.input);
+        let uncached = usage.non_cached_input() as f64 * self.input;
+        let output = usage.output_tokens as f64 * self.output;
+        (cached + uncached + output) / 1_000_000.0
+    }
+}
+
+/// Session guardrails (`[limits]`).
+#[derive(Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// Estimated session cost in USD, priced from `[model_pricing]`, that a
+    /// turn may not run past without confirmation. Unset means no budget.
+    #[serde(default)]
+    pub max_session_cost: Option<f64>,
+}
+
+/// Patch safety settings (`[patches]`).
+#[derive(Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+pub struct PatchesConfig {
+    /// Glob patterns (relative to the session cwd) that apply_patch may never
+    /// modify, e.g. `["**/*.lock", ".env", "secrets/**"]`. Matching changes are
+    /// dropped from the patch regardless of approval mode.
+    #[serde(default)]
+    pub protected: Vec<String>,
+
+    /// Optional formatter per glob, run on every file a patch adds or modifies
+    /// once it applies successfully, e.g. `"*.rs" = "rustfmt --edition 2024"`.
+    /// Matching files are appended to the command line; the first matching
+    /// glob wins. Failures are reported as warnings and never fail the patch.
+    #[serde(default)]
+    pub format_command: BTreeMap<String, String>,
+
+    /// Linters run before a patch is sent for approval, keyed by a short name
+    /// (`[patches.lint.clippy]`). The approval prompt reports the problems a
+    /// patch would introduce, measured on a scratch copy of the workspace.
+    #[serde(default)]
+    pub lint: BTreeMap<String, PatchLintConfig>,
+}
+
+/// One pre-approval linter (`[patches.lint.<name>]`).
+#[derive(Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+pub struct PatchLintConfig {
+    /// Command run from the root of the workspace copy, e.g.
+    /// `"cargo clippy --quiet --message-format=short"`. Output lines shaped
+    /// like `path:line[:col]: message` count as problems.
+    pub command: String,
+
+    /// Only run when the patch touches a file matching one of these globs.
+    /// Empty runs the linter for every patch.
+    #[serde(default)]
+    pub files: Vec<String>,
+
+    /// Defaults to 120 seconds.
+    #[serde(default)]
+    pub timeout_seconds: Option<u64>,
+}
+
+/// User scripting hooks (`[hooks]`), run at fixed points of every session.
+///
+/// Unlike project hooks these can veto or annotate the action they guard:
+/// each command gets a JSON payload on stdin and answers with its exit code
+/// and, optionally, a JSON object on stdout.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+pub struct HooksConfig {
+    /// Run before a user turn is sent to the model.
+    #[serde(default)]
+    pub pre_turn: Vec<ScriptHookConfig>,
+
+    /// Run before a shell command executes.
+    #[serde(default)]
+    pub pre_exec: Vec<ScriptHookConfig>,
+
+    /// Run after a patch applies successfully.
+    #[serde(default)]
+    pub post_patch: Vec<ScriptHookConfig>,
+}
+
+impl HooksConfig {
+    pub fn is_empty(&self) -> bool {
+        self.pre_turn.is_empty() && self.pre_exec.is_empty() && self.post_patch.is_empty()
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct ScriptHookConfig {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(alias = "run", deserialize_with = "deserialize_command_vec")]
+    pub command: Vec<String>,
+    #[serde(default)]
+    pub env: Option<HashMap<String, String>>,
+    /// Defaults to 10 seconds; a hook that times out lets the action proceed.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// Outbound webhook (`[[webhooks]]`) that receives a JSON payload for each
+/// selected session lifecycle event.
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Shared secret used to sign each payload with HMAC-SHA256. Use
+    /// `${env:VAR}` or `${keyring:entry}` to keep it out of `config.toml`.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Events to deliver; all of them when unset.
+    #[serde(default)]
+    pub events: Option<Vec<WebhookEventKind>>,
+    /// Defaults to 10 seconds.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventKind {
+    /// A turn finished and the agent is waiting for input.
+    TurnComplete,
+    /// A patch finished applying, successfully or not.
+    PatchApply,
+    /// The session reported an error.
+    Error,
+}
+
 #[derive(Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
 pub struct ValidationConfig {
     /// Legacy master toggle for the validation harness (kept for config compatibility).
@@ -839,6 +1473,59 @@ pub struct History {
     /// If set, the maximum size of the history file in bytes.
     /// TODO(mbolin): Not currently honored.
     pub max_bytes: Option<usize>,
+
+    /// Where session rollouts are indexed in addition to the JSONL files.
+    #[serde(default)]
+    pub store: HistoryStoreKind,
+
+    /// How adjacent history cells are combined or deduplicated.
+    #[serde(default)]
+    pub merge: HistoryMergePolicy,
+}
+
+/// Rules for folding related history cells together. Applied the same way
+/// to live events and to items replayed from a resumed session.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct HistoryMergePolicy {
+    /// Combine consecutive read/search/list commands with output into one
+    /// cell.
+    #[serde(default = "default_true")]
+    pub merge_exec: bool,
+
+    /// Fold a finished explore cell into the finished explore cell directly
+    /// above it.
+    #[serde(default = "default_true")]
+    pub merge_explore: bool,
+
+    /// Drop an assistant message whose text matches the one directly above
+    /// it.
+    #[serde(default = "default_true")]
+    pub dedupe_assistant: bool,
+
+    /// Skip replayed response items whose id has already been rendered.
+    #[serde(default = "default_true")]
+    pub dedupe_replay_ids: bool,
+}
+
+impl Default for HistoryMergePolicy {
+    fn default() -> Self {
+        Self {
+            merge_exec: true,
+            merge_explore: true,
+            dedupe_assistant: true,
+            dedupe_replay_ids: true,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum HistoryStoreKind {
+    /// JSONL rollouts and the JSONL session catalog only.
+    #[default]
+    Jsonl,
+    /// Also mirror rollouts into `sessions/index/history.sqlite`.
+    Sqlite,
 }
 
 #[derive(Deserialize, Debug, Copy, Clone, PartialEq, Default, JsonSchema)]
@@ -864,6 +1551,34 @@ impl Default for Notifications {
     }
 }
 
+/// Physical cue for an event, for noticing it without watching the screen.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedbackKind {
+    #[default]
+    None,
+    /// Terminal bell (BEL).
+    Bell,
+    /// `termux-vibrate` on Termux; nothing elsewhere.
+    Vibrate,
+    /// Bell and vibration.
+    Both,
+}
+
+/// `[tui.feedback]`: which cue to give for each kind of event.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default, JsonSchema)]
+pub struct FeedbackConfig {
+    /// An exec or patch approval is waiting.
+    #[serde(default)]
+    pub approval: FeedbackKind,
+    /// A turn failed with an error.
+    #[serde(default)]
+    pub error: FeedbackKind,
+    /// A turn finished.
+    #[serde(default)]
+    pub turn_complete: FeedbackKind,
+}
+
 /// Collection of settings that are specific to the TUI.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 pub struct CachedTerminalBackground {
@@ -952,6 +1667,50 @@ pub struct Tui {
     #[serde(default)]
     pub notifications: Notifications,
 
+    /// On Termux, deliver notifications through Termux:API
+    /// (`termux-notification`), with approve/deny buttons on approval prompts,
+    /// instead of terminal escape codes. Ignored elsewhere. Defaults to true.
+    #[serde(default = "default_true")]
+    pub termux_api: bool,
+
+    /// On Termux, hold `termux-wake-lock` while a turn runs so Android does
+    /// not doze the process. Defaults to true.
+    #[serde(default = "default_true")]
+    pub termux_wake_lock: bool,
+
+    /// Bell or vibration cues for approvals, errors, and turn completion.
+    #[serde(default)]
+    pub feedback: FeedbackConfig,
+
+    /// Wrap URLs and paths to existing files in OSC 8 hyperlinks when history
+    /// is written to terminal scrollback (standard terminal mode). Defaults
+    /// to true.
+    #[serde(default = "default_true")]
+    pub hyperlinks: bool,
+
+    /// How links in assistant answers are shown: inline (default), or as
+    /// `[1]`-style references listed at the end of the answer.
+    #[serde(default)]
+    pub link_style: LinkStyle,
+
+    /// Show each history cell's wall-clock time in the gutter, and how long
+    /// the turn took beside final answers. Alt+T toggles it at runtime.
+    #[serde(default)]
+    pub timestamps: bool,
+
+    /// Accessibility mode for terminal screen readers: no animations or
+    /// fades, history cells announced by text labels (`ASSISTANT:`,
+    /// `COMMAND OUTPUT:`), ASCII instead of box-drawing characters, and
+    /// approval prompts laid out one line per fact.
+    #[serde(default)]
+    pub accessibility: bool,
+
+    /// Speech-to-text command run by `/dictate` (argv form). It should record
+    /// speech and print the transcription to stdout. Defaults to
+    /// `termux-speech-to-text` when unset.
+    #[serde(default)]
+    pub dictation_command: Option<Vec<String>>,
+
     /// Whether to use the terminal's Alternate Screen (full-screen) mode.
     /// When false, Codex renders nothing and leaves the standard terminal
     /// buffer visible; users can toggle back to Alternate Screen at runtime
@@ -1003,6 +1762,18 @@ pub enum HeaderHoverStyle {
     None,
 }
 
+/// How links in assistant answers are rendered (`tui.link_style`).
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkStyle {
+    /// Links stay where they are in the text.
+    #[default]
+    Inline,
+    /// Links become numbered references with the URLs listed at the end of
+    /// the answer.
+    Footnotes,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum StatusLineLane {
@@ -1088,6 +1859,14 @@ impl Default for Tui {
             stream: StreamConfig::default(),
             spinner: SpinnerSelection::default(),
             notifications: Notifications::default(),
+            termux_api: true,
+            termux_wake_lock: true,
+            feedback: FeedbackConfig::default(),
+            hyperlinks: true,
+            link_style: LinkStyle::default(),
+            timestamps: false,
+            accessibility: false,
+            dictation_command: None,
             alternate_screen: true,
             review_auto_resolve: true,
             auto_review_enabled: true,
@@ -1231,6 +2010,25 @@ pub struct AutoDriveSettings {
     /// Maximum number of coordinator turns before stopping the session (0 = unlimited).
     #[serde(default = "default_auto_drive_coordinator_turn_cap")]
     pub coordinator_turn_cap: u32,
+
+    /// Guardrail: pause after this many turns in a row without user input
+    /// (0 = unlimited).
+    #[serde(default)]
+    pub max_consecutive_turns: u32,
+
+    /// Guardrail: pause each time the run's estimated cost grows by this
+    /// many US dollars.
+    #[serde(default)]
+    pub max_cost_usd: Option<f64>,
+
+    /// Guardrail: pause for a check-in every N turns (0 = never).
+    #[serde(default)]
+    pub check_in_every_turns: u32,
+
+    /// Guardrail: pause when a turn's final output matches one of these
+    /// regular expressions.
+    #[serde(default)]
+    pub stop_patterns: Vec<String>,
 }
 
 impl Default for AutoDriveSettings {
@@ -1250,6 +2048,10 @@ impl Default for AutoDriveSettings {
             auto_resolve_review_attempts: AutoResolveAttemptLimit::default(),
             auto_review_followup_attempts: AutoResolveAttemptLimit::default(),
             coordinator_turn_cap: default_auto_drive_coordinator_turn_cap(),
+            max_consecutive_turns: 0,
+            max_cost_usd: None,
+            check_in_every_turns: 0,
+            stop_patterns: Vec::new(),
         }
     }
 }
@@ -1329,6 +2131,12 @@ pub struct StreamConfig {
     #[serde(default)]
     pub responsive: bool,
 
+    /// Upper bound on redraws per second triggered by streamed output and
+    /// other background updates; bursts in between are coalesced into one
+    /// frame. Keypresses always redraw immediately. If unset, defaults to 60;
+    /// 0 disables coalescing.
+    #[serde(default)]
+    pub max_fps: Option<u32>,
 }
 
 impl Default for StreamConfig {
@@ -1342,6 +2150,7 @@ impl Default for StreamConfig {
             relax_list_holdback: false,
             relax_code_holdback: false,
             responsive: false,
+            max_fps: None,
         }
     }
 }
@@ -2151,4 +2960,28 @@ mod tests {
             vec!["Use shared bash/zsh syntax.".to_string()]
         );
     }
+
+    #[test]
+    fn model_pricing_prices_cached_input_separately() {
+        let parsed: HashMap<String, ModelPricing> = toml::from_str(
+            r#"
+            [gpt-5]
+            input = 1.25
+            cached_input = 0.125
+            output = 10.0
+        "#,
+        )
+        .expect("pricing table should deserialize");
+
+        let pricing = ModelPricing::find(&parsed, "GPT-5").expect("case-insensitive lookup");
+        let usage = crate::protocol::TokenUsage {
+            input_tokens: 1_000_000,
+            cached_input_tokens: 400_000,
+            output_tokens: 100_000,
+            reasoning_output_tokens: 50_000,
+            total_tokens: 1_100_000,
+        };
+        // 600k uncached * 1.25 + 400k cached * 0.125 + 100k output * 10.
+        assert!((pricing.cost_usd(&usage) - 1.8).abs() < 1e-9);
+    }
 }
@@ -23,6 +23,8 @@ fn create_test_snapshot(cwd: &str, git_branch: Option<&str>) -> EnvironmentConte
         shell: None,
         git_branch: git_branch.map(std::string::ToString::to_string),
         reasoning_effort: None,
+        shell_session: None,
+        providers: BTreeMap::new(),
     }
 }
 
@@ -0,0 +1,219 @@
+//! Detached sessions over a Unix socket.
+//!
+//! `code daemon` runs one conversation in the background and serves it with
+//! [`serve`]; `code attach` connects with [`connect`] and drives it from the
+//! TUI. The wire format is newline-delimited JSON. A client opens with
+//! [`ClientMessage::Attach`] naming the last event sequence number it has
+//! seen (if any); the daemon replays every newer event from its
+//! [`SessionHub`] backlog and then streams live ones as
+//! [`DaemonMessage::Event`]. Every later client line is a
+//! [`ClientMessage::Op`] for the conversation. Several clients may attach at
+//! once and all of them see every event.
+
+use std::io;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::io::Lines;
+use tokio::net::UnixListener;
+use tokio::net::UnixStream;
+use tokio::net::unix::OwnedReadHalf;
+use tokio::net::unix::OwnedWriteHalf;
+use tokio::sync::broadcast;
+use tracing::debug;
+use tracing::warn;
+
+use crate::CodexConversation;
+use crate::protocol::Event;
+use crate::protocol::Op;
+use crate::session_hub::SessionHub;
+use crate::session_hub::is_newer;
+
+/// Socket file created under `CODE_HOME` when no explicit path is given.
+pub const SOCKET_FILE_NAME: &str = "daemon.sock";
+
+/// Time attached clients get to receive `ShutdownComplete` before exit.
+const SHUTDOWN_GRACE: Duration = Duration::from_millis(250);
+
+pub fn default_socket_path(code_home: &Path) -> PathBuf {
+    code_home.join(SOCKET_FILE_NAME)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// First message on every connection. `since` is the last sequence
+    /// number the client already has; `None` replays the whole session.
+    Attach { since: Option<u64> },
+    Op { op: Op },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DaemonMessage {
+    /// `seq` starts at 0 for the `SessionConfigured` event and increases by
+    /// one per event.
+    Event { seq: u64, event: Event },
+}
+
+/// Serves `conversation` on `socket_path` until the conversation shuts down.
+/// Fails if another daemon is already listening there.
+pub async fn serve(
+    conversation: Arc<CodexConversation>,
+    session_configured: Event,
+    socket_path: &Path,
+) -> io::Result<()> {
+    let listener = bind(socket_path).await?;
+    let (hub, mut pump) = SessionHub::start(conversation, session_configured);
+    let result = loop {
+        tokio::select! {
+            _ = &mut pump => {
+                tokio::time::sleep(SHUTDOWN_GRACE).await;
+                break Ok(());
+            }
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    let hub = hub.clone();
+                    tokio::spawn(async move {
+                        if let Err(err) = handle_client(stream, hub).await {
+                            debug!("daemon client disconnected: {err}");
+                        }
+                    });
+                }
+                Err(err) => break Err(err),
+            },
+        }
+    };
+    let _ = std::fs::remove_file(socket_path);
+    result
+}
+
+async fn bind(socket_path: &Path) -> io::Result<UnixListener> {
+    if socket_path.exists() {
+        if UnixStream::connect(socket_path).await.is_ok() {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                format!("a daemon is already listening on {}", socket_path.display()),
+            ));
+        }
+        // Left behind by a daemon that did not exit cleanly.
+        std::fs::remove_file(socket_path)?;
+    }
+    if let Some(parent) = socket_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let listener = UnixListener::bind(socket_path)?;
+    std::fs::set_permissions(socket_path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+async fn handle_client(stream: UnixStream, hub: Arc<SessionHub>) -> io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    let since = match lines.next_line().await? {
+        Some(line) => match serde_json::from_str(&line) {
+            Ok(ClientMessage::Attach { since }) => since,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "expected an attach message",
+                ));
+            }
+        },
+        None => return Ok(()),
+    };
+
+    let (replay, mut live_rx) = hub.subscribe(since)?;
+    let mut last_sent = since;
+    for (seq, event) in replay {
+        write_message(&mut writer, &DaemonMessage::Event { seq, event }).await?;
+        last_sent = Some(seq);
+    }
+
+    loop {
+        tokio::select! {
+            line = lines.next_line() => {
+                let Some(line) = line? else {
+                    return Ok(());
+                };
+                match serde_json::from_str(&line) {
+                    Ok(ClientMessage::Op { op }) => {
+                        if let Err(err) = hub.conversation().submit(op).await {
+                            warn!("daemon failed to submit op: {err}");
+                        }
+                    }
+                    Ok(ClientMessage::Attach { .. }) => {}
+                    Err(err) => warn!("daemon ignored invalid client message: {err}"),
+                }
+            }
+            received = live_rx.recv() => match received {
+                Ok((seq, event)) => {
+                    if is_newer(seq, last_sent) {
+                        write_message(&mut writer, &DaemonMessage::Event { seq, event }).await?;
+                        last_sent = Some(seq);
+                    }
+                }
+                // Too far behind: drop the connection so the client
+                // reattaches and catches up from the backlog.
+                Err(broadcast::error::RecvError::Lagged(_)) => return Ok(()),
+                Err(broadcast::error::RecvError::Closed) => return Ok(()),
+            },
+        }
+    }
+}
+
+async fn write_message<T: Serialize>(writer: &mut OwnedWriteHalf, message: &T) -> io::Result<()> {
+    let mut line = serde_json::to_vec(message).map_err(io::Error::other)?;
+    line.push(b'\n');
+    writer.write_all(&line).await
+}
+
+/// Events from a daemon, in sequence order.
+pub struct DaemonEvents {
+    lines: Lines<BufReader<OwnedReadHalf>>,
+}
+
+impl DaemonEvents {
+    /// Next event with its sequence number; `None` once the daemon hangs up.
+    pub async fn next_event(&mut self) -> io::Result<Option<(u64, Event)>> {
+        let Some(line) = self.lines.next_line().await? else {
+            return Ok(None);
+        };
+        match serde_json::from_str(&line).map_err(io::Error::other)? {
+            DaemonMessage::Event { seq, event } => Ok(Some((seq, event))),
+        }
+    }
+}
+
+/// Sends ops to a daemon's conversation.
+pub struct DaemonOps {
+    writer: OwnedWriteHalf,
+}
+
+impl DaemonOps {
+    pub async fn submit(&mut self, op: Op) -> io::Result<()> {
+        write_message(&mut self.writer, &ClientMessage::Op { op }).await
+    }
+}
+
+/// Attaches to the daemon at `socket_path`, asking for events after `since`
+/// (or the whole session when `None`).
+pub async fn connect(socket_path: &Path, since: Option<u64>) -> io::Result<(DaemonEvents, DaemonOps)> {
+    let stream = UnixStream::connect(socket_path).await?;
+    let (reader, mut writer) = stream.into_split();
+    write_message(&mut writer, &ClientMessage::Attach { since }).await?;
+    Ok((
+        DaemonEvents {
+            lines: BufReader::new(reader).lines(),
+        },
+        DaemonOps { writer },
+    ))
+}
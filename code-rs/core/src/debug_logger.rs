@@ -8,6 +8,8 @@ use std::path::PathBuf;
 use std::sync::Mutex;
 use uuid::Uuid;
 
+use crate::wire_log::WireLog;
+
 #[derive(Debug)]
 struct StreamInfo {
     response_file: PathBuf,
@@ -24,6 +26,9 @@ pub struct DebugLogger {
     session_usage_file: Mutex<PathBuf>,
     turn_latency_dir: PathBuf,
     turn_latency_file: Mutex<Option<PathBuf>>,
+    /// `[wire_log]`: sanitized requests and responses, kept even when
+    /// debug logging is off.
+    wire: Option<WireLog>,
 }
 
 impl DebugLogger {
@@ -37,6 +42,7 @@ impl DebugLogger {
                 session_usage_file: Mutex::new(PathBuf::new()),
                 turn_latency_dir: PathBuf::new(),
                 turn_latency_file: Mutex::new(None),
+                wire: None,
             });
         }
 
@@ -60,9 +66,15 @@ impl DebugLogger {
             session_usage_file: Mutex::new(PathBuf::new()),
             turn_latency_dir,
             turn_latency_file: Mutex::new(None),
+            wire: None,
         })
     }
 
+    pub fn with_wire_log(mut self, wire: Option<WireLog>) -> Self {
+        self.wire = wire;
+        self
+    }
+
     fn ensure_log_dir(&self, tag: Option<&str>) -> Result<PathBuf, std::io::Error> {
         let Some(tag) = tag else {
             return Ok(self.log_dir.clone());
@@ -103,13 +115,20 @@ impl DebugLogger {
         headers: Option<&Value>,
         tag: Option<&str>,
     ) -> Result<String, std::io::Error> {
-        if !self.enabled {
+        if !self.enabled && self.wire.is_none() {
             return Ok(String::new());
         }
 
+        let request_id = Uuid::new_v4().to_string();
+        if let Some(wire) = self.wire.as_ref() {
+            wire.record_request(&request_id, endpoint, payload, headers);
+        }
+        if !self.enabled {
+            return Ok(request_id);
+        }
+
         let log_dir = self.ensure_log_dir(tag)?;
         let timestamp = Local::now();
-        let request_id = Uuid::new_v4().to_string();
         let request_id_short = &request_id[..8]; // Use first 8 chars of UUID for brevity
 
         // Create request file with pretty-printed JSON
@@ -164,7 +183,13 @@ impl DebugLogger {
         event_type: &str,
         data: &Value,
     ) -> Result<(), std::io::Error> {
-        if !self.enabled || request_id.is_empty() {
+        if request_id.is_empty() {
+            return Ok(());
+        }
+        if let Some(wire) = self.wire.as_ref() {
+            wire.record_event(request_id, event_type, data);
+        }
+        if !self.enabled {
             return Ok(());
         }
 
@@ -192,7 +217,13 @@ impl DebugLogger {
 
     /// Mark a stream as completed and write all collected events to response file
     pub fn end_request_log(&self, request_id: &str) -> Result<(), std::io::Error> {
-        if !self.enabled || request_id.is_empty() {
+        if request_id.is_empty() {
+            return Ok(());
+        }
+        if let Some(wire) = self.wire.as_ref() {
+            wire.finish(request_id);
+        }
+        if !self.enabled {
             return Ok(());
         }
 
@@ -447,6 +478,9 @@ impl DebugLogger {
         error: &str,
         tag: Option<&str>,
     ) -> Result<(), std::io::Error> {
+        if let Some(wire) = self.wire.as_ref() {
+            wire.record_error(endpoint, error);
+        }
         if !self.enabled {
             return Ok(());
         }
@@ -164,6 +164,7 @@ pub fn create_client(originator: &str) -> reqwest::Client {
     if is_sandboxed() {
         builder = builder.no_proxy();
     }
+    builder = crate::http_client::apply_network_config(builder);
 
     builder.build().unwrap_or_else(|_| reqwest::Client::new())
 }
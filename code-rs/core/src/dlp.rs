@@ -0,0 +1,210 @@
+//! Outbound content rules (`[[dlp.rules]]`). Before file content, command
+//! output or a browser snapshot is sent to the model it is checked against
+//! the configured path globs and regex denylist; a match either ends the turn
+//! or asks the user to confirm the send.
+
+use std::path::Path;
+
+use regex_lite::Regex;
+use wildmatch::WildMatch;
+
+use crate::config_types::DlpAction;
+use crate::config_types::DlpConfig;
+use crate::parse_command::ParsedCommand;
+use crate::parse_command::parse_command;
+
+#[derive(Debug, Clone, Default)]
+pub struct DlpPolicy {
+    rules: Vec<CompiledRule>,
+}
+
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    name: String,
+    action: DlpAction,
+    paths: Vec<(String, WildMatch)>,
+    patterns: Vec<Regex>,
+}
+
+/// A rule matched by content about to be sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DlpViolation {
+    pub rule: String,
+    pub action: DlpAction,
+    /// What matched, e.g. "content of `secrets/prod.env`".
+    pub reason: String,
+}
+
+impl DlpViolation {
+    pub fn describe(&self) -> String {
+        format!("DLP rule `{}` flagged {}", self.rule, self.reason)
+    }
+}
+
+impl DlpPolicy {
+    /// Compiles `config.rules`. Invalid patterns are rejected when the config
+    /// loads, so any that reach here are skipped.
+    pub fn from_config(config: &DlpConfig) -> Self {
+        let rules = config
+            .rules
+            .iter()
+            .map(|rule| CompiledRule {
+                name: rule.name.clone(),
+                action: rule.action,
+                paths: rule
+                    .paths
+                    .iter()
+                    .map(|glob| (glob.clone(), WildMatch::new(glob.trim_start_matches("./"))))
+                    .collect(),
+                patterns: rule
+                    .patterns
+                    .iter()
+                    .filter_map(|pattern| match Regex::new(pattern) {
+                        Ok(regex) => Some(regex),
+                        Err(err) => {
+                            tracing::warn!("Skipping DLP pattern `{pattern}`: {err}");
+                            None
+                        }
+                    })
+                    .collect(),
+            })
+            .collect();
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The rule broken by sending `text`, read from `paths`. Files embedded
+    /// as `<file path="…">` attachments count as read. A blocking rule wins
+    /// over one that asks for confirmation.
+    pub fn check(&self, text: &str, paths: &[String]) -> Option<DlpViolation> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut paths = paths.to_vec();
+        paths.extend(attached_paths(text));
+
+        let mut found: Option<DlpViolation> = None;
+        for rule in &self.rules {
+            let Some(reason) = rule.matches(text, &paths) else {
+                continue;
+            };
+            let violation = DlpViolation {
+                rule: rule.name.clone(),
+                action: rule.action,
+                reason,
+            };
+            if violation.action == DlpAction::Block {
+                return Some(violation);
+            }
+            found.get_or_insert(violation);
+        }
+        found
+    }
+}
+
+impl CompiledRule {
+    fn matches(&self, text: &str, paths: &[String]) -> Option<String> {
+        for path in paths {
+            let path = path.trim_start_matches("./");
+            let file_name = Path::new(path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or(path);
+            for (glob, matcher) in &self.paths {
+                let candidate = if glob.contains('/') { path } else { file_name };
+                if matcher.matches(candidate) {
+                    return Some(format!("content of `{path}`"));
+                }
+            }
+        }
+        self.patterns
+            .iter()
+            .find(|regex| regex.is_match(text))
+            .map(|regex| format!("text matching `{}`", regex.as_str()))
+    }
+}
+
+/// Paths of files embedded in `text` as `@path` attachments.
+fn attached_paths(text: &str) -> Vec<String> {
+    const OPEN: &str = "<file path=\"";
+    let mut paths = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(OPEN) {
+        rest = &rest[start + OPEN.len()..];
+        let Some(end) = rest.find('"') else {
+            break;
+        };
+        paths.push(rest[..end].to_string());
+        rest = &rest[end..];
+    }
+    paths
+}
+
+/// Files a shell command reads, as far as the command parser can tell.
+pub fn read_paths(command: &[String]) -> Vec<String> {
+    let mut paths = Vec::new();
+    for parsed in parse_command(command) {
+        let ParsedCommand::Read { cmd, name } = parsed else {
+            continue;
+        };
+        let args = shlex::split(&cmd).unwrap_or_default();
+        paths.extend(args.into_iter().skip(1).filter(|arg| !arg.starts_with('-')));
+        paths.push(name);
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_types::DlpRule;
+
+    fn policy(rules: Vec<DlpRule>) -> DlpPolicy {
+        DlpPolicy::from_config(&DlpConfig { rules })
+    }
+
+    fn rule(name: &str, paths: &[&str], patterns: &[&str], action: DlpAction) -> DlpRule {
+        DlpRule {
+            name: name.to_string(),
+            paths: paths.iter().map(ToString::to_string).collect(),
+            patterns: patterns.iter().map(ToString::to_string).collect(),
+            action,
+        }
+    }
+
+    #[test]
+    fn path_globs_match_reads_and_attachments() {
+        let policy = policy(vec![
+            rule("env", &[".env*"], &[], DlpAction::Confirm),
+            rule("prod", &["deploy/prod/*"], &[], DlpAction::Block),
+        ]);
+        let read = read_paths(&["cat".to_string(), "config/.env.local".to_string()]);
+        let violation = policy.check("API=1", &read).expect("env read");
+        assert_eq!(violation.rule, "env");
+        assert_eq!(violation.reason, "content of `config/.env.local`");
+
+        let attached = "see\n<file path=\"deploy/prod/values.yaml\">\nreplicas: 3\n</file>\n";
+        let violation = policy.check(attached, &[]).expect("prod attachment");
+        assert_eq!(violation.action, DlpAction::Block);
+        assert_eq!(policy.check("<file path=\"src/lib.rs\">", &[]), None);
+    }
+
+    #[test]
+    fn blocking_rules_win_over_confirmation() {
+        let policy = policy(vec![
+            rule("internal", &[], &["(?i)internal only"], DlpAction::Confirm),
+            rule("customer ids", &[], &[r"CUST-\d{8}"], DlpAction::Block),
+        ]);
+        let violation = policy
+            .check("INTERNAL ONLY: CUST-12345678", &[])
+            .expect("violation");
+        assert_eq!(violation.rule, "customer ids");
+        assert_eq!(
+            violation.describe(),
+            r"DLP rule `customer ids` flagged text matching `CUST-\d{8}`"
+        );
+    }
+}
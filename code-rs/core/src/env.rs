@@ -1,5 +1,10 @@
 //! Environment detection helpers shared across crates.
 
+use std::process::Command;
+use std::process::Stdio;
+use std::time::Duration;
+use std::time::Instant;
+
 fn env_var_set(key: &str) -> bool {
     std::env::var(key).is_ok_and(|value| !value.trim().is_empty())
 }
@@ -22,16 +27,50 @@ pub fn is_wsl() -> bool {
     }
 }
 
+/// Returns true when running inside the Termux app on Android.
+pub fn is_termux() -> bool {
+    std::env::var_os("TERMUX_VERSION").is_some()
+        || std::env::var("PREFIX").is_ok_and(|prefix| prefix.contains("/com.termux/"))
+}
+
+/// Runs a Termux:API command such as `termux-battery-status` and returns its
+/// stdout, giving up after `timeout`; these commands block indefinitely when
+/// the Termux:API app is missing.
+pub(crate) fn termux_api_output(program: &str, timeout: Duration) -> Option<Vec<u8>> {
+    let mut child = Command::new(program)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(50)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                tracing::debug!("{program} timed out");
+                return None;
+            }
+        }
+    }
+    Some(child.wait_with_output().ok()?.stdout)
+}
+
+/// Returns true when the process was started from an SSH login, so the user's
+/// terminal (and clipboard) live on another machine.
+pub fn is_ssh_session() -> bool {
+    env_var_set("SSH_CONNECTION") || env_var_set("SSH_CLIENT") || env_var_set("SSH_TTY")
+}
+
 /// Returns true when Code is likely running in an environment without a usable GUI.
 ///
 /// This is intentionally conservative and is used by frontends to avoid flows that would try to
 /// open a browser (for example, device-code auth fallback).
 pub fn is_headless_environment() -> bool {
-    if env_var_set("CI")
-        || env_var_set("SSH_CONNECTION")
-        || env_var_set("SSH_CLIENT")
-        || env_var_set("SSH_TTY")
-    {
+    if env_var_set("CI") || is_ssh_session() {
         return true;
     }
 
@@ -10,6 +10,7 @@ use std::path::PathBuf;
 use strum_macros::Display as DeriveDisplay;
 use which::which;
 
+use crate::persistent_shell::ShellSessionContext;
 use crate::protocol::AskForApproval;
 use crate::protocol::SandboxPolicy;
 use crate::shell::Shell;
@@ -42,6 +43,12 @@ pub(crate) struct EnvironmentContext {
     pub common_tools: Option<Vec<String>>,
     pub shell: Option<Shell>,
     pub current_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell_session: Option<ShellSessionContext>,
+    /// Facts from the `[environment_context] providers`: toolchain versions,
+    /// running compose services, the virtualenv.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub providers: Option<BTreeMap<String, String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -155,9 +162,24 @@ impl EnvironmentContext {
             common_tools: detect_common_tools(),
             shell,
             current_date: Some(Local::now().format("%Y-%m-%d").to_string()),
+            shell_session: None,
+            providers: None,
         }
     }
 
+    /// Attaches the persistent shell state (working directory and exports
+    /// carried over from earlier commands), when that feature is enabled.
+    pub fn with_shell_session(mut self, shell_session: Option<ShellSessionContext>) -> Self {
+        self.shell_session = shell_session;
+        self
+    }
+
+    /// Attaches the facts reported by the environment providers.
+    pub fn with_providers(mut self, providers: BTreeMap<String, String>) -> Self {
+        self.providers = (!providers.is_empty()).then_some(providers);
+        self
+    }
+
     /// Compares two environment contexts, ignoring the shell. Useful when
     /// comparing turn to turn, since the initial environment_context will
     /// include the shell, and then it is not configurable from turn to turn.
@@ -172,6 +194,8 @@ impl EnvironmentContext {
             operating_system,
             common_tools,
             current_date,
+            shell_session,
+            providers,
             // should compare all fields except shell
             shell: _,
         } = other;
@@ -184,6 +208,8 @@ impl EnvironmentContext {
             && self.operating_system == *operating_system
             && self.common_tools == *common_tools
             && self.current_date == *current_date
+            && self.shell_session == *shell_session
+            && self.providers == *providers
     }
 }
 
@@ -209,6 +235,13 @@ impl EnvironmentContext {
     ///   </operating_system>
     ///   <common_tools>...</common_tools>
     ///   <shell>...</shell>
+    ///   <shell_session>
+    ///     <cwd>...</cwd>
+    ///     <exported>...</exported>
+    ///   </shell_session>
+    ///   <providers>
+    ///     <rust>...</rust>
+    ///   </providers>
     /// </environment_context>
     /// ```
     pub fn serialize_to_xml(self) -> String {
@@ -268,6 +301,26 @@ impl EnvironmentContext {
         {
             lines.push(format!("  <shell>{shell_name}</shell>"));
         }
+        if let Some(shell_session) = self.shell_session {
+            lines.push("  <shell_session>".to_string());
+            if let Some(cwd) = shell_session.cwd {
+                lines.push(format!("    <cwd>{}</cwd>", cwd.to_string_lossy()));
+            }
+            if !shell_session.exported.is_empty() {
+                lines.push(format!(
+                    "    <exported>{}</exported>",
+                    shell_session.exported.join(" ")
+                ));
+            }
+            lines.push("  </shell_session>".to_string());
+        }
+        if let Some(providers) = self.providers {
+            lines.push("  <providers>".to_string());
+            for (name, value) in providers {
+                lines.push(format!("    <{name}>{value}</{name}>"));
+            }
+            lines.push("  </providers>".to_string());
+        }
         lines.push(ENVIRONMENT_CONTEXT_CLOSE_TAG.to_string());
         lines.join("\n")
     }
@@ -308,6 +361,10 @@ pub struct EnvironmentContextSnapshot {
     pub git_branch: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reasoning_effort: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell_session: Option<ShellSessionContext>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub providers: BTreeMap<String, String>,
 }
 
 impl EnvironmentContextSnapshot {
@@ -330,6 +387,8 @@ impl EnvironmentContextSnapshot {
             shell: ctx.shell.clone(),
             git_branch: None,
             reasoning_effort: None,
+            shell_session: ctx.shell_session.clone(),
+            providers: ctx.providers.clone().unwrap_or_default(),
         }
     }
 
@@ -357,6 +416,12 @@ impl EnvironmentContextSnapshot {
         if let Some(reasoning) = &self.reasoning_effort {
             map.insert("reasoning_effort", reasoning.clone());
         }
+        if !self.providers.is_empty() {
+            map.insert(
+                "providers",
+                serde_json::to_string(&self.providers).unwrap_or_default(),
+            );
+        }
 
         let encoded = serde_json::to_vec(&map).unwrap_or_default();
         let mut sha = Sha1::new();
@@ -424,6 +489,18 @@ impl EnvironmentContextSnapshot {
                 option_string_to_json(&self.reasoning_effort),
             );
         }
+        if self.shell_session != previous.shell_session {
+            changes.insert(
+                "shell_session".to_string(),
+                serde_json::to_value(&self.shell_session).unwrap_or(JsonValue::Null),
+            );
+        }
+        if self.providers != previous.providers {
+            changes.insert(
+                "providers".to_string(),
+                serde_json::to_value(&self.providers).unwrap_or(JsonValue::Null),
+            );
+        }
 
         EnvironmentContextDelta {
             version: Self::VERSION,
@@ -506,6 +583,20 @@ impl EnvironmentContextSnapshot {
                         _ => updated.reasoning_effort.clone(),
                     };
                 }
+                "shell_session" => {
+                    updated.shell_session =
+                        serde_json::from_value::<Option<ShellSessionContext>>(value.clone())
+                            .unwrap_or_else(|_| updated.shell_session.clone());
+                }
+                "providers" => {
+                    if let Ok(providers) =
+                        serde_json::from_value::<BTreeMap<String, String>>(value.clone())
+                    {
+                        updated.providers = providers;
+                    } else if value.is_null() {
+                        updated.providers.clear();
+                    }
+                }
                 _ => {}
             }
         }
@@ -557,6 +648,10 @@ pub struct BrowserSnapshot {
     pub viewport: Option<ViewportDimensions>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, String>>,
+    /// Open tabs, listed when there is more than one so the snapshot says
+    /// which of them it shows.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tabs: Vec<code_browser::BrowserTab>,
 }
 
 impl BrowserSnapshot {
@@ -570,6 +665,7 @@ impl BrowserSnapshot {
             captured_at,
             viewport: None,
             metadata: None,
+            tabs: Vec::new(),
         }
     }
 
@@ -1239,4 +1335,20 @@ mod tests {
         assert_eq!(date.chars().nth(4), Some('-'));
         assert_eq!(date.chars().nth(7), Some('-'));
     }
+
+    #[test]
+    fn provider_changes_travel_as_deltas() {
+        let ctx = EnvironmentContext::new(Some(PathBuf::from("/repo")), None, None, None);
+        let mut facts = BTreeMap::new();
+        facts.insert("rust".to_string(), "1.79.0".to_string());
+        let before = EnvironmentContextSnapshot::from_context(&ctx.clone().with_providers(facts.clone()));
+        facts.insert("rust".to_string(), "1.80.0".to_string());
+        facts.insert("docker_compose".to_string(), "db, web".to_string());
+        let after = EnvironmentContextSnapshot::from_context(&ctx.with_providers(facts.clone()));
+
+        assert_ne!(before.fingerprint(), after.fingerprint());
+        let delta = after.diff_from(&before);
+        assert_eq!(delta.changes.keys().collect::<Vec<_>>(), vec!["providers"]);
+        assert_eq!(before.apply_delta(&delta).providers, facts);
+    }
 }
@@ -0,0 +1,317 @@
+//! Environment context providers. Besides the git branch, the environment
+//! context reports facts about the project's tooling: pinned or installed
+//! toolchain versions, running docker compose services and the Python
+//! virtualenv. Each provider adds `name -> value` facts to the snapshot's
+//! `providers` map, so a change (a new toolchain pin, a service that stopped)
+//! travels as an ordinary environment context delta.
+//!
+//! Pinned versions are read from files first. Commands such as
+//! `rustc --version` are only run as a fallback, with a short timeout, and
+//! results are cached for [`REFRESH_INTERVAL`].
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::config_types::EnvironmentContextConfig;
+use crate::config_types::EnvironmentProviderKind;
+
+/// How long detected facts are reused before the providers run again.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Longest a detection command may run.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(2);
+
+const COMPOSE_FILES: &[&str] = &[
+    "compose.yaml",
+    "compose.yml",
+    "docker-compose.yaml",
+    "docker-compose.yml",
+];
+
+/// A source of environment facts for the project in `cwd`.
+trait EnvironmentProvider: Send + Sync {
+    fn detect(&self, cwd: &Path, facts: &mut BTreeMap<String, String>);
+}
+
+fn provider_for(kind: EnvironmentProviderKind) -> Box<dyn EnvironmentProvider> {
+    match kind {
+        EnvironmentProviderKind::Toolchains => Box::new(ToolchainProvider),
+        EnvironmentProviderKind::DockerCompose => Box::new(DockerComposeProvider),
+        EnvironmentProviderKind::Virtualenv => Box::new(VirtualenvProvider),
+    }
+}
+
+struct CachedFacts {
+    cwd: PathBuf,
+    detected_at: Instant,
+    facts: BTreeMap<String, String>,
+}
+
+/// The providers enabled by `[environment_context] providers`, with their
+/// most recent results.
+pub(crate) struct EnvironmentProviders {
+    providers: Vec<Box<dyn EnvironmentProvider>>,
+    cache: Mutex<Option<CachedFacts>>,
+}
+
+impl EnvironmentProviders {
+    pub(crate) fn new(config: &EnvironmentContextConfig) -> Self {
+        Self {
+            providers: config.providers().into_iter().map(provider_for).collect(),
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Facts for `cwd`, detected at most once per [`REFRESH_INTERVAL`].
+    pub(crate) fn facts(&self, cwd: &Path) -> BTreeMap<String, String> {
+        if self.providers.is_empty() {
+            return BTreeMap::new();
+        }
+        let Ok(mut cache) = self.cache.lock() else {
+            return BTreeMap::new();
+        };
+        if let Some(cached) = cache.as_ref()
+            && cached.cwd == cwd
+            && cached.detected_at.elapsed() < REFRESH_INTERVAL
+        {
+            return cached.facts.clone();
+        }
+        let mut facts = BTreeMap::new();
+        for provider in &self.providers {
+            provider.detect(cwd, &mut facts);
+        }
+        *cache = Some(CachedFacts {
+            cwd: cwd.to_path_buf(),
+            detected_at: Instant::now(),
+            facts: facts.clone(),
+        });
+        facts
+    }
+}
+
+/// Rust, Node and Python versions for projects that use them.
+struct ToolchainProvider;
+
+impl EnvironmentProvider for ToolchainProvider {
+    fn detect(&self, cwd: &Path, facts: &mut BTreeMap<String, String>) {
+        let asdf = read_tool_versions(cwd);
+
+        let rust_pin = read_rust_toolchain(cwd).or_else(|| asdf.get("rust").cloned());
+        if rust_pin.is_some() || cwd.join("Cargo.toml").is_file() {
+            let version = rust_pin.or_else(|| {
+                command_output("rustc", &["--version"])
+                    .and_then(|out| out.split_whitespace().nth(1).map(str::to_string))
+            });
+            if let Some(version) = version {
+                facts.insert("rust".to_string(), version);
+            }
+        }
+
+        let node_pin = read_first_line(cwd, &[".nvmrc", ".node-version"])
+            .or_else(|| asdf.get("nodejs").cloned());
+        if node_pin.is_some() || cwd.join("package.json").is_file() {
+            let version = node_pin.or_else(|| command_output("node", &["--version"]));
+            if let Some(version) = version {
+                facts.insert("node".to_string(), version);
+            }
+        }
+
+        let python_pin =
+            read_first_line(cwd, &[".python-version"]).or_else(|| asdf.get("python").cloned());
+        let python_project = ["pyproject.toml", "requirements.txt", "setup.py"]
+            .iter()
+            .any(|name| cwd.join(name).is_file());
+        if python_pin.is_some() || python_project {
+            let version = python_pin.or_else(|| {
+                command_output("python3", &["--version"])
+                    .map(|out| out.trim_start_matches("Python").trim().to_string())
+            });
+            if let Some(version) = version {
+                facts.insert("python".to_string(), version);
+            }
+        }
+    }
+}
+
+/// Running services of the project's compose file.
+struct DockerComposeProvider;
+
+impl EnvironmentProvider for DockerComposeProvider {
+    fn detect(&self, cwd: &Path, facts: &mut BTreeMap<String, String>) {
+        if !COMPOSE_FILES.iter().any(|name| cwd.join(name).is_file()) {
+            return;
+        }
+        let value = match command_output_in(
+            cwd,
+            "docker",
+            &["compose", "ps", "--services", "--filter", "status=running"],
+        ) {
+            Some(output) => running_services(&output),
+            None => "unavailable".to_string(),
+        };
+        facts.insert("docker_compose".to_string(), value);
+    }
+}
+
+/// The active virtualenv, or a project-local one that is not activated.
+struct VirtualenvProvider;
+
+impl EnvironmentProvider for VirtualenvProvider {
+    fn detect(&self, cwd: &Path, facts: &mut BTreeMap<String, String>) {
+        if let Some(active) = std::env::var_os("VIRTUAL_ENV").filter(|value| !value.is_empty()) {
+            let path = PathBuf::from(active);
+            let shown = path
+                .strip_prefix(cwd)
+                .map(Path::to_path_buf)
+                .unwrap_or(path);
+            facts.insert("virtualenv".to_string(), format!("{} (active)", shown.display()));
+            return;
+        }
+        if let Some(conda) = std::env::var("CONDA_DEFAULT_ENV").ok().filter(|value| !value.is_empty()) {
+            facts.insert("virtualenv".to_string(), format!("conda {conda} (active)"));
+            return;
+        }
+        for name in [".venv", "venv"] {
+            if cwd.join(name).join("pyvenv.cfg").is_file() {
+                facts.insert("virtualenv".to_string(), format!("{name} (not activated)"));
+                return;
+            }
+        }
+    }
+}
+
+/// `rust-toolchain.toml`'s `channel`, or the plain `rust-toolchain` file.
+fn read_rust_toolchain(cwd: &Path) -> Option<String> {
+    if let Ok(contents) = std::fs::read_to_string(cwd.join("rust-toolchain.toml")) {
+        return contents
+            .parse::<toml::Table>()
+            .ok()?
+            .get("toolchain")?
+            .get("channel")?
+            .as_str()
+            .map(str::to_string);
+    }
+    read_first_line(cwd, &["rust-toolchain"])
+}
+
+/// asdf's `.tool-versions`: one `tool version` pair per line.
+fn read_tool_versions(cwd: &Path) -> BTreeMap<String, String> {
+    let Ok(contents) = std::fs::read_to_string(cwd.join(".tool-versions")) else {
+        return BTreeMap::new();
+    };
+    parse_tool_versions(&contents)
+}
+
+fn parse_tool_versions(contents: &str) -> BTreeMap<String, String> {
+    contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            Some((parts.next()?.to_string(), parts.next()?.to_string()))
+        })
+        .collect()
+}
+
+fn read_first_line(cwd: &Path, names: &[&str]) -> Option<String> {
+    names.iter().find_map(|name| {
+        let contents = std::fs::read_to_string(cwd.join(name)).ok()?;
+        contents
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+    })
+}
+
+fn running_services(output: &str) -> String {
+    let mut services: Vec<&str> = output.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+    if services.is_empty() {
+        return "no services running".to_string();
+    }
+    services.sort_unstable();
+    services.join(", ")
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    command_output_in(Path::new("."), program, args)
+}
+
+/// Trimmed stdout of a successful command, or `None` when the program is
+/// missing, fails or runs past [`COMMAND_TIMEOUT`].
+fn command_output_in(cwd: &Path, program: &str, args: &[&str]) -> Option<String> {
+    let program = which::which(program).ok()?;
+    let mut child = Command::new(program)
+        .args(args)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if started.elapsed() < COMMAND_TIMEOUT => {
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+    let output = child.wait_with_output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pinned_versions_come_from_project_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            dir.path().join("rust-toolchain.toml"),
+            "[toolchain]\nchannel = \"1.80.0\"\n",
+        )
+        .expect("write toolchain");
+        std::fs::write(dir.path().join(".nvmrc"), "# lts\nv20.11.1\n").expect("write nvmrc");
+        std::fs::write(dir.path().join(".tool-versions"), "python 3.12.2 # pinned\n")
+            .expect("write tool-versions");
+
+        let mut facts = BTreeMap::new();
+        ToolchainProvider.detect(dir.path(), &mut facts);
+        assert_eq!(facts.get("rust").map(String::as_str), Some("1.80.0"));
+        assert_eq!(facts.get("node").map(String::as_str), Some("v20.11.1"));
+        assert_eq!(facts.get("python").map(String::as_str), Some("3.12.2"));
+    }
+
+    #[test]
+    fn compose_services_are_sorted() {
+        assert_eq!(running_services("web\ndb\n\n"), "db, web");
+        assert_eq!(running_services(""), "no services running");
+    }
+
+    #[test]
+    fn disabled_providers_report_nothing() {
+        let providers = EnvironmentProviders::new(&EnvironmentContextConfig {
+            providers: Some(Vec::new()),
+        });
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join(".nvmrc"), "20\n").expect("write nvmrc");
+        assert!(providers.facts(dir.path()).is_empty());
+    }
+}
@@ -91,6 +91,7 @@ pub(crate) fn map_response_item_to_event_messages(
                                 snapshot,
                                 url,
                                 captured_at,
+                                screenshot_path: None,
                             }));
                             continue;
                         }
@@ -323,6 +324,7 @@ mod tests {
                 snapshot,
                 url,
                 captured_at,
+                screenshot_path: None,
             }) => {
                 assert_eq!(snapshot, &payload);
                 assert_eq!(url.as_deref(), Some("https://example.com"));
@@ -127,6 +127,9 @@ pub struct StdoutStream {
     /// Optional ordering metadata so UIs can associate deltas with the correct
     /// provider attempt/output index even when `session` is not available.
     pub(crate) order: Option<OrderMeta>,
+    /// Masks secrets in live output chunks. A secret split across two chunks
+    /// is still masked in the final output.
+    pub(crate) redactor: Option<Arc<crate::redaction::SecretRedactor>>,
 
     /// Optional directory to spool full stdout/stderr output for this exec.
     ///
@@ -614,7 +617,15 @@ async fn emit_pending_delta(
         return;
     }
 
-    let chunk = std::mem::take(pending_delta);
+    let mut chunk = std::mem::take(pending_delta);
+    if let Some(redactor) = &stream.redactor
+        && let Ok(text) = std::str::from_utf8(&chunk)
+    {
+        let (masked, count) = redactor.redact(text);
+        if count > 0 {
+            chunk = masked.into_owned().into_bytes();
+        }
+    }
     let msg = EventMsg::ExecCommandOutputDelta(ExecCommandOutputDeltaEvent {
         call_id: stream.call_id.clone(),
         stream: if is_stderr {
@@ -0,0 +1,146 @@
+//! Git helpers for exporting or committing the files a session touched.
+//!
+//! The TUI tracks the first-seen contents of every file the agent patched; these
+//! helpers turn that baseline into a standalone `.patch` or a scoped commit
+//! without picking up unrelated work in the tree.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use tokio::process::Command;
+
+/// Builds a git-style unified diff of `files` (absolute path plus baseline
+/// contents) against what is on disk now. Paths are written relative to
+/// `repo_root`; an empty baseline for a file that now exists is shown as an
+/// addition and a file missing on disk as a deletion.
+pub fn session_diff_patch(repo_root: &Path, files: &[(PathBuf, String)]) -> String {
+    let mut sorted: Vec<&(PathBuf, String)> = files.iter().collect();
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut out = String::new();
+    for (path, baseline) in sorted {
+        let current = std::fs::read_to_string(path).ok();
+        if current.as_deref() == Some(baseline.as_str()) {
+            continue;
+        }
+        let rel = path
+            .strip_prefix(repo_root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let added = baseline.is_empty() && current.is_some();
+        let deleted = current.is_none();
+        let modified = current.unwrap_or_default();
+        let patch = diffy::create_patch(baseline, &modified).to_string();
+
+        out.push_str(&format!("diff --git a/{rel} b/{rel}\n"));
+        if added {
+            out.push_str(&format!("new file mode 100644\n--- /dev/null\n+++ b/{rel}\n"));
+        } else if deleted {
+            out.push_str(&format!("deleted file mode 100644\n--- a/{rel}\n+++ /dev/null\n"));
+        } else {
+            out.push_str(&format!("--- a/{rel}\n+++ b/{rel}\n"));
+        }
+        // Drop diffy's own `--- original` / `+++ modified` header.
+        for line in patch.lines().skip_while(|line| !line.starts_with("@@")) {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Stages exactly `paths` (including deletions), leaving everything else in the
+/// index untouched.
+pub async fn stage_paths(repo_root: &Path, paths: &[PathBuf]) -> Result<(), String> {
+    if paths.is_empty() {
+        return Err("No files to stage".to_string());
+    }
+    let output = Command::new("git")
+        .current_dir(repo_root)
+        .args(["add", "-A", "--"])
+        .args(paths)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git add: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git add failed: {}", stderr.trim()));
+    }
+    Ok(())
+}
+
+/// Returns `git diff --cached` for the repository.
+pub async fn staged_diff(repo_root: &Path) -> Result<String, String> {
+    let output = Command::new("git")
+        .current_dir(repo_root)
+        .args(["diff", "--cached", "--no-color"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git diff: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git diff --cached failed: {}", stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Commits whatever is staged with `message` and returns the short hash of the
+/// new commit.
+pub async fn commit_staged(repo_root: &Path, message: &str) -> Result<String, String> {
+    let output = Command::new("git")
+        .current_dir(repo_root)
+        .args(["commit", "-q", "-m", message])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git commit: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let detail = if stderr.trim().is_empty() { stdout } else { stderr };
+        return Err(format!("git commit failed: {}", detail.trim()));
+    }
+    let output = Command::new("git")
+        .current_dir(repo_root)
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to read new commit: {e}"))?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_patch_marks_added_modified_and_deleted_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        let modified = root.join("src/lib.rs");
+        let added = root.join("NEW.md");
+        let deleted = root.join("old.txt");
+        let untouched = root.join("same.txt");
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(&modified, "fn a() {}\nfn b() {}\n").unwrap();
+        std::fs::write(&added, "hello\n").unwrap();
+        std::fs::write(&untouched, "same\n").unwrap();
+
+        let patch = session_diff_patch(
+            root,
+            &[
+                (modified, "fn a() {}\n".to_string()),
+                (added, String::new()),
+                (deleted, "bye\n".to_string()),
+                (untouched, "same\n".to_string()),
+            ],
+        );
+
+        assert!(patch.contains("diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@"));
+        assert!(patch.contains("+fn b() {}\n"));
+        assert!(patch.contains("new file mode 100644\n--- /dev/null\n+++ b/NEW.md\n"));
+        assert!(patch.contains("deleted file mode 100644\n--- a/old.txt\n+++ /dev/null\n"));
+        assert!(!patch.contains("same.txt"));
+        assert!(!patch.contains("original"));
+    }
+}
@@ -792,6 +792,41 @@ pub async fn detect_default_branch(cwd: &Path) -> Option<String> {
     None
 }
 
+/// Move a session started in `cwd` into its own worktree (`worktree_isolation`).
+///
+/// Creates a `code-session-<timestamp>` branch off the current HEAD, carries
+/// uncommitted changes over and records the branch metadata `/merge` uses to
+/// fast-forward the result back. Returns the directory in the worktree that
+/// matches `cwd` and the branch name.
+pub async fn setup_isolated_session_worktree(cwd: &Path) -> Result<(PathBuf, String), String> {
+    let git_root = get_git_root_from(cwd).await?;
+    let base_branch = Command::new("git")
+        .current_dir(&git_root)
+        .args(["branch", "--show-current"])
+        .output()
+        .await
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|name| !name.is_empty());
+
+    let branch_name = format!("code-session-{}", Utc::now().format("%Y%m%d-%H%M%S"));
+    let (worktree, branch) = setup_worktree(&git_root, &branch_name, None).await?;
+    copy_uncommitted_to_worktree(&git_root, &worktree).await?;
+    if let Some(meta) = ensure_local_default_remote(&git_root, base_branch.as_deref()).await? {
+        write_branch_metadata(&worktree, &meta).await?;
+    }
+
+    let session_cwd = std::fs::canonicalize(cwd)
+        .ok()
+        .zip(std::fs::canonicalize(&git_root).ok())
+        .and_then(|(cwd, root)| cwd.strip_prefix(&root).ok().map(Path::to_path_buf))
+        .map(|relative| worktree.join(relative))
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(|| worktree.clone());
+    Ok((session_cwd, branch))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -867,4 +902,36 @@ mod tests {
 
         restore_home(prev_home);
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn isolated_session_worktree_keeps_checkout_untouched() {
+        let temp_home = TempDir::new().expect("temp home");
+        let repo_dir = temp_home.path().join("repo");
+        init_repo(&repo_dir).await;
+        tokio::fs::create_dir_all(repo_dir.join("src")).await.expect("create src");
+        tokio::fs::write(repo_dir.join("src/lib.rs"), b"// wip").await.expect("write wip");
+
+        let prev_home = std::env::var("HOME").ok();
+        set_home(temp_home.path());
+
+        let (session_cwd, branch) = setup_isolated_session_worktree(&repo_dir.join("src"))
+            .await
+            .expect("isolated worktree");
+
+        assert!(branch.starts_with("code-session-"));
+        assert!(is_branch_worktree_path(&session_cwd));
+        assert!(session_cwd.ends_with("src"));
+        assert_eq!(
+            std::fs::read_to_string(session_cwd.join("lib.rs")).expect("copied wip"),
+            "// wip"
+        );
+        std::fs::write(session_cwd.join("lib.rs"), "// agent edit").expect("agent edit");
+        assert_eq!(
+            std::fs::read_to_string(repo_dir.join("src/lib.rs")).expect("original wip"),
+            "// wip"
+        );
+
+        restore_home(prev_home);
+    }
 }
@@ -0,0 +1,300 @@
+//! Stable textual rendering of a [`HistorySnapshot`] for golden tests.
+//!
+//! The format lists one block per record in display order: a header with the
+//! order key and record kind, followed by `| `-prefixed content lines.
+//! History ids, timestamps, durations, and other run-specific values are left
+//! out so transcripts from separate runs diff cleanly.
+
+use std::fmt::Write as _;
+
+use super::state::*;
+use crate::protocol::FileChange;
+
+/// First line of every golden transcript; bump when the layout changes.
+pub const GOLDEN_HEADER: &str = "# code history golden v1";
+
+/// Renders `snapshot` as a golden transcript.
+pub fn golden_transcript(snapshot: &HistorySnapshot) -> String {
+    let mut out = String::new();
+    out.push_str(GOLDEN_HEADER);
+    out.push('\n');
+    for (idx, record) in snapshot.records.iter().enumerate() {
+        let order = snapshot
+            .order
+            .get(idx)
+            .map(|key| format!("{}:{}:{}", key.req, key.out, key.seq))
+            .unwrap_or_else(|| "-".to_string());
+        let (kind, lines) = describe_record(record);
+        let _ = writeln!(out, "\n[{idx:04}] order={order} {kind}");
+        for line in lines {
+            if line.is_empty() {
+                out.push_str("  |\n");
+            } else {
+                let _ = writeln!(out, "  | {line}");
+            }
+        }
+    }
+    out
+}
+
+fn describe_record(record: &HistoryRecord) -> (String, Vec<String>) {
+    match record {
+        HistoryRecord::PlainMessage(state) => {
+            let mut lines = Vec::new();
+            if let Some(header) = &state.header {
+                lines.push(header_text(header));
+            }
+            lines.extend(state.lines.iter().map(message_line_text));
+            (format!("plain kind={:?} role={:?}", state.kind, state.role), lines)
+        }
+        HistoryRecord::WaitStatus(state) => {
+            let mut lines = vec![state.header.title.clone()];
+            lines.extend(state.header.summary.clone());
+            lines.extend(state.details.iter().map(|detail| match &detail.value {
+                Some(value) => format!("{}: {value}", detail.label),
+                None => detail.label.clone(),
+            }));
+            ("wait_status".to_string(), lines)
+        }
+        HistoryRecord::Loading(state) => ("loading".to_string(), vec![state.message.clone()]),
+        HistoryRecord::RunningTool(state) => {
+            let mut lines = vec![state.title.clone()];
+            lines.extend(state.arguments.iter().map(argument_text));
+            ("running_tool".to_string(), lines)
+        }
+        HistoryRecord::ToolCall(state) => {
+            let mut lines = vec![state.title.clone()];
+            lines.extend(state.arguments.iter().map(argument_text));
+            if let Some(preview) = &state.result_preview {
+                lines.extend(preview.lines.iter().map(|line| format!("> {line}")));
+                if preview.truncated {
+                    lines.push("> …".to_string());
+                }
+            }
+            lines.extend(state.error_message.iter().map(|err| format!("error: {err}")));
+            (format!("tool_call status={:?}", state.status), lines)
+        }
+        HistoryRecord::PlanUpdate(state) => {
+            let mut lines = vec![format!(
+                "{} ({}/{})",
+                state.name, state.progress.completed, state.progress.total
+            )];
+            lines.extend(
+                state
+                    .steps
+                    .iter()
+                    .map(|step| format!("[{:?}] {}", step.status, step.description)),
+            );
+            ("plan_update".to_string(), lines)
+        }
+        HistoryRecord::UpgradeNotice(state) => (
+            "upgrade_notice".to_string(),
+            vec![
+                format!("{} -> {}", state.current_version, state.latest_version),
+                state.message.clone(),
+            ],
+        ),
+        HistoryRecord::Reasoning(state) => {
+            let mut lines = Vec::new();
+            for section in &state.sections {
+                lines.extend(section.heading.iter().map(|heading| format!("## {heading}")));
+                lines.extend(section.blocks.iter().flat_map(reasoning_block_lines));
+            }
+            ("reasoning".to_string(), lines)
+        }
+        HistoryRecord::Exec(record) => (
+            format!("exec status={:?}", record.status),
+            exec_lines(record),
+        ),
+        HistoryRecord::MergedExec(record) => (
+            format!("merged_exec action={:?}", record.action),
+            record.segments.iter().flat_map(exec_lines).collect(),
+        ),
+        HistoryRecord::AssistantStream(state) => (
+            format!("assistant_stream in_progress={}", state.in_progress),
+            text_lines(&state.preview_markdown),
+        ),
+        HistoryRecord::AssistantMessage(state) => {
+            let kind = if state.mid_turn {
+                "assistant mid_turn"
+            } else {
+                "assistant"
+            };
+            (kind.to_string(), text_lines(&state.markdown))
+        }
+        HistoryRecord::Diff(record) => {
+            let mut lines = vec![record.title.clone()];
+            for hunk in &record.hunks {
+                lines.push(hunk.header.clone());
+                lines.extend(hunk.lines.iter().map(|line| {
+                    let sign = match line.kind {
+                        DiffLineKind::Context => ' ',
+                        DiffLineKind::Addition => '+',
+                        DiffLineKind::Removal => '-',
+                    };
+                    format!("{sign}{}", line.content)
+                }));
+            }
+            ("diff".to_string(), lines)
+        }
+        HistoryRecord::Image(record) => {
+            let mut lines = vec![format!("{}x{}", record.width, record.height)];
+            lines.extend(record.alt_text.clone());
+            ("image".to_string(), lines)
+        }
+        HistoryRecord::Explore(record) => (
+            "explore".to_string(),
+            record
+                .entries
+                .iter()
+                .map(|entry| format!("[{:?}] {:?}", entry.status, entry.summary))
+                .collect(),
+        ),
+        HistoryRecord::RateLimits(record) => (
+            "rate_limits".to_string(),
+            record
+                .legend
+                .iter()
+                .map(|entry| format!("{}: {}", entry.label, entry.description))
+                .collect(),
+        ),
+        HistoryRecord::Patch(record) => {
+            let mut paths: Vec<_> = record.changes.iter().collect();
+            paths.sort_by(|a, b| a.0.cmp(b.0));
+            let mut lines: Vec<String> = paths
+                .into_iter()
+                .map(|(path, change)| match change {
+                    FileChange::Add { content } => {
+                        format!("A {} (+{})", path.display(), content.lines().count())
+                    }
+                    FileChange::Delete => format!("D {}", path.display()),
+                    FileChange::Update {
+                        move_path: Some(dest),
+                        ..
+                    } => format!("R {} -> {}", path.display(), dest.display()),
+                    FileChange::Update { .. } => format!("M {}", path.display()),
+                })
+                .collect();
+            if let Some(failure) = &record.failure {
+                lines.push(format!("failed: {}", failure.message));
+            }
+            (format!("patch type={:?}", record.patch_type), lines)
+        }
+        HistoryRecord::BackgroundEvent(record) => {
+            let mut lines = Vec::new();
+            if !record.title.is_empty() {
+                lines.push(record.title.clone());
+            }
+            lines.extend(text_lines(&record.description));
+            ("background".to_string(), lines)
+        }
+        HistoryRecord::Notice(record) => {
+            let mut lines: Vec<String> = record.title.iter().cloned().collect();
+            lines.extend(record.body.iter().map(message_line_text));
+            ("notice".to_string(), lines)
+        }
+        HistoryRecord::Context(record) => {
+            let mut lines = Vec::new();
+            lines.extend(record.cwd.iter().map(|cwd| format!("cwd: {cwd}")));
+            lines.extend(record.git_branch.iter().map(|branch| format!("branch: {branch}")));
+            lines.extend(record.deltas.iter().map(|delta| {
+                format!(
+                    "{:?}: {} -> {}",
+                    delta.field,
+                    delta.previous.as_deref().unwrap_or("-"),
+                    delta.current.as_deref().unwrap_or("-")
+                )
+            }));
+            ("context".to_string(), lines)
+        }
+    }
+}
+
+fn header_text(header: &MessageHeader) -> String {
+    match &header.badge {
+        Some(badge) => format!("# {} [{badge}]", header.label),
+        None => format!("# {}", header.label),
+    }
+}
+
+fn spans_text(spans: &[InlineSpan]) -> String {
+    spans.iter().map(|span| span.text.as_str()).collect()
+}
+
+fn message_line_text(line: &MessageLine) -> String {
+    let text = spans_text(&line.spans);
+    match &line.kind {
+        MessageLineKind::Bullet { indent, .. } => {
+            format!("{}- {text}", "  ".repeat(usize::from(*indent)))
+        }
+        MessageLineKind::Quote => format!("> {text}"),
+        MessageLineKind::Separator => "---".to_string(),
+        _ => text,
+    }
+}
+
+fn reasoning_block_lines(block: &ReasoningBlock) -> Vec<String> {
+    match block {
+        ReasoningBlock::Paragraph(spans) => vec![spans_text(spans)],
+        ReasoningBlock::Bullet { indent, spans, .. } => vec![format!(
+            "{}- {}",
+            "  ".repeat(usize::from(*indent)),
+            spans_text(spans)
+        )],
+        ReasoningBlock::Code { content, .. } => text_lines(content),
+        ReasoningBlock::Quote(spans) => vec![format!("> {}", spans_text(spans))],
+        ReasoningBlock::Separator => vec!["---".to_string()],
+    }
+}
+
+fn argument_text(argument: &ToolArgument) -> String {
+    format!("{}: {:?}", argument.name, argument.value)
+}
+
+fn exec_lines(record: &ExecRecord) -> Vec<String> {
+    let mut lines = vec![format!("$ {}", record.command.join(" "))];
+    for (label, chunks) in [("stdout", &record.stdout_chunks), ("stderr", &record.stderr_chunks)] {
+        let mut chunks: Vec<&ExecStreamChunk> = chunks.iter().collect();
+        chunks.sort_by_key(|chunk| chunk.offset);
+        let text: String = chunks.iter().map(|chunk| chunk.content.as_str()).collect();
+        lines.extend(text.lines().map(|line| format!("{label}: {line}")));
+    }
+    if let Some(code) = record.exit_code {
+        lines.push(format!("exit {code}"));
+    }
+    lines
+}
+
+fn text_lines(text: &str) -> Vec<String> {
+    text.trim_end().lines().map(|line| line.trim_end().to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transcript_includes_order_keys_and_omits_ids() {
+        let snapshot = HistorySnapshot {
+            records: vec![HistoryRecord::BackgroundEvent(BackgroundEventRecord {
+                id: HistoryId(42),
+                title: String::new(),
+                description: "indexing\nready".to_string(),
+            })],
+            next_id: 43,
+            exec_call_lookup: Default::default(),
+            tool_call_lookup: Default::default(),
+            stream_lookup: Default::default(),
+            order: vec![OrderKeySnapshot {
+                req: 1,
+                out: -1,
+                seq: 3,
+            }],
+            order_debug: Vec::new(),
+        };
+        assert_eq!(
+            golden_transcript(&snapshot),
+            "# code history golden v1\n\n[0000] order=1:-1:3 background\n  | indexing\n  | ready\n"
+        );
+    }
+}
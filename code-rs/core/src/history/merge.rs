@@ -0,0 +1,162 @@
+//! Merge and deduplication rules shared by live rendering and replay.
+//!
+//! Each helper answers a single question about two neighbouring records and
+//! returns the combined record (or a verdict) when the configured
+//! [`HistoryMergePolicy`] allows it. Callers own the actual cell swap.
+
+use super::state::*;
+pub use crate::config_types::HistoryMergePolicy;
+
+impl HistoryMergePolicy {
+    /// Folds a completed read/search/list exec into the exec or merged exec
+    /// directly above it. Both sides must have produced output and share the
+    /// same action.
+    pub fn merge_exec(
+        &self,
+        previous: &HistoryRecord,
+        current: &ExecRecord,
+    ) -> Option<MergedExecRecord> {
+        if !self.merge_exec
+            || matches!(current.action, ExecAction::Run)
+            || !exec_has_output(current)
+        {
+            return None;
+        }
+        match previous {
+            HistoryRecord::Exec(prev) if prev.action == current.action && exec_has_output(prev) => {
+                Some(MergedExecRecord {
+                    id: prev.id,
+                    action: prev.action,
+                    segments: vec![prev.clone(), current.clone()],
+                })
+            }
+            HistoryRecord::MergedExec(prev) if prev.action == current.action => {
+                let mut merged = prev.clone();
+                merged.segments.push(current.clone());
+                Some(merged)
+            }
+            _ => None,
+        }
+    }
+
+    /// Appends `current`'s entries to `previous` when both explore cells
+    /// would render the same header, i.e. neither has a running entry.
+    pub fn merge_explore(
+        &self,
+        previous: &ExploreRecord,
+        current: &ExploreRecord,
+    ) -> Option<ExploreRecord> {
+        if !self.merge_explore
+            || previous.entries.is_empty()
+            || current.entries.is_empty()
+            || explore_is_running(previous)
+            || explore_is_running(current)
+        {
+            return None;
+        }
+        let mut merged = previous.clone();
+        merged.entries.extend(current.entries.iter().cloned());
+        Some(merged)
+    }
+
+    /// True when `previous` is an assistant message whose text matches
+    /// `markdown` once whitespace and bullet glyphs are normalized.
+    pub fn is_duplicate_assistant(&self, previous: Option<&HistoryRecord>, markdown: &str) -> bool {
+        if !self.dedupe_assistant {
+            return false;
+        }
+        let Some(HistoryRecord::AssistantMessage(prev)) = previous else {
+            return false;
+        };
+        normalize_message_text(&prev.markdown) == normalize_message_text(markdown)
+    }
+}
+
+/// Normalizes message text for equality checks: unifies newlines, trims
+/// trailing whitespace, collapses blank runs, and maps bullet glyphs to `-`.
+pub fn normalize_message_text(text: &str) -> String {
+    let text = text.replace("\r\n", "\n");
+    let mut out: Vec<String> = Vec::new();
+    let mut saw_blank = false;
+    for line in text.lines() {
+        let line = line.replace(['\u{2022}', '\u{25E6}', '\u{2219}'], "-");
+        let trimmed = line.trim_end();
+        if trimmed.is_empty() {
+            if !saw_blank {
+                out.push(String::new());
+            }
+            saw_blank = true;
+        } else {
+            out.push(trimmed.to_string());
+            saw_blank = false;
+        }
+    }
+    while out.last().is_some_and(String::is_empty) {
+        out.pop();
+    }
+    out.join("\n")
+}
+
+fn exec_has_output(record: &ExecRecord) -> bool {
+    !record.stdout_chunks.is_empty() || !record.stderr_chunks.is_empty()
+}
+
+fn explore_is_running(record: &ExploreRecord) -> bool {
+    record
+        .entries
+        .iter()
+        .any(|entry| matches!(entry.status, ExploreEntryStatus::Running))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn explore(status: ExploreEntryStatus) -> ExploreRecord {
+        ExploreRecord {
+            id: HistoryId(1),
+            entries: vec![ExploreEntry {
+                action: ExecAction::List,
+                summary: ExploreSummary::List {
+                    path: Some("src".to_string()),
+                },
+                status,
+            }],
+        }
+    }
+
+    #[test]
+    fn explore_cells_merge_only_when_both_finished() {
+        let policy = HistoryMergePolicy::default();
+        let done = explore(ExploreEntryStatus::Success);
+        let running = explore(ExploreEntryStatus::Running);
+
+        let merged = policy.merge_explore(&done, &done).expect("finished cells merge");
+        assert_eq!(merged.entries.len(), 2);
+        assert!(policy.merge_explore(&done, &running).is_none());
+        assert!(policy.merge_explore(&running, &running).is_none());
+
+        let disabled = HistoryMergePolicy {
+            merge_explore: false,
+            ..HistoryMergePolicy::default()
+        };
+        assert!(disabled.merge_explore(&done, &done).is_none());
+    }
+
+    #[test]
+    fn duplicate_assistant_ignores_trailing_whitespace_and_bullets() {
+        let policy = HistoryMergePolicy::default();
+        let previous = HistoryRecord::AssistantMessage(AssistantMessageState {
+            id: HistoryId(1),
+            stream_id: None,
+            markdown: "• one\n\n\n• two  \n".to_string(),
+            citations: Vec::new(),
+            metadata: None,
+            token_usage: None,
+            mid_turn: false,
+            created_at: std::time::SystemTime::UNIX_EPOCH,
+        });
+        assert!(policy.is_duplicate_assistant(Some(&previous), "- one\n\n- two"));
+        assert!(!policy.is_duplicate_assistant(Some(&previous), "- one"));
+    }
+}
@@ -1,3 +1,7 @@
+pub mod merge;
 pub mod state;
+#[cfg(any(test, feature = "test-support"))]
+pub mod golden;
 
+pub use merge::*;
 pub use state::*;
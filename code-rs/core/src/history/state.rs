@@ -1,6 +1,7 @@
 use crate::plan_tool::StepStatus;
 use crate::parse_command::ParsedCommand;
 use crate::protocol::{FileChange, RateLimitSnapshotEvent, TokenUsage};
+use super::merge::HistoryMergePolicy;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
@@ -88,6 +89,7 @@ pub enum HistoryDomainEvent {
         wait_total: Option<Duration>,
         wait_active: bool,
         wait_notes: Vec<ExecWaitNote>,
+        redacted_values: usize,
     },
 }
 
@@ -628,6 +630,9 @@ pub struct ExecRecord {
     pub env: Vec<(String, String)>,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Secret values masked in the output by `[redaction]`.
+    #[serde(default)]
+    pub redacted_values: usize,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -998,6 +1003,8 @@ pub enum ContextDeltaField {
     GitBranch,
     ReasoningEffort,
     BrowserSnapshot,
+    /// A fact from an environment provider, e.g. `rust` or `docker_compose`.
+    Environment(String),
 }
 
 
@@ -1024,6 +1031,19 @@ pub struct ContextBrowserSnapshotRecord {
     pub height: Option<u32>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub metadata: BTreeMap<String, String>,
+    /// Open tabs when the snapshot listed more than one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tabs: Vec<ContextBrowserTabRecord>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextBrowserTabRecord {
+    pub index: usize,
+    pub url: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub active: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -1037,6 +1057,11 @@ pub struct ContextRecord {
     pub deltas: Vec<ContextDeltaRecord>,
     pub browser_snapshot: Option<ContextBrowserSnapshotRecord>,
     pub expanded: bool,
+    /// Facts the user pinned with `/context pin key=value`.
+    pub pinned: BTreeMap<String, String>,
+    /// Toolchain versions, compose services and virtualenv reported by the
+    /// environment providers.
+    pub providers: BTreeMap<String, String>,
 }
 
 impl Default for ContextRecord {
@@ -1050,6 +1075,8 @@ impl Default for ContextRecord {
             deltas: Vec::new(),
             browser_snapshot: None,
             expanded: false,
+            pinned: BTreeMap::new(),
+            providers: BTreeMap::new(),
         }
     }
 }
@@ -1067,8 +1094,9 @@ const EXEC_STREAM_BYTE_THRESHOLD: usize = 8 * 1024 * 1024;
 const EXEC_STREAM_BYTE_STEP: usize = 2 * 1024 * 1024;
 
 /// Maximum per-stream payload we retain in memory for exec stdout/stderr.
-/// Older bytes are truncated from the front once this threshold is exceeded.
-pub const MAX_EXEC_STREAM_RETAINED_BYTES: usize = 32 * 1024 * 1024; // 32 MiB
+/// Older bytes are truncated from the front once this threshold is exceeded;
+/// the TUI spills the full output of larger commands to disk instead.
+pub const MAX_EXEC_STREAM_RETAINED_BYTES: usize = 2 * 1024 * 1024; // 2 MiB
 
 /// Global cap across *all* exec streams we keep in memory. When exceeded, we
 /// progressively trim the oldest exec records down to a small tail to keep RSS
@@ -1381,6 +1409,24 @@ pub struct HistoryState {
     exec_stream_retained_total_bytes: usize,
     #[serde(skip)]
     exec_stream_retained_by_id: HashMap<HistoryId, usize>,
+    #[serde(skip)]
+    merge_policy: HistoryMergePolicy,
+    #[serde(skip)]
+    replayed_item_ids: HashSet<String>,
+    #[serde(skip)]
+    token_usage_cursor: Option<HistoryId>,
+    #[serde(skip)]
+    pending_token_usage: TokenUsage,
+}
+
+/// Aggregate of the per-turn token usage stored on assistant messages.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TokenUsageSummary {
+    /// Assistant messages that carry a usage report.
+    pub turns: usize,
+    pub total: TokenUsage,
+    /// The most expensive turn by blended total.
+    pub largest: Option<(HistoryId, TokenUsage)>,
 }
 
 #[allow(dead_code)]
@@ -1402,7 +1448,32 @@ impl HistoryState {
             usage_tracker: HistoryUsageTracker::default(),
             exec_stream_retained_total_bytes: 0,
             exec_stream_retained_by_id: HashMap::new(),
+            merge_policy: HistoryMergePolicy::default(),
+            replayed_item_ids: HashSet::new(),
+            token_usage_cursor: None,
+            pending_token_usage: TokenUsage::default(),
+        }
+    }
+
+    pub fn with_merge_policy(merge_policy: HistoryMergePolicy) -> Self {
+        Self {
+            merge_policy,
+            ..Self::new()
+        }
+    }
+
+    pub fn merge_policy(&self) -> &HistoryMergePolicy {
+        &self.merge_policy
+    }
+
+    /// Records that a replayed response item with `item_id` is being
+    /// rendered. Returns false when the id was already seen and the merge
+    /// policy asks for replay deduplication.
+    pub fn note_replayed_item(&mut self, item_id: &str) -> bool {
+        if !self.merge_policy.dedupe_replay_ids {
+            return true;
         }
+        self.replayed_item_ids.insert(item_id.to_string())
     }
 
     pub fn push(&mut self, record: HistoryRecord) -> HistoryId {
@@ -1593,6 +1664,65 @@ impl HistoryState {
             self.usage_tracker.on_insert(record);
         }
         self.rebuild_exec_stream_retained_totals();
+        self.token_usage_cursor = self.latest_assistant_message().map(|(_, id)| id);
+        self.pending_token_usage = TokenUsage::default();
+    }
+
+    fn latest_assistant_message(&self) -> Option<(usize, HistoryId)> {
+        self.records
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(idx, record)| match record {
+                HistoryRecord::AssistantMessage(state) => Some((idx, state.id)),
+                _ => None,
+            })
+    }
+
+    /// Attributes one provider usage report (`TokenCount.last_token_usage`)
+    /// to the newest assistant message. Reports that arrive before a new
+    /// assistant message exists, such as tool-call requests, are carried
+    /// forward and folded into the next message's count.
+    pub fn record_token_usage(&mut self, usage: &TokenUsage) -> Option<HistoryId> {
+        self.pending_token_usage.add_assign(usage);
+        let (idx, id) = self.latest_assistant_message()?;
+        if self.token_usage_cursor == Some(id) {
+            return None;
+        }
+        if let Some(HistoryRecord::AssistantMessage(state)) = self.records.get_mut(idx) {
+            state.token_usage = Some(std::mem::take(&mut self.pending_token_usage));
+        }
+        self.token_usage_cursor = Some(id);
+        Some(id)
+    }
+
+    /// Per-turn usage in display order.
+    pub fn token_usage_by_turn(&self) -> Vec<(HistoryId, &TokenUsage)> {
+        self.records
+            .iter()
+            .filter_map(|record| match record {
+                HistoryRecord::AssistantMessage(state) => {
+                    state.token_usage.as_ref().map(|usage| (state.id, usage))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn token_usage_summary(&self) -> TokenUsageSummary {
+        let mut summary = TokenUsageSummary::default();
+        for (id, usage) in self.token_usage_by_turn() {
+            summary.turns += 1;
+            summary.total.add_assign(usage);
+            let is_larger = summary
+                .largest
+                .as_ref()
+                .is_none_or(|(_, largest)| usage.blended_total() > largest.blended_total());
+            if is_larger {
+                summary.largest = Some((id, usage.clone()));
+            }
+        }
+        summary
     }
 
     fn rebuild_exec_stream_retained_totals(&mut self) {
@@ -2059,6 +2189,7 @@ impl HistoryState {
                     working_dir,
                     env,
                     tags,
+                    redacted_values: 0,
                 };
                 self.apply_event(HistoryEvent::Insert {
                     index: insert_index,
@@ -2076,6 +2207,7 @@ impl HistoryState {
                 wait_notes,
                 stdout_tail,
                 stderr_tail,
+                redacted_values,
             } => {
                 let mut target_idx = id.and_then(|hid| self.index_of(hid));
                 if target_idx.is_none()
@@ -2093,6 +2225,7 @@ impl HistoryState {
                         updated.wait_total = wait_total;
                         updated.wait_active = wait_active;
                         updated.wait_notes = wait_notes;
+                        updated.redacted_values = redacted_values;
 
                         if let Some(tail) = stdout_tail
                             && !tail.is_empty() {
@@ -2337,6 +2470,25 @@ impl HistoryRecord {
             HistoryRecord::Context(state) => state.id,
         }
     }
+
+    /// When the record's event happened, for records that carry a time.
+    pub fn timestamp(&self) -> Option<SystemTime> {
+        match self {
+            HistoryRecord::RunningTool(state) => Some(state.started_at),
+            HistoryRecord::Exec(state) => Some(state.started_at),
+            HistoryRecord::MergedExec(state) => {
+                state.segments.first().map(|segment| segment.started_at)
+            }
+            HistoryRecord::AssistantStream(state) => Some(
+                state
+                    .deltas
+                    .first()
+                    .map_or(state.last_updated_at, |delta| delta.received_at),
+            ),
+            HistoryRecord::AssistantMessage(state) => Some(state.created_at),
+            _ => None,
+        }
+    }
 }
 
 #[allow(dead_code)]
@@ -2477,6 +2629,7 @@ mod tests {
             }],
             stdout_tail: Some("output".into()),
             stderr_tail: Some("warn".into()),
+            redacted_values: 0,
         });
 
         assert!(matches!(finish, HistoryMutation::Replaced { id, .. } if id == inserted_id));
@@ -2633,6 +2786,49 @@ mod tests {
         assert_eq!(remaining.stream_id.as_deref(), Some("stream-dup"));
     }
 
+    #[test]
+    fn token_usage_carries_tool_requests_into_next_message() {
+        let usage = |total: u64| TokenUsage {
+            input_tokens: total,
+            total_tokens: total,
+            ..TokenUsage::default()
+        };
+        let assistant = |markdown: &str| {
+            HistoryRecord::AssistantMessage(AssistantMessageState {
+                id: HistoryId::ZERO,
+                stream_id: None,
+                markdown: markdown.to_string(),
+                citations: Vec::new(),
+                metadata: None,
+                token_usage: None,
+                mid_turn: false,
+                created_at: SystemTime::UNIX_EPOCH,
+            })
+        };
+
+        let mut state = HistoryState::new();
+        let first = state.push(assistant("first"));
+        assert_eq!(state.record_token_usage(&usage(100)), Some(first));
+        // A tool-call request with no new message is held back...
+        assert_eq!(state.record_token_usage(&usage(30)), None);
+        let second = state.push(assistant("second"));
+        // ...and folded into the next message's count.
+        assert_eq!(state.record_token_usage(&usage(20)), Some(second));
+
+        let summary = state.token_usage_summary();
+        assert_eq!(summary.turns, 2);
+        assert_eq!(summary.total.total_tokens, 150);
+        assert_eq!(summary.largest.map(|(id, _)| id), Some(first));
+        assert_eq!(
+            state
+                .token_usage_by_turn()
+                .into_iter()
+                .map(|(_, usage)| usage.total_tokens)
+                .collect::<Vec<_>>(),
+            vec![100, 50]
+        );
+    }
+
     #[test]
     fn restore_preserves_distinct_messages_without_stream_id() {
         let assistant = |id: u64, text: &str| HistoryRecord::AssistantMessage(AssistantMessageState {
@@ -2797,6 +2993,7 @@ mod tests {
             working_dir: None,
             env: Vec::new(),
             tags: Vec::new(),
+            redacted_values: 0,
         };
         let exec_id = state.push(HistoryRecord::Exec(exec_record));
         assert_ne!(exec_id, HistoryId::ZERO);
@@ -2852,6 +3049,7 @@ mod tests {
             working_dir: None,
             env: Vec::new(),
             tags: Vec::new(),
+            redacted_values: 0,
         }));
 
         let tool_id = state.push(HistoryRecord::ToolCall(ToolCallState {
@@ -3029,6 +3227,7 @@ mod tests {
             working_dir: None,
             env: Vec::new(),
             tags: Vec::new(),
+            redacted_values: 0,
         }));
 
         records.push(HistoryRecord::AssistantStream(AssistantStreamState {
@@ -1,5 +1,12 @@
 use std::fs;
 use std::path::PathBuf;
+use std::sync::RwLock;
+
+use crate::config_types::NetworkConfig;
+
+/// `[network]` settings applied by [`apply_network_config`]; set once config
+/// is loaded.
+static NETWORK: RwLock<Option<NetworkSettings>> = RwLock::new(None);
 
 /// Build a reqwest Client with optional extra root certificates loaded from
 /// common environment variables (SSL_CERT_FILE, REQUESTS_CA_BUNDLE,
@@ -43,5 +50,149 @@ pub fn build_http_client() -> reqwest::Client {
             }
     }
 
-    builder.build().unwrap_or_else(|_| reqwest::Client::new())
+    apply_network_config(builder)
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new())
+}
+
+/// Compiled `[network]` config: the proxy (with its `no_proxy` list) and the
+/// extra root certificates.
+#[derive(Clone, Default)]
+pub struct NetworkSettings {
+    proxy: Option<reqwest::Proxy>,
+    root_certificates: Vec<reqwest::Certificate>,
+}
+
+impl NetworkSettings {
+    /// Parses the proxy URL and reads `ca_bundle`, which must already be an
+    /// absolute path.
+    pub fn from_config(config: &NetworkConfig) -> Result<Self, String> {
+        let proxy = match config.proxy.as_deref().map(str::trim) {
+            Some(url) if !url.is_empty() => {
+                if let Some((scheme, _)) = url.split_once("://")
+                    && !matches!(scheme, "http" | "https" | "socks5" | "socks5h")
+                {
+                    return Err(format!(
+                        "proxy `{url}` must use http://, https://, socks5:// or socks5h://"
+                    ));
+                }
+                let proxy = reqwest::Proxy::all(url)
+                    .map_err(|err| format!("proxy `{url}` is not a valid proxy URL: {err}"))?;
+                let no_proxy = config
+                    .no_proxy
+                    .iter()
+                    .map(|host| host.trim().trim_start_matches("*."))
+                    .filter(|host| !host.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(",");
+                Some(proxy.no_proxy(reqwest::NoProxy::from_string(&no_proxy)))
+            }
+            _ => None,
+        };
+
+        let root_certificates = match config.ca_bundle.as_ref() {
+            Some(path) => {
+                let bytes = fs::read(path)
+                    .map_err(|err| format!("cannot read ca_bundle {}: {err}", path.display()))?;
+                let certificates = reqwest::Certificate::from_pem_bundle(&bytes)
+                    .ok()
+                    .filter(|certificates| !certificates.is_empty())
+                    // DER certificates start with an ASN.1 SEQUENCE tag.
+                    .or_else(|| {
+                        (bytes.first() == Some(&0x30))
+                            .then(|| reqwest::Certificate::from_der(&bytes).ok())
+                            .flatten()
+                            .map(|cert| vec![cert])
+                    });
+                certificates.ok_or_else(|| {
+                    format!(
+                        "ca_bundle {} contains no PEM or DER certificates",
+                        path.display()
+                    )
+                })?
+            }
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            proxy,
+            root_certificates,
+        })
+    }
+}
+
+/// Makes [`apply_network_config`] use `config`, including for MCP servers
+/// reached over HTTP. Frontends call this once config is loaded; invalid
+/// settings are rejected when config loads, so an error here only logs.
+pub fn set_network_config(config: &NetworkConfig) {
+    let settings = match NetworkSettings::from_config(config) {
+        Ok(settings) => settings,
+        Err(err) => {
+            tracing::warn!("ignoring [network] config: {err}");
+            NetworkSettings::default()
+        }
+    };
+    if let Ok(mut network) = NETWORK.write() {
+        *network = Some(settings);
+    }
+    code_rmcp_client::set_http_client_hook(apply_network_config);
+}
+
+/// Applies the `[network]` proxy and extra root certificates to `builder`.
+/// A configured proxy replaces the `HTTPS_PROXY`/`ALL_PROXY` environment
+/// variables, which reqwest honours otherwise.
+pub fn apply_network_config(mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    let Ok(network) = NETWORK.read() else {
+        return builder;
+    };
+    let Some(settings) = network.as_ref() else {
+        return builder;
+    };
+    if let Some(proxy) = settings.proxy.clone() {
+        builder = builder.proxy(proxy);
+    }
+    for certificate in &settings.root_certificates {
+        builder = builder.add_root_certificate(certificate.clone());
+    }
+    builder
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn rejects_bad_proxy_urls_and_missing_bundles() {
+        let config = NetworkConfig {
+            proxy: Some("socks5h://127.0.0.1:1055".to_string()),
+            no_proxy: vec!["*.corp.example".to_string(), "10.0.0.0/8".to_string()],
+            ca_bundle: None,
+        };
+        assert!(NetworkSettings::from_config(&config).is_ok());
+
+        let config = NetworkConfig {
+            proxy: Some("ftp://proxy.corp.example:21".to_string()),
+            ..NetworkConfig::default()
+        };
+        assert_eq!(
+            NetworkSettings::from_config(&config).err().as_deref(),
+            Some("proxy `ftp://proxy.corp.example:21` must use http://, https://, socks5:// or socks5h://")
+        );
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let bundle = dir.path().join("corp-ca.pem");
+        std::fs::write(&bundle, "not a certificate").expect("write bundle");
+        let config = NetworkConfig {
+            ca_bundle: Some(bundle.clone()),
+            ..NetworkConfig::default()
+        };
+        assert_eq!(
+            NetworkSettings::from_config(&config).err(),
+            Some(format!(
+                "ca_bundle {} contains no PEM or DER certificates",
+                bundle.display()
+            ))
+        );
+    }
 }
@@ -8,6 +8,9 @@
 
 mod apply_patch;
 mod fs_sanitize;
+pub mod android_storage;
+pub mod artifacts;
+pub mod auto_review;
 pub mod auth;
 pub mod auth_accounts;
 pub mod account_usage;
@@ -16,8 +19,10 @@ pub use account_switching::{
     RateLimitSwitchState, SwitchActiveAccountOnRateLimitParams, switch_active_account_on_rate_limit,
 };
 pub mod bash;
+pub mod battery;
 mod auto_drive_pid;
 mod chat_completions;
+mod checkpoints;
 mod client;
 mod client_common;
 pub mod codex;
@@ -38,11 +43,16 @@ pub mod personality_migration;
 mod conversation_history;
 pub mod context_timeline;
 pub mod acp;
+pub mod command_plugins;
 pub mod custom_prompts;
+#[cfg(unix)]
+pub mod daemon;
 pub mod debug_logger;
+pub mod dlp;
 pub mod review_coord;
 pub mod env;
 mod environment_context;
+mod environment_providers;
 mod reasoning;
 pub mod retention;
 pub mod telemetry;
@@ -61,16 +71,22 @@ mod exec_command;
 pub mod exec_env;
 mod flags;
 pub mod git_info;
+pub mod git_session;
 pub mod landlock;
 pub mod http_client;
 pub mod housekeeping;
+mod lsp;
 pub mod mcp_connection_manager;
 pub mod mcp_snapshot;
 mod mcp;
 mod mcp_tool_call;
 mod message_history;
+pub mod metered;
+pub mod monorepo;
 mod model_provider_info;
 pub mod remote_models;
+pub mod redaction;
+pub mod repo_map;
 // Remote model discovery caches its own on-disk state within the module.
 mod cgroup;
 pub mod agent_defaults;
@@ -89,6 +105,8 @@ mod truncate;
 mod unified_exec;
 mod user_instructions;
 mod skills;
+pub use skills::loader::load_skills;
+pub use skills::model::SkillLoadOutcome;
 pub use model_provider_info::BUILT_IN_OSS_MODEL_PROVIDER_ID;
 pub use model_provider_info::ModelProviderInfo;
 pub use model_provider_info::OpenRouterConfig;
@@ -100,6 +118,8 @@ mod conversation_manager;
 pub mod protocol;
 mod event_mapping;
 pub mod review_format;
+pub mod review_passes;
+pub mod output_schema;
 #[cfg(test)]
 mod prompt_assembly_tests;
 pub use code_protocol::protocol::InitialHistory;
@@ -114,23 +134,30 @@ pub use tool_apply_patch::ApplyPatchToolType;
 pub mod default_client;
 pub mod model_family;
 mod openai_tools;
+mod patch_format;
 mod patch_harness;
+mod patch_lint;
+pub mod persistent_shell;
 pub mod plan_tool;
 pub mod project_doc;
 pub mod project_features;
 mod rollout;
+mod script_hooks;
 pub(crate) mod safety;
 pub mod session_catalog;
+pub mod session_hub;
 pub mod seatbelt;
 pub mod shell;
 pub mod spawn;
 pub mod terminal;
+pub mod test_runner;
 pub mod otel_init;
 mod text_encoding;
 mod tool_apply_patch;
 mod tools;
 mod workflow_validation;
 pub mod turn_diff_tracker;
+pub mod url_fetch;
 pub use rollout::ARCHIVED_SESSIONS_SUBDIR;
 pub use rollout::INTERACTIVE_SESSION_SOURCES;
 pub use rollout::RolloutRecorder;
@@ -141,11 +168,20 @@ pub use rollout::list::ConversationItem;
 pub use rollout::list::ConversationsPage;
 pub use rollout::list::Cursor;
 pub use rollout::catalog::SessionIndexEntry;
+pub use rollout::history_store::HistoryStats;
+pub use rollout::history_store::HistoryStore;
+pub use rollout::interrupted::InterruptedTurn;
+pub use rollout::interrupted::find_interrupted_turn;
+pub use rollout::order_audit::audit_rollout_order;
+pub use rollout::summary::SessionSummary;
+pub use rollout::summary::summarize_rollout;
 pub use session_catalog::entry_to_rollout_path;
 pub use session_catalog::SessionCatalog;
 pub use session_catalog::SessionQuery;
 mod function_tool;
 mod user_notification;
+mod webhooks;
+pub mod wire_log;
 pub mod util;
 
 pub use apply_patch::CODEX_APPLY_PATCH_ARG1;
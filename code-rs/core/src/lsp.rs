@@ -0,0 +1,767 @@
+//! Language server bridge (`[lsp]`): diagnostics for the files a patch touched.
+//!
+//! Servers are started lazily over stdio the first time a patch touches a file
+//! with one of their extensions and live as long as the session. Each touched
+//! file is synced with `didOpen`/`didChange`/`didSave`, and the diagnostics the
+//! server publishes within the wait window are compared with what it reported
+//! for the file before the patch, so new problems can be told from old ones.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+
+use serde_json::Value;
+use serde_json::json;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::process::Child;
+use tokio::process::ChildStdin;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+use tokio::sync::Notify;
+use tokio::sync::oneshot;
+use tokio::time::Instant;
+
+use crate::config_types::LspConfig;
+use crate::config_types::LspServerConfig;
+use crate::protocol::DiagnosticSeverity;
+use crate::protocol::FileChange;
+use crate::protocol::LspDiagnostic;
+use crate::protocol::LspFileDiagnostics;
+
+pub const DEFAULT_LSP_WAIT_MS: u64 = 5_000;
+
+/// Time allowed for `initialize`, which includes project loading for some
+/// servers.
+const INITIALIZE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Once a file's diagnostics arrive, how long to wait for follow-up publishes
+/// (rust-analyzer, for one, publishes native diagnostics before `cargo check`
+/// results).
+const SETTLE: Duration = Duration::from_millis(300);
+
+/// New errors listed in the apply_patch output before the rest are counted.
+const MAX_ERRORS_FOR_MODEL: usize = 10;
+
+/// A file touched by an applied patch.
+#[derive(Debug, Clone)]
+pub(crate) struct TouchedFile {
+    /// Where the file is now (the destination of a move).
+    pub path: PathBuf,
+    /// Contents before the patch, for updated files. Used to learn the old
+    /// diagnostics of files the server has not seen yet.
+    pub before: Option<String>,
+}
+
+impl TouchedFile {
+    /// The files of an applied patch, at their new location.
+    pub(crate) fn from_changes(changes: &HashMap<PathBuf, FileChange>) -> Vec<Self> {
+        let mut files: Vec<Self> = changes
+            .iter()
+            .map(|(path, change)| match change {
+                FileChange::Add { .. } | FileChange::Delete => Self {
+                    path: path.clone(),
+                    before: None,
+                },
+                FileChange::Update {
+                    move_path,
+                    original_content,
+                    ..
+                } => Self {
+                    path: move_path.clone().unwrap_or_else(|| path.clone()),
+                    before: Some(original_content.clone()),
+                },
+            })
+            .collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        files
+    }
+}
+
+pub(crate) struct LspBridge {
+    config: LspConfig,
+    root: PathBuf,
+    servers: Mutex<HashMap<String, ServerSlot>>,
+}
+
+enum ServerSlot {
+    Running(LanguageServer),
+    /// Failed to start; not retried for the rest of the session.
+    Failed,
+}
+
+/// Result of checking one patch.
+#[derive(Debug, Default)]
+pub(crate) struct PatchDiagnostics {
+    pub files: Vec<LspFileDiagnostics>,
+    pub errors: Vec<String>,
+}
+
+impl LspBridge {
+    /// `None` when no servers are configured.
+    pub(crate) fn new(config: &LspConfig, root: &Path) -> Option<Self> {
+        if config.servers.is_empty() {
+            return None;
+        }
+        Some(Self {
+            config: config.clone(),
+            root: root.to_path_buf(),
+            servers: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Syncs `files` with their servers and returns the diagnostics each one
+    /// publishes, marking the problems that were not there before.
+    pub(crate) async fn check(&self, files: &[TouchedFile]) -> PatchDiagnostics {
+        let mut by_server: Vec<(&str, &LspServerConfig, Vec<&TouchedFile>)> = Vec::new();
+        for file in files {
+            let Some((name, server)) = self.server_for(&file.path) else {
+                continue;
+            };
+            match by_server.iter_mut().find(|(existing, _, _)| *existing == name) {
+                Some((_, _, group)) => group.push(file),
+                None => by_server.push((name, server, vec![file])),
+            }
+        }
+
+        let wait = Duration::from_millis(self.config.wait_ms());
+        let mut result = PatchDiagnostics::default();
+        let mut servers = self.servers.lock().await;
+        for (name, config, group) in by_server {
+            if !servers.contains_key(name) {
+                let slot = match LanguageServer::start(config, &self.root).await {
+                    Ok(server) => ServerSlot::Running(server),
+                    Err(err) => {
+                        result.errors.push(format!("{name}: {err}"));
+                        ServerSlot::Failed
+                    }
+                };
+                servers.insert(name.to_string(), slot);
+            }
+            let Some(ServerSlot::Running(server)) = servers.get_mut(name) else {
+                continue;
+            };
+            let synced = server.sync(config, &group, wait).await;
+            match synced {
+                Ok(diagnostics) => {
+                    result.files.extend(diagnostics.into_iter().map(|(path, diagnostics)| {
+                        LspFileDiagnostics {
+                            path,
+                            server: name.to_string(),
+                            diagnostics,
+                        }
+                    }));
+                }
+                Err(err) => {
+                    result.errors.push(format!("{name}: {err}"));
+                    servers.insert(name.to_string(), ServerSlot::Failed);
+                }
+            }
+        }
+        result
+    }
+
+    fn server_for(&self, path: &Path) -> Option<(&str, &LspServerConfig)> {
+        let extension = path.extension()?.to_str()?;
+        self.config
+            .servers
+            .iter()
+            .find(|(_, server)| {
+                server
+                    .extensions
+                    .iter()
+                    .any(|candidate| candidate.trim_start_matches('.') == extension)
+            })
+            .map(|(name, server)| (name.as_str(), server))
+    }
+}
+
+/// Diagnostics published for one document, with a counter bumped on every
+/// publish so waiters can tell fresh results from stale ones.
+#[derive(Default)]
+struct Published {
+    generation: u64,
+    diagnostics: Vec<Value>,
+}
+
+#[derive(Default)]
+struct Shared {
+    published: StdMutex<HashMap<String, Published>>,
+    pending: StdMutex<HashMap<i64, oneshot::Sender<Value>>>,
+    notify: Notify,
+}
+
+struct LanguageServer {
+    _child: Child,
+    /// Shared with the reader, which answers the server's own requests.
+    stdin: Arc<Mutex<ChildStdin>>,
+    shared: Arc<Shared>,
+    next_id: i64,
+    /// Open documents and their version.
+    open: HashMap<PathBuf, i32>,
+    /// Diagnostics last seen per document, the baseline for "new".
+    last: HashMap<PathBuf, Vec<LspDiagnostic>>,
+}
+
+impl LanguageServer {
+    async fn start(config: &LspServerConfig, root: &Path) -> Result<Self, String> {
+        let mut child = Command::new(&config.command)
+            .args(&config.args)
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| format!("cannot start `{}`: {err}", config.command))?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err("no stdio pipes".to_string());
+        };
+        let shared = Arc::new(Shared::default());
+        let stdin = Arc::new(Mutex::new(stdin));
+        tokio::spawn(read_messages(stdout, Arc::clone(&shared), Arc::clone(&stdin)));
+
+        let mut server = Self {
+            _child: child,
+            stdin,
+            shared,
+            next_id: 0,
+            open: HashMap::new(),
+            last: HashMap::new(),
+        };
+        let root_uri = file_uri(root);
+        let name = root
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let params = json!({
+            "processId": std::process::id(),
+            "rootUri": root_uri,
+            "rootPath": root.display().to_string(),
+            "workspaceFolders": [{ "uri": root_uri, "name": name }],
+            "capabilities": {
+                "textDocument": {
+                    "synchronization": { "didSave": true },
+                    "publishDiagnostics": { "relatedInformation": false },
+                },
+                "workspace": { "configuration": true, "workspaceFolders": true },
+            },
+        });
+        server.request("initialize", params, INITIALIZE_TIMEOUT).await?;
+        server.notify("initialized", json!({})).await?;
+        Ok(server)
+    }
+
+    /// Sends the current contents of `files` and collects their diagnostics.
+    async fn sync(
+        &mut self,
+        config: &LspServerConfig,
+        files: &[&TouchedFile],
+        wait: Duration,
+    ) -> Result<Vec<(PathBuf, Vec<LspDiagnostic>)>, String> {
+        // Files the server has not seen: open them as they were before the
+        // patch first so their old diagnostics become the baseline.
+        let mut baseline = Vec::new();
+        for file in files {
+            if self.open.contains_key(&file.path) {
+                continue;
+            }
+            if let Some(before) = file.before.as_ref() {
+                let since = self.generation(&file.path);
+                self.did_open(config, &file.path, before).await?;
+                baseline.push((file.path.clone(), since));
+            }
+        }
+        for (path, diagnostics) in self.collect(&baseline, wait).await {
+            self.last.insert(path, diagnostics);
+        }
+
+        let mut changed = Vec::new();
+        for file in files {
+            let since = self.generation(&file.path);
+            let Ok(text) = std::fs::read_to_string(&file.path) else {
+                // Deleted or unreadable: nothing to check.
+                if self.open.remove(&file.path).is_some() {
+                    let uri = file_uri(&file.path);
+                    self.notify("textDocument/didClose", json!({ "textDocument": { "uri": uri } }))
+                        .await?;
+                }
+                self.last.remove(&file.path);
+                continue;
+            };
+            match self.open.get_mut(&file.path) {
+                Some(version) => {
+                    *version += 1;
+                    let version = *version;
+                    let uri = file_uri(&file.path);
+                    self.notify(
+                        "textDocument/didChange",
+                        json!({
+                            "textDocument": { "uri": uri, "version": version },
+                            "contentChanges": [{ "text": text }],
+                        }),
+                    )
+                    .await?;
+                }
+                None => self.did_open(config, &file.path, &text).await?,
+            }
+            let uri = file_uri(&file.path);
+            self.notify("textDocument/didSave", json!({ "textDocument": { "uri": uri } }))
+                .await?;
+            changed.push((file.path.clone(), since));
+        }
+
+        let mut results = Vec::new();
+        for (path, mut diagnostics) in self.collect(&changed, wait).await {
+            let before = self.last.get(&path).map(Vec::as_slice).unwrap_or_default();
+            mark_new(&mut diagnostics, before);
+            self.last.insert(path.clone(), diagnostics.clone());
+            results.push((path, diagnostics));
+        }
+        Ok(results)
+    }
+
+    async fn did_open(&mut self, config: &LspServerConfig, path: &Path, text: &str) -> Result<(), String> {
+        let language_id = config
+            .language_id
+            .clone()
+            .unwrap_or_else(|| language_id_for(path));
+        self.open.insert(path.to_path_buf(), 1);
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": {
+                    "uri": file_uri(path),
+                    "languageId": language_id,
+                    "version": 1,
+                    "text": text,
+                },
+            }),
+        )
+        .await
+    }
+
+    fn generation(&self, path: &Path) -> u64 {
+        let published = self.shared.published.lock().unwrap_or_else(|err| err.into_inner());
+        published
+            .get(&file_uri(path))
+            .map_or(0, |entry| entry.generation)
+    }
+
+    /// Waits until every file has new diagnostics and they stop changing, or
+    /// until `wait` runs out. Files without a publish keep their last known
+    /// diagnostics.
+    async fn collect(
+        &self,
+        files: &[(PathBuf, u64)],
+        wait: Duration,
+    ) -> Vec<(PathBuf, Vec<LspDiagnostic>)> {
+        if files.is_empty() {
+            return Vec::new();
+        }
+        let deadline = Instant::now() + wait;
+        let mut settled_at: Option<Instant> = None;
+        loop {
+            let notified = self.shared.notify.notified();
+            let all_published = {
+                let published = self.shared.published.lock().unwrap_or_else(|err| err.into_inner());
+                files.iter().all(|(path, since)| {
+                    published
+                        .get(&file_uri(path))
+                        .is_some_and(|entry| entry.generation > *since)
+                })
+            };
+            let now = Instant::now();
+            if all_published {
+                let settle = *settled_at.get_or_insert(now + SETTLE);
+                if now >= settle || now >= deadline {
+                    break;
+                }
+                // A later publish restarts the settle window.
+                if tokio::time::timeout_at(settle.min(deadline), notified).await.is_ok() {
+                    settled_at = None;
+                }
+            } else if now >= deadline
+                || tokio::time::timeout_at(deadline, notified).await.is_err()
+            {
+                break;
+            }
+        }
+
+        let published = self.shared.published.lock().unwrap_or_else(|err| err.into_inner());
+        files
+            .iter()
+            .map(|(path, since)| {
+                let diagnostics = match published.get(&file_uri(path)) {
+                    Some(entry) if entry.generation > *since => {
+                        entry.diagnostics.iter().filter_map(parse_diagnostic).collect()
+                    }
+                    _ => self.last.get(path).cloned().unwrap_or_default(),
+                };
+                (path.clone(), diagnostics)
+            })
+            .collect()
+    }
+
+    async fn request(&mut self, method: &str, params: Value, timeout: Duration) -> Result<Value, String> {
+        self.next_id += 1;
+        let id = self.next_id;
+        let (tx, rx) = oneshot::channel();
+        self.shared
+            .pending
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .insert(id, tx);
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+            .await?;
+        let reply = tokio::time::timeout(timeout, rx)
+            .await
+            .map_err(|_| format!("no answer to {method} after {}s", timeout.as_secs()))?
+            .map_err(|_| "the server exited".to_string())?;
+        match reply.get("error") {
+            Some(error) => Err(format!(
+                "{method} failed: {}",
+                error.get("message").and_then(Value::as_str).unwrap_or("unknown error")
+            )),
+            None => Ok(reply.get("result").cloned().unwrap_or(Value::Null)),
+        }
+    }
+
+    async fn notify(&mut self, method: &str, params: Value) -> Result<(), String> {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+            .await
+    }
+
+    async fn send(&mut self, message: Value) -> Result<(), String> {
+        write_message(&mut *self.stdin.lock().await, &message)
+            .await
+            .map_err(|err| format!("the server stopped reading: {err}"))
+    }
+}
+
+async fn write_message(writer: &mut (impl AsyncWriteExt + Unpin), message: &Value) -> std::io::Result<()> {
+    let body = message.to_string();
+    writer
+        .write_all(format!("Content-Length: {}\r\n\r\n{body}", body.len()).as_bytes())
+        .await?;
+    writer.flush().await
+}
+
+/// Reads server messages: responses complete pending requests, diagnostics
+/// are stored, and server requests get an empty answer.
+async fn read_messages(
+    stdout: impl AsyncRead + Unpin,
+    shared: Arc<Shared>,
+    stdin: Arc<Mutex<ChildStdin>>,
+) {
+    let mut reader = BufReader::new(stdout);
+    while let Some(message) = read_message(&mut reader).await {
+        let method = message.get("method").and_then(Value::as_str);
+        let id = message.get("id");
+        match (method, id) {
+            (Some("textDocument/publishDiagnostics"), None) => {
+                let params = message.get("params");
+                let uri = params
+                    .and_then(|params| params.get("uri"))
+                    .and_then(Value::as_str)
+                    .map(normalize_uri);
+                let diagnostics = params
+                    .and_then(|params| params.get("diagnostics"))
+                    .and_then(Value::as_array)
+                    .cloned()
+                    .unwrap_or_default();
+                if let Some(uri) = uri {
+                    let mut published = shared.published.lock().unwrap_or_else(|err| err.into_inner());
+                    let entry = published.entry(uri).or_default();
+                    entry.generation += 1;
+                    entry.diagnostics = diagnostics;
+                }
+                shared.notify.notify_waiters();
+            }
+            (None, Some(id)) => {
+                let sender = id.as_i64().and_then(|id| {
+                    shared
+                        .pending
+                        .lock()
+                        .unwrap_or_else(|err| err.into_inner())
+                        .remove(&id)
+                });
+                if let Some(sender) = sender {
+                    let _ = sender.send(message);
+                }
+            }
+            (Some(method), Some(id)) => {
+                // `workspace/configuration` expects one entry per requested
+                // item; `null` leaves every setting at its default.
+                let result = match method {
+                    "workspace/configuration" => {
+                        let items = message
+                            .get("params")
+                            .and_then(|params| params.get("items"))
+                            .and_then(Value::as_array)
+                            .map_or(0, Vec::len);
+                        Value::Array(vec![Value::Null; items])
+                    }
+                    _ => Value::Null,
+                };
+                let reply = json!({ "jsonrpc": "2.0", "id": id, "result": result });
+                let _ = write_message(&mut *stdin.lock().await, &reply).await;
+            }
+            _ => {}
+        }
+    }
+    // The server exited: fail pending requests and wake waiters.
+    shared
+        .pending
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clear();
+    shared.notify.notify_waiters();
+}
+
+async fn read_message(reader: &mut (impl AsyncBufReadExt + Unpin)) -> Option<Value> {
+    loop {
+        let mut length: Option<usize> = None;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).await.ok()? == 0 {
+                return None;
+            }
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':')
+                && name.eq_ignore_ascii_case("content-length")
+            {
+                length = value.trim().parse().ok();
+            }
+        }
+        let Some(length) = length else {
+            continue;
+        };
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).await.ok()?;
+        if let Ok(message) = serde_json::from_slice(&body) {
+            return Some(message);
+        }
+    }
+}
+
+fn parse_diagnostic(value: &Value) -> Option<LspDiagnostic> {
+    let start = value.get("range")?.get("start")?;
+    let position = |key: &str| {
+        start
+            .get(key)
+            .and_then(Value::as_u64)
+            .and_then(|n| u32::try_from(n).ok())
+            .map_or(1, |n| n.saturating_add(1))
+    };
+    let severity = match value.get("severity").and_then(Value::as_u64) {
+        Some(2) => DiagnosticSeverity::Warning,
+        Some(3) => DiagnosticSeverity::Information,
+        Some(4) => DiagnosticSeverity::Hint,
+        // Servers may omit the severity; the client decides, and errors are
+        // the safe reading.
+        _ => DiagnosticSeverity::Error,
+    };
+    let code = value.get("code").and_then(|code| match code {
+        Value::String(code) => Some(code.clone()),
+        Value::Number(code) => Some(code.to_string()),
+        _ => None,
+    });
+    Some(LspDiagnostic {
+        line: position("line"),
+        column: position("character"),
+        severity,
+        message: value.get("message")?.as_str()?.trim().to_string(),
+        source: value.get("source").and_then(Value::as_str).map(str::to_string),
+        code,
+        is_new: false,
+    })
+}
+
+/// Marks diagnostics that have no match in `before`. Repeats count: a second
+/// copy of an old problem is new.
+fn mark_new(diagnostics: &mut [LspDiagnostic], before: &[LspDiagnostic]) {
+    let mut unmatched: Vec<&LspDiagnostic> = before.iter().collect();
+    for diagnostic in diagnostics.iter_mut() {
+        match unmatched.iter().position(|old| old.same_problem(diagnostic)) {
+            Some(index) => {
+                unmatched.swap_remove(index);
+                diagnostic.is_new = false;
+            }
+            None => diagnostic.is_new = true,
+        }
+    }
+}
+
+/// Lines appended to the apply_patch output when the patch introduced errors.
+pub(crate) fn new_errors_note(files: &[LspFileDiagnostics], cwd: &Path) -> Option<String> {
+    let errors: Vec<String> = files
+        .iter()
+        .flat_map(|file| {
+            let path = file.path.strip_prefix(cwd).unwrap_or(&file.path).display().to_string();
+            file.diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.is_new && diagnostic.severity == DiagnosticSeverity::Error)
+                .map(move |diagnostic| {
+                    let source = diagnostic
+                        .source
+                        .as_deref()
+                        .map(|source| format!(" [{source}]"))
+                        .unwrap_or_default();
+                    format!(
+                        "- {path}:{}:{}{source} {}",
+                        diagnostic.line, diagnostic.column, diagnostic.message
+                    )
+                })
+        })
+        .collect();
+    if errors.is_empty() {
+        return None;
+    }
+    let count = errors.len();
+    let mut note = format!(
+        "Language server: this patch introduced {count} error{}:\n",
+        if count == 1 { "" } else { "s" }
+    );
+    note.push_str(&errors[..count.min(MAX_ERRORS_FOR_MODEL)].join("\n"));
+    if count > MAX_ERRORS_FOR_MODEL {
+        note.push_str(&format!("\n… and {} more", count - MAX_ERRORS_FOR_MODEL));
+    }
+    Some(note)
+}
+
+fn language_id_for(path: &Path) -> String {
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+    match extension {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "typescriptreact",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "javascriptreact",
+        "go" => "go",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" => "cpp",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "rb" => "ruby",
+        "sh" | "bash" => "shellscript",
+        "yml" => "yaml",
+        other => other,
+    }
+    .to_string()
+}
+
+fn file_uri(path: &Path) -> String {
+    url::Url::from_file_path(path)
+        .map(String::from)
+        .unwrap_or_else(|()| format!("file://{}", path.display()))
+}
+
+/// Servers may percent-encode differently than `file_uri`; round-trip
+/// through a path so lookups match.
+fn normalize_uri(uri: &str) -> String {
+    url::Url::parse(uri)
+        .ok()
+        .and_then(|url| url.to_file_path().ok())
+        .map_or_else(|| uri.to_string(), |path| file_uri(&path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic(message: &str, severity: DiagnosticSeverity, line: u32) -> LspDiagnostic {
+        LspDiagnostic {
+            line,
+            column: 1,
+            severity,
+            message: message.to_string(),
+            source: Some("rustc".to_string()),
+            code: None,
+            is_new: false,
+        }
+    }
+
+    #[test]
+    fn new_diagnostics_ignore_moved_lines_but_count_repeats() {
+        let before = vec![
+            diagnostic("unused variable: `x`", DiagnosticSeverity::Warning, 3),
+            diagnostic("mismatched types", DiagnosticSeverity::Error, 10),
+        ];
+        let mut after = vec![
+            diagnostic("unused variable: `x`", DiagnosticSeverity::Warning, 5),
+            diagnostic("mismatched types", DiagnosticSeverity::Error, 12),
+            diagnostic("mismatched types", DiagnosticSeverity::Error, 20),
+            diagnostic("cannot find value `y`", DiagnosticSeverity::Error, 21),
+        ];
+        mark_new(&mut after, &before);
+        let new: Vec<(u32, bool)> = after.iter().map(|d| (d.line, d.is_new)).collect();
+        assert_eq!(new, vec![(5, false), (12, false), (20, true), (21, true)]);
+    }
+
+    #[test]
+    fn parses_published_diagnostics() {
+        let value = json!({
+            "range": { "start": { "line": 4, "character": 8 }, "end": { "line": 4, "character": 9 } },
+            "severity": 1,
+            "code": "E0308",
+            "source": "rustc",
+            "message": "mismatched types\n",
+        });
+        assert_eq!(
+            parse_diagnostic(&value),
+            Some(LspDiagnostic {
+                line: 5,
+                column: 9,
+                severity: DiagnosticSeverity::Error,
+                message: "mismatched types".to_string(),
+                source: Some("rustc".to_string()),
+                code: Some("E0308".to_string()),
+                is_new: false,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_framed_messages() {
+        let input = b"Content-Length: 16\r\nContent-Type: application/vscode-jsonrpc\r\n\r\n{\"id\":1,\"a\":\"b\"}Content-Length: 2\r\n\r\n{}";
+        let mut reader = BufReader::new(&input[..]);
+        assert_eq!(read_message(&mut reader).await, Some(json!({ "id": 1, "a": "b" })));
+        assert_eq!(read_message(&mut reader).await, Some(json!({})));
+        assert_eq!(read_message(&mut reader).await, None);
+    }
+
+    #[test]
+    fn note_lists_only_new_errors() {
+        let mut old = diagnostic("old error", DiagnosticSeverity::Error, 1);
+        old.is_new = false;
+        let mut warning = diagnostic("new warning", DiagnosticSeverity::Warning, 2);
+        warning.is_new = true;
+        let mut error = diagnostic("cannot find value `y`", DiagnosticSeverity::Error, 21);
+        error.is_new = true;
+        let files = vec![LspFileDiagnostics {
+            path: PathBuf::from("/repo/src/lib.rs"),
+            server: "rust".to_string(),
+            diagnostics: vec![old, warning, error],
+        }];
+        assert_eq!(
+            new_errors_note(&files, Path::new("/repo")),
+            Some(
+                "Language server: this patch introduced 1 error:\n- src/lib.rs:21:1 [rustc] cannot find value `y`"
+                    .to_string()
+            )
+        );
+    }
+}
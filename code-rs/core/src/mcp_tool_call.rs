@@ -1,5 +1,6 @@
 use std::time::Instant;
 
+use tracing::Instrument;
 use tracing::error;
 
 use crate::codex::{Session, ToolCallCtx};
@@ -49,8 +50,16 @@ pub(crate) async fn handle_mcp_tool_call(
 
     let start = Instant::now();
     // Perform the tool call.
+    let mcp_span = tracing::info_span!(
+        target: code_otel::TRACE_TARGET,
+        "mcp_call",
+        call_id = %ctx.call_id,
+        server = %server,
+        tool = %tool_name,
+    );
     let result = sess
         .call_tool(&server, &tool_name, arguments_value.clone(), None)
+        .instrument(mcp_span)
         .await
         .map_err(|e| format!("tool call error: {e}"));
     let protocol_result = result.clone().and_then(|value| {
@@ -0,0 +1,207 @@
+//! Metered-connection (data saving) mode.
+//!
+//! While metered, requests leave out images (attachments, screenshots in
+//! history, image tool output), per-turn browser screenshots are not taken,
+//! and optional context such as the repository map is skipped. The mode is
+//! `metered_connection` in config and can be switched at runtime with
+//! `/metered`. In `auto` mode Termux reports the connection through
+//! `termux-wifi-connectioninfo` (Termux:API): anything other than Wi-Fi is
+//! treated as metered. Other platforms are never metered in `auto` mode.
+
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+use code_protocol::models::ContentItem;
+use code_protocol::models::FunctionCallOutputBody;
+use code_protocol::models::FunctionCallOutputContentItem;
+use code_protocol::models::ResponseItem;
+use serde::Deserialize;
+
+use crate::config_types::MeteredConnection;
+
+/// Sent in place of each image left out of a request.
+pub const IMAGE_OMITTED: &str = "[image omitted: metered connection]";
+
+const WIFI_CACHE_TTL: Duration = Duration::from_secs(60);
+const WIFI_TIMEOUT: Duration = Duration::from_secs(5);
+
+static MODE: AtomicU8 = AtomicU8::new(0);
+static WIFI_CACHE: Mutex<Option<(Instant, Option<bool>)>> = Mutex::new(None);
+static REFRESH_PENDING: AtomicBool = AtomicBool::new(false);
+
+pub fn set_metered_connection(mode: MeteredConnection) {
+    let value = match mode {
+        MeteredConnection::Auto => 0,
+        MeteredConnection::On => 1,
+        MeteredConnection::Off => 2,
+    };
+    MODE.store(value, Ordering::Relaxed);
+    if mode == MeteredConnection::Auto {
+        let _ = is_metered();
+    }
+}
+
+pub fn metered_connection() -> MeteredConnection {
+    match MODE.load(Ordering::Relaxed) {
+        1 => MeteredConnection::On,
+        2 => MeteredConnection::Off,
+        _ => MeteredConnection::Auto,
+    }
+}
+
+/// Whether to save data right now. Never blocks: in `auto` mode it answers
+/// from the last Wi-Fi reading and refreshes a stale one in the background,
+/// so the first call after startup reports unmetered.
+pub fn is_metered() -> bool {
+    match metered_connection() {
+        MeteredConnection::On => return true,
+        MeteredConnection::Off => return false,
+        MeteredConnection::Auto => {}
+    }
+    if !crate::env::is_termux() {
+        return false;
+    }
+    // A held lock means a reading is in flight; treat that as unmetered.
+    let (fresh, on_wifi) = match WIFI_CACHE.try_lock() {
+        Ok(cache) => match cache.as_ref() {
+            Some((read_at, on_wifi)) => (read_at.elapsed() < WIFI_CACHE_TTL, *on_wifi),
+            None => (false, None),
+        },
+        Err(_) => (true, None),
+    };
+    if !fresh && !REFRESH_PENDING.swap(true, Ordering::AcqRel) {
+        let spawned = std::thread::Builder::new()
+            .name("wifi-status".to_string())
+            .spawn(|| {
+                let on_wifi = read_termux_wifi_connected();
+                if let Ok(mut cache) = WIFI_CACHE.lock() {
+                    *cache = Some((Instant::now(), on_wifi));
+                }
+                REFRESH_PENDING.store(false, Ordering::Release);
+            });
+        if spawned.is_err() {
+            REFRESH_PENDING.store(false, Ordering::Release);
+        }
+    }
+    // Without Termux:API there is no reading; assume unmetered.
+    on_wifi == Some(false)
+}
+
+/// Replaces every image in `items` with [`IMAGE_OMITTED`] and returns how
+/// many were replaced. History keeps the images, so they are sent again once
+/// the connection is no longer metered.
+pub fn omit_images(items: &mut [ResponseItem]) -> usize {
+    let mut omitted = 0;
+    for item in items {
+        match item {
+            ResponseItem::Message { content, .. } => {
+                for content in content.iter_mut() {
+                    if matches!(content, ContentItem::InputImage { .. }) {
+                        *content = ContentItem::InputText {
+                            text: IMAGE_OMITTED.to_string(),
+                        };
+                        omitted += 1;
+                    }
+                }
+            }
+            ResponseItem::FunctionCallOutput { output, .. } => {
+                if let FunctionCallOutputBody::ContentItems(content) = &mut output.body {
+                    for content in content.iter_mut() {
+                        if matches!(content, FunctionCallOutputContentItem::InputImage { .. }) {
+                            *content = FunctionCallOutputContentItem::InputText {
+                                text: IMAGE_OMITTED.to_string(),
+                            };
+                            omitted += 1;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    omitted
+}
+
+#[derive(Deserialize)]
+struct TermuxWifiConnectionInfo {
+    #[serde(default)]
+    supplicant_state: String,
+}
+
+/// `Some(true)` when Wi-Fi is connected, `None` when the reading failed.
+fn parse_termux_wifi_connected(json: &[u8]) -> Option<bool> {
+    let raw: TermuxWifiConnectionInfo = serde_json::from_slice(json).ok()?;
+    Some(raw.supplicant_state.eq_ignore_ascii_case("COMPLETED"))
+}
+
+fn read_termux_wifi_connected() -> Option<bool> {
+    let output = crate::env::termux_api_output("termux-wifi-connectioninfo", WIFI_TIMEOUT)?;
+    parse_termux_wifi_connected(&output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_termux_wifi_state() {
+        assert_eq!(
+            parse_termux_wifi_connected(
+                br#"{"bssid":"02:00:00:00:00:00","ip":"192.168.1.20","ssid":"home","supplicant_state":"COMPLETED"}"#,
+            ),
+            Some(true)
+        );
+        assert_eq!(
+            parse_termux_wifi_connected(br#"{"ip":"0.0.0.0","supplicant_state":"DISCONNECTED"}"#),
+            Some(false)
+        );
+        assert_eq!(parse_termux_wifi_connected(b"not json"), None);
+    }
+
+    #[test]
+    fn images_are_replaced_with_placeholders() {
+        let image = "data:image/png;base64,AAAA".to_string();
+        let mut items = vec![
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![
+                    ContentItem::InputText {
+                        text: "what is this?".to_string(),
+                    },
+                    ContentItem::InputImage {
+                        image_url: image.clone(),
+                    },
+                ],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload {
+                    body: FunctionCallOutputBody::ContentItems(vec![
+                        FunctionCallOutputContentItem::InputImage { image_url: image },
+                    ]),
+                    success: Some(true),
+                },
+            },
+        ];
+        assert_eq!(omit_images(&mut items), 2);
+        assert_eq!(omit_images(&mut items), 0);
+        let ResponseItem::Message { content, .. } = &items[0] else {
+            panic!("expected a message");
+        };
+        assert_eq!(
+            content[1],
+            ContentItem::InputText {
+                text: IMAGE_OMITTED.to_string()
+            }
+        );
+    }
+}
@@ -61,7 +61,9 @@ pub struct ModelProviderInfo {
 
     /// Value to use with `Authorization: Bearer <token>` header. Use of this
     /// config is discouraged in favor of `env_key` for security reasons, but
-    /// this may be necessary when using this programmatically.
+    /// this may be necessary when using this programmatically. Also accepted
+    /// as `api_key`, e.g. for the static key of a local inference server.
+    #[serde(alias = "api_key")]
     pub experimental_bearer_token: Option<String>,
 
     /// Which wire protocol this provider expects.
@@ -98,6 +100,23 @@ pub struct ModelProviderInfo {
     /// Optional OpenRouter-specific configuration for routing preferences and metadata.
     #[serde(default)]
     pub openrouter: Option<OpenRouterConfig>,
+
+    /// Marks a local inference server (Ollama, llama.cpp, vLLM, ...). Requests
+    /// only carry this provider's own key, never the OpenAI login, and a server
+    /// that refuses connections is reported instead of waited on as a network
+    /// outage.
+    #[serde(default)]
+    pub local: bool,
+
+    /// Whether requests may ask for reasoning summaries. Defaults to `true`,
+    /// or `false` for `local` providers.
+    #[serde(default)]
+    pub supports_reasoning_summaries: Option<bool>,
+
+    /// Stop sequences sent with every Chat Completions request, for models
+    /// whose server does not end turns on the chat template's own tokens.
+    #[serde(default)]
+    pub stop: Option<Vec<String>>,
 }
 
 /// OpenRouter-specific configuration, allowing users to control routing and pricing metadata.
@@ -268,9 +287,10 @@ impl ModelProviderInfo {
 
         match self.api_key() {
             Ok(Some(key)) => Ok(Some(CodexAuth::from_api_key(&key))),
+            Ok(None) if self.local => Ok(None),
             Ok(None) => Ok(auth.clone()),
             Err(err) => {
-                if auth.is_some() {
+                if auth.is_some() && !self.local {
                     Ok(auth.clone())
                 } else {
                     Err(err)
@@ -444,6 +464,11 @@ impl ModelProviderInfo {
             .unwrap_or(Duration::from_millis(DEFAULT_STREAM_IDLE_TIMEOUT_MS))
     }
 
+    /// Whether requests may ask for reasoning summaries.
+    pub fn supports_reasoning_summaries(&self) -> bool {
+        self.supports_reasoning_summaries.unwrap_or(!self.local)
+    }
+
     pub fn base_url_for_probe(&self) -> String {
         self.base_url
             .clone()
@@ -527,6 +552,9 @@ pub fn built_in_model_providers() -> HashMap<String, ModelProviderInfo> {
                 stream_idle_timeout_ms: None,
                 requires_openai_auth: true,
                 openrouter: None,
+                local: false,
+                supports_reasoning_summaries: None,
+                stop: None,
             },
         ),
         (BUILT_IN_OSS_MODEL_PROVIDER_ID, create_oss_provider()),
@@ -573,6 +601,9 @@ pub fn create_oss_provider_with_base_url(base_url: &str) -> ModelProviderInfo {
         stream_idle_timeout_ms: None,
         requires_openai_auth: false,
         openrouter: None,
+        local: true,
+        supports_reasoning_summaries: None,
+        stop: None,
     }
 }
 
@@ -614,12 +645,39 @@ base_url = "http://localhost:11434/v1"
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
         assert_eq!(expected_provider, provider);
     }
 
+    #[test]
+    fn test_deserialize_local_model_provider_toml() {
+        let llama_cpp_provider_toml = r#"
+name = "llama.cpp"
+base_url = "http://127.0.0.1:8080/v1"
+api_key = "sk-local"
+local = true
+stop = ["<|im_end|>"]
+        "#;
+
+        let provider: ModelProviderInfo = toml::from_str(llama_cpp_provider_toml).unwrap();
+        assert!(provider.local);
+        assert!(!provider.supports_reasoning_summaries());
+        assert_eq!(provider.experimental_bearer_token.as_deref(), Some("sk-local"));
+        assert_eq!(provider.stop, Some(vec!["<|im_end|>".to_string()]));
+
+        let auth = Some(CodexAuth::from_api_key("sk-openai"));
+        let keyless = ModelProviderInfo {
+            experimental_bearer_token: None,
+            ..provider
+        };
+        assert!(keyless.effective_auth(&auth).unwrap().is_none());
+    }
+
     #[test]
     fn test_deserialize_azure_model_provider_toml() {
         let azure_provider_toml = r#"
@@ -645,6 +703,9 @@ query_params = { api-version = "2025-04-01-preview" }
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -679,6 +740,9 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -703,6 +767,9 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 stream_idle_timeout_ms: None,
                 requires_openai_auth: false,
                 openrouter: None,
+                local: false,
+                supports_reasoning_summaries: None,
+                stop: None,
             }
         }
 
@@ -737,6 +804,9 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            local: false,
+            supports_reasoning_summaries: None,
+            stop: None,
         };
         assert!(named_provider.is_azure_responses_endpoint());
 
@@ -0,0 +1,324 @@
+//! Monorepo workspaces: Cargo workspaces, pnpm workspaces and Bazel
+//! repositories. `/scope <dir>` narrows a session to one subproject; the
+//! model is told to keep its reads, edits and commands inside it, and the
+//! TUI limits `@` mentions, file search and `/diff` to that subtree.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use wildmatch::WildMatch;
+
+/// How deep `**` patterns and the Bazel package walk descend.
+const MAX_WALK_DEPTH: usize = 4;
+
+/// Directories never treated as subprojects.
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceKind {
+    Cargo,
+    Pnpm,
+    Bazel,
+}
+
+impl fmt::Display for WorkspaceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            WorkspaceKind::Cargo => "Cargo workspace",
+            WorkspaceKind::Pnpm => "pnpm workspace",
+            WorkspaceKind::Bazel => "Bazel workspace",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    pub kind: WorkspaceKind,
+    pub root: PathBuf,
+    /// Member directories relative to `root`, sorted.
+    pub members: Vec<PathBuf>,
+}
+
+impl Workspace {
+    /// The member `path` (absolute) lies in, if any.
+    pub fn member_containing(&self, path: &Path) -> Option<&Path> {
+        let relative = path.strip_prefix(&self.root).ok()?;
+        self.members
+            .iter()
+            .filter(|member| relative.starts_with(member))
+            .max_by_key(|member| member.components().count())
+            .map(PathBuf::as_path)
+    }
+}
+
+/// The nearest workspace enclosing `dir`, looking no higher than the git
+/// repository root.
+pub fn detect_workspace(dir: &Path) -> Option<Workspace> {
+    let boundary = crate::git_info::get_git_repo_root(dir);
+    for candidate in dir.ancestors() {
+        if let Some(workspace) = workspace_at(candidate) {
+            return Some(workspace);
+        }
+        if boundary.as_deref() == Some(candidate) {
+            break;
+        }
+    }
+    None
+}
+
+fn workspace_at(root: &Path) -> Option<Workspace> {
+    let (kind, members) = if let Some(members) = cargo_members(root) {
+        (WorkspaceKind::Cargo, members)
+    } else if let Some(members) = pnpm_members(root) {
+        (WorkspaceKind::Pnpm, members)
+    } else if ["MODULE.bazel", "WORKSPACE", "WORKSPACE.bazel"]
+        .iter()
+        .any(|name| root.join(name).is_file())
+    {
+        (WorkspaceKind::Bazel, bazel_packages(root))
+    } else {
+        return None;
+    };
+    Some(Workspace {
+        kind,
+        root: root.to_path_buf(),
+        members,
+    })
+}
+
+#[derive(Deserialize)]
+struct CargoManifest {
+    workspace: Option<CargoWorkspace>,
+}
+
+#[derive(Deserialize)]
+struct CargoWorkspace {
+    #[serde(default)]
+    members: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+fn cargo_members(root: &Path) -> Option<Vec<PathBuf>> {
+    let manifest = fs::read_to_string(root.join("Cargo.toml")).ok()?;
+    let workspace = toml::from_str::<CargoManifest>(&manifest).ok()?.workspace?;
+    Some(expand_members(
+        root,
+        &workspace.members,
+        &workspace.exclude,
+        "Cargo.toml",
+    ))
+}
+
+#[derive(Deserialize)]
+struct PnpmWorkspace {
+    #[serde(default)]
+    packages: Vec<String>,
+}
+
+fn pnpm_members(root: &Path) -> Option<Vec<PathBuf>> {
+    let manifest = fs::read_to_string(root.join("pnpm-workspace.yaml")).ok()?;
+    let workspace = serde_yaml::from_str::<PnpmWorkspace>(&manifest).ok()?;
+    let (exclude, include): (Vec<String>, Vec<String>) = workspace
+        .packages
+        .into_iter()
+        .partition(|pattern| pattern.starts_with('!'));
+    let exclude: Vec<String> = exclude
+        .iter()
+        .map(|pattern| pattern.trim_start_matches('!').to_string())
+        .collect();
+    Some(expand_members(root, &include, &exclude, "package.json"))
+}
+
+/// Directories holding a `BUILD` file; the repository root is not listed.
+fn bazel_packages(root: &Path) -> Vec<PathBuf> {
+    let mut packages: Vec<PathBuf> = descendant_dirs(root, MAX_WALK_DEPTH)
+        .into_iter()
+        .filter(|dir| ["BUILD", "BUILD.bazel"].iter().any(|name| dir.join(name).is_file()))
+        .filter_map(|dir| dir.strip_prefix(root).ok().map(Path::to_path_buf))
+        .collect();
+    packages.sort();
+    packages
+}
+
+/// Expands member globs (`crates/*`, `apps/**`) into directories that hold
+/// `manifest`, minus those matching `exclude`.
+fn expand_members(
+    root: &Path,
+    include: &[String],
+    exclude: &[String],
+    manifest: &str,
+) -> Vec<PathBuf> {
+    let clean = |pattern: &str| {
+        pattern
+            .trim()
+            .trim_start_matches("./")
+            .trim_end_matches('/')
+            .to_string()
+    };
+    let exclude: Vec<WildMatch> = exclude
+        .iter()
+        .map(|pattern| WildMatch::new(&clean(pattern)))
+        .collect();
+    let mut members = Vec::new();
+    for pattern in include {
+        for dir in expand_pattern(root, &clean(pattern)) {
+            let Ok(relative) = dir.strip_prefix(root) else {
+                continue;
+            };
+            let relative_text = relative.to_string_lossy().replace('\\', "/");
+            if relative.as_os_str().is_empty()
+                || !dir.join(manifest).is_file()
+                || exclude.iter().any(|matcher| matcher.matches(&relative_text))
+            {
+                continue;
+            }
+            members.push(relative.to_path_buf());
+        }
+    }
+    members.sort();
+    members.dedup();
+    members
+}
+
+fn expand_pattern(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut dirs = vec![root.to_path_buf()];
+    for component in pattern.split('/').filter(|part| !part.is_empty() && *part != ".") {
+        dirs = if component == "**" {
+            dirs.iter()
+                .flat_map(|dir| {
+                    let mut found = vec![dir.clone()];
+                    found.extend(descendant_dirs(dir, MAX_WALK_DEPTH));
+                    found
+                })
+                .collect()
+        } else if component.contains(['*', '?']) {
+            let matcher = WildMatch::new(component);
+            dirs.iter()
+                .flat_map(|dir| child_dirs(dir))
+                .filter(|dir| {
+                    dir.file_name()
+                        .is_some_and(|name| matcher.matches(&name.to_string_lossy()))
+                })
+                .collect()
+        } else {
+            dirs.iter()
+                .map(|dir| dir.join(component))
+                .filter(|dir| dir.is_dir())
+                .collect()
+        };
+    }
+    dirs
+}
+
+fn child_dirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            !name.starts_with('.')
+                && !name.starts_with("bazel-")
+                && !SKIPPED_DIRS.contains(&name.as_str())
+        })
+        .map(|entry| entry.path())
+        .collect()
+}
+
+fn descendant_dirs(dir: &Path, depth: usize) -> Vec<PathBuf> {
+    if depth == 0 {
+        return Vec::new();
+    }
+    let mut found = Vec::new();
+    for child in child_dirs(dir) {
+        found.extend(descendant_dirs(&child, depth - 1));
+        found.push(child);
+    }
+    found
+}
+
+/// Developer message telling the model the session is scoped to `scope`.
+pub fn scope_message(scope: &Path, workspace: Option<&Workspace>) -> String {
+    let location = match workspace.and_then(|workspace| {
+        workspace
+            .member_containing(scope)
+            .map(|member| (workspace, member))
+    }) {
+        Some((workspace, member)) => format!(
+            " It is the `{}` member of the {} at {}.",
+            member.display(),
+            workspace.kind,
+            workspace.root.display()
+        ),
+        None => String::new(),
+    };
+    format!(
+        "The user scoped this session to the subproject at {}.{location} Keep reads, edits and diffs inside that directory and run commands with it as the working directory (`workdir`), unless the task clearly needs the rest of the repository.",
+        scope.display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn write(path: &Path, contents: &str) {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("create dirs");
+        }
+        fs::write(path, contents).expect("write file");
+    }
+
+    #[test]
+    fn cargo_and_pnpm_members_are_expanded() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        write(
+            &root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"cli\", \"crates/*\"]\nexclude = [\"crates/scratch\"]\n",
+        );
+        for member in ["cli", "crates/core", "crates/scratch"] {
+            write(&root.join(member).join("Cargo.toml"), "[package]\n");
+        }
+        fs::create_dir_all(root.join("crates/docs")).expect("create dirs");
+
+        let workspace = detect_workspace(&root.join("crates/core/src")).expect("workspace");
+        assert_eq!(workspace.kind, WorkspaceKind::Cargo);
+        assert_eq!(workspace.root, root);
+        assert_eq!(
+            workspace.members,
+            vec![PathBuf::from("cli"), PathBuf::from("crates/core")]
+        );
+        assert_eq!(
+            workspace.member_containing(&root.join("crates/core/src")),
+            Some(Path::new("crates/core"))
+        );
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        write(
+            &root.join("pnpm-workspace.yaml"),
+            "packages:\n  - 'apps/*'\n  - 'packages/**'\n  - '!packages/legacy'\n",
+        );
+        for member in ["apps/web", "packages/ui", "packages/legacy", "packages/tools/lint"] {
+            write(&root.join(member).join("package.json"), "{}");
+        }
+        let workspace = detect_workspace(root).expect("workspace");
+        assert_eq!(workspace.kind, WorkspaceKind::Pnpm);
+        assert_eq!(
+            workspace.members,
+            vec![
+                PathBuf::from("apps/web"),
+                PathBuf::from("packages/tools/lint"),
+                PathBuf::from("packages/ui"),
+            ]
+        );
+    }
+}
@@ -300,6 +300,138 @@ fn create_image_view_tool() -> OpenAiTool {
     })
 }
 
+fn create_fetch_url_tool(allowed_domains: Option<&[String]>) -> OpenAiTool {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "url".to_string(),
+        JsonSchema::String {
+            description: Some("http(s) URL of the page to read.".to_string()),
+            allowed_values: None,
+        },
+    );
+
+    let mut description = "Download a web page over plain HTTP (no browser) and return its main content as markdown. Use it for documentation and reference lookups; use the browser tool for pages that need JavaScript or interaction.".to_string();
+    if let Some(domains) = allowed_domains.filter(|domains| !domains.is_empty()) {
+        description.push_str(&format!(" Only these domains (and their subdomains) can be fetched: {}.", domains.join(", ")));
+    }
+
+    OpenAiTool::Function(ResponsesApiTool {
+        name: "fetch_url".to_string(),
+        description,
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["url".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_save_artifact_tool() -> OpenAiTool {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "name".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "File name for the artifact, e.g. `report.md` or `chart.png`. No directories; an existing artifact with the same name is replaced.".to_string(),
+            ),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "content".to_string(),
+        JsonSchema::String {
+            description: Some("File content. Omit when `source_path` is given.".to_string()),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "encoding".to_string(),
+        JsonSchema::String {
+            description: Some("Encoding of `content`: `utf8` (default) or `base64` for binary data.".to_string()),
+            allowed_values: Some(vec!["utf8".to_string(), "base64".to_string()]),
+        },
+    );
+    properties.insert(
+        "source_path".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Existing file to copy into the artifacts folder instead of passing `content` (relative paths resolve against the working directory).".to_string(),
+            ),
+            allowed_values: None,
+        },
+    );
+
+    OpenAiTool::Function(ResponsesApiTool {
+        name: "save_artifact".to_string(),
+        description: "Save a file meant for the user rather than the repository (a report, generated image, CSV export) to this session's artifacts folder, where the user can open it with /artifacts. Returns the saved path and size.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_run_tests_tool() -> OpenAiTool {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "runner".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Test runner. Omit to detect it from the nearest Cargo.toml, jest package.json or pytest configuration.".to_string(),
+            ),
+            allowed_values: Some(vec!["cargo".to_string(), "pytest".to_string(), "jest".to_string()]),
+        },
+    );
+    properties.insert(
+        "filter".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Only run tests matching this name (`cargo test <filter>`, `pytest -k <filter>`, `jest -t <filter>`).".to_string(),
+            ),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "args".to_string(),
+        JsonSchema::Array {
+            items: Box::new(JsonSchema::String {
+                description: None,
+                allowed_values: None,
+            }),
+            description: Some(
+                "Extra arguments for the runner, e.g. `[\"-p\", \"my-crate\"]` or test file paths.".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "workdir".to_string(),
+        JsonSchema::String {
+            description: Some("Directory to run the tests in; defaults to the project the runner was detected in.".to_string()),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "timeout_ms".to_string(),
+        JsonSchema::Number {
+            description: Some("Kill the run after this many milliseconds (default 1,200,000 / 20 minutes).".to_string()),
+        },
+    );
+
+    OpenAiTool::Function(ResponsesApiTool {
+        name: "run_tests".to_string(),
+        description: "Run the project's tests with cargo test, pytest or jest and get structured results: pass/fail counts and, for each failing test, its name, file, line, message and its own output. Prefer it over running the test command through the shell; the user sees the failures as a list.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_request_user_input_tool() -> OpenAiTool {
     let mut option_props = BTreeMap::new();
     option_props.insert(
@@ -802,6 +934,15 @@ pub fn get_openai_tools(
     }
 
     tools.push(create_browser_tool(browser_enabled));
+    if config.fetch_url {
+        tools.push(create_fetch_url_tool(config.fetch_url_allowed_domains.as_deref()));
+    }
+    if config.artifacts {
+        tools.push(create_save_artifact_tool());
+    }
+    if config.run_tests {
+        tools.push(create_run_tests_tool());
+    }
 
     // Add agent management tool for launching and monitoring asynchronous agents
     tools.push(create_agent_tool(config.agent_models()));
@@ -1044,6 +1185,12 @@ fn create_browser_tool(browser_enabled: bool) -> OpenAiTool {
             "console",
             "cleanup",
             "cdp",
+            "tabs",
+            "new_tab",
+            "switch_tab",
+            "close_tab",
+            "snapshot",
+            "read",
         ]);
     }
 
@@ -1063,12 +1210,48 @@ fn create_browser_tool(browser_enabled: bool) -> OpenAiTool {
         "url".to_string(),
         JsonSchema::String {
             description: Some(
-                "For action=open or fetch: URL to navigate to or retrieve (e.g., https://example.com)."
+                "For action=open, fetch or new_tab: URL to navigate to or retrieve (e.g., https://example.com)."
                     .to_string(),
             ),
             allowed_values: None,
         },
     );
+    properties.insert(
+        "format".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "For action=read: 'text' (default) for the page's rendered text, or 'accessibility' for an outline of its accessibility tree (roles, names, states). Much cheaper than a screenshot on text-heavy pages."
+                    .to_string(),
+            ),
+            allowed_values: Some(vec!["text".to_string(), "accessibility".to_string()]),
+        },
+    );
+    properties.insert(
+        "screenshot".to_string(),
+        JsonSchema::Boolean {
+            description: Some(
+                "For action=read: also return a screenshot of the page (default false).".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "max_chars".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "For action=read: maximum characters of extracted text to return (default 20000)."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "tab".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "For actions=switch_tab/close_tab/snapshot: 1-based tab index as listed by action=tabs (snapshot defaults to the active tab)."
+                    .to_string(),
+            ),
+        },
+    );
     properties.insert(
         "type".to_string(),
         JsonSchema::String {
@@ -1209,7 +1392,7 @@ fn create_browser_tool(browser_enabled: bool) -> OpenAiTool {
 
     OpenAiTool::Function(ResponsesApiTool {
         name: "browser".to_string(),
-        description: "Unified browser controller for navigation, interaction, console access, DevTools commands, and one-shot fetches. Choose an action and supply the matching fields.".to_string(),
+        description: "Unified browser controller for navigation, tabs, interaction, console access, DevTools commands, and one-shot fetches. Choose an action and supply the matching fields.".to_string(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
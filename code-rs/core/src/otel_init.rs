@@ -14,7 +14,19 @@ pub fn build_provider(
     config: &Config,
     service_version: &str,
 ) -> Result<Option<OtelProvider>, Box<dyn Error>> {
-    let exporter = match &config.otel.exporter {
+    OtelProvider::from(&OtelSettings {
+        service_name: config.responses_originator_header.clone(),
+        service_version: service_version.to_string(),
+        code_home: config.code_home.clone(),
+        environment: config.otel.environment.to_string(),
+        exporter: to_exporter(&config.otel.exporter),
+        trace_exporter: to_exporter(&config.otel.trace_exporter),
+        metrics_exporter: to_exporter(&config.otel.metrics_exporter),
+    })
+}
+
+fn to_exporter(kind: &Kind) -> OtelExporter {
+    match kind {
         Kind::None => OtelExporter::None,
         Kind::OtlpHttp {
             endpoint,
@@ -42,15 +54,7 @@ pub fn build_provider(
                 .map(|(k, v)| (k.clone(), v.clone()))
                 .collect(),
         },
-    };
-
-    OtelProvider::from(&OtelSettings {
-        service_name: config.responses_originator_header.clone(),
-        service_version: service_version.to_string(),
-        code_home: config.code_home.clone(),
-        environment: config.otel.environment.to_string(),
-        exporter,
-    })
+    }
 }
 
 /// Filter predicate for exporting only Codex-owned events via OTEL.
@@ -58,3 +62,9 @@ pub fn build_provider(
 pub fn code_export_filter(meta: &tracing::Metadata<'_>) -> bool {
     meta.target().starts_with("code_otel")
 }
+
+/// Filter predicate for exporting only the spans core opens for tracing
+/// (target [`code_otel::TRACE_TARGET`]).
+pub fn code_trace_filter(meta: &tracing::Metadata<'_>) -> bool {
+    meta.is_span() && meta.target() == code_otel::TRACE_TARGET
+}
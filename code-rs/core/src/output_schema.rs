@@ -0,0 +1,247 @@
+//! Structured output mode. When a turn carries `final_output_json_schema`,
+//! the model is asked to answer with JSON matching it, and the final answer
+//! is checked here before the task completes.
+//!
+//! Only the schema keywords that structured output uses are understood:
+//! `type`, `enum`, `const`, `properties`, `required`,
+//! `additionalProperties`, `items`, `minItems`/`maxItems`,
+//! `minLength`/`maxLength`, `minimum`/`maximum` and `anyOf`. Other keywords
+//! are ignored.
+
+use serde_json::Value;
+
+/// Most problems listed for one answer.
+const MAX_REPORTED_ERRORS: usize = 8;
+
+/// Parses the final answer as JSON. A single surrounding ```json fence is
+/// tolerated because some models add one even in structured mode.
+pub fn parse_structured_answer(text: &str) -> Result<Value, String> {
+    let trimmed = text.trim();
+    let unfenced = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.strip_suffix("```"))
+        .unwrap_or(trimmed);
+    serde_json::from_str(unfenced.trim()).map_err(|err| format!("answer is not valid JSON: {err}"))
+}
+
+/// Every place where `value` breaks `schema`, as `path: problem` lines.
+/// An empty list means the value matches.
+pub fn validate(schema: &Value, value: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    check(schema, value, "$", &mut errors);
+    errors.truncate(MAX_REPORTED_ERRORS);
+    errors
+}
+
+/// Parses and validates the final answer of a structured turn. Returns the
+/// warning to show when it does not match.
+pub fn check_final_answer(schema: &Value, answer: Option<&str>) -> Option<String> {
+    let Some(answer) = answer else {
+        return Some("Structured output: the turn ended without a final answer.".to_string());
+    };
+    let problems = match parse_structured_answer(answer) {
+        Ok(value) => validate(schema, &value),
+        Err(err) => vec![err],
+    };
+    if problems.is_empty() {
+        return None;
+    }
+    let list = problems.join("\n- ");
+    Some(format!("Structured output: the final answer does not match the schema.\n- {list}"))
+}
+
+fn type_matches(name: &str, value: &Value) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn check(schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        if schema == &Value::Bool(false) {
+            errors.push(format!("{path}: no value is allowed here"));
+        }
+        return;
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let names: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !names.is_empty() && !names.iter().any(|name| type_matches(name, value)) {
+            let expected = names.join(" or ");
+            let found = type_name(value);
+            errors.push(format!("{path}: expected {expected}, found {found}"));
+            return;
+        }
+    }
+    if let Some(constant) = schema.get("const")
+        && constant != value
+    {
+        errors.push(format!("{path}: expected {constant}"));
+    }
+    if let Some(Value::Array(allowed)) = schema.get("enum")
+        && !allowed.contains(value)
+    {
+        let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+        let allowed = allowed.join(", ");
+        errors.push(format!("{path}: {value} is not one of {allowed}"));
+    }
+    if let Some(Value::Array(options)) = schema.get("anyOf")
+        && !options.iter().any(|option| validate(option, value).is_empty())
+    {
+        errors.push(format!("{path}: matches none of the anyOf options"));
+    }
+
+    match value {
+        Value::Object(map) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !map.contains_key(key) {
+                        errors.push(format!("{path}: missing required field `{key}`"));
+                    }
+                }
+            }
+            for (key, item) in map {
+                let child = format!("{path}.{key}");
+                match properties.and_then(|props| props.get(key)) {
+                    Some(item_schema) => check(item_schema, item, &child, errors),
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            errors.push(format!("{path}: unexpected field `{key}`"));
+                        }
+                        Some(extra) if extra.is_object() => check(extra, item, &child, errors),
+                        _ => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            let len = items.len() as u64;
+            if let Some(min) = schema.get("minItems").and_then(Value::as_u64)
+                && len < min
+            {
+                errors.push(format!("{path}: expected at least {min} items, found {len}"));
+            }
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64)
+                && len > max
+            {
+                errors.push(format!("{path}: expected at most {max} items, found {len}"));
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (idx, item) in items.iter().enumerate() {
+                    check(item_schema, item, &format!("{path}[{idx}]"), errors);
+                }
+            }
+        }
+        Value::String(text) => {
+            let len = text.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64)
+                && len < min
+            {
+                errors.push(format!("{path}: shorter than {min} characters"));
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64)
+                && len > max
+            {
+                errors.push(format!("{path}: longer than {max} characters"));
+            }
+        }
+        Value::Number(number) => {
+            let Some(number) = number.as_f64() else {
+                return;
+            };
+            if let Some(min) = schema.get("minimum").and_then(Value::as_f64)
+                && number < min
+            {
+                errors.push(format!("{path}: {number} is below the minimum {min}"));
+            }
+            if let Some(max) = schema.get("maximum").and_then(Value::as_f64)
+                && number > max
+            {
+                errors.push(format!("{path}: {number} is above the maximum {max}"));
+            }
+        }
+        Value::Null | Value::Bool(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "status": { "type": "string", "enum": ["ok", "failed"] },
+                "files": { "type": "array", "items": { "type": "string" }, "minItems": 1 },
+                "count": { "type": "integer", "minimum": 0 }
+            },
+            "required": ["status", "files"],
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn matching_answer_has_no_errors() {
+        let value = parse_structured_answer("```json\n{\"status\":\"ok\",\"files\":[\"a.rs\"],\"count\":2}\n```")
+            .expect("fenced JSON parses");
+        assert!(validate(&schema(), &value).is_empty());
+    }
+
+    #[test]
+    fn reports_each_mismatch_with_its_path() {
+        let value = json!({ "status": "maybe", "files": [1], "count": 1.5, "extra": true });
+        // Field order depends on whether serde_json preserves insertion order.
+        let mut errors = validate(&schema(), &value);
+        errors.sort();
+        assert_eq!(
+            errors,
+            vec![
+                "$.count: expected integer, found number".to_string(),
+                "$.files[0]: expected string, found number".to_string(),
+                "$.status: \"maybe\" is not one of \"ok\", \"failed\"".to_string(),
+                "$: unexpected field `extra`".to_string(),
+            ]
+        );
+        let missing = validate(&schema(), &json!({ "files": [] }));
+        assert_eq!(
+            missing,
+            vec![
+                "$: missing required field `status`".to_string(),
+                "$.files: expected at least 1 items, found 0".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn non_json_answer_is_reported() {
+        let warning = check_final_answer(&schema(), Some("All done!")).expect("warning");
+        assert!(warning.contains("not valid JSON"), "{warning}");
+        assert_eq!(check_final_answer(&schema(), Some("{\"status\":\"ok\",\"files\":[\"x\"]}")), None);
+    }
+}
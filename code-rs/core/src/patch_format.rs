@@ -0,0 +1,170 @@
+//! Opt-in formatter pass run after a patch applies successfully.
+//!
+//! `[patches] format_command` maps globs to a formatter command line; every
+//! file the patch added or modified is passed to the first matching command.
+//! Formatter failures never fail the patch: callers surface them as warnings.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use tokio::process::Command;
+
+use crate::apply_patch::matches_patch_glob;
+use crate::protocol::FileChange;
+
+const FORMAT_TIMEOUT: Duration = Duration::from_secs(60);
+const MAX_OUTPUT_CHARS: usize = 2_000;
+
+#[derive(Debug)]
+pub(crate) struct FormatOutcome {
+    pub command: String,
+    pub files: Vec<PathBuf>,
+    pub success: bool,
+    pub output: String,
+}
+
+/// Files left on disk by a patch: additions and (possibly renamed) updates.
+pub(crate) fn formattable_paths(changes: &HashMap<PathBuf, FileChange>) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = changes
+        .iter()
+        .filter_map(|(path, change)| match change {
+            FileChange::Add { .. } => Some(path.clone()),
+            FileChange::Update { move_path, .. } => {
+                Some(move_path.clone().unwrap_or_else(|| path.clone()))
+            }
+            FileChange::Delete => None,
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Groups `paths` by the first glob in `commands` they match.
+fn plan_format_commands(
+    cwd: &Path,
+    commands: &BTreeMap<String, String>,
+    paths: &[PathBuf],
+) -> Vec<(String, Vec<PathBuf>)> {
+    let mut plan: Vec<(String, Vec<PathBuf>)> = Vec::new();
+    for path in paths {
+        let Some(command) = commands
+            .iter()
+            .find(|(glob, _)| matches_patch_glob(cwd, path, std::slice::from_ref(*glob)))
+            .map(|(_, command)| command)
+        else {
+            continue;
+        };
+        match plan.iter_mut().find(|(existing, _)| existing == command) {
+            Some((_, files)) => files.push(path.clone()),
+            None => plan.push((command.clone(), vec![path.clone()])),
+        }
+    }
+    plan
+}
+
+/// Runs each configured formatter once with its matching files appended.
+pub(crate) async fn run_format_commands(
+    cwd: &Path,
+    commands: &BTreeMap<String, String>,
+    paths: &[PathBuf],
+) -> Vec<FormatOutcome> {
+    let mut outcomes = Vec::new();
+    for (command, files) in plan_format_commands(cwd, commands, paths) {
+        let (success, output) = run_one(cwd, &command, &files).await;
+        outcomes.push(FormatOutcome {
+            command,
+            files,
+            success,
+            output,
+        });
+    }
+    outcomes
+}
+
+async fn run_one(cwd: &Path, command: &str, files: &[PathBuf]) -> (bool, String) {
+    let Some(argv) = shlex::split(command).filter(|argv| !argv.is_empty()) else {
+        return (false, format!("could not parse format command `{command}`"));
+    };
+    let mut cmd = Command::new(&argv[0]);
+    cmd.args(&argv[1..])
+        .args(files)
+        .current_dir(cwd)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true);
+    match tokio::time::timeout(FORMAT_TIMEOUT, cmd.output()).await {
+        Err(_) => (
+            false,
+            format!("timed out after {}s", FORMAT_TIMEOUT.as_secs()),
+        ),
+        Ok(Err(err)) => (false, format!("failed to start: {err}")),
+        Ok(Ok(output)) => {
+            let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if !stderr.trim().is_empty() {
+                if !text.trim().is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(&stderr);
+            }
+            let mut text = text.trim().to_string();
+            if text.chars().count() > MAX_OUTPUT_CHARS {
+                text = text.chars().take(MAX_OUTPUT_CHARS).collect();
+                text.push('…');
+            }
+            (output.status.success(), text)
+        }
+    }
+}
+
+/// One-line summary per formatter run, followed by its output when present.
+pub(crate) fn format_report(cwd: &Path, outcome: &FormatOutcome) -> String {
+    let files = outcome
+        .files
+        .iter()
+        .map(|path| path.strip_prefix(cwd).unwrap_or(path).display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let status = if outcome.success { "formatted" } else { "format failed" };
+    let mut report = format!("`{}` {status}: {files}", outcome.command);
+    if !outcome.output.is_empty() {
+        report.push('\n');
+        report.push_str(&outcome.output);
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_files_by_first_matching_glob() {
+        let cwd = Path::new("/repo");
+        let commands: BTreeMap<String, String> = [
+            ("*.rs".to_string(), "rustfmt --edition 2024".to_string()),
+            ("web/**".to_string(), "prettier --write".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let paths = vec![
+            cwd.join("src/lib.rs"),
+            cwd.join("web/app.ts"),
+            cwd.join("src/main.rs"),
+            cwd.join("README.md"),
+        ];
+        let plan = plan_format_commands(cwd, &commands, &paths);
+        assert_eq!(
+            plan,
+            vec![
+                (
+                    "rustfmt --edition 2024".to_string(),
+                    vec![cwd.join("src/lib.rs"), cwd.join("src/main.rs")]
+                ),
+                ("prettier --write".to_string(), vec![cwd.join("web/app.ts")]),
+            ]
+        );
+    }
+}
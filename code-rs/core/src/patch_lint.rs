@@ -0,0 +1,517 @@
+//! Pre-approval lint gate (`[patches.lint]`).
+//!
+//! Before a patch that needs approval is shown to the user, each configured
+//! linter whose globs match the patch runs twice on a scratch copy of the
+//! workspace: once as it is and once with the patch applied. Problems only
+//! the second run reports are summarised in the approval prompt ("introduces
+//! 3 clippy warnings"), so low-quality patches can be rejected before they
+//! touch the real files. The copy lives for the session, so later runs only
+//! re-copy what changed and build caches (an ignored `target/`, say) are
+//! reused.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use code_apply_patch::ApplyPatchAction;
+use code_apply_patch::ApplyPatchFileChange;
+use tokio::process::Command;
+use uuid::Uuid;
+
+use crate::apply_patch::matches_patch_glob;
+use crate::config_types::PatchLintConfig;
+
+const DEFAULT_LINT_TIMEOUT_SECS: u64 = 120;
+
+/// Workspaces with more files than this are not copied.
+const MAX_OVERLAY_FILES: usize = 20_000;
+
+/// New problems quoted in the approval prompt.
+const MAX_LISTED_FINDINGS: usize = 5;
+
+pub(crate) struct LintGate {
+    root: PathBuf,
+    overlay: PathBuf,
+    linters: BTreeMap<String, PatchLintConfig>,
+    /// One gate run at a time; they share the overlay.
+    running: tokio::sync::Mutex<()>,
+}
+
+/// One problem reported by a linter, without its position so it still
+/// matches after edits above it move it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LintFinding {
+    path: String,
+    message: String,
+}
+
+impl LintFinding {
+    fn is_error(&self) -> bool {
+        let message = self.message.to_ascii_lowercase();
+        message.starts_with("error") || message.contains("[error")
+    }
+}
+
+#[derive(Debug)]
+enum LintOutcome {
+    Compared { new: Vec<LintFinding>, fixed: usize },
+    Failed(String),
+}
+
+impl LintGate {
+    /// `None` when no linters are configured.
+    pub(crate) fn new(
+        linters: &BTreeMap<String, PatchLintConfig>,
+        root: &Path,
+        session_id: Uuid,
+    ) -> Option<Self> {
+        if linters.is_empty() {
+            return None;
+        }
+        Some(Self {
+            root: root.to_path_buf(),
+            overlay: std::env::temp_dir().join(format!("code-lint-gate-{session_id}")),
+            linters: linters.clone(),
+            running: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Whether any linter would run for `action`.
+    pub(crate) fn applies_to(&self, action: &ApplyPatchAction) -> bool {
+        let changes = overlay_changes(&self.root, action);
+        !self.linters_for(&changes).is_empty()
+    }
+
+    fn linters_for(
+        &self,
+        changes: &[(PathBuf, Option<String>)],
+    ) -> Vec<(&String, &PatchLintConfig)> {
+        if changes.is_empty() {
+            return Vec::new();
+        }
+        self.linters
+            .iter()
+            .filter(|(_, linter)| {
+                linter.files.is_empty()
+                    || changes
+                        .iter()
+                        .any(|(path, _)| matches_patch_glob(Path::new(""), path, &linter.files))
+            })
+            .collect()
+    }
+
+    /// Runs the linters that apply to `action` and returns the note for the
+    /// approval prompt, or `None` when no linter matches the patch.
+    pub(crate) async fn check(&self, action: &ApplyPatchAction) -> Option<String> {
+        let changes = overlay_changes(&self.root, action);
+        let linters = self.linters_for(&changes);
+        if linters.is_empty() {
+            return None;
+        }
+
+        let _running = self.running.lock().await;
+        let (root, overlay) = (self.root.clone(), self.overlay.clone());
+        let synced = tokio::task::spawn_blocking(move || sync_overlay(&root, &overlay))
+            .await
+            .unwrap_or_else(|err| Err(std::io::Error::other(err)));
+        if let Err(err) = synced {
+            return Some(format!("Lint gate: could not copy the workspace: {err}"));
+        }
+
+        let mut baseline = Vec::new();
+        for (_, linter) in &linters {
+            baseline.push(run_linter(&self.overlay, linter).await);
+        }
+        let overlay = self.overlay.clone();
+        let applied = tokio::task::spawn_blocking(move || apply_to_overlay(&overlay, &changes))
+            .await
+            .unwrap_or_else(|err| Err(std::io::Error::other(err)));
+        if let Err(err) = applied {
+            return Some(format!("Lint gate: could not apply the patch to the copy: {err}"));
+        }
+
+        let mut outcomes = Vec::new();
+        for ((name, linter), before) in linters.into_iter().zip(baseline) {
+            let after = run_linter(&self.overlay, linter).await;
+            let outcome = match (before, after) {
+                (Ok(before), Ok(after)) => {
+                    let (new, fixed) = compare_findings(&before, after);
+                    LintOutcome::Compared { new, fixed }
+                }
+                (Err(err), _) | (_, Err(err)) => LintOutcome::Failed(err),
+            };
+            outcomes.push((name.clone(), outcome));
+        }
+        Some(gate_summary(&outcomes))
+    }
+}
+
+impl Drop for LintGate {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.overlay);
+    }
+}
+
+/// The patch's changes relative to `root`: new contents, or `None` for a
+/// removed file. Changes outside `root` are left out.
+fn overlay_changes(root: &Path, action: &ApplyPatchAction) -> Vec<(PathBuf, Option<String>)> {
+    let relative = |path: &Path| path.strip_prefix(root).ok().map(Path::to_path_buf);
+    let mut changes = Vec::new();
+    for (path, change) in action.changes() {
+        match change {
+            ApplyPatchFileChange::Add { content } => {
+                changes.extend(relative(path).map(|path| (path, Some(content.clone()))));
+            }
+            ApplyPatchFileChange::Delete { .. } => {
+                changes.extend(relative(path).map(|path| (path, None)));
+            }
+            ApplyPatchFileChange::Update {
+                move_path,
+                new_content,
+                ..
+            } => {
+                if let Some(dest) = move_path.as_deref()
+                    && dest != path.as_path()
+                {
+                    changes.extend(relative(path).map(|path| (path, None)));
+                }
+                let dest = move_path.as_deref().unwrap_or(path);
+                changes.extend(relative(dest).map(|path| (path, Some(new_content.clone()))));
+            }
+        }
+    }
+    changes
+}
+
+fn overlay_walker(dir: &Path) -> ignore::Walk {
+    // The copy has no `.git`, so ignore files must apply without one.
+    ignore::WalkBuilder::new(dir)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build()
+}
+
+/// Makes `overlay` a copy of the files under `root` that git would not
+/// ignore. Files whose copy is newer and the same size are kept, and ignored
+/// files already in the copy (build output) are left alone.
+fn sync_overlay(root: &Path, overlay: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(overlay)?;
+    let mut files: HashSet<PathBuf> = HashSet::new();
+    for entry in overlay_walker(root).flatten() {
+        if !entry.file_type().is_some_and(|kind| kind.is_file()) {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        files.insert(relative.to_path_buf());
+        if files.len() > MAX_OVERLAY_FILES {
+            return Err(std::io::Error::other(format!(
+                "more than {MAX_OVERLAY_FILES} files"
+            )));
+        }
+        let dest = overlay.join(relative);
+        if is_current_copy(entry.path(), &dest) {
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(entry.path(), &dest)?;
+    }
+    for entry in overlay_walker(overlay).flatten() {
+        if !entry.file_type().is_some_and(|kind| kind.is_file()) {
+            continue;
+        }
+        if let Ok(relative) = entry.path().strip_prefix(overlay)
+            && !files.contains(relative)
+        {
+            std::fs::remove_file(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+fn is_current_copy(source: &Path, dest: &Path) -> bool {
+    let (Ok(source), Ok(dest)) = (std::fs::metadata(source), std::fs::metadata(dest)) else {
+        return false;
+    };
+    let modified = |metadata: &std::fs::Metadata| metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    source.len() == dest.len() && modified(&dest) >= modified(&source)
+}
+
+fn apply_to_overlay(overlay: &Path, changes: &[(PathBuf, Option<String>)]) -> std::io::Result<()> {
+    for (path, content) in changes {
+        let dest = overlay.join(path);
+        match content {
+            Some(content) => {
+                if let Some(parent) = dest.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&dest, content)?;
+            }
+            None => match std::fs::remove_file(&dest) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err),
+                _ => {}
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Runs one linter in the overlay and parses `path:line[:col]: message`
+/// lines from its output. Other lines are ignored.
+async fn run_linter(overlay: &Path, linter: &PatchLintConfig) -> Result<Vec<LintFinding>, String> {
+    let Some(argv) = shlex::split(&linter.command).filter(|argv| !argv.is_empty()) else {
+        return Err(format!("could not parse `{}`", linter.command));
+    };
+    let timeout = Duration::from_secs(linter.timeout_seconds.unwrap_or(DEFAULT_LINT_TIMEOUT_SECS));
+    let mut cmd = Command::new(&argv[0]);
+    cmd.args(&argv[1..])
+        .current_dir(overlay)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true);
+    let output = match tokio::time::timeout(timeout, cmd.output()).await {
+        Err(_) => return Err(format!("timed out after {}s", timeout.as_secs())),
+        Ok(Err(err)) => return Err(format!("failed to start: {err}")),
+        Ok(Ok(output)) => output,
+    };
+    let text = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(text
+        .lines()
+        .filter_map(parse_finding)
+        .map(|mut finding| {
+            if let Ok(relative) = Path::new(&finding.path).strip_prefix(overlay) {
+                finding.path = relative.display().to_string();
+            }
+            finding
+        })
+        .collect())
+}
+
+fn parse_finding(line: &str) -> Option<LintFinding> {
+    let (path, rest) = line.trim().split_once(':')?;
+    if path.is_empty() || path.contains(char::is_whitespace) {
+        return None;
+    }
+    let (line_number, mut rest) = rest.split_once(':')?;
+    line_number.trim().parse::<u32>().ok()?;
+    if let Some((column, after)) = rest.split_once(':')
+        && column.trim().parse::<u32>().is_ok()
+    {
+        rest = after;
+    }
+    let message = rest.trim();
+    if message.is_empty() {
+        return None;
+    }
+    Some(LintFinding {
+        path: path.trim_start_matches("./").to_string(),
+        message: message.to_string(),
+    })
+}
+
+/// Findings only `after` reports, and how many of `before` are gone.
+/// Repeats count: a second copy of an old problem is new.
+fn compare_findings(before: &[LintFinding], after: Vec<LintFinding>) -> (Vec<LintFinding>, usize) {
+    let mut unmatched: Vec<&LintFinding> = before.iter().collect();
+    let new = after
+        .into_iter()
+        .filter(|finding| match unmatched.iter().position(|old| *old == finding) {
+            Some(index) => {
+                unmatched.swap_remove(index);
+                false
+            }
+            None => true,
+        })
+        .collect();
+    (new, unmatched.len())
+}
+
+fn counted(count: usize, what: &str) -> String {
+    format!("{count} {what}{}", if count == 1 { "" } else { "s" })
+}
+
+/// The approval note: what the patch introduces per linter, then a few of
+/// the new problems.
+fn gate_summary(outcomes: &[(String, LintOutcome)]) -> String {
+    let mut introduced = Vec::new();
+    let mut fixed = Vec::new();
+    let mut failed = Vec::new();
+    let mut clean = Vec::new();
+    let mut listed = Vec::new();
+    for (name, outcome) in outcomes {
+        match outcome {
+            LintOutcome::Compared { new, fixed: gone } => {
+                let errors = new.iter().filter(|finding| finding.is_error()).count();
+                let warnings = new.len() - errors;
+                if warnings > 0 {
+                    introduced.push(counted(warnings, &format!("{name} warning")));
+                }
+                if errors > 0 {
+                    introduced.push(counted(errors, &format!("{name} error")));
+                }
+                if new.is_empty() {
+                    clean.push(name.as_str());
+                }
+                if *gone > 0 {
+                    fixed.push(counted(*gone, &format!("{name} problem")));
+                }
+                listed.extend(new.iter().map(|finding| format!("{}: {}", finding.path, finding.message)));
+            }
+            LintOutcome::Failed(reason) => failed.push(format!("{name} could not run ({reason})")),
+        }
+    }
+
+    let mut summary = if introduced.is_empty() {
+        if clean.is_empty() {
+            "Lint gate:".to_string()
+        } else {
+            format!("Lint gate: no new problems from {}", clean.join(", "))
+        }
+    } else {
+        format!("Lint gate: introduces {}", introduced.join(" and "))
+    };
+    let mut tail: Vec<String> = Vec::new();
+    if !fixed.is_empty() {
+        tail.push(format!("fixes {}", fixed.join(" and ")));
+    }
+    tail.extend(failed);
+    if !tail.is_empty() {
+        if summary.ends_with(':') {
+            summary.push(' ');
+        } else {
+            summary.push_str("; ");
+        }
+        summary.push_str(&tail.join("; "));
+    }
+    summary.push('.');
+    for line in listed.iter().take(MAX_LISTED_FINDINGS) {
+        summary.push_str("\n  ");
+        summary.push_str(line);
+    }
+    if listed.len() > MAX_LISTED_FINDINGS {
+        summary.push_str(&format!("\n  … {} more", listed.len() - MAX_LISTED_FINDINGS));
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(path: &str, message: &str) -> LintFinding {
+        LintFinding {
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_common_linter_output() {
+        assert_eq!(
+            parse_finding("src/lib.rs:3:9: warning: unused variable: `x`"),
+            Some(finding("src/lib.rs", "warning: unused variable: `x`"))
+        );
+        assert_eq!(
+            parse_finding("./app.py:12: error: Incompatible return value type"),
+            Some(finding("app.py", "error: Incompatible return value type"))
+        );
+        assert_eq!(
+            parse_finding("web/a.ts:1:7: 'x' is defined but never used. [Error/no-unused-vars]"),
+            Some(finding("web/a.ts", "'x' is defined but never used. [Error/no-unused-vars]"))
+        );
+        assert_eq!(parse_finding("warning: build failed, waiting for other jobs"), None);
+        assert_eq!(parse_finding("Checking crate v0.1.0 (/repo)"), None);
+    }
+
+    #[test]
+    fn counts_new_findings_ignoring_moved_lines() {
+        let before = vec![
+            finding("src/lib.rs", "warning: unused variable: `x`"),
+            finding("src/lib.rs", "warning: unused import: `std::fs`"),
+        ];
+        let after = vec![
+            finding("src/lib.rs", "warning: unused variable: `x`"),
+            finding("src/lib.rs", "warning: unused variable: `x`"),
+            finding("src/main.rs", "error[E0308]: mismatched types"),
+        ];
+        let (new, fixed) = compare_findings(&before, after);
+        assert_eq!(
+            new,
+            vec![
+                finding("src/lib.rs", "warning: unused variable: `x`"),
+                finding("src/main.rs", "error[E0308]: mismatched types"),
+            ]
+        );
+        assert_eq!(fixed, 1);
+    }
+
+    #[test]
+    fn summary_names_what_each_linter_introduces() {
+        let outcomes = vec![
+            (
+                "clippy".to_string(),
+                LintOutcome::Compared {
+                    new: vec![
+                        finding("src/lib.rs", "warning: this `if` has identical blocks"),
+                        finding("src/lib.rs", "warning: redundant clone"),
+                        finding("src/main.rs", "warning: needless borrow"),
+                    ],
+                    fixed: 0,
+                },
+            ),
+            (
+                "ruff".to_string(),
+                LintOutcome::Compared { new: Vec::new(), fixed: 2 },
+            ),
+            (
+                "eslint".to_string(),
+                LintOutcome::Failed("failed to start: No such file or directory".to_string()),
+            ),
+        ];
+        assert_eq!(
+            gate_summary(&outcomes),
+            "Lint gate: introduces 3 clippy warnings; fixes 2 ruff problems; eslint could not run (failed to start: No such file or directory).\n  src/lib.rs: warning: this `if` has identical blocks\n  src/lib.rs: warning: redundant clone\n  src/main.rs: warning: needless borrow"
+        );
+        let clean = vec![(
+            "clippy".to_string(),
+            LintOutcome::Compared { new: Vec::new(), fixed: 0 },
+        )];
+        assert_eq!(gate_summary(&clean), "Lint gate: no new problems from clippy.");
+    }
+
+    #[test]
+    fn overlay_copy_tracks_the_workspace_and_keeps_ignored_output() {
+        let root = tempfile::tempdir().expect("root");
+        let overlay = tempfile::tempdir().expect("overlay");
+        std::fs::write(root.path().join(".gitignore"), "target/\n").expect("write");
+        std::fs::create_dir_all(root.path().join("src")).expect("mkdir");
+        std::fs::write(root.path().join("src/lib.rs"), "fn a() {}\n").expect("write");
+        std::fs::create_dir_all(root.path().join("target")).expect("mkdir");
+        std::fs::write(root.path().join("target/big"), "build output").expect("write");
+
+        sync_overlay(root.path(), overlay.path()).expect("sync");
+        assert!(overlay.path().join("src/lib.rs").is_file());
+        assert!(!overlay.path().join("target/big").exists());
+
+        std::fs::create_dir_all(overlay.path().join("target")).expect("mkdir");
+        std::fs::write(overlay.path().join("target/cache"), "kept").expect("write");
+        apply_to_overlay(
+            overlay.path(),
+            &[(PathBuf::from("src/new.rs"), Some("fn b() {}\n".to_string()))],
+        )
+        .expect("apply");
+        sync_overlay(root.path(), overlay.path()).expect("sync");
+        assert!(!overlay.path().join("src/new.rs").exists());
+        assert!(overlay.path().join("target/cache").is_file());
+    }
+}
@@ -0,0 +1,194 @@
+//! Opt-in shell state that carries across exec calls within one session.
+//!
+//! Every exec still spawns its own (sandboxed) process. When persistence is
+//! enabled, POSIX-style `sh -c`/`bash -lc`/`zsh -lc` invocations are wrapped so
+//! that the exported environment and final working directory are written to a
+//! per-session state directory on exit and restored before the next command.
+//! This gives the model the `cd`/`export`/`source venv/bin/activate` behaviour
+//! of a long-lived terminal without bypassing per-command approval or sandboxing.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::Deserialize;
+use serde::Serialize;
+use uuid::Uuid;
+
+const CWD_FILE: &str = "cwd";
+const ENV_FILE: &str = "env";
+
+/// Shell session state reported to the model in the environment context.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShellSessionContext {
+    pub cwd: Option<PathBuf>,
+    /// Names of variables exported by earlier commands in this session.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exported: Vec<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct PersistentShell {
+    state_dir: PathBuf,
+    baseline_env: BTreeSet<String>,
+    context: Mutex<ShellSessionContext>,
+}
+
+impl PersistentShell {
+    pub(crate) fn new(session_id: Uuid) -> std::io::Result<Self> {
+        let state_dir = std::env::temp_dir().join(format!("code-shell-{session_id}"));
+        std::fs::create_dir_all(&state_dir)?;
+        Ok(Self {
+            state_dir,
+            baseline_env: std::env::vars().map(|(name, _)| name).collect(),
+            context: Mutex::new(ShellSessionContext::default()),
+        })
+    }
+
+    /// Working directory left behind by the previous command, if it still exists.
+    pub(crate) fn cwd(&self) -> Option<PathBuf> {
+        let context = self.context.lock().unwrap();
+        context.cwd.clone().filter(|dir| dir.is_dir())
+    }
+
+    pub(crate) fn context(&self) -> Option<ShellSessionContext> {
+        let context = self.context.lock().unwrap();
+        if context.cwd.is_none() && context.exported.is_empty() {
+            None
+        } else {
+            Some(context.clone())
+        }
+    }
+
+    /// Wraps `command` so that it restores and then records shell state. Commands
+    /// that are not a recognised POSIX shell invocation are returned unchanged.
+    pub(crate) fn wrap_command(&self, command: Vec<String>) -> Vec<String> {
+        let [shell, flag, script] = command.as_slice() else {
+            return command;
+        };
+        if !is_posix_shell(shell) || !matches!(flag.as_str(), "-c" | "-lc") {
+            return command;
+        }
+        let Ok(state_dir) = shlex::try_quote(&self.state_dir.to_string_lossy()) else {
+            return command;
+        };
+        let wrapped = format!(
+            "__code_state={state_dir}\n\
+             trap 'pwd > \"$__code_state/{CWD_FILE}\"; export -p > \"$__code_state/{ENV_FILE}\"' EXIT\n\
+             [ -f \"$__code_state/{ENV_FILE}\" ] && . \"$__code_state/{ENV_FILE}\" 2>/dev/null\n\
+             {script}"
+        );
+        vec![shell.clone(), flag.clone(), wrapped]
+    }
+
+    /// Re-reads the state written by the last command. Returns the new working
+    /// directory when it changed.
+    pub(crate) fn refresh(&self) -> Option<PathBuf> {
+        let cwd = std::fs::read_to_string(self.state_dir.join(CWD_FILE))
+            .ok()
+            .map(|raw| PathBuf::from(raw.trim_end_matches(['\n', '\r'])))
+            .filter(|dir| dir.is_absolute());
+        let exported = std::fs::read_to_string(self.state_dir.join(ENV_FILE))
+            .map(|raw| exported_names(&raw, &self.baseline_env))
+            .unwrap_or_default();
+
+        let mut context = self.context.lock().unwrap();
+        let changed = cwd.is_some() && cwd != context.cwd;
+        if cwd.is_some() {
+            context.cwd = cwd;
+        }
+        context.exported = exported;
+        if changed { context.cwd.clone() } else { None }
+    }
+}
+
+impl Drop for PersistentShell {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.state_dir);
+    }
+}
+
+fn is_posix_shell(program: &str) -> bool {
+    let name = Path::new(program)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(program);
+    matches!(name, "sh" | "bash" | "zsh" | "dash")
+}
+
+/// Extracts variable names from `export -p` output (`declare -x NAME=...` in
+/// bash, `export NAME=...` in sh/zsh) that were not present at startup.
+fn exported_names(export_p: &str, baseline: &BTreeSet<String>) -> Vec<String> {
+    let names: BTreeSet<String> = export_p
+        .lines()
+        .filter_map(|line| {
+            let rest = line
+                .strip_prefix("declare -x ")
+                .or_else(|| line.strip_prefix("export "))?;
+            let name = rest.split('=').next()?.trim();
+            let valid = !name.is_empty()
+                && name.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+            valid.then(|| name.to_string())
+        })
+        .filter(|name| !baseline.contains(name))
+        .filter(|name| !matches!(name.as_str(), "OLDPWD" | "PWD" | "SHLVL" | "_"))
+        .collect();
+    names.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_bash_lc_invocations_only() {
+        let shell = PersistentShell::new(Uuid::new_v4()).expect("state dir");
+        let wrapped = shell.wrap_command(vec![
+            "bash".to_string(),
+            "-lc".to_string(),
+            "cd src && ls".to_string(),
+        ]);
+        assert_eq!(wrapped.len(), 3);
+        assert!(wrapped[2].contains("trap '"));
+        assert!(wrapped[2].ends_with("cd src && ls"));
+
+        let direct = vec!["ls".to_string(), "-la".to_string()];
+        assert_eq!(shell.wrap_command(direct.clone()), direct);
+    }
+
+    #[test]
+    fn exported_names_skip_baseline_and_shell_bookkeeping() {
+        let baseline: BTreeSet<String> = ["PATH".to_string()].into_iter().collect();
+        let raw = "declare -x PATH=\"/usr/bin\"\n\
+                   declare -x VIRTUAL_ENV=\"/tmp/venv\"\n\
+                   export FOO='bar'\n\
+                   declare -x OLDPWD\n";
+        assert_eq!(
+            exported_names(raw, &baseline),
+            vec!["FOO".to_string(), "VIRTUAL_ENV".to_string()]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn refresh_picks_up_cwd_and_exports() {
+        let shell = PersistentShell::new(Uuid::new_v4()).expect("state dir");
+        let target = tempfile::tempdir().expect("tempdir");
+        let script = format!("cd {} && export CODE_PERSIST_TEST=1", target.path().display());
+        let command = shell.wrap_command(vec!["sh".to_string(), "-c".to_string(), script]);
+        let status = std::process::Command::new(&command[0])
+            .args(&command[1..])
+            .status()
+            .expect("run sh");
+        assert!(status.success());
+
+        let changed = shell.refresh().expect("cwd change");
+        assert_eq!(
+            changed.canonicalize().unwrap(),
+            target.path().canonicalize().unwrap()
+        );
+        let context = shell.context().expect("context");
+        assert!(context.exported.contains(&"CODE_PERSIST_TEST".to_string()));
+    }
+}
@@ -3,6 +3,7 @@
 //! Uses a SQ (Submission Queue) / EQ (Event Queue) pattern to asynchronously communicate
 //! between user and agent.
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fmt;
 use std::path::Path;
@@ -213,6 +214,35 @@ pub enum Op {
         format: TextFormat,
     },
 
+    /// Run the next turn on `model` instead of the session model. Later
+    /// turns go back to the session model.
+    SetNextTurnModel {
+        model: String,
+    },
+
+    /// Run the last user turn again: its answer is dropped from the model's
+    /// history (the rollout keeps it) and the same input is resent, optionally
+    /// on another model or with another sampling temperature.
+    RetryLastTurn {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        temperature: Option<f64>,
+    },
+
+    /// Replace the facts pinned with `/context pin`. They are sent as a
+    /// developer message with every request until changed.
+    SetPinnedFacts {
+        facts: BTreeMap<String, String>,
+    },
+
+    /// Scope the session to a subproject directory (absolute), or clear the
+    /// scope with `None`. The model is asked to stay inside it.
+    SetScope {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<PathBuf>,
+    },
+
     /// Approve a command execution
     ExecApproval {
         /// The id of the submission we are approving
@@ -289,6 +319,17 @@ pub enum Op {
         name: String,
     },
 
+    /// Re-run a command that previously executed in this session. The command
+    /// is re-assessed against the current approval policy before it runs.
+    RerunCommand {
+        /// Call id to use for the resulting exec events.
+        call_id: String,
+        command: Vec<String>,
+        /// Working directory of the original run; defaults to the session cwd.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cwd: Option<PathBuf>,
+    },
+
     /// Internally queue a developer-role message to be included in the next turn.
     AddPendingInputDeveloper {
         /// The developer message text to add to pending input.
@@ -642,7 +683,11 @@ pub fn event_msg_from_protocol(msg: &code_protocol::protocol::EventMsg) -> Optio
                 .rate_limits
                 .as_ref()
                 .map(rate_limit_snapshot_from_protocol);
-            Some(EventMsg::TokenCount(TokenCountEvent { info, rate_limits }))
+            Some(EventMsg::TokenCount(TokenCountEvent {
+                info,
+                rate_limits,
+                session_cost_usd: None,
+            }))
         }
         _ => {
             let converted = convert_value(msg)?;
@@ -902,6 +947,10 @@ pub enum EventMsg {
     /// Notification that a patch application has finished.
     PatchApplyEnd(PatchApplyEndEvent),
 
+    /// Language server diagnostics for the files an applied patch touched
+    /// (`[lsp]`).
+    LspDiagnostics(LspDiagnosticsEvent),
+
     TurnDiff(TurnDiffEvent),
 
     /// Response to GetHistoryEntryRequest.
@@ -1104,6 +1153,9 @@ pub struct RateLimitSnapshotEvent {
 pub struct TokenCountEvent {
     pub info: Option<TokenUsageInfo>,
     pub rate_limits: Option<RateLimitSnapshotEvent>,
+    /// Estimated session cost in USD; `None` until a priced model answers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_cost_usd: Option<f64>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -1290,6 +1342,9 @@ pub struct ExecCommandEndEvent {
     pub exit_code: i32,
     /// The duration of the command execution.
     pub duration: Duration,
+    /// Secret values masked in the output by `[redaction]`.
+    #[serde(default)]
+    pub redacted_values: usize,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -1386,6 +1441,74 @@ pub struct PatchApplyEndEvent {
     pub success: bool,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LspDiagnosticsEvent {
+    /// The apply_patch call whose files were checked.
+    pub call_id: String,
+    /// One entry per touched file a configured server handles, including
+    /// files without diagnostics.
+    pub files: Vec<LspFileDiagnostics>,
+    /// Servers that could not be started or answered, with the reason.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+impl LspDiagnosticsEvent {
+    /// Errors that were not in their file's diagnostics before the patch.
+    pub fn new_errors(&self) -> impl Iterator<Item = (&LspFileDiagnostics, &LspDiagnostic)> {
+        self.files.iter().flat_map(|file| {
+            file.diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.is_new && diagnostic.severity == DiagnosticSeverity::Error)
+                .map(move |diagnostic| (file, diagnostic))
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LspFileDiagnostics {
+    pub path: PathBuf,
+    /// The `[lsp.servers]` entry that checked the file.
+    pub server: String,
+    pub diagnostics: Vec<LspDiagnostic>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LspDiagnostic {
+    /// 1-based line and column.
+    pub line: u32,
+    pub column: u32,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// Not reported for this file before the patch.
+    #[serde(default)]
+    pub is_new: bool,
+}
+
+impl LspDiagnostic {
+    /// Identity used to tell new diagnostics from old ones. Positions are left
+    /// out because edits above a diagnostic move it.
+    pub fn same_problem(&self, other: &LspDiagnostic) -> bool {
+        self.severity == other.severity
+            && self.message == other.message
+            && self.source == other.source
+            && self.code == other.code
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Information,
+    Hint,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TurnDiffEvent {
     pub unified_diff: String,
@@ -1567,6 +1690,10 @@ pub struct BrowserSnapshotEvent {
     /// Timestamp when the snapshot was captured.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub captured_at: Option<String>,
+    /// Screenshot file captured with the snapshot, for the browser overlay's
+    /// timeline. Not part of the model-facing snapshot JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub screenshot_path: Option<PathBuf>,
 }
 
 #[cfg(test)]
@@ -0,0 +1,289 @@
+//! Repository map: the file tree plus a symbol outline of each source file,
+//! parsed with tree-sitter. `/map` and `[repo_map] auto` hand it to the model
+//! so it can find its way around a large repository without files being
+//! pasted in by hand.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use ignore::WalkBuilder;
+use tree_sitter::Language;
+use tree_sitter::Node;
+use tree_sitter::Parser;
+
+/// Token budget used when `[repo_map] max_tokens` is unset.
+pub const DEFAULT_REPO_MAP_MAX_TOKENS: usize = 4096;
+
+/// Files larger than this are listed but not outlined.
+const MAX_OUTLINE_FILE_BYTES: u64 = 512 * 1024;
+/// Upper bound on files walked, so huge trees stay cheap to map.
+const MAX_WALKED_FILES: usize = 20_000;
+/// How deep nested symbols (methods in impls/classes) are listed.
+const MAX_SYMBOL_DEPTH: usize = 2;
+
+const REPO_MAP_OPEN_TAG: &str = "<repo_map>";
+const REPO_MAP_CLOSE_TAG: &str = "</repo_map>";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoMap {
+    /// One line per file (relative path) followed by its indented symbols.
+    pub text: String,
+    /// Files listed in `text`.
+    pub files: usize,
+    /// Symbols listed in `text`.
+    pub symbols: usize,
+    /// Files left out because the budget ran out.
+    pub omitted_files: usize,
+}
+
+impl RepoMap {
+    /// Developer message carrying the map into the model's context.
+    pub fn to_context_message(&self) -> String {
+        let mut message = format!(
+            "{REPO_MAP_OPEN_TAG}\nFiles in this repository with the symbols they define. Use it to decide what to open; read a file before editing it.\n\n{}",
+            self.text
+        );
+        if self.omitted_files > 0 {
+            message.push_str(&format!("… {} more files not shown\n", self.omitted_files));
+        }
+        message.push_str(REPO_MAP_CLOSE_TAG);
+        message
+    }
+}
+
+/// Maps the files under `root` (honouring `.gitignore`), stopping once the
+/// text reaches roughly `max_tokens` tokens. Files keep their outline while it
+/// fits; after that they are listed by path only.
+pub fn build_repo_map(root: &Path, max_tokens: usize) -> RepoMap {
+    let budget = max_tokens.saturating_mul(4);
+    let mut map = RepoMap {
+        text: String::new(),
+        files: 0,
+        symbols: 0,
+        omitted_files: 0,
+    };
+    let mut outlines_fit = true;
+    let paths = walk_files(root);
+    for (idx, path) in paths.iter().enumerate() {
+        let relative = path.strip_prefix(root).unwrap_or(path).display().to_string();
+        let mut entry = format!("{relative}\n");
+        let mut symbols = 0;
+        if outlines_fit && let Some(outline) = outline_file(path) {
+            let mut with_outline = entry.clone();
+            for line in &outline {
+                with_outline.push_str("  ");
+                with_outline.push_str(line);
+                with_outline.push('\n');
+            }
+            if map.text.len() + with_outline.len() <= budget {
+                entry = with_outline;
+                symbols = outline.len();
+            } else {
+                outlines_fit = false;
+            }
+        }
+        if map.text.len() + entry.len() > budget {
+            map.omitted_files = paths.len() - idx;
+            break;
+        }
+        map.text.push_str(&entry);
+        map.files += 1;
+        map.symbols += symbols;
+    }
+    map
+}
+
+fn walk_files(root: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = WalkBuilder::new(root)
+        .hidden(true)
+        .require_git(false)
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_some_and(|ft| ft.is_file()))
+        .map(ignore::DirEntry::into_path)
+        .take(MAX_WALKED_FILES)
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SourceLanguage {
+    Rust,
+    Python,
+    JavaScript,
+    TypeScript,
+    Tsx,
+    Go,
+}
+
+impl SourceLanguage {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "rs" => Some(Self::Rust),
+            "py" | "pyi" => Some(Self::Python),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            "go" => Some(Self::Go),
+            _ => None,
+        }
+    }
+
+    fn grammar(self) -> Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+        }
+    }
+}
+
+/// Symbol outline of one file, or `None` for unsupported, oversized or
+/// unparsable files.
+fn outline_file(path: &Path) -> Option<Vec<String>> {
+    let language = SourceLanguage::from_path(path)?;
+    if std::fs::metadata(path).ok()?.len() > MAX_OUTLINE_FILE_BYTES {
+        return None;
+    }
+    let source = std::fs::read_to_string(path).ok()?;
+    outline_source(&source, language)
+}
+
+fn outline_source(source: &str, language: SourceLanguage) -> Option<Vec<String>> {
+    let mut parser = Parser::new();
+    parser.set_language(&language.grammar()).ok()?;
+    let tree = parser.parse(source, None)?;
+    let mut lines = Vec::new();
+    collect_symbols(tree.root_node(), source, language, 0, &mut lines);
+    Some(lines)
+}
+
+fn collect_symbols(
+    node: Node<'_>,
+    source: &str,
+    language: SourceLanguage,
+    depth: usize,
+    lines: &mut Vec<String>,
+) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if let Some(label) = symbol_label(child, source, language) {
+            lines.push(format!("{}{label}", "  ".repeat(depth)));
+            if depth + 1 < MAX_SYMBOL_DEPTH
+                && let Some(body) = child.child_by_field_name("body")
+            {
+                collect_symbols(body, source, language, depth + 1, lines);
+            }
+        } else if is_transparent(child.kind()) {
+            collect_symbols(child, source, language, depth, lines);
+        }
+    }
+}
+
+/// Wrapper nodes whose children are listed as if they were siblings.
+fn is_transparent(kind: &str) -> bool {
+    matches!(
+        kind,
+        "decorated_definition" | "export_statement" | "declaration_list" | "type_declaration"
+    )
+}
+
+fn symbol_label(node: Node<'_>, source: &str, language: SourceLanguage) -> Option<String> {
+    let field = |name: &str| {
+        node.child_by_field_name(name)
+            .and_then(|child| child.utf8_text(source.as_bytes()).ok())
+            .map(|text| text.split_whitespace().collect::<Vec<_>>().join(" "))
+    };
+    let named = |keyword: &str| field("name").map(|name| format!("{keyword} {name}"));
+    match (language, node.kind()) {
+        (SourceLanguage::Rust, "function_item" | "function_signature_item") => named("fn"),
+        (SourceLanguage::Rust, "struct_item") => named("struct"),
+        (SourceLanguage::Rust, "enum_item") => named("enum"),
+        (SourceLanguage::Rust, "union_item") => named("union"),
+        (SourceLanguage::Rust, "trait_item") => named("trait"),
+        (SourceLanguage::Rust, "type_item") => named("type"),
+        (SourceLanguage::Rust, "mod_item") => named("mod"),
+        (SourceLanguage::Rust, "const_item") => named("const"),
+        (SourceLanguage::Rust, "static_item") => named("static"),
+        (SourceLanguage::Rust, "macro_definition") => named("macro_rules!"),
+        (SourceLanguage::Rust, "impl_item") => {
+            let ty = field("type")?;
+            Some(match field("trait") {
+                Some(trait_name) => format!("impl {trait_name} for {ty}"),
+                None => format!("impl {ty}"),
+            })
+        }
+        (SourceLanguage::Python, "function_definition") => named("def"),
+        (SourceLanguage::Python, "class_definition") => named("class"),
+        (
+            SourceLanguage::JavaScript | SourceLanguage::TypeScript | SourceLanguage::Tsx,
+            kind,
+        ) => match kind {
+            "function_declaration" | "generator_function_declaration" => named("function"),
+            "class_declaration" | "abstract_class_declaration" => named("class"),
+            "method_definition" => named("method"),
+            "interface_declaration" => named("interface"),
+            "type_alias_declaration" => named("type"),
+            "enum_declaration" => named("enum"),
+            _ => None,
+        },
+        (SourceLanguage::Go, "function_declaration") => named("func"),
+        (SourceLanguage::Go, "method_declaration") => {
+            let receiver = field("receiver")?;
+            named(&format!("func {receiver}"))
+        }
+        (SourceLanguage::Go, "type_spec") => named("type"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn outlines_nested_rust_and_python_symbols() {
+        let rust = "pub struct Config;\nimpl Config {\n    pub fn new() -> Self { Config }\n}\nfn helper() {}\n";
+        assert_eq!(
+            outline_source(rust, SourceLanguage::Rust),
+            Some(vec![
+                "struct Config".to_string(),
+                "impl Config".to_string(),
+                "  fn new".to_string(),
+                "fn helper".to_string(),
+            ])
+        );
+
+        let python = "@dataclass\nclass Job:\n    def run(self):\n        pass\n\ndef main():\n    pass\n";
+        assert_eq!(
+            outline_source(python, SourceLanguage::Python),
+            Some(vec![
+                "class Job".to_string(),
+                "  def run".to_string(),
+                "def main".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn map_lists_paths_once_the_budget_runs_out() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let root = temp.path();
+        std::fs::create_dir(root.join("src")).expect("mkdir");
+        std::fs::write(root.join("src/lib.rs"), "pub fn run() {}\n").expect("write");
+        std::fs::write(root.join("README.md"), "docs\n").expect("write");
+
+        let map = build_repo_map(root, DEFAULT_REPO_MAP_MAX_TOKENS);
+        assert_eq!(map.text, "README.md\nsrc/lib.rs\n  fn run\n");
+        assert_eq!((map.files, map.symbols, map.omitted_files), (2, 1, 0));
+
+        let tight = build_repo_map(root, 3);
+        assert_eq!(tight.text, "README.md\n");
+        assert_eq!(tight.omitted_files, 1);
+    }
+}
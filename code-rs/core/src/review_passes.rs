@@ -0,0 +1,249 @@
+//! Specialized `/review` passes (`[[review_passes]]`). After the main pass
+//! the reviewer looks at the same changes once more per configured pass.
+//! Each pass's findings are tagged with the pass name (`[security] …`) and
+//! merged into a single [`ReviewOutputEvent`], so the review stays one event
+//! and the TUI can split it back into per-pass sections.
+
+use crate::config_types::ReviewPassConfig;
+use crate::protocol::ReviewFinding;
+use crate::protocol::ReviewOutputEvent;
+
+const SECURITY_PASS_PROMPT: &str = "Focus on security: injection, unsafe deserialization, path traversal, missing authentication or authorization checks, secrets in code or logs, and unsafe handling of untrusted input.";
+const PERFORMANCE_PASS_PROMPT: &str = "Focus on performance: accidental quadratic work, blocking calls on async paths, unbounded allocations or queues, redundant I/O and work repeated inside hot loops.";
+const TESTS_PASS_PROMPT: &str = "Focus on tests: changed behavior without test coverage, tests that cannot fail, missing edge cases and assertions that no longer match the code.";
+
+/// What a pass looks for: its configured prompt, the built-in prompt for
+/// `security`, `performance` and `tests`, or a generic focus on its name.
+pub fn review_pass_focus(pass: &ReviewPassConfig) -> String {
+    if let Some(prompt) = pass.prompt.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        return prompt.to_string();
+    }
+    match pass.name.trim().to_ascii_lowercase().as_str() {
+        "security" => SECURITY_PASS_PROMPT.to_string(),
+        "performance" | "perf" => PERFORMANCE_PASS_PROMPT.to_string(),
+        "tests" | "testing" => TESTS_PASS_PROMPT.to_string(),
+        other => format!("Focus on {other} issues."),
+    }
+}
+
+/// The message that starts `pass` after the previous pass has answered.
+pub fn review_pass_request(pass: &ReviewPassConfig) -> String {
+    let name = pass.name.trim();
+    let focus = review_pass_focus(pass);
+    format!(
+        "Now run the `{name}` review pass over the same changes. {focus} Report only findings that belong to this pass, using the same JSON output format as before. Return an empty findings list if there are none."
+    )
+}
+
+/// Prefixes `text` with the pass tag, e.g. `[security] [P1] Title`.
+fn tag_with_pass(name: &str, text: &str) -> String {
+    format!("[{name}] {text}")
+}
+
+/// Splits a leading pass tag off `text`. Priority markers such as `[P1]`
+/// are not pass tags.
+pub fn split_pass_tag(text: &str) -> (Option<&str>, &str) {
+    let Some(rest) = text.strip_prefix('[') else {
+        return (None, text);
+    };
+    let Some((tag, rest)) = rest.split_once(']') else {
+        return (None, text);
+    };
+    let is_priority = tag
+        .strip_prefix('P')
+        .is_some_and(|digits| !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()));
+    if tag.is_empty() || is_priority || tag.contains(char::is_whitespace) {
+        return (None, text);
+    }
+    (Some(tag), rest.trim_start())
+}
+
+fn same_finding(a: &ReviewFinding, b: &ReviewFinding) -> bool {
+    a.code_location == b.code_location
+        && split_pass_tag(&a.title).1.trim().eq_ignore_ascii_case(split_pass_tag(&b.title).1.trim())
+}
+
+/// Merges the main pass (named `None`) and the specialized passes into one
+/// review. Findings a later pass repeats are dropped; the verdict is the
+/// first "incorrect" one and the confidence the lowest reported.
+pub fn merge_review_passes(outputs: Vec<(Option<String>, ReviewOutputEvent)>) -> Option<ReviewOutputEvent> {
+    if outputs.len() <= 1 && outputs.first().is_none_or(|(name, _)| name.is_none()) {
+        return outputs.into_iter().next().map(|(_, output)| output);
+    }
+
+    let mut merged = ReviewOutputEvent::default();
+    let mut explanations: Vec<String> = Vec::new();
+    for (name, output) in outputs {
+        let explanation = output.overall_explanation.trim();
+        match name.as_deref() {
+            None => {
+                if !explanation.is_empty() {
+                    explanations.push(explanation.to_string());
+                }
+            }
+            Some(name) => {
+                let explanation = if explanation.is_empty() {
+                    if output.findings.is_empty() { "No issues found." } else { "" }
+                } else {
+                    explanation
+                };
+                if !explanation.is_empty() {
+                    explanations.push(tag_with_pass(name, explanation));
+                }
+            }
+        }
+        for mut finding in output.findings {
+            if merged.findings.iter().any(|seen| same_finding(seen, &finding)) {
+                continue;
+            }
+            if let Some(name) = name.as_deref() {
+                finding.title = tag_with_pass(name, &finding.title);
+            }
+            merged.findings.push(finding);
+        }
+        let correctness = output.overall_correctness.trim();
+        let merged_incorrect = merged.overall_correctness.to_ascii_lowercase().contains("incorrect");
+        if merged.overall_correctness.is_empty()
+            || (!merged_incorrect && correctness.to_ascii_lowercase().contains("incorrect"))
+        {
+            merged.overall_correctness = correctness.to_string();
+        }
+        let score = output.overall_confidence_score;
+        if score > 0.0
+            && (merged.overall_confidence_score == 0.0 || score < merged.overall_confidence_score)
+        {
+            merged.overall_confidence_score = score;
+        }
+    }
+    merged.overall_explanation = explanations.join("\n\n");
+    Some(merged)
+}
+
+/// One pass of a merged review, as split back out by [`split_review_passes`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewPassSection {
+    /// `None` for the main pass.
+    pub name: Option<String>,
+    pub explanation: String,
+    /// Findings with the pass tag removed from their titles.
+    pub findings: Vec<ReviewFinding>,
+}
+
+/// Splits a review produced by [`merge_review_passes`] into its passes,
+/// main pass first. A review without pass tags yields just the main pass.
+pub fn split_review_passes(output: &ReviewOutputEvent) -> Vec<ReviewPassSection> {
+    let mut sections = vec![ReviewPassSection {
+        name: None,
+        explanation: String::new(),
+        findings: Vec::new(),
+    }];
+    fn section_for<'a>(sections: &'a mut Vec<ReviewPassSection>, name: Option<&str>) -> &'a mut ReviewPassSection {
+        let idx = match sections.iter().position(|section| section.name.as_deref() == name) {
+            Some(idx) => idx,
+            None => {
+                sections.push(ReviewPassSection {
+                    name: name.map(str::to_string),
+                    explanation: String::new(),
+                    findings: Vec::new(),
+                });
+                sections.len() - 1
+            }
+        };
+        &mut sections[idx]
+    }
+
+    let mut current: Option<String> = None;
+    for paragraph in output.overall_explanation.split("\n\n") {
+        let (tag, text) = split_pass_tag(paragraph);
+        if let Some(tag) = tag {
+            current = Some(tag.to_string());
+        }
+        let section = section_for(&mut sections, current.as_deref());
+        if !section.explanation.is_empty() {
+            section.explanation.push_str("\n\n");
+        }
+        section.explanation.push_str(text.trim());
+    }
+    for finding in &output.findings {
+        let (tag, title) = split_pass_tag(&finding.title);
+        let mut finding = finding.clone();
+        finding.title = title.to_string();
+        section_for(&mut sections, tag).findings.push(finding);
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::ReviewCodeLocation;
+    use crate::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn finding(title: &str, line: u32) -> ReviewFinding {
+        ReviewFinding {
+            title: title.to_string(),
+            body: String::new(),
+            confidence_score: 0.8,
+            priority: 1,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: PathBuf::from("/repo/src/lib.rs"),
+                line_range: ReviewLineRange { start: line, end: line },
+            },
+        }
+    }
+
+    fn output(explanation: &str, findings: Vec<ReviewFinding>, score: f32) -> ReviewOutputEvent {
+        ReviewOutputEvent {
+            findings,
+            overall_correctness: "patch is correct".to_string(),
+            overall_explanation: explanation.to_string(),
+            overall_confidence_score: score,
+        }
+    }
+
+    #[test]
+    fn merges_passes_and_splits_them_back() {
+        let mut security = output("Input reaches a shell.", vec![
+            finding("[P1] Unescaped argument", 10),
+            finding("[P1] Off-by-one in loop", 4),
+        ], 0.6);
+        security.overall_correctness = "patch is incorrect".to_string();
+        let merged = merge_review_passes(vec![
+            (None, output("Looks mostly fine.", vec![finding("[P1] Off-by-one in loop", 4)], 0.9)),
+            (Some("security".to_string()), security),
+            (Some("tests".to_string()), output("", Vec::new(), 0.0)),
+        ])
+        .expect("merged review");
+
+        let titles: Vec<&str> = merged.findings.iter().map(|f| f.title.as_str()).collect();
+        assert_eq!(titles, vec!["[P1] Off-by-one in loop", "[security] [P1] Unescaped argument"]);
+        assert_eq!(merged.overall_correctness, "patch is incorrect");
+        assert_eq!(merged.overall_confidence_score, 0.6);
+
+        let sections = split_review_passes(&merged);
+        let summary: Vec<(Option<&str>, &str, usize)> = sections
+            .iter()
+            .map(|s| (s.name.as_deref(), s.explanation.as_str(), s.findings.len()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (None, "Looks mostly fine.", 1),
+                (Some("security"), "Input reaches a shell.", 1),
+                (Some("tests"), "No issues found.", 0),
+            ]
+        );
+        assert_eq!(sections[1].findings[0].title, "[P1] Unescaped argument");
+    }
+
+    #[test]
+    fn single_pass_reviews_are_unchanged() {
+        let main = output("Fine.", vec![finding("[P2] Nit", 3)], 0.9);
+        assert_eq!(merge_review_passes(vec![(None, main.clone())]), Some(main));
+        assert_eq!(merge_review_passes(Vec::new()), None);
+        assert_eq!(split_pass_tag("[P0] Crash"), (None, "[P0] Crash"));
+        assert_eq!(split_pass_tag("[perf] Slow"), (Some("perf"), "Slow"));
+    }
+}
@@ -7,15 +7,16 @@ use std::io::{self, BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
 use code_protocol::models::{ContentItem, ResponseItem};
-use code_protocol::protocol::{RolloutItem, RolloutLine, SessionSource};
+use code_protocol::protocol::{EventMsg, RolloutItem, RolloutLine, SessionSource};
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 use uuid::Uuid;
 
 use super::ARCHIVED_SESSIONS_SUBDIR;
+use super::history_store::SessionCounts;
 use super::SESSIONS_SUBDIR;
 
-const INDEX_SUBDIR: &str = "sessions/index";
+pub(super) const INDEX_SUBDIR: &str = "sessions/index";
 const CATALOG_FILENAME: &str = "catalog.jsonl";
 
 /// Canonical entry in the session catalog index.
@@ -89,6 +90,10 @@ pub struct SessionIndexEntry {
     /// Whether session is marked as deleted
     #[serde(default)]
     pub deleted: bool,
+
+    /// Provider-reported tokens summed over every request in the session
+    #[serde(default)]
+    pub total_tokens: u64,
 }
 
 impl SessionIndexEntry {
@@ -300,6 +305,17 @@ impl SessionCatalog {
     /// that are newer or missing from the catalog.
     #[allow(dead_code)]
     pub async fn reconcile(&mut self, code_home: &Path) -> io::Result<ReconcileResult> {
+        self.reconcile_with_progress(code_home, &mut |_| {}).await
+    }
+
+    /// Like [`Self::reconcile`], but hands each rollout file's entry to
+    /// `on_parsed` as soon as it has been parsed, before the catalog itself
+    /// is updated. Entries arrive in no particular order.
+    pub async fn reconcile_with_progress(
+        &mut self,
+        code_home: &Path,
+        on_parsed: &mut (dyn FnMut(&SessionIndexEntry) + Send),
+    ) -> io::Result<ReconcileResult> {
         let sessions_root = code_home.join(SESSIONS_SUBDIR);
         let archived_root = code_home.join(ARCHIVED_SESSIONS_SUBDIR);
 
@@ -308,9 +324,9 @@ impl SessionCatalog {
         }
 
         let mut result = ReconcileResult::default();
-        let discovered_entries = scan_rollout_files(&sessions_root, false).await?;
+        let discovered_entries = scan_rollout_files(&sessions_root, false, on_parsed).await?;
         let mut discovered_entries = if archived_root.exists() {
-            let archived_entries = scan_rollout_files(&archived_root, true).await?;
+            let archived_entries = scan_rollout_files(&archived_root, true, on_parsed).await?;
             merge_discovered_entries(discovered_entries, archived_entries)
         } else {
             discovered_entries
@@ -385,15 +401,17 @@ pub struct ReconcileResult {
 }
 
 /// Scan all rollout files under the sessions directory and build index entries.
-#[allow(dead_code)]
+/// Files are parsed concurrently, at most [`parse_concurrency`] at a time.
 async fn scan_rollout_files(
     sessions_root: &Path,
     archived: bool,
+    on_parsed: &mut (dyn FnMut(&SessionIndexEntry) + Send),
 ) -> io::Result<HashMap<Uuid, SessionIndexEntry>> {
     use tokio::fs;
+    use tokio::task::JoinSet;
 
     let mut queue = vec![sessions_root.to_path_buf()];
-    let mut discovered = HashMap::new();
+    let mut rollout_paths = Vec::new();
 
     while let Some(dir) = queue.pop() {
         let mut read_dir = fs::read_dir(&dir).await?;
@@ -408,25 +426,64 @@ async fn scan_rollout_files(
                 && let Some(name) = path.file_name().and_then(|n| n.to_str())
                 && name.ends_with(".jsonl")
                 && name.starts_with("rollout-")
-                && let Some(index_entry) = parse_rollout_file(&path, sessions_root, archived).await
             {
-                match discovered.get(&index_entry.session_id) {
-                    Some(existing) => {
-                        if should_replace(existing, &index_entry) {
-                            discovered.insert(index_entry.session_id, index_entry);
-                        }
-                    }
-                    None => {
-                        discovered.insert(index_entry.session_id, index_entry);
-                    }
+                rollout_paths.push(path);
+            }
+        }
+    }
+
+    // Newest first (file names start with the creation timestamp), so callers
+    // streaming progress see recent sessions before old ones.
+    rollout_paths.sort_unstable_by(|a, b| b.file_name().cmp(&a.file_name()));
+
+    let limit = parse_concurrency();
+    let mut pending = rollout_paths.into_iter();
+    let mut workers = JoinSet::new();
+    let mut discovered: HashMap<Uuid, SessionIndexEntry> = HashMap::new();
+
+    loop {
+        while workers.len() < limit
+            && let Some(path) = pending.next()
+        {
+            let sessions_root = sessions_root.to_path_buf();
+            workers.spawn(async move { parse_rollout_file(&path, &sessions_root, archived).await });
+        }
+        let Some(joined) = workers.join_next().await else {
+            break;
+        };
+        let index_entry = match joined {
+            Ok(Some(index_entry)) => index_entry,
+            Ok(None) => continue,
+            Err(e) => {
+                warn!("Rollout parse task failed: {e}");
+                continue;
+            }
+        };
+        on_parsed(&index_entry);
+        match discovered.get(&index_entry.session_id) {
+            Some(existing) => {
+                if should_replace(existing, &index_entry) {
+                    discovered.insert(index_entry.session_id, index_entry);
                 }
             }
+            None => {
+                discovered.insert(index_entry.session_id, index_entry);
+            }
         }
     }
 
     Ok(discovered)
 }
 
+/// Number of rollout files parsed at once during a scan.
+fn parse_concurrency() -> usize {
+    const MAX_PARSE_WORKERS: usize = 8;
+    std::thread::available_parallelism()
+        .map(std::num::NonZeroUsize::get)
+        .unwrap_or(1)
+        .clamp(2, MAX_PARSE_WORKERS)
+}
+
 /// Parse a rollout file and extract catalog entry information.
 async fn parse_rollout_file(
     path: &Path,
@@ -456,6 +513,7 @@ async fn parse_rollout_file(
     let mut message_count = 0usize;
     let mut user_message_count = 0usize;
     let mut last_user_snippet: Option<String> = None;
+    let mut total_tokens = 0u64;
 
     // Parse the file line by line
     while let Some(line) = reader.next_line().await.ok().flatten() {
@@ -504,15 +562,17 @@ async fn parse_rollout_file(
                         }
                     }
             }
-            RolloutItem::Event(_event) => {
+            RolloutItem::Event(event) => {
                 // Event lines record internal state changes (tool output, approvals, etc.).
                 // They are not a reliable indicator of user-submitted turns, so avoid
                 // counting them toward `user_message_count` to keep resume filters strict.
                 message_count += 1;
+                total_tokens += reported_tokens(&event.msg);
             }
-            RolloutItem::EventMsg(_event) => {
+            RolloutItem::EventMsg(event) => {
                 // Same accounting as RolloutItem::Event.
                 message_count += 1;
+                total_tokens += reported_tokens(&event);
             }
             RolloutItem::Compacted(_) | RolloutItem::TurnContext(_) => {
                 message_count += 1;
@@ -567,6 +627,7 @@ async fn parse_rollout_file(
         sync_version: 0,
         archived,
         deleted: false,
+        total_tokens,
     })
 }
 
@@ -612,6 +673,19 @@ pub async fn update_catalog_entry(
     rollout_path: &Path,
     session_id: Uuid,
     last_timestamp: &str,
+) -> io::Result<()> {
+    update_catalog_entry_with_counts(code_home, rollout_path, session_id, last_timestamp, None)
+        .await
+}
+
+/// Same as [`update_catalog_entry`], but takes counters already maintained by
+/// the SQLite history store so existing entries skip re-parsing the rollout.
+pub(crate) async fn update_catalog_entry_with_counts(
+    code_home: &Path,
+    rollout_path: &Path,
+    session_id: Uuid,
+    last_timestamp: &str,
+    counts: Option<SessionCounts>,
 ) -> io::Result<()> {
     let mut catalog = SessionCatalog::load(code_home)?;
 
@@ -627,11 +701,21 @@ pub async fn update_catalog_entry(
         if let Some(mut entry) = catalog.entries.remove(&session_id) {
             entry.last_event_at = last_timestamp.to_string();
 
+            if let Some(counts) = counts {
+                entry.message_count = counts.message_count;
+                entry.user_message_count = counts.user_message_count;
+                entry.last_user_snippet = counts.last_user_snippet;
+                entry.total_tokens = counts.total_tokens;
+                catalog.upsert(entry)?;
+                return Ok(());
+            }
+
             // Re-parse file to update message count and snippet
             if let Some(updated) = parse_rollout_file(rollout_path, parse_root, archived).await {
                 entry.message_count = updated.message_count;
                 entry.user_message_count = updated.user_message_count;
                 entry.last_user_snippet = updated.last_user_snippet;
+                entry.total_tokens = updated.total_tokens;
                 entry.archived = updated.archived;
                 entry.rollout_path = updated.rollout_path;
                 entry.snapshot_path = updated.snapshot_path;
@@ -648,7 +732,19 @@ pub async fn update_catalog_entry(
     Ok(())
 }
 
-fn snippet_from_content(content: &[ContentItem]) -> Option<String> {
+/// Tokens reported by one `TokenCount` event for its request.
+pub(super) fn reported_tokens(msg: &EventMsg) -> u64 {
+    match msg {
+        EventMsg::TokenCount(count) => count
+            .info
+            .as_ref()
+            .map(|info| info.last_token_usage.total_tokens.max(0) as u64)
+            .unwrap_or(0),
+        _ => 0,
+    }
+}
+
+pub(super) fn snippet_from_content(content: &[ContentItem]) -> Option<String> {
     content.iter().find_map(|item| match item {
         ContentItem::InputText { text }
         | ContentItem::OutputText { text } => Some(truncate_snippet(text)),
@@ -660,7 +756,7 @@ fn truncate_snippet(text: &str) -> String {
     text.chars().take(100).collect()
 }
 
-fn is_system_status_snippet(text: &str) -> bool {
+pub(super) fn is_system_status_snippet(text: &str) -> bool {
     text.starts_with("== System Status ==")
 }
 
@@ -691,6 +787,7 @@ mod tests {
             sync_version: 0,
             archived: false,
             deleted: false,
+            total_tokens: 0,
         };
 
         let entry2 = SessionIndexEntry {
@@ -727,6 +824,7 @@ mod tests {
             sync_version: 0,
             archived: false,
             deleted: false,
+            total_tokens: 0,
         };
 
         // Create and save catalog
@@ -766,6 +864,7 @@ mod tests {
             sync_version: 0,
             archived: false,
             deleted: false,
+            total_tokens: 0,
         };
 
         let mut catalog = SessionCatalog::load(code_home)?;
@@ -807,6 +906,7 @@ mod tests {
             sync_version: 0,
             archived: false,
             deleted: false,
+            total_tokens: 0,
         };
 
         let entry2 = SessionIndexEntry {
@@ -858,6 +958,7 @@ mod tests {
             sync_version: 0,
             archived: false,
             deleted: false,
+            total_tokens: 0,
         };
 
         let mut catalog = SessionCatalog::load(code_home)?;
@@ -908,6 +1009,7 @@ mod tests {
             sync_version: 0,
             archived: false,
             deleted: false,
+            total_tokens: 0,
         };
 
         let mut catalog = SessionCatalog::load(code_home)?;
@@ -952,6 +1054,7 @@ mod tests {
             sync_version: 0,
             archived: false,
             deleted: false,
+            total_tokens: 0,
         };
 
         let entry2 = SessionIndexEntry {
@@ -1013,6 +1116,7 @@ mod tests {
             sync_version: 0,
             archived: false,
             deleted: false,
+            total_tokens: 0,
         };
 
         let mut catalog = SessionCatalog::load(code_home)?;
@@ -1091,6 +1195,7 @@ mod tests {
             sync_version: 0,
             archived: false,
             deleted: false,
+            total_tokens: 0,
         };
 
         let mut catalog = SessionCatalog::load(code_home)?;
@@ -0,0 +1,614 @@
+//! Optional SQLite mirror of rollout files (`[history] store = "sqlite"`).
+//!
+//! JSONL rollouts stay the source of truth for resume. The store keeps one row
+//! per rollout line plus extracted exec metadata and token usage so catalog
+//! updates, search, and stats do not have to re-parse whole rollout files.
+
+use std::fs;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::path::Path;
+use std::path::PathBuf;
+
+use code_protocol::models::ContentItem;
+use code_protocol::models::ResponseItem;
+use code_protocol::protocol::EventMsg;
+use code_protocol::protocol::RolloutItem;
+use code_protocol::protocol::RolloutLine;
+use rusqlite::Connection;
+use rusqlite::OptionalExtension;
+use rusqlite::params;
+use uuid::Uuid;
+
+use super::ARCHIVED_SESSIONS_SUBDIR;
+use super::SESSIONS_SUBDIR;
+use super::catalog::INDEX_SUBDIR;
+use super::catalog::is_system_status_snippet;
+use super::catalog::snippet_from_content;
+
+const HISTORY_DB_FILENAME: &str = "history.sqlite";
+const SCHEMA_VERSION: i64 = 1;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS sessions (
+    session_id TEXT PRIMARY KEY,
+    rollout_path TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    last_event_at TEXT NOT NULL,
+    cwd TEXT NOT NULL,
+    git_branch TEXT,
+    source TEXT NOT NULL,
+    message_count INTEGER NOT NULL DEFAULT 0,
+    user_message_count INTEGER NOT NULL DEFAULT 0,
+    last_user_snippet TEXT
+);
+CREATE TABLE IF NOT EXISTS records (
+    session_id TEXT NOT NULL,
+    seq INTEGER NOT NULL,
+    timestamp TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    request_ordinal INTEGER,
+    output_index INTEGER,
+    sequence_number INTEGER,
+    text TEXT,
+    payload TEXT NOT NULL,
+    PRIMARY KEY (session_id, seq)
+);
+CREATE INDEX IF NOT EXISTS records_by_timestamp ON records (timestamp);
+CREATE TABLE IF NOT EXISTS exec_commands (
+    session_id TEXT NOT NULL,
+    call_id TEXT NOT NULL,
+    timestamp TEXT NOT NULL,
+    command TEXT NOT NULL,
+    cwd TEXT NOT NULL,
+    exit_code INTEGER NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    PRIMARY KEY (session_id, call_id)
+);
+CREATE TABLE IF NOT EXISTS token_usage (
+    session_id TEXT NOT NULL,
+    seq INTEGER NOT NULL,
+    timestamp TEXT NOT NULL,
+    input_tokens INTEGER NOT NULL,
+    cached_input_tokens INTEGER NOT NULL,
+    output_tokens INTEGER NOT NULL,
+    reasoning_output_tokens INTEGER NOT NULL,
+    total_tokens INTEGER NOT NULL,
+    PRIMARY KEY (session_id, seq)
+);
+";
+
+/// Catalog counters maintained incrementally as lines are appended.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionCounts {
+    pub message_count: usize,
+    pub user_message_count: usize,
+    pub last_user_snippet: Option<String>,
+    pub total_tokens: u64,
+}
+
+/// A record whose text matched a search query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistorySearchHit {
+    pub session_id: Uuid,
+    pub timestamp: String,
+    pub kind: String,
+    pub text: String,
+}
+
+/// Aggregates over one session or the whole store.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HistoryStats {
+    pub sessions: u64,
+    pub records: u64,
+    pub exec_commands: u64,
+    pub failed_exec_commands: u64,
+    pub input_tokens: i64,
+    pub cached_input_tokens: i64,
+    pub output_tokens: i64,
+    pub total_tokens: i64,
+}
+
+/// Outcome of importing existing rollout files into the store.
+#[derive(Debug, Default)]
+pub struct MigrationReport {
+    pub imported: usize,
+    pub unchanged: usize,
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+pub struct HistoryStore {
+    conn: Connection,
+    code_home: PathBuf,
+}
+
+impl HistoryStore {
+    /// Opens (creating if needed) `sessions/index/history.sqlite` under `code_home`.
+    pub fn open(code_home: &Path) -> io::Result<Self> {
+        let dir = code_home.join(INDEX_SUBDIR);
+        fs::create_dir_all(&dir)?;
+        let conn = Connection::open(dir.join(HISTORY_DB_FILENAME)).map_err(sql_err)?;
+        conn.pragma_update(None, "journal_mode", "WAL")
+            .map_err(sql_err)?;
+        conn.execute_batch(SCHEMA).map_err(sql_err)?;
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)
+            .map_err(sql_err)?;
+        Ok(Self {
+            conn,
+            code_home: code_home.to_path_buf(),
+        })
+    }
+
+    /// Appends freshly written rollout lines for `session_id`. The session row
+    /// is created from the `SessionMeta` line, so the first batch must include it.
+    pub fn append(
+        &mut self,
+        session_id: Uuid,
+        rollout_path: &Path,
+        lines: &[RolloutLine],
+    ) -> io::Result<()> {
+        if lines.is_empty() {
+            return Ok(());
+        }
+        let relative = rollout_path
+            .strip_prefix(&self.code_home)
+            .unwrap_or(rollout_path)
+            .to_string_lossy()
+            .to_string();
+        let id = session_id.to_string();
+        let tx = self.conn.transaction().map_err(sql_err)?;
+        let mut seq: i64 = tx
+            .query_row(
+                "SELECT COALESCE(MAX(seq) + 1, 0) FROM records WHERE session_id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .map_err(sql_err)?;
+        let mut counts = load_counts(&tx, &id).map_err(sql_err)?.unwrap_or_default();
+
+        for line in lines {
+            let payload = serde_json::to_string(&line.item)?;
+            let (kind, order, text) = describe_item(&line.item);
+            tx.execute(
+                "INSERT OR REPLACE INTO records
+                 (session_id, seq, timestamp, kind, request_ordinal, output_index, sequence_number, text, payload)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    id,
+                    seq,
+                    line.timestamp,
+                    kind,
+                    order.map(|o| o.0),
+                    order.and_then(|o| o.1),
+                    order.and_then(|o| o.2),
+                    text,
+                    payload
+                ],
+            )
+            .map_err(sql_err)?;
+
+            match &line.item {
+                RolloutItem::SessionMeta(meta_line) => {
+                    tx.execute(
+                        "INSERT OR IGNORE INTO sessions
+                         (session_id, rollout_path, created_at, last_event_at, cwd, git_branch, source)
+                         VALUES (?1, ?2, ?3, ?3, ?4, ?5, ?6)",
+                        params![
+                            id,
+                            relative,
+                            line.timestamp,
+                            meta_line.meta.cwd.to_string_lossy(),
+                            meta_line.git.as_ref().and_then(|git| git.branch.clone()),
+                            serde_json::to_string(&meta_line.meta.source)?
+                        ],
+                    )
+                    .map_err(sql_err)?;
+                }
+                RolloutItem::ResponseItem(item) => {
+                    counts.message_count += 1;
+                    if let ResponseItem::Message { role, content, .. } = item
+                        && role.eq_ignore_ascii_case("user")
+                    {
+                        let snippet = snippet_from_content(content);
+                        if !snippet.as_deref().is_some_and(is_system_status_snippet) {
+                            counts.user_message_count += 1;
+                            if snippet.is_some() {
+                                counts.last_user_snippet = snippet;
+                            }
+                        }
+                    }
+                }
+                RolloutItem::Event(event) => {
+                    counts.message_count += 1;
+                    record_event(&tx, &id, seq, &line.timestamp, &event.msg)?;
+                }
+                RolloutItem::EventMsg(msg) => {
+                    counts.message_count += 1;
+                    record_event(&tx, &id, seq, &line.timestamp, msg)?;
+                }
+                RolloutItem::Compacted(_) | RolloutItem::TurnContext(_) => {
+                    counts.message_count += 1;
+                }
+            }
+            seq += 1;
+        }
+
+        let last_event_at = lines.last().map(|line| line.timestamp.clone());
+        tx.execute(
+            "UPDATE sessions SET last_event_at = COALESCE(?2, last_event_at), rollout_path = ?3,
+             message_count = ?4, user_message_count = ?5, last_user_snippet = ?6
+             WHERE session_id = ?1",
+            params![
+                id,
+                last_event_at,
+                relative,
+                counts.message_count as i64,
+                counts.user_message_count as i64,
+                counts.last_user_snippet
+            ],
+        )
+        .map_err(sql_err)?;
+        tx.commit().map_err(sql_err)
+    }
+
+    /// Catalog counters for `session_id`, if the session has been recorded.
+    pub fn session_counts(&self, session_id: Uuid) -> io::Result<Option<SessionCounts>> {
+        load_counts(&self.conn, &session_id.to_string()).map_err(sql_err)
+    }
+
+    /// Newest-first records whose text contains `needle` (case-insensitive).
+    pub fn search(&self, needle: &str, limit: usize) -> io::Result<Vec<HistorySearchHit>> {
+        let escaped = needle
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT session_id, timestamp, kind, text FROM records
+                 WHERE text LIKE ?1 ESCAPE '\\'
+                 ORDER BY timestamp DESC, seq DESC LIMIT ?2",
+            )
+            .map_err(sql_err)?;
+        let rows = stmt
+            .query_map(params![format!("%{escaped}%"), limit as i64], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })
+            .map_err(sql_err)?;
+        let mut hits = Vec::new();
+        for row in rows {
+            let (id, timestamp, kind, text) = row.map_err(sql_err)?;
+            let Ok(session_id) = Uuid::parse_str(&id) else {
+                continue;
+            };
+            hits.push(HistorySearchHit {
+                session_id,
+                timestamp,
+                kind,
+                text,
+            });
+        }
+        Ok(hits)
+    }
+
+    /// Record, exec, and token totals for one session, or for all sessions.
+    pub fn stats(&self, session_id: Option<Uuid>) -> io::Result<HistoryStats> {
+        let id = session_id.map(|id| id.to_string());
+        let count = |sql: &str| -> io::Result<i64> {
+            self.conn
+                .query_row(sql, params![id], |row| row.get(0))
+                .map_err(sql_err)
+        };
+        let sessions = count("SELECT COUNT(*) FROM sessions WHERE ?1 IS NULL OR session_id = ?1")?;
+        let records = count("SELECT COUNT(*) FROM records WHERE ?1 IS NULL OR session_id = ?1")?;
+        let exec_commands =
+            count("SELECT COUNT(*) FROM exec_commands WHERE ?1 IS NULL OR session_id = ?1")?;
+        let failed_exec_commands = count(
+            "SELECT COUNT(*) FROM exec_commands WHERE (?1 IS NULL OR session_id = ?1) AND exit_code != 0",
+        )?;
+        let (input_tokens, cached_input_tokens, output_tokens, total_tokens) = self
+            .conn
+            .query_row(
+                "SELECT COALESCE(SUM(input_tokens), 0), COALESCE(SUM(cached_input_tokens), 0),
+                        COALESCE(SUM(output_tokens), 0), COALESCE(SUM(total_tokens), 0)
+                 FROM token_usage WHERE ?1 IS NULL OR session_id = ?1",
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .map_err(sql_err)?;
+        Ok(HistoryStats {
+            sessions: sessions as u64,
+            records: records as u64,
+            exec_commands: exec_commands as u64,
+            failed_exec_commands: failed_exec_commands as u64,
+            input_tokens,
+            cached_input_tokens,
+            output_tokens,
+            total_tokens,
+        })
+    }
+
+    /// Imports every rollout under `sessions/` and `archived_sessions/`.
+    /// Files already mirrored with the same number of lines are left alone,
+    /// so the migration can be re-run safely.
+    pub fn migrate_rollouts(&mut self) -> io::Result<MigrationReport> {
+        let mut files = Vec::new();
+        for subdir in [SESSIONS_SUBDIR, ARCHIVED_SESSIONS_SUBDIR] {
+            collect_rollout_files(&self.code_home.join(subdir), &mut files);
+        }
+        files.sort();
+
+        let mut report = MigrationReport::default();
+        for path in files {
+            match self.import_rollout(&path) {
+                Ok(true) => report.imported += 1,
+                Ok(false) => report.unchanged += 1,
+                Err(err) => report.failed.push((path, err.to_string())),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Replaces the mirrored copy of one rollout file. Returns `false` when
+    /// the store already holds every line of it.
+    fn import_rollout(&mut self, path: &Path) -> io::Result<bool> {
+        let reader = BufReader::new(fs::File::open(path)?);
+        let mut lines = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Ok(parsed) = serde_json::from_str::<RolloutLine>(&line) {
+                lines.push(parsed);
+            }
+        }
+        let Some(session_id) = lines.iter().find_map(|line| match &line.item {
+            RolloutItem::SessionMeta(meta_line) => Some(Uuid::from(meta_line.meta.id)),
+            _ => None,
+        }) else {
+            return Err(io::Error::other("rollout has no session metadata"));
+        };
+
+        let id = session_id.to_string();
+        let existing: i64 = self
+            .conn
+            .query_row(
+                "SELECT COUNT(*) FROM records WHERE session_id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .map_err(sql_err)?;
+        if existing as usize == lines.len() {
+            return Ok(false);
+        }
+        for table in ["sessions", "records", "exec_commands", "token_usage"] {
+            self.conn
+                .execute(&format!("DELETE FROM {table} WHERE session_id = ?1"), params![id])
+                .map_err(sql_err)?;
+        }
+        self.append(session_id, path, &lines)?;
+        Ok(true)
+    }
+}
+
+fn load_counts(conn: &Connection, id: &str) -> rusqlite::Result<Option<SessionCounts>> {
+    conn.query_row(
+        "SELECT message_count, user_message_count, last_user_snippet,
+                (SELECT COALESCE(SUM(total_tokens), 0) FROM token_usage WHERE session_id = ?1)
+         FROM sessions WHERE session_id = ?1",
+        params![id],
+        |row| {
+            Ok(SessionCounts {
+                message_count: row.get::<_, i64>(0)? as usize,
+                user_message_count: row.get::<_, i64>(1)? as usize,
+                last_user_snippet: row.get(2)?,
+                total_tokens: row.get::<_, i64>(3)?.max(0) as u64,
+            })
+        },
+    )
+    .optional()
+}
+
+fn record_event(
+    conn: &Connection,
+    id: &str,
+    seq: i64,
+    timestamp: &str,
+    msg: &EventMsg,
+) -> io::Result<()> {
+    match msg {
+        EventMsg::ExecCommandEnd(end) => {
+            conn.execute(
+                "INSERT OR REPLACE INTO exec_commands
+                 (session_id, call_id, timestamp, command, cwd, exit_code, duration_ms)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    id,
+                    end.call_id,
+                    timestamp,
+                    shlex::try_join(end.command.iter().map(String::as_str))
+                        .unwrap_or_else(|_| end.command.join(" ")),
+                    end.cwd.to_string_lossy(),
+                    end.exit_code,
+                    end.duration.as_millis() as i64
+                ],
+            )
+            .map_err(sql_err)?;
+        }
+        EventMsg::TokenCount(count) => {
+            let Some(info) = &count.info else {
+                return Ok(());
+            };
+            let usage = &info.last_token_usage;
+            conn.execute(
+                "INSERT OR REPLACE INTO token_usage
+                 (session_id, seq, timestamp, input_tokens, cached_input_tokens, output_tokens,
+                  reasoning_output_tokens, total_tokens)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    id,
+                    seq,
+                    timestamp,
+                    usage.input_tokens,
+                    usage.cached_input_tokens,
+                    usage.output_tokens,
+                    usage.reasoning_output_tokens,
+                    usage.total_tokens
+                ],
+            )
+            .map_err(sql_err)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+type OrderKey = (i64, Option<i64>, Option<i64>);
+
+/// Record kind, order key (for recorded events), and searchable text.
+fn describe_item(item: &RolloutItem) -> (&'static str, Option<OrderKey>, Option<String>) {
+    match item {
+        RolloutItem::SessionMeta(_) => ("session_meta", None, None),
+        RolloutItem::ResponseItem(ResponseItem::Message { content, .. }) => {
+            ("response_item", None, content_text(content))
+        }
+        RolloutItem::ResponseItem(_) => ("response_item", None, None),
+        RolloutItem::Compacted(compacted) => ("compacted", None, Some(compacted.message.clone())),
+        RolloutItem::TurnContext(_) => ("turn_context", None, None),
+        RolloutItem::Event(event) => {
+            let order = event.order.as_ref().map(|order| {
+                (
+                    order.request_ordinal as i64,
+                    order.output_index.map(i64::from),
+                    order.sequence_number.map(|seq| seq as i64),
+                )
+            });
+            ("event", order, event_text(&event.msg))
+        }
+        RolloutItem::EventMsg(msg) => ("event_msg", None, event_text(msg)),
+    }
+}
+
+fn content_text(content: &[ContentItem]) -> Option<String> {
+    let text = content
+        .iter()
+        .filter_map(|item| match item {
+            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                Some(text.as_str())
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    (!text.is_empty()).then_some(text)
+}
+
+fn event_text(msg: &EventMsg) -> Option<String> {
+    match msg {
+        EventMsg::ExecCommandEnd(end) => Some(end.command.join(" ")),
+        _ => None,
+    }
+}
+
+fn collect_rollout_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if path.file_name().is_some_and(|name| name == "index") {
+                continue;
+            }
+            collect_rollout_files(&path, out);
+        } else if path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("rollout-") && name.ends_with(".jsonl"))
+        {
+            out.push(path);
+        }
+    }
+}
+
+fn sql_err(err: rusqlite::Error) -> io::Error {
+    io::Error::other(format!("history store: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_protocol::ThreadId;
+    use code_protocol::protocol::SessionMeta;
+    use code_protocol::protocol::SessionMetaLine;
+    use code_protocol::protocol::SessionSource;
+    use tempfile::TempDir;
+
+    fn meta_line(id: ThreadId) -> RolloutLine {
+        RolloutLine {
+            timestamp: "2025-01-01T00:00:00.000Z".to_string(),
+            item: RolloutItem::SessionMeta(SessionMetaLine {
+                meta: SessionMeta {
+                    id,
+                    timestamp: "2025-01-01T00:00:00.000Z".to_string(),
+                    cwd: PathBuf::from("/repo"),
+                    source: SessionSource::Cli,
+                    ..Default::default()
+                },
+                git: None,
+            }),
+        }
+    }
+
+    fn user_line(timestamp: &str, text: &str) -> RolloutLine {
+        RolloutLine {
+            timestamp: timestamp.to_string(),
+            item: RolloutItem::ResponseItem(ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: text.to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn append_tracks_counts_and_search() -> io::Result<()> {
+        let home = TempDir::new()?;
+        let mut store = HistoryStore::open(home.path())?;
+        let thread = ThreadId::new();
+        let session_id = Uuid::from(thread);
+        let rollout = home.path().join("sessions/rollout-test.jsonl");
+
+        store.append(session_id, &rollout, &[meta_line(thread)])?;
+        store.append(
+            session_id,
+            &rollout,
+            &[
+                user_line("2025-01-01T00:00:01.000Z", "fix the flaky test"),
+                user_line("2025-01-01T00:00:02.000Z", "== System Status == ok"),
+            ],
+        )?;
+
+        let counts = store.session_counts(session_id)?.expect("session row");
+        assert_eq!(counts.message_count, 2);
+        assert_eq!(counts.user_message_count, 1);
+        assert_eq!(counts.last_user_snippet.as_deref(), Some("fix the flaky test"));
+
+        let hits = store.search("FLAKY", 10)?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].session_id, session_id);
+        assert_eq!(store.stats(None)?.records, 3);
+        Ok(())
+    }
+}
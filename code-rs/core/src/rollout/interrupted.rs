@@ -0,0 +1,360 @@
+//! Turns cut short by a crash or a killed process.
+//!
+//! Each turn opens with `task_started` in the rollout and closes with
+//! `task_complete` or `turn_aborted`; a rollout whose last turn never closed
+//! was interrupted. Streamed deltas are not persisted, so while a turn runs
+//! the session also keeps a checkpoint next to the rollout
+//! (`<rollout>.inflight.json`) holding the reply text received since the last
+//! complete message. The checkpoint is removed when the turn ends.
+
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use code_protocol::models::ContentItem;
+use code_protocol::models::ResponseItem;
+use code_protocol::protocol::EventMsg as ProtoEventMsg;
+use code_protocol::protocol::RolloutItem;
+use code_protocol::protocol::RolloutLine;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::protocol::EventMsg;
+
+/// Minimum gap between checkpoint writes while deltas stream in.
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Where the in-flight checkpoint for `rollout_path` lives.
+pub fn turn_checkpoint_path(rollout_path: &Path) -> PathBuf {
+    rollout_path.with_extension("inflight.json")
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct TurnCheckpoint {
+    sub_id: String,
+    #[serde(default)]
+    agent_text: String,
+    #[serde(default)]
+    reasoning_text: String,
+}
+
+impl TurnCheckpoint {
+    fn is_empty(&self) -> bool {
+        self.agent_text.is_empty() && self.reasoning_text.is_empty()
+    }
+}
+
+/// Mirrors the streamed text of the running turn into the checkpoint file.
+#[derive(Debug)]
+pub(crate) struct TurnCheckpointWriter {
+    path: PathBuf,
+    turn: Option<TurnCheckpoint>,
+    written: bool,
+    last_write: Option<Instant>,
+}
+
+impl TurnCheckpointWriter {
+    pub(crate) fn new(rollout_path: &Path) -> Self {
+        Self {
+            path: turn_checkpoint_path(rollout_path),
+            turn: None,
+            written: false,
+            last_write: None,
+        }
+    }
+
+    /// Updates the checkpoint for an event the session is about to emit.
+    pub(crate) fn observe(&mut self, sub_id: &str, msg: &EventMsg) {
+        match msg {
+            EventMsg::TaskStarted => {
+                self.turn = Some(TurnCheckpoint {
+                    sub_id: sub_id.to_string(),
+                    ..TurnCheckpoint::default()
+                });
+                // A leftover from an earlier crash must not be mistaken for
+                // this turn's text.
+                self.written = true;
+                self.flush(true);
+            }
+            EventMsg::AgentMessageDelta(delta) => {
+                self.update(sub_id, false, |turn| turn.agent_text.push_str(&delta.delta));
+            }
+            EventMsg::AgentReasoningDelta(delta) => {
+                self.update(sub_id, false, |turn| turn.reasoning_text.push_str(&delta.delta));
+            }
+            // Complete messages are in the rollout; only text after them is partial.
+            EventMsg::AgentMessage(_) => self.update(sub_id, true, |turn| turn.agent_text.clear()),
+            EventMsg::AgentReasoning(_) => {
+                self.update(sub_id, true, |turn| turn.reasoning_text.clear());
+            }
+            EventMsg::TaskComplete(_) | EventMsg::TurnAborted(_) => {
+                if self.turn.as_ref().is_some_and(|turn| turn.sub_id == sub_id) {
+                    self.turn = None;
+                    self.flush(true);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn update(&mut self, sub_id: &str, force: bool, apply: impl FnOnce(&mut TurnCheckpoint)) {
+        let Some(turn) = self.turn.as_mut().filter(|turn| turn.sub_id == sub_id) else {
+            return;
+        };
+        apply(turn);
+        self.flush(force);
+    }
+
+    /// Writes the checkpoint, or removes it when there is nothing partial.
+    /// Unforced writes are throttled to [`CHECKPOINT_INTERVAL`].
+    fn flush(&mut self, force: bool) {
+        let result = match self.turn.as_ref().filter(|turn| !turn.is_empty()) {
+            Some(turn) => {
+                if !force
+                    && self
+                        .last_write
+                        .is_some_and(|at| at.elapsed() < CHECKPOINT_INTERVAL)
+                {
+                    return;
+                }
+                self.last_write = Some(Instant::now());
+                self.written = true;
+                serde_json::to_vec(turn)
+                    .map_err(io::Error::other)
+                    .and_then(|json| fs::write(&self.path, json))
+            }
+            None if self.written => {
+                self.written = false;
+                self.last_write = None;
+                match fs::remove_file(&self.path) {
+                    Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+                    _ => Ok(()),
+                }
+            }
+            None => Ok(()),
+        };
+        if let Err(err) = result {
+            debug!("failed to update turn checkpoint {}: {err}", self.path.display());
+        }
+    }
+}
+
+/// The last turn of a rollout that never completed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InterruptedTurn {
+    /// The user message that started the turn, when it was recorded.
+    pub user_message: Option<String>,
+    /// Reply text streamed before the interruption that is not in the rollout.
+    pub partial_reply: Option<String>,
+    /// Reasoning streamed before the interruption that is not in the rollout.
+    pub partial_reasoning: Option<String>,
+}
+
+/// Reads the rollout at `path` and returns its last turn if that turn never
+/// completed or was aborted, merged with the turn's checkpoint if one exists.
+pub fn find_interrupted_turn(path: &Path) -> io::Result<Option<InterruptedTurn>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut open_turn: Option<String> = None;
+    let mut user_message: Option<String> = None;
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Ok(parsed) = serde_json::from_str::<RolloutLine>(&line) else {
+            continue;
+        };
+        let (id, msg) = match parsed.item {
+            RolloutItem::ResponseItem(ResponseItem::Message { role, content, .. })
+                if role == "user" =>
+            {
+                if let Some(text) = user_text(&content) {
+                    user_message = Some(text);
+                }
+                continue;
+            }
+            RolloutItem::Event(event) => (Some(event.id), event.msg),
+            RolloutItem::EventMsg(msg) => (None, msg),
+            _ => continue,
+        };
+        match msg {
+            ProtoEventMsg::TurnStarted(_) => open_turn = Some(id.unwrap_or_default()),
+            ProtoEventMsg::TurnComplete(_) | ProtoEventMsg::TurnAborted(_) => {
+                if id.is_none() || open_turn == id {
+                    open_turn = None;
+                    user_message = None;
+                }
+            }
+            ProtoEventMsg::UserMessage(event) if !event.message.trim().is_empty() => {
+                user_message = Some(event.message);
+            }
+            _ => {}
+        }
+    }
+    let Some(sub_id) = open_turn else {
+        return Ok(None);
+    };
+
+    let checkpoint = fs::read(turn_checkpoint_path(path))
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<TurnCheckpoint>(&bytes).ok())
+        .filter(|checkpoint| checkpoint.sub_id == sub_id)
+        .unwrap_or_default();
+    let non_empty = |text: String| (!text.trim().is_empty()).then_some(text);
+    Ok(Some(InterruptedTurn {
+        user_message,
+        partial_reply: non_empty(checkpoint.agent_text),
+        partial_reasoning: non_empty(checkpoint.reasoning_text),
+    }))
+}
+
+/// Text the user typed, skipping the status and context blocks the session
+/// adds as user messages.
+fn user_text(content: &[ContentItem]) -> Option<String> {
+    let text = content
+        .iter()
+        .filter_map(|item| match item {
+            ContentItem::InputText { text }
+                if !super::catalog::is_system_status_snippet(text)
+                    && !text.trim_start().starts_with('<') =>
+            {
+                Some(text.as_str())
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    (!text.trim().is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::AgentMessageDeltaEvent;
+    use crate::protocol::AgentMessageEvent;
+    use crate::protocol::TaskCompleteEvent;
+    use code_protocol::protocol::RecordedEvent;
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+
+    fn event_line(id: &str, msg: EventMsg) -> String {
+        let msg = crate::protocol::event_msg_to_protocol(&msg).expect("protocol event");
+        let line = RolloutLine {
+            timestamp: "2025-01-01T00:00:00.000Z".to_string(),
+            item: RolloutItem::Event(RecordedEvent {
+                id: id.to_string(),
+                event_seq: 0,
+                order: None,
+                msg,
+            }),
+        };
+        serde_json::to_string(&line).expect("serialize")
+    }
+
+    fn user_line(text: &str) -> String {
+        let line = RolloutLine {
+            timestamp: "2025-01-01T00:00:00.000Z".to_string(),
+            item: RolloutItem::ResponseItem(ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: text.to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            }),
+        };
+        serde_json::to_string(&line).expect("serialize")
+    }
+
+    fn write_rollout(path: &Path, lines: &[String]) {
+        let mut file = File::create(path).expect("create rollout");
+        for line in lines {
+            writeln!(file, "{line}").expect("write line");
+        }
+    }
+
+    #[test]
+    fn completed_turns_are_not_interrupted() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let rollout = dir.path().join("rollout.jsonl");
+        write_rollout(
+            &rollout,
+            &[
+                user_line("fix the build"),
+                event_line("1", EventMsg::TaskStarted),
+                event_line(
+                    "1",
+                    EventMsg::TaskComplete(TaskCompleteEvent {
+                        last_agent_message: None,
+                    }),
+                ),
+            ],
+        );
+        assert_eq!(find_interrupted_turn(&rollout).expect("read"), None);
+    }
+
+    #[test]
+    fn open_turn_is_restored_with_streamed_text() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let rollout = dir.path().join("rollout.jsonl");
+        write_rollout(
+            &rollout,
+            &[
+                user_line("fix the build"),
+                event_line("1", EventMsg::TaskStarted),
+                event_line(
+                    "1",
+                    EventMsg::TaskComplete(TaskCompleteEvent {
+                        last_agent_message: None,
+                    }),
+                ),
+                user_line("now add tests"),
+                event_line("2", EventMsg::TaskStarted),
+            ],
+        );
+
+        let mut writer = TurnCheckpointWriter::new(&rollout);
+        writer.observe("2", &EventMsg::TaskStarted);
+        writer.observe(
+            "2",
+            &EventMsg::AgentMessage(AgentMessageEvent {
+                message: "Looking at the crate.".to_string(),
+            }),
+        );
+        for delta in ["Adding ", "tests to ", "lib.rs"] {
+            writer.observe(
+                "2",
+                &EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
+                    delta: delta.to_string(),
+                }),
+            );
+        }
+        // Deltas after the first are throttled; a forced write flushes them.
+        writer.flush(true);
+
+        assert_eq!(
+            find_interrupted_turn(&rollout).expect("read"),
+            Some(InterruptedTurn {
+                user_message: Some("now add tests".to_string()),
+                partial_reply: Some("Adding tests to lib.rs".to_string()),
+                partial_reasoning: None,
+            })
+        );
+
+        writer.observe(
+            "2",
+            &EventMsg::TaskComplete(TaskCompleteEvent {
+                last_agent_message: None,
+            }),
+        );
+        assert!(!turn_checkpoint_path(&rollout).exists());
+    }
+}
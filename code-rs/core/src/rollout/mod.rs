@@ -9,9 +9,13 @@ pub const INTERACTIVE_SESSION_SOURCES: &[SessionSource] =
     &[SessionSource::Cli, SessionSource::VSCode];
 
 pub mod catalog;
+pub mod history_store;
+pub mod interrupted;
 pub mod list;
+pub mod order_audit;
 pub(crate) mod policy;
 pub mod recorder;
+pub mod summary;
 
 pub use code_protocol::protocol::SessionMeta;
 #[allow(unused_imports)]
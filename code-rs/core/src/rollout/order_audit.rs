@@ -0,0 +1,181 @@
+//! Ordering checks over a recorded rollout (`code session audit-order`).
+//!
+//! The TUI places history cells by the `OrderMeta` attached to each event, so
+//! an event that arrives with a smaller request ordinal or sequence number than
+//! its predecessors lands in the wrong request window. This module replays the
+//! recorded events of a rollout and reports every such regression.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::path::Path;
+
+use code_protocol::protocol::RecordedEvent;
+use code_protocol::protocol::RolloutItem;
+use code_protocol::protocol::RolloutLine;
+
+/// Output index used for background events that are not tied to a model
+/// output item; they are exempt from ordering checks.
+const BACKGROUND_OUTPUT_INDEX: u32 = i32::MAX as u32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderViolation {
+    /// 1-based line number in the rollout file.
+    pub line: usize,
+    pub submission_id: String,
+    pub message: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OrderAudit {
+    /// Recorded events seen in the rollout.
+    pub events: usize,
+    /// Events that carried model ordering metadata and were checked.
+    pub ordered_events: usize,
+    /// Lines that could not be parsed as rollout lines.
+    pub unparsed_lines: usize,
+    pub violations: Vec<OrderViolation>,
+}
+
+impl OrderAudit {
+    pub fn is_clean(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+#[derive(Default)]
+struct AuditState {
+    max_request: u64,
+    last_event_seq: HashMap<String, u64>,
+    last_sequence: HashMap<(u64, u32), u64>,
+}
+
+/// Reads the rollout at `path` and checks that recorded events are monotonic:
+/// request ordinals never go backwards, sequence numbers never go backwards
+/// within one output item, and each submission's `event_seq` only restarts at
+/// zero.
+pub fn audit_rollout_order(path: &Path) -> io::Result<OrderAudit> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut audit = OrderAudit::default();
+    let mut state = AuditState::default();
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Ok(parsed) = serde_json::from_str::<RolloutLine>(&line) else {
+            audit.unparsed_lines += 1;
+            continue;
+        };
+        if let RolloutItem::Event(event) = parsed.item {
+            check_event(&mut audit, &mut state, idx + 1, &event);
+        }
+    }
+    Ok(audit)
+}
+
+fn check_event(audit: &mut OrderAudit, state: &mut AuditState, line: usize, event: &RecordedEvent) {
+    audit.events += 1;
+    let mut messages = Vec::new();
+
+    if let Some(previous) = state.last_event_seq.insert(event.id.clone(), event.event_seq)
+        && event.event_seq != 0
+        && event.event_seq <= previous
+    {
+        messages.push(format!(
+            "event_seq {} does not follow {previous}",
+            event.event_seq
+        ));
+    }
+
+    if let Some(order) = &event.order
+        && order.output_index != Some(BACKGROUND_OUTPUT_INDEX)
+    {
+        audit.ordered_events += 1;
+        let output_index = order.output_index.unwrap_or(0);
+
+        if order.request_ordinal < state.max_request {
+            messages.push(format!(
+                "request {} arrived after request {}",
+                order.request_ordinal, state.max_request
+            ));
+        }
+        state.max_request = state.max_request.max(order.request_ordinal);
+
+        if let Some(sequence) = order.sequence_number
+            && let Some(previous) = state
+                .last_sequence
+                .insert((order.request_ordinal, output_index), sequence)
+            && sequence < previous
+        {
+            messages.push(format!(
+                "sequence {sequence} after {previous} in request {} output {output_index}",
+                order.request_ordinal
+            ));
+        }
+    }
+
+    audit
+        .violations
+        .extend(messages.into_iter().map(|message| OrderViolation {
+            line,
+            submission_id: event.id.clone(),
+            message,
+        }));
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use code_protocol::protocol::BackgroundEventEvent;
+    use code_protocol::protocol::EventMsg;
+    use code_protocol::protocol::OrderMeta;
+    use std::io::Write;
+
+    fn event_line(event_seq: u64, req: u64, out: u32, seq: u64) -> String {
+        let line = RolloutLine {
+            timestamp: "2025-01-01T00:00:00.000Z".to_string(),
+            item: RolloutItem::Event(RecordedEvent {
+                id: "sub-1".to_string(),
+                event_seq,
+                order: Some(OrderMeta {
+                    request_ordinal: req,
+                    output_index: Some(out),
+                    sequence_number: Some(seq),
+                }),
+                msg: EventMsg::BackgroundEvent(BackgroundEventEvent {
+                    message: "tick".to_string(),
+                }),
+            }),
+        };
+        serde_json::to_string(&line).unwrap()
+    }
+
+    #[test]
+    fn reports_request_and_sequence_regressions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rollout.jsonl");
+        let mut file = File::create(&path).unwrap();
+        for line in [
+            event_line(1, 1, 0, 0),
+            event_line(2, 1, 0, 3),
+            event_line(3, 2, 0, 0),
+            event_line(4, 1, 0, 4),
+            event_line(5, 2, 0, 0),
+            event_line(0, 3, 0, 0),
+            event_line(1, 1, BACKGROUND_OUTPUT_INDEX, 0),
+        ] {
+            writeln!(file, "{line}").unwrap();
+        }
+
+        let audit = audit_rollout_order(&path).unwrap();
+        assert_eq!(audit.events, 7);
+        assert_eq!(audit.ordered_events, 6);
+        let lines: Vec<usize> = audit.violations.iter().map(|v| v.line).collect();
+        assert_eq!(lines, vec![4]);
+        assert_eq!(audit.violations[0].message, "request 1 arrived after request 2");
+    }
+}
@@ -29,7 +29,9 @@ use super::list::get_conversations;
 use super::list::ConversationsPage;
 use super::list::Cursor;
 use super::policy::{should_persist_response_item, should_persist_rollout_item};
+use super::history_store::HistoryStore;
 use crate::config::Config;
+use crate::config_types::HistoryStoreKind;
 use crate::default_client::DEFAULT_ORIGINATOR;
 use crate::git_info::collect_git_info;
 use crate::history::HistorySnapshot;
@@ -95,6 +97,42 @@ struct CatalogUpdateState {
     session_id: ThreadId,
     rollout_path: PathBuf,
     last_timestamp: String,
+    /// SQLite mirror when `[history] store = "sqlite"`.
+    history_store: Option<HistoryStore>,
+}
+
+impl CatalogUpdateState {
+    /// Whether written lines must be kept around for the history store.
+    fn mirrors_lines(&self) -> bool {
+        self.history_store.is_some()
+    }
+
+    /// Mirrors `lines` into the history store (if any) and refreshes the
+    /// catalog entry, reusing the store's counters instead of re-parsing.
+    async fn record(&mut self, lines: &[RolloutLine]) {
+        let session_id: uuid::Uuid = self.session_id.into();
+        let mut counts = None;
+        if let Some(store) = self.history_store.as_mut() {
+            match store
+                .append(session_id, &self.rollout_path, lines)
+                .and_then(|()| store.session_counts(session_id))
+            {
+                Ok(found) => counts = found,
+                Err(err) => warn!("failed to mirror rollout into history store: {err}"),
+            }
+        }
+        if let Err(err) = super::catalog::update_catalog_entry_with_counts(
+            &self.code_home,
+            &self.rollout_path,
+            session_id,
+            &self.last_timestamp,
+            counts,
+        )
+        .await
+        {
+            warn!("failed to update session catalog: {err}");
+        }
+    }
 }
 
 impl RolloutRecorderParams {
@@ -187,6 +225,12 @@ impl RolloutRecorder {
             session_id: meta.id,
             rollout_path: rollout_path.clone(),
             last_timestamp: meta.timestamp.clone(),
+            history_store: match config.history.store {
+                HistoryStoreKind::Jsonl => None,
+                HistoryStoreKind::Sqlite => HistoryStore::open(&config.code_home)
+                    .inspect_err(|err| warn!("history store disabled: {err}"))
+                    .ok(),
+            },
         });
 
         // A reasonably-sized bounded channel. If the buffer fills up the send
@@ -502,22 +546,20 @@ async fn rollout_writer(
         };
 
         // Write the SessionMeta as the first item in the file, wrapped in a rollout line
-        let (timestamp, _) = writer
-            .write_rollout_item(RolloutItem::SessionMeta(session_meta_line))
-            .await?;
+        let item = RolloutItem::SessionMeta(session_meta_line);
+        let mirrored = catalog_state
+            .as_ref()
+            .filter(|state| state.mirrors_lines())
+            .map(|_| item.clone());
+        let (timestamp, _) = writer.write_rollout_item(item).await?;
 
         if let Some(ref mut state) = catalog_state {
-            state.last_timestamp = timestamp;
-            if let Err(err) = super::catalog::update_catalog_entry(
-                &state.code_home,
-                &state.rollout_path,
-                state.session_id.into(),
-                &state.last_timestamp,
-            )
-            .await
-            {
-                warn!("failed to update session catalog after meta write: {err}");
-            }
+            state.last_timestamp = timestamp.clone();
+            let lines: Vec<RolloutLine> = mirrored
+                .map(|item| RolloutLine { timestamp, item })
+                .into_iter()
+                .collect();
+            state.record(&lines).await;
         }
     }
 
@@ -525,26 +567,26 @@ async fn rollout_writer(
     while let Some(cmd) = rx.recv().await {
         match cmd {
             RolloutCmd::AddItems(items) => {
+                let mut written: Vec<RolloutLine> = Vec::new();
                 for item in items {
                     if should_persist_rollout_item(&item) {
+                        let mirrored = catalog_state
+                            .as_ref()
+                            .filter(|state| state.mirrors_lines())
+                            .map(|_| item.clone());
                         let (timestamp, _) = writer.write_rollout_item(item).await?;
                         if let Some(ref mut state) = catalog_state {
-                            state.last_timestamp = timestamp;
+                            state.last_timestamp = timestamp.clone();
+                        }
+                        if let Some(item) = mirrored {
+                            written.push(RolloutLine { timestamp, item });
                         }
                     }
                 }
 
-                if let Some(ref state) = catalog_state
-                    && let Err(err) = super::catalog::update_catalog_entry(
-                        &state.code_home,
-                        &state.rollout_path,
-                        state.session_id.into(),
-                        &state.last_timestamp,
-                    )
-                    .await
-                    {
-                        warn!("failed to update session catalog after AddItems: {err}");
-                    }
+                if let Some(ref mut state) = catalog_state {
+                    state.record(&written).await;
+                }
             }
             RolloutCmd::SetSnapshot(snapshot) => {
                 if let Err(err) = write_snapshot(&snapshot_path, &snapshot).await {
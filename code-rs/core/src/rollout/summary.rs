@@ -0,0 +1,256 @@
+//! Structured facts about a recorded rollout (`code session summarize`).
+//!
+//! Dashboards and the resume picker want to know what a session did without
+//! replaying its JSONL themselves: which files it changed, which commands it
+//! ran, how many tokens it used and what it finally answered.
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::path::Path;
+use std::path::PathBuf;
+
+use code_protocol::models::ResponseItem;
+use code_protocol::protocol::EventMsg;
+use code_protocol::protocol::FileChange;
+use code_protocol::protocol::RolloutItem;
+use code_protocol::protocol::RolloutLine;
+use serde::Serialize;
+
+use super::catalog::is_system_status_snippet;
+use super::catalog::snippet_from_content;
+use crate::util::strip_bash_lc_and_escape;
+
+/// Tokens summed over every request of the session.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionTokenTotals {
+    pub input: u64,
+    pub cached_input: u64,
+    pub output: u64,
+    pub reasoning_output: u64,
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionCommandSummary {
+    pub command: String,
+    pub exit_code: i32,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionSummary {
+    pub session_id: Option<String>,
+    pub cwd: Option<PathBuf>,
+    pub started_at: Option<String>,
+    pub last_event_at: Option<String>,
+    /// Model of the most recent turn.
+    pub model: Option<String>,
+    pub user_messages: usize,
+    pub last_user_message: Option<String>,
+    /// Files touched by successfully applied patches, sorted; renames list
+    /// both the source and the destination.
+    pub files_changed: Vec<PathBuf>,
+    pub commands: Vec<SessionCommandSummary>,
+    pub tokens: SessionTokenTotals,
+    /// The agent's last answer, if it gave one.
+    pub final_answer: Option<String>,
+    /// Lines that could not be parsed as rollout lines.
+    pub unparsed_lines: usize,
+}
+
+impl SessionSummary {
+    /// One line for narrow displays such as the resume picker: the first line
+    /// of the final answer (or the last prompt) after the change counts.
+    pub fn headline(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        if !self.files_changed.is_empty() {
+            let count = self.files_changed.len();
+            parts.push(format!("{count} file{}", if count == 1 { "" } else { "s" }));
+        }
+        if !self.commands.is_empty() {
+            let count = self.commands.len();
+            parts.push(format!("{count} cmd{}", if count == 1 { "" } else { "s" }));
+        }
+        let text = self
+            .final_answer
+            .as_deref()
+            .or(self.last_user_message.as_deref())
+            .and_then(|text| text.lines().map(str::trim).find(|line| !line.is_empty()));
+        if let Some(text) = text {
+            parts.push(text.to_string());
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Reads the rollout at `path` and collects its [`SessionSummary`].
+pub fn summarize_rollout(path: &Path) -> io::Result<SessionSummary> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut summary = SessionSummary::default();
+    let mut files: BTreeSet<PathBuf> = BTreeSet::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Ok(parsed) = serde_json::from_str::<RolloutLine>(&line) else {
+            summary.unparsed_lines += 1;
+            continue;
+        };
+        summary.last_event_at = Some(parsed.timestamp.clone());
+        match parsed.item {
+            RolloutItem::SessionMeta(meta_line) => {
+                if summary.session_id.is_none() {
+                    summary.session_id = Some(meta_line.meta.id.to_string());
+                    summary.cwd = Some(meta_line.meta.cwd);
+                    summary.started_at = Some(parsed.timestamp);
+                }
+            }
+            RolloutItem::TurnContext(context) => summary.model = Some(context.model),
+            RolloutItem::ResponseItem(ResponseItem::Message { role, content, .. })
+                if role.eq_ignore_ascii_case("user") =>
+            {
+                let snippet = snippet_from_content(&content);
+                if snippet.as_deref().is_some_and(is_system_status_snippet) {
+                    continue;
+                }
+                summary.user_messages += 1;
+                if snippet.is_some() {
+                    summary.last_user_message = snippet;
+                }
+            }
+            RolloutItem::Event(event) => record_event(&mut summary, &mut files, event.msg),
+            RolloutItem::EventMsg(msg) => record_event(&mut summary, &mut files, msg),
+            RolloutItem::ResponseItem(_) | RolloutItem::Compacted(_) => {}
+        }
+    }
+    summary.files_changed = files.into_iter().collect();
+    Ok(summary)
+}
+
+fn record_event(summary: &mut SessionSummary, files: &mut BTreeSet<PathBuf>, msg: EventMsg) {
+    match msg {
+        EventMsg::ExecCommandEnd(end) => summary.commands.push(SessionCommandSummary {
+            command: strip_bash_lc_and_escape(&end.command),
+            exit_code: end.exit_code,
+            duration_ms: u64::try_from(end.duration.as_millis()).unwrap_or(u64::MAX),
+        }),
+        EventMsg::PatchApplyEnd(end) if end.success => {
+            for (path, change) in end.changes {
+                if let FileChange::Update {
+                    move_path: Some(dest),
+                    ..
+                } = change
+                {
+                    files.insert(dest);
+                }
+                files.insert(path);
+            }
+        }
+        EventMsg::TokenCount(count) => {
+            if let Some(info) = count.info {
+                let usage = info.last_token_usage;
+                let tokens = &mut summary.tokens;
+                tokens.input += usage.input_tokens.max(0) as u64;
+                tokens.cached_input += usage.cached_input_tokens.max(0) as u64;
+                tokens.output += usage.output_tokens.max(0) as u64;
+                tokens.reasoning_output += usage.reasoning_output_tokens.max(0) as u64;
+                tokens.total += usage.total_tokens.max(0) as u64;
+            }
+        }
+        EventMsg::AgentMessage(message) if !message.message.trim().is_empty() => {
+            summary.final_answer = Some(message.message);
+        }
+        EventMsg::TurnComplete(complete) => {
+            if let Some(message) = complete.last_agent_message
+                && !message.trim().is_empty()
+            {
+                summary.final_answer = Some(message);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use code_protocol::protocol::AgentMessageEvent;
+    use code_protocol::protocol::RecordedEvent;
+    use code_protocol::protocol::TokenCountEvent;
+    use code_protocol::protocol::TokenUsage;
+    use code_protocol::protocol::TokenUsageInfo;
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+
+    fn event_line(msg: EventMsg) -> String {
+        let line = RolloutLine {
+            timestamp: "2025-01-01T00:00:00.000Z".to_string(),
+            item: RolloutItem::Event(RecordedEvent {
+                id: "sub-1".to_string(),
+                event_seq: 0,
+                order: None,
+                msg,
+            }),
+        };
+        serde_json::to_string(&line).unwrap()
+    }
+
+    fn token_line(total: i64) -> String {
+        let usage = TokenUsage {
+            input_tokens: total - 10,
+            cached_input_tokens: 0,
+            output_tokens: 10,
+            reasoning_output_tokens: 0,
+            total_tokens: total,
+        };
+        event_line(EventMsg::TokenCount(TokenCountEvent {
+            info: Some(TokenUsageInfo {
+                total_token_usage: usage.clone(),
+                last_token_usage: usage,
+                requested_model: None,
+                latest_response_model: None,
+                model_context_window: None,
+            }),
+            rate_limits: None,
+        }))
+    }
+
+    #[test]
+    fn collects_tokens_and_final_answer() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rollout.jsonl");
+        let mut file = File::create(&path).unwrap();
+        for line in [
+            token_line(100),
+            event_line(EventMsg::AgentMessage(AgentMessageEvent {
+                message: "Working on it".to_string(),
+            })),
+            token_line(50),
+            event_line(EventMsg::AgentMessage(AgentMessageEvent {
+                message: "Fixed the parser.\nDetails follow.".to_string(),
+            })),
+            "not json".to_string(),
+        ] {
+            writeln!(file, "{line}").unwrap();
+        }
+
+        let summary = summarize_rollout(&path).unwrap();
+        assert_eq!(
+            summary.tokens,
+            SessionTokenTotals {
+                input: 130,
+                cached_input: 0,
+                output: 20,
+                reasoning_output: 0,
+                total: 150,
+            }
+        );
+        assert_eq!(summary.unparsed_lines, 1);
+        assert_eq!(summary.headline(), "Fixed the parser.");
+    }
+}
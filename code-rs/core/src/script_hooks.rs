@@ -0,0 +1,304 @@
+//! User scripting hooks configured under `[hooks]`.
+//!
+//! Each hook is a command that receives a JSON payload on stdin describing
+//! the action about to happen (or that just happened) and answers through
+//! its exit status:
+//!
+//! - exit 0 lets the action proceed. Non-empty stdout is an annotation:
+//!   `pre_turn` and `post_patch` annotations reach the model, `pre_exec` ones
+//!   are shown to the user. Stdout may instead be a JSON object
+//!   `{"decision": "allow" | "deny", "reason": "...", "annotation": "..."}`.
+//! - exit 2 vetoes the action, with stderr (or the JSON `reason`) as the
+//!   reason.
+//! - Any other status, a spawn failure or a timeout is reported and the
+//!   action proceeds, so a broken hook never wedges a session.
+//!
+//! Hooks run in order; the first veto stops the rest.
+
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::config_types::ScriptHookConfig;
+
+const DEFAULT_HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_OUTPUT_CHARS: usize = 2_000;
+
+/// Exit status a hook uses to veto the action.
+const VETO_EXIT_CODE: i32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ScriptHookPoint {
+    PreTurn,
+    PreExec,
+    PostPatch,
+}
+
+impl ScriptHookPoint {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            ScriptHookPoint::PreTurn => "pre_turn",
+            ScriptHookPoint::PreExec => "pre_exec",
+            ScriptHookPoint::PostPatch => "post_patch",
+        }
+    }
+}
+
+/// Combined answer of every hook configured for one point.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct ScriptHookVerdict {
+    /// `(hook label, reason)` of the hook that vetoed the action.
+    pub denied: Option<(String, String)>,
+    /// `(hook label, text)` annotations, in hook order.
+    pub annotations: Vec<(String, String)>,
+    /// `(hook label, error)` for hooks that failed open.
+    pub failures: Vec<(String, String)>,
+}
+
+impl ScriptHookVerdict {
+    /// Why the action was blocked, in words fit for the model.
+    pub(crate) fn denial(&self) -> Option<String> {
+        self.denied.as_ref().map(|(label, reason)| {
+            if reason.is_empty() {
+                format!("blocked by hook `{label}`")
+            } else {
+                format!("blocked by hook `{label}`: {reason}")
+            }
+        })
+    }
+
+    /// Annotations joined one per line, `None` when there are none.
+    pub(crate) fn annotation_text(&self) -> Option<String> {
+        if self.annotations.is_empty() {
+            return None;
+        }
+        let lines: Vec<String> = self
+            .annotations
+            .iter()
+            .map(|(label, text)| format!("[hook `{label}`] {text}"))
+            .collect();
+        Some(lines.join("\n"))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum HookOutcome {
+    Allow { annotation: Option<String> },
+    Deny { reason: String },
+    Failed(String),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum HookDecision {
+    Allow,
+    Deny,
+}
+
+#[derive(Debug, Deserialize)]
+struct HookResponse {
+    #[serde(default)]
+    decision: Option<HookDecision>,
+    #[serde(default)]
+    reason: Option<String>,
+    #[serde(default)]
+    annotation: Option<String>,
+}
+
+fn hook_label(hook: &ScriptHookConfig) -> String {
+    hook.name
+        .clone()
+        .or_else(|| hook.command.first().cloned())
+        .unwrap_or_else(|| "hook".to_string())
+}
+
+/// Runs `hooks` for `point` with `payload` on stdin.
+pub(crate) async fn run_script_hooks(
+    hooks: &[ScriptHookConfig],
+    point: ScriptHookPoint,
+    cwd: &Path,
+    payload: &Value,
+) -> ScriptHookVerdict {
+    let mut verdict = ScriptHookVerdict::default();
+    let stdin = payload.to_string();
+    for hook in hooks {
+        let label = hook_label(hook);
+        match run_one(hook, point, cwd, &stdin).await {
+            HookOutcome::Allow { annotation: Some(text) } => verdict.annotations.push((label, text)),
+            HookOutcome::Allow { annotation: None } => {}
+            HookOutcome::Deny { reason } => {
+                verdict.denied = Some((label, reason));
+                break;
+            }
+            HookOutcome::Failed(error) => verdict.failures.push((label, error)),
+        }
+    }
+    verdict
+}
+
+async fn run_one(hook: &ScriptHookConfig, point: ScriptHookPoint, cwd: &Path, stdin: &str) -> HookOutcome {
+    let Some((program, args)) = hook.command.split_first() else {
+        return HookOutcome::Failed("empty command".to_string());
+    };
+    let mut cmd = Command::new(program);
+    cmd.args(args)
+        .current_dir(cwd)
+        .envs(hook.env.clone().unwrap_or_default())
+        .env("CODE_HOOK", point.as_str())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(err) => return HookOutcome::Failed(format!("failed to start: {err}")),
+    };
+    if let Some(mut pipe) = child.stdin.take() {
+        // A hook that ignores its payload may close stdin early; that is fine.
+        let _ = pipe.write_all(stdin.as_bytes()).await;
+    }
+    let timeout = hook
+        .timeout_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_HOOK_TIMEOUT);
+    match tokio::time::timeout(timeout, child.wait_with_output()).await {
+        Err(_) => HookOutcome::Failed(format!("timed out after {}ms", timeout.as_millis())),
+        Ok(Err(err)) => HookOutcome::Failed(format!("failed to run: {err}")),
+        Ok(Ok(output)) => parse_outcome(
+            output.status.code(),
+            &String::from_utf8_lossy(&output.stdout),
+            &String::from_utf8_lossy(&output.stderr),
+        ),
+    }
+}
+
+fn parse_outcome(exit_code: Option<i32>, stdout: &str, stderr: &str) -> HookOutcome {
+    let stdout = stdout.trim();
+    let stderr = clip(stderr.trim());
+    let response = stdout
+        .starts_with('{')
+        .then(|| serde_json::from_str::<HookResponse>(stdout).ok())
+        .flatten();
+    match exit_code {
+        Some(VETO_EXIT_CODE) => {
+            let reason = response
+                .and_then(|response| response.reason)
+                .map(|reason| clip(reason.trim()))
+                .filter(|reason| !reason.is_empty())
+                .unwrap_or(stderr);
+            HookOutcome::Deny { reason }
+        }
+        Some(0) => match response {
+            Some(HookResponse {
+                decision: Some(HookDecision::Deny),
+                reason,
+                ..
+            }) => HookOutcome::Deny {
+                reason: reason
+                    .map(|reason| clip(reason.trim()))
+                    .filter(|reason| !reason.is_empty())
+                    .unwrap_or(stderr),
+            },
+            Some(HookResponse { annotation, .. }) => HookOutcome::Allow {
+                annotation: annotation
+                    .map(|annotation| clip(annotation.trim()))
+                    .filter(|annotation| !annotation.is_empty()),
+            },
+            None => HookOutcome::Allow {
+                annotation: (!stdout.is_empty()).then(|| clip(stdout)),
+            },
+        },
+        Some(code) if stderr.is_empty() => HookOutcome::Failed(format!("exited with status {code}")),
+        Some(code) => HookOutcome::Failed(format!("exited with status {code}: {stderr}")),
+        None => HookOutcome::Failed("terminated by a signal".to_string()),
+    }
+}
+
+fn clip(text: &str) -> String {
+    if text.chars().count() > MAX_OUTPUT_CHARS {
+        let mut clipped: String = text.chars().take(MAX_OUTPUT_CHARS).collect();
+        clipped.push('…');
+        clipped
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    fn test_hook(command: &[&str], env: Option<HashMap<String, String>>) -> ScriptHookConfig {
+        ScriptHookConfig {
+            name: None,
+            command: command.iter().map(|part| part.to_string()).collect(),
+            env,
+            timeout_ms: None,
+        }
+    }
+
+    #[test]
+    fn exit_codes_and_json_decide_the_outcome() {
+        assert_eq!(
+            parse_outcome(Some(0), "", "noise"),
+            HookOutcome::Allow { annotation: None }
+        );
+        assert_eq!(
+            parse_outcome(Some(0), "  remember the changelog\n", ""),
+            HookOutcome::Allow {
+                annotation: Some("remember the changelog".to_string())
+            }
+        );
+        assert_eq!(
+            parse_outcome(Some(0), r#"{"annotation": "ok by policy"}"#, ""),
+            HookOutcome::Allow {
+                annotation: Some("ok by policy".to_string())
+            }
+        );
+        assert_eq!(
+            parse_outcome(Some(0), r#"{"decision": "deny", "reason": "no pushes"}"#, ""),
+            HookOutcome::Deny {
+                reason: "no pushes".to_string()
+            }
+        );
+        assert_eq!(
+            parse_outcome(Some(2), "", "touches prod\n"),
+            HookOutcome::Deny {
+                reason: "touches prod".to_string()
+            }
+        );
+        assert_eq!(
+            parse_outcome(Some(1), "", "boom"),
+            HookOutcome::Failed("exited with status 1: boom".to_string())
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn first_veto_stops_later_hooks() {
+        let cwd = std::env::temp_dir();
+        let env = HashMap::from([("HOOK_NOTE".to_string(), "seen".to_string())]);
+        let hooks = vec![
+            test_hook(&["sh", "-c", "cat >/dev/null; echo \"$CODE_HOOK $HOOK_NOTE\""], Some(env)),
+            test_hook(&["sh", "-c", "grep -q rm && { echo 'no rm' >&2; exit 2; }; exit 0"], None),
+            test_hook(&["sh", "-c", "echo unreachable"], None),
+        ];
+        let payload = serde_json::json!({ "command": ["rm", "-rf", "build"] });
+        let verdict = run_script_hooks(&hooks, ScriptHookPoint::PreExec, &cwd, &payload).await;
+        assert_eq!(
+            verdict,
+            ScriptHookVerdict {
+                denied: Some(("sh".to_string(), "no rm".to_string())),
+                annotations: vec![("sh".to_string(), "pre_exec seen".to_string())],
+                failures: Vec::new(),
+            }
+        );
+    }
+}
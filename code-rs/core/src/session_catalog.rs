@@ -3,6 +3,7 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 use code_protocol::protocol::SessionSource;
@@ -33,6 +34,60 @@ pub struct SessionQuery {
     pub limit: Option<usize>,
 }
 
+impl SessionQuery {
+    fn matches(&self, entry: &SessionIndexEntry) -> bool {
+        if !self.include_archived && entry.archived {
+            return false;
+        }
+        if !self.include_deleted && entry.deleted {
+            return false;
+        }
+        if let Some(cwd) = &self.cwd
+            && &entry.cwd_real != cwd {
+                return false;
+            }
+        if let Some(git_root) = &self.git_root
+            && entry.git_project_root.as_ref() != Some(git_root) {
+                return false;
+            }
+        if !self.sources.is_empty() && !self.sources.contains(&entry.session_source) {
+            return false;
+        }
+        entry.user_message_count >= self.min_user_messages
+    }
+
+    fn select(&self, catalog: &rollout_catalog::SessionCatalog) -> Vec<SessionIndexEntry> {
+        let candidates: Vec<&SessionIndexEntry> = if let Some(cwd) = &self.cwd {
+            catalog.by_cwd(cwd)
+        } else if let Some(git_root) = &self.git_root {
+            catalog.by_git_root(git_root)
+        } else {
+            catalog.all_ordered()
+        };
+
+        let matching = candidates
+            .into_iter()
+            .filter(|entry| self.matches(entry))
+            .cloned();
+        match self.limit {
+            Some(limit) => matching.take(limit).collect(),
+            None => matching.collect(),
+        }
+    }
+}
+
+/// Minimum gap between provisional result sets from
+/// [`SessionCatalog::query_streaming`] while rollouts are being parsed.
+const PARTIAL_UPDATE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Progress reported while loading the catalog.
+enum CatalogProgress<'a> {
+    /// The index as loaded from disk or cache, before rescanning.
+    Indexed(&'a rollout_catalog::SessionCatalog),
+    /// One rollout file parsed during the rescan.
+    Parsed(&'a SessionIndexEntry),
+}
+
 /// Public catalog facade used by TUI/CLI/Exec entrypoints.
 pub struct SessionCatalog {
     code_home: PathBuf,
@@ -49,47 +104,62 @@ impl SessionCatalog {
     /// Query the catalog with the provided filters, returning ordered entries.
     pub async fn query(&self, query: &SessionQuery) -> Result<Vec<SessionIndexEntry>> {
         let catalog = self.load_inner().await?;
-        let mut rows = Vec::new();
+        Ok(query.select(&catalog))
+    }
 
-        let candidates: Vec<&SessionIndexEntry> = if let Some(cwd) = &query.cwd {
-            catalog.by_cwd(cwd)
-        } else if let Some(git_root) = &query.git_root {
-            catalog.by_git_root(git_root)
-        } else {
-            catalog.all_ordered()
+    /// Like [`Self::query`], but calls `on_partial` with provisional results
+    /// while the sessions directory is rescanned: first the rows already in
+    /// the index, then (throttled) the index merged with freshly parsed
+    /// rollouts. The returned rows are authoritative.
+    pub async fn query_streaming(
+        &self,
+        query: &SessionQuery,
+        mut on_partial: impl FnMut(Vec<SessionIndexEntry>) + Send,
+    ) -> Result<Vec<SessionIndexEntry>> {
+        let mut rows: HashMap<Uuid, SessionIndexEntry> = HashMap::new();
+        let mut last_emit: Option<Instant> = None;
+        let mut emit = |rows: &HashMap<Uuid, SessionIndexEntry>| {
+            let mut partial: Vec<SessionIndexEntry> = rows.values().cloned().collect();
+            partial.sort_by_key(SessionIndexEntry::ordering_key);
+            if let Some(limit) = query.limit {
+                partial.truncate(limit);
+            }
+            on_partial(partial);
         };
 
-        for entry in candidates {
-            if !query.include_archived && entry.archived {
-                continue;
-            }
-            if !query.include_deleted && entry.deleted {
-                continue;
-            }
-            if let Some(cwd) = &query.cwd
-                && &entry.cwd_real != cwd {
-                    continue;
+        let catalog = self
+            .load_with_progress(&mut |progress| match progress {
+                CatalogProgress::Indexed(catalog) => {
+                    rows.extend(
+                        query
+                            .select(catalog)
+                            .into_iter()
+                            .map(|entry| (entry.session_id, entry)),
+                    );
+                    if !rows.is_empty() {
+                        emit(&rows);
+                        last_emit = Some(Instant::now());
+                    }
                 }
-            if let Some(git_root) = &query.git_root
-                && entry.git_project_root.as_ref() != Some(git_root) {
-                    continue;
+                CatalogProgress::Parsed(entry) => {
+                    if !query.matches(entry) {
+                        return;
+                    }
+                    let mut entry = entry.clone();
+                    if entry.nickname.is_none() {
+                        entry.nickname = rows
+                            .get(&entry.session_id)
+                            .and_then(|existing| existing.nickname.clone());
+                    }
+                    rows.insert(entry.session_id, entry);
+                    if last_emit.is_none_or(|at| at.elapsed() >= PARTIAL_UPDATE_INTERVAL) {
+                        emit(&rows);
+                        last_emit = Some(Instant::now());
+                    }
                 }
-            if !query.sources.is_empty() && !query.sources.contains(&entry.session_source) {
-                continue;
-            }
-            if entry.user_message_count < query.min_user_messages {
-                continue;
-            }
-
-            rows.push(entry.clone());
-
-            if let Some(limit) = query.limit
-                && rows.len() >= limit {
-                    break;
-                }
-        }
-
-        Ok(rows)
+            })
+            .await?;
+        Ok(query.select(&catalog))
     }
 
     /// Find a session by UUID (prefix matches allowed, case-insensitive).
@@ -236,13 +306,33 @@ impl SessionCatalog {
     }
 
     async fn load_inner(&self) -> Result<rollout_catalog::SessionCatalog> {
+        self.load_with_progress(&mut |_| {}).await
+    }
+
+    async fn load_with_progress(
+        &self,
+        on_progress: &mut (dyn FnMut(CatalogProgress<'_>) + Send),
+    ) -> Result<rollout_catalog::SessionCatalog> {
         {
             let mut guard = self.cache.lock().await;
             if let Some(existing) = guard.as_mut() {
-                existing
-                    .reconcile(&self.code_home)
+                // Rescanning the sessions tree is deferrable; serve the cached
+                // index while the device is low on battery or hot.
+                let constraint = task::spawn_blocking(crate::battery::background_work_constraint)
                     .await
-                    .context("failed to reconcile session catalog")?;
+                    .ok()
+                    .flatten();
+                if let Some(constraint) = constraint {
+                    tracing::debug!("skipping session catalog reconcile: {constraint}");
+                } else {
+                    on_progress(CatalogProgress::Indexed(existing));
+                    existing
+                        .reconcile_with_progress(&self.code_home, &mut |entry| {
+                            on_progress(CatalogProgress::Parsed(entry))
+                        })
+                        .await
+                        .context("failed to reconcile session catalog")?;
+                }
                 return Ok(existing.clone());
             }
         }
@@ -253,8 +343,11 @@ impl SessionCatalog {
             .context("catalog task panicked")?
             .context("failed to load session catalog")?;
 
+        on_progress(CatalogProgress::Indexed(&catalog));
         catalog
-            .reconcile(&self.code_home)
+            .reconcile_with_progress(&self.code_home, &mut |entry| {
+                on_progress(CatalogProgress::Parsed(entry))
+            })
             .await
             .context("failed to reconcile session catalog")?;
 
@@ -0,0 +1,182 @@
+//! Fan-out of one conversation's events to any number of clients.
+//!
+//! A [`SessionHub`] owns the only reader of a conversation's event stream.
+//! Every event gets a sequence number (0 is the `SessionConfigured` event),
+//! is kept in a bounded backlog and is broadcast to live subscribers. A
+//! client subscribes with the last sequence number it has seen and gets the
+//! newer events from the backlog followed by live ones, so reconnecting
+//! clients catch up without gaps. `code daemon` and `code serve` both serve
+//! conversations through a hub.
+
+use std::collections::VecDeque;
+use std::io;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+use crate::CodexConversation;
+use crate::protocol::Event;
+use crate::protocol::EventMsg;
+
+/// Events kept for replay. A client that reattaches after the backlog has
+/// rolled over sees the session from the oldest retained event onward.
+const BACKLOG_LIMIT: usize = 20_000;
+
+/// Live events buffered per client before a slow client lags (it
+/// resubscribes and catches up from the backlog).
+const LIVE_CHANNEL_CAPACITY: usize = 1024;
+
+struct Backlog {
+    session_configured: Event,
+    events: VecDeque<(u64, Event)>,
+    next_seq: u64,
+}
+
+impl Backlog {
+    fn push(&mut self, event: Event) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        if self.events.len() == BACKLOG_LIMIT {
+            self.events.pop_front();
+        }
+        self.events.push_back((seq, event));
+        seq
+    }
+
+    fn since(&self, since: Option<u64>) -> Vec<(u64, Event)> {
+        let mut replay = Vec::new();
+        if since.is_none() {
+            replay.push((0, self.session_configured.clone()));
+        }
+        replay.extend(
+            self.events
+                .iter()
+                .filter(|(seq, _)| is_newer(*seq, since))
+                .cloned(),
+        );
+        replay
+    }
+}
+
+/// Whether the event numbered `seq` comes after `since` (`None` means the
+/// client has seen nothing yet).
+pub fn is_newer(seq: u64, since: Option<u64>) -> bool {
+    since.is_none_or(|since| seq > since)
+}
+
+pub struct SessionHub {
+    conversation: Arc<CodexConversation>,
+    backlog: Mutex<Backlog>,
+    live_tx: broadcast::Sender<(u64, Event)>,
+}
+
+impl SessionHub {
+    /// Starts reading `conversation`'s events into a new hub. The returned
+    /// task finishes once the conversation shuts down or its stream ends.
+    pub fn start(
+        conversation: Arc<CodexConversation>,
+        session_configured: Event,
+    ) -> (Arc<Self>, JoinHandle<()>) {
+        let (live_tx, _) = broadcast::channel(LIVE_CHANNEL_CAPACITY);
+        let hub = Arc::new(Self {
+            conversation,
+            backlog: Mutex::new(Backlog {
+                session_configured,
+                events: VecDeque::new(),
+                next_seq: 1,
+            }),
+            live_tx,
+        });
+        let pump = tokio::spawn(pump_events(hub.clone()));
+        (hub, pump)
+    }
+
+    pub fn conversation(&self) -> &Arc<CodexConversation> {
+        &self.conversation
+    }
+
+    /// Events after `since` from the backlog, and a receiver for the ones
+    /// that follow. Together they contain every event exactly once, as long
+    /// as the receiver keeps up; drop duplicates with [`is_newer`].
+    pub fn subscribe(
+        &self,
+        since: Option<u64>,
+    ) -> io::Result<(Vec<(u64, Event)>, broadcast::Receiver<(u64, Event)>)> {
+        let backlog = self
+            .backlog
+            .lock()
+            .map_err(|_| io::Error::other("session backlog lock poisoned"))?;
+        Ok((backlog.since(since), self.live_tx.subscribe()))
+    }
+}
+
+async fn pump_events(hub: Arc<SessionHub>) {
+    loop {
+        let event = match hub.conversation.next_event().await {
+            Ok(event) => event,
+            Err(err) => {
+                debug!("session event stream ended: {err}");
+                return;
+            }
+        };
+        let shutdown = matches!(event.msg, EventMsg::ShutdownComplete);
+        // Record and publish under the lock so a subscribing client sees each
+        // event exactly once, either in its replay or on its live receiver.
+        if let Ok(mut backlog) = hub.backlog.lock() {
+            let seq = backlog.push(event.clone());
+            let _ = hub.live_tx.send((seq, event));
+        }
+        if shutdown {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn event(id: &str) -> Event {
+        Event {
+            id: id.to_string(),
+            event_seq: 0,
+            msg: EventMsg::ShutdownComplete,
+            order: None,
+        }
+    }
+
+    #[test]
+    fn replays_events_after_last_seen_sequence() {
+        let mut backlog = Backlog {
+            session_configured: event("configured"),
+            events: VecDeque::new(),
+            next_seq: 1,
+        };
+        for id in ["a", "b", "c"] {
+            backlog.push(event(id));
+        }
+        let ids = |since| {
+            backlog
+                .since(since)
+                .into_iter()
+                .map(|(seq, event)| (seq, event.id))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ids(None),
+            vec![
+                (0, "configured".to_string()),
+                (1, "a".to_string()),
+                (2, "b".to_string()),
+                (3, "c".to_string()),
+            ]
+        );
+        assert_eq!(ids(Some(0)), ids(None)[1..].to_vec());
+        assert_eq!(ids(Some(2)), vec![(3, "c".to_string())]);
+        assert_eq!(ids(Some(3)), Vec::new());
+    }
+}
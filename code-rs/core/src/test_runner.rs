@@ -0,0 +1,720 @@
+//! The `run_tests` tool: runs a project's tests with a runner it knows
+//! (`cargo test`, pytest, jest) and turns the output into structured
+//! results, one entry per failing test with its location, message and
+//! output. The TUI renders the report as a failures cell.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Output kept per failing test; the head holds the panic or assertion.
+const MAX_FAILURE_OUTPUT_BYTES: usize = 4_000;
+
+/// Output tail returned when a run fails without any parsed test failure
+/// (a compile error, a crashed runner).
+const MAX_OUTPUT_TAIL_BYTES: usize = 8_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestRunner {
+    Cargo,
+    Pytest,
+    Jest,
+}
+
+impl fmt::Display for TestRunner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TestRunner::Cargo => "cargo",
+            TestRunner::Pytest => "pytest",
+            TestRunner::Jest => "jest",
+        })
+    }
+}
+
+impl TestRunner {
+    /// The command line for this runner. `filter` selects tests by name
+    /// (`cargo test <filter>`, `pytest -k <filter>`, `jest -t <filter>`);
+    /// `args` are passed through before it.
+    pub fn command(self, filter: Option<&str>, args: &[String]) -> Vec<String> {
+        let mut command: Vec<String> = match self {
+            TestRunner::Cargo => vec!["cargo", "test", "--no-fail-fast"],
+            TestRunner::Pytest => vec!["pytest", "-rfE"],
+            TestRunner::Jest => vec!["npx", "jest", "--ci"],
+        }
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+        command.extend(args.iter().cloned());
+        if let Some(filter) = filter.map(str::trim).filter(|filter| !filter.is_empty()) {
+            match self {
+                TestRunner::Cargo => {}
+                TestRunner::Pytest => command.push("-k".to_string()),
+                TestRunner::Jest => command.push("-t".to_string()),
+            }
+            command.push(filter.to_string());
+        }
+        command
+    }
+
+    fn parse_failures(self, output: &str) -> Vec<TestFailure> {
+        match self {
+            TestRunner::Cargo => parse_cargo_failures(output),
+            TestRunner::Pytest => parse_pytest_failures(output),
+            TestRunner::Jest => parse_jest_failures(output),
+        }
+    }
+
+    fn parse_counts(self, output: &str) -> TestCounts {
+        match self {
+            TestRunner::Cargo => output
+                .lines()
+                .filter_map(|line| line.trim().strip_prefix("test result:"))
+                .map(|summary| count_words(summary, &[("passed", 0), ("failed", 1), ("ignored", 2)]))
+                .fold(TestCounts::default(), TestCounts::add),
+            TestRunner::Pytest => output
+                .lines()
+                .rev()
+                .find(|line| line.starts_with('=') && line.contains(" in ") && has_count(line))
+                .map(|line| {
+                    count_words(
+                        line,
+                        &[
+                            ("passed", 0),
+                            ("failed", 1),
+                            ("error", 1),
+                            ("errors", 1),
+                            ("skipped", 2),
+                        ],
+                    )
+                })
+                .unwrap_or_default(),
+            TestRunner::Jest => output
+                .lines()
+                .find_map(|line| line.trim().strip_prefix("Tests:"))
+                .map(|summary| {
+                    count_words(summary, &[("passed", 0), ("failed", 1), ("skipped", 2), ("todo", 2)])
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// The runner for `dir`: the nearest directory (up to the git root) with a
+/// `Cargo.toml`, a jest `package.json` or a pytest configuration, and that
+/// directory, where the runner should start.
+pub fn detect_runner(dir: &Path) -> Option<(TestRunner, PathBuf)> {
+    let boundary = crate::git_info::get_git_repo_root(dir);
+    for candidate in dir.ancestors() {
+        if let Some(runner) = runner_at(candidate) {
+            return Some((runner, candidate.to_path_buf()));
+        }
+        if boundary.as_deref() == Some(candidate) {
+            break;
+        }
+    }
+    None
+}
+
+fn runner_at(dir: &Path) -> Option<TestRunner> {
+    if dir.join("Cargo.toml").is_file() {
+        return Some(TestRunner::Cargo);
+    }
+    let mentions = |name: &str, needle: &str| {
+        fs::read_to_string(dir.join(name)).is_ok_and(|text| text.contains(needle))
+    };
+    if mentions("package.json", "jest")
+        || dir.join("jest.config.js").is_file()
+        || dir.join("jest.config.ts").is_file()
+    {
+        return Some(TestRunner::Jest);
+    }
+    if dir.join("pytest.ini").is_file()
+        || dir.join("conftest.py").is_file()
+        || mentions("pyproject.toml", "[tool.pytest")
+        || mentions("setup.cfg", "[tool:pytest]")
+        || mentions("tox.ini", "[pytest]")
+    {
+        return Some(TestRunner::Pytest);
+    }
+    None
+}
+
+/// One failing test.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestFailure {
+    /// The runner's name for the test (`tests::parses`, `tests/test_io.py::test_read`).
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    /// The assertion or panic message, usually one line.
+    #[serde(default)]
+    pub message: String,
+    /// The runner's output for this test alone.
+    #[serde(default)]
+    pub output: String,
+}
+
+impl TestFailure {
+    /// `file:line`, `file`, or nothing.
+    pub fn location(&self) -> Option<String> {
+        match (self.file.as_deref(), self.line) {
+            (Some(file), Some(line)) => Some(format!("{file}:{line}")),
+            (Some(file), None) => Some(file.to_string()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct TestCounts {
+    passed: Option<u32>,
+    failed: Option<u32>,
+    skipped: Option<u32>,
+}
+
+impl TestCounts {
+    fn add(self, other: TestCounts) -> TestCounts {
+        let sum = |a: Option<u32>, b: Option<u32>| match (a, b) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+        };
+        TestCounts {
+            passed: sum(self.passed, other.passed),
+            failed: sum(self.failed, other.failed),
+            skipped: sum(self.skipped, other.skipped),
+        }
+    }
+}
+
+/// The result of one `run_tests` call, returned to the model as JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestReport {
+    pub runner: TestRunner,
+    pub command: String,
+    pub cwd: PathBuf,
+    pub exit_code: i32,
+    #[serde(default)]
+    pub timed_out: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passed: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<u32>,
+    #[serde(default)]
+    pub failures: Vec<TestFailure>,
+    /// The end of the output, when the run failed but no test failure could
+    /// be parsed from it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_tail: Option<String>,
+}
+
+impl TestReport {
+    pub fn from_output(
+        runner: TestRunner,
+        command: &[String],
+        cwd: PathBuf,
+        exit_code: i32,
+        timed_out: bool,
+        output: &str,
+    ) -> Self {
+        let failures = runner.parse_failures(output);
+        let counts = runner.parse_counts(output);
+        let output_tail = (exit_code != 0 && failures.is_empty()).then(|| tail(output, MAX_OUTPUT_TAIL_BYTES));
+        Self {
+            runner,
+            command: crate::util::strip_bash_lc_and_escape(command),
+            cwd,
+            exit_code,
+            timed_out,
+            passed: counts.passed,
+            failed: counts.failed.or((!failures.is_empty()).then_some(failures.len() as u32)),
+            skipped: counts.skipped,
+            failures,
+            output_tail,
+        }
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.exit_code == 0 && !self.timed_out && self.failures.is_empty()
+    }
+
+    /// Only the failing tests' output, for attaching to the next prompt.
+    pub fn failures_context(&self) -> Option<String> {
+        if self.failures.is_empty() {
+            return None;
+        }
+        let mut text = format!(
+            "<failing_tests runner=\"{}\" command=\"{}\">\n",
+            self.runner, self.command
+        );
+        for failure in &self.failures {
+            let location = failure
+                .location()
+                .map(|location| format!(" location=\"{location}\""))
+                .unwrap_or_default();
+            text.push_str(&format!(
+                "<test name=\"{}\"{location}>\n{}\n</test>\n",
+                failure.name,
+                failure.output.trim_end()
+            ));
+        }
+        text.push_str("</failing_tests>");
+        Some(text)
+    }
+}
+
+fn has_count(line: &str) -> bool {
+    ["passed", "failed", "error", "skipped"]
+        .iter()
+        .any(|word| line.contains(word))
+}
+
+/// Reads `<n> <word>` pairs (`3 passed; 1 failed`, `1 failed, 2 passed`)
+/// into counts; `slots` maps a word to passed (0), failed (1) or skipped (2).
+fn count_words(summary: &str, slots: &[(&str, usize)]) -> TestCounts {
+    let mut counts = [None::<u32>; 3];
+    let words: Vec<&str> = summary
+        .split(|c: char| c.is_whitespace() || c == ',' || c == ';' || c == '=')
+        .filter(|word| !word.is_empty())
+        .collect();
+    for pair in words.windows(2) {
+        let Ok(count) = pair[0].parse::<u32>() else {
+            continue;
+        };
+        if let Some((_, slot)) = slots.iter().find(|(word, _)| *word == pair[1]) {
+            counts[*slot] = Some(counts[*slot].unwrap_or(0) + count);
+        }
+    }
+    TestCounts {
+        passed: counts[0],
+        failed: counts[1],
+        skipped: counts[2],
+    }
+}
+
+fn head(text: &str, max: usize) -> String {
+    let text = text.trim_matches('\n');
+    if text.len() <= max {
+        return text.to_string();
+    }
+    let mut cut = max;
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    format!("{}\n[… {} more bytes]", &text[..cut], text.len() - cut)
+}
+
+fn tail(text: &str, max: usize) -> String {
+    let text = text.trim_matches('\n');
+    if text.len() <= max {
+        return text.to_string();
+    }
+    let mut cut = text.len() - max;
+    while !text.is_char_boundary(cut) {
+        cut += 1;
+    }
+    format!("[… {cut} earlier bytes]\n{}", &text[cut..])
+}
+
+/// `path:line` or `path:line:column` at the end of `text`.
+fn split_location(text: &str) -> Option<(String, u32)> {
+    let text = text.trim().trim_end_matches([':', ')']);
+    let mut parts = text.rsplitn(3, ':');
+    let last = parts.next()?;
+    let middle = parts.next()?;
+    match (parts.next(), middle.parse::<u32>(), last.parse::<u32>()) {
+        (Some(file), Ok(line), Ok(_column)) if !file.is_empty() => Some((file.to_string(), line)),
+        (_, _, Ok(line)) if !middle.is_empty() && middle.parse::<u32>().is_err() => {
+            Some((middle.to_string(), line))
+        }
+        _ => None,
+    }
+}
+
+/// `---- name stdout ----` blocks in the `failures:` section of
+/// `cargo test`, with the location and message of the panic inside.
+fn parse_cargo_failures(output: &str) -> Vec<TestFailure> {
+    let mut failures = Vec::new();
+    let mut current: Option<(String, Vec<&str>)> = None;
+    let mut finish = |current: Option<(String, Vec<&str>)>| {
+        if let Some((name, lines)) = current {
+            failures.push(cargo_failure(name, &lines));
+        }
+    };
+    for line in output.lines() {
+        let header = line
+            .strip_prefix("---- ")
+            .and_then(|rest| rest.strip_suffix(" stdout ----"));
+        if let Some(name) = header {
+            finish(current.take());
+            current = Some((name.to_string(), Vec::new()));
+        } else if line == "failures:" || line.starts_with("test result:") {
+            finish(current.take());
+        } else if let Some((_, lines)) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+    finish(current);
+    failures
+}
+
+fn cargo_failure(name: String, lines: &[&str]) -> TestFailure {
+    let mut failure = TestFailure {
+        name,
+        output: head(&lines.join("\n"), MAX_FAILURE_OUTPUT_BYTES),
+        ..TestFailure::default()
+    };
+    let Some((index, panic_line)) = lines
+        .iter()
+        .enumerate()
+        .find(|(_, line)| line.contains("panicked at "))
+    else {
+        failure.message = lines
+            .iter()
+            .map(|line| line.trim())
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .to_string();
+        return failure;
+    };
+    let after = panic_line
+        .split_once("panicked at ")
+        .map(|(_, rest)| rest)
+        .unwrap_or_default();
+    if let Some(quoted) = after.strip_prefix('\'') {
+        // Before Rust 1.73: `panicked at 'message', src/lib.rs:10:5`.
+        if let Some((message, location)) = quoted.rsplit_once("', ") {
+            failure.message = message.to_string();
+            if let Some((file, line)) = split_location(location) {
+                failure.file = Some(file);
+                failure.line = Some(line);
+            }
+        }
+    } else {
+        if let Some((file, line)) = split_location(after) {
+            failure.file = Some(file);
+            failure.line = Some(line);
+        }
+        failure.message = lines[index + 1..]
+            .iter()
+            .take_while(|line| !line.starts_with("note:") && !line.starts_with("stack backtrace:"))
+            .map(|line| line.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+    }
+    failure
+}
+
+/// `FAILED path::test - message` lines of pytest's short summary (`-rfE`),
+/// matched with the `____ test ____` blocks of its failures section.
+fn parse_pytest_failures(output: &str) -> Vec<TestFailure> {
+    let mut blocks: Vec<(String, Vec<&str>)> = Vec::new();
+    let mut in_section = false;
+    for line in output.lines() {
+        if line.starts_with("====") {
+            in_section = line.contains(" FAILURES ") || line.contains(" ERRORS ");
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+        let title = line
+            .strip_prefix("___")
+            .map(|rest| rest.trim_matches('_').trim())
+            .filter(|title| !title.is_empty() && line.trim_end().ends_with("___"));
+        match (title, blocks.last_mut()) {
+            (Some(title), _) => blocks.push((title.to_string(), Vec::new())),
+            (None, Some((_, lines))) => lines.push(line),
+            (None, None) => {}
+        }
+    }
+
+    let mut failures: Vec<TestFailure> = output
+        .lines()
+        .filter_map(|line| {
+            line.strip_prefix("FAILED ")
+                .or_else(|| line.strip_prefix("ERROR "))
+        })
+        .map(|entry| {
+            let (node, message) = entry.split_once(" - ").unwrap_or((entry, ""));
+            let node = node.trim();
+            TestFailure {
+                name: node.to_string(),
+                file: node.split("::").next().filter(|file| file.ends_with(".py")).map(str::to_string),
+                message: message.trim().to_string(),
+                ..TestFailure::default()
+            }
+        })
+        .collect();
+
+    for (title, lines) in blocks {
+        // Block titles are `test_name` or `TestClass.test_name`, possibly
+        // with parameters; node ids use `::`.
+        let suffix = title.replace('.', "::");
+        let position = failures
+            .iter()
+            .position(|failure| failure.output.is_empty() && failure.name.ends_with(&suffix));
+        let failure = match position {
+            Some(index) => &mut failures[index],
+            None => {
+                failures.push(TestFailure {
+                    name: title.clone(),
+                    ..TestFailure::default()
+                });
+                let last = failures.len() - 1;
+                &mut failures[last]
+            }
+        };
+        failure.output = head(&lines.join("\n"), MAX_FAILURE_OUTPUT_BYTES);
+        // `tests/test_io.py:12: AssertionError` marks the failing line.
+        if let Some((file, line)) = lines.iter().rev().find_map(|line| {
+            let (location, _) = line.split_once(": ")?;
+            let (file, line) = split_location(location)?;
+            file.ends_with(".py").then_some((file, line))
+        }) {
+            failure.file = Some(file);
+            failure.line = Some(line);
+        }
+        if failure.message.is_empty() {
+            failure.message = lines
+                .iter()
+                .find_map(|line| line.strip_prefix("E "))
+                .map(|message| message.trim().to_string())
+                .unwrap_or_default();
+        }
+    }
+    failures
+}
+
+/// `● Suite › test` blocks of jest, located by the first stack frame in
+/// the failing test file.
+fn parse_jest_failures(output: &str) -> Vec<TestFailure> {
+    let mut failures = Vec::new();
+    let mut file: Option<String> = None;
+    let mut current: Option<(String, Option<String>, Vec<&str>)> = None;
+    let mut finish = |current: Option<(String, Option<String>, Vec<&str>)>| {
+        if let Some((name, file, lines)) = current {
+            failures.push(jest_failure(name, file, &lines));
+        }
+    };
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(path) = trimmed.strip_prefix("FAIL ") {
+            finish(current.take());
+            file = Some(path.trim().to_string());
+        } else if trimmed.starts_with("PASS ")
+            || trimmed.starts_with("Test Suites:")
+            || trimmed.starts_with("Summary of all failing tests")
+        {
+            finish(current.take());
+            if trimmed.starts_with("Summary of all failing tests") {
+                // Jest repeats every failure at the end; the first pass has them all.
+                break;
+            }
+        } else if let Some(name) = trimmed.strip_prefix("● ") {
+            finish(current.take());
+            current = Some((name.trim().to_string(), file.clone(), Vec::new()));
+        } else if let Some((_, _, lines)) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+    finish(current);
+    failures
+}
+
+fn jest_failure(name: String, file: Option<String>, lines: &[&str]) -> TestFailure {
+    let frames: Vec<(String, u32)> = lines
+        .iter()
+        .filter_map(|line| {
+            let frame = line.trim().strip_prefix("at ")?;
+            let location = frame
+                .rsplit_once('(')
+                .map_or(frame, |(_, location)| location);
+            split_location(location)
+        })
+        .filter(|(path, _)| !path.contains("node_modules"))
+        .collect();
+    // Prefer the frame in the test file, named as jest printed it.
+    let location = frames
+        .iter()
+        .find(|(path, _)| file.as_deref().is_some_and(|file| path.ends_with(file)))
+        .map(|(_, line)| (file.clone().unwrap_or_default(), *line))
+        .or_else(|| frames.first().cloned());
+    let message = lines
+        .iter()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or_default()
+        .to_string();
+    let (file, line) = match location {
+        Some((path, line)) => (Some(path), Some(line)),
+        None => (file, None),
+    };
+    TestFailure {
+        name,
+        file,
+        line,
+        message,
+        output: head(&lines.join("\n"), MAX_FAILURE_OUTPUT_BYTES),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn cargo_failures_carry_the_panic_location_and_message() {
+        let output = "\
+running 3 tests
+test tests::adds ... ok
+test tests::parses ... FAILED
+test tests::legacy ... FAILED
+
+failures:
+
+---- tests::parses stdout ----
+
+thread 'tests::parses' panicked at src/lib.rs:42:9:
+assertion `left == right` failed
+  left: 1
+ right: 2
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+---- tests::legacy stdout ----
+thread 'tests::legacy' panicked at 'boom', src/old.rs:7:5
+
+
+failures:
+    tests::legacy
+    tests::parses
+
+test result: FAILED. 1 passed; 2 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.01s
+";
+        let report = TestReport::from_output(
+            TestRunner::Cargo,
+            &TestRunner::Cargo.command(None, &[]),
+            PathBuf::from("/repo"),
+            101,
+            false,
+            output,
+        );
+        assert_eq!((report.passed, report.failed, report.skipped), (Some(1), Some(2), Some(1)));
+        assert_eq!(report.output_tail, None);
+        let [parses, legacy] = report.failures.as_slice() else {
+            panic!("expected two failures, got {:?}", report.failures);
+        };
+        assert_eq!(parses.name, "tests::parses");
+        assert_eq!(parses.location().as_deref(), Some("src/lib.rs:42"));
+        assert_eq!(parses.message, "assertion `left == right` failed\n  left: 1\n right: 2");
+        assert!(parses.output.starts_with("thread 'tests::parses' panicked"));
+        assert_eq!(legacy.location().as_deref(), Some("src/old.rs:7"));
+        assert_eq!(legacy.message, "boom");
+    }
+
+    #[test]
+    fn pytest_failures_pair_the_summary_with_their_blocks() {
+        let output = "\
+============================= test session starts ==============================
+collected 3 items
+
+tests/test_io.py .F.                                                     [100%]
+
+=================================== FAILURES ===================================
+___________________________ TestReader.test_reads ____________________________
+
+self = <tests.test_io.TestReader object at 0x1>
+
+    def test_reads(self):
+>       assert read() == \"b\"
+E       AssertionError: assert 'a' == 'b'
+
+tests/test_io.py:12: AssertionError
+=========================== short test summary info ============================
+FAILED tests/test_io.py::TestReader::test_reads - AssertionError: assert 'a' == 'b'
+========================= 1 failed, 2 passed in 0.05s ==========================
+";
+        let report = TestReport::from_output(
+            TestRunner::Pytest,
+            &TestRunner::Pytest.command(Some("reads"), &[]),
+            PathBuf::from("/repo"),
+            1,
+            false,
+            output,
+        );
+        assert_eq!(report.command, "pytest -rfE -k reads");
+        assert_eq!((report.passed, report.failed), (Some(2), Some(1)));
+        let [failure] = report.failures.as_slice() else {
+            panic!("expected one failure, got {:?}", report.failures);
+        };
+        assert_eq!(failure.name, "tests/test_io.py::TestReader::test_reads");
+        assert_eq!(failure.location().as_deref(), Some("tests/test_io.py:12"));
+        assert_eq!(failure.message, "AssertionError: assert 'a' == 'b'");
+        assert!(failure.output.contains(">       assert read() == \"b\""));
+    }
+
+    #[test]
+    fn jest_failures_use_the_frame_in_the_test_file() {
+        let output = "\
+FAIL src/sum.test.js
+  ● sum › adds numbers
+
+    expect(received).toBe(expected) // Object.is equality
+
+    Expected: 4
+    Received: 3
+
+      at Object.<anonymous> (src/sum.test.js:4:21)
+      at Promise.then.completed (node_modules/jest-circus/build/utils.js:298:28)
+
+PASS src/other.test.js
+
+Test Suites: 1 failed, 1 passed, 2 total
+Tests:       1 failed, 3 passed, 4 total
+";
+        let report = TestReport::from_output(
+            TestRunner::Jest,
+            &TestRunner::Jest.command(None, &[]),
+            PathBuf::from("/repo"),
+            1,
+            false,
+            output,
+        );
+        assert_eq!((report.passed, report.failed), (Some(3), Some(1)));
+        let [failure] = report.failures.as_slice() else {
+            panic!("expected one failure, got {:?}", report.failures);
+        };
+        assert_eq!(failure.name, "sum › adds numbers");
+        assert_eq!(failure.location().as_deref(), Some("src/sum.test.js:4"));
+        assert_eq!(failure.message, "expect(received).toBe(expected) // Object.is equality");
+    }
+
+    #[test]
+    fn failed_runs_without_parsed_failures_keep_the_output_tail() {
+        let report = TestReport::from_output(
+            TestRunner::Cargo,
+            &TestRunner::Cargo.command(None, &[]),
+            PathBuf::from("/repo"),
+            101,
+            false,
+            "error[E0425]: cannot find value `x` in this scope\n",
+        );
+        assert!(!report.succeeded());
+        assert_eq!(
+            report.output_tail.as_deref(),
+            Some("error[E0425]: cannot find value `x` in this scope")
+        );
+        assert_eq!(report.failures_context(), None);
+    }
+}
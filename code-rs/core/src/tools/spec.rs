@@ -23,6 +23,10 @@ pub struct ToolsConfig {
     #[allow(dead_code)]
     pub include_view_image_tool: bool,
     pub web_search_allowed_domains: Option<Vec<String>>,
+    pub fetch_url: bool,
+    pub fetch_url_allowed_domains: Option<Vec<String>>,
+    pub artifacts: bool,
+    pub run_tests: bool,
     pub agent_model_allowed_values: Vec<String>,
 }
 
@@ -88,6 +92,10 @@ impl ToolsConfig {
             search_tool: false,
             include_view_image_tool,
             web_search_allowed_domains: None,
+            fetch_url: false,
+            fetch_url_allowed_domains: None,
+            artifacts: false,
+            run_tests: false,
             agent_model_allowed_values: Vec::new(),
         }
     }
@@ -0,0 +1,330 @@
+//! `fetch_url` and `/fetch`: download a page over plain HTTP, keep its
+//! readable part (article or main content, without navigation, scripts and
+//! other chrome) and convert it to markdown. Unlike the browser tool's `fetch`
+//! action this never starts a browser, so documentation lookups stay fast.
+//! Requests are limited to `[tools] fetch_url_allowed_domains` when it is set.
+
+use std::time::Duration;
+
+use url::Url;
+
+/// Characters of markdown kept from a fetched page.
+pub const DEFAULT_FETCH_MAX_CHARS: usize = 60_000;
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(20);
+const MAX_REDIRECTS: usize = 10;
+/// Response bodies beyond this are cut before extraction.
+const MAX_BODY_BYTES: usize = 5 * 1024 * 1024;
+
+/// Elements that never carry page content.
+const SKIPPED_TAGS: [&str; 13] = [
+    "script", "style", "noscript", "template", "svg", "canvas", "iframe", "nav", "header",
+    "footer", "aside", "form", "button",
+];
+
+const FETCHED_URL_CLOSE_TAG: &str = "</fetched_url>";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchedPage {
+    /// URL as requested (after normalisation).
+    pub url: String,
+    /// URL after redirects.
+    pub final_url: String,
+    pub title: Option<String>,
+    pub markdown: String,
+    /// Whether `markdown` was cut to the character limit.
+    pub truncated: bool,
+}
+
+impl FetchedPage {
+    /// Developer message carrying the page into the model's context.
+    pub fn to_context_message(&self) -> String {
+        let mut message = format!("<fetched_url url=\"{}\">\n", self.final_url);
+        if let Some(title) = &self.title {
+            message.push_str(&format!("# {title}\n\n"));
+        }
+        message.push_str(self.markdown.trim_end());
+        if self.truncated {
+            message.push_str("\n\n[page truncated]");
+        }
+        message.push('\n');
+        message.push_str(FETCHED_URL_CLOSE_TAG);
+        message
+    }
+
+    /// JSON body returned by the `fetch_url` tool. The `markdown` field is
+    /// what the TUI renders as the preview.
+    pub fn to_tool_output(&self) -> serde_json::Value {
+        serde_json::json!({
+            "url": self.url,
+            "final_url": self.final_url,
+            "title": self.title,
+            "truncated": self.truncated,
+            "markdown": self.markdown,
+        })
+    }
+}
+
+/// Whether `host` is one of `allowed` or a subdomain of one. `None` or an
+/// empty list allows every host.
+pub fn domain_allowed(host: &str, allowed: Option<&[String]>) -> bool {
+    let Some(allowed) = allowed.filter(|allowed| !allowed.is_empty()) else {
+        return true;
+    };
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    allowed.iter().any(|domain| {
+        let domain = domain
+            .trim()
+            .trim_start_matches("*.")
+            .trim_end_matches('.')
+            .to_ascii_lowercase();
+        !domain.is_empty()
+            && (host == domain
+                || host
+                    .strip_suffix(domain.as_str())
+                    .is_some_and(|prefix| prefix.ends_with('.')))
+    })
+}
+
+/// Parses `raw` as an http(s) URL (a bare `docs.rs/serde` gets `https://`)
+/// and checks its host against `allowed`.
+pub fn parse_fetch_url(raw: &str, allowed: Option<&[String]>) -> Result<Url, String> {
+    let raw = raw.trim();
+    let url = match Url::parse(raw) {
+        Ok(url) => url,
+        Err(url::ParseError::RelativeUrlWithoutBase) => Url::parse(&format!("https://{raw}"))
+            .map_err(|err| format!("invalid URL `{raw}`: {err}"))?,
+        Err(err) => return Err(format!("invalid URL `{raw}`: {err}")),
+    };
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("only http and https URLs can be fetched, not `{}`", url.scheme()));
+    }
+    let Some(host) = url.host_str() else {
+        return Err(format!("URL `{raw}` has no host"));
+    };
+    if !domain_allowed(host, allowed) {
+        return Err(format!("`{host}` is not in `[tools] fetch_url_allowed_domains`"));
+    }
+    Ok(url)
+}
+
+/// Downloads `raw_url` and returns its readable content as markdown of at
+/// most `max_chars` characters. Redirects are followed only within `allowed`.
+pub async fn fetch_url(
+    raw_url: &str,
+    allowed: Option<&[String]>,
+    max_chars: usize,
+) -> Result<FetchedPage, String> {
+    let url = parse_fetch_url(raw_url, allowed)?;
+    let redirect_allowed = allowed.map(<[String]>::to_vec);
+    let redirect_policy = reqwest::redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() >= MAX_REDIRECTS {
+            attempt.error("too many redirects")
+        } else if attempt
+            .url()
+            .host_str()
+            .is_some_and(|host| domain_allowed(host, redirect_allowed.as_deref()))
+        {
+            attempt.follow()
+        } else {
+            attempt.stop()
+        }
+    });
+    let client = crate::http_client::apply_network_config(reqwest::Client::builder())
+        .timeout(FETCH_TIMEOUT)
+        .user_agent(crate::default_client::get_code_user_agent(Some("fetch_url")))
+        .redirect(redirect_policy)
+        .build()
+        .map_err(|err| format!("failed to build HTTP client: {err}"))?;
+
+    let response = client
+        .get(url.clone())
+        .header(
+            reqwest::header::ACCEPT,
+            "text/html,application/xhtml+xml,text/plain;q=0.9,*/*;q=0.5",
+        )
+        .send()
+        .await
+        .map_err(|err| format!("request to {url} failed: {err}"))?;
+    let status = response.status();
+    let final_url = response.url().to_string();
+    if status.is_redirection() {
+        let target = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("another site");
+        return Err(format!(
+            "{url} redirects to {target}, which is outside `[tools] fetch_url_allowed_domains`"
+        ));
+    }
+    if !status.is_success() {
+        return Err(format!("{final_url} returned HTTP {status}"));
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("text/html")
+        .to_ascii_lowercase();
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|err| format!("failed to read {final_url}: {err}"))?;
+    let body = String::from_utf8_lossy(&bytes[..bytes.len().min(MAX_BODY_BYTES)]).into_owned();
+
+    let (title, markdown) = if content_type.contains("html") {
+        html_to_readable_markdown(&body)?
+    } else if content_type.starts_with("text/")
+        || content_type.contains("json")
+        || content_type.contains("xml")
+    {
+        (None, body)
+    } else {
+        return Err(format!("{final_url} is `{content_type}`, not a text page"));
+    };
+
+    let (markdown, truncated) = truncate_chars(markdown.trim(), max_chars);
+    Ok(FetchedPage {
+        url: url.to_string(),
+        final_url,
+        title,
+        markdown,
+        truncated,
+    })
+}
+
+/// Title and markdown of the page's readable content: the first `<article>`,
+/// else `<main>`, else `<body>`, with navigation, scripts and forms dropped.
+fn html_to_readable_markdown(html: &str) -> Result<(Option<String>, String), String> {
+    // Titles hold no markup, so anything from a later `</...` (e.g. an SVG
+    // `<title>` further down) is not part of the page title.
+    let title = element_inner(html, "title")
+        .and_then(|title| title.split("</").next())
+        .map(|title| decode_entities(title).split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|title| !title.is_empty());
+    let content = ["article", "main", "body"]
+        .iter()
+        .filter_map(|tag| element_inner(html, tag))
+        .find(|inner| !inner.trim().is_empty())
+        .unwrap_or(html);
+
+    let options = htmd::options::Options {
+        heading_style: htmd::options::HeadingStyle::Atx,
+        code_block_style: htmd::options::CodeBlockStyle::Fenced,
+        link_style: htmd::options::LinkStyle::Inlined,
+        ..Default::default()
+    };
+    let converter = htmd::HtmlToMarkdown::builder()
+        .options(options)
+        .skip_tags(SKIPPED_TAGS.to_vec())
+        .build();
+    let markdown = converter
+        .convert(content)
+        .map_err(|err| format!("failed to convert the page to markdown: {err}"))?;
+    Ok((title, collapse_blank_lines(&markdown)))
+}
+
+/// Contents between the first `<tag ...>` and the last `</tag>`, matched
+/// ASCII case-insensitively.
+fn element_inner<'a>(html: &'a str, tag: &str) -> Option<&'a str> {
+    // ASCII lowercasing keeps byte offsets aligned with `html`.
+    let lower = html.to_ascii_lowercase();
+    let open_pattern = format!("<{tag}");
+    let mut search_from = 0;
+    let open_end = loop {
+        let start = search_from + lower[search_from..].find(&open_pattern)?;
+        let after = start + open_pattern.len();
+        match lower.as_bytes().get(after) {
+            Some(b'>') => break after + 1,
+            Some(byte) if byte.is_ascii_whitespace() => {
+                break after + lower[after..].find('>')? + 1;
+            }
+            _ => search_from = after,
+        }
+    };
+    let close = lower[open_end..]
+        .rfind(&format!("</{tag}"))
+        .map_or(html.len(), |close| open_end + close);
+    Some(&html[open_end..close])
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+fn collapse_blank_lines(markdown: &str) -> String {
+    let mut out = String::with_capacity(markdown.len());
+    let mut blank_run = 0;
+    for line in markdown.lines() {
+        if line.trim().is_empty() {
+            blank_run += 1;
+            if blank_run > 1 {
+                continue;
+            }
+        } else {
+            blank_run = 0;
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> (String, bool) {
+    match text.char_indices().nth(max_chars) {
+        Some((cut, _)) => (text[..cut].to_string(), true),
+        None => (text.to_string(), false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn allowlist_matches_domains_and_their_subdomains() {
+        let allowed = vec!["docs.rs".to_string(), "*.mozilla.org".to_string()];
+        assert!(domain_allowed("docs.rs", Some(&allowed)));
+        assert!(domain_allowed("DOCS.RS", Some(&allowed)));
+        assert!(domain_allowed("developer.mozilla.org", Some(&allowed)));
+        assert!(!domain_allowed("notdocs.rs", Some(&allowed)));
+        assert!(!domain_allowed("example.com", Some(&allowed)));
+        assert!(domain_allowed("example.com", None));
+        assert!(domain_allowed("example.com", Some(&[])));
+
+        assert_eq!(
+            parse_fetch_url("docs.rs/serde", Some(&allowed)).map(|url| url.to_string()),
+            Ok("https://docs.rs/serde".to_string())
+        );
+        assert!(parse_fetch_url("https://example.com", Some(&allowed)).is_err());
+        assert!(parse_fetch_url("file:///etc/passwd", None).is_err());
+    }
+
+    #[test]
+    fn readable_markdown_keeps_the_article_and_drops_chrome() {
+        let html = "<html><head><title>Serde &amp; you</title><script>track()</script></head>\
+            <body><nav><a href=\"/\">Home</a></nav>\
+            <ARTICLE class=\"doc\"><h1>Derive</h1><p>Use <code>#[derive(Serialize)]</code>.</p>\
+            <script>ads()</script></ARTICLE><footer>© site</footer></body></html>";
+        let (title, markdown) = html_to_readable_markdown(html).expect("convert");
+        assert_eq!(title.as_deref(), Some("Serde & you"));
+        assert!(markdown.starts_with("# Derive\n"));
+        assert!(markdown.contains("`#[derive(Serialize)]`"));
+        for chrome in ["Home", "track", "ads", "site"] {
+            assert!(!markdown.contains(chrome), "{chrome} leaked into {markdown:?}");
+        }
+    }
+
+    #[test]
+    fn truncation_counts_characters() {
+        assert_eq!(truncate_chars("héllo", 2), ("hé".to_string(), true));
+        assert_eq!(truncate_chars("héllo", 5), ("héllo".to_string(), false));
+    }
+}
@@ -44,7 +44,7 @@ pub fn format_bytes(bytes: usize) -> String {
 /// Blocks until the given endpoint responds, pausing between attempts with
 /// exponential backoff (capped). Used to pause retries while the user is
 /// offline so we resume immediately once connectivity returns.
-pub(crate) async fn wait_for_connectivity(probe_url: &str) {
+pub async fn wait_for_connectivity(probe_url: &str) {
     // Cap individual waits to avoid very long sleeps while still backing off.
     const MAX_DELAY: Duration = Duration::from_secs(30);
     let client = reqwest::Client::new();
@@ -0,0 +1,248 @@
+//! Outbound webhooks configured under `[[webhooks]]`.
+//!
+//! Turn completion, patch apply and error events are POSTed to every
+//! webhook that selected them as a JSON object:
+//!
+//! ```json
+//! { "delivery_id": "…", "event": "turn_complete", "timestamp": "…",
+//!   "session_id": "…", "turn_id": "…", "cwd": "…", "user": "…",
+//!   "data": { "last_agent_message": "…" } }
+//! ```
+//!
+//! When a secret is configured the request carries
+//! `X-Code-Signature: sha256=<hex HMAC-SHA256 of the body>`. Deliveries are
+//! fire-and-forget: a slow or failing endpoint is logged and never holds up
+//! the session.
+
+use std::path::Path;
+use std::time::Duration;
+
+use serde_json::Value;
+use serde_json::json;
+use sha2::Digest;
+use sha2::Sha256;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::config_types::WebhookConfig;
+use crate::config_types::WebhookEventKind;
+use crate::protocol::Event;
+use crate::protocol::EventMsg;
+
+const DEFAULT_WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_TEXT_CHARS: usize = 4_000;
+
+pub(crate) const SIGNATURE_HEADER: &str = "X-Code-Signature";
+pub(crate) const EVENT_HEADER: &str = "X-Code-Event";
+pub(crate) const DELIVERY_HEADER: &str = "X-Code-Delivery";
+
+impl WebhookEventKind {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            WebhookEventKind::TurnComplete => "turn_complete",
+            WebhookEventKind::PatchApply => "patch_apply",
+            WebhookEventKind::Error => "error",
+        }
+    }
+}
+
+/// Sends session events to the configured webhooks.
+pub(crate) struct WebhookEmitter {
+    webhooks: Vec<WebhookConfig>,
+    client: reqwest::Client,
+}
+
+impl WebhookEmitter {
+    /// `None` when no webhooks are configured.
+    pub(crate) fn new(webhooks: &[WebhookConfig]) -> Option<Self> {
+        if webhooks.is_empty() {
+            return None;
+        }
+        Some(Self {
+            webhooks: webhooks.to_vec(),
+            client: crate::default_client::create_client(crate::default_client::DEFAULT_ORIGINATOR),
+        })
+    }
+
+    /// Queues a delivery of `event` to every webhook that selected its kind.
+    pub(crate) fn dispatch(&self, session_id: Uuid, cwd: &Path, event: &Event) {
+        let Some((kind, data)) = event_payload(&event.msg) else {
+            return;
+        };
+        let targets: Vec<WebhookConfig> = self
+            .webhooks
+            .iter()
+            .filter(|webhook| wants(webhook, kind))
+            .cloned()
+            .collect();
+        if targets.is_empty() {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        let delivery_id = Uuid::new_v4().to_string();
+        let body = json!({
+            "delivery_id": delivery_id,
+            "event": kind.as_str(),
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "session_id": session_id.to_string(),
+            "turn_id": event.id,
+            "cwd": cwd,
+            "user": std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok(),
+            "data": data,
+        })
+        .to_string();
+
+        for webhook in targets {
+            let client = self.client.clone();
+            let body = body.clone();
+            let delivery_id = delivery_id.clone();
+            runtime.spawn(async move {
+                deliver(&client, &webhook, kind, &delivery_id, body).await;
+            });
+        }
+    }
+}
+
+fn wants(webhook: &WebhookConfig, kind: WebhookEventKind) -> bool {
+    webhook
+        .events
+        .as_ref()
+        .is_none_or(|events| events.contains(&kind))
+}
+
+async fn deliver(
+    client: &reqwest::Client,
+    webhook: &WebhookConfig,
+    kind: WebhookEventKind,
+    delivery_id: &str,
+    body: String,
+) {
+    let timeout = webhook
+        .timeout_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_WEBHOOK_TIMEOUT);
+    let mut request = client
+        .post(&webhook.url)
+        .timeout(timeout)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, kind.as_str())
+        .header(DELIVERY_HEADER, delivery_id);
+    if let Some(secret) = webhook.secret.as_deref().filter(|secret| !secret.is_empty()) {
+        request = request.header(SIGNATURE_HEADER, signature(secret.as_bytes(), body.as_bytes()));
+    }
+    match request.body(body).send().await {
+        Ok(response) if response.status().is_success() => {}
+        Ok(response) => warn!(
+            "webhook {} answered {} for {}",
+            webhook.url,
+            response.status(),
+            kind.as_str()
+        ),
+        Err(err) => warn!("webhook {} failed for {}: {err}", webhook.url, kind.as_str()),
+    }
+}
+
+/// The event kind and `data` object for events webhooks report on.
+fn event_payload(msg: &EventMsg) -> Option<(WebhookEventKind, Value)> {
+    match msg {
+        EventMsg::TaskComplete(event) => Some((
+            WebhookEventKind::TurnComplete,
+            json!({ "last_agent_message": event.last_agent_message.as_deref().map(clip) }),
+        )),
+        EventMsg::PatchApplyEnd(event) => Some((
+            WebhookEventKind::PatchApply,
+            json!({
+                "call_id": event.call_id,
+                "success": event.success,
+                "stdout": clip(&event.stdout),
+                "stderr": clip(&event.stderr),
+            }),
+        )),
+        EventMsg::Error(event) => Some((
+            WebhookEventKind::Error,
+            json!({ "message": clip(&event.message) }),
+        )),
+        _ => None,
+    }
+}
+
+fn clip(text: &str) -> String {
+    if text.chars().count() > MAX_TEXT_CHARS {
+        text.chars().take(MAX_TEXT_CHARS).collect::<String>() + "…"
+    } else {
+        text.to_string()
+    }
+}
+
+/// `sha256=<hex>` value of the signature header for `body`.
+pub(crate) fn signature(secret: &[u8], body: &[u8]) -> String {
+    let mac = hmac_sha256(secret, body);
+    let hex: String = mac.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("sha256={hex}")
+}
+
+/// HMAC-SHA256 as specified in RFC 2104.
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 64;
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha256::new();
+    inner.update(block.map(|byte| byte ^ 0x36));
+    inner.update(message);
+    let inner_hash = inner.finalize();
+
+    let mut outer = Sha256::new();
+    outer.update(block.map(|byte| byte ^ 0x5c));
+    outer.update(inner_hash);
+    outer.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::ErrorEvent;
+    use crate::protocol::TokenCountEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn signature_matches_rfc_4231_vector() {
+        assert_eq!(
+            signature(b"Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn only_selected_lifecycle_events_are_delivered() {
+        let error = EventMsg::Error(ErrorEvent {
+            message: "stream disconnected".to_string(),
+        });
+        let (kind, data) = event_payload(&error).expect("error payload");
+        assert_eq!(kind, WebhookEventKind::Error);
+        assert_eq!(data, json!({ "message": "stream disconnected" }));
+
+        let token_count = EventMsg::TokenCount(TokenCountEvent {
+            info: None,
+            rate_limits: None,
+            session_cost_usd: None,
+        });
+        assert!(event_payload(&token_count).is_none());
+
+        let webhook = WebhookConfig {
+            url: "https://example.com/hook".to_string(),
+            secret: None,
+            events: Some(vec![WebhookEventKind::PatchApply]),
+            timeout_ms: None,
+        };
+        assert!(wants(&webhook, WebhookEventKind::PatchApply));
+        assert!(!wants(&webhook, WebhookEventKind::Error));
+    }
+}
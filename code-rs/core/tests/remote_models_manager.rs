@@ -60,6 +60,9 @@ fn provider_for(base_url: String) -> ModelProviderInfo {
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
         openrouter: None,
+        local: false,
+        supports_reasoning_summaries: None,
+        stop: None,
     }
 }
 
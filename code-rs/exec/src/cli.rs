@@ -128,6 +128,12 @@ pub struct Cli {
     #[arg(long = "review-output-json", value_name = "FILE")]
     pub review_output_json: Option<PathBuf>,
 
+    /// Run a script of prompts in order, one turn each, in the same session.
+    /// Prompts are separated by lines containing only `---`. Use `-` to read
+    /// the script from stdin.
+    #[arg(long = "prompts-file", value_name = "FILE", conflicts_with = "prompt")]
+    pub prompts_file: Option<PathBuf>,
+
     /// Initial instructions for the agent. If not provided as an argument (or
     /// if `-` is used), instructions are read from stdin.
     #[arg(value_name = "PROMPT")]
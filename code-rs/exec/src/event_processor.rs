@@ -2,6 +2,7 @@ use std::path::Path;
 
 use code_core::config::Config;
 use code_core::protocol::Event;
+use code_core::protocol::ReviewDecision;
 
 pub(crate) enum CodexStatus {
     Running,
@@ -16,6 +17,12 @@ pub(crate) trait EventProcessor {
     /// Handle a single event emitted by the agent.
     fn process_event(&mut self, event: Event) -> CodexStatus;
 
+    /// A prompt was submitted as turn `turn` (1-based) of the run.
+    fn print_turn_started(&mut self, _turn: usize, _prompt: &str) {}
+
+    /// An approval request was answered without a user, per the run's policy.
+    fn print_approval_resolved(&mut self, _call_id: &str, _decision: ReviewDecision) {}
+
     /// Emit final user-facing output once the run has completed.
     /// Default no-op for processors that do not have a final-output concept.
     fn print_final_output(&mut self) {}
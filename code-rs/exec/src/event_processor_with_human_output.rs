@@ -19,6 +19,7 @@ use code_core::protocol::McpToolCallBeginEvent;
 use code_core::protocol::McpToolCallEndEvent;
 use code_core::protocol::PatchApplyBeginEvent;
 use code_core::protocol::PatchApplyEndEvent;
+use code_core::protocol::ReviewDecision;
 use code_core::protocol::SessionConfiguredEvent;
 use code_core::protocol::TaskCompleteEvent;
 use code_protocol::protocol::TurnAbortReason;
@@ -198,6 +199,30 @@ impl EventProcessor for EventProcessorWithHumanOutput {
         );
     }
 
+    fn print_turn_started(&mut self, turn: usize, prompt: &str) {
+        // The first prompt was already echoed with the config summary.
+        if turn > 1 {
+            ts_println!(
+                self,
+                "{}\n{}",
+                format!("User instructions (turn {turn}):").style(self.bold).style(self.cyan),
+                prompt
+            );
+        }
+    }
+
+    fn print_approval_resolved(&mut self, call_id: &str, decision: ReviewDecision) {
+        let outcome = match decision {
+            ReviewDecision::Approved | ReviewDecision::ApprovedForSession => "approved",
+            ReviewDecision::Denied | ReviewDecision::Abort => "denied",
+        };
+        ts_println!(
+            self,
+            "{} {call_id}: {outcome} (non-interactive)",
+            "approval".style(self.magenta)
+        );
+    }
+
     fn process_event(&mut self, event: Event) -> CodexStatus {
         let Event { id, msg, .. } = event;
         match msg {
@@ -359,7 +384,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 );
             }
             EventMsg::ExecCommandOutputDelta(_) => {}
-            EventMsg::ExecCommandEnd(ExecCommandEndEvent { call_id, stdout, stderr, duration, exit_code }) => {
+            EventMsg::ExecCommandEnd(ExecCommandEndEvent { call_id, stdout, stderr, duration, exit_code, .. }) => {
                 let exec_command = self.call_id_to_command.remove(&call_id);
                 let (duration, call) = if let Some(ExecCommandBegin { command, .. }) = exec_command
                 {
@@ -565,6 +590,32 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     eprintln!("{}", line.style(self.dimmed));
                 }
             }
+            EventMsg::LspDiagnostics(ev) => {
+                for error in &ev.errors {
+                    ts_println!(self, "{} {error}", "lsp:".style(self.red));
+                }
+                for file in &ev.files {
+                    for diagnostic in &file.diagnostics {
+                        let style = match diagnostic.severity {
+                            code_core::protocol::DiagnosticSeverity::Error => self.red,
+                            _ => self.dimmed,
+                        };
+                        let marker = if diagnostic.is_new { " (new)" } else { "" };
+                        eprintln!(
+                            "{}",
+                            format!(
+                                "{}:{}:{} {:?}{marker}: {}",
+                                file.path.display(),
+                                diagnostic.line,
+                                diagnostic.column,
+                                diagnostic.severity,
+                                diagnostic.message
+                            )
+                            .style(style)
+                        );
+                    }
+                }
+            }
             EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => {
                 if self.last_turn_diff.as_deref() == Some(&unified_diff) {
                     // Suppress duplicate turn diffs; they are sometimes streamed multiple times.
@@ -4,6 +4,7 @@ use std::path::PathBuf;
 use code_core::config::Config;
 use code_core::protocol::Event;
 use code_core::protocol::EventMsg;
+use code_core::protocol::ReviewDecision;
 use code_core::protocol::TaskCompleteEvent;
 use serde_json::json;
 
@@ -41,6 +42,28 @@ impl EventProcessor for EventProcessorWithJsonOutput {
         println!("{prompt_json}");
     }
 
+    #[allow(clippy::print_stdout)]
+    fn print_turn_started(&mut self, turn: usize, prompt: &str) {
+        let turn_json = json!({
+            "turn_started": {
+                "turn": turn,
+                "prompt": prompt,
+            },
+        });
+        println!("{turn_json}");
+    }
+
+    #[allow(clippy::print_stdout)]
+    fn print_approval_resolved(&mut self, call_id: &str, decision: ReviewDecision) {
+        let approval_json = json!({
+            "approval_resolved": {
+                "call_id": call_id,
+                "decision": decision,
+            },
+        });
+        println!("{approval_json}");
+    }
+
     #[allow(clippy::print_stdout)]
     fn process_event(&mut self, event: Event) -> CodexStatus {
         match event.msg {
@@ -49,7 +72,11 @@ impl EventProcessor for EventProcessorWithJsonOutput {
                 // Suppress streaming events in JSON mode.
                 CodexStatus::Running
             }
-            EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message }) => {
+            EventMsg::TaskComplete(TaskCompleteEvent { ref last_agent_message }) => {
+                // The final answer of the turn.
+                if let Ok(line) = serde_json::to_string(&event) {
+                    println!("{line}");
+                }
                 if let Some(output_file) = self.last_message_path.as_deref() {
                     handle_last_message(last_agent_message.as_deref(), output_file);
                 }
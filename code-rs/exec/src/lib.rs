@@ -42,6 +42,7 @@ use supports_color::Stream;
 use tokio::time::{Duration, Instant};
 use tracing::info;
 use tracing_subscriber::EnvFilter;
+use tracing_subscriber::prelude::*;
 
 use crate::auto_drive_session::build_auto_drive_exec_config;
 use crate::auto_drive_session::run_auto_drive_session;
@@ -80,6 +81,7 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
         json: json_mode,
         sandbox_mode: sandbox_mode_cli_arg,
         prompt,
+        prompts_file,
         output_schema: output_schema_path,
         include_plan_tool,
         config_overrides,
@@ -97,13 +99,14 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
     let PreparedRunInputs {
         mut review_request,
         mut prompt_to_send,
+        followup_prompts,
         mut summary_prompt,
         auto_drive_goal,
         images,
         timeboxed_auto_exec,
-    } = prepare_run_inputs(&command, prompt, images, auto_drive, max_seconds);
+    } = prepare_run_inputs(&command, prompt, prompts_file, images, auto_drive, max_seconds);
 
-    let _output_schema = load_output_schema(output_schema_path);
+    let output_schema = load_output_schema(output_schema_path);
 
     let (stdout_with_ansi, stderr_with_ansi) = match color {
         cli::Color::Always => (true, true),
@@ -122,12 +125,6 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
         .or_else(|_| EnvFilter::try_new(default_level))
         .unwrap_or_else(|_| EnvFilter::new(default_level));
 
-    let _ = tracing_subscriber::fmt()
-        .with_env_filter(env_filter)
-        .with_ansi(stderr_with_ansi)
-        .with_writer(std::io::stderr)
-        .try_init();
-
     let sandbox_mode = if full_auto {
         Some(SandboxMode::WorkspaceWrite)
     } else if dangerously_bypass_approvals_and_sandbox {
@@ -153,14 +150,27 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
         None // No specific model provider override.
     };
 
+    // Parse `-c` overrides.
+    let cli_kv_overrides = match config_overrides.parse_overrides() {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Error parsing -c overrides: {e}");
+            std::process::exit(1);
+        }
+    };
+    // This CLI is intended to be headless and has no affordances for asking
+    // the user for approval. An explicit `-c approval_policy=...` still wins:
+    // approval requests are then answered from the sandbox policy.
+    let approval_policy_requested = cli_kv_overrides
+        .iter()
+        .any(|(key, _)| key == "approval_policy");
+
     // Load configuration and determine approval policy
     let overrides = ConfigOverrides {
         model,
         review_model: None,
         config_profile,
-        // This CLI is intended to be headless and has no affordances for asking
-        // the user for approval.
-        approval_policy: Some(AskForApproval::Never),
+        approval_policy: (!approval_policy_requested).then_some(AskForApproval::Never),
         sandbox_mode,
         cwd: cwd.map(|p| p.canonicalize().unwrap_or(p)),
         model_provider,
@@ -179,16 +189,38 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
         compact_prompt_override: None,
         compact_prompt_override_file: None,
     };
-    // Parse `-c` overrides.
-    let cli_kv_overrides = match config_overrides.parse_overrides() {
-        Ok(v) => v,
-        Err(e) => {
-            eprintln!("Error parsing -c overrides: {e}");
-            std::process::exit(1);
+
+    let mut config = Config::load_with_cli_overrides(cli_kv_overrides, overrides)?;
+    code_core::http_client::set_network_config(&config.network);
+
+    // The subscriber waits for the config so it can include the OTEL layers.
+    let otel = match code_core::otel_init::build_provider(&config, env!("CARGO_PKG_VERSION")) {
+        Ok(provider) => provider,
+        Err(err) => {
+            eprintln!("Could not start OpenTelemetry export: {err}");
+            None
         }
     };
+    let otel_log_layer = otel.as_ref().and_then(|provider| provider.log_layer()).map(|layer| {
+        layer.with_filter(tracing_subscriber::filter::filter_fn(
+            code_core::otel_init::code_export_filter,
+        ))
+    });
+    let otel_trace_layer = otel.as_ref().and_then(|provider| provider.trace_layer()).map(|layer| {
+        layer.with_filter(tracing_subscriber::filter::filter_fn(
+            code_core::otel_init::code_trace_filter,
+        ))
+    });
+    let fmt_layer = tracing_subscriber::fmt::layer()
+        .with_ansi(stderr_with_ansi)
+        .with_writer(std::io::stderr)
+        .with_filter(env_filter);
+    let _ = tracing_subscriber::registry()
+        .with(fmt_layer)
+        .with(otel_log_layer)
+        .with(otel_trace_layer)
+        .try_init();
 
-    let mut config = Config::load_with_cli_overrides(cli_kv_overrides, overrides)?;
     config.max_run_seconds = max_seconds;
     config.max_run_deadline = run_deadline_std;
     config.demo_developer_message = cli.demo_developer_message.clone();
@@ -220,6 +252,10 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
                     summary_prompt = summary;
                 }
             }
+            Ok(SlashDispatch::Review { .. }) if !followup_prompts.is_empty() => {
+                eprintln!("A prompt script cannot start with /review.");
+                std::process::exit(1);
+            }
             Ok(SlashDispatch::Review { request, summary }) => {
                 review_request = Some(request);
                 if auto_drive_goal.is_none() {
@@ -415,12 +451,14 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
         event_processor: event_processor.as_mut(),
         review_request,
         prompt_to_send,
+        followup_prompts,
         images,
         run_deadline,
         max_seconds,
         auto_resolve_state,
         max_auto_resolve_attempts,
         is_auto_review,
+        output_schema,
     })
     .await?;
     if let Some(path) = review_output_json
@@ -1,6 +1,7 @@
 use serde_json::Value;
 use std::io::IsTerminal;
 use std::io::Read;
+use std::path::Path;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -117,6 +118,62 @@ pub(crate) fn resolve_prompt(prompt_arg: Option<String>) -> String {
     }
 }
 
+/// Reads a `--prompts-file` script (`-` for stdin) and splits it into prompts.
+pub(crate) fn load_prompt_script(path: &Path) -> Vec<String> {
+    let bytes = if path == Path::new("-") {
+        let mut bytes = Vec::new();
+        if let Err(error) = std::io::stdin().read_to_end(&mut bytes) {
+            eprintln!("Failed to read prompt script from stdin: {error}");
+            std::process::exit(1);
+        }
+        bytes
+    } else {
+        match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                eprintln!("Failed to read prompt script {}: {error}", path.display());
+                std::process::exit(1);
+            }
+        }
+    };
+
+    let script = match decode_prompt_bytes(&bytes) {
+        Ok(decoded) => decoded,
+        Err(error) => {
+            eprintln!("Failed to read prompt script {}: {error}", path.display());
+            std::process::exit(1);
+        }
+    };
+
+    let prompts = split_prompt_script(&script);
+    if prompts.is_empty() {
+        eprintln!("Prompt script {} contains no prompts.", path.display());
+        std::process::exit(1);
+    }
+    prompts
+}
+
+/// Splits a prompt script on lines that contain only `---`, dropping blank
+/// prompts.
+pub(crate) fn split_prompt_script(script: &str) -> Vec<String> {
+    let mut prompts = Vec::new();
+    let mut current = String::new();
+    for line in script.lines() {
+        if line.trim() == "---" {
+            prompts.push(std::mem::take(&mut current));
+            continue;
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+    prompts.push(current);
+    prompts
+        .into_iter()
+        .map(|prompt| prompt.trim().to_string())
+        .filter(|prompt| !prompt.is_empty())
+        .collect()
+}
+
 pub(crate) fn load_output_schema(path: Option<PathBuf>) -> Option<Value> {
     let path = path?;
 
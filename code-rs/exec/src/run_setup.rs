@@ -1,5 +1,6 @@
 use crate::auto_runtime::append_timeboxed_auto_drive_goal;
 use crate::cli::Command as ExecCommand;
+use crate::prompt_input::load_prompt_script;
 use crate::prompt_input::resolve_prompt;
 use crate::review_command::build_review_request;
 use crate::review_command::review_summary;
@@ -9,6 +10,8 @@ use std::path::PathBuf;
 pub(crate) struct PreparedRunInputs {
     pub(crate) review_request: Option<ReviewRequest>,
     pub(crate) prompt_to_send: String,
+    /// Remaining `--prompts-file` prompts, sent one turn at a time.
+    pub(crate) followup_prompts: Vec<String>,
     pub(crate) summary_prompt: String,
     pub(crate) auto_drive_goal: Option<String>,
     pub(crate) images: Vec<PathBuf>,
@@ -18,6 +21,7 @@ pub(crate) struct PreparedRunInputs {
 pub(crate) fn prepare_run_inputs(
     command: &Option<ExecCommand>,
     prompt: Option<String>,
+    prompts_file: Option<PathBuf>,
     images: Vec<PathBuf>,
     auto_drive: bool,
     max_seconds: Option<u64>,
@@ -53,8 +57,18 @@ pub(crate) fn prepare_run_inputs(
         std::process::exit(1);
     }
 
+    if prompts_file.is_some() && (review_request.is_some() || auto_drive) {
+        eprintln!("--prompts-file cannot be combined with `review` or --auto.");
+        std::process::exit(1);
+    }
+
+    let mut followup_prompts = Vec::new();
     let prompt = if review_request.is_some() {
         String::new()
+    } else if let Some(path) = prompts_file.as_deref() {
+        let mut prompts = load_prompt_script(path);
+        followup_prompts = prompts.split_off(1);
+        prompts.remove(0)
     } else {
         resolve_prompt(prompt_arg)
     };
@@ -85,6 +99,11 @@ pub(crate) fn prepare_run_inputs(
         std::process::exit(1);
     }
 
+    if auto_drive_goal.is_some() && !followup_prompts.is_empty() {
+        eprintln!("A prompt script cannot start with /auto.");
+        std::process::exit(1);
+    }
+
     let timeboxed_auto_exec = auto_drive_goal.is_some() && max_seconds.is_some();
     if timeboxed_auto_exec
         && let Some(goal) = auto_drive_goal.as_mut() {
@@ -103,6 +122,7 @@ pub(crate) fn prepare_run_inputs(
     PreparedRunInputs {
         review_request,
         prompt_to_send,
+        followup_prompts,
         summary_prompt,
         auto_drive_goal,
         images,
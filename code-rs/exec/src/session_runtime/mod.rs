@@ -10,6 +10,7 @@ use code_core::config::Config;
 use code_core::protocol::ReviewOutputEvent;
 use code_core::protocol::ReviewRequest;
 use code_core::protocol::ReviewSnapshotInfo;
+use serde_json::Value;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::time::Instant;
@@ -20,12 +21,15 @@ pub(crate) struct SessionRuntimeParams<'a> {
     pub(crate) event_processor: &'a mut dyn crate::event_processor::EventProcessor,
     pub(crate) review_request: Option<ReviewRequest>,
     pub(crate) prompt_to_send: String,
+    pub(crate) followup_prompts: Vec<String>,
     pub(crate) images: Vec<PathBuf>,
     pub(crate) run_deadline: Option<Instant>,
     pub(crate) max_seconds: Option<u64>,
     pub(crate) auto_resolve_state: Option<AutoResolveState>,
     pub(crate) max_auto_resolve_attempts: u32,
     pub(crate) is_auto_review: bool,
+    /// `--output-schema`: every turn asks for JSON matching this schema.
+    pub(crate) output_schema: Option<Value>,
 }
 
 pub(crate) struct SessionRuntimeOutcome {
@@ -1,7 +1,9 @@
+mod approvals;
 mod exited_review;
 mod helpers;
 mod task_complete;
 
+use super::review_submission::submit_prompt;
 use super::state::ReviewRuntimeState;
 use crate::auto_review_status::AutoReviewTracker;
 use crate::auto_review_status::emit_auto_review_completion;
@@ -10,14 +12,18 @@ use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
 use code_core::CodexConversation;
 use code_core::config::Config;
+use code_core::output_schema::check_final_answer;
 use code_core::protocol::Event;
 use code_core::protocol::EventMsg;
 use code_core::protocol::Op;
 use code_core::protocol::ReviewRequest;
+use serde_json::Value;
+use std::collections::VecDeque;
 use std::sync::Arc;
 use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::time::Instant;
 
+use self::approvals::headless_approval;
 use self::exited_review::handle_exited_review_mode_event;
 use self::task_complete::handle_task_complete_event;
 
@@ -29,6 +35,10 @@ pub(super) struct ReviewEventLoopParams<'a> {
     pub(super) run_deadline: Option<Instant>,
     pub(super) max_seconds: Option<u64>,
     pub(super) rx: &'a mut UnboundedReceiver<Event>,
+    /// Prompt script turns still to send, in order.
+    pub(super) followup_prompts: VecDeque<String>,
+    /// `--output-schema`; a final answer that does not match fails the run.
+    pub(super) output_schema: Option<&'a Value>,
     pub(super) state: &'a mut ReviewRuntimeState,
 }
 
@@ -99,6 +109,8 @@ pub(super) async fn run_review_event_loop(
         max_seconds,
         rx,
         state,
+        mut followup_prompts,
+        output_schema,
     } = params;
 
     // Track whether a fatal error was reported by the server so we can
@@ -106,6 +118,7 @@ pub(super) async fn run_review_event_loop(
     let mut error_seen = false;
     let mut shutdown_state = ShutdownState::new(config.tui.auto_review_enabled);
     let mut auto_review_tracker = AutoReviewTracker::new(&config.cwd);
+    let mut turn = 1;
 
     loop {
         tokio::select! {
@@ -138,6 +151,13 @@ pub(super) async fn run_review_event_loop(
                 if matches!(event.msg, EventMsg::Error(_)) {
                     error_seen = true;
                 }
+                // Core reports the mismatch as a warning; scripts also get a failing exit status.
+                if let (Some(schema), EventMsg::TaskComplete(task_complete)) = (output_schema, &event.msg)
+                    && review_request.is_none()
+                    && check_final_answer(schema, task_complete.last_agent_message.as_deref()).is_some()
+                {
+                    error_seen = true;
+                }
 
                 let loop_control = match &event.msg {
                     EventMsg::ExitedReviewMode(review_event) => {
@@ -152,6 +172,7 @@ pub(super) async fn run_review_event_loop(
                             &auto_review_tracker,
                             &mut shutdown_state,
                             task_complete,
+                            !error_seen && !followup_prompts.is_empty(),
                         )
                         .await?
                     }
@@ -162,13 +183,26 @@ pub(super) async fn run_review_event_loop(
                     continue;
                 }
 
+                let approval = headless_approval(config, &event.msg);
                 let shutdown = event_processor.process_event(event);
+                if let Some(approval) = approval {
+                    approval.submit(conversation, event_processor).await?;
+                }
                 match shutdown {
                     CodexStatus::Running => {}
                     CodexStatus::InitiateShutdown => {
-                        shutdown_state
-                            .request(conversation, &auto_review_tracker)
-                            .await?;
+                        // A failed turn ends the prompt script.
+                        if !error_seen
+                            && let Some(prompt) = followup_prompts.pop_front()
+                        {
+                            turn += 1;
+                            event_processor.print_turn_started(turn, &prompt);
+                            submit_prompt(conversation, prompt, Vec::new(), output_schema.cloned()).await?;
+                        } else {
+                            shutdown_state
+                                .request(conversation, &auto_review_tracker)
+                                .await?;
+                        }
                     }
                     CodexStatus::Shutdown => {
                         break;
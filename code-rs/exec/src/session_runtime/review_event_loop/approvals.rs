@@ -0,0 +1,66 @@
+use crate::event_processor::EventProcessor;
+use code_core::CodexConversation;
+use code_core::config::Config;
+use code_core::protocol::EventMsg;
+use code_core::protocol::Op;
+use code_core::protocol::ReviewDecision;
+use code_core::protocol::SandboxPolicy;
+use std::sync::Arc;
+
+/// Exec has nobody to ask, so approval requests are answered from the run's
+/// policy: approved only when the sandbox was explicitly bypassed, otherwise
+/// denied so the agent can try another way instead of stalling the turn.
+fn headless_approval_decision(config: &Config) -> ReviewDecision {
+    if matches!(config.sandbox_policy, SandboxPolicy::DangerFullAccess) {
+        ReviewDecision::Approved
+    } else {
+        ReviewDecision::Denied
+    }
+}
+
+/// The answer to one approval request, to submit once the request itself has
+/// been reported.
+pub(super) struct HeadlessApproval {
+    call_id: String,
+    decision: ReviewDecision,
+    op: Op,
+}
+
+pub(super) fn headless_approval(config: &Config, msg: &EventMsg) -> Option<HeadlessApproval> {
+    let decision = headless_approval_decision(config);
+    let (call_id, op) = match msg {
+        EventMsg::ExecApprovalRequest(ev) => (
+            ev.call_id.clone(),
+            Op::ExecApproval {
+                id: ev.call_id.clone(),
+                turn_id: None,
+                decision,
+            },
+        ),
+        EventMsg::ApplyPatchApprovalRequest(ev) => (
+            ev.call_id.clone(),
+            Op::PatchApproval {
+                id: ev.call_id.clone(),
+                decision,
+            },
+        ),
+        _ => return None,
+    };
+    Some(HeadlessApproval {
+        call_id,
+        decision,
+        op,
+    })
+}
+
+impl HeadlessApproval {
+    pub(super) async fn submit(
+        self,
+        conversation: &Arc<CodexConversation>,
+        event_processor: &mut dyn EventProcessor,
+    ) -> anyhow::Result<()> {
+        conversation.submit(self.op).await?;
+        event_processor.print_approval_resolved(&self.call_id, self.decision);
+        Ok(())
+    }
+}
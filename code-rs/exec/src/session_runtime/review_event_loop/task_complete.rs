@@ -27,6 +27,7 @@ pub(super) async fn handle_task_complete_event(
     auto_review_tracker: &AutoReviewTracker,
     shutdown_state: &mut ShutdownState,
     task_complete: &TaskCompleteEvent,
+    script_continues: bool,
 ) -> anyhow::Result<LoopControl> {
     if let Some(state_snapshot) = state.auto_resolve_state.clone() {
         let current_epoch = current_snapshot_epoch_for(&config.cwd);
@@ -239,7 +240,8 @@ pub(super) async fn handle_task_complete_event(
         }
     }
 
-    if state.auto_resolve_state.is_none() && !shutdown_state.is_sent() {
+    // The event loop sends the next prompt of a script instead.
+    if state.auto_resolve_state.is_none() && !shutdown_state.is_sent() && !script_continues {
         state.auto_resolve_base_snapshot = None;
         shutdown_state.request(conversation, auto_review_tracker).await?;
     }
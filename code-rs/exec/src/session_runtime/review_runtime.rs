@@ -15,17 +15,22 @@ pub(crate) async fn run_session_runtime(
         event_processor,
         review_request,
         prompt_to_send,
+        followup_prompts,
         images,
         run_deadline,
         max_seconds,
         auto_resolve_state,
         max_auto_resolve_attempts: _max_auto_resolve_attempts,
         is_auto_review,
+        output_schema,
     } = params;
 
     let mut state = ReviewRuntimeState::new(auto_resolve_state);
     let mut rx = start_event_stream(conversation.clone());
 
+    if review_request.is_none() {
+        event_processor.print_turn_started(1, &prompt_to_send);
+    }
     let submitted = submit_initial_turn(
         &conversation,
         config,
@@ -33,6 +38,7 @@ pub(crate) async fn run_session_runtime(
         prompt_to_send,
         images,
         is_auto_review,
+        output_schema.clone(),
         &mut state,
     )
     .await?;
@@ -53,6 +59,8 @@ pub(crate) async fn run_session_runtime(
         run_deadline,
         max_seconds,
         rx: &mut rx,
+        followup_prompts: followup_prompts.into(),
+        output_schema: output_schema.as_ref(),
         state: &mut state,
     })
     .await?;
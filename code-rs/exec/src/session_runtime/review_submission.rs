@@ -11,6 +11,7 @@ use code_core::review_coord::bump_snapshot_epoch_for;
 use code_core::review_coord::clear_stale_lock_if_dead;
 use code_core::review_coord::current_snapshot_epoch_for;
 use code_core::review_coord::try_acquire_lock;
+use serde_json::Value;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tracing::info;
@@ -22,6 +23,7 @@ pub(super) async fn submit_initial_turn(
     prompt_to_send: String,
     images: Vec<PathBuf>,
     is_auto_review: bool,
+    output_schema: Option<Value>,
     state: &mut ReviewRuntimeState,
 ) -> anyhow::Result<bool> {
     // Clear stale review lock in case a prior process crashed.
@@ -90,19 +92,28 @@ pub(super) async fn submit_initial_turn(
         return Ok(true);
     }
 
+    submit_prompt(conversation, prompt_to_send, images, output_schema).await?;
+    Ok(true)
+}
+
+/// Sends `prompt` as a new user turn, asking for an answer matching
+/// `output_schema` when one is given.
+pub(super) async fn submit_prompt(
+    conversation: &Arc<CodexConversation>,
+    prompt: String,
+    images: Vec<PathBuf>,
+    output_schema: Option<Value>,
+) -> anyhow::Result<()> {
     let mut items: Vec<InputItem> = Vec::new();
-    items.push(InputItem::Text {
-        text: prompt_to_send,
-    });
+    items.push(InputItem::Text { text: prompt });
     items.extend(images.into_iter().map(|path| InputItem::LocalImage { path }));
 
-    // Fallback for older core protocol: send only user input items.
     let event_id = conversation
         .submit(Op::UserInput {
             items,
-            final_output_json_schema: None,
+            final_output_json_schema: output_schema,
         })
         .await?;
     info!("Sent prompt with event ID: {event_id}");
-    Ok(true)
+    Ok(())
 }
@@ -3,6 +3,7 @@ use crate::auto_runtime::shutdown_state_after_request;
 use crate::auto_runtime::capture_auto_resolve_snapshot;
 use crate::prompt_input::PromptDecodeError;
 use crate::prompt_input::decode_prompt_bytes;
+use crate::prompt_input::split_prompt_script;
 use crate::review_command::build_review_request;
 use crate::review_scope::head_is_ancestor_of_base;
 use crate::review_scope::should_skip_followup;
@@ -156,6 +157,15 @@ fn decode_prompt_bytes_rejects_invalid_utf8() {
     assert_eq!(output, Err(PromptDecodeError::InvalidUtf8 { valid_up_to: 0 }));
 }
 
+#[test]
+fn split_prompt_script_separates_on_dashes() {
+    let script = "Fix the build.\n\n  ---\nRun the tests\nand report.\n---\n\n---\n";
+    assert_eq!(
+        split_prompt_script(script),
+        vec!["Fix the build.".to_string(), "Run the tests\nand report.".to_string()]
+    );
+}
+
 #[test]
 fn write_review_json_includes_snapshot() {
     let dir = TempDir::new().unwrap();
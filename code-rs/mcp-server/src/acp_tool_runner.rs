@@ -12,6 +12,7 @@ use code_core::config::Config as CodexConfig;
 use code_core::protocol::EventMsg;
 use code_core::protocol::InputItem;
 use code_core::protocol::Op;
+use code_core::protocol::ReviewDecision;
 use code_protocol::protocol::TurnAbortReason;
 use mcp_types::CallToolResult;
 use mcp_types::ContentBlock;
@@ -141,7 +142,47 @@ pub async fn prompt(
                     meta: None,
                 }))
             }
-            EventMsg::ExecApprovalRequest(_) | EventMsg::ApplyPatchApprovalRequest(_) => None,
+            EventMsg::ExecApprovalRequest(event) => {
+                let mut tool_call = code_core::acp::new_execute_tool_call(
+                    &event.call_id,
+                    &event.command,
+                    acp::ToolCallStatus::Pending,
+                );
+                if let Some(reason) = event.reason {
+                    tool_call.content.push(reason.into());
+                }
+                spawn_permission_request(
+                    acp_session_id.clone(),
+                    tool_call.clone(),
+                    PendingApproval::Exec {
+                        id: event.call_id,
+                        turn_id: (!event.turn_id.is_empty()).then_some(event.turn_id),
+                    },
+                    codex.clone(),
+                    outgoing.clone(),
+                );
+                Some(acp::SessionUpdate::ToolCall(tool_call))
+            }
+            EventMsg::ApplyPatchApprovalRequest(event) => {
+                // The proposal goes out as structured diffs so the editor can
+                // preview it in its buffers before the user decides.
+                let mut tool_call = code_core::acp::new_patch_tool_call(
+                    &event.call_id,
+                    &event.changes,
+                    acp::ToolCallStatus::Pending,
+                );
+                if let Some(reason) = event.reason {
+                    tool_call.content.insert(0, reason.into());
+                }
+                spawn_permission_request(
+                    acp_session_id.clone(),
+                    tool_call.clone(),
+                    PendingApproval::Patch { id: event.call_id },
+                    codex.clone(),
+                    outgoing.clone(),
+                );
+                Some(acp::SessionUpdate::ToolCall(tool_call))
+            }
             EventMsg::ExecCommandBegin(event) => Some(acp::SessionUpdate::ToolCall(
                 code_core::acp::new_execute_tool_call(
                     &event.call_id,
@@ -220,6 +261,74 @@ pub async fn prompt(
     }
 }
 
+/// Which core approval a `session/request_permission` answer resolves.
+enum PendingApproval {
+    Exec { id: String, turn_id: Option<String> },
+    Patch { id: String },
+}
+
+/// Asks the client to approve `tool_call` and forwards its answer to core.
+///
+/// Runs on its own task so the event loop keeps streaming updates while the
+/// editor shows the prompt. A failed or malformed response denies the
+/// request.
+fn spawn_permission_request(
+    session_id: acp::SessionId,
+    tool_call: acp::ToolCall,
+    approval: PendingApproval,
+    codex: Arc<CodexConversation>,
+    outgoing: Arc<OutgoingMessageSender>,
+) {
+    let request = acp::RequestPermissionRequest {
+        session_id,
+        tool_call: acp::ToolCallUpdate {
+            id: tool_call.id,
+            fields: ToolCallUpdateFields {
+                kind: Some(tool_call.kind),
+                status: Some(tool_call.status),
+                title: Some(tool_call.title),
+                content: Some(tool_call.content),
+                locations: Some(tool_call.locations),
+                ..Default::default()
+            },
+            meta: None,
+        },
+        options: code_core::acp::permission_options(),
+        meta: None,
+    };
+
+    tokio::spawn(async move {
+        let on_response = outgoing
+            .send_request(
+                acp::CLIENT_METHOD_NAMES.session_request_permission,
+                serde_json::to_value(request).ok(),
+            )
+            .await;
+        let decision = match on_response.await {
+            Ok(value) => serde_json::from_value::<acp::RequestPermissionResponse>(value)
+                .map_err(anyhow::Error::from)
+                .and_then(|response| code_core::acp::review_decision_for_outcome(response.outcome)),
+            Err(err) => Err(anyhow::anyhow!("permission request dropped: {err}")),
+        }
+        .unwrap_or_else(|err| {
+            tracing::error!("failed to read session/request_permission response: {err}");
+            ReviewDecision::Denied
+        });
+
+        let op = match approval {
+            PendingApproval::Exec { id, turn_id } => Op::ExecApproval {
+                id,
+                turn_id,
+                decision,
+            },
+            PendingApproval::Patch { id } => Op::PatchApproval { id, decision },
+        };
+        if let Err(err) = codex.submit(op).await {
+            tracing::error!("failed to submit approval decision: {err}");
+        }
+    });
+}
+
 fn acp_content_block_to_item(block: acp::ContentBlock) -> Option<InputItem> {
     match block {
         acp::ContentBlock::Text(text_content) => Some(InputItem::Text {
@@ -231,7 +340,24 @@ fn acp_content_block_to_item(block: acp::ContentBlock) -> Option<InputItem> {
         acp::ContentBlock::Image(image_content) => Some(InputItem::Image {
             image_url: image_content.data,
         }),
-        acp::ContentBlock::Audio(_) | acp::ContentBlock::Resource(_) => None,
+        acp::ContentBlock::Resource(resource) => match resource.resource {
+            // Buffer context from the editor: pass unsaved contents through
+            // verbatim, labelled with the buffer's URI.
+            acp::EmbeddedResourceResource::TextResourceContents(contents) => {
+                Some(InputItem::Text {
+                    text: format!(
+                        "<context uri=\"{}\">\n{}\n</context>",
+                        contents.uri, contents.text
+                    ),
+                })
+            }
+            acp::EmbeddedResourceResource::BlobResourceContents(contents) => {
+                Some(InputItem::Text {
+                    text: format!("@{}", contents.uri),
+                })
+            }
+        },
+        acp::ContentBlock::Audio(_) => None,
     }
 }
 
@@ -316,3 +442,33 @@ fn to_acp_content_block(block: mcp_types::ContentBlock) -> acp::ContentBlock {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn embedded_buffer_becomes_labelled_context() {
+        let block = acp::ContentBlock::Resource(acp::EmbeddedResource {
+            annotations: None,
+            resource: acp::EmbeddedResourceResource::TextResourceContents(
+                acp::TextResourceContents {
+                    mime_type: Some("text/x-rust".to_string()),
+                    text: "fn main() {}".to_string(),
+                    uri: "file:///tmp/main.rs".to_string(),
+                    meta: None,
+                },
+            ),
+            meta: None,
+        });
+
+        let Some(InputItem::Text { text }) = acp_content_block_to_item(block) else {
+            panic!("expected a text item");
+        };
+        assert_eq!(
+            text,
+            "<context uri=\"file:///tmp/main.rs\">\nfn main() {}\n</context>"
+        );
+    }
+}
@@ -96,6 +96,7 @@ pub async fn run_main(
         .map_err(|e| {
             std::io::Error::new(ErrorKind::InvalidData, format!("error loading config: {e}"))
         })?;
+    code_core::http_client::set_network_config(&config.network);
 
     // Task: process incoming messages.
     let processor_handle = tokio::spawn({
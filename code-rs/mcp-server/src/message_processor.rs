@@ -121,6 +121,15 @@ impl MessageProcessor {
         // Hold on to the ID so we can respond.
         let request_id = request.id.clone();
 
+        if request.method == acp::AGENT_METHOD_NAMES.authenticate {
+            // Credentials come from `code login`; the advertised auth method
+            // only tells the editor to run it, so there is nothing to do here.
+            self.outgoing
+                .send_response(request_id, json!({}))
+                .await;
+            return;
+        }
+
         if request.method == acp::AGENT_METHOD_NAMES.session_new {
             tracing::info!("handling session/new via ACP shim");
             if let Some(params) = request.params.clone() {
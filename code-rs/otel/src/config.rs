@@ -7,7 +7,12 @@ pub struct OtelSettings {
     pub service_name: String,
     pub service_version: String,
     pub code_home: PathBuf,
+    /// Destination for log events.
     pub exporter: OtelExporter,
+    /// Destination for spans.
+    pub trace_exporter: OtelExporter,
+    /// Destination for counters.
+    pub metrics_exporter: OtelExporter,
 }
 
 #[derive(Clone, Debug)]
@@ -1,9 +1,13 @@
 pub mod config;
 
+pub mod metrics;
 pub mod otel_event_manager;
 #[cfg(feature = "otel")]
 pub mod otel_provider;
 
+/// `tracing` target of the spans exported through `[otel] trace_exporter`.
+pub const TRACE_TARGET: &str = "code_otel::trace";
+
 #[cfg(not(feature = "otel"))]
 mod imp {
     use reqwest::header::HeaderMap;
@@ -0,0 +1,77 @@
+//! Counters recorded through the global OpenTelemetry meter.
+//!
+//! Until [`crate::otel_provider::OtelProvider`] installs a meter provider
+//! (`[otel] metrics_exporter`), the global meter is a no-op and recording is
+//! free.
+
+#[cfg(feature = "otel")]
+mod imp {
+    use std::sync::OnceLock;
+
+    use opentelemetry::KeyValue;
+    use opentelemetry::metrics::Counter;
+
+    struct Counters {
+        tokens: Counter<u64>,
+        approvals: Counter<u64>,
+    }
+
+    // Created on first use so they bind to the meter provider installed at
+    // startup rather than the no-op default.
+    static COUNTERS: OnceLock<Counters> = OnceLock::new();
+
+    fn counters() -> &'static Counters {
+        COUNTERS.get_or_init(|| {
+            let meter = opentelemetry::global::meter("code");
+            Counters {
+                tokens: meter
+                    .u64_counter("code.tokens")
+                    .with_description("Tokens reported by completed model responses")
+                    .with_unit("{token}")
+                    .build(),
+                approvals: meter
+                    .u64_counter("code.approvals")
+                    .with_description("Approval decisions for tool calls")
+                    .build(),
+            }
+        })
+    }
+
+    pub fn record_tokens(model: &str, kind: &'static str, count: u64) {
+        if count == 0 {
+            return;
+        }
+        counters().tokens.add(
+            count,
+            &[
+                KeyValue::new("model", model.to_string()),
+                KeyValue::new("kind", kind),
+            ],
+        );
+    }
+
+    pub fn record_approval(tool_name: &str, decision: &str, source: &str) {
+        counters().approvals.add(
+            1,
+            &[
+                KeyValue::new("tool_name", tool_name.to_string()),
+                KeyValue::new("decision", decision.to_string()),
+                KeyValue::new("source", source.to_string()),
+            ],
+        );
+    }
+}
+
+#[cfg(not(feature = "otel"))]
+mod imp {
+    pub fn record_tokens(_model: &str, _kind: &'static str, _count: u64) {}
+
+    pub fn record_approval(_tool_name: &str, _decision: &str, _source: &str) {}
+}
+
+/// Adds `count` tokens of `kind` (`input`, `cached_input`, `output`,
+/// `reasoning_output`) to the `code.tokens` counter.
+pub use imp::record_tokens;
+
+/// Counts one approval decision in the `code.approvals` counter.
+pub use imp::record_approval;
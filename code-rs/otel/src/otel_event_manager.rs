@@ -343,6 +343,12 @@ impl OtelEventManager {
             reasoning_token_count = reasoning_token_count,
             tool_token_count = %tool_token_count,
         );
+
+        let model = self.metadata.model.as_str();
+        crate::metrics::record_tokens(model, "input", input_token_count);
+        crate::metrics::record_tokens(model, "cached_input", cached_token_count.unwrap_or(0));
+        crate::metrics::record_tokens(model, "output", output_token_count);
+        crate::metrics::record_tokens(model, "reasoning_output", reasoning_token_count.unwrap_or(0));
     }
 
     pub fn user_prompt(&self, items: &[InputItem]) {
@@ -399,6 +405,12 @@ impl OtelEventManager {
             decision = %decision.to_string().to_lowercase(),
             source = %source.to_string(),
         );
+
+        crate::metrics::record_approval(
+            tool_name,
+            &decision.to_string().to_lowercase(),
+            &source.to_string(),
+        );
     }
 
     pub async fn log_tool_result<F, Fut, E>(
@@ -2,32 +2,60 @@ use crate::config::OtelExporter;
 use crate::config::OtelHttpProtocol;
 use crate::config::OtelSettings;
 use opentelemetry::KeyValue;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
 use opentelemetry_otlp::LogExporter;
+use opentelemetry_otlp::MetricExporter;
 use opentelemetry_otlp::Protocol;
+use opentelemetry_otlp::SpanExporter;
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_otlp::WithHttpConfig;
 use opentelemetry_otlp::WithTonicConfig;
 use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::logs::SdkLogger;
 use opentelemetry_sdk::logs::SdkLoggerProvider;
+use opentelemetry_sdk::metrics::PeriodicReader;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::trace::SdkTracer;
+use opentelemetry_sdk::trace::SdkTracerProvider;
 use opentelemetry_semantic_conventions as semconv;
 use reqwest::header::HeaderMap;
 use reqwest::header::HeaderName;
 use reqwest::header::HeaderValue;
+use std::collections::HashMap;
 use std::error::Error;
 use tonic::metadata::MetadataMap;
+use tracing::Subscriber;
 use tracing::debug;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
 
 const ENV_ATTRIBUTE: &str = "env";
+const TRACER_NAME: &str = "code";
 
 pub struct OtelProvider {
-    pub logger: SdkLoggerProvider,
+    pub logger: Option<SdkLoggerProvider>,
+    pub tracer: Option<SdkTracerProvider>,
+    pub meter: Option<SdkMeterProvider>,
 }
 
 impl OtelProvider {
     pub fn shutdown(&self) {
-        let _ = self.logger.shutdown();
+        if let Some(logger) = &self.logger {
+            let _ = logger.shutdown();
+        }
+        if let Some(tracer) = &self.tracer {
+            let _ = tracer.shutdown();
+        }
+        if let Some(meter) = &self.meter {
+            let _ = meter.shutdown();
+        }
     }
 
+    /// Builds a provider for every exporter configured in `settings`.
+    ///
+    /// Returns `None` when none is. A configured meter provider becomes the
+    /// global one so the counters in [`crate::metrics`] reach it.
     pub fn from(settings: &OtelSettings) -> Result<Option<Self>, Box<dyn Error>> {
         let resource = Resource::builder()
             .with_service_name(settings.service_name.clone())
@@ -40,64 +68,159 @@ impl OtelProvider {
             ])
             .build();
 
-        let mut builder = SdkLoggerProvider::builder().with_resource(resource);
+        let logger = build_logger(&settings.exporter, resource.clone())?;
+        let tracer = build_tracer(&settings.trace_exporter, resource.clone())?;
+        let meter = build_meter(&settings.metrics_exporter, resource)?;
 
-        match &settings.exporter {
-            OtelExporter::None => {
-                debug!("No exporter enabled in OTLP settings.");
-                return Ok(None);
-            }
-            OtelExporter::OtlpGrpc { endpoint, headers } => {
-                debug!("Using OTLP Grpc exporter: {}", endpoint);
+        if logger.is_none() && tracer.is_none() && meter.is_none() {
+            debug!("No exporter enabled in OTLP settings.");
+            return Ok(None);
+        }
+        if let Some(meter) = &meter {
+            opentelemetry::global::set_meter_provider(meter.clone());
+        }
 
-                let mut header_map = HeaderMap::new();
-                for (key, value) in headers {
-                    if let Ok(name) = HeaderName::from_bytes(key.as_bytes())
-                        && let Ok(val) = HeaderValue::from_str(value)
-                    {
-                        header_map.insert(name, val);
-                    }
-                }
+        Ok(Some(Self {
+            logger,
+            tracer,
+            meter,
+        }))
+    }
 
-                let exporter = LogExporter::builder()
-                    .with_tonic()
-                    .with_endpoint(endpoint)
-                    .with_metadata(MetadataMap::from_headers(header_map))
-                    .build()?;
+    /// Layer forwarding `tracing` events to the log exporter.
+    pub fn log_layer(&self) -> Option<OpenTelemetryTracingBridge<SdkLoggerProvider, SdkLogger>> {
+        self.logger.as_ref().map(OpenTelemetryTracingBridge::new)
+    }
 
-                builder = builder.with_batch_exporter(exporter);
-            }
-            OtelExporter::OtlpHttp {
-                endpoint,
-                headers,
-                protocol,
-            } => {
-                debug!("Using OTLP Http exporter: {}", endpoint);
+    /// Layer forwarding `tracing` spans to the trace exporter.
+    pub fn trace_layer<S>(&self) -> Option<OpenTelemetryLayer<S, SdkTracer>>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        self.tracer.as_ref().map(|provider| {
+            tracing_opentelemetry::layer().with_tracer(provider.tracer(TRACER_NAME))
+        })
+    }
+}
 
-                let protocol = match protocol {
-                    OtelHttpProtocol::Binary => Protocol::HttpBinary,
-                    OtelHttpProtocol::Json => Protocol::HttpJson,
-                };
+impl Drop for OtelProvider {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
 
-                let exporter = LogExporter::builder()
-                    .with_http()
-                    .with_endpoint(endpoint)
-                    .with_protocol(protocol)
-                    .with_headers(headers.clone())
-                    .build()?;
+fn build_logger(
+    exporter: &OtelExporter,
+    resource: Resource,
+) -> Result<Option<SdkLoggerProvider>, Box<dyn Error>> {
+    let builder = SdkLoggerProvider::builder().with_resource(resource);
+    let exporter = match exporter {
+        OtelExporter::None => return Ok(None),
+        OtelExporter::OtlpGrpc { endpoint, headers } => {
+            debug!("Using OTLP Grpc log exporter: {}", endpoint);
+            LogExporter::builder()
+                .with_tonic()
+                .with_endpoint(endpoint)
+                .with_metadata(grpc_metadata(headers))
+                .build()?
+        }
+        OtelExporter::OtlpHttp {
+            endpoint,
+            headers,
+            protocol,
+        } => {
+            debug!("Using OTLP Http log exporter: {}", endpoint);
+            LogExporter::builder()
+                .with_http()
+                .with_endpoint(endpoint)
+                .with_protocol(http_protocol(protocol))
+                .with_headers(headers.clone())
+                .build()?
+        }
+    };
+    Ok(Some(builder.with_batch_exporter(exporter).build()))
+}
 
-                builder = builder.with_batch_exporter(exporter);
-            }
+fn build_tracer(
+    exporter: &OtelExporter,
+    resource: Resource,
+) -> Result<Option<SdkTracerProvider>, Box<dyn Error>> {
+    let builder = SdkTracerProvider::builder().with_resource(resource);
+    let exporter = match exporter {
+        OtelExporter::None => return Ok(None),
+        OtelExporter::OtlpGrpc { endpoint, headers } => {
+            debug!("Using OTLP Grpc span exporter: {}", endpoint);
+            SpanExporter::builder()
+                .with_tonic()
+                .with_endpoint(endpoint)
+                .with_metadata(grpc_metadata(headers))
+                .build()?
+        }
+        OtelExporter::OtlpHttp {
+            endpoint,
+            headers,
+            protocol,
+        } => {
+            debug!("Using OTLP Http span exporter: {}", endpoint);
+            SpanExporter::builder()
+                .with_http()
+                .with_endpoint(endpoint)
+                .with_protocol(http_protocol(protocol))
+                .with_headers(headers.clone())
+                .build()?
         }
+    };
+    Ok(Some(builder.with_batch_exporter(exporter).build()))
+}
 
-        Ok(Some(Self {
-            logger: builder.build(),
-        }))
+fn build_meter(
+    exporter: &OtelExporter,
+    resource: Resource,
+) -> Result<Option<SdkMeterProvider>, Box<dyn Error>> {
+    let builder = SdkMeterProvider::builder().with_resource(resource);
+    let exporter = match exporter {
+        OtelExporter::None => return Ok(None),
+        OtelExporter::OtlpGrpc { endpoint, headers } => {
+            debug!("Using OTLP Grpc metric exporter: {}", endpoint);
+            MetricExporter::builder()
+                .with_tonic()
+                .with_endpoint(endpoint)
+                .with_metadata(grpc_metadata(headers))
+                .build()?
+        }
+        OtelExporter::OtlpHttp {
+            endpoint,
+            headers,
+            protocol,
+        } => {
+            debug!("Using OTLP Http metric exporter: {}", endpoint);
+            MetricExporter::builder()
+                .with_http()
+                .with_endpoint(endpoint)
+                .with_protocol(http_protocol(protocol))
+                .with_headers(headers.clone())
+                .build()?
+        }
+    };
+    let reader = PeriodicReader::builder(exporter).build();
+    Ok(Some(builder.with_reader(reader).build()))
+}
+
+fn grpc_metadata(headers: &HashMap<String, String>) -> MetadataMap {
+    let mut header_map = HeaderMap::new();
+    for (key, value) in headers {
+        if let Ok(name) = HeaderName::from_bytes(key.as_bytes())
+            && let Ok(val) = HeaderValue::from_str(value)
+        {
+            header_map.insert(name, val);
+        }
     }
+    MetadataMap::from_headers(header_map)
 }
 
-impl Drop for OtelProvider {
-    fn drop(&mut self) {
-        let _ = self.logger.shutdown();
+fn http_protocol(protocol: &OtelHttpProtocol) -> Protocol {
+    match protocol {
+        OtelHttpProtocol::Binary => Protocol::HttpBinary,
+        OtelHttpProtocol::Json => Protocol::HttpJson,
     }
 }
@@ -1519,6 +1519,14 @@ pub struct AgentReasoningDeltaEvent {
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct CompactionCheckpointWarningEvent {
     pub message: String,
+    /// Conversation messages folded into the summary.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compacted_messages: Option<u32>,
+    /// Rollout file that still holds the original messages.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollout_path: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
@@ -20,3 +20,5 @@ pub use perform_oauth_login::OauthLoginArgs;
 pub use perform_oauth_login::perform_oauth_login;
 pub use perform_oauth_login::perform_oauth_login_return_url;
 pub use rmcp_client::RmcpClient;
+pub use utils::HttpClientHook;
+pub use utils::set_http_client_hook;
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::env;
+use std::sync::OnceLock;
 use std::time::Duration;
 
 use anyhow::Context;
@@ -140,7 +141,23 @@ pub(crate) fn build_default_headers(
     Ok(headers)
 }
 
+/// Adjusts every HTTP client this crate builds, e.g. to add the host
+/// application's proxy and extra root certificates.
+pub type HttpClientHook = fn(ClientBuilder) -> ClientBuilder;
+
+static HTTP_CLIENT_HOOK: OnceLock<HttpClientHook> = OnceLock::new();
+
+/// Installs `hook` for all later MCP HTTP clients. Only the first call takes
+/// effect; the hook should read settings that can change from shared state.
+pub fn set_http_client_hook(hook: HttpClientHook) {
+    let _ = HTTP_CLIENT_HOOK.set(hook);
+}
+
 pub(crate) fn apply_default_headers(builder: ClientBuilder, default_headers: &HeaderMap) -> ClientBuilder {
+    let builder = match HTTP_CLIENT_HOOK.get() {
+        Some(hook) => hook(builder),
+        None => builder,
+    };
     if default_headers.is_empty() {
         builder
     } else {
@@ -0,0 +1,241 @@
+//! Just enough HTTP/1.1 for the serve API.
+//!
+//! One request per connection: requests are read with a bounded header block
+//! and a `Content-Length` body, and every response closes the connection.
+//! Server-sent event streams write their head once and then one event at a
+//! time, flushing each so clients see events as they happen.
+
+use std::io;
+
+use serde_json::Value;
+use tokio::io::AsyncBufRead;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+
+/// Upper bound on the request line plus headers.
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+
+/// Upper bound on request bodies; messages and approvals are small.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct HttpRequest {
+    pub(crate) method: String,
+    pub(crate) path: String,
+    pub(crate) query: Option<String>,
+    /// Header names are lowercased.
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) body: Vec<u8>,
+}
+
+impl HttpRequest {
+    pub(crate) fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub(crate) fn query_param(&self, name: &str) -> Option<&str> {
+        self.query.as_deref()?.split('&').find_map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key == name).then_some(value)
+        })
+    }
+}
+
+/// Reads one request. `Ok(None)` means the peer closed the connection before
+/// sending anything.
+pub(crate) async fn read_request<R>(reader: &mut R) -> io::Result<Option<HttpRequest>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut head_bytes = 0;
+    let mut request_line = String::new();
+    if read_head_line(reader, &mut request_line, &mut head_bytes).await? == 0 {
+        return Ok(None);
+    }
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target), Some(_version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid("malformed request line"));
+    };
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), Some(query.to_string())),
+        None => (target.to_string(), None),
+    };
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        if read_head_line(reader, &mut line, &mut head_bytes).await? == 0 {
+            return Err(invalid("connection closed inside the request head"));
+        }
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(invalid("malformed header"));
+        };
+        headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+    }
+
+    let mut request = HttpRequest {
+        method: method.to_string(),
+        path,
+        query,
+        headers,
+        body: Vec::new(),
+    };
+    if request.header("transfer-encoding").is_some() {
+        return Err(invalid("chunked request bodies are not supported"));
+    }
+    let length = match request.header("content-length") {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|_| invalid("invalid Content-Length"))?,
+        None => 0,
+    };
+    if length > MAX_BODY_BYTES {
+        return Err(invalid("request body too large"));
+    }
+    request.body = vec![0; length];
+    reader.read_exact(&mut request.body).await?;
+    Ok(Some(request))
+}
+
+async fn read_head_line<R>(reader: &mut R, line: &mut String, head_bytes: &mut usize) -> io::Result<usize>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let remaining = (MAX_HEAD_BYTES - *head_bytes) as u64;
+    let read = (&mut *reader).take(remaining).read_line(line).await?;
+    *head_bytes += read;
+    if read > 0 && !line.ends_with('\n') {
+        return Err(invalid("request head too large"));
+    }
+    Ok(read)
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Writes a complete response with a JSON body (none for 204).
+pub(crate) async fn write_json<W>(writer: &mut W, status: u16, body: &Value) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let body = if status == 204 {
+        Vec::new()
+    } else {
+        serde_json::to_vec(body).map_err(io::Error::other)?
+    };
+    let head = format!(
+        "HTTP/1.1 {status} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        reason_phrase(status),
+        body.len()
+    );
+    writer.write_all(head.as_bytes()).await?;
+    writer.write_all(&body).await?;
+    writer.flush().await
+}
+
+pub(crate) async fn write_error<W>(writer: &mut W, status: u16, message: &str) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    write_json(writer, status, &serde_json::json!({ "error": message })).await
+}
+
+/// Starts a `text/event-stream` response; the body runs until the
+/// connection closes.
+pub(crate) async fn write_sse_head<W>(writer: &mut W) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    writer
+        .write_all(
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n",
+        )
+        .await?;
+    writer.flush().await
+}
+
+/// Writes one server-sent event. `data` must be a single line (compact
+/// JSON is).
+pub(crate) async fn write_sse_event<W>(writer: &mut W, id: u64, data: &str) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    writer
+        .write_all(format!("id: {id}\ndata: {data}\n\n").as_bytes())
+        .await?;
+    writer.flush().await
+}
+
+/// A comment line that keeps idle streams open through proxies and lets
+/// the server notice clients that went away.
+pub(crate) async fn write_sse_keepalive<W>(writer: &mut W) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    writer.write_all(b": keepalive\n\n").await?;
+    writer.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    async fn parse(raw: &str) -> io::Result<Option<HttpRequest>> {
+        read_request(&mut raw.as_bytes()).await
+    }
+
+    #[tokio::test]
+    async fn parses_request_with_query_headers_and_body() {
+        let request = parse(
+            "POST /sessions/abc/messages?since=4&x HTTP/1.1\r\nHost: localhost\r\nContent-Length: 15\r\nLast-Event-ID: 7\r\n\r\n{\"text\":\"hi\"}\r\nextra",
+        )
+        .await
+        .expect("parse")
+        .expect("request");
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/sessions/abc/messages");
+        assert_eq!(request.query_param("since"), Some("4"));
+        assert_eq!(request.query_param("x"), Some(""));
+        assert_eq!(request.header("last-event-id"), Some("7"));
+        assert_eq!(request.body, b"{\"text\":\"hi\"}\r\n".to_vec());
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_heads_and_bodies() {
+        assert_eq!(parse("").await.expect("empty"), None);
+
+        let long_header = format!("GET / HTTP/1.1\r\nX: {}\r\n\r\n", "a".repeat(MAX_HEAD_BYTES));
+        let err = parse(&long_header).await.expect_err("head too large");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let big_body = format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_BODY_BYTES + 1);
+        let err = parse(&big_body).await.expect_err("body too large");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,141 @@
+//! `code serve`: the conversation API over HTTP and server-sent events.
+//!
+//! Web and editor frontends drive the same core conversation engine as the
+//! TUI without embedding it:
+//!
+//! - `POST /sessions` starts a session and returns its `session_id`.
+//! - `GET /sessions` lists running sessions.
+//! - `POST /sessions/{id}/messages` sends `{"text": "..."}` as a user turn.
+//! - `GET /sessions/{id}/events` streams core events as server-sent events.
+//!   Each event's `id` is its sequence number; reconnect with a
+//!   `Last-Event-ID` header (or `?since=N`) to resume without gaps.
+//! - `POST /sessions/{id}/approvals` answers an exec or patch approval
+//!   request: `{"call_id": "...", "kind": "exec" | "patch", "decision":
+//!   "approved" | "approved_for_session" | "denied" | "abort"}`.
+//! - `POST /sessions/{id}/interrupt` interrupts the running turn.
+//! - `DELETE /sessions/{id}` shuts the session down.
+//!
+//! When `CODE_SERVE_TOKEN` is set, every request must carry it as
+//! `Authorization: Bearer <token>`. Listening on a non-loopback address
+//! requires the token.
+
+mod http;
+mod sessions;
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use code_common::CliConfigOverrides;
+use code_core::config::Config;
+use code_core::config::ConfigOverrides;
+use tokio::io::BufReader;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tracing::debug;
+use tracing::info;
+
+use crate::http::read_request;
+use crate::http::write_error;
+use crate::sessions::Sessions;
+
+/// Environment variable holding the bearer token clients must present.
+pub const TOKEN_ENV_VAR: &str = "CODE_SERVE_TOKEN";
+
+#[derive(Debug, Parser)]
+pub struct ServeCli {
+    /// Address to listen on.
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:7878")]
+    listen: SocketAddr,
+
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+}
+
+impl ServeCli {
+    pub async fn run(self, code_linux_sandbox_exe: Option<PathBuf>) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(
+            overrides,
+            ConfigOverrides {
+                code_linux_sandbox_exe,
+                ..Default::default()
+            },
+        )?;
+        let token = std::env::var(TOKEN_ENV_VAR)
+            .ok()
+            .filter(|token| !token.is_empty());
+        anyhow::ensure!(
+            token.is_some() || self.listen.ip().is_loopback(),
+            "set {TOKEN_ENV_VAR} before listening on a non-loopback address ({})",
+            self.listen
+        );
+        run_server(config, self.listen, token).await
+    }
+}
+
+async fn run_server(config: Config, listen: SocketAddr, token: Option<String>) -> Result<()> {
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .with_writer(std::io::stderr)
+        .try_init();
+
+    let listener = TcpListener::bind(listen)
+        .await
+        .with_context(|| format!("failed to bind {listen}"))?;
+    let sessions = Arc::new(Sessions::new(config));
+    let token = token.map(Arc::<str>::from);
+    info!("code serve listening on http://{}", listener.local_addr()?);
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, peer) = accepted.context("accept failed")?;
+                let sessions = sessions.clone();
+                let token = token.clone();
+                tokio::spawn(async move {
+                    if let Err(err) = handle_connection(stream, sessions, token.as_deref()).await {
+                        debug!("connection from {peer} ended: {err}");
+                    }
+                });
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+    info!("shutting down");
+    sessions.shutdown_all().await;
+    Ok(())
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    sessions: Arc<Sessions>,
+    token: Option<&str>,
+) -> std::io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    let request = match read_request(&mut reader).await {
+        Ok(Some(request)) => request,
+        Ok(None) => return Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::InvalidData => {
+            return write_error(&mut writer, 400, &err.to_string()).await;
+        }
+        Err(err) => return Err(err),
+    };
+    if let Some(token) = token {
+        let expected = format!("Bearer {token}");
+        if request.header("authorization") != Some(expected.as_str()) {
+            return write_error(&mut writer, 401, "missing or invalid bearer token").await;
+        }
+    }
+    sessions.handle(request, &mut writer).await
+}
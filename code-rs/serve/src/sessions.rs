@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use code_core::AuthManager;
+use code_core::ConversationManager;
+use code_core::NewConversation;
+use code_core::config::Config;
+use code_core::protocol::Event;
+use code_core::protocol::EventMsg;
+use code_core::protocol::InputItem;
+use code_core::protocol::Op;
+use code_core::protocol::ReviewDecision;
+use code_core::session_hub::SessionHub;
+use code_core::session_hub::is_newer;
+use code_protocol::ConversationId;
+use code_protocol::protocol::SessionSource;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use tokio::io::AsyncWrite;
+use tokio::sync::Mutex;
+use tokio::sync::broadcast;
+use tracing::info;
+use tracing::warn;
+
+use crate::http::HttpRequest;
+use crate::http::write_error;
+use crate::http::write_json;
+use crate::http::write_sse_event;
+use crate::http::write_sse_head;
+use crate::http::write_sse_keepalive;
+
+/// Idle time after which an event stream sends a keepalive comment.
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Default, Deserialize)]
+struct CreateSessionBody {
+    /// Working directory for the session; defaults to the server's.
+    cwd: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageBody {
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ApprovalKind {
+    Exec,
+    Patch,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApprovalBody {
+    call_id: String,
+    kind: ApprovalKind,
+    decision: ReviewDecision,
+}
+
+struct Session {
+    conversation_id: ConversationId,
+    hub: Arc<SessionHub>,
+}
+
+pub(crate) struct Sessions {
+    config: Config,
+    manager: ConversationManager,
+    sessions: Mutex<HashMap<String, Session>>,
+}
+
+impl Sessions {
+    pub(crate) fn new(config: Config) -> Self {
+        let auth_manager = AuthManager::shared_with_mode_and_originator(
+            config.code_home.clone(),
+            code_login::AuthMode::ApiKey,
+            config.responses_originator_header.clone(),
+            config.cli_auth_credentials_store_mode,
+        );
+        Self {
+            config,
+            manager: ConversationManager::new(auth_manager, SessionSource::Mcp),
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) async fn handle<W>(&self, request: HttpRequest, writer: &mut W) -> std::io::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+        match (request.method.as_str(), segments.as_slice()) {
+            ("POST", ["sessions"]) => self.create(&request, writer).await,
+            ("GET", ["sessions"]) => {
+                let ids: Vec<String> = self.sessions.lock().await.keys().cloned().collect();
+                write_json(writer, 200, &json!({ "sessions": ids })).await
+            }
+            ("DELETE", ["sessions", id]) => self.delete(id, writer).await,
+            ("POST", ["sessions", id, "messages"]) => {
+                let body: MessageBody = match parse_body(&request) {
+                    Ok(body) => body,
+                    Err(err) => return write_error(writer, 400, &err).await,
+                };
+                if body.text.trim().is_empty() {
+                    return write_error(writer, 400, "message text is empty").await;
+                }
+                let op = Op::UserInput {
+                    items: vec![InputItem::Text { text: body.text }],
+                    final_output_json_schema: None,
+                };
+                self.submit(id, op, writer).await
+            }
+            ("POST", ["sessions", id, "approvals"]) => {
+                let body: ApprovalBody = match parse_body(&request) {
+                    Ok(body) => body,
+                    Err(err) => return write_error(writer, 400, &err).await,
+                };
+                let op = match body.kind {
+                    ApprovalKind::Exec => Op::ExecApproval {
+                        id: body.call_id,
+                        turn_id: None,
+                        decision: body.decision,
+                    },
+                    ApprovalKind::Patch => Op::PatchApproval {
+                        id: body.call_id,
+                        decision: body.decision,
+                    },
+                };
+                self.submit(id, op, writer).await
+            }
+            ("POST", ["sessions", id, "interrupt"]) => self.submit(id, Op::Interrupt, writer).await,
+            ("GET", ["sessions", id, "events"]) => {
+                let since = request
+                    .header("last-event-id")
+                    .or_else(|| request.query_param("since"))
+                    .and_then(|value| value.parse::<u64>().ok());
+                self.stream_events(id, since, writer).await
+            }
+            _ => write_error(writer, 404, "no such endpoint").await,
+        }
+    }
+
+    async fn create<W>(&self, request: &HttpRequest, writer: &mut W) -> std::io::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let body: CreateSessionBody = if request.body.is_empty() {
+            CreateSessionBody::default()
+        } else {
+            match parse_body(request) {
+                Ok(body) => body,
+                Err(err) => return write_error(writer, 400, &err).await,
+            }
+        };
+        let mut config = self.config.clone();
+        if let Some(cwd) = body.cwd {
+            if !cwd.is_absolute() || !cwd.is_dir() {
+                return write_error(writer, 400, "cwd must be an existing absolute directory").await;
+            }
+            config.cwd = cwd;
+        }
+
+        let NewConversation {
+            conversation_id,
+            conversation,
+            session_configured,
+        } = match self.manager.new_conversation(config).await {
+            Ok(new_conversation) => new_conversation,
+            Err(err) => {
+                let message = format!("failed to start session: {err}");
+                return write_error(writer, 500, &message).await;
+            }
+        };
+        let session_configured = Event {
+            id: conversation_id.to_string(),
+            event_seq: 0,
+            msg: EventMsg::SessionConfigured(session_configured),
+            order: None,
+        };
+        let (hub, _pump) = SessionHub::start(conversation, session_configured);
+        let session_id = conversation_id.to_string();
+        info!("session {session_id} started");
+        self.sessions.lock().await.insert(
+            session_id.clone(),
+            Session {
+                conversation_id,
+                hub,
+            },
+        );
+        write_json(writer, 201, &json!({ "session_id": session_id })).await
+    }
+
+    async fn hub(&self, id: &str) -> Option<Arc<SessionHub>> {
+        self.sessions
+            .lock()
+            .await
+            .get(id)
+            .map(|session| session.hub.clone())
+    }
+
+    async fn submit<W>(&self, id: &str, op: Op, writer: &mut W) -> std::io::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let Some(hub) = self.hub(id).await else {
+            return write_error(writer, 404, "no such session").await;
+        };
+        match hub.conversation().submit(op).await {
+            Ok(submission_id) => {
+                write_json(writer, 202, &json!({ "submission_id": submission_id })).await
+            }
+            Err(err) => write_error(writer, 500, &format!("failed to submit: {err}")).await,
+        }
+    }
+
+    async fn delete<W>(&self, id: &str, writer: &mut W) -> std::io::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let Some(session) = self.sessions.lock().await.remove(id) else {
+            return write_error(writer, 404, "no such session").await;
+        };
+        if let Err(err) = session.hub.conversation().submit(Op::Shutdown).await {
+            warn!("session {id} did not accept shutdown: {err}");
+        }
+        self.manager
+            .remove_conversation(&session.conversation_id)
+            .await;
+        info!("session {id} closed");
+        write_json(writer, 204, &json!({})).await
+    }
+
+    /// Replays events after `since`, then streams live ones until the
+    /// session shuts down or the client goes away. A client that falls too
+    /// far behind is disconnected and catches up by reconnecting with
+    /// `Last-Event-ID`.
+    async fn stream_events<W>(&self, id: &str, since: Option<u64>, writer: &mut W) -> std::io::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let Some(hub) = self.hub(id).await else {
+            return write_error(writer, 404, "no such session").await;
+        };
+        let (replay, mut live_rx) = hub.subscribe(since)?;
+        write_sse_head(writer).await?;
+        let mut last_sent = since;
+        for (seq, event) in replay {
+            let shutdown = write_event(writer, seq, &event).await?;
+            last_sent = Some(seq);
+            if shutdown {
+                return Ok(());
+            }
+        }
+
+        loop {
+            let received = tokio::time::timeout(KEEPALIVE_INTERVAL, live_rx.recv()).await;
+            match received {
+                Err(_) => write_sse_keepalive(writer).await?,
+                Ok(Ok((seq, event))) => {
+                    if !is_newer(seq, last_sent) {
+                        continue;
+                    }
+                    let shutdown = write_event(writer, seq, &event).await?;
+                    last_sent = Some(seq);
+                    if shutdown {
+                        return Ok(());
+                    }
+                }
+                Ok(Err(broadcast::error::RecvError::Lagged(_)))
+                | Ok(Err(broadcast::error::RecvError::Closed)) => return Ok(()),
+            }
+        }
+    }
+
+    /// Asks every session to shut down; used when the server exits.
+    pub(crate) async fn shutdown_all(&self) {
+        let sessions: Vec<Session> = self
+            .sessions
+            .lock()
+            .await
+            .drain()
+            .map(|(_, session)| session)
+            .collect();
+        for session in sessions {
+            let _ = session.hub.conversation().submit(Op::Shutdown).await;
+        }
+    }
+}
+
+/// Writes `event` and reports whether it ended the session.
+async fn write_event<W>(writer: &mut W, seq: u64, event: &Event) -> std::io::Result<bool>
+where
+    W: AsyncWrite + Unpin,
+{
+    let data = serde_json::to_string(event).map_err(std::io::Error::other)?;
+    write_sse_event(writer, seq, &data).await?;
+    Ok(matches!(event.msg, EventMsg::ShutdownComplete))
+}
+
+fn parse_body<T: DeserializeOwned>(request: &HttpRequest) -> Result<T, String> {
+    serde_json::from_slice(&request.body).map_err(|err| format!("invalid request body: {err}"))
+}
@@ -0,0 +1,98 @@
+//! Per-frame cost of drawing cached history rows into the frame buffer.
+//!
+//! `full_width_clone` is the previous scheme: every row stored at the full
+//! terminal width and cloned cell by cell, followed by a background pass.
+//! `cached_row_blit` is `CachedRow::blit`. Run with
+//! `cargo bench -p code-tui --features test-helpers --bench cached_layout`.
+
+use code_tui::test_helpers::CachedRow;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use ratatui::buffer::Cell;
+use ratatui::style::Color;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use std::hint::black_box;
+
+const ROWS: usize = 60;
+
+/// Typical transcript text: mostly short lines, a few that wrap to width.
+fn sample_lines(width: u16) -> Vec<Line<'static>> {
+    (0..ROWS)
+        .map(|i| {
+            let len = match i % 4 {
+                0 => width as usize,
+                1 => 12,
+                _ => 48,
+            };
+            let text: String = "lorem ipsum dolor sit amet ".chars().cycle().take(len).collect();
+            Line::from(vec![
+                Span::styled("• ", Style::default().fg(Color::Cyan)),
+                Span::raw(text),
+            ])
+        })
+        .collect()
+}
+
+fn full_width_rows(rows: &[CachedRow], width: usize) -> Vec<Vec<Cell>> {
+    rows.iter()
+        .map(|row| {
+            let mut cells = vec![Cell::default(); width];
+            row.blit(&mut cells, Color::Reset, false);
+            cells
+        })
+        .collect()
+}
+
+fn bench_cached_layout(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cached_layout");
+    for width in [80u16, 200, 400] {
+        let rows: Vec<CachedRow> = sample_lines(width)
+            .iter()
+            .map(|line| CachedRow::from_line(line, width))
+            .collect();
+        let legacy = full_width_rows(&rows, width as usize);
+        let mut frame = vec![Cell::default(); width as usize * ROWS];
+
+        group.bench_with_input(BenchmarkId::new("full_width_clone", width), &width, |b, _| {
+            b.iter(|| {
+                for (dest, src) in frame.chunks_mut(width as usize).zip(&legacy) {
+                    dest.clone_from_slice(src);
+                    for cell in dest.iter_mut() {
+                        if cell.bg == Color::Reset {
+                            cell.bg = Color::Black;
+                        }
+                    }
+                }
+                black_box(&frame);
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("cached_row_blit", width), &width, |b, _| {
+            b.iter(|| {
+                for (dest, src) in frame.chunks_mut(width as usize).zip(&rows) {
+                    src.blit(dest, Color::Black, false);
+                }
+                black_box(&frame);
+            });
+        });
+        group.bench_with_input(
+            BenchmarkId::new("cached_row_blit_painted_bg", width),
+            &width,
+            |b, _| {
+                b.iter(|| {
+                    for (dest, src) in frame.chunks_mut(width as usize).zip(&rows) {
+                        src.blit(dest, Color::Black, true);
+                    }
+                    black_box(&frame);
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_cached_layout);
+criterion_main!(benches);
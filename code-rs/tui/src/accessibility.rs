@@ -0,0 +1,147 @@
+//! Accessibility mode (`tui.accessibility`) for terminal screen readers.
+//! Animations and fades are skipped, each history cell is announced by a
+//! text label such as `ASSISTANT:` or `COMMAND OUTPUT:`, box-drawing
+//! characters are replaced with ASCII, and approval prompts are laid out as
+//! one line per fact.
+
+use std::borrow::Cow;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use ratatui::buffer::Buffer;
+use ratatui::text::Line;
+
+use crate::history_cell::ExecKind;
+use crate::history_cell::HistoryCellType;
+use crate::streaming::StreamKind;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The stream whose label was last written to the terminal buffer, so a
+/// stream is announced once rather than on every committed chunk.
+static ANNOUNCED_STREAM: Mutex<Option<(StreamKind, Option<String>)>> = Mutex::new(None);
+
+pub(crate) fn configure(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub(crate) fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// The label announcing a cell of `kind`, or `None` for cells that carry no
+/// content of their own (spacers, the intro art, loading placeholders).
+pub(crate) fn cell_label(kind: HistoryCellType) -> Option<&'static str> {
+    Some(match kind {
+        HistoryCellType::Plain
+        | HistoryCellType::AnimatedWelcome
+        | HistoryCellType::Loading => return None,
+        HistoryCellType::User => "YOU:",
+        HistoryCellType::Assistant => "ASSISTANT:",
+        HistoryCellType::Reasoning => "REASONING:",
+        HistoryCellType::Error => "ERROR:",
+        HistoryCellType::Tool { .. } => "TOOL:",
+        HistoryCellType::Exec {
+            kind: ExecKind::Run,
+            ..
+        } => "COMMAND OUTPUT:",
+        HistoryCellType::Exec { .. } => "EXPLORING:",
+        HistoryCellType::Patch { .. } => "PATCH:",
+        HistoryCellType::PlanUpdate => "PLAN:",
+        HistoryCellType::BackgroundEvent => "EVENT:",
+        HistoryCellType::Notice => "NOTICE:",
+        HistoryCellType::CompactionSummary => "SUMMARY:",
+        HistoryCellType::Diff => "DIFF:",
+        HistoryCellType::Image => "IMAGE:",
+        HistoryCellType::Context => "CONTEXT:",
+    })
+}
+
+fn stream_label(kind: StreamKind) -> &'static str {
+    match kind {
+        StreamKind::Answer => "ASSISTANT:",
+        StreamKind::Reasoning => "REASONING:",
+    }
+}
+
+/// The label line to write before streamed lines of `kind`/`id` in the
+/// terminal buffer, when that stream has not been announced yet.
+pub(crate) fn announce_stream(kind: StreamKind, id: Option<&str>) -> Option<Line<'static>> {
+    if !enabled() {
+        return None;
+    }
+    let mut announced = ANNOUNCED_STREAM.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+    let current = (kind, id.map(str::to_string));
+    if announced.as_ref() == Some(&current) {
+        return None;
+    }
+    *announced = Some(current);
+    Some(Line::from(stream_label(kind)))
+}
+
+/// ASCII stand-in for a box-drawing or block character.
+fn ascii_for(ch: char) -> Option<char> {
+    Some(match ch {
+        '─' | '━' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' | '═' | '╴' | '╶' | '╸' | '╺' => '-',
+        '│' | '┃' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' | '║' | '╵' | '╷' | '╹' | '╻' => '|',
+        '╱' => '/',
+        '╲' => '\\',
+        '╳' => 'X',
+        '\u{2500}'..='\u{257f}' => '+',
+        '▌' | '▐' | '▍' | '▎' | '▏' | '▕' => '|',
+        '▀' | '▔' => '-',
+        '▄' | '▁' => '_',
+        '█' => '#',
+        '\u{2580}'..='\u{259f}' => ' ',
+        _ => return None,
+    })
+}
+
+/// `text` with box-drawing characters replaced by ASCII. Unchanged unless
+/// accessibility mode is on.
+pub(crate) fn asciify(text: &str) -> Cow<'_, str> {
+    if !enabled() || !text.chars().any(|ch| ascii_for(ch).is_some()) {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(text.chars().map(|ch| ascii_for(ch).unwrap_or(ch)).collect())
+}
+
+/// Replace box-drawing characters across a rendered frame.
+pub(crate) fn asciify_buffer(buf: &mut Buffer) {
+    if !enabled() {
+        return;
+    }
+    for cell in &mut buf.content {
+        let mut chars = cell.symbol().chars();
+        if let (Some(ch), None) = (chars.next(), chars.next())
+            && let Some(ascii) = ascii_for(ch)
+        {
+            cell.set_char(ascii);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn box_drawing_maps_to_ascii() {
+        let mapped: String = "╭─┬─╮│▌x"
+            .chars()
+            .map(|ch| ascii_for(ch).unwrap_or(ch))
+            .collect();
+        assert_eq!(mapped, "+-+-+||x");
+    }
+
+    #[test]
+    fn exec_runs_are_announced_as_command_output() {
+        let kind = HistoryCellType::Exec {
+            kind: ExecKind::Run,
+            status: crate::history::state::ExecStatus::Success,
+        };
+        assert_eq!(cell_label(kind), Some("COMMAND OUTPUT:"));
+        assert_eq!(cell_label(HistoryCellType::Plain), None);
+    }
+}
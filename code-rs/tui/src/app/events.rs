@@ -13,6 +13,7 @@ use crossterm::SynchronizedUpdate;
 use code_cloud_tasks_client::{CloudTaskError, TaskId};
 use code_core::config::add_project_allowed_command;
 use code_core::config_types::AuthCredentialsStoreMode;
+use code_core::config_types::FeedbackKind;
 use code_core::config_types::Notifications;
 #[cfg(debug_assertions)]
 use code_core::protocol::Event;
@@ -27,6 +28,7 @@ use crate::chatwidget::ChatWidget;
 use crate::cloud_tasks_service;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::external_editor;
+use crate::file_search::FileSearchManager;
 use crate::get_git_diff::get_git_diff;
 use crate::history_cell;
 use crate::slash_command::SlashCommand;
@@ -65,6 +67,20 @@ impl App<'_> {
         self.spawn_remote_model_discovery();
     }
 
+    /// Points file search (and `/diff`) at the `/scope` subproject, or back
+    /// at the whole session directory. A new session starts unscoped.
+    fn set_scope(&mut self, scope: Option<PathBuf>) {
+        self.file_search = match scope.as_deref() {
+            Some(scope) => FileSearchManager::new_scoped(
+                &self.config.cwd,
+                scope,
+                self.app_event_tx.clone(),
+            ),
+            None => FileSearchManager::new(self.config.cwd.clone(), self.app_event_tx.clone()),
+        };
+        self.scope = scope;
+    }
+
     fn spawn_remote_model_discovery(&self) {
         if crate::chatwidget::is_test_mode() {
             return;
@@ -156,7 +172,12 @@ impl App<'_> {
                         // Always update widget history, even in terminal mode.
                         // In terminal mode, the widget will emit an InsertHistory event
                         // which we will mirror to scrollback in the handler above.
-                        let to_mirror = lines.clone();
+                        let mut to_mirror = lines.clone();
+                        if !self.alt_screen_active
+                            && let Some(label) = crate::accessibility::announce_stream(kind, id.as_deref())
+                        {
+                            to_mirror.insert(0, label);
+                        }
                         widget.insert_history_lines_with_kind(kind, id, lines);
                         if !self.alt_screen_active {
                             use std::io::stdout;
@@ -173,7 +194,15 @@ impl App<'_> {
                 AppEvent::InsertFinalAnswer { id, lines, source } => match &mut self.app_state {
                     AppState::Chat { widget } => {
                         tracing::debug!("app: InsertFinalAnswer id={:?} lines={} source_len={}", id, lines.len(), source.len());
-                        let to_mirror = lines.clone();
+                        let mut to_mirror = lines.clone();
+                        if !self.alt_screen_active
+                            && let Some(label) = crate::accessibility::announce_stream(
+                                crate::streaming::StreamKind::Answer,
+                                id.as_deref(),
+                            )
+                        {
+                            to_mirror.insert(0, label);
+                        }
                         widget.insert_final_answer_with_id(id, lines, source);
                         if !self.alt_screen_active {
                             use std::io::stdout;
@@ -212,8 +241,35 @@ impl App<'_> {
                     }
                     AppState::Onboarding { .. } => {}
                 },
+                AppEvent::StartupAccountsLoaded {
+                    primary_next_reset_at,
+                    secondary_next_reset_at,
+                } => match &mut self.app_state {
+                    AppState::Chat { widget } => {
+                        widget.on_startup_accounts_loaded(primary_next_reset_at, secondary_next_reset_at)
+                    }
+                    AppState::Onboarding { .. } => {}
+                },
+                AppEvent::InterruptedTurnFound { path, turn, resumed } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.on_interrupted_turn_found(path, turn, resumed);
+                    }
+                }
+                AppEvent::RecoverInterruptedTurn(action) => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.recover_interrupted_turn(action);
+                    }
+                }
+                AppEvent::ConnectivityRestored => match &mut self.app_state {
+                    AppState::Chat { widget } => widget.on_connectivity_restored(),
+                    AppState::Onboarding { .. } => {}
+                },
+                AppEvent::StartupStageFinished(stage) => match &mut self.app_state {
+                    AppState::Chat { widget } => widget.finish_startup_stage(stage),
+                    AppState::Onboarding { .. } => {}
+                },
                 AppEvent::RequestRedraw => {
-                    self.schedule_redraw();
+                    self.queue_redraw();
                 }
                 AppEvent::BottomPaneViewChanged => {
                     // Notify the height manager that the bottom pane view changed
@@ -286,9 +342,10 @@ impl App<'_> {
                     };
 
                     self.redraw_inflight.store(false, Ordering::Release);
+                    self.note_frame_drawn();
                     let needs_follow_up = self.post_frame_redraw.swap(false, Ordering::AcqRel);
                     if needs_follow_up {
-                        self.schedule_redraw();
+                        self.queue_redraw();
                     }
 
                     match flatten_draw_result(draw_result) {
@@ -363,6 +420,7 @@ impl App<'_> {
                 }
                 AppEvent::KeyEvent(mut key_event) => {
                     if self.timing_enabled { self.timing.on_key(); }
+                    self.input_awaiting_frame.set(true);
                     #[cfg(windows)]
                     {
                         use crossterm::event::KeyCode;
@@ -475,6 +533,50 @@ impl App<'_> {
                             }
                             self.app_event_tx.send(AppEvent::RequestRedraw);
                         }
+                        KeyEvent {
+                            code: KeyCode::Char('m'),
+                            modifiers: crossterm::event::KeyModifiers::ALT,
+                            kind: KeyEventKind::Press,
+                            ..
+                        } => {
+                            // Start dictation (same as /dictate)
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_dictate_command();
+                            }
+                        }
+                        KeyEvent {
+                            code: KeyCode::Char('p'),
+                            modifiers: crossterm::event::KeyModifiers::ALT,
+                            kind: KeyEventKind::Press,
+                            ..
+                        } => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.toggle_plan_panel();
+                            }
+                        }
+                        KeyEvent {
+                            code: KeyCode::Char('t'),
+                            modifiers: crossterm::event::KeyModifiers::ALT,
+                            kind: KeyEventKind::Press,
+                            ..
+                        } => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.toggle_cell_timestamps();
+                            }
+                        }
+                        KeyEvent {
+                            code: KeyCode::Char(digit @ '1'..='9'),
+                            modifiers: crossterm::event::KeyModifiers::ALT,
+                            kind: KeyEventKind::Press,
+                            ..
+                        } if crate::markdown_links::footnotes_enabled() => {
+                            // Open a numbered link of the last answer
+                            if let AppState::Chat { widget } = &mut self.app_state
+                                && let Some(number) = digit.to_digit(10)
+                            {
+                                widget.open_footnote_link(number as usize);
+                            }
+                        }
                         KeyEvent {
                             code: KeyCode::Char('c'),
                             modifiers: crossterm::event::KeyModifiers::CONTROL,
@@ -582,41 +684,39 @@ impl App<'_> {
                     };
                 }
                 AppEvent::MouseEvent(mouse_event) => {
+                    self.input_awaiting_frame.set(true);
                     self.dispatch_mouse_event(mouse_event);
                 }
                 AppEvent::Paste(text) => {
+                    self.input_awaiting_frame.set(true);
                     self.dispatch_paste_event(text);
                 }
                 AppEvent::OpenExternalEditor { initial } => {
-                    let was_alt_screen = self.alt_screen_active;
-                    self.input_suspended.store(true, Ordering::Release);
-                    let editor_result = match tui::restore() {
-                        Ok(()) => external_editor::run_editor(&initial),
-                        Err(err) => Err(external_editor::ExternalEditorError::LaunchFailed(format!(
-                            "Failed to reset terminal: {err}",
-                        ))),
-                    };
-                    let (new_terminal, new_terminal_info) = tui::init(&self.config)?;
-                    *terminal = new_terminal;
-                    self.terminal_info = new_terminal_info;
-                    terminal.clear()?;
-                    if was_alt_screen {
-                        self.alt_screen_active = true;
-                    } else {
-                        let _ = tui::leave_alt_screen_only();
-                        self.alt_screen_active = false;
-                    }
-                    if let AppState::Chat { widget } = &mut self.app_state {
-                        widget.set_standard_terminal_mode(!self.alt_screen_active);
-                    }
-                    self.input_suspended.store(false, Ordering::Release);
+                    let editor_result = self
+                        .with_terminal_released(terminal, || external_editor::run_editor(&initial))?
+                        .unwrap_or_else(|err| {
+                            Err(external_editor::ExternalEditorError::LaunchFailed(format!(
+                                "Failed to reset terminal: {err}",
+                            )))
+                        });
                     if let AppState::Chat { widget } = &mut self.app_state {
                         match editor_result {
                             Ok(text) => widget.set_composer_text(text),
                             Err(err) => widget.debug_notice(err.to_string()),
                         }
                     }
-                    self.app_event_tx.send(AppEvent::RequestRedraw);
+                }
+                AppEvent::OpenOutputPager { path } => {
+                    let pager_result = self
+                        .with_terminal_released(terminal, || external_editor::run_pager(&path))?
+                        .unwrap_or_else(|err| {
+                            Err(external_editor::ExternalEditorError::PagerLaunchFailed(format!(
+                                "Failed to reset terminal: {err}",
+                            )))
+                        });
+                    if let (Err(err), AppState::Chat { widget }) = (pager_result, &mut self.app_state) {
+                        widget.debug_notice(err.to_string());
+                    }
                 }
                 AppEvent::RegisterPastedImage { placeholder, path } => {
                     if let AppState::Chat { widget } = &mut self.app_state {
@@ -1098,7 +1198,14 @@ impl App<'_> {
                 }
                 // fallthrough handled by break
                 AppEvent::CodexOp(op) => match &mut self.app_state {
-                    AppState::Chat { widget } => widget.submit_op(op),
+                    AppState::Chat { widget } => {
+                        if let (Some(notifier), Op::ExecApproval { id, .. } | Op::PatchApproval { id, .. }) =
+                            (crate::termux_api::notifier(), &op)
+                        {
+                            notifier.dismiss_approval(id);
+                        }
+                        widget.submit_op(op)
+                    }
                     AppState::Onboarding { .. } => {}
                 },
                 AppEvent::RequestUserInputAnswer { turn_id, response } => {
@@ -1220,6 +1327,11 @@ impl App<'_> {
                                 widget.handle_undo_command();
                             }
                         }
+                        SlashCommand::UndoPatch => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_undo_patch_command(command_args);
+                            }
+                        }
                         SlashCommand::Review => {
                             if let AppState::Chat { widget } = &mut self.app_state {
                                 if command_args.is_empty() {
@@ -1249,11 +1361,81 @@ impl App<'_> {
                                 widget.handle_push_command();
                             }
                         }
+                        SlashCommand::Pr => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_pr_command();
+                            }
+                        }
+                        SlashCommand::Map => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_map_command();
+                            }
+                        }
+                        SlashCommand::Fetch => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_fetch_command(command_args);
+                            }
+                        }
+                        SlashCommand::Context => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_context_command(command_args);
+                            }
+                        }
+                        SlashCommand::Retry => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_retry_command(command_args);
+                            }
+                        }
+                        SlashCommand::Schema => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_schema_command(command_args);
+                            }
+                        }
+                        SlashCommand::Metered => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_metered_command(command_args);
+                            }
+                        }
+                        SlashCommand::Cd => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_cd_command(command_args);
+                            }
+                        }
+                        SlashCommand::Scope => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_scope_command(command_args);
+                            }
+                        }
+                        SlashCommand::Spawn => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_spawn_command(command_args);
+                            }
+                        }
+                        SlashCommand::Artifacts => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_artifacts_command(command_args);
+                            }
+                        }
+                        SlashCommand::Tests => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.show_test_failures();
+                            }
+                        }
                         SlashCommand::Resume => {
                             if let AppState::Chat { widget } = &mut self.app_state {
                                 widget.show_resume_picker();
                             }
                         }
+                        SlashCommand::Copy => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_copy_command();
+                            }
+                        }
+                        SlashCommand::Dictate => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_dictate_command();
+                            }
+                        }
                         SlashCommand::Rename => {
                             if let AppState::Chat { widget } = &mut self.app_state {
                                 let trimmed = command_args.trim();
@@ -1332,6 +1514,7 @@ impl App<'_> {
                             new_widget.enable_perf(self.timing_enabled);
                             self.app_state = AppState::Chat { widget: Box::new(new_widget) };
                             self.terminal_runs.clear();
+                            self.set_scope(None);
                             self.app_event_tx.send(AppEvent::RequestRedraw);
                         }
                         SlashCommand::Init => {
@@ -1363,10 +1546,27 @@ impl App<'_> {
                             if let Err(e) = code_login::logout(&self.config.code_home) { tracing::error!("failed to logout: {e}"); }
                             break 'main;
                         }
+                        SlashCommand::Diff if command_args.split_whitespace().next() == Some("export") => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_diff_export(command_args["export".len()..].trim());
+                            }
+                        }
+                        SlashCommand::Diff if command_args == "refresh" => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_diff_refresh();
+                            }
+                        }
+                        SlashCommand::Diff if command_args == "commit" => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_diff_commit();
+                            }
+                        }
                         SlashCommand::Diff => {
                             let tx = self.app_event_tx.clone();
+                            let cwd = self.config.cwd.clone();
+                            let scope = self.scope.clone();
                             tokio::spawn(async move {
-                                match get_git_diff().await {
+                                match get_git_diff(&cwd, scope.as_deref()).await {
                                     Ok((is_git_repo, diff_text)) => {
                                         let text = if is_git_repo {
                                             diff_text
@@ -1393,6 +1593,21 @@ impl App<'_> {
                                 widget.handle_project_command(command_args);
                             }
                         }
+                        SlashCommand::Rerun => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_rerun_command(command_args);
+                            }
+                        }
+                        SlashCommand::Output => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_output_command(command_args);
+                            }
+                        }
+                        SlashCommand::Debug => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_debug_command(command_args);
+                            }
+                        }
                         SlashCommand::Auto => {
                             if let AppState::Chat { widget } = &mut self.app_state {
                                 let goal = if command_args.is_empty() {
@@ -1473,6 +1688,11 @@ impl App<'_> {
                                 widget.handle_mode_command(command_args);
                             }
                         }
+                        SlashCommand::Profile => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_profile_command(command_args);
+                            }
+                        }
                         SlashCommand::Reasoning => {
                             if let AppState::Chat { widget } = &mut self.app_state {
                                 widget.handle_reasoning_command(command_args);
@@ -1586,13 +1806,16 @@ impl App<'_> {
                 AppEvent::SwitchCwd(new_cwd, initial_prompt) => {
                     let target = new_cwd.clone();
                     self.config.cwd = target.clone();
+                    crate::hyperlinks::set_cwd(&target);
+                    self.set_scope(None);
                     if let AppState::Chat { widget } = &mut self.app_state {
                         widget.switch_cwd(target, initial_prompt);
                     }
                 }
-                AppEvent::ResumePickerLoaded { cwd, candidates } => {
+                AppEvent::ScopeChanged(scope) => self.set_scope(scope),
+                AppEvent::ResumePickerLoaded { cwd, candidates, complete } => {
                     if let AppState::Chat { widget } = &mut self.app_state {
-                        widget.present_resume_picker(cwd, candidates);
+                        widget.present_resume_picker(cwd, candidates, complete);
                     }
                 }
                 AppEvent::ResumePickerLoadFailed { message } => {
@@ -1600,6 +1823,19 @@ impl App<'_> {
                         widget.handle_resume_picker_load_failed(message);
                     }
                 }
+                AppEvent::SummarizeResumeSession(path) => {
+                    let tx = self.app_event_tx.clone();
+                    tokio::task::spawn_blocking(move || {
+                        let result = code_core::summarize_rollout(&path)
+                            .map_err(|err| format!("Failed to summarize session: {err}"));
+                        tx.send(AppEvent::ResumeSummaryReady { path, result });
+                    });
+                }
+                AppEvent::ResumeSummaryReady { path, result } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.apply_resume_summary(path, result);
+                    }
+                }
                 AppEvent::SessionRenameCompleted { message } => {
                     if let AppState::Chat { widget } = &mut self.app_state {
                         widget.debug_notice(message);
@@ -1623,6 +1859,7 @@ impl App<'_> {
                         new_widget.enable_perf(self.timing_enabled);
                         self.app_state = AppState::Chat { widget: Box::new(new_widget) };
                         self.terminal_runs.clear();
+                        self.set_scope(None);
                         self.app_event_tx.send(AppEvent::RequestRedraw);
                     }
                 }
@@ -1768,10 +2005,37 @@ impl App<'_> {
                     self.apply_terminal_title();
                 }
                 AppEvent::EmitTuiNotification { title, body } => {
-                    if let Some(message) = Self::format_notification_message(&title, body.as_deref()) {
+                    if let Some(notifier) = crate::termux_api::notifier() {
+                        notifier.notify(&title, body.as_deref());
+                    } else if let Some(message) = Self::format_notification_message(&title, body.as_deref()) {
+                        Self::emit_osc9_notification(&message);
+                    }
+                }
+                AppEvent::EmitApprovalNotification { call_id, title, detail } => {
+                    if let Some(notifier) = crate::termux_api::notifier() {
+                        notifier.notify_approval(&call_id, &title, &detail);
+                    } else if let Some(message) = Self::format_notification_message(&title, Some(&detail)) {
                         Self::emit_osc9_notification(&message);
                     }
                 }
+                AppEvent::DictationFinished(result) => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.on_dictation_finished(result);
+                    }
+                }
+                AppEvent::EmitFeedback(kind) => {
+                    if matches!(kind, FeedbackKind::Bell | FeedbackKind::Both) {
+                        Self::emit_terminal_bell();
+                    }
+                    if matches!(kind, FeedbackKind::Vibrate | FeedbackKind::Both) {
+                        crate::termux_api::vibrate();
+                    }
+                }
+                AppEvent::ExternalApprovalDecision { call_id, approved } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.resolve_external_approval(&call_id, approved);
+                    }
+                }
                 AppEvent::UpdateMcpServer { name, enable } => {
                     if let AppState::Chat { widget } = &mut self.app_state {
                         widget.toggle_mcp_server(&name, enable);
@@ -1877,6 +2141,36 @@ impl App<'_> {
                         widget.handle_review_command(args);
                     }
                 }
+                AppEvent::FixReviewFinding(index) => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.fix_review_finding(index);
+                    }
+                }
+                AppEvent::SubmitReviewFindingFix { index, hunk } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.submit_review_finding_fix(index, hunk);
+                    }
+                }
+                AppEvent::FixTestFailure(index) => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.fix_test_failure(index);
+                    }
+                }
+                AppEvent::SpawnBatchStarted { batch_id, prompt, root, base, agents } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.on_spawn_batch_started(batch_id, prompt, root, base, agents);
+                    }
+                }
+                AppEvent::SpawnDiffsReady { batch_id, diffs } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.show_spawn_results(batch_id, diffs);
+                    }
+                }
+                AppEvent::MergeSpawnResult(index) => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.merge_spawn_result(index);
+                    }
+                }
                 AppEvent::UpdateReviewAutoResolveEnabled(enabled) => {
                     if let AppState::Chat { widget } = &mut self.app_state {
                         widget.set_review_auto_resolve_enabled(enabled);
@@ -2147,6 +2441,30 @@ impl App<'_> {
                         widget.add_diff_output(text);
                     }
                 }
+                AppEvent::SessionCommitReady { repo_root, message } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.show_session_commit_prompt(repo_root, message);
+                    }
+                }
+                AppEvent::CommandPluginFinished { name, output, result } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.handle_command_plugin_finished(name, output, result);
+                    }
+                }
+                AppEvent::SubmitSessionCommit { repo_root, message } => {
+                    let tx = self.app_event_tx.clone();
+                    tokio::spawn(async move {
+                        let text = match code_core::git_session::commit_staged(&repo_root, &message).await {
+                            Ok(hash) => format!("Committed session changes as {hash}"),
+                            Err(err) => format!("`/diff commit` — {err}"),
+                        };
+                        tx.send(AppEvent::InsertBackgroundEvent {
+                            message: text,
+                            placement: crate::app_event::BackgroundPlacement::Tail,
+                            order: None,
+                        });
+                    });
+                }
                 AppEvent::SetThemeSplitPreview { current, preview } => {
                     let next = ThemeSplitPreview { current, preview };
                     let unchanged = self
@@ -2465,6 +2783,7 @@ impl App<'_> {
                     }
                     self.app_state = AppState::Chat { widget: Box::new(w) };
                     self.terminal_runs.clear();
+                    self.set_scope(None);
                 }
                 AppEvent::StartFileSearch(query) => {
                     if !query.is_empty() {
@@ -2610,6 +2929,7 @@ impl App<'_> {
                         self.app_state = AppState::Chat { widget: Box::new(new_widget) };
                     }
                     self.terminal_runs.clear();
+                    self.set_scope(None);
                     // Reset any transient state from the previous widget/session
                     self.commit_anim_running.store(false, Ordering::Release);
                     self.last_esc_time = None;
@@ -57,6 +57,18 @@ impl App<'_> {
         let (bulk_tx, app_event_rx_bulk) = channel();
         let app_event_tx = AppEventSender::new_dual(high_tx, bulk_tx);
 
+        crate::hyperlinks::configure(
+            config.tui.hyperlinks && !crate::chatwidget::is_test_mode(),
+            &config.cwd,
+        );
+
+        crate::markdown_links::configure(config.tui.link_style);
+        crate::accessibility::configure(config.tui.accessibility);
+
+        if config.tui.termux_api && !crate::chatwidget::is_test_mode() {
+            crate::termux_api::init(app_event_tx.clone());
+        }
+
         {
             let remote_tx = app_event_tx.clone();
             let remote_auth_manager = auth_manager;
@@ -324,7 +336,11 @@ impl App<'_> {
             config,
             latest_upgrade_version,
             file_search,
+            scope: None,
             pending_redraw,
+            min_frame_interval: super::state::min_frame_interval(config.tui.stream.max_fps),
+            last_frame_at: std::cell::Cell::new(None),
+            input_awaiting_frame: std::cell::Cell::new(false),
             redraw_inflight,
             post_frame_redraw,
             stdout_backpressure_skips: 0,
@@ -1,6 +1,6 @@
 use std::sync::atomic::Ordering;
-use std::thread;
 use std::time::Duration;
+use std::time::Instant;
 
 use color_eyre::eyre::Result;
 use crossterm::SynchronizedUpdate;
@@ -10,18 +10,44 @@ use ratatui::buffer::Cell;
 use ratatui::layout::Rect;
 
 use crate::app_event::AppEvent;
-use crate::thread_spawner;
+use crate::dirty_regions::FrameDamage;
 use crate::tui;
 
-use super::state::{App, AppState, REDRAW_DEBOUNCE};
+use super::state::{coalesced_frame_delay, App, AppState};
 
 impl App<'_> {
-    /// Schedule a redraw immediately and open a short debounce window to coalesce
-    /// subsequent requests. Crucially, even if a timer is already armed (e.g., an
-    /// animation scheduled a future frame), we still trigger an immediate redraw
-    /// to keep keypress echo latency low.
-    #[allow(clippy::unwrap_used)]
+    /// Schedule a redraw. Requests from background updates are coalesced so
+    /// frames stay `min_frame_interval` apart; a frame owed to user input is
+    /// always drawn immediately to keep keypress echo latency low.
     pub(super) fn schedule_redraw(&self) {
+        self.app_event_tx.mark_full_redraw("app");
+        self.queue_redraw();
+    }
+
+    /// Like `schedule_redraw`, but leaves the damage to whoever asked for the
+    /// frame (see `AppEventSender::request_redraw_of`).
+    #[allow(clippy::unwrap_used)]
+    pub(super) fn queue_redraw(&self) {
+        if !self.input_awaiting_frame.get()
+            && let Some(delay) = coalesced_frame_delay(
+                self.last_frame_at.get(),
+                self.min_frame_interval,
+                Instant::now(),
+            )
+        {
+            // A streaming burst: one deferred frame covers every request until
+            // the interval has passed. The frame timer sends RequestRedraw,
+            // which lands back here once drawing is allowed.
+            if self
+                .pending_redraw
+                .compare_exchange(false, true, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok()
+            {
+                self.schedule_redraw_in(delay);
+            }
+            return;
+        }
+
         // Only queue a new frame when one is not already in flight; otherwise record
         // that we owe a follow-up immediately after the active frame completes.
         let should_send = self
@@ -33,24 +59,14 @@ impl App<'_> {
         } else {
             self.post_frame_redraw.store(true, Ordering::Release);
         }
+    }
 
-        // Arm debounce window if not already armed.
-        if self
-            .pending_redraw
-            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
-            .is_ok()
-        {
-            let pending_redraw = self.pending_redraw.clone();
-            let pending_redraw_for_thread = pending_redraw.clone();
-            if thread_spawner::spawn_lightweight("redraw-debounce", move || {
-                thread::sleep(REDRAW_DEBOUNCE);
-                pending_redraw_for_thread.store(false, Ordering::Release);
-            })
-            .is_none()
-            {
-                pending_redraw.store(false, Ordering::Release);
-            }
-        }
+    /// Bookkeeping after a frame is drawn: it satisfied any coalesced request
+    /// and any input waiting to be echoed.
+    pub(super) fn note_frame_drawn(&self) {
+        self.last_frame_at.set(Some(Instant::now()));
+        self.pending_redraw.store(false, Ordering::Release);
+        self.input_awaiting_frame.set(false);
     }
 
     /// Schedule a redraw after the specified duration.
@@ -132,6 +148,13 @@ impl App<'_> {
             .theme_split_preview
             .filter(|split| split.current != split.preview);
         let theme_before_split = split_preview.map(|_| crate::theme::current_theme_name());
+        // The split preview paints the frame twice with different themes, so
+        // nothing from the previous frame can be reused.
+        let damage = if split_preview.is_some() {
+            FrameDamage::full("theme preview")
+        } else {
+            self.app_event_tx.take_frame_damage()
+        };
 
         let completed_frame = terminal.draw(|frame| {
             match &mut self.app_state {
@@ -143,6 +166,7 @@ impl App<'_> {
                         if let Some((x, y)) = cursor_pos {
                             frame.set_cursor_position((x, y));
                         }
+                        widget.set_frame_damage(damage);
                         frame.render_widget_ref(&**widget, area);
                         let left_snapshot = snapshot_left_half(frame.buffer_mut(), area);
 
@@ -150,17 +174,20 @@ impl App<'_> {
                         if let Some((x, y)) = cursor_pos {
                             frame.set_cursor_position((x, y));
                         }
+                        widget.set_frame_damage(damage);
                         frame.render_widget_ref(&**widget, area);
                         restore_left_half(frame.buffer_mut(), area, &left_snapshot);
                     } else {
                         if let Some((x, y)) = cursor_pos {
                             frame.set_cursor_position((x, y));
                         }
+                        widget.set_frame_damage(damage);
                         frame.render_widget_ref(&**widget, area);
                     }
                 }
                 AppState::Onboarding { screen } => frame.render_widget_ref(&*screen, frame.area()),
             }
+            crate::accessibility::asciify_buffer(frame.buffer_mut());
         });
 
         if let Some(theme_name) = theme_before_split {
@@ -168,6 +195,7 @@ impl App<'_> {
         }
 
         let completed_frame = completed_frame?;
+        crate::startup::mark("first_paint");
         self.buffer_diff_profiler.record(&completed_frame);
         Ok(())
     }
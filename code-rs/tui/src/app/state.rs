@@ -30,12 +30,28 @@ use tokio::task::JoinHandle;
 #[cfg(unix)]
 use signal_hook::SigId;
 
-/// Time window for debouncing redraw requests.
-///
-/// Temporarily widened to ~30 FPS (33 ms) to coalesce bursts of updates while
-/// we smooth out per-frame hotspots; keeps redraws responsive without pegging
-/// the main thread.
-pub(super) const REDRAW_DEBOUNCE: Duration = Duration::from_millis(33);
+/// Frame rate cap for redraws requested by background updates (streaming
+/// deltas, tool output) when `tui.stream.max_fps` is unset.
+pub(super) const DEFAULT_MAX_FPS: u32 = 60;
+
+/// Minimum spacing between coalesced frames; zero disables coalescing.
+pub(super) fn min_frame_interval(max_fps: Option<u32>) -> Duration {
+    match max_fps.unwrap_or(DEFAULT_MAX_FPS) {
+        0 => Duration::ZERO,
+        fps => Duration::from_secs(1) / fps,
+    }
+}
+
+/// How long a non-input redraw must wait so frames stay `min_interval`
+/// apart, or `None` when it can draw now.
+pub(super) fn coalesced_frame_delay(
+    last_frame_at: Option<Instant>,
+    min_interval: Duration,
+    now: Instant,
+) -> Option<Duration> {
+    let next_allowed = last_frame_at? + min_interval;
+    (now < next_allowed).then(|| next_allowed - now)
+}
 // Prevent bulk events (Codex output/tool completions) from being starved behind a
 // continuous stream of high-priority events (e.g., redraw scheduling).
 pub(super) const HIGH_EVENT_BURST_MAX: u32 = 32;
@@ -229,9 +245,20 @@ pub(crate) struct App<'a> {
     pub(super) latest_upgrade_version: Option<String>,
 
     pub(super) file_search: FileSearchManager,
+    /// `/scope` subproject; file search and `/diff` stay inside it.
+    pub(super) scope: Option<PathBuf>,
 
-    /// True when a redraw has been scheduled but not yet executed (debounce window).
+    /// True while a coalesced frame is armed on the frame timer; redraw
+    /// requests that arrive before it fires ride on that frame.
     pub(super) pending_redraw: Arc<AtomicBool>,
+    /// Minimum spacing between frames requested by background updates
+    /// (`tui.stream.max_fps`).
+    pub(super) min_frame_interval: Duration,
+    /// When the last frame finished drawing.
+    pub(super) last_frame_at: std::cell::Cell<Option<Instant>>,
+    /// Set by key, mouse and paste events so the frame that echoes them is
+    /// never held back by coalescing.
+    pub(super) input_awaiting_frame: std::cell::Cell<bool>,
     /// Tracks whether a frame is currently queued or being drawn. Used to coalesce
     /// rapid-fire redraw requests without dropping the final state.
     pub(super) redraw_inflight: Arc<AtomicBool>,
@@ -614,6 +641,8 @@ impl TimingStats {
 #[cfg(test)]
 mod tests {
     use super::FrameTimer;
+    use super::coalesced_frame_delay;
+    use super::min_frame_interval;
     use crate::app_event::AppEvent;
     use crate::app_event_sender::AppEventSender;
     use crate::thread_spawner;
@@ -662,6 +691,25 @@ mod tests {
         String::from_utf8_lossy(&guard).to_string()
     }
 
+    #[test]
+    fn background_redraws_wait_out_the_frame_interval() {
+        let interval = min_frame_interval(Some(50));
+        assert_eq!(interval, Duration::from_millis(20));
+        assert_eq!(min_frame_interval(Some(0)), Duration::ZERO);
+
+        let last = std::time::Instant::now();
+        assert_eq!(coalesced_frame_delay(None, interval, last), None);
+        assert_eq!(
+            coalesced_frame_delay(Some(last), interval, last + Duration::from_millis(5)),
+            Some(Duration::from_millis(15))
+        );
+        assert_eq!(
+            coalesced_frame_delay(Some(last), interval, last + interval),
+            None
+        );
+        assert_eq!(coalesced_frame_delay(Some(last), Duration::ZERO, last), None);
+    }
+
     fn count_occurrences(haystack: &str, needle: &str) -> usize {
         haystack.match_indices(needle).count()
     }
@@ -1,4 +1,5 @@
 use std::io::{Read, Write};
+use std::sync::atomic::Ordering;
 use std::sync::mpsc::channel;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
@@ -85,6 +86,12 @@ impl App<'_> {
         Some(message)
     }
 
+    pub(super) fn emit_terminal_bell() {
+        let mut stdout = std::io::stdout();
+        let _ = stdout.write_all(b"\x07");
+        let _ = stdout.flush();
+    }
+
     pub(super) fn emit_osc9_notification(message: &str) {
         let payload = format!("\u{1b}]9;{message}\u{7}");
         let mut stdout = std::io::stdout();
@@ -505,8 +512,42 @@ impl App<'_> {
         Ok(())
     }
 
+    /// Hands the terminal to a foreground program (editor, pager) for the
+    /// duration of `run`, then re-initialises the TUI in its previous screen
+    /// mode. The inner error reports a failure to release the terminal, in
+    /// which case `run` was not called.
+    pub(super) fn with_terminal_released<T>(
+        &mut self,
+        terminal: &mut tui::Tui,
+        run: impl FnOnce() -> T,
+    ) -> Result<std::io::Result<T>> {
+        let was_alt_screen = self.alt_screen_active;
+        self.input_suspended.store(true, Ordering::Release);
+        let result = tui::restore().map(|()| run());
+        let (new_terminal, new_terminal_info) = tui::init(&self.config)?;
+        *terminal = new_terminal;
+        self.terminal_info = new_terminal_info;
+        terminal.clear()?;
+        if was_alt_screen {
+            self.alt_screen_active = true;
+        } else {
+            let _ = tui::leave_alt_screen_only();
+            self.alt_screen_active = false;
+        }
+        if let AppState::Chat { widget } = &mut self.app_state {
+            widget.set_standard_terminal_mode(!self.alt_screen_active);
+        }
+        self.input_suspended.store(false, Ordering::Release);
+        self.app_event_tx.send(AppEvent::RequestRedraw);
+        Ok(result)
+    }
+
     /// Toggle between alternate-screen TUI and standard terminal buffer (Ctrl+T).
     pub(super) fn toggle_screen_mode(&mut self, _terminal: &mut tui::Tui) -> Result<()> {
+        if crate::insert_history::is_plain() {
+            // `--plain` sessions stay in the terminal's own buffer.
+            return Ok(());
+        }
         if self.alt_screen_active {
             // Leave alt screen only; keep raw mode enabled for key handling.
             let _ = crate::tui::leave_alt_screen_only();
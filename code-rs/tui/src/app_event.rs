@@ -96,6 +96,17 @@ pub(crate) enum GitInitResume {
     DispatchCommand { command: SlashCommand, command_text: String },
 }
 
+/// What to do with a turn the previous run never finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TurnRecovery {
+    /// Ask the model to pick up where it stopped.
+    Continue,
+    /// Send the interrupted message again.
+    Resubmit,
+    /// Keep the session as it is.
+    Dismiss,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum BackgroundPlacement {
     /// Default: append to the end of the current request/history window.
@@ -155,6 +166,24 @@ pub(crate) enum AppEvent {
     /// Emit a best-effort OSC 9 notification from the terminal.
     EmitTuiNotification { title: String, body: Option<String> },
 
+    /// Notify that an exec or patch approval is waiting on the user. On
+    /// Termux the notification carries approve/deny buttons.
+    EmitApprovalNotification {
+        call_id: String,
+        title: String,
+        detail: String,
+    },
+
+    /// Speech-to-text finished: the transcription, or an error message.
+    DictationFinished(Result<String, String>),
+
+    /// Ring the terminal bell and/or vibrate (Termux) for a notable event.
+    EmitFeedback(code_core::config_types::FeedbackKind),
+
+    /// Approval decision made outside the TUI (e.g. a Termux notification
+    /// button) for the pending request with `call_id`.
+    ExternalApprovalDecision { call_id: String, approved: bool },
+
     /// Schedule a one-shot animation frame roughly after the given duration.
     /// Multiple requests are coalesced by the central frame scheduler.
     ScheduleFrameIn(Duration),
@@ -192,6 +221,10 @@ pub(crate) enum AppEvent {
     /// Open the external editor with the current composer text.
     OpenExternalEditor { initial: String },
 
+    /// Page through a file (a command's full output, the last wire log
+    /// exchange) with `$PAGER`.
+    OpenOutputPager { path: PathBuf },
+
     /// Request to exit the application gracefully.
     ExitRequest,
 
@@ -268,15 +301,30 @@ pub(crate) enum AppEvent {
     /// initial prompt once the new session is ready.
     SwitchCwd(std::path::PathBuf, Option<String>),
 
-    /// Resume picker data finished loading
+    /// `/scope` set (absolute subproject directory) or cleared.
+    ScopeChanged(Option<std::path::PathBuf>),
+
+    /// Resume picker data loaded. Provisional sets (`complete == false`)
+    /// arrive while session files are still being scanned; the final set
+    /// replaces them.
     ResumePickerLoaded {
         cwd: std::path::PathBuf,
         candidates: Vec<ResumeCandidate>,
+        complete: bool,
     },
 
     /// Resume picker failed to load
     ResumePickerLoadFailed { message: String },
 
+    /// Recompute the resume picker's summary for the session at this path.
+    SummarizeResumeSession(std::path::PathBuf),
+
+    /// Summary for a resume picker row, or why it could not be computed.
+    ResumeSummaryReady {
+        path: std::path::PathBuf,
+        result: Result<code_core::SessionSummary, String>,
+    },
+
     /// Session nickname update finished
     SessionRenameCompleted { message: String },
 
@@ -464,6 +512,28 @@ pub(crate) enum AppEvent {
     /// Run the review command with the given argument string (mirrors `/review <args>`)
     RunReviewCommand(String),
 
+    /// Send a fix prompt for one finding of the last `/review`.
+    FixReviewFinding(usize),
+    /// The diff hunk for a finding's fix prompt has been looked up.
+    SubmitReviewFindingFix { index: usize, hunk: Option<String> },
+
+    /// Send a fix prompt for one failing test of the last `run_tests` call.
+    FixTestFailure(usize),
+
+    /// The agents of a `/spawn` fan-out were created; `agents` holds each
+    /// one's id and label.
+    SpawnBatchStarted {
+        batch_id: String,
+        prompt: String,
+        root: PathBuf,
+        base: String,
+        agents: Vec<(String, String)>,
+    },
+    /// Every `/spawn` agent finished; one diff (or error) per agent, in order.
+    SpawnDiffsReady { batch_id: String, diffs: Vec<Result<String, String>> },
+    /// Merge one `/spawn` agent's changes into the workspace.
+    MergeSpawnResult(usize),
+
     /// Open a bottom-pane form that lets the user select a commit to review.
     StartReviewCommitPicker,
     /// Populate the commit picker with retrieved commit entries.
@@ -581,6 +651,20 @@ pub(crate) enum AppEvent {
     #[allow(dead_code)]
     DiffResult(String),
 
+    /// `/diff commit` staged the session's files; `message` is the suggested
+    /// commit message (empty when the model could not provide one).
+    SessionCommitReady { repo_root: PathBuf, message: String },
+    /// The user confirmed the `/diff commit` message.
+    SubmitSessionCommit { repo_root: PathBuf, message: String },
+
+    /// A command plugin from `code_home/commands` exited; `result` holds its
+    /// stdout or the reason it failed.
+    CommandPluginFinished {
+        name: String,
+        output: code_core::command_plugins::CommandPluginOutput,
+        result: Result<String, String>,
+    },
+
     InsertHistory(Vec<Line<'static>>),
     InsertHistoryWithKind { id: Option<String>, kind: StreamKind, lines: Vec<Line<'static>> },
     /// Finalized assistant answer with raw markdown for re-rendering under theme changes.
@@ -600,6 +684,21 @@ pub(crate) enum AppEvent {
     /// Background rate limit refresh persisted an account snapshot.
     RateLimitSnapshotStored { account_id: String },
 
+    /// The active account's stored rate-limit reset times, read off the UI
+    /// thread during startup.
+    StartupAccountsLoaded {
+        primary_next_reset_at: Option<chrono::DateTime<chrono::Utc>>,
+        secondary_next_reset_at: Option<chrono::DateTime<chrono::Utc>>,
+    },
+
+    /// A connectivity probe reached the model provider again after the
+    /// network was reported unavailable.
+    ConnectivityRestored,
+
+    /// A background startup stage ended without its usual completion event
+    /// (e.g. the session failed to start).
+    StartupStageFinished(crate::startup::StartupStage),
+
     #[allow(dead_code)]
     StartCommitAnimation,
     #[allow(dead_code)]
@@ -637,6 +736,19 @@ pub(crate) enum AppEvent {
     /// Start a new chat session by resuming from the given rollout file
     ResumeFrom(std::path::PathBuf),
 
+    /// The rollout at `path` ends in a turn that never finished. `resumed`
+    /// is true when it belongs to the current session rather than to the
+    /// previous session in this directory.
+    InterruptedTurnFound {
+        path: std::path::PathBuf,
+        turn: code_core::InterruptedTurn,
+        resumed: bool,
+    },
+
+    /// Apply `TurnRecovery` to the interrupted turn of the current session,
+    /// once it has been found.
+    RecoverInterruptedTurn(TurnRecovery),
+
     /// Begin jump-back to the Nth last user message (1 = latest).
     /// Trims visible history up to that point and pre-fills the composer.
     JumpBack { nth: usize, prefill: String, history_snapshot: Option<HistorySnapshot> },
@@ -1,7 +1,10 @@
+use std::sync::Arc;
 use std::sync::mpsc::Sender;
+use std::time::Duration;
 
 use crate::app_event::{AppEvent, BackgroundPlacement};
 use crate::chatwidget::BackgroundOrderTicket;
+use crate::dirty_regions::{DirtyRegions, FrameDamage, Regions};
 use crate::session_log;
 use code_core::protocol::OrderMeta;
 
@@ -11,18 +14,20 @@ pub(crate) struct AppEventSender {
     high_tx: Sender<AppEvent>,
     // Bulk/streaming events (history inserts, commit ticks, file search, etc.).
     bulk_tx: Sender<AppEvent>,
+    // Regions of the chat frame redraw requests have touched since the last frame.
+    dirty: Arc<DirtyRegions>,
 }
 
 impl AppEventSender {
     /// Create a sender that splits events by priority across two channels.
     pub(crate) fn new_dual(high_tx: Sender<AppEvent>, bulk_tx: Sender<AppEvent>) -> Self {
-        Self { high_tx, bulk_tx }
+        Self { high_tx, bulk_tx, dirty: Arc::default() }
     }
     /// Backward‑compatible constructor for tests/fixtures that expect a single
     /// channel. Routes both high‑priority and bulk events to the same sender.
     #[allow(dead_code)]
     pub(crate) fn new(app_event_tx: Sender<AppEvent>) -> Self {
-        Self { high_tx: app_event_tx.clone(), bulk_tx: app_event_tx }
+        Self { high_tx: app_event_tx.clone(), bulk_tx: app_event_tx, dirty: Arc::default() }
     }
 
     /// Send an event to the app event channel. If it fails, we swallow the
@@ -31,10 +36,40 @@ impl AppEventSender {
         let _ = self.send_with_result(event);
     }
 
+    /// Send `event` once `delay` has elapsed, from the shared timer thread.
+    pub(crate) fn send_after(&self, delay: Duration, event: AppEvent) {
+        let tx = self.clone();
+        crate::timer_queue::run_after(delay, move || tx.send(event));
+    }
+
     /// Send an event while surfacing whether the channel was still connected.
     /// Returns `true` if the event was delivered, `false` if the channel was
     /// disconnected (already logged).
     pub(crate) fn send_with_result(&self, event: AppEvent) -> bool {
+        if matches!(event, AppEvent::RequestRedraw) {
+            self.dirty.mark(Regions::FULL, "redraw");
+        }
+        self.route(event)
+    }
+
+    /// Request a redraw that only changes `regions` of the chat frame, so the
+    /// rest can be reused from the previous frame.
+    pub(crate) fn request_redraw_of(&self, regions: Regions, cause: &'static str) {
+        self.dirty.mark(regions, cause);
+        let _ = self.route(AppEvent::RequestRedraw);
+    }
+
+    /// Mark the whole frame dirty without queueing a redraw event.
+    pub(crate) fn mark_full_redraw(&self, cause: &'static str) {
+        self.dirty.mark(Regions::FULL, cause);
+    }
+
+    /// Damage collected since the previous call; consumed once per frame.
+    pub(crate) fn take_frame_damage(&self) -> FrameDamage {
+        self.dirty.take()
+    }
+
+    fn route(&self, event: AppEvent) -> bool {
         // Record inbound events for high-fidelity session replay.
         // Avoid double-logging Ops; those are logged at the point of submission.
         if !matches!(event, AppEvent::CodexOp(_)) {
@@ -50,6 +85,8 @@ impl AppEventSender {
                 | AppEvent::ExitRequest
                 | AppEvent::SetTerminalTitle { .. }
                 | AppEvent::EmitTuiNotification { .. }
+                | AppEvent::EmitApprovalNotification { .. }
+                | AppEvent::EmitFeedback(_)
                 | AppEvent::AutoCoordinatorCountdown { .. }
         );
 
@@ -8,6 +8,7 @@ use crate::app_event_sender::AppEventSender;
 use crate::chatwidget::BackgroundOrderTicket;
 use crate::user_approval_widget::ApprovalRequest;
 use crate::user_approval_widget::UserApprovalWidget;
+use code_core::protocol::ReviewDecision;
 
 use super::BottomPane;
 use super::BottomPaneView;
@@ -93,6 +94,28 @@ impl<'a> BottomPaneView<'a> for ApprovalModalView<'a> {
         self.enqueue_request(req, ticket);
         None
     }
+
+    fn try_resolve_approval(&mut self, id: &str, decision: ReviewDecision) -> bool {
+        if !self.current.is_complete() && self.current.request_id() == Some(id) {
+            self.current.resolve_external(decision);
+            self.maybe_advance();
+            return true;
+        }
+        let Some(idx) = self.queue.iter().position(|(req, _)| {
+            matches!(
+                req,
+                ApprovalRequest::Exec { id: queued, .. } | ApprovalRequest::ApplyPatch { id: queued, .. }
+                    if queued == id
+            )
+        }) else {
+            return false;
+        };
+        if let Some((req, ticket)) = self.queue.remove(idx) {
+            let mut widget = super::build_user_approval_widget(req, ticket, self.app_event_tx.clone());
+            widget.resolve_external(decision);
+        }
+        true
+    }
 }
 
 impl ApprovalModalView<'_> {
@@ -1,5 +1,6 @@
 use crate::chatwidget::BackgroundOrderTicket;
 use crate::user_approval_widget::ApprovalRequest;
+use code_core::protocol::ReviewDecision;
 use crossterm::event::{KeyEvent, MouseEvent};
 use std::any::Any;
 use ratatui::buffer::Buffer;
@@ -114,6 +115,12 @@ pub(crate) trait BottomPaneView<'a> {
         Some((request, ticket))
     }
 
+    /// Resolve the pending approval with `id` using a decision made outside
+    /// the view. Returns true when the view held that request.
+    fn try_resolve_approval(&mut self, _id: &str, _decision: ReviewDecision) -> bool {
+        false
+    }
+
     /// Handle pasted text while this view is active. Return whether a redraw
     /// is needed. Default: ignore paste.
     fn handle_paste(&mut self, _text: String) -> ConditionalUpdate {
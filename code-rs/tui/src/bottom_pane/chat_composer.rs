@@ -31,6 +31,7 @@ use crate::components::textarea::TextArea;
 use crate::components::textarea::TextAreaState;
 use crate::clipboard_paste::normalize_pasted_path;
 use crate::clipboard_paste::paste_image_to_temp_png;
+use crate::clipboard_paste::paste_text_from_clipboard;
 use crate::clipboard_paste::try_decode_base64_image_to_temp_png;
 use code_file_search::FileMatch;
 use std::cell::RefCell;
@@ -148,6 +149,8 @@ pub(crate) struct ChatComposer {
     file_popup_origin: Option<FilePopupOrigin>,
     pending_pastes: Vec<(String, String)>,
     token_usage_info: Option<TokenUsageInfo>,
+    /// Estimated session cost in USD, once a priced model has answered.
+    session_cost_usd: Option<f64>,
     has_focus: bool,
     has_chat_history: bool,
     /// Tracks whether the user has typed or pasted any content since startup.
@@ -163,6 +166,8 @@ pub(crate) struct ChatComposer {
     footer_notice: Option<(String, std::time::Instant)>,
     // Persistent hint for specific modes (e.g., standard terminal mode)
     standard_terminal_hint: Option<String>,
+    // Background startup work still in flight (e.g., "Loading accounts…")
+    startup_status: Option<String>,
     // Auto Review status displayed in the footer
     auto_review_status: Option<AutoReviewFooterStatus>,
     // Agent hint label to display alongside Auto Review footer state
@@ -175,6 +180,10 @@ pub(crate) struct ChatComposer {
     show_reasoning_hint: bool,
     show_diffs_hint: bool,
     reasoning_shown: bool,
+    // Shown while a Termux wake lock keeps Android from dozing the process
+    wake_lock_held: bool,
+    // Shown while `/dictate` is recording speech
+    dictating: bool,
     // Sticky flag: after a chat ScrollUp, make the very next Down trigger
     // chat ScrollDown instead of moving within the textarea, unless another
     // key is pressed in between.
@@ -226,6 +235,7 @@ impl ChatComposer {
             file_popup_origin: None,
             pending_pastes: Vec::new(),
             token_usage_info: None,
+            session_cost_usd: None,
             has_focus: has_input_focus,
             has_chat_history: false,
             typed_anything: false,
@@ -237,6 +247,7 @@ impl ChatComposer {
             custom_prompts: Vec::new(),
             footer_notice: None,
             standard_terminal_hint: None,
+            startup_status: None,
             auto_review_status: None,
             agent_hint_label: AgentHintLabel::Agents,
             access_mode_label: None,
@@ -245,6 +256,8 @@ impl ChatComposer {
             show_reasoning_hint: false,
             show_diffs_hint: false,
             reasoning_shown: false,
+            wake_lock_held: false,
+            dictating: false,
             next_down_scrolls_history: false,
             paste_burst: PasteBurst::default(),
             post_paste_space_guard: None,
@@ -269,6 +282,18 @@ impl ChatComposer {
         self.agent_hint_label = label;
     }
 
+    pub(crate) fn set_wake_lock_held(&mut self, held: bool) {
+        self.wake_lock_held = held;
+    }
+
+    pub(crate) fn set_dictating(&mut self, dictating: bool) {
+        self.dictating = dictating;
+    }
+
+    pub(crate) fn is_dictating(&self) -> bool {
+        self.dictating
+    }
+
     #[cfg(test)]
     pub(crate) fn auto_review_status(&self) -> Option<AutoReviewFooterStatus> {
         self.auto_review_status
@@ -341,7 +366,11 @@ impl ChatComposer {
                             thread::sleep(sleep_dur);
                         } else {
                             // If we're late (system busy), request a redraw immediately.
-                            app_event_tx_clone.send(crate::app_event::AppEvent::RequestRedraw);
+                            // Only the composer spins, so history can be reused.
+                            app_event_tx_clone.request_redraw_of(
+                                crate::dirty_regions::Regions::BOTTOM_PANE,
+                                "spinner",
+                            );
                             // Step the schedule forward by whole periods to avoid
                             // bursty catch‑up redraws.
                             let mut target = next;
@@ -653,6 +682,11 @@ impl ChatComposer {
         });
     }
 
+    /// Update the estimated session cost shown next to the token count.
+    pub(crate) fn set_session_cost(&mut self, cost_usd: f64) {
+        self.session_cost_usd = Some(cost_usd);
+    }
+
     /// Record the history metadata advertised by `SessionConfiguredEvent` so
     /// that the composer can navigate cross-session history.
     pub(crate) fn set_history_metadata(&mut self, log_id: u64, entry_count: usize) {
@@ -723,7 +757,14 @@ impl ChatComposer {
                     ));
                 }
                 Err(_) => {
-                    // Fall back to doing nothing special; keep composer unchanged.
+                    // No image: paste clipboard text instead. Terminals such as
+                    // Termux that never emit bracketed paste for the system
+                    // clipboard rely on this path.
+                    match paste_text_from_clipboard() {
+                        Ok(text) if !text.trim().is_empty() => return self.handle_paste(text),
+                        Ok(_) => {}
+                        Err(err) => tracing::debug!("clipboard text unavailable: {err}"),
+                    }
                 }
             }
         } else {
@@ -932,6 +973,10 @@ impl ChatComposer {
         self.standard_terminal_hint.as_deref()
     }
 
+    pub fn set_startup_status(&mut self, status: Option<String>) {
+        self.startup_status = status;
+    }
+
     pub fn set_text_content(&mut self, text: String) {
         self.textarea.set_text(&text);
         *self.textarea_state.borrow_mut() = TextAreaState::default();
@@ -1189,8 +1234,8 @@ impl ChatComposer {
                     (InputResult::None, true)
                 }
             }
-            CommandItem::Subagent(i) => {
-                if let Some(name) = popup.subagent_name(i) {
+            CommandItem::Subagent(_) | CommandItem::Plugin(_) => {
+                if let Some(name) = popup.custom_command_name(sel) {
                     let first_line = command_text.lines().next().unwrap_or("");
                     let starts_with = first_line.trim_start().starts_with(&format!("/{name}"));
                     if starts_with {
@@ -1302,8 +1347,8 @@ impl ChatComposer {
                                 }
                             }
                         }
-                        CommandItem::Subagent(i) => {
-                            if let Some(name) = popup.subagent_name(i) {
+                        CommandItem::Subagent(_) | CommandItem::Plugin(_) => {
+                            if let Some(name) = popup.custom_command_name(sel) {
                                 let starts_with_cmd = first_line
                                     .trim_start()
                                     .starts_with(&format!("/{name}"));
@@ -1351,8 +1396,8 @@ impl ChatComposer {
                             }
                             return (InputResult::None, true);
                         }
-                        CommandItem::Subagent(i) => {
-                            if let Some(name) = popup.subagent_name(i) {
+                        CommandItem::Subagent(_) | CommandItem::Plugin(_) => {
+                            if let Some(name) = popup.custom_command_name(sel) {
                                 let first_line = command_text.lines().next().unwrap_or("");
                                 let starts_with = first_line
                                     .trim_start()
@@ -1548,12 +1593,13 @@ impl ChatComposer {
             None
         };
 
-        let left_at = token_left
-            .filter(|t| t.starts_with('@'))
-            .map(|t| t[1..].to_string());
-        let right_at = token_right
-            .filter(|t| t.starts_with('@'))
-            .map(|t| t[1..].to_string());
+        // `@path:10-80` and glob mentions (`@src/**/*.ts`) are finished
+        // mentions, not queries.
+        let is_query = |t: &&str| {
+            t.starts_with('@') && !Self::has_line_range_suffix(t) && !t.contains(['*', '?'])
+        };
+        let left_at = token_left.filter(is_query).map(|t| t[1..].to_string());
+        let right_at = token_right.filter(is_query).map(|t| t[1..].to_string());
 
         if at_whitespace {
             if right_at.is_some() {
@@ -1570,6 +1616,15 @@ impl ChatComposer {
         left_at.or(right_at)
     }
 
+    /// Whether `token` ends in a `:N` or `:N-M` line range.
+    fn has_line_range_suffix(token: &str) -> bool {
+        token.rsplit_once(':').is_some_and(|(path, range)| {
+            !path.is_empty()
+                && range.starts_with(|c: char| c.is_ascii_digit())
+                && range.chars().all(|c| c.is_ascii_digit() || c == '-')
+        })
+    }
+
     /// Extract the completion token under the cursor for auto file search.
     ///
     /// Auto-trigger only for:
@@ -1704,6 +1759,12 @@ impl ChatComposer {
         } else {
             path.to_string()
         };
+        // Keep the `@` of an explicit mention so the file is attached on submit.
+        let inserted = if text[start_idx..end_idx].starts_with('@') {
+            format!("@{inserted}")
+        } else {
+            inserted
+        };
 
         // Replace the slice `[start_idx, end_idx)` with the chosen path and a trailing space.
         let mut new_text =
@@ -2081,6 +2142,12 @@ impl ChatComposer {
                         names.sort();
                         command_popup.set_subagent_commands(names);
                     }
+                    // Rescan plugins on every open so new executables appear without a restart
+                    if let Some(dir) = code_core::command_plugins::default_commands_dir() {
+                        command_popup.set_command_plugins(
+                            code_core::command_plugins::discover_command_plugins(&dir),
+                        );
+                    }
                     command_popup.on_composer_text_change(first_line.to_string());
                     self.active_popup = ActivePopup::Command(command_popup);
                     // Notify app: composer expanded due to slash popup
@@ -2246,6 +2313,7 @@ impl ChatComposer {
                     spans.push(Span::from("% left)").style(label_style));
                 }
         }
+        self.push_session_cost_span(&mut spans, label_style);
         spans
     }
 
@@ -2264,9 +2332,19 @@ impl ChatComposer {
                     spans.push(Span::from(percent_remaining.to_string()).style(label_style.add_modifier(Modifier::BOLD)));
                     spans.push(Span::from("% left)").style(label_style));
                 }
+        self.push_session_cost_span(&mut spans, label_style);
         spans
     }
 
+    fn push_session_cost_span(&self, spans: &mut Vec<Span<'static>>, label_style: Style) {
+        if let Some(cost) = self.session_cost_usd {
+            if !spans.is_empty() {
+                spans.push(Span::from(" ").style(label_style));
+            }
+            spans.push(Span::from(format!("${cost:.2}")).style(label_style.add_modifier(Modifier::BOLD)));
+        }
+    }
+
     fn build_auto_drive_hint_spans(
         text: &str,
         key_style: Style,
@@ -2577,6 +2655,13 @@ impl ChatComposer {
                     }
                 }
 
+                if let Some(status) = &self.startup_status {
+                    if !left_misc_after_ctrlc.is_empty() {
+                        left_misc_after_ctrlc.push(Span::from("   "));
+                    }
+                    left_misc_after_ctrlc.push(Span::from(status.clone()).style(label_style));
+                }
+
                 if let Some((msg, until)) = &self.footer_notice
                     && std::time::Instant::now() <= *until {
                         if !left_misc_after_ctrlc.is_empty() {
@@ -2621,6 +2706,24 @@ impl ChatComposer {
                 // Tokens placeholder (actual spans chosen later)
                 right_sections.push((1, Vec::new(), include_tokens));
 
+                // Dictation indicator (priority 7)
+                if self.dictating {
+                    right_sections.push((
+                        7,
+                        vec![Span::from("● recording").style(Style::default().fg(crate::colors::error()))],
+                        true,
+                    ));
+                }
+
+                // Wake lock indicator (priority 7)
+                if self.wake_lock_held {
+                    right_sections.push((
+                        7,
+                        vec![Span::from("wake lock").style(Style::default().fg(crate::colors::info()))],
+                        true,
+                    ));
+                }
+
                 // Auth label (priority 7)
                 if !self.using_chatgpt_auth {
                     right_sections.push((7, vec![Span::from("API key").style(label_style)], true));
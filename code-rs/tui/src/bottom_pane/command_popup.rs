@@ -10,6 +10,7 @@ use crate::components::selection_popup_common::render_rows;
 use crate::slash_command::SlashCommand;
 use crate::slash_command::built_in_slash_commands;
 use code_common::fuzzy_match::fuzzy_match;
+use code_core::command_plugins::CommandPlugin;
 use code_protocol::custom_prompts::CustomPrompt;
 use code_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use std::collections::HashSet;
@@ -22,6 +23,8 @@ pub(crate) enum CommandItem {
     UserPrompt(usize),
     // Index into `subagents`
     Subagent(usize),
+    // Index into `plugins`
+    Plugin(usize),
 }
 
 pub(crate) struct CommandPopup {
@@ -30,6 +33,7 @@ pub(crate) struct CommandPopup {
     prompts: Vec<CustomPrompt>,
     state: ScrollState,
     subagents: Vec<String>,
+    plugins: Vec<CommandPlugin>,
 }
 
 impl CommandPopup {
@@ -45,6 +49,7 @@ impl CommandPopup {
             prompts: Vec::new(),
             state: ScrollState::new(),
             subagents: Vec::new(),
+            plugins: Vec::new(),
         }
     }
 
@@ -79,6 +84,34 @@ impl CommandPopup {
         self.state.clamp_selection(self.filtered_items().len());
     }
 
+    pub(crate) fn plugin(&self, idx: usize) -> Option<&CommandPlugin> {
+        self.plugins.get(idx)
+    }
+
+    /// Name of a subagent or plugin item. These run from the text typed after
+    /// `/name`, so selecting one completes the name instead of dispatching.
+    pub(crate) fn custom_command_name(&self, item: CommandItem) -> Option<&str> {
+        match item {
+            CommandItem::Subagent(idx) => self.subagent_name(idx),
+            CommandItem::Plugin(idx) => self.plugin(idx).map(|plugin| plugin.name.as_str()),
+            CommandItem::Builtin(_) | CommandItem::UserPrompt(_) => None,
+        }
+    }
+
+    /// Supply command plugins discovered under `code_home/commands`. Names
+    /// already taken by built-ins or subagents are dropped.
+    pub(crate) fn set_command_plugins(&mut self, mut plugins: Vec<CommandPlugin>) {
+        plugins.retain(|plugin| {
+            !self.builtins.iter().any(|(name, _)| *name == plugin.name)
+                && !self
+                    .subagents
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(&plugin.name))
+        });
+        self.plugins = plugins;
+        self.state.clamp_selection(self.filtered_items().len());
+    }
+
     /// Update the filter string based on the current composer text. The text
     /// passed in is expected to start with a leading '/'. Everything after the
     /// *first* '/" on the *first* line becomes the active filter that is used
@@ -131,6 +164,10 @@ impl CommandPopup {
             for (idx, _) in self.subagents.iter().enumerate() {
                 out.push((CommandItem::Subagent(idx), None, 0));
             }
+            // Then command plugins, already sorted by name.
+            for idx in 0..self.plugins.len() {
+                out.push((CommandItem::Plugin(idx), None, 0));
+            }
             // Then prompts, already sorted by name.
             for idx in 0..self.prompts.len() {
                 out.push((CommandItem::UserPrompt(idx), None, 0));
@@ -148,6 +185,11 @@ impl CommandPopup {
                 out.push((CommandItem::Subagent(idx), Some(indices), score));
             }
         }
+        for (idx, plugin) in self.plugins.iter().enumerate() {
+            if let Some((indices, score)) = fuzzy_match(&plugin.name, filter) {
+                out.push((CommandItem::Plugin(idx), Some(indices), score));
+            }
+        }
         for (idx, p) in self.prompts.iter().enumerate() {
             let prefixed = format!("{PROMPTS_CMD_PREFIX}:{}", p.name);
             let mut best: Option<(Vec<usize>, i32)> = None;
@@ -167,7 +209,7 @@ impl CommandPopup {
             }
         }
         // When filtering, sort by ascending score, then prefer built-ins over
-        // subagents over prompts to keep core commands easy to reach on exact
+        // subagents over plugins over prompts to keep core commands easy to reach on exact
         // matches (e.g., `/prompts` should not auto-select a custom prompt),
         // and finally fall back to name for stability.
         out.sort_by(|a, b| {
@@ -181,7 +223,8 @@ impl CommandPopup {
             let rank = |item: &CommandItem| match item {
                 CommandItem::Builtin(_) => 0,
                 CommandItem::Subagent(_) => 1,
-                CommandItem::UserPrompt(_) => 2,
+                CommandItem::Plugin(_) => 2,
+                CommandItem::UserPrompt(_) => 3,
             };
             let rank_cmp = rank(&a.0).cmp(&rank(&b.0));
             if rank_cmp != Ordering::Equal {
@@ -192,11 +235,13 @@ impl CommandPopup {
                 CommandItem::Builtin(c) => c.command(),
                 CommandItem::UserPrompt(i) => &self.prompts[i].name,
                 CommandItem::Subagent(i) => &self.subagents[i],
+                CommandItem::Plugin(i) => &self.plugins[i].name,
             };
             let bn = match b.0 {
                 CommandItem::Builtin(c) => c.command(),
                 CommandItem::UserPrompt(i) => &self.prompts[i].name,
                 CommandItem::Subagent(i) => &self.subagents[i],
+                CommandItem::Plugin(i) => &self.plugins[i].name,
             };
             an.cmp(bn)
         });
@@ -278,6 +323,23 @@ impl WidgetRef for CommandPopup {
                             format!("/{}", self.subagents[i]),
                             Some("custom subagent".to_string()),
                         ),
+                        CommandItem::Plugin(i) => {
+                            let plugin = &self.plugins[i];
+                            let detail = [
+                                plugin.argument_hint.as_deref(),
+                                plugin.description.as_deref(),
+                            ]
+                            .into_iter()
+                            .flatten()
+                            .collect::<Vec<_>>()
+                            .join(" · ");
+                            let desc = if detail.is_empty() {
+                                "[plugin]".to_string()
+                            } else {
+                                format!("[plugin] {detail}")
+                            };
+                            (format!("/{}", plugin.name), Some(desc))
+                        }
                     };
                     GenericDisplayRow {
                         name,
@@ -329,4 +391,30 @@ mod tests {
         let first = popup.filtered_items().first().copied();
         assert!(matches!(first, Some(CommandItem::Builtin(SlashCommand::Prompts))));
     }
+
+    #[test]
+    fn plugins_skip_taken_names_and_rank_after_subagents() {
+        let plugin = |name: &str| CommandPlugin {
+            name: name.to_string(),
+            program: PathBuf::from(format!("/commands/{name}")),
+            description: None,
+            argument_hint: None,
+            output: Default::default(),
+            timeout: std::time::Duration::from_secs(1),
+        };
+        let mut popup = CommandPopup::new_with_filter(false);
+        popup.set_subagent_commands(vec!["ship".to_string()]);
+        popup.set_command_plugins(vec![plugin("model"), plugin("ship"), plugin("shipit")]);
+
+        assert_eq!(popup.plugins.len(), 1);
+        assert_eq!(popup.plugin(0).map(|p| p.name.as_str()), Some("shipit"));
+
+        popup.on_composer_text_change("/ship".to_string());
+        let custom: Vec<CommandItem> = popup
+            .filtered_items()
+            .into_iter()
+            .filter(|item| !matches!(item, CommandItem::Builtin(_)))
+            .collect();
+        assert_eq!(custom, vec![CommandItem::Subagent(0), CommandItem::Plugin(0)]);
+    }
 }
@@ -67,6 +67,12 @@ impl CustomPromptView {
             last_textarea_rect: RefCell::new(None),
         }
     }
+
+    /// Prefills the input, e.g. with a suggestion the user can edit before submitting.
+    pub(crate) fn with_initial_text(mut self, text: &str) -> Self {
+        self.textarea.set_text(text);
+        self
+    }
 }
 
 impl BottomPaneView<'_> for CustomPromptView {
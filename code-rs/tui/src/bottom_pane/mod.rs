@@ -4,22 +4,25 @@ use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::auto_drive_style::AutoDriveVariant;
 use crate::bottom_pane::chat_composer::ComposerRenderMode;
+use crate::dirty_regions::Regions;
 use crate::chatwidget::BackgroundOrderTicket;
 use crate::user_approval_widget::{ApprovalRequest, UserApprovalWidget};
-use crate::thread_spawner;
 pub(crate) use bottom_pane_view::BottomPaneView;
 pub(crate) use bottom_pane_view::ConditionalUpdate;
 use crate::util::buffer::fill_rect;
 use code_common::shell_presets::ShellPreset;
 use code_protocol::custom_prompts::CustomPrompt;
 use code_protocol::skills::Skill;
+use code_core::config_types::ModelOverrides;
 use code_core::config_types::ShellConfig;
 use code_core::protocol::TokenUsage;
+use code_core::protocol::ReviewDecision;
 use code_file_search::FileMatch;
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::widgets::WidgetRef;
+use std::collections::HashMap;
 use std::time::Duration;
 
 mod approval_modal_view;
@@ -743,6 +746,11 @@ impl BottomPane<'_> {
         self.composer.standard_terminal_hint()
     }
 
+    pub(crate) fn set_startup_status(&mut self, status: Option<String>) {
+        self.composer.set_startup_status(status);
+        self.request_redraw();
+    }
+
     pub(crate) fn set_auto_review_status(&mut self, status: Option<AutoReviewFooterStatus>) {
         self.composer.set_auto_review_status(status);
         self.request_redraw();
@@ -753,6 +761,20 @@ impl BottomPane<'_> {
         self.request_redraw();
     }
 
+    pub(crate) fn set_wake_lock_held(&mut self, held: bool) {
+        self.composer.set_wake_lock_held(held);
+        self.request_redraw();
+    }
+
+    pub(crate) fn set_dictating(&mut self, dictating: bool) {
+        self.composer.set_dictating(dictating);
+        self.request_redraw();
+    }
+
+    pub(crate) fn is_dictating(&self) -> bool {
+        self.composer.is_dictating()
+    }
+
     #[cfg(test)]
     pub(crate) fn auto_review_status(&self) -> Option<AutoReviewFooterStatus> {
         self.composer.auto_review_status()
@@ -831,6 +853,12 @@ impl BottomPane<'_> {
         self.request_redraw();
     }
 
+    /// Update the estimated session cost shown in the footer.
+    pub(crate) fn set_session_cost(&mut self, cost_usd: f64) {
+        self.composer.set_session_cost(cost_usd);
+        self.request_redraw();
+    }
+
     /// Called when the agent requests user approval.
     pub fn push_approval_request(
         &mut self,
@@ -859,6 +887,25 @@ impl BottomPane<'_> {
         self.request_redraw()
     }
 
+    /// Resolve a pending approval from outside the modal. Returns false when
+    /// no visible or queued request matches `id`.
+    pub(crate) fn resolve_approval(&mut self, id: &str, decision: ReviewDecision) -> bool {
+        let Some(mut view) = self.active_view.take() else {
+            return false;
+        };
+        let resolved = view.try_resolve_approval(id, decision);
+        if !view.is_complete() {
+            self.active_view = Some(view);
+        } else {
+            self.active_view_kind = ActiveViewKind::None;
+            self.set_standard_terminal_hint(None);
+        }
+        if resolved {
+            self.request_redraw();
+        }
+        resolved
+    }
+
     /// Show the model selection UI
     pub fn show_model_selection(
         &mut self,
@@ -867,6 +914,7 @@ impl BottomPane<'_> {
         current_effort: ReasoningEffort,
         use_chat_model: bool,
         target: ModelSelectionTarget,
+        model_overrides: HashMap<String, ModelOverrides>,
     ) {
         let view = ModelSelectionView::new(
             presets,
@@ -874,6 +922,7 @@ impl BottomPane<'_> {
             current_effort,
             use_chat_model,
             target,
+            model_overrides,
             self.app_event_tx.clone(),
         );
         self.active_view = Some(Box::new(view));
@@ -1005,6 +1054,43 @@ impl BottomPane<'_> {
         self.request_redraw_with_height_change()
     }
 
+    pub(crate) fn resume_selection_active(&self) -> bool {
+        self.active_view
+            .as_ref()
+            .and_then(|view| view.as_any())
+            .is_some_and(|any| any.is::<resume_selection_view::ResumeSelectionView>())
+    }
+
+    /// Refresh the rows of an open resume picker.
+    pub(crate) fn update_resume_selection(&mut self, rows: Vec<resume_selection_view::ResumeRow>) {
+        let Some(view) = self.active_view.as_mut() else {
+            return;
+        };
+        let Some(resume_view) = view
+            .as_any_mut()
+            .and_then(|any| any.downcast_mut::<resume_selection_view::ResumeSelectionView>())
+        else {
+            return;
+        };
+
+        resume_view.set_rows(rows);
+        self.request_redraw_with_height_change();
+    }
+
+    /// Update one row of an open resume picker in place.
+    pub(crate) fn update_resume_summary(&mut self, path: &std::path::Path, summary: String, tokens: String) {
+        let Some(resume_view) = self
+            .active_view
+            .as_mut()
+            .and_then(|view| view.as_any_mut())
+            .and_then(|any| any.downcast_mut::<resume_selection_view::ResumeSelectionView>())
+        else {
+            return;
+        };
+        resume_view.set_row_summary(path, summary, tokens);
+        self.request_redraw();
+    }
+
     pub fn show_undo_timeline_view(&mut self, view: UndoTimelineView) {
         self.active_view = Some(Box::new(view));
         self.active_view_kind = ActiveViewKind::Other;
@@ -1105,7 +1191,8 @@ impl BottomPane<'_> {
 
     /// Height (terminal rows) required by the current bottom pane.
     pub(crate) fn request_redraw(&self) {
-        self.app_event_tx.send(AppEvent::RequestRedraw)
+        self.app_event_tx
+            .request_redraw_of(Regions::BOTTOM_PANE, "bottom pane")
     }
 
     /// Request redraw and notify that the bottom pane view changed.
@@ -1231,36 +1318,20 @@ impl BottomPane<'_> {
     pub(crate) fn set_access_mode_label(&mut self, label: Option<String>) {
         self.composer.set_access_mode_label(label);
         // Hide the "(Shift+Tab change)" suffix after a short time for persistent modes.
-        // Avoid using a global frame scheduler which can be coalesced; instead spawn
-        // a tiny timer to request a redraw slightly after expiry.
+        // Avoid using the frame scheduler, which can be coalesced; instead queue a
+        // one-off redraw slightly after expiry.
         let dur = Duration::from_secs(4);
         self.composer.set_access_mode_hint_for(dur);
-        let tx = self.app_event_tx.clone();
-        let fallback_tx = self.app_event_tx.clone();
-        if thread_spawner::spawn_lightweight("access-hint", move || {
-            std::thread::sleep(dur + Duration::from_millis(120));
-            tx.send(AppEvent::RequestRedraw);
-        })
-        .is_none()
-        {
-            fallback_tx.send(AppEvent::RequestRedraw);
-        }
+        self.app_event_tx
+            .send_after(dur + Duration::from_millis(120), AppEvent::RequestRedraw);
         self.request_redraw();
     }
 
     pub(crate) fn set_access_mode_label_ephemeral(&mut self, label: String, dur: Duration) {
         self.composer.set_access_mode_label_ephemeral(label, dur);
         // Schedule a redraw after expiry without blocking other scheduled frames.
-        let tx = self.app_event_tx.clone();
-        let fallback_tx = self.app_event_tx.clone();
-        if thread_spawner::spawn_lightweight("access-hint-ephemeral", move || {
-            std::thread::sleep(dur + Duration::from_millis(120));
-            tx.send(AppEvent::RequestRedraw);
-        })
-        .is_none()
-        {
-            fallback_tx.send(AppEvent::RequestRedraw);
-        }
+        self.app_event_tx
+            .send_after(dur + Duration::from_millis(120), AppEvent::RequestRedraw);
         self.request_redraw();
     }
 
@@ -10,6 +10,7 @@ use super::BottomPane;
 use crate::app_event::{AppEvent, ModelSelectionKind};
 use crate::app_event_sender::AppEventSender;
 use code_common::model_presets::ModelPreset;
+use code_core::config_types::ModelOverrides;
 use code_core::config_types::ReasoningEffort;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 use ratatui::buffer::Buffer;
@@ -20,6 +21,7 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::collections::HashMap;
 
 /// Flattened preset entry combining a model with a specific reasoning effort.
 #[derive(Clone, Debug)]
@@ -136,6 +138,8 @@ pub(crate) struct ModelSelectionView {
     app_event_tx: AppEventSender,
     is_complete: bool,
     target: ModelSelectionTarget,
+    /// `[models.<slug>]` overrides, shown under each model's header.
+    model_overrides: HashMap<String, ModelOverrides>,
     /// Cached (entry_index, rect) pairs from last render for mouse hit testing
     item_rects: RefCell<Vec<(usize, Rect)>>,
     /// Scroll offset for rendering when content exceeds available height
@@ -157,6 +161,7 @@ impl ModelSelectionView {
         current_effort: ReasoningEffort,
         use_chat_model: bool,
         target: ModelSelectionTarget,
+        model_overrides: HashMap<String, ModelOverrides>,
         app_event_tx: AppEventSender,
     ) -> Self {
         let flat_presets: Vec<FlatPreset> = presets
@@ -181,6 +186,7 @@ impl ModelSelectionView {
             app_event_tx,
             is_complete: false,
             target,
+            model_overrides,
             item_rects: RefCell::new(Vec::new()),
             scroll_offset: 0,
             last_render_height: RefCell::new(0),
@@ -400,6 +406,9 @@ impl ModelSelectionView {
                 if Self::model_description(&flat_preset.model).is_some() {
                     line += 1; // Model description
                 }
+                if self.override_summary(&flat_preset.model).is_some() {
+                    line += 1; // Active overrides
+                }
                 previous_model = Some(&flat_preset.model);
             }
 
@@ -570,6 +579,9 @@ impl ModelSelectionView {
                 if Self::model_description(&flat_preset.model).is_some() {
                     lines = lines.saturating_add(1);
                 }
+                if self.override_summary(&flat_preset.model).is_some() {
+                    lines = lines.saturating_add(1);
+                }
                 previous_model = Some(&flat_preset.model);
             }
 
@@ -627,6 +639,12 @@ impl ModelSelectionView {
         }
     }
 
+    /// Active `[models.<slug>]` settings for `model`, joined for display.
+    fn override_summary(&self, model: &str) -> Option<String> {
+        let parts = ModelOverrides::find(&self.model_overrides, model)?.summary();
+        (!parts.is_empty()).then(|| parts.join(" · "))
+    }
+
     fn model_description(model: &str) -> Option<&'static str> {
         if model.eq_ignore_ascii_case("gpt-5.3-codex") {
             Some("Latest frontier agentic coding model.")
@@ -918,6 +936,13 @@ impl ModelSelectionView {
                     )]));
                     current_line += 1;
                 }
+                if let Some(summary) = self.override_summary(&flat_preset.model) {
+                    lines.push(Line::from(vec![
+                        Span::styled("Overrides: ", Style::default().fg(crate::colors::text_dim())),
+                        Span::styled(summary, Style::default().fg(crate::colors::info())),
+                    ]));
+                    current_line += 1;
+                }
                 previous_model = Some(&flat_preset.model);
             }
 
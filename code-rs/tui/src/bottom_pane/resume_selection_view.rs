@@ -19,6 +19,7 @@ pub struct ResumeRow {
     pub modified: String,
     pub created: String,
     pub user_msgs: String,
+    pub tokens: String,
     pub branch: String,
     pub last_user_message: String,
     pub path: std::path::PathBuf,
@@ -50,6 +51,25 @@ impl ResumeSelectionView {
         }
     }
 
+    /// Replaces the rows while keeping the same session selected when it is
+    /// still listed.
+    pub fn set_rows(&mut self, rows: Vec<ResumeRow>) {
+        let selected_path = self.rows.get(self.selected).map(|row| row.path.clone());
+        self.rows = rows;
+        self.selected = selected_path
+            .and_then(|path| self.rows.iter().position(|row| row.path == path))
+            .unwrap_or(0);
+        self.ensure_selected_visible();
+    }
+
+    /// Replaces the summary and token columns of the row for `path`.
+    pub fn set_row_summary(&mut self, path: &std::path::Path, summary: String, tokens: String) {
+        if let Some(row) = self.rows.iter_mut().find(|row| row.path == path) {
+            row.last_user_message = summary;
+            row.tokens = tokens;
+        }
+    }
+
     fn move_up(&mut self) {
         if self.rows.is_empty() { return; }
         if self.selected == 0 { self.selected = self.rows.len().saturating_sub(1); }
@@ -120,6 +140,12 @@ impl BottomPaneView<'_> for ResumeSelectionView {
                     self.complete = true;
                 }
             }
+            KeyCode::Char('s') => {
+                if let Some(row) = self.rows.get_mut(self.selected) {
+                    row.last_user_message = "Summarizing…".to_string();
+                    self.app_event_tx.send(AppEvent::SummarizeResumeSession(row.path.clone()));
+                }
+            }
             KeyCode::Esc => self.complete = true,
             _ => {}
         }
@@ -127,6 +153,10 @@ impl BottomPaneView<'_> for ResumeSelectionView {
 
     fn is_complete(&self) -> bool { self.complete }
 
+    fn as_any(&self) -> Option<&dyn std::any::Any> { Some(self) }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn std::any::Any> { Some(self) }
+
     fn on_ctrl_c(&mut self, _pane: &mut BottomPane<'_>) -> super::CancellationEvent {
         self.complete = true; super::CancellationEvent::Handled
     }
@@ -196,6 +226,7 @@ impl BottomPaneView<'_> for ResumeSelectionView {
                 r.modified.clone(),
                 r.created.clone(),
                 r.user_msgs.clone(),
+                r.tokens.clone(),
                 r.branch.clone(),
                 r.last_user_message.clone(),
             ]
@@ -213,11 +244,12 @@ impl BottomPaneView<'_> for ResumeSelectionView {
             Constraint::Length(10), // Modified
             Constraint::Length(10), // Created
             Constraint::Length(11), // User Msgs
+            Constraint::Length(8),  // Tokens
             Constraint::Length(10), // Branch
             Constraint::Min(10),    // Last User Message
         ];
 
-        let header = Row::new(vec!["Modified", "Created", "User Msgs", "Branch", "Session"]).height(1)
+        let header = Row::new(vec!["Modified", "Created", "User Msgs", "Tokens", "Branch", "Session"]).height(1)
             .style(Style::default().fg(crate::colors::text_bright()));
 
         let table = Table::new(rows_iter, widths)
@@ -234,6 +266,8 @@ impl BottomPaneView<'_> for ResumeSelectionView {
             Span::raw(" Navigate  "),
             Span::styled("Enter", Style::default().fg(crate::colors::success())),
             Span::raw(" Select  "),
+            Span::styled("s", Style::default().fg(crate::colors::light_blue())),
+            Span::raw(" Summarize  "),
             Span::styled("Esc", Style::default().fg(crate::colors::error())),
             Span::raw(" Cancel"),
         ]);
@@ -258,6 +292,7 @@ mod tests {
                 modified: "m".to_string(),
                 created: "c".to_string(),
                 user_msgs: "1".to_string(),
+                tokens: "-".to_string(),
                 branch: "main".to_string(),
                 last_user_message: format!("row-{i}"),
                 path: std::path::PathBuf::from(format!("/tmp/sess-{i}")),
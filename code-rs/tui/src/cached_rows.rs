@@ -0,0 +1,136 @@
+//! Pre-laid-out buffer rows for cached history layouts.
+//!
+//! A `CachedRow` holds the cells of one wrapped line up to its last
+//! non-blank cell; the blank tail (usually most of a wide terminal) is not
+//! stored. Rendering copies that prefix into the frame buffer as one slice
+//! and only clears the tail when the caller has not already painted the
+//! cell background, so blank space costs nothing per frame.
+
+use ratatui::buffer::Cell as BufferCell;
+use ratatui::style::Color;
+use ratatui::text::Line;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+#[derive(Debug, Clone, Default)]
+pub struct CachedRow {
+    cells: Box<[BufferCell]>,
+}
+
+impl CachedRow {
+    /// Lays out `line` into at most `width` cells, dropping control
+    /// characters and any grapheme that would not fit.
+    pub fn from_line(line: &Line<'_>, width: u16) -> Self {
+        let target_width = width as usize;
+        if target_width == 0 {
+            return Self::default();
+        }
+
+        let mut cells = vec![BufferCell::default(); target_width];
+        let mut x = 0usize;
+        'spans: for span in &line.spans {
+            let span_style = line.style.patch(span.style);
+            for symbol in UnicodeSegmentation::graphemes(span.content.as_ref(), true) {
+                if symbol.chars().any(char::is_control) {
+                    continue;
+                }
+                let symbol_width = UnicodeWidthStr::width(symbol);
+                if symbol_width == 0 {
+                    continue;
+                }
+                if x + symbol_width > target_width {
+                    break 'spans;
+                }
+                cells[x].set_symbol(symbol).set_style(span_style);
+                // Continuation cells of wide graphemes stay default.
+                x += symbol_width;
+            }
+        }
+
+        let blank = BufferCell::default();
+        let content_len = cells
+            .iter()
+            .rposition(|cell| *cell != blank)
+            .map_or(0, |idx| idx + 1);
+        cells.truncate(content_len);
+        Self {
+            cells: cells.into_boxed_slice(),
+        }
+    }
+
+    /// Number of stored cells; everything after them is blank.
+    pub fn content_width(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Writes the row into `dest` (one buffer row, clipped to the render
+    /// area). Cells without a background get `fill_bg`. The blank tail is
+    /// reset unless `background_painted` says the caller already filled it.
+    pub fn blit(&self, dest: &mut [BufferCell], fill_bg: Color, background_painted: bool) {
+        let copy_len = self.cells.len().min(dest.len());
+        let (head, tail) = dest.split_at_mut(copy_len);
+        head.clone_from_slice(&self.cells[..copy_len]);
+        for cell in head.iter_mut() {
+            if cell.bg == Color::Reset {
+                cell.bg = fill_bg;
+            }
+        }
+        if !background_painted {
+            for cell in tail {
+                cell.reset();
+                cell.bg = fill_bg;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use ratatui::style::Style;
+    use ratatui::text::Span;
+
+    fn symbols(cells: &[BufferCell]) -> String {
+        cells.iter().map(BufferCell::symbol).collect()
+    }
+
+    #[test]
+    fn stores_only_the_non_blank_prefix() {
+        let line = Line::from(vec![Span::raw("ab"), Span::raw("  ")]);
+        let row = CachedRow::from_line(&line, 80);
+        assert_eq!(row.content_width(), 2);
+
+        // Styled blanks are content: they paint a background.
+        let styled = Line::from("x  ").style(Style::default().bg(Color::Blue));
+        assert_eq!(CachedRow::from_line(&styled, 80).content_width(), 3);
+    }
+
+    #[test]
+    fn wide_graphemes_that_do_not_fit_are_dropped() {
+        let row = CachedRow::from_line(&Line::from("a界b"), 2);
+        assert_eq!(symbols(&row.cells), "a");
+        let row = CachedRow::from_line(&Line::from("界b"), 3);
+        assert_eq!(symbols(&row.cells), "界 b");
+    }
+
+    #[test]
+    fn blit_fills_background_and_clears_stale_tail() {
+        let row = CachedRow::from_line(&Line::from("hi"), 6);
+        let mut dest = vec![BufferCell::default(); 6];
+        for cell in &mut dest {
+            cell.set_symbol("z");
+        }
+        row.blit(&mut dest, Color::Green, false);
+        assert_eq!(symbols(&dest), "hi    ");
+        assert!(dest.iter().all(|cell| cell.bg == Color::Green));
+
+        let mut painted = vec![BufferCell::default(); 6];
+        for cell in &mut painted {
+            cell.set_symbol(" ").set_bg(Color::Magenta);
+        }
+        row.blit(&mut painted, Color::Green, true);
+        assert_eq!(painted[0].bg, Color::Green);
+        assert_eq!(painted[5].bg, Color::Magenta);
+    }
+}
@@ -76,6 +76,7 @@ mod agent_install;
 mod internals;
 mod code_event_pipeline;
 mod cloud_workflow;
+mod command_plugin_flow;
 mod context_flow;
 mod diff_ui;
 mod exec_tools;
@@ -91,15 +92,42 @@ mod limits_overlay;
 mod interrupts;
 mod input_pipeline;
 mod layout_scroll;
-mod message;
+pub(crate) mod message;
 mod notifications;
 mod ordering;
 mod overlay_rendering;
 mod perf;
+mod plan_panel;
 mod rate_limit_refresh;
 mod repo_workflow;
+mod diff_export_flow;
+mod external_edits_flow;
+mod fetch_flow;
+mod review_config_flow;
+mod review_findings_flow;
+mod spawn_flow;
+mod schema_flow;
+mod metered_flow;
+mod debug_flow;
+mod retry_flow;
+mod cell_timestamps_flow;
+mod footnote_links_flow;
+mod table_scroll_flow;
+mod artifacts_flow;
+mod patch_conflict_flow;
+mod patch_undo_flow;
+mod touched_files_flow;
+mod rerun_flow;
+mod offline_flow;
+mod turn_recovery_flow;
+mod cwd_flow;
+mod scope_flow;
+mod test_results_flow;
+mod lsp_flow;
+mod output_flow;
 mod review_flow;
 mod session_flow;
+mod startup_flow;
 mod shell_config_flow;
 mod session_tuning_flow;
 mod status_line_flow;
@@ -112,6 +140,8 @@ mod terminal_surface_header;
 mod terminal_surface_render;
 mod tools;
 mod browser_sessions;
+mod browser_tabs_flow;
+mod browser_timeline_flow;
 mod agent_runs;
 mod web_search_sessions;
 mod auto_drive_cards;
@@ -125,6 +155,8 @@ pub mod smoke_helpers;
 
 #[cfg(test)]
 pub(crate) use self::esc::EscIntent;
+#[cfg(unix)]
+pub(crate) use self::agent::set_attach_socket;
 use self::agent_summary::agent_summary_counts;
 use self::esc::AutoGoalEscState;
 use self::agent_install::{
@@ -181,6 +213,7 @@ use code_core::protocol::AgentMessageDeltaEvent;
 use code_core::protocol::ApprovedCommandMatchKind;
 use code_core::protocol::AskForApproval;
 use code_core::protocol::SandboxPolicy;
+use code_core::protocol::ReviewDecision;
 use code_core::protocol::AgentSourceKind;
 use code_core::protocol::AgentMessageEvent;
 use code_core::protocol::AgentReasoningDeltaEvent;
@@ -251,6 +284,8 @@ use crate::bottom_pane::mcp_settings_view::{McpServerRow, McpServerRows};
 use crate::exec_command::strip_bash_lc_and_escape;
 #[cfg(feature = "code-fork")]
 use crate::tui_event_extensions::handle_browser_screenshot;
+use crate::chatwidget::message::AttachedFile;
+use crate::chatwidget::message::AttachmentBundle;
 use crate::chatwidget::message::UserMessage;
 use crate::history::compat::{
     ContextBrowserSnapshotRecord,
@@ -784,6 +819,16 @@ impl ChatWidget<'_> {
         code_op_rx: UnboundedReceiver<Op>,
     ) {
         let ticket = self.make_background_tail_ticket();
+        #[cfg(unix)]
+        if let Some(socket) = agent::attach_socket() {
+            agent::spawn_attached_conversation_runtime(
+                socket.to_path_buf(),
+                self.app_event_tx.clone(),
+                code_op_rx,
+                ticket,
+            );
+            return;
+        }
         agent::spawn_new_conversation_runtime(
             config,
             self.app_event_tx.clone(),
@@ -869,7 +914,9 @@ impl ChatWidget<'_> {
         self.perf_state.enabled = enable;
     }
     pub(crate) fn perf_summary(&self) -> String {
-        self.perf_state.stats.borrow().summary()
+        let mut stats = self.perf_state.stats.borrow().clone();
+        stats.height_cache_invalidations = self.history_render.invalidations();
+        stats.summary()
     }
     // Build an ordered key from model-provided OrderMeta. Callers must
     // guarantee presence by passing a concrete reference (compile-time guard).
@@ -1101,6 +1148,9 @@ impl ChatWidget<'_> {
         {
             self.bottom_pane.set_task_running(false);
             self.bottom_pane.update_status_text(String::new());
+            if crate::termux_api::release_wake_lock() {
+                self.bottom_pane.set_wake_lock_held(false);
+            }
         }
     }
 
@@ -1282,16 +1332,10 @@ impl ChatWidget<'_> {
             return;
         }
         self.interrupt_flush_scheduled = true;
-        let tx = self.app_event_tx.clone();
-        let fallback_tx = tx.clone();
-        if thread_spawner::spawn_lightweight("interrupt-flush", move || {
-            std::thread::sleep(std::time::Duration::from_millis(180));
-            tx.send(AppEvent::FlushInterruptsIfIdle);
-        })
-        .is_none()
-        {
-            fallback_tx.send(AppEvent::FlushInterruptsIfIdle);
-        }
+        self.app_event_tx.send_after(
+            std::time::Duration::from_millis(180),
+            AppEvent::FlushInterruptsIfIdle,
+        );
     }
 
     /// Finalize a stalled stream and flush queued interrupts once the stream is idle.
@@ -1403,6 +1447,13 @@ impl ChatWidget<'_> {
         self.app_event_tx.send(AppEvent::RequestRedraw);
     }
 
+    /// Redraw after a change confined to the history viewport, such as a
+    /// streaming delta landing in the tail cell.
+    fn request_history_redraw(&mut self, cause: &'static str) {
+        self.app_event_tx
+            .request_redraw_of(crate::dirty_regions::Regions::HISTORY, cause);
+    }
+
     /// Notify the height manager that the bottom pane view has changed.
     /// This bypasses hysteresis so the new view's height is applied immediately.
     pub(crate) fn notify_bottom_pane_view_changed(&mut self) {
@@ -1426,15 +1477,25 @@ impl ChatWidget<'_> {
             }
             "reset" => {
                 self.perf_state.stats.borrow_mut().reset();
+                self.history_render.reset_invalidations();
                 self.perf_state.pending_scroll_rows.set(0);
                 self.add_perf_output("performance stats reset".to_string());
             }
             "show" | "" => {
-                let summary = self.perf_state.stats.borrow().summary();
+                let summary = self.perf_summary();
                 self.add_perf_output(summary);
             }
+            "order" => {
+                self.show_order_overlay = !self.show_order_overlay;
+                let state = if self.show_order_overlay { "on" } else { "off" };
+                self.add_perf_output(format!("order-key overlay: {state}"));
+            }
+            "hud" => {
+                let state = if self.toggle_frame_hud() { "on" } else { "off" };
+                self.add_perf_output(format!("frame HUD: {state}"));
+            }
             _ => {
-                self.add_perf_output("usage: /perf on | off | show | reset".to_string());
+                self.add_perf_output("usage: /perf on | off | show | reset | order | hud".to_string());
             }
         }
         self.request_redraw();
@@ -1728,6 +1789,7 @@ impl ChatWidget<'_> {
                 &self.config,
                 &self.total_token_usage,
                 &self.last_token_usage,
+                &self.history_state.token_usage_summary(),
                 None,
                 None,
             ));
@@ -2022,12 +2084,14 @@ impl ChatWidget<'_> {
     }
 
     pub(crate) fn add_status_output(&mut self) {
+        let (requested_model, response_model) = &self.last_request_models;
         self.history_push_plain_state(history_cell::new_status_output(
             &self.config,
             &self.total_token_usage,
             &self.last_token_usage,
-            None,
-            None,
+            &self.history_state.token_usage_summary(),
+            requested_model.as_deref(),
+            response_model.as_deref(),
         ));
     }
 
@@ -2266,51 +2330,50 @@ impl ChatWidget<'_> {
             return;
         }
 
-        if thread_spawner::spawn_lightweight("rate-reset-refresh", move || {
-            let now = Utc::now();
-            let delay = reset_at.signed_duration_since(now) + ChronoDuration::seconds(1);
-            if let Ok(delay) = delay.to_std()
-                && !delay.is_zero() {
-                    std::thread::sleep(delay);
-                }
-
+        let delay = (reset_at.signed_duration_since(Utc::now()) + ChronoDuration::seconds(1))
+            .to_std()
+            .unwrap_or_default();
+        // Wait on the shared timer; only the refresh itself needs a worker.
+        crate::timer_queue::run_after(delay, move || {
             if schedule_token.load(Ordering::SeqCst) != schedule_id {
                 return;
             }
 
-            let Some(account) = account else {
-                return;
-            };
+            if thread_spawner::spawn_lightweight("rate-reset-refresh", move || {
+                let Some(account) = account else {
+                    return;
+                };
 
-            let plan = account
-                .tokens
-                .as_ref()
-                .and_then(|tokens| tokens.id_token.get_chatgpt_plan_type());
-            let should_refresh = account_usage::mark_rate_limit_refresh_attempt_if_due(
-                &config.code_home,
-                &account.id,
-                plan.as_deref(),
-                Some(reset_at),
-                Utc::now(),
-                account_usage::rate_limit_refresh_stale_interval(),
-            )
-            .unwrap_or(false);
+                let plan = account
+                    .tokens
+                    .as_ref()
+                    .and_then(|tokens| tokens.id_token.get_chatgpt_plan_type());
+                let should_refresh = account_usage::mark_rate_limit_refresh_attempt_if_due(
+                    &config.code_home,
+                    &account.id,
+                    plan.as_deref(),
+                    Some(reset_at),
+                    Utc::now(),
+                    account_usage::rate_limit_refresh_stale_interval(),
+                )
+                .unwrap_or(false);
 
-            if should_refresh {
-                start_rate_limit_refresh_for_account(
-                    app_event_tx,
-                    config,
-                    debug_enabled,
-                    account,
-                    true,
-                    false,
-                );
+                if should_refresh {
+                    start_rate_limit_refresh_for_account(
+                        app_event_tx,
+                        config,
+                        debug_enabled,
+                        account,
+                        true,
+                        false,
+                    );
+                }
+            })
+            .is_none()
+            {
+                tracing::warn!("rate reset refresh scheduling failed: worker unavailable");
             }
-        })
-        .is_none()
-        {
-            tracing::warn!("rate reset refresh scheduling failed: worker unavailable");
-        }
+        });
     }
 
     pub(crate) fn handle_update_command(&mut self, command_args: &str) {
@@ -3244,7 +3307,7 @@ impl ChatWidget<'_> {
 
             if is_terminal {
                 let (mut has_findings, findings_count, summary) =
-                    Self::parse_agent_review_result(entry.result.as_deref());
+                    self.parse_auto_review_result(entry.result.as_deref(), None);
 
                 // Avoid showing a warning when we didn't get an explicit findings list.
                 // Some heuristic parses can claim "issues" but provide a zero count; treat those as clean
@@ -4302,25 +4365,10 @@ fi\n\
     }
 
     pub(crate) fn show_diffs_popup(&mut self) {
+        self.check_external_edits();
         use crate::diff_render::create_diff_details_only;
-        // Build a latest-first unique file list
-        let mut order: Vec<PathBuf> = Vec::new();
-        let mut seen: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
-        for changes in self.diffs.session_patch_sets.iter().rev() {
-            for (path, change) in changes.iter() {
-                // If this change represents a move/rename, show the destination path in the tabs
-                let display_path: PathBuf = match change {
-                    code_core::protocol::FileChange::Update {
-                        move_path: Some(dest),
-                        ..
-                    } => dest.clone(),
-                    _ => path.clone(),
-                };
-                if seen.insert(display_path.clone()) {
-                    order.push(display_path);
-                }
-            }
-        }
+        let order = self.session_diff_order();
+        let files: Vec<DiffFileMeta> = order.iter().map(|path| self.diff_file_meta(path)).collect();
         // Build tabs: for each file, create a single unified diff against the original baseline
         let mut tabs: Vec<(String, Vec<DiffBlock>)> = Vec::new();
         for path in order {
@@ -4349,32 +4397,7 @@ fi\n\
             let mut blocks: Vec<DiffBlock> = vec![DiffBlock { lines: detail }];
 
             // Count adds/removes for the header label from the unified diff
-            let mut total_added: usize = 0;
-            let mut total_removed: usize = 0;
-            if let Ok(patch) = diffy::Patch::from_str(&unified) {
-                for h in patch.hunks() {
-                    for l in h.lines() {
-                        match l {
-                            diffy::Line::Insert(_) => total_added += 1,
-                            diffy::Line::Delete(_) => total_removed += 1,
-                            _ => {}
-                        }
-                    }
-                }
-            } else {
-                for l in unified.lines() {
-                    if l.starts_with("+++") || l.starts_with("---") || l.starts_with("@@") {
-                        continue;
-                    }
-                    if let Some(b) = l.as_bytes().first() {
-                        if *b == b'+' {
-                            total_added += 1;
-                        } else if *b == b'-' {
-                            total_removed += 1;
-                        }
-                    }
-                }
-            }
+            let (total_added, total_removed) = touched_files_flow::diff_line_counts(&unified);
             // Prepend a header block with the full path and counts
             let header_line = {
                 use ratatui::style::Modifier;
@@ -4421,7 +4444,7 @@ fi\n\
                 .flash_footer_notice("No diffs recorded this session".to_string());
             return;
         }
-        self.diffs.overlay = Some(DiffOverlay::new(tabs));
+        self.diffs.overlay = Some(DiffOverlay::new(tabs).with_files(files));
         self.diffs.confirm = None;
         self.request_redraw();
     }
@@ -4465,6 +4488,7 @@ fi\n\
         lines.push(kv("Ctrl+R", "Toggle reasoning"));
         lines.push(kv("Ctrl+T", "Toggle screen"));
         lines.push(kv("Ctrl+D", "Diff viewer"));
+        lines.push(kv("Alt+P", "Collapse or expand the plan panel"));
         lines.push(kv("Esc", &format!("{} / close popups", Self::double_esc_hint_label())));
         // Task control shortcuts
         lines.push(kv("Esc", "End current task"));
@@ -4690,6 +4714,64 @@ fi\n\
         self.request_redraw();
     }
 
+    /// `/copy`: place the last assistant response on the system clipboard.
+    pub(crate) fn handle_copy_command(&mut self) {
+        let Some(message) = self.last_assistant_message.clone() else {
+            self.flash_footer_notice("Nothing to copy yet".to_string());
+            return;
+        };
+        match crate::clipboard_paste::copy_text_to_clipboard(&message) {
+            Ok(()) => self.flash_footer_notice(format!(
+                "Copied last response ({} chars)",
+                message.chars().count()
+            )),
+            Err(err) => self.history_push_plain_state(history_cell::new_error_event(format!(
+                "`/copy` failed: {err}"
+            ))),
+        }
+    }
+
+    pub(crate) fn handle_dictate_command(&mut self) {
+        if self.bottom_pane.is_dictating() {
+            self.flash_footer_notice("Already recording".to_string());
+            return;
+        }
+        let command = self.config.tui.dictation_command.as_deref();
+        match crate::dictation::start(command, self.app_event_tx.clone()) {
+            Ok(()) => {
+                self.bottom_pane.set_dictating(true);
+                self.flash_footer_notice("Listening…".to_string());
+            }
+            Err(err) => self.history_push_plain_state(history_cell::new_error_event(format!(
+                "`/dictate` failed: {err}"
+            ))),
+        }
+    }
+
+    pub(crate) fn on_dictation_finished(&mut self, result: Result<String, String>) {
+        self.bottom_pane.set_dictating(false);
+        match result {
+            Ok(text) if text.is_empty() => {
+                self.flash_footer_notice("No speech recognized".to_string());
+            }
+            Ok(text) => {
+                let needs_space = self
+                    .bottom_pane
+                    .composer_text()
+                    .chars()
+                    .last()
+                    .is_some_and(|ch| !ch.is_whitespace());
+                if needs_space {
+                    self.bottom_pane.insert_str(" ");
+                }
+                self.bottom_pane.insert_str(&text);
+            }
+            Err(err) => self.history_push_plain_state(history_cell::new_error_event(format!(
+                "`/dictate` failed: {err}"
+            ))),
+        }
+    }
+
     pub(crate) fn refresh_accounts_settings_content(&mut self) {
         let should_refresh_accounts = matches!(
             self.settings
@@ -5525,9 +5607,9 @@ fi\n\
                                 );
                                 reasoning_cell.append_lines_dedup(lines);
                                 reasoning_cell.set_in_progress(true);
-                                self.invalidate_height_cache();
+                                self.invalidate_cell_height(idx);
                                 self.autoscroll_if_near_bottom();
-                                self.request_redraw();
+                                self.request_history_redraw("reasoning stream");
                                 self.refresh_reasoning_collapsed_visibility();
                                 return;
                             }
@@ -5551,9 +5633,9 @@ fi\n\
                                 );
                                 reasoning_cell.append_lines_dedup(lines);
                                 reasoning_cell.set_in_progress(true);
-                                self.invalidate_height_cache();
+                                self.invalidate_cell_height(found_idx);
                                 self.autoscroll_if_near_bottom();
-                                self.request_redraw();
+                                self.request_history_redraw("reasoning stream");
                                 self.refresh_reasoning_collapsed_visibility();
                                 return;
                             }
@@ -5681,7 +5763,7 @@ fi\n\
 
         // Auto-follow if near bottom so new inserts are visible
         self.autoscroll_if_near_bottom();
-        self.request_redraw();
+        self.request_history_redraw("answer stream");
         self.flush_history_snapshot_if_needed(false);
     }
 
@@ -6168,8 +6250,8 @@ fi\n\
                         .as_any()
                         .downcast_ref::<history_cell::AssistantMarkdownCell>()
                     {
-                        let prev = Self::normalize_text(amc.markdown());
-                        let newn = Self::normalize_text(&source);
+                        let prev = crate::history::normalize_message_text(amc.markdown());
+                        let newn = crate::history::normalize_message_text(&source);
                         if prev == newn {
                             tracing::debug!(
                                 "InsertFinalAnswer: dropping duplicate final for id={}",
@@ -6290,8 +6372,8 @@ fi\n\
                 .as_any()
                 .downcast_ref::<history_cell::AssistantMarkdownCell>()
                 .map(|amc| {
-                    let prev = Self::normalize_text(amc.markdown());
-                    let newn = Self::normalize_text(&source);
+                    let prev = crate::history::normalize_message_text(amc.markdown());
+                    let newn = crate::history::normalize_message_text(&source);
                     let identical = prev == newn;
                     if identical || prev.is_empty() {
                         return identical;
@@ -6384,35 +6466,6 @@ fi\n\
     // Assign or fetch a stable sequence for a stream kind+id within its originating turn
     // removed legacy ensure_stream_order_key; strict variant is used instead
 
-    /// Normalize text for duplicate detection (trim trailing whitespace and normalize newlines)
-    fn normalize_text(s: &str) -> String {
-        // 1) Normalize newlines
-        let s = s.replace("\r\n", "\n");
-        // 2) Trim trailing whitespace per line; collapse repeated blank lines
-        let mut out: Vec<String> = Vec::new();
-        let mut saw_blank = false;
-        for line in s.lines() {
-            // Replace common Unicode bullets with ASCII to stabilize equality checks
-            let line = line
-                .replace(['\u{2022}', '\u{25E6}', '\u{2219}'], "-"); // ∙
-            let trimmed = line.trim_end();
-            if trimmed.chars().all(char::is_whitespace) {
-                if !saw_blank {
-                    out.push(String::new());
-                }
-                saw_blank = true;
-            } else {
-                out.push(trimmed.to_string());
-                saw_blank = false;
-            }
-        }
-        // 3) Remove trailing blank lines
-        while out.last().is_some_and(std::string::String::is_empty) {
-            out.pop();
-        }
-        out.join("\n")
-    }
-
     pub(crate) fn toggle_reasoning_visibility(&mut self) {
         // Track whether any reasoning cells are found and their new state
         let mut has_reasoning_cells = false;
@@ -7685,6 +7738,10 @@ fi\n\
             } else {
                 // It's a subcommand
                 match first_arg {
+                    "tabs" | "tab" | "snapshot" => {
+                        self.handle_browser_tab_command(&parts, browser_ticket);
+                        return;
+                    }
                     "off" => {
                         // Disable browser mode
                         // Clear the screenshot popup
@@ -7792,13 +7849,13 @@ fi\n\
                     }
                     _ => {
                         format!(
-                            "Unknown browser command: '{first_arg}'\nUsage: /browser <url> | off | status | fullpage | config"
+                            "Unknown browser command: '{first_arg}'\nUsage: /browser <url> | off | status | tabs | tab | snapshot | fullpage | config"
                         )
                     }
                 }
             }
         } else {
-            "Browser commands:\n• /browser <url> - Open URL in internal browser\n• /browser off - Disable browser mode\n• /browser status - Show current status\n• /browser fullpage [on|off] - Toggle full-page mode\n• /browser config <key> <value> - Update configuration\n\nUse /chrome [port] to connect to external Chrome browser".to_string()
+            "Browser commands:\n• /browser <url> - Open URL in internal browser\n• /browser off - Disable browser mode\n• /browser status - Show current status\n• /browser tabs - List open tabs\n• /browser tab <n> | new [url] | close <n> - Switch, open or close a tab\n• /browser snapshot [n] - Add a tab's screenshot to the browser timeline\n• /browser fullpage [on|off] - Toggle full-page mode\n• /browser config <key> <value> - Update configuration\n\nUse /chrome [port] to connect to external Chrome browser".to_string()
         };
 
         // Add the response to the UI as a ticketed background event so it stays with
@@ -8717,6 +8774,9 @@ fi\n\
             display_text: display,
             ordered_items: ordered,
             suppress_persistence: false,
+            attachment_bundles: Vec::new(),
+            redacted_values: 0,
+            model_override: None,
         };
         self.submit_user_message(msg);
     }
@@ -8741,6 +8801,9 @@ fi\n\
             display_text: visible,
             ordered_items: ordered,
             suppress_persistence: false,
+            attachment_bundles: Vec::new(),
+            redacted_values: 0,
+            model_override: None,
         };
         self.submit_user_message(msg);
     }
@@ -8800,6 +8863,9 @@ fi\n\
             display_text: String::new(),
             ordered_items: ordered,
             suppress_persistence: false,
+            attachment_bundles: Vec::new(),
+            redacted_values: 0,
+            model_override: None,
         };
         let mut cache = String::new();
         if !preface_cache.trim().is_empty() {
@@ -9071,7 +9137,8 @@ async fn run_background_review(
 
         // Use the /review entrypoint so upstream wiring (model defaults, review formatting) stays intact.
         let mut review_prompt = format!(
-            "/review Analyze only changes made in commit {snapshot_id}. Identify critical bugs, regressions, security/performance/concurrency risks or incorrect assumptions. Provide actionable feedback and references to the changed code; ignore minor style or formatting nits."
+            "/review {}",
+            code_core::auto_review::auto_review_prompt(&config.auto_review, &snapshot_id)
         );
 
         if let Some(context) = turn_context {
@@ -1,4 +1,12 @@
+#[cfg(unix)]
+use std::path::Path;
+#[cfg(unix)]
+use std::path::PathBuf;
 use std::sync::Arc;
+#[cfg(unix)]
+use std::sync::OnceLock;
+#[cfg(unix)]
+use std::time::Duration;
 
 use code_core::protocol::Event;
 use code_core::protocol::EventMsg;
@@ -43,6 +51,9 @@ pub(crate) fn spawn_new_conversation_runtime(
             Ok(conv) => conv,
             Err(e) => {
                 tracing::error!("failed to initialize conversation: {e}");
+                app_event_tx_clone.send(AppEvent::StartupStageFinished(
+                    crate::startup::StartupStage::Session,
+                ));
                 app_event_tx_clone.send_background_event_with_ticket(
                     &ticket,
                     format!(
@@ -118,3 +129,107 @@ pub(crate) fn spawn_existing_conversation_runtime(
         }
     });
 }
+
+#[cfg(unix)]
+static ATTACH_SOCKET: OnceLock<PathBuf> = OnceLock::new();
+
+#[cfg(unix)]
+const REATTACH_INITIAL_DELAY: Duration = Duration::from_millis(500);
+#[cfg(unix)]
+const REATTACH_MAX_DELAY: Duration = Duration::from_secs(10);
+
+/// Makes every conversation runtime attach to the `code daemon` listening on
+/// `socket` instead of starting a local session.
+#[cfg(unix)]
+pub(crate) fn set_attach_socket(socket: PathBuf) {
+    let _ = ATTACH_SOCKET.set(socket);
+}
+
+#[cfg(unix)]
+pub(super) fn attach_socket() -> Option<&'static Path> {
+    ATTACH_SOCKET.get().map(PathBuf::as_path)
+}
+
+/// Spawn forwarding loops for a session served by `code daemon`. When the
+/// socket drops, reconnects with backoff and asks only for events it has not
+/// seen. Quitting detaches instead of shutting the session down.
+#[cfg(unix)]
+pub(crate) fn spawn_attached_conversation_runtime(
+    socket: PathBuf,
+    app_event_tx: AppEventSender,
+    code_op_rx: UnboundedReceiver<Op>,
+    ticket: BackgroundOrderTicket,
+) {
+    tokio::spawn(async move {
+        let mut code_op_rx = code_op_rx;
+        let mut last_seq: Option<u64> = None;
+        let mut retry_delay = REATTACH_INITIAL_DELAY;
+        loop {
+            let (mut events, mut ops) = match code_core::daemon::connect(&socket, last_seq).await {
+                Ok(connection) => connection,
+                Err(e) if last_seq.is_none() => {
+                    app_event_tx.send_background_event_with_ticket(
+                        &ticket,
+                        format!("❌ Failed to attach to daemon at {}: {e}", socket.display()),
+                    );
+                    return;
+                }
+                Err(e) => {
+                    tracing::debug!("reattach failed: {e}");
+                    tokio::time::sleep(retry_delay).await;
+                    retry_delay = (retry_delay * 2).min(REATTACH_MAX_DELAY);
+                    continue;
+                }
+            };
+            if last_seq.is_some() {
+                app_event_tx.send_background_event_with_ticket(
+                    &ticket,
+                    "Reattached to daemon".to_string(),
+                );
+            }
+            retry_delay = REATTACH_INITIAL_DELAY;
+
+            loop {
+                tokio::select! {
+                    event = events.next_event() => match event {
+                        Ok(Some((seq, event))) => {
+                            last_seq = Some(seq);
+                            let shutdown = matches!(event.msg, EventMsg::ShutdownComplete);
+                            app_event_tx.send(AppEvent::CodexEvent(event));
+                            if shutdown {
+                                return;
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            tracing::warn!("daemon connection failed: {e}");
+                            break;
+                        }
+                    },
+                    op = code_op_rx.recv() => match op {
+                        // Leave the daemon running; only this client exits.
+                        Some(Op::Shutdown) => {
+                            app_event_tx.send(AppEvent::ExitRequest);
+                            return;
+                        }
+                        Some(op) => {
+                            if let Err(e) = ops.submit(op).await {
+                                tracing::warn!("failed to submit op to daemon: {e}");
+                                app_event_tx.send_background_event_with_ticket(
+                                    &ticket,
+                                    format!("⚠️ Failed to submit Op to daemon: {e}"),
+                                );
+                                break;
+                            }
+                        }
+                        None => return,
+                    },
+                }
+            }
+            app_event_tx.send_background_event_with_ticket(
+                &ticket,
+                "⚠️ Lost connection to daemon; reconnecting…".to_string(),
+            );
+        }
+    });
+}
@@ -0,0 +1,147 @@
+use super::*;
+use std::path::Path;
+use std::process::Command;
+use std::process::Stdio;
+
+const ARTIFACTS_USAGE: &str = "Usage: /artifacts [open | copy [name]]";
+
+/// What an `/artifacts` command asks for.
+#[derive(Debug, PartialEq, Eq)]
+enum ArtifactsCommand {
+    List,
+    Open,
+    /// Copy the folder path, or one artifact's path when named.
+    Copy(Option<String>),
+}
+
+fn parse_artifacts_command(args: &str) -> Result<ArtifactsCommand, String> {
+    let args = args.trim();
+    let (verb, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+    let rest = rest.trim();
+    match (verb, rest.is_empty()) {
+        ("" | "list", true) => Ok(ArtifactsCommand::List),
+        ("open", true) => Ok(ArtifactsCommand::Open),
+        ("copy", true) => Ok(ArtifactsCommand::Copy(None)),
+        ("copy", false) => Ok(ArtifactsCommand::Copy(Some(rest.to_string()))),
+        _ => Err(ARTIFACTS_USAGE.to_string()),
+    }
+}
+
+/// The platform's "open with the default app" command for `path`.
+fn opener_command(path: &Path) -> Command {
+    let program = if code_core::env::is_termux() {
+        "termux-open"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(windows) {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    let mut command = Command::new(program);
+    command
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    command
+}
+
+impl ChatWidget<'_> {
+    /// `/artifacts`: lists the files the agent saved for the user this
+    /// session. `open` reveals the folder in the platform file manager (or
+    /// copies its path when nothing can open it); `copy [name]` copies the
+    /// folder's or one artifact's path.
+    pub(crate) fn handle_artifacts_command(&mut self, args: String) {
+        let command = match parse_artifacts_command(&args) {
+            Ok(command) => command,
+            Err(usage) => {
+                self.history_push_plain_state(crate::history_cell::new_error_event(usage));
+                self.request_redraw();
+                return;
+            }
+        };
+        let Some(session_id) = self.session_id() else {
+            self.push_background_tail("No session yet; artifacts appear once the agent saves one.".to_string());
+            self.request_redraw();
+            return;
+        };
+        let dir = code_core::artifacts::session_artifacts_dir(&self.config.code_home, &session_id.to_string());
+        let artifacts = code_core::artifacts::list_artifacts(&dir);
+
+        match command {
+            ArtifactsCommand::List => {
+                self.history_push(crate::history_cell::ArtifactsCell::new(dir, artifacts, None));
+            }
+            ArtifactsCommand::Copy(name) => {
+                let target = match name {
+                    Some(name) => match artifacts.iter().find(|artifact| artifact.name == name) {
+                        Some(artifact) => artifact.path.clone(),
+                        None => {
+                            self.push_background_tail(format!("No artifact named `{name}` in {}", dir.display()));
+                            self.request_redraw();
+                            return;
+                        }
+                    },
+                    None => dir,
+                };
+                self.copy_artifact_path(&target);
+            }
+            ArtifactsCommand::Open => {
+                if artifacts.is_empty() {
+                    self.push_background_tail("No artifacts saved in this session yet.".to_string());
+                    self.request_redraw();
+                    return;
+                }
+                let tx = self.app_event_tx.clone();
+                let ticket = self.make_background_tail_ticket();
+                tokio::spawn(async move {
+                    let folder = dir.clone();
+                    let opened = tokio::task::spawn_blocking(move || opener_command(&folder).status())
+                        .await
+                        .ok()
+                        .and_then(Result::ok)
+                        .is_some_and(|status| status.success());
+                    let message = if opened {
+                        format!("Opened {}", dir.display())
+                    } else {
+                        let path = dir.display().to_string();
+                        match crate::clipboard_paste::copy_text_to_clipboard(&path) {
+                            Ok(()) => format!("Could not open the folder; copied its path instead: {path}"),
+                            Err(_) => format!("Could not open the folder: {path}"),
+                        }
+                    };
+                    tx.send_background_event_with_ticket(&ticket, message);
+                });
+            }
+        }
+        self.request_redraw();
+    }
+
+    fn copy_artifact_path(&mut self, path: &Path) {
+        let path = path.display().to_string();
+        let message = match crate::clipboard_paste::copy_text_to_clipboard(&path) {
+            Ok(()) => format!("Copied {path}"),
+            Err(err) => format!("Could not copy to the clipboard ({err}): {path}"),
+        };
+        self.push_background_tail(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_artifacts_subcommands() {
+        assert_eq!(parse_artifacts_command(""), Ok(ArtifactsCommand::List));
+        assert_eq!(parse_artifacts_command(" open "), Ok(ArtifactsCommand::Open));
+        assert_eq!(parse_artifacts_command("copy"), Ok(ArtifactsCommand::Copy(None)));
+        assert_eq!(
+            parse_artifacts_command("copy report final.md"),
+            Ok(ArtifactsCommand::Copy(Some("report final.md".to_string())))
+        );
+        assert!(parse_artifacts_command("open now").is_err());
+        assert!(parse_artifacts_command("delete").is_err());
+    }
+}
@@ -2,6 +2,7 @@ use super::*;
 use crate::app_event::AutoDriveSettingsUpdate;
 
 mod decision_runtime;
+mod guardrails;
 mod review_runtime;
 mod presentation;
 
@@ -46,6 +47,9 @@ impl ChatWidget<'_> {
     }
 
     pub(super) fn auto_reduced_motion_preference() -> bool {
+        if crate::accessibility::enabled() {
+            return true;
+        }
         match std::env::var("CODE_TUI_REDUCED_MOTION") {
             Ok(value) => {
                 let normalized = value.trim().to_ascii_lowercase();
@@ -167,6 +171,7 @@ impl ChatWidget<'_> {
                 reduced_motion,
             },
         );
+        self.auto_guardrails_start_run();
         self.config.auto_drive.cross_check_enabled = cross_check_enabled;
         self.config.auto_drive.qa_automation_enabled = qa_automation_enabled;
         let coordinator_events = {
@@ -320,6 +325,7 @@ impl ChatWidget<'_> {
         if self.auto_state.is_active() {
             self.auto_stop(None);
         }
+        self.warn_if_power_constrained("Auto Drive");
 
         let defaults = self.config.auto_drive.clone();
         let default_mode = auto_continue_from_config(defaults.continue_mode);
@@ -446,6 +452,7 @@ impl ChatWidget<'_> {
             self.auto_state.clear_bypass_coordinator_flag();
         }
         let conversation = std::sync::Arc::<[ResponseItem]>::from(self.current_auto_history());
+        self.auto_guardrails_record_output(&conversation);
         let Some(handle) = self.auto_handle.as_ref() else {
             return;
         };
@@ -530,6 +537,7 @@ impl ChatWidget<'_> {
         };
         match handle.send(command) {
             Ok(()) => {
+                self.auto_guardrails_user_stepped_in();
                 self.auto_state.on_prompt_submitted();
                 self.auto_state.set_coordinator_waiting(true);
                 self.auto_state.placeholder_phrase =
@@ -566,22 +574,8 @@ impl ChatWidget<'_> {
     }
 
     pub(super) fn auto_schedule_restart_event(&self, token: u64, attempt: u32, delay: Duration) {
-        let tx = self.app_event_tx.clone();
-        if let Ok(handle) = tokio::runtime::Handle::try_current() {
-            handle.spawn(async move {
-                if !delay.is_zero() {
-                    tokio::time::sleep(delay).await;
-                }
-                tx.send(AppEvent::AutoCoordinatorRestart { token, attempt });
-            });
-        } else {
-            std::thread::spawn(move || {
-                if !delay.is_zero() {
-                    std::thread::sleep(delay);
-                }
-                tx.send(AppEvent::AutoCoordinatorRestart { token, attempt });
-            });
-        }
+        self.app_event_tx
+            .send_after(delay, AppEvent::AutoCoordinatorRestart { token, attempt });
     }
 
     pub(super) fn auto_pause_for_transient_failure(&mut self, message: String) {
@@ -155,6 +155,7 @@ impl ChatWidget<'_> {
                         ));
                     }
                 } else {
+                    self.auto_guardrails_check_turn();
                     self.schedule_auto_cli_prompt(seq, prompt_text);
                 }
             }
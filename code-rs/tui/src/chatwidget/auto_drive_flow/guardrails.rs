@@ -0,0 +1,171 @@
+use super::*;
+use code_core::config_types::AutoDriveSettings;
+use code_protocol::models::ContentItem;
+
+/// Final assistant text of the conversation the coordinator is about to see.
+fn last_assistant_text(conversation: &[ResponseItem]) -> Option<String> {
+    conversation.iter().rev().find_map(|item| match item {
+        ResponseItem::Message { role, content, .. } if role == "assistant" => {
+            let text: String = content
+                .iter()
+                .filter_map(|part| match part {
+                    ContentItem::OutputText { text } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect();
+            (!text.trim().is_empty()).then_some(text)
+        }
+        _ => None,
+    })
+}
+
+/// The first guardrail the next turn would cross, if any. Invalid
+/// `stop_patterns` are skipped with a warning.
+fn fired_guardrail(
+    settings: &AutoDriveSettings,
+    state: &AutoGuardrailState,
+    turns_completed: usize,
+) -> Option<AutoGuardrail> {
+    if let Some(output) = state.last_output.as_deref() {
+        for pattern in &settings.stop_patterns {
+            match regex_lite::Regex::new(pattern) {
+                Ok(regex) if regex.is_match(output) => {
+                    return Some(AutoGuardrail::StopPattern(pattern.clone()));
+                }
+                Ok(_) => {}
+                Err(err) => tracing::warn!("ignoring invalid auto_drive.stop_patterns entry {pattern:?}: {err}"),
+            }
+        }
+    }
+    if let Some(limit) = settings.max_cost_usd.filter(|limit| *limit > 0.0) {
+        let spent = state.session_cost_usd - state.cost_baseline_usd;
+        if spent >= limit {
+            return Some(AutoGuardrail::MaxCost { limit, spent });
+        }
+    }
+    let max_turns = settings.max_consecutive_turns;
+    if max_turns > 0 && state.unattended_turns >= max_turns {
+        return Some(AutoGuardrail::MaxConsecutiveTurns(max_turns));
+    }
+    let every = settings.check_in_every_turns as usize;
+    if every > 0 && turns_completed > 0 && turns_completed.is_multiple_of(every) {
+        return Some(AutoGuardrail::CheckIn(settings.check_in_every_turns));
+    }
+    None
+}
+
+fn describe_guardrail(guardrail: &AutoGuardrail) -> String {
+    match guardrail {
+        AutoGuardrail::MaxConsecutiveTurns(max) => {
+            format!("max_consecutive_turns: {max} turns in a row without your input.")
+        }
+        AutoGuardrail::MaxCost { limit, spent } => {
+            format!("max_cost_usd: this run has spent about ${spent:.2} (limit ${limit:.2}).")
+        }
+        AutoGuardrail::CheckIn(every) => format!("check_in_every_turns: check-in due after {every} turns."),
+        AutoGuardrail::StopPattern(pattern) => {
+            format!("stop_patterns: the last output matched `{pattern}`.")
+        }
+    }
+}
+
+impl ChatWidget<'_> {
+    /// Starts the guardrail counters for a new run.
+    pub(crate) fn auto_guardrails_start_run(&mut self) {
+        let session_cost_usd = self.auto_guardrails.session_cost_usd;
+        self.auto_guardrails = AutoGuardrailState {
+            session_cost_usd,
+            cost_baseline_usd: session_cost_usd,
+            ..AutoGuardrailState::default()
+        };
+    }
+
+    pub(crate) fn auto_guardrails_record_output(&mut self, conversation: &[ResponseItem]) {
+        self.auto_guardrails.last_output = last_assistant_text(conversation);
+    }
+
+    /// The user sent something themselves, so the run is attended again.
+    pub(crate) fn auto_guardrails_user_stepped_in(&mut self) {
+        self.auto_guardrails.unattended_turns = 0;
+    }
+
+    /// Counts the turn the coordinator just decided on. When a guardrail
+    /// fires, the prompt is held for Enter and a notice says which one.
+    pub(crate) fn auto_guardrails_check_turn(&mut self) {
+        self.auto_guardrails.unattended_turns = self.auto_guardrails.unattended_turns.saturating_add(1);
+        let fired = fired_guardrail(
+            &self.config.auto_drive,
+            &self.auto_guardrails,
+            self.auto_state.turns_completed,
+        );
+        self.auto_guardrails.last_output = None;
+        let Some(guardrail) = fired else {
+            return;
+        };
+        let reason = describe_guardrail(&guardrail);
+        self.auto_guardrails.fired = Some(guardrail);
+        self.auto_state.guardrail_hold = true;
+        self.history_push_plain_paragraphs(
+            PlainMessageKind::Notice,
+            [
+                "AUTO DRIVE PAUSED".to_string(),
+                format!("Guardrail {reason}"),
+                "Press Enter to send the next prompt, Esc to edit it, or stop Auto Drive.".to_string(),
+            ],
+        );
+        self.auto_card_add_action(format!("Paused by guardrail {reason}"), AutoDriveActionKind::Warning);
+        self.auto_card_set_status(AutoDriveStatus::Paused);
+    }
+
+    /// Called as a held prompt is sent: the user has continued past the
+    /// guardrail, so its measure starts over.
+    pub(crate) fn auto_guardrails_release(&mut self) {
+        if !self.auto_state.guardrail_hold {
+            return;
+        }
+        if let Some(AutoGuardrail::MaxCost { .. }) = self.auto_guardrails.fired.take() {
+            self.auto_guardrails.cost_baseline_usd = self.auto_guardrails.session_cost_usd;
+        }
+        self.auto_guardrails.unattended_turns = 0;
+        self.auto_card_set_status(AutoDriveStatus::Running);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guardrails_fire_in_priority_order() {
+        let settings = AutoDriveSettings {
+            max_consecutive_turns: 5,
+            max_cost_usd: Some(2.0),
+            check_in_every_turns: 4,
+            stop_patterns: vec!["(?i)need (human|user) input".to_string(), "[".to_string()],
+            ..AutoDriveSettings::default()
+        };
+        let mut state = AutoGuardrailState {
+            unattended_turns: 3,
+            session_cost_usd: 3.0,
+            cost_baseline_usd: 1.5,
+            ..AutoGuardrailState::default()
+        };
+        assert_eq!(fired_guardrail(&settings, &state, 3), None);
+        assert_eq!(fired_guardrail(&settings, &state, 8), Some(AutoGuardrail::CheckIn(4)));
+        state.unattended_turns = 5;
+        assert_eq!(
+            fired_guardrail(&settings, &state, 8),
+            Some(AutoGuardrail::MaxConsecutiveTurns(5))
+        );
+        state.session_cost_usd = 3.5;
+        assert_eq!(
+            fired_guardrail(&settings, &state, 8),
+            Some(AutoGuardrail::MaxCost { limit: 2.0, spent: 2.0 })
+        );
+        state.last_output = Some("Blocked: need USER input on the schema.".to_string());
+        assert_eq!(
+            fired_guardrail(&settings, &state, 8),
+            Some(AutoGuardrail::StopPattern("(?i)need (human|user) input".to_string()))
+        );
+    }
+}
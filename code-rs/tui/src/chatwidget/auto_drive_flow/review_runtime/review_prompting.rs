@@ -109,6 +109,7 @@ impl ChatWidget<'_> {
 
     pub(crate) fn auto_dispatch_cli_prompt(&mut self, full_prompt: String) {
         self.auto_pending_goal_request = false;
+        self.auto_guardrails_release();
 
         self.bottom_pane.set_standard_terminal_hint(None);
         self.auto_state.on_prompt_submitted();
@@ -568,6 +568,24 @@ fn summarize_action(
         "browser_close" => {
             summary.action = "Close".to_string();
         }
+        "browser_new_tab" => {
+            summary.target = params
+                .and_then(|value| value.get("url"))
+                .and_then(Value::as_str)
+                .map(std::string::ToString::to_string);
+        }
+        "browser_read" => {
+            summary.value = params
+                .and_then(|value| value.get("format"))
+                .and_then(Value::as_str)
+                .map(std::string::ToString::to_string);
+        }
+        "browser_switch_tab" | "browser_close_tab" | "browser_snapshot" => {
+            summary.target = params
+                .and_then(|value| value.get("tab"))
+                .and_then(Value::as_u64)
+                .map(|tab| format!("tab {tab}"));
+        }
         _ => {
             summary.target = params
                 .and_then(|value| value.get("target"))
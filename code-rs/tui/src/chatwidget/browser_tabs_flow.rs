@@ -0,0 +1,189 @@
+use super::*;
+use super::browser_timeline_flow::browser_snapshot_app_event;
+use code_core::protocol::BrowserScreenshotUpdateEvent;
+
+const BROWSER_TAB_USAGE: &str = "Usage: /browser tabs | tab <n> | tab new [url] | tab close <n> | snapshot [n]";
+
+/// What a `/browser tabs|tab|snapshot` command asks for.
+#[derive(Debug, PartialEq, Eq)]
+enum BrowserTabCommand {
+    List,
+    Open(String),
+    Switch(usize),
+    Close(usize),
+    Snapshot(Option<usize>),
+}
+
+fn parse_browser_tab_command(parts: &[&str]) -> Result<BrowserTabCommand, String> {
+    let parse_index = |raw: &str| {
+        raw.parse::<usize>()
+            .ok()
+            .filter(|index| *index > 0)
+            .ok_or_else(|| format!("'{raw}' is not a tab number.\n{BROWSER_TAB_USAGE}"))
+    };
+    match parts {
+        ["tabs"] | ["tab"] => Ok(BrowserTabCommand::List),
+        ["tab", "new"] => Ok(BrowserTabCommand::Open("about:blank".to_string())),
+        ["tab", "new", url] => {
+            let url = if url.contains("://") || *url == "about:blank" {
+                url.to_string()
+            } else {
+                format!("https://{url}")
+            };
+            Ok(BrowserTabCommand::Open(url))
+        }
+        ["tab", "close", index] => parse_index(index).map(BrowserTabCommand::Close),
+        ["tab", index] => parse_index(index).map(BrowserTabCommand::Switch),
+        ["snapshot"] => Ok(BrowserTabCommand::Snapshot(None)),
+        ["snapshot", index] => parse_index(index).map(|index| BrowserTabCommand::Snapshot(Some(index))),
+        _ => Err(BROWSER_TAB_USAGE.to_string()),
+    }
+}
+
+fn format_tab_list(tabs: &[code_browser::BrowserTab]) -> String {
+    if tabs.is_empty() {
+        return "No browser tabs open.".to_string();
+    }
+    let mut text = "Browser tabs:".to_string();
+    for tab in tabs {
+        let marker = if tab.active { "▸" } else { " " };
+        match tab.title.as_deref() {
+            Some(title) => text.push_str(&format!("\n{marker} {}. {title} — {}", tab.index, tab.url)),
+            None => text.push_str(&format!("\n{marker} {}. {}", tab.index, tab.url)),
+        }
+    }
+    text
+}
+
+impl ChatWidget<'_> {
+    /// `/browser tabs`, `/browser tab …` and `/browser snapshot [n]`: list,
+    /// open, switch and close tabs of the running browser, or add a tab's
+    /// screenshot to the browser overlay timeline.
+    pub(super) fn handle_browser_tab_command(&mut self, parts: &[&str], ticket: BackgroundOrderTicket) {
+        let command = match parse_browser_tab_command(parts) {
+            Ok(command) => command,
+            Err(usage) => {
+                self.app_event_tx.send_background_event_with_ticket(&ticket, usage);
+                return;
+            }
+        };
+        let tx = self.app_event_tx.clone();
+        let latest_screenshot = self.latest_browser_screenshot.clone();
+
+        tokio::spawn(async move {
+            let browser_manager = ChatWidget::get_browser_manager().await;
+            if !browser_manager.is_enabled().await {
+                tx.send_background_event_with_ticket(
+                    &ticket,
+                    "Browser is not running; start it with /browser <url> or /chrome.".to_string(),
+                );
+                return;
+            }
+
+            let outcome = match command {
+                BrowserTabCommand::List => Ok(None),
+                BrowserTabCommand::Open(url) => browser_manager
+                    .open_tab(&url)
+                    .await
+                    .map(|tab| Some(format!("Opened tab {}: {}", tab.index, tab.url))),
+                BrowserTabCommand::Switch(index) => browser_manager
+                    .switch_tab(index)
+                    .await
+                    .map(|tab| Some(format!("Switched to tab {}: {}", tab.index, tab.url))),
+                BrowserTabCommand::Close(index) => browser_manager
+                    .close_tab(index)
+                    .await
+                    .map(|()| Some(format!("Closed tab {index}"))),
+                BrowserTabCommand::Snapshot(index) => {
+                    let captured = match index {
+                        Some(index) => browser_manager.capture_tab_screenshot(index).await,
+                        None => browser_manager
+                            .capture_screenshot_with_url()
+                            .await
+                            .map(|(paths, url)| (paths, url.unwrap_or_default())),
+                    };
+                    match captured {
+                        Ok((paths, url)) => match paths.into_iter().next() {
+                            Some(path) => {
+                                let tabs = browser_manager.list_tabs().await.unwrap_or_default();
+                                tx.send(browser_snapshot_app_event(path, url.clone(), &tabs));
+                                tx.send_background_event_with_ticket(
+                                    &ticket,
+                                    format!("📸 Snapshot of {url} added to the browser timeline (Ctrl+B)"),
+                                );
+                            }
+                            None => tx.send_background_event_with_ticket(
+                                &ticket,
+                                "Screenshot capture returned no image.".to_string(),
+                            ),
+                        },
+                        Err(err) => tx.send_background_event_with_ticket(
+                            &ticket,
+                            format!("❌ Snapshot failed: {err}"),
+                        ),
+                    }
+                    return;
+                }
+            };
+
+            let summary = match outcome {
+                Ok(summary) => summary,
+                Err(err) => {
+                    tx.send_background_event_with_ticket(&ticket, format!("❌ {err}"));
+                    return;
+                }
+            };
+            let tabs = browser_manager.list_tabs().await.unwrap_or_default();
+            let listing = format_tab_list(&tabs);
+            let message = match summary {
+                Some(summary) => format!("{summary}\n{listing}"),
+                None => listing,
+            };
+            tx.send_background_event_with_ticket(&ticket, message);
+
+            // The active tab may have changed; refresh the screenshot preview.
+            if let Ok((paths, url)) = browser_manager.capture_screenshot_with_url().await
+                && let Some(path) = paths.into_iter().next()
+            {
+                let url = url.unwrap_or_default();
+                if let Ok(mut latest) = latest_screenshot.lock() {
+                    *latest = Some((path.clone(), url.clone()));
+                }
+                tx.send(AppEvent::CodexEvent(Event {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    event_seq: 0,
+                    msg: EventMsg::BrowserScreenshotUpdate(BrowserScreenshotUpdateEvent {
+                        screenshot_path: path,
+                        url,
+                    }),
+                    order: None,
+                }));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tab_subcommands() {
+        assert_eq!(parse_browser_tab_command(&["tabs"]), Ok(BrowserTabCommand::List));
+        assert_eq!(parse_browser_tab_command(&["tab", "2"]), Ok(BrowserTabCommand::Switch(2)));
+        assert_eq!(
+            parse_browser_tab_command(&["tab", "new", "docs.rs"]),
+            Ok(BrowserTabCommand::Open("https://docs.rs".to_string()))
+        );
+        assert_eq!(
+            parse_browser_tab_command(&["tab", "close", "3"]),
+            Ok(BrowserTabCommand::Close(3))
+        );
+        assert_eq!(
+            parse_browser_tab_command(&["snapshot", "1"]),
+            Ok(BrowserTabCommand::Snapshot(Some(1)))
+        );
+        assert!(parse_browser_tab_command(&["tab", "0"]).is_err());
+        assert!(parse_browser_tab_command(&["tab", "close"]).is_err());
+    }
+}
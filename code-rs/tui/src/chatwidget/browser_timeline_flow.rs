@@ -0,0 +1,196 @@
+use super::*;
+
+/// One screenshot on the browser overlay's timeline.
+pub(super) struct BrowserTimelineShot {
+    pub(super) path: PathBuf,
+    pub(super) url: Option<String>,
+    pub(super) title: Option<String>,
+    /// Offset into the browser tool session, for screenshots it recorded.
+    pub(super) timestamp: Option<Duration>,
+    /// Capture time (RFC 3339) reported with a snapshot event.
+    pub(super) captured_at: Option<String>,
+}
+
+impl BrowserTimelineShot {
+    /// Short time label for the timeline list.
+    pub(super) fn time_label(&self, chat: &ChatWidget<'_>) -> String {
+        if let Some(timestamp) = self.timestamp {
+            return chat.format_overlay_mm_ss(timestamp);
+        }
+        // `2025-11-05T12:00:00Z` → `12:00:00`
+        self.captured_at
+            .as_deref()
+            .and_then(|captured| captured.get(11..19))
+            .unwrap_or("--:--")
+            .to_string()
+    }
+}
+
+impl ChatWidget<'_> {
+    /// Adds a snapshot event's screenshot to the overlay timeline, following
+    /// it when the overlay is closed or already showing the newest shot.
+    pub(super) fn record_browser_snapshot(&mut self, entry: BrowserSnapshotEntry) {
+        let previous_len = self.browser_overlay_timeline().len();
+        let at_end = self.browser_overlay_state.screenshot_index() + 1 >= previous_len;
+        self.browser_overlay_state.record_snapshot(entry);
+        if !self.browser_overlay_visible || at_end {
+            let len = self.browser_overlay_timeline().len();
+            self.browser_overlay_state
+                .set_screenshot_index(len.saturating_sub(1));
+        }
+        self.request_redraw();
+    }
+
+    /// Screenshots recorded by the overlay's browser tool session, followed by
+    /// snapshot-event screenshots it does not have, oldest first. Snapshot
+    /// titles are attached to the session screenshots they match.
+    pub(super) fn browser_overlay_timeline(&self) -> Vec<BrowserTimelineShot> {
+        let snapshots = self.browser_overlay_state.snapshots();
+        let mut shots: Vec<BrowserTimelineShot> = Vec::new();
+        if let Some((_, tracker)) = self.browser_overlay_tracker() {
+            for record in tracker.cell.screenshot_history() {
+                let snapshot = snapshots.iter().find(|snapshot| snapshot.path == record.path);
+                shots.push(BrowserTimelineShot {
+                    path: record.path.clone(),
+                    url: record
+                        .url
+                        .clone()
+                        .or_else(|| snapshot.and_then(|snapshot| snapshot.url.clone())),
+                    title: snapshot.and_then(|snapshot| snapshot.title.clone()),
+                    timestamp: Some(record.timestamp),
+                    captured_at: snapshot.and_then(|snapshot| snapshot.captured_at.clone()),
+                });
+            }
+        }
+        for snapshot in snapshots {
+            if shots.iter().any(|shot| shot.path == snapshot.path) {
+                continue;
+            }
+            shots.push(BrowserTimelineShot {
+                path: snapshot.path,
+                url: snapshot.url,
+                title: snapshot.title,
+                timestamp: None,
+                captured_at: snapshot.captured_at,
+            });
+        }
+        shots
+    }
+
+    pub(super) fn selected_browser_shot(&self) -> Option<BrowserTimelineShot> {
+        let mut timeline = self.browser_overlay_timeline();
+        if timeline.is_empty() {
+            return None;
+        }
+        let index = self
+            .browser_overlay_state
+            .screenshot_index()
+            .min(timeline.len() - 1);
+        Some(timeline.swap_remove(index))
+    }
+
+    /// `c` in the browser overlay: copies the selected screenshot's URL.
+    pub(super) fn copy_selected_browser_url(&mut self) {
+        let Some(url) = self
+            .selected_browser_shot()
+            .and_then(|shot| shot.url)
+            .filter(|url| !url.trim().is_empty())
+        else {
+            self.bottom_pane
+                .update_status_text("no URL for this screenshot".to_string());
+            return;
+        };
+        match crate::clipboard_paste::copy_text_to_clipboard(&url) {
+            Ok(()) => self
+                .bottom_pane
+                .update_status_text(format!("copied {url}")),
+            Err(err) => self
+                .bottom_pane
+                .update_status_text(format!("copy failed: {err}")),
+        }
+        self.request_redraw();
+    }
+
+    /// `r` in the browser overlay: captures the page now and adds the
+    /// screenshot to the timeline through a snapshot event.
+    pub(super) fn recapture_browser_snapshot(&mut self) {
+        let tx = self.app_event_tx.clone();
+        let latest_screenshot = self.latest_browser_screenshot.clone();
+        let ticket = self.make_background_tail_ticket();
+        self.bottom_pane
+            .update_status_text("capturing screenshot".to_string());
+        self.request_redraw();
+
+        tokio::spawn(async move {
+            let browser_manager = ChatWidget::get_browser_manager().await;
+            if !browser_manager.is_enabled().await {
+                tx.send_background_event_with_ticket(
+                    &ticket,
+                    "Browser is not running; start it with /browser to capture a screenshot."
+                        .to_string(),
+                );
+                return;
+            }
+            let (path, url) = match browser_manager.capture_screenshot_with_url().await {
+                Ok((paths, url)) => match paths.into_iter().next() {
+                    Some(path) => (path, url.unwrap_or_else(|| "Browser".to_string())),
+                    None => {
+                        tx.send_background_event_with_ticket(
+                            &ticket,
+                            "Screenshot capture returned no image.".to_string(),
+                        );
+                        return;
+                    }
+                },
+                Err(err) => {
+                    tx.send_background_event_with_ticket(
+                        &ticket,
+                        format!("Screenshot capture failed: {err}"),
+                    );
+                    return;
+                }
+            };
+
+            if let Ok(mut latest) = latest_screenshot.lock() {
+                *latest = Some((path.clone(), url.clone()));
+            }
+            let tabs = browser_manager.list_tabs().await.unwrap_or_default();
+            tx.send(browser_snapshot_app_event(path, url, &tabs));
+        });
+    }
+}
+
+/// A snapshot event for a screenshot the TUI captured itself, shaped like the
+/// ones core emits so the timeline and context summary treat them alike.
+/// `tabs` is attached when more than one tab is open; the title comes from
+/// the tab showing `url`, preferring the active one.
+pub(super) fn browser_snapshot_app_event(
+    path: PathBuf,
+    url: String,
+    tabs: &[code_browser::BrowserTab],
+) -> AppEvent {
+    let captured_at = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let title = tabs
+        .iter()
+        .filter(|tab| tab.url == url)
+        .max_by_key(|tab| tab.active)
+        .and_then(|tab| tab.title.clone());
+    let mut snapshot = serde_json::json!({ "url": url, "captured_at": captured_at });
+    if let Some(title) = title {
+        snapshot["title"] = serde_json::Value::String(title);
+    }
+    if tabs.len() > 1 {
+        snapshot["tabs"] = serde_json::to_value(tabs).unwrap_or_default();
+    }
+    AppEvent::CodexEvent(Event {
+        id: uuid::Uuid::new_v4().to_string(),
+        event_seq: 0,
+        msg: EventMsg::BrowserSnapshot(BrowserSnapshotEvent {
+            snapshot,
+            url: Some(url),
+            captured_at: Some(captured_at),
+            screenshot_path: Some(path),
+        }),
+        order: None,
+    })
+}
@@ -0,0 +1,97 @@
+use super::*;
+
+/// Gutter width with timestamps: `HH:MM`, a space, then the usual symbol
+/// column.
+const STAMPED_GUTTER_WIDTH: u16 = 8;
+const PLAIN_GUTTER_WIDTH: u16 = 2;
+
+/// What the gutter shows beside one history cell.
+pub(super) struct GutterStamp {
+    /// Wall-clock time of the cell's event.
+    pub(super) time: String,
+    /// How long the turn took, beside a turn's final answer.
+    pub(super) turn_took: Option<String>,
+}
+
+/// `42s`, `3m42s` or `1h02m`.
+fn format_turn_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60),
+    }
+}
+
+impl ChatWidget<'_> {
+    pub(super) fn history_gutter_width(&self) -> u16 {
+        if self.cell_timestamps.enabled {
+            STAMPED_GUTTER_WIDTH
+        } else {
+            PLAIN_GUTTER_WIDTH
+        }
+    }
+
+    /// Alt+T: shows or hides timestamps in the history gutter.
+    pub(crate) fn toggle_cell_timestamps(&mut self) {
+        self.cell_timestamps.enabled = !self.cell_timestamps.enabled;
+        self.history_render.invalidate_all();
+        let state = if self.cell_timestamps.enabled { "on" } else { "off" };
+        self.push_background_tail(format!("Timestamps {state} (Alt+T toggles)"));
+        self.request_redraw();
+    }
+
+    pub(super) fn cell_gutter_stamp(&self, id: HistoryId) -> Option<GutterStamp> {
+        if !self.cell_timestamps.enabled || id == HistoryId::ZERO {
+            return None;
+        }
+        let at = self.history_state.record(id)?.timestamp()?;
+        let time = chrono::DateTime::<chrono::Local>::from(at)
+            .format("%H:%M")
+            .to_string();
+        let turn_took = self
+            .cell_timestamps
+            .turn_durations
+            .get(&id)
+            .map(|took| format_turn_duration(*took));
+        Some(GutterStamp { time, turn_took })
+    }
+
+    pub(super) fn note_turn_started(&mut self) {
+        if self.active_task_ids.is_empty() {
+            self.cell_timestamps.turn_started_at = Some(SystemTime::now());
+        }
+    }
+
+    /// Records how long the turn that just ended took, against its final
+    /// answer.
+    pub(super) fn note_turn_finished(&mut self) {
+        let Some(started) = self.cell_timestamps.turn_started_at.take() else {
+            return;
+        };
+        let answer = self.history_cells.iter().rev().find_map(|cell| {
+            cell.as_any()
+                .downcast_ref::<history_cell::AssistantMarkdownCell>()
+                .map(history_cell::AssistantMarkdownCell::state)
+                .filter(|state| !state.mid_turn)
+        });
+        if let Some(answer) = answer
+            && answer.created_at >= started
+            && let Ok(took) = SystemTime::now().duration_since(started)
+        {
+            self.cell_timestamps.turn_durations.insert(answer.id, took);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turn_durations_are_compact() {
+        assert_eq!(format_turn_duration(Duration::from_secs(42)), "42s");
+        assert_eq!(format_turn_duration(Duration::from_secs(222)), "3m42s");
+        assert_eq!(format_turn_duration(Duration::from_secs(3720)), "1h02m");
+    }
+}
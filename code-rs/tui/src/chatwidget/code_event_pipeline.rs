@@ -94,11 +94,22 @@ impl ChatWidget<'_> {
                 self.handle_browser_snapshot_event(&ev);
             }
             EventMsg::CompactionCheckpointWarning(event) => {
-                self.history_push_plain_paragraphs(PlainMessageKind::Notice, [event.message]);
+                let mut lines = vec![match event.compacted_messages {
+                    Some(count) => format!(
+                        "Compacted {count} earlier message{} into a summary.",
+                        if count == 1 { "" } else { "s" }
+                    ),
+                    None => event.message,
+                }];
+                if let Some(path) = event.rollout_path {
+                    lines.push(format!("Originals are kept in {}", path.display()));
+                }
+                self.history_push_plain_paragraphs(PlainMessageKind::Notice, lines);
             }
             EventMsg::SessionConfigured(event) => {
                 // Record session id for potential future fork/backtrack features
                 self.session_id = Some(event.session_id);
+                self.finish_startup_stage(crate::startup::StartupStage::Session);
                 self.bottom_pane
                     .set_history_metadata(event.history_log_id, event.history_entry_count);
                 // Record session information at the top of the conversation.
@@ -123,11 +134,14 @@ impl ChatWidget<'_> {
                         .history_insert_plain_state_with_key(session_state, key, "prelude");
                 }
 
+                self.check_interrupted_turn();
+                self.restore_scope();
                 if let Some(user_message) = self.initial_user_message.take() {
                     // If the user provided an initial message, add it to the
                     // conversation history.
                     self.submit_user_message(user_message);
                 }
+                self.restore_offline_queue();
 
                 // Ask core for custom prompts so the slash menu can show them.
                 self.submit_op(Op::ListCustomPrompts);
@@ -247,6 +261,9 @@ impl ChatWidget<'_> {
                 if let Some(info) = &event.info {
                     self.total_token_usage = info.total_token_usage.clone();
                     self.last_token_usage = info.last_token_usage.clone();
+                    self.last_request_models =
+                        (info.requested_model.clone(), info.latest_response_model.clone());
+                    self.history_state.record_token_usage(&info.last_token_usage);
                 }
                 if let Some(snapshot) = event.rate_limits {
                     self.update_rate_limit_resets(&snapshot);
@@ -310,6 +327,10 @@ impl ChatWidget<'_> {
                     self.last_token_usage.clone(),
                     self.config.model_context_window,
                 );
+                if let Some(cost) = event.session_cost_usd {
+                    self.auto_guardrails.session_cost_usd = cost;
+                    self.bottom_pane.set_session_cost(cost);
+                }
                 self.update_stream_token_usage_metadata();
             }
             EventMsg::Error(ErrorEvent { message }) => {
@@ -341,6 +362,7 @@ impl ChatWidget<'_> {
                 // the current (last-seen) request — do NOT advance to the next
                 // request when a prompt is already queued, since these belong
                 // to the in-flight turn.
+                self.sync_plan_panel(&update.plan);
                 let key = self.near_time_key_current_req(event.order.as_ref());
                 let _ = self.history_insert_with_key_global(
                     Box::new(history_cell::new_plan_update(update)),
@@ -379,6 +401,9 @@ impl ChatWidget<'_> {
             EventMsg::PatchApplyEnd(ev) => {
                 self.handle_patch_apply_end_event(ev, event.event_seq);
             }
+            EventMsg::LspDiagnostics(ev) => {
+                self.handle_lsp_diagnostics_event(ev);
+            }
             EventMsg::ExecCommandEnd(ev) => {
                 self.handle_exec_command_end_event(ev, event.order.clone(), event.event_seq);
             }
@@ -34,8 +34,12 @@ impl ChatWidget<'_> {
         let key = self.near_time_key_current_req(order);
         let mut lines: Vec<String> = Vec::new();
         let is_mcp_access_prompt = ev.call_id.starts_with("mcp_access:");
+        // Auto Drive must not raise a spending limit on the user's behalf.
+        let is_cost_budget_prompt = ev.call_id.starts_with("session_cost_budget:");
         if is_mcp_access_prompt {
             lines.push("Permission requested: MCP access".to_string());
+        } else if is_cost_budget_prompt {
+            lines.push("Session cost budget reached".to_string());
         } else {
             lines.push("Model requested user input".to_string());
         }
@@ -54,8 +58,10 @@ impl ChatWidget<'_> {
                 }
             }
         }
-        let auto_answer =
-            !is_mcp_access_prompt && self.auto_state.is_active() && !self.auto_state.is_paused_manual();
+        let auto_answer = !is_mcp_access_prompt
+            && !is_cost_budget_prompt
+            && self.auto_state.is_active()
+            && !self.auto_state.is_paused_manual();
         if auto_answer {
             lines.push("\nAuto Drive is active; continuing automatically.".to_string());
         } else if is_mcp_access_prompt {
@@ -167,7 +167,7 @@ impl ChatWidget<'_> {
         }
         self.bottom_pane
             .update_status_text("waiting for command".to_string());
-        self.invalidate_height_cache();
+        self.invalidate_history_offsets();
         self.request_redraw();
         true
     }
@@ -193,7 +193,7 @@ impl ChatWidget<'_> {
             .insert(ToolCallId(call_id.to_string()), exec_call_id);
         self.bottom_pane
             .update_status_text("cancelling command".to_string());
-        self.invalidate_height_cache();
+        self.invalidate_history_offsets();
         self.request_redraw();
         true
     }
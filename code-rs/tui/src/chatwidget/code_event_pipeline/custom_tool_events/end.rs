@@ -78,6 +78,8 @@ impl ChatWidget<'_> {
             || self.try_handle_wait_cancelled_end(ctx)
             || self.try_handle_kill_end(ctx)
             || self.try_handle_fetch_end(ctx)
+            || self.try_handle_save_artifact_end(ctx)
+            || self.try_handle_run_tests_end(ctx)
     }
 
     fn resolve_image_view_path_for_custom_tool_end(
@@ -220,13 +222,13 @@ impl ChatWidget<'_> {
             self.bottom_pane.update_status_text("kill failed".to_string());
         }
         self.maybe_hide_spinner();
-        self.invalidate_height_cache();
+        self.invalidate_history_offsets();
         self.request_redraw();
         true
     }
 
     fn try_handle_fetch_end(&mut self, ctx: &CustomToolEndContext) -> bool {
-        if ctx.tool_name != "web_fetch" && ctx.tool_name != "browser_fetch" {
+        if !matches!(ctx.tool_name.as_str(), "web_fetch" | "browser_fetch" | "fetch_url") {
             return false;
         }
         let completed = history_cell::new_completed_web_fetch_tool_call(
@@ -249,6 +251,58 @@ impl ChatWidget<'_> {
         true
     }
 
+    fn try_handle_save_artifact_end(&mut self, ctx: &CustomToolEndContext) -> bool {
+        if ctx.tool_name != "save_artifact" || !ctx.success {
+            return false;
+        }
+        let Ok(saved) = serde_json::from_str::<serde_json::Value>(&ctx.content) else {
+            return false;
+        };
+        let Some(dir) = saved.get("dir").and_then(serde_json::Value::as_str) else {
+            return false;
+        };
+        let dir = std::path::PathBuf::from(dir);
+        let name = saved
+            .get("name")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string);
+        let artifacts = code_core::artifacts::list_artifacts(&dir);
+        let cell = history_cell::ArtifactsCell::new(dir, artifacts, name);
+        if let Some(idx) = ctx.resolved_idx {
+            self.history_replace_at(idx, Box::new(cell));
+        } else {
+            running_tools::collapse_spinner(self, &ctx.call_id);
+            let _ = self.history_insert_with_key_global(Box::new(cell), ctx.order_key);
+        }
+
+        self.bottom_pane.update_status_text("responding".to_string());
+        self.maybe_hide_spinner();
+        true
+    }
+
+    fn try_handle_run_tests_end(&mut self, ctx: &CustomToolEndContext) -> bool {
+        if ctx.tool_name != "run_tests" {
+            return false;
+        }
+        // Errors before the run (bad arguments, no runner, declined) are not
+        // reports; the generic cell shows them.
+        let Ok(report) = serde_json::from_str::<code_core::test_runner::TestReport>(&ctx.content) else {
+            return false;
+        };
+        self.record_test_report(&report);
+        let cell = history_cell::TestResultsCell::new(report);
+        if let Some(idx) = ctx.resolved_idx {
+            self.history_replace_at(idx, Box::new(cell));
+        } else {
+            running_tools::collapse_spinner(self, &ctx.call_id);
+            let _ = self.history_insert_with_key_global(Box::new(cell), ctx.order_key);
+        }
+
+        self.bottom_pane.update_status_text("responding".to_string());
+        self.maybe_hide_spinner();
+        true
+    }
+
     fn handle_generic_custom_tool_end(&mut self, ctx: CustomToolEndContext) {
         let CustomToolEndContext {
             call_id,
@@ -515,7 +569,7 @@ impl ChatWidget<'_> {
         if state.exec_completed {
             self.bottom_pane.update_status_text("responding".to_string());
             self.maybe_hide_spinner();
-            self.invalidate_height_cache();
+            self.invalidate_history_offsets();
             self.request_redraw();
             return;
         }
@@ -541,7 +595,7 @@ impl ChatWidget<'_> {
             self.bottom_pane
                 .update_status_text("waiting for command".to_string());
         }
-        self.invalidate_height_cache();
+        self.invalidate_history_offsets();
         self.request_redraw();
     }
 }
@@ -11,6 +11,7 @@ impl ChatWidget<'_> {
         order: Option<OrderMeta>,
         seq: u64,
     ) {
+        self.check_external_edits();
         let om_begin = order.unwrap_or_else(|| {
             tracing::warn!("missing OrderMeta for ExecCommandBegin; using synthetic order");
             code_core::protocol::OrderMeta {
@@ -29,6 +30,7 @@ impl ChatWidget<'_> {
         let call_id = ExecCallId(ev.call_id.clone());
         if self.exec.running_commands.contains_key(&call_id) {
             self.ensure_spinner_for_activity("exec-output");
+            self.exec.output_spill.append(&ev.call_id, &ev.chunk);
         }
         if let Some(running) = self.exec.running_commands.get_mut(&call_id) {
             let chunk = String::from_utf8_lossy(&ev.chunk).to_string();
@@ -88,7 +90,7 @@ impl ChatWidget<'_> {
                     self.update_cell_from_record(id, HistoryRecord::Exec(exec_record));
                 }
             }
-            self.invalidate_height_cache();
+            self.invalidate_history_offsets();
             self.autoscroll_if_near_bottom();
             self.request_redraw();
         }
@@ -104,6 +106,8 @@ impl ChatWidget<'_> {
             auto_approved,
             changes,
         } = event;
+        self.check_external_edits();
+        self.note_patch_apply_begin(&call_id, &changes);
         let exec_call_id = ExecCallId(call_id);
         self.exec.suppress_exec_end(exec_call_id);
         // Store for session diff popup (clone before moving into history)
@@ -168,6 +172,8 @@ impl ChatWidget<'_> {
         order: Option<OrderMeta>,
         seq: u64,
     ) {
+        self.note_rerun_exec_end(&ev);
+        self.resync_session_file_contents();
         let ev2 = ev.clone();
         let order_meta_end = order.unwrap_or_else(|| {
             tracing::warn!("missing OrderMeta for ExecCommandEnd; using synthetic order");
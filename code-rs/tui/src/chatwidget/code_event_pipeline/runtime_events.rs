@@ -120,6 +120,7 @@ impl ChatWidget<'_> {
 
         self.update_agents_terminal_state(&agents, context.clone(), task.clone());
         self.observe_auto_review_status(&agents);
+        self.observe_spawn_status(&agents);
 
         let agent_hint_label = if has_running_auto_review && !has_running_non_auto_review {
             AgentHintLabel::Review
@@ -250,14 +251,12 @@ impl ChatWidget<'_> {
 
         if let Some(key) = update.session_key.as_ref() {
             self.browser_overlay_state.set_session_key(Some(key.clone()));
-            if let Some(tracker) = self.tools_state.browser_sessions.get(key) {
-                let len = tracker.cell.screenshot_history().len();
-                if len > 0 {
-                    let last_index = len.saturating_sub(1);
-                    let current_index = self.browser_overlay_state.screenshot_index();
-                    if !self.browser_overlay_visible || current_index >= last_index {
-                        self.browser_overlay_state.set_screenshot_index(last_index);
-                    }
+            let len = self.browser_overlay_timeline().len();
+            if len > 0 {
+                let last_index = len.saturating_sub(1);
+                let current_index = self.browser_overlay_state.screenshot_index();
+                if !self.browser_overlay_visible || current_index >= last_index {
+                    self.browser_overlay_state.set_screenshot_index(last_index);
                 }
             }
         }
@@ -326,11 +325,25 @@ impl ChatWidget<'_> {
         self.review_guard = None;
         let hint = self.active_review_hint.take();
         let prompt = self.active_review_prompt.take();
+        let review_root = review_event
+            .snapshot
+            .as_ref()
+            .and_then(|snapshot| snapshot.worktree_path.clone());
         match review_event.review_output {
             Some(output) => {
-                let summary_cell =
-                    self.build_review_summary_cell(hint.as_deref(), prompt.as_deref(), &output);
-                self.history_push(summary_cell);
+                self.record_review_findings(&output, review_root);
+                let sections = code_core::review_passes::split_review_passes(&output);
+                if sections.len() > 1 {
+                    self.history_push(history_cell::ReviewPassesCell::new(
+                        hint.as_deref(),
+                        &output,
+                        sections,
+                    ));
+                } else {
+                    let summary_cell =
+                        self.build_review_summary_cell(hint.as_deref(), prompt.as_deref(), &output);
+                    self.history_push(summary_cell);
+                }
                 let finish_banner = match hint.as_deref() {
                     Some(h) if !h.trim().is_empty() => {
                         let trimmed = h.trim();
@@ -339,6 +352,12 @@ impl ChatWidget<'_> {
                     _ => "<< Code review finished >>".to_string(),
                 };
                 self.push_background_tail(finish_banner);
+                if !self.review_findings.entries.is_empty()
+                    && !self.auto_resolve_enabled()
+                    && !self.auto_state.is_active()
+                {
+                    self.show_review_findings();
+                }
             }
             None => {
                 let banner = match hint.as_deref() {
@@ -29,6 +29,7 @@ impl ChatWidget<'_> {
         // after every tool call.
         self.turn_sequence = self.turn_sequence.saturating_add(1);
         self.turn_had_code_edits = false;
+        self.lsp.introduced.clear();
         self.current_turn_origin = self.pending_turn_origin.take();
         self.cleared_lingering_execs_this_turn = false;
         self.ensure_lingering_execs_cleared();
@@ -53,6 +54,7 @@ impl ChatWidget<'_> {
         self.bottom_pane.clear_ctrl_c_quit_hint();
         // Accept streaming again for this turn.
         self.stream_state.drop_streaming = false;
+        self.note_turn_started();
         // Mark this task id as active and ensure the status stays visible.
         self.active_task_ids.insert(id.clone());
         // Reset per-turn UI indicators; ordering is now global-only.
@@ -61,6 +63,9 @@ impl ChatWidget<'_> {
         self.bottom_pane
             .update_status_text("waiting for model".to_string());
         self.ensure_spinner_for_activity("task-started");
+        if self.config.tui.termux_wake_lock && crate::termux_api::acquire_wake_lock() {
+            self.bottom_pane.set_wake_lock_held(true);
+        }
         tracing::info!("[order] EventMsg::TaskStarted id={}", id);
 
         // Capture a baseline snapshot for this turn so background auto review only
@@ -104,10 +109,16 @@ impl ChatWidget<'_> {
         if self.auto_resolve_enabled() {
             self.auto_resolve_on_task_complete(last_agent_message.clone());
         }
+        if self.active_task_ids.is_empty() {
+            self.check_off_review_finding_fix();
+        }
         // Defensive: mark any lingering agent state as complete so the spinner can quiesce.
         self.finalize_agent_activity();
         // Convert any lingering running exec/tool cells to completed so the UI doesn't hang.
         self.finalize_all_running_due_to_answer();
+        if self.active_task_ids.is_empty() {
+            self.note_turn_finished();
+        }
         // Mark any running web searches as completed.
         web_search_sessions::finalize_all_failed(self, "Search cancelled before completion");
         if had_running_execs {
@@ -136,7 +147,17 @@ impl ChatWidget<'_> {
         self.stream_state.current_kind = None;
         // Final re-check for idle state.
         self.maybe_hide_spinner();
+        if self.active_task_ids.is_empty() {
+            self.maybe_start_lsp_fixup();
+        }
         self.maybe_trigger_auto_review();
+        self.emit_feedback(self.config.tui.feedback.turn_complete);
+        if self.active_task_ids.is_empty() {
+            self.record_retried_answer();
+        }
+        if self.active_task_ids.is_empty() && std::mem::take(&mut self.structured_turn_pending) {
+            self.show_structured_answer(last_agent_message.as_deref());
+        }
         self.emit_turn_complete_notification(last_agent_message);
         self.suppress_next_agent_hint = false;
         self.mark_needs_redraw();
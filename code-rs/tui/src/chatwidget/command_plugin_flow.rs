@@ -0,0 +1,84 @@
+use super::*;
+
+use code_core::command_plugins::CommandPluginOutput;
+
+impl ChatWidget<'_> {
+    /// Runs `/name args` as a command plugin from `code_home/commands` when
+    /// `name` is not a built-in or a saved subagent. Returns `true` when the
+    /// message was consumed; the plugin's stdout arrives later through
+    /// `AppEvent::CommandPluginFinished`.
+    pub(super) fn try_run_command_plugin(&mut self, text: &str) -> bool {
+        let Some(rest) = text.trim_start().strip_prefix('/') else {
+            return false;
+        };
+        let (name, args) = match rest.split_once(char::is_whitespace) {
+            Some((name, args)) => (name, args.trim()),
+            None => (rest.trim_end(), ""),
+        };
+        if name.is_empty()
+            || SlashCommand::from_str(name).is_ok()
+            || self
+                .config
+                .subagent_commands
+                .iter()
+                .any(|command| command.name.eq_ignore_ascii_case(name))
+        {
+            return false;
+        }
+        let Some(dir) = code_core::command_plugins::default_commands_dir() else {
+            return false;
+        };
+        let Some(plugin) = code_core::command_plugins::discover_command_plugins(&dir)
+            .into_iter()
+            .find(|plugin| plugin.name == name)
+        else {
+            return false;
+        };
+
+        self.push_background_tail(format!("Running /{name}…"));
+        let tx = self.app_event_tx.clone();
+        let args = args.to_string();
+        let cwd = self.config.cwd.clone();
+        tokio::spawn(async move {
+            let result = code_core::command_plugins::run_command_plugin(&plugin, &args, &cwd).await;
+            tx.send(AppEvent::CommandPluginFinished {
+                name: plugin.name,
+                output: plugin.output,
+                result,
+            });
+        });
+        true
+    }
+
+    /// Delivers a finished command plugin's stdout as the user's message or
+    /// into the composer, depending on its manifest.
+    pub(crate) fn handle_command_plugin_finished(
+        &mut self,
+        name: String,
+        output: CommandPluginOutput,
+        result: Result<String, String>,
+    ) {
+        let stdout = match result {
+            Ok(stdout) => stdout,
+            Err(err) => {
+                self.history_push_plain_state(history_cell::new_error_event(format!(
+                    "`/{name}` — {err}"
+                )));
+                self.request_redraw();
+                return;
+            }
+        };
+        if stdout.trim().is_empty() {
+            self.push_background_tail(format!("`/{name}` finished with no output."));
+            return;
+        }
+        // Output that is itself a slash command goes to the composer so a
+        // plugin can never loop by invoking itself.
+        if output == CommandPluginOutput::Composer || stdout.trim_start().starts_with('/') {
+            self.bottom_pane.set_composer_text(stdout);
+            self.request_redraw();
+        } else {
+            self.submit_text_message(stdout);
+        }
+    }
+}
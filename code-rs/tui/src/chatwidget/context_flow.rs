@@ -1,6 +1,170 @@
 use super::*;
 
+/// A `/context` subcommand.
+#[derive(Debug, PartialEq)]
+enum ContextCommand {
+    List,
+    Pin { key: String, value: String },
+    Unpin(String),
+    Edit(String),
+    Clear,
+    History,
+}
+
+fn parse_context_command(args: &str) -> Result<ContextCommand, String> {
+    let args = args.trim();
+    let (action, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+    let rest = rest.trim();
+    match action {
+        "" | "list" | "pins" => Ok(ContextCommand::List),
+        "pin" => {
+            let Some((key, value)) = rest.split_once('=') else {
+                return Err("use `/context pin key=value`".to_string());
+            };
+            let (key, value) = (key.trim(), value.trim());
+            if key.is_empty() || key.contains(char::is_whitespace) {
+                return Err("the key must be a single word, e.g. `db=postgres15`".to_string());
+            }
+            if value.is_empty() {
+                return Err(format!("`{key}` needs a value; `/context unpin {key}` removes it"));
+            }
+            Ok(ContextCommand::Pin {
+                key: key.to_string(),
+                value: value.to_string(),
+            })
+        }
+        "unpin" | "edit" if rest.is_empty() => Err(format!("`/context {action}` needs a key")),
+        "unpin" => Ok(ContextCommand::Unpin(rest.to_string())),
+        "edit" => Ok(ContextCommand::Edit(rest.to_string())),
+        "clear" => Ok(ContextCommand::Clear),
+        "history" | "timeline" => Ok(ContextCommand::History),
+        other => Err(format!("unknown action `{other}`")),
+    }
+}
+
+/// `#seq  HH:MM:SS  what` for one timeline entry.
+fn format_timeline_entry(entry: &ContextTimelineEntry) -> String {
+    let sequence = entry
+        .sequence
+        .map_or_else(|| "   —".to_string(), |seq| format!("#{seq:<3}"));
+    let at = entry.at.format("%H:%M:%S");
+    let what = match &entry.change {
+        ContextTimelineChange::Baseline(facts) => {
+            let facts: Vec<String> = facts.iter().map(|(name, value)| format!("{name} {value}")).collect();
+            format!("baseline: {}", facts.join(", "))
+        }
+        ContextTimelineChange::Delta(delta) => {
+            let field = history_cell::context_delta_label(&delta.field);
+            let previous = delta.previous.as_deref().unwrap_or("—");
+            let current = delta.current.as_deref().unwrap_or("—");
+            format!("{field}: {previous} → {current}")
+        }
+    };
+    format!("{sequence}  {at}  {what}")
+}
+
 impl ChatWidget<'_> {
+    /// `/context pin key=value` pins a fact the model sees with every request;
+    /// `unpin`, `edit` and `clear` change them and `/context` lists them.
+    /// `/context history` lists every context change of the session.
+    pub(crate) fn handle_context_command(&mut self, args: String) {
+        let command = match parse_context_command(&args) {
+            Ok(command) => command,
+            Err(err) => {
+                self.history_push_plain_state(history_cell::new_error_event(format!(
+                    "`/context` — {err}. Usage: `/context pin key=value`, `/context unpin key`, `/context edit key`, `/context clear`, `/context history`."
+                )));
+                self.request_redraw();
+                return;
+            }
+        };
+        let mut summary = self.context_summary.clone().unwrap_or_default();
+        let message = match command {
+            ContextCommand::List => {
+                let message = if summary.pinned.is_empty() {
+                    "No pinned facts. `/context pin key=value` adds one.".to_string()
+                } else {
+                    let facts: Vec<String> = summary
+                        .pinned
+                        .iter()
+                        .map(|(key, value)| format!("{key}={value}"))
+                        .collect();
+                    format!("Pinned facts: {}", facts.join(", "))
+                };
+                self.push_background_tail(message);
+                self.request_redraw();
+                return;
+            }
+            ContextCommand::History => {
+                self.show_context_timeline();
+                self.request_redraw();
+                return;
+            }
+            ContextCommand::Edit(key) => {
+                match summary.pinned.get(&key) {
+                    Some(value) => self
+                        .bottom_pane
+                        .set_composer_text(format!("/context pin {key}={value}")),
+                    None => self.push_background_tail(format!("No pinned fact named `{key}`.")),
+                }
+                self.request_redraw();
+                return;
+            }
+            ContextCommand::Pin { key, value } => match summary.pinned.insert(key.clone(), value.clone()) {
+                Some(previous) if previous == value => format!("`{key}` is already pinned as {value}."),
+                Some(previous) => format!("Updated pinned `{key}`: {previous} → {value}."),
+                None => format!("Pinned `{key}` = {value}; the model sees it with every request."),
+            },
+            ContextCommand::Unpin(key) => match summary.pinned.remove(&key) {
+                Some(_) => format!("Unpinned `{key}`."),
+                None => format!("No pinned fact named `{key}`."),
+            },
+            ContextCommand::Clear => {
+                let count = summary.pinned.len();
+                summary.pinned.clear();
+                format!("Cleared {count} pinned fact{}.", if count == 1 { "" } else { "s" })
+            }
+        };
+        self.submit_op(Op::SetPinnedFacts {
+            facts: summary.pinned.clone(),
+        });
+        self.context_summary = Some(summary.clone());
+        if self.context_ui_enabled() {
+            self.update_context_cell(summary);
+        }
+        self.push_background_tail(message);
+        self.request_redraw();
+    }
+
+    /// Lists every context baseline and change of the session with its
+    /// time and sequence number.
+    fn show_context_timeline(&mut self) {
+        if self.context_timeline.is_empty() {
+            let message = if self.context_ui_enabled() {
+                "No context changes recorded yet."
+            } else {
+                "The context timeline is recorded when the context UI is on (`CTX_UI=1`)."
+            };
+            self.push_background_tail(message.to_string());
+            return;
+        }
+        let count = self.context_timeline.len();
+        let mut lines = vec![format!(
+            "Context history ({count} entr{})",
+            if count == 1 { "y" } else { "ies" }
+        )];
+        lines.extend(self.context_timeline.iter().map(format_timeline_entry));
+        self.history_push_plain_paragraphs(PlainMessageKind::Notice, lines);
+    }
+
+    fn record_context_timeline(&mut self, sequence: Option<u64>, change: ContextTimelineChange) {
+        self.context_timeline.push(ContextTimelineEntry {
+            at: chrono::Local::now(),
+            sequence,
+            change,
+        });
+    }
+
     pub(super) fn context_ui_enabled(&self) -> bool {
         self.config.env_ctx_v2
     }
@@ -23,13 +187,30 @@ impl ChatWidget<'_> {
 
         if let Some(prev) = previous.as_ref() {
             summary.expanded = prev.expanded;
+            summary.pinned = prev.pinned.clone();
         }
 
         if is_baseline {
             summary.deltas.clear();
+            let mut facts = Vec::new();
+            let fields = [
+                ("cwd", &summary.cwd),
+                ("branch", &summary.git_branch),
+                ("reasoning", &summary.reasoning_effort),
+            ];
+            for (name, value) in fields {
+                if let Some(value) = value {
+                    facts.push((name.to_string(), value.clone()));
+                }
+            }
+            facts.extend(summary.providers.iter().map(|(name, value)| (name.clone(), value.clone())));
+            self.record_context_timeline(sequence, ContextTimelineChange::Baseline(facts));
         } else if let Some(prev) = previous.as_ref() {
             summary.deltas = prev.deltas.clone();
             for delta in Self::compute_context_deltas(prev, &summary, sequence) {
+                if delta.previous != delta.current {
+                    self.record_context_timeline(sequence, ContextTimelineChange::Delta(delta.clone()));
+                }
                 Self::push_context_delta(&mut summary.deltas, delta);
             }
         }
@@ -48,6 +229,19 @@ impl ChatWidget<'_> {
         self.update_context_cell(summary);
     }
 
+    /// Shows a working-directory change right away rather than after the
+    /// next turn's environment delta.
+    pub(super) fn set_context_cwd(&mut self, cwd: &Path) {
+        if !self.context_ui_enabled() {
+            return;
+        }
+        let Some(mut summary) = self.context_summary.clone() else {
+            return;
+        };
+        summary.cwd = Some(cwd.display().to_string());
+        self.set_context_summary(summary, None, false);
+    }
+
     pub(super) fn strict_stream_ids_enabled(&self) -> bool {
         self.config.env_ctx_v2 && (self.test_mode || cfg!(debug_assertions))
     }
@@ -106,6 +300,8 @@ impl ChatWidget<'_> {
             deltas: summary.deltas.clone(),
             browser_snapshot: summary.browser_snapshot.clone(),
             expanded: summary.expanded,
+            pinned: summary.pinned.clone(),
+            providers: summary.providers.clone(),
         };
 
         if let Some(id) = self.context_cell_id
@@ -182,6 +378,24 @@ impl ChatWidget<'_> {
             });
         }
 
+        let names: std::collections::BTreeSet<&String> = previous
+            .providers
+            .keys()
+            .chain(current.providers.keys())
+            .collect();
+        for name in names {
+            let before = previous.providers.get(name);
+            let after = current.providers.get(name);
+            if before != after {
+                deltas.push(ContextDeltaRecord {
+                    field: ContextDeltaField::Environment(name.clone()),
+                    previous: before.cloned(),
+                    current: after.cloned(),
+                    sequence,
+                });
+            }
+        }
+
         if previous.browser_snapshot != current.browser_snapshot {
             let prev_label = previous
                 .browser_snapshot
@@ -228,10 +442,17 @@ impl ChatWidget<'_> {
     }
 
     pub(super) fn context_snapshot_label(snapshot: &ContextBrowserSnapshotRecord) -> Option<String> {
-        if let Some(title) = snapshot.title.as_ref().filter(|s| !s.is_empty()) {
+        let label = if let Some(title) = snapshot.title.as_ref().filter(|s| !s.is_empty()) {
             Some(title.clone())
         } else {
             snapshot.url.clone()
+        };
+        // With several tabs open, say which one the snapshot shows.
+        match snapshot.tabs.iter().find(|tab| tab.active) {
+            Some(active) => label.map(|label| {
+                format!("{label} (tab {}/{})", active.index, snapshot.tabs.len())
+            }),
+            None => label,
         }
     }
 
@@ -254,6 +475,9 @@ impl ChatWidget<'_> {
             if let Some(reason) = obj.get("reasoning_effort").and_then(|v| v.as_str()) {
                 summary.reasoning_effort = Some(reason.to_string());
             }
+            if let Some(providers) = obj.get("providers") {
+                summary.providers = Self::value_to_string_map(providers);
+            }
         }
 
         summary.browser_session_active = false;
@@ -281,6 +505,15 @@ impl ChatWidget<'_> {
     }
 
     pub(super) fn handle_browser_snapshot_event(&mut self, payload: &BrowserSnapshotEvent) {
+        if let Some(path) = payload.screenshot_path.as_ref() {
+            let record = Self::browser_snapshot_from_event(payload);
+            self.record_browser_snapshot(BrowserSnapshotEntry {
+                path: path.clone(),
+                url: record.url,
+                title: record.title,
+                captured_at: record.captured_at,
+            });
+        }
         if !self.context_ui_enabled() {
             return;
         }
@@ -306,6 +539,24 @@ impl ChatWidget<'_> {
         if let Some(value) = changes.get("reasoning_effort") {
             summary.reasoning_effort = Self::value_to_optional_string(value);
         }
+        if let Some(value) = changes.get("providers") {
+            summary.providers = Self::value_to_string_map(value);
+        }
+    }
+
+    fn value_to_string_map(
+        value: &serde_json::Value,
+    ) -> std::collections::BTreeMap<String, String> {
+        value
+            .as_object()
+            .map(|map| {
+                map.iter()
+                    .filter_map(|(key, value)| {
+                        Self::value_to_optional_string(value).map(|value| (key.clone(), value))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
     }
 
     pub(super) fn value_to_optional_string(value: &serde_json::Value) -> Option<String> {
@@ -354,6 +605,12 @@ impl ChatWidget<'_> {
                     record.metadata = map;
                 }
             }
+            if let Some(tabs) = obj.get("tabs").and_then(|v| v.as_array()) {
+                record.tabs = tabs
+                    .iter()
+                    .filter_map(|tab| serde_json::from_value(tab.clone()).ok())
+                    .collect();
+            }
         }
 
         if record.url.is_none() {
@@ -367,3 +624,61 @@ impl ChatWidget<'_> {
         record
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn timeline_entries_show_sequence_time_and_change() {
+        let at = chrono::Local
+            .with_ymd_and_hms(2025, 3, 4, 9, 5, 7)
+            .single()
+            .expect("valid time");
+        let baseline = ContextTimelineEntry {
+            at,
+            sequence: Some(1),
+            change: ContextTimelineChange::Baseline(vec![
+                ("cwd".to_string(), "/repo".to_string()),
+                ("branch".to_string(), "main".to_string()),
+            ]),
+        };
+        assert_eq!(format_timeline_entry(&baseline), "#1    09:05:07  baseline: cwd /repo, branch main");
+        let delta = ContextTimelineEntry {
+            at,
+            sequence: None,
+            change: ContextTimelineChange::Delta(ContextDeltaRecord {
+                field: ContextDeltaField::BrowserSnapshot,
+                previous: None,
+                current: Some("Docs".to_string()),
+                sequence: None,
+            }),
+        };
+        assert_eq!(format_timeline_entry(&delta), "   —  09:05:07  browser: — → Docs");
+        assert_eq!(parse_context_command("history"), Ok(ContextCommand::History));
+    }
+
+    #[test]
+    fn parses_pin_commands() {
+        assert_eq!(
+            parse_context_command("pin db = postgres15"),
+            Ok(ContextCommand::Pin {
+                key: "db".to_string(),
+                value: "postgres15".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_context_command("pin url=https://x.test/?a=b"),
+            Ok(ContextCommand::Pin {
+                key: "url".to_string(),
+                value: "https://x.test/?a=b".to_string(),
+            })
+        );
+        assert_eq!(parse_context_command(""), Ok(ContextCommand::List));
+        assert_eq!(parse_context_command("unpin db"), Ok(ContextCommand::Unpin("db".to_string())));
+        assert!(parse_context_command("pin db").is_err());
+        assert!(parse_context_command("pin my db=x").is_err());
+        assert!(parse_context_command("edit").is_err());
+    }
+}
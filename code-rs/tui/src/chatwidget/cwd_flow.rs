@@ -0,0 +1,155 @@
+use super::*;
+
+use crate::bottom_pane::SelectionAction;
+
+/// Resolves the `/cd` argument against `cwd`: `~` expands to `home`, and the
+/// result must be an existing directory.
+pub(super) fn resolve_cd_target(
+    arg: &str,
+    cwd: &Path,
+    home: Option<&Path>,
+) -> Result<PathBuf, String> {
+    let parts = shlex::split(arg).unwrap_or_default();
+    let [raw] = parts.as_slice() else {
+        return Err("expected one directory (quote paths with spaces)".to_string());
+    };
+    let path = match raw.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+            let home = home.ok_or_else(|| "HOME is not set".to_string())?;
+            home.join(rest.trim_start_matches('/'))
+        }
+        _ => cwd.join(raw),
+    };
+    let resolved = path
+        .canonicalize()
+        .map_err(|err| format!("cannot open {}: {err}", path.display()))?;
+    if !resolved.is_dir() {
+        return Err(format!("{} is not a directory", resolved.display()));
+    }
+    Ok(resolved)
+}
+
+impl ChatWidget<'_> {
+    /// `/cd <path>` moves the session to another directory without a
+    /// restart; `/cd -` goes back to the previous one and `/cd` alone shows
+    /// where the session is. Leaving the repository (or directory) the
+    /// session started in asks first.
+    pub(crate) fn handle_cd_command(&mut self, args: String) {
+        let args = args.trim();
+        if args.is_empty() {
+            self.push_background_tail(format!(
+                "Working directory: {}\nSession started in: {}",
+                self.config.cwd.display(),
+                self.session_root.display()
+            ));
+            self.request_redraw();
+            return;
+        }
+        if self.is_task_running() {
+            self.history_push_plain_state(history_cell::new_error_event(
+                "`/cd` — wait for the current turn to finish (or press Esc) before changing directory.".to_string(),
+            ));
+            self.request_redraw();
+            return;
+        }
+
+        let target = if args == "-" {
+            last_existing_cwd(&self.config.cwd)
+                .ok_or_else(|| "there is no previous directory".to_string())
+        } else {
+            let home = std::env::var_os("HOME").map(PathBuf::from);
+            resolve_cd_target(args, &self.config.cwd, home.as_deref())
+        };
+        let target = match target {
+            Ok(target) => target,
+            Err(err) => {
+                self.history_push_plain_state(history_cell::new_error_event(format!(
+                    "`/cd` — {err}."
+                )));
+                self.request_redraw();
+                return;
+            }
+        };
+        if target == self.config.cwd {
+            self.push_background_tail(format!("Already in {}.", target.display()));
+            self.request_redraw();
+            return;
+        }
+
+        let root = code_core::git_info::get_git_repo_root(&self.session_root)
+            .unwrap_or_else(|| self.session_root.clone());
+        let root = root.canonicalize().unwrap_or(root);
+        if target.starts_with(&root) {
+            self.app_event_tx.send(AppEvent::SwitchCwd(target, None));
+        } else {
+            self.confirm_cd_outside_root(target, &root);
+        }
+    }
+
+    fn confirm_cd_outside_root(&mut self, target: PathBuf, root: &Path) {
+        let switch: SelectionAction = {
+            let target = target.clone();
+            Box::new(move |tx: &crate::app_event_sender::AppEventSender| {
+                tx.send(AppEvent::SwitchCwd(target.clone(), None));
+            })
+        };
+        let items = vec![
+            SelectionItem {
+                name: format!("Switch to {}", target.display()),
+                description: Some(
+                    "Commands, file edits and @ mentions use this directory from now on".to_string(),
+                ),
+                is_current: true,
+                actions: vec![switch],
+            },
+            SelectionItem {
+                name: "Stay".to_string(),
+                description: Some(format!("Keep working in {}", self.config.cwd.display())),
+                is_current: false,
+                actions: Vec::new(),
+            },
+        ];
+        let view = ListSelectionView::new(
+            " Change directory ".to_string(),
+            Some(format!("{} is outside {}", target.display(), root.display())),
+            Some("Enter select · Esc stay".to_string()),
+            items,
+            self.app_event_tx.clone(),
+            2,
+        );
+        self.bottom_pane
+            .show_list_selection("Change directory".to_string(), None, None, view);
+        self.request_redraw();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cd_targets_resolve_against_the_session_directory() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let base = dir.path().canonicalize().expect("canonicalize");
+        std::fs::create_dir_all(base.join("repo/src")).expect("create dirs");
+        std::fs::create_dir_all(base.join("other repo")).expect("create dirs");
+        std::fs::write(base.join("repo/README.md"), "").expect("write file");
+        let cwd = base.join("repo");
+
+        assert_eq!(resolve_cd_target("src", &cwd, None), Ok(cwd.join("src")));
+        assert_eq!(
+            resolve_cd_target("'../other repo'", &cwd, None),
+            Ok(base.join("other repo"))
+        );
+        assert_eq!(
+            resolve_cd_target("~/repo/src", &cwd, Some(&base)),
+            Ok(cwd.join("src"))
+        );
+        assert_eq!(
+            resolve_cd_target("README.md", &cwd, None),
+            Err(format!("{} is not a directory", cwd.join("README.md").display()))
+        );
+        assert!(resolve_cd_target("missing", &cwd, None).is_err());
+        assert!(resolve_cd_target("a b", &cwd, None).is_err());
+    }
+}
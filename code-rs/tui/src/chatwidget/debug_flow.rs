@@ -0,0 +1,40 @@
+use super::*;
+
+impl ChatWidget<'_> {
+    /// `/debug last-request` opens the most recent model request and its
+    /// response from the wire log in `$PAGER`.
+    pub(crate) fn handle_debug_command(&mut self, args: String) {
+        match args.trim() {
+            "last-request" => self.open_last_wire_exchange(),
+            "" => self.push_background_tail(
+                "Usage: /debug last-request to page through the last model request and response."
+                    .to_string(),
+            ),
+            other => {
+                self.history_push_plain_state(crate::history_cell::new_error_event(format!(
+                    "`/debug` — unknown action `{other}`. Use `/debug last-request`."
+                )));
+            }
+        }
+        self.request_redraw();
+    }
+
+    fn open_last_wire_exchange(&mut self) {
+        if !self.config.wire_log.enabled {
+            self.history_push_plain_state(crate::history_cell::new_error_event(
+                "`/debug last-request` — the wire log is off. Set `[wire_log] enabled = true` in config.toml and start a new session."
+                    .to_string(),
+            ));
+            return;
+        }
+        match code_core::wire_log::write_last_exchange(&self.config.code_home) {
+            Ok(Some(path)) => self.app_event_tx.send(AppEvent::OpenOutputPager { path }),
+            Ok(None) => self.push_background_tail("No model request has been logged yet.".to_string()),
+            Err(err) => {
+                self.history_push_plain_state(crate::history_cell::new_error_event(format!(
+                    "`/debug last-request` — could not read the wire log: {err}"
+                )));
+            }
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use super::*;
+
+use code_core::config_types::ReasoningEffort;
+use code_core::debug_logger::DebugLogger;
+use code_core::git_session;
+use code_core::{ModelClient, Prompt, ResponseEvent};
+use code_protocol::models::{ContentItem, ResponseItem};
+use futures::StreamExt;
+
+/// Upper bound on how much staged diff is sent when asking for a commit message.
+const COMMIT_MESSAGE_DIFF_MAX_BYTES: usize = 60 * 1024;
+
+impl ChatWidget<'_> {
+    /// `/diff export [path]`: writes the accumulated session diff (baseline vs
+    /// current contents) as a unified `.patch` file.
+    pub(crate) fn handle_diff_export(&mut self, path_arg: &str) {
+        if self.diffs.baseline_file_contents.is_empty() {
+            self.history_push_plain_state(crate::history_cell::new_error_event(
+                "`/diff export` — no files were changed this session".to_string(),
+            ));
+            self.request_redraw();
+            return;
+        }
+
+        let root = code_core::git_info::get_git_repo_root(&self.config.cwd)
+            .unwrap_or_else(|| self.config.cwd.clone());
+        let files: Vec<(PathBuf, String)> = self
+            .diffs
+            .baseline_file_contents
+            .iter()
+            .map(|(path, baseline)| (path.clone(), baseline.clone()))
+            .collect();
+        let patch = git_session::session_diff_patch(&root, &files);
+        if patch.is_empty() {
+            self.push_background_tail(
+                "`/diff export` — session files match their baselines; nothing to export."
+                    .to_string(),
+            );
+            return;
+        }
+
+        let target = if path_arg.is_empty() {
+            let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+            root.join(format!("code-session-{stamp}.patch"))
+        } else {
+            self.config.cwd.join(path_arg)
+        };
+        match std::fs::write(&target, patch) {
+            Ok(()) => self.push_background_tail(format!(
+                "Wrote session diff to {}",
+                target.display()
+            )),
+            Err(err) => self.history_push_plain_state(crate::history_cell::new_error_event(
+                format!("`/diff export` — failed to write {}: {err}", target.display()),
+            )),
+        }
+        self.request_redraw();
+    }
+
+    /// `/diff commit`: stages only the files touched this session, then asks
+    /// the model for a commit message to prefill the confirmation prompt.
+    pub(crate) fn handle_diff_commit(&mut self) {
+        let Some(repo_root) = code_core::git_info::get_git_repo_root(&self.config.cwd) else {
+            self.history_push_plain_state(crate::history_cell::new_error_event(
+                "`/diff commit` — not inside a git repository".to_string(),
+            ));
+            self.request_redraw();
+            return;
+        };
+        let paths = self.session_touched_paths();
+        if paths.is_empty() {
+            self.history_push_plain_state(crate::history_cell::new_error_event(
+                "`/diff commit` — no files were changed this session".to_string(),
+            ));
+            self.request_redraw();
+            return;
+        }
+
+        self.push_background_tail(format!(
+            "Staging {} session file{} and drafting a commit message…",
+            paths.len(),
+            if paths.len() == 1 { "" } else { "s" }
+        ));
+        let tx = self.app_event_tx.clone();
+        let fallback_tx = tx.clone();
+        let config = self.config.clone();
+        if thread_spawner::spawn_lightweight("session-commit", move || {
+            let result = prepare_session_commit(&config, &repo_root, &paths);
+            match result {
+                Ok(message) => tx.send(AppEvent::SessionCommitReady { repo_root, message }),
+                Err(err) => tx.send(AppEvent::InsertBackgroundEvent {
+                    message: format!("`/diff commit` — {err}"),
+                    placement: crate::app_event::BackgroundPlacement::Tail,
+                    order: None,
+                }),
+            }
+        })
+        .is_none()
+        {
+            fallback_tx.send(AppEvent::InsertBackgroundEvent {
+                message: "`/diff commit` — background worker unavailable".to_string(),
+                placement: crate::app_event::BackgroundPlacement::Tail,
+                order: None,
+            });
+        }
+    }
+
+    pub(crate) fn show_session_commit_prompt(&mut self, repo_root: PathBuf, message: String) {
+        let submit_tx = self.app_event_tx.clone();
+        let on_submit: Box<dyn Fn(String) + Send + Sync> = Box::new(move |text: String| {
+            submit_tx.send(AppEvent::SubmitSessionCommit {
+                repo_root: repo_root.clone(),
+                message: text,
+            });
+        });
+        let view = CustomPromptView::new(
+            "Commit session changes".to_string(),
+            "Commit message".to_string(),
+            Some("Enter to commit · Esc cancel (files stay staged)".to_string()),
+            self.app_event_tx.clone(),
+            None,
+            on_submit,
+        )
+        .with_initial_text(message.trim());
+        self.bottom_pane.show_custom_prompt(view);
+        self.request_redraw();
+    }
+
+    /// Every path a patch touched this session, including rename sources and
+    /// destinations so moves are staged as a delete plus an add.
+    fn session_touched_paths(&self) -> Vec<PathBuf> {
+        let mut paths: BTreeSet<PathBuf> = BTreeSet::new();
+        for changes in &self.diffs.session_patch_sets {
+            for (path, change) in changes {
+                paths.insert(path.clone());
+                if let code_core::protocol::FileChange::Update {
+                    move_path: Some(dest),
+                    ..
+                } = change
+                {
+                    paths.insert(dest.clone());
+                }
+            }
+        }
+        paths.into_iter().collect()
+    }
+}
+
+/// Stages `paths` and returns a model-suggested commit message. Failing to
+/// reach the model is not fatal: the prompt simply opens empty.
+fn prepare_session_commit(
+    config: &Config,
+    repo_root: &Path,
+    paths: &[PathBuf],
+) -> anyhow::Result<String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    runtime.block_on(async {
+        git_session::stage_paths(repo_root, paths)
+            .await
+            .map_err(anyhow::Error::msg)?;
+        let diff = git_session::staged_diff(repo_root)
+            .await
+            .map_err(anyhow::Error::msg)?;
+        if diff.trim().is_empty() {
+            anyhow::bail!("nothing to commit; session files match HEAD");
+        }
+        match request_commit_message(config, &diff).await {
+            Ok(message) => Ok(message),
+            Err(err) => {
+                tracing::warn!("failed to draft commit message: {err}");
+                Ok(String::new())
+            }
+        }
+    })
+}
+
+async fn request_commit_message(config: &Config, diff: &str) -> anyhow::Result<String> {
+    let auth_mode = if config.using_chatgpt_auth {
+        AuthMode::ChatGPT
+    } else {
+        AuthMode::ApiKey
+    };
+    let auth_mgr = AuthManager::shared_with_mode_and_originator(
+        config.code_home.clone(),
+        auth_mode,
+        config.responses_originator_header.clone(),
+        config.cli_auth_credentials_store_mode,
+    );
+    let debug_logger = DebugLogger::new(config.debug).or_else(|_| DebugLogger::new(false))?;
+    let client = ModelClient::new(code_core::ModelClientInit {
+        config: Arc::new(config.clone()),
+        auth_manager: Some(auth_mgr),
+        otel_event_manager: None,
+        provider: config.model_provider.clone(),
+        effort: ReasoningEffort::Low,
+        summary: config.model_reasoning_summary,
+        verbosity: config.model_text_verbosity,
+        session_id: uuid::Uuid::new_v4(),
+        debug_logger: Arc::new(Mutex::new(debug_logger)),
+    });
+
+    let mut diff = diff.to_string();
+    if diff.len() > COMMIT_MESSAGE_DIFF_MAX_BYTES {
+        let mut cut = COMMIT_MESSAGE_DIFF_MAX_BYTES;
+        while !diff.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        diff.truncate(cut);
+        diff.push_str("\n[… diff truncated …]");
+    }
+    let mut prompt = Prompt::default();
+    prompt.store = false;
+    prompt.input.push(ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText {
+            text: format!(
+                "Write a git commit message for the staged diff below: an imperative subject \
+                 line under 72 characters, optionally followed by a blank line and a short body. \
+                 Reply with the message only.\n\n```diff\n{diff}\n```"
+            ),
+        }],
+        end_turn: None,
+        phase: None,
+    });
+    prompt.set_log_tag("tui/session_commit_message");
+
+    let mut stream = client.stream(&prompt).await?;
+    let mut streamed = String::new();
+    let mut finished = String::new();
+    while let Some(event) = stream.next().await {
+        match event? {
+            ResponseEvent::OutputTextDelta { delta, .. } => streamed.push_str(&delta),
+            ResponseEvent::OutputItemDone {
+                item: ResponseItem::Message { content, .. },
+                ..
+            } => {
+                for item in content {
+                    if let ContentItem::OutputText { text } = item {
+                        finished.push_str(&text);
+                    }
+                }
+            }
+            ResponseEvent::Completed { .. } => break,
+            _ => {}
+        }
+    }
+    let message = if finished.trim().is_empty() { streamed } else { finished };
+    Ok(message.trim().trim_matches('`').trim().to_string())
+}
@@ -11,6 +11,9 @@ pub(super) fn handle_diff_key(chat: &mut ChatWidget<'_>, key_event: KeyEvent) ->
     if handle_diff_confirm_key(chat, key_event.code) {
         return true;
     }
+    if handle_diff_search_key(chat, key_event.code) {
+        return true;
+    }
 
     match key_event.code {
         KeyCode::Left => {
@@ -37,6 +40,13 @@ pub(super) fn handle_diff_key(chat: &mut ChatWidget<'_>, key_event: KeyEvent) ->
             prompt_explain_selected_diff_block(chat);
             true
         }
+        KeyCode::Char('/') if chat.diffs.overlay.as_ref().is_some_and(|o| o.has_tree()) => {
+            if let Some(overlay) = chat.diffs.overlay.as_mut() {
+                overlay.searching = true;
+            }
+            chat.request_redraw();
+            true
+        }
         KeyCode::Esc => {
             chat.diffs.overlay = None;
             chat.diffs.confirm = None;
@@ -61,12 +71,55 @@ fn handle_diff_confirm_key(chat: &mut ChatWidget<'_>, code: KeyCode) -> bool {
     false
 }
 
+// While the file tree's `/` search is active, typed characters edit the
+// filter; Enter keeps it and Esc clears it.
+fn handle_diff_search_key(chat: &mut ChatWidget<'_>, code: KeyCode) -> bool {
+    let Some(overlay) = chat.diffs.overlay.as_mut() else {
+        return false;
+    };
+    if !overlay.searching {
+        return false;
+    }
+    match code {
+        KeyCode::Char(ch) => overlay.query.push(ch),
+        KeyCode::Backspace => {
+            overlay.query.pop();
+        }
+        KeyCode::Enter => overlay.searching = false,
+        KeyCode::Esc => {
+            overlay.query.clear();
+            overlay.searching = false;
+        }
+        // Let navigation keys through so results can be browsed mid-search.
+        _ => return false,
+    }
+    let order = overlay.tree_order();
+    if !order.contains(&overlay.selected)
+        && let Some(first) = order.first()
+    {
+        overlay.selected = *first;
+    }
+    chat.request_redraw();
+    true
+}
+
 fn move_selected_diff_tab(chat: &mut ChatWidget<'_>, right: bool) {
     let Some(overlay) = chat.diffs.overlay.as_mut() else {
         return;
     };
 
-    if right {
+    if overlay.has_tree() {
+        // Step through files in tree order, skipping ones hidden by the filter.
+        let order = overlay.tree_order();
+        let next = match order.iter().position(|tab| *tab == overlay.selected) {
+            Some(pos) if right => order.get(pos + 1),
+            Some(pos) => pos.checked_sub(1).and_then(|prev| order.get(prev)),
+            None => order.first(),
+        };
+        if let Some(next) = next {
+            overlay.selected = *next;
+        }
+    } else if right {
         if overlay.selected + 1 < overlay.tabs.len() {
             overlay.selected += 1;
         }
@@ -3,20 +3,122 @@
 //! Separated to keep `chatwidget.rs` smaller and focused on behavior.
 
 use ratatui::text::Line;
+use std::path::PathBuf;
 
 pub struct DiffOverlay {
     pub tabs: Vec<(String, Vec<DiffBlock>)>,
     pub selected: usize,
     pub scroll_offsets: Vec<u16>,
+    /// Per-tab file metadata; when present the viewer shows a file tree
+    /// instead of the tab strip.
+    pub files: Vec<DiffFileMeta>,
+    /// Tree filter (case-insensitive substring of the display path).
+    pub query: String,
+    /// True while `/` search input is capturing keys.
+    pub searching: bool,
 }
 
 impl DiffOverlay {
     pub fn new(tabs: Vec<(String, Vec<DiffBlock>)>) -> Self {
         let n = tabs.len();
-        Self { tabs, selected: 0, scroll_offsets: vec![0; n] }
+        Self {
+            tabs,
+            selected: 0,
+            scroll_offsets: vec![0; n],
+            files: Vec::new(),
+            query: String::new(),
+            searching: false,
+        }
+    }
+
+    /// Attaches file metadata (one entry per tab, same order).
+    pub fn with_files(mut self, files: Vec<DiffFileMeta>) -> Self {
+        if files.len() == self.tabs.len() {
+            self.files = files;
+        }
+        self
+    }
+
+    pub fn has_tree(&self) -> bool {
+        self.files.len() > 1
+    }
+
+    /// Directory headers and files grouped under them, filtered by `query`.
+    pub fn tree_rows(&self) -> Vec<DiffTreeRow> {
+        let query = self.query.to_lowercase();
+        let mut entries: Vec<(String, String, usize)> = self
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(_, file)| query.is_empty() || file.display.to_lowercase().contains(&query))
+            .map(|(tab, file)| {
+                let (dir, name) = match file.display.rsplit_once('/') {
+                    Some((dir, name)) => (format!("{dir}/"), name.to_string()),
+                    None => (String::new(), file.display.clone()),
+                };
+                (dir, name, tab)
+            })
+            .collect();
+        entries.sort();
+
+        let mut rows: Vec<DiffTreeRow> = Vec::new();
+        let mut current_dir: Option<String> = None;
+        for (dir, name, tab) in entries {
+            if current_dir.as_ref() != Some(&dir) {
+                if !dir.is_empty() {
+                    rows.push(DiffTreeRow::Dir(dir.clone()));
+                }
+                current_dir = Some(dir.clone());
+            }
+            rows.push(DiffTreeRow::File {
+                tab,
+                name,
+                nested: !dir.is_empty(),
+                status: self.files[tab].status,
+            });
+        }
+        rows
+    }
+
+    /// Tabs in tree order (respecting the filter); used for ◂ ▸ navigation.
+    pub fn tree_order(&self) -> Vec<usize> {
+        self.tree_rows()
+            .into_iter()
+            .filter_map(|row| match row {
+                DiffTreeRow::File { tab, .. } => Some(tab),
+                DiffTreeRow::Dir(_) => None,
+            })
+            .collect()
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffFileStatus {
+    /// The file on disk matches the latest patch that touched it.
+    Applied,
+    /// The latest patch for this file is still awaiting approval or apply.
+    Pending,
+}
+
+#[derive(Clone, Debug)]
+pub struct DiffFileMeta {
+    pub path: PathBuf,
+    /// Path relative to the session cwd, `/`-separated.
+    pub display: String,
+    pub status: DiffFileStatus,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiffTreeRow {
+    Dir(String),
+    File {
+        tab: usize,
+        name: String,
+        nested: bool,
+        status: DiffFileStatus,
+    },
+}
+
 #[derive(Clone)]
 pub struct DiffBlock {
     pub lines: Vec<Line<'static>>,
@@ -26,3 +128,37 @@ pub struct DiffConfirm {
     pub text_to_submit: String,
 }
 
+
+#[cfg(test)]
+mod diff_ui_tests {
+    use super::*;
+
+    fn overlay(paths: &[&str]) -> DiffOverlay {
+        let tabs = paths.iter().map(|p| (p.to_string(), Vec::new())).collect();
+        let files = paths
+            .iter()
+            .map(|p| DiffFileMeta {
+                path: PathBuf::from(p),
+                display: p.to_string(),
+                status: DiffFileStatus::Applied,
+            })
+            .collect();
+        DiffOverlay::new(tabs).with_files(files)
+    }
+
+    #[test]
+    fn tree_groups_files_by_directory() {
+        let overlay = overlay(&["src/b.rs", "README.md", "src/a.rs"]);
+        let rows = overlay.tree_rows();
+        assert!(matches!(&rows[0], DiffTreeRow::File { name, nested: false, .. } if name == "README.md"));
+        assert_eq!(rows[1], DiffTreeRow::Dir("src/".to_string()));
+        assert_eq!(overlay.tree_order(), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn query_filters_tree() {
+        let mut overlay = overlay(&["src/b.rs", "README.md", "src/a.rs"]);
+        overlay.query = "A.R".to_string();
+        assert_eq!(overlay.tree_order(), vec![2]);
+    }
+}
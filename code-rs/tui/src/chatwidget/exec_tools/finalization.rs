@@ -69,12 +69,13 @@ pub(in super::super) fn finalize_all_running_as_interrupted(chat: &mut ChatWidge
                     chat.autoscroll_if_near_bottom();
                     agg_was_updated = true;
                 }
+        chat.exec.output_spill.finish(call_id.as_ref());
         chat.canceled_exec_call_ids.insert(call_id.clone());
     }
     chat.exec.running_commands.clear();
     if agg_was_updated {
         chat.exec.running_explore_agg_index = None;
-        chat.invalidate_height_cache();
+        chat.invalidate_history_offsets();
         chat.request_redraw();
     }
 
@@ -134,7 +135,7 @@ pub(in super::super) fn finalize_all_running_as_interrupted(chat: &mut ChatWidge
                 }
         }
         chat.tools_state.running_custom_tools.clear();
-        chat.invalidate_height_cache();
+        chat.invalidate_history_offsets();
         chat.request_redraw();
     }
 
@@ -172,6 +173,7 @@ pub(in super::super) fn finalize_all_running_due_to_answer(chat: &mut ChatWidget
 
     for call_id in running_keys {
         if let Some(running) = chat.exec.running_commands.remove(&call_id) {
+            chat.exec.output_spill.finish(call_id.as_ref());
             // Update any explore aggregation entry tied to this exec.
             if let Some((agg_idx, entry_idx)) = running.explore_entry {
                 let updated = update_explore_entry_status(
@@ -214,6 +216,7 @@ pub(in super::super) fn finalize_all_running_due_to_answer(chat: &mut ChatWidget
                     wait_notes: wait_notes_record,
                     stdout_tail: stdout_tail_event,
                     stderr_tail: stderr_tail_event,
+                    redacted_values: 0,
                 },
             );
 
@@ -293,7 +296,7 @@ pub(in super::super) fn finalize_all_running_due_to_answer(chat: &mut ChatWidget
 
     if agg_was_updated {
         chat.exec.running_explore_agg_index = None;
-        chat.invalidate_height_cache();
+        chat.invalidate_history_offsets();
         chat.request_redraw();
     }
 
@@ -327,6 +330,7 @@ pub(in super::super) fn finalize_wait_missing_exec(
     let mut explore_entry: Option<(usize, usize)> = None;
 
     if let Some(running) = chat.exec.running_commands.remove(&call_id) {
+        chat.exec.output_spill.finish(call_id.as_ref());
         history_id = running
             .history_id
             .or_else(|| chat.history_state.history_id_for_exec_call(call_id.as_ref()))
@@ -402,6 +406,7 @@ pub(in super::super) fn finalize_wait_missing_exec(
             wait_notes: wait_notes_record,
             stdout_tail: None,
             stderr_tail: Some(fallback_message.to_string()),
+            redacted_values: 0,
         });
 
     let mut handled_via_state = false;
@@ -489,52 +494,23 @@ pub(in super::super) fn try_merge_completed_exec_at(chat: &mut ChatWidget<'_>, i
         return;
     };
 
-    if !exec_record_has_output(&current_exec) {
-        return;
-    }
-
-    if matches!(current_exec.action, ExecAction::Run) {
-        return;
-    }
-
     let Some(prev_record) = history_record_for_cell(chat, idx - 1) else {
         return;
     };
 
-    match prev_record {
-        HistoryRecord::Exec(prev_exec) => {
-            if prev_exec.action != current_exec.action {
-                return;
-            }
-            if !exec_record_has_output(&prev_exec) {
-                return;
-            }
+    let Some(merged) = chat
+        .history_state
+        .merge_policy()
+        .merge_exec(&prev_record, &current_exec)
+    else {
+        return;
+    };
 
-            let merged = history_cell::MergedExecCell::from_records(
-                prev_exec.id,
-                prev_exec.action,
-                vec![prev_exec, current_exec],
-            );
-            chat.history_replace_at(idx - 1, Box::new(merged));
-            chat.history_remove_at(idx);
-            chat.autoscroll_if_near_bottom();
-            chat.bottom_pane.set_has_chat_history(true);
-            chat.process_animation_cleanup();
-            chat.app_event_tx.send(AppEvent::RequestRedraw);
-        }
-        HistoryRecord::MergedExec(mut merged_exec) => {
-            if merged_exec.action != current_exec.action {
-                return;
-            }
-            merged_exec.segments.push(current_exec);
-            let merged_cell = history_cell::MergedExecCell::from_state(merged_exec.clone());
-            chat.history_replace_at(idx - 1, Box::new(merged_cell));
-            chat.history_remove_at(idx);
-            chat.autoscroll_if_near_bottom();
-            chat.bottom_pane.set_has_chat_history(true);
-            chat.process_animation_cleanup();
-            chat.app_event_tx.send(AppEvent::RequestRedraw);
-        }
-        _ => {}
-    }
+    let merged_cell = history_cell::MergedExecCell::from_state(merged);
+    chat.history_replace_at(idx - 1, Box::new(merged_cell));
+    chat.history_remove_at(idx);
+    chat.autoscroll_if_near_bottom();
+    chat.bottom_pane.set_has_chat_history(true);
+    chat.process_animation_cleanup();
+    chat.app_event_tx.send(AppEvent::RequestRedraw);
 }
@@ -278,6 +278,37 @@ pub(super) fn update_explore_entry_status(
     None
 }
 
+/// Folds the finished explore cell at `idx` into a finished explore cell
+/// directly above it, as allowed by the history merge policy. Returns the
+/// index of the surviving cell.
+pub(super) fn try_merge_explore_at(chat: &mut ChatWidget<'_>, idx: usize) -> usize {
+    if idx == 0 || idx >= chat.history_cells.len() {
+        return idx;
+    }
+    let (Some(HistoryRecord::Explore(previous)), Some(HistoryRecord::Explore(current))) = (
+        history_record_for_cell(chat, idx - 1),
+        history_record_for_cell(chat, idx),
+    ) else {
+        return idx;
+    };
+    let Some(merged) = chat
+        .history_state
+        .merge_policy()
+        .merge_explore(&previous, &current)
+    else {
+        return idx;
+    };
+    let cell = history_cell::ExploreAggregationCell::from_record(merged.clone());
+    chat.history_replace_with_record(
+        idx - 1,
+        Box::new(cell),
+        HistoryDomainRecord::Explore(merged),
+    );
+    chat.history_remove_at(idx);
+    chat.autoscroll_if_near_bottom();
+    idx - 1
+}
+
 pub(super) fn exec_record_from_begin(ev: &ExecCommandBeginEvent) -> ExecRecord {
     let action = history_cell::action_enum_from_parsed(&ev.parsed_cmd);
     ExecRecord {
@@ -298,6 +329,7 @@ pub(super) fn exec_record_from_begin(ev: &ExecCommandBeginEvent) -> ExecRecord {
         working_dir: Some(ev.cwd.clone()),
         env: Vec::new(),
         tags: Vec::new(),
+        redacted_values: 0,
     }
 }
 
@@ -343,7 +375,3 @@ pub(super) fn history_record_for_cell(chat: &ChatWidget<'_>, idx: usize) -> Opti
         .get(idx)
         .and_then(|cell| history_cell::record_from_cell(cell.as_ref()))
 }
-
-pub(super) fn exec_record_has_output(record: &ExecRecord) -> bool {
-    !record.stdout_chunks.is_empty() || !record.stderr_chunks.is_empty()
-}
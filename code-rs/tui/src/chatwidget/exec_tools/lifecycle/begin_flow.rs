@@ -72,6 +72,7 @@ fn try_upgrade_fallback_exec_cell(
                         .downcast_mut::<history_cell::ExecCell>()
                     {
                         exec_mut.replace_command_metadata(ev.command.clone(), ev.parsed_cmd.clone());
+                        chat.invalidate_cell_height(i);
                     }
                     if promote_exec_cell_to_explore(chat, i) {
                         return true;
@@ -79,7 +80,7 @@ fn try_upgrade_fallback_exec_cell(
                     try_merge_completed_exec_at(chat, i);
                 }
 
-                chat.invalidate_height_cache();
+                chat.invalidate_history_offsets();
                 chat.request_redraw();
                 return true;
             }
@@ -162,7 +163,7 @@ fn apply_exec_begin_metadata_to_finished_call(
                     }
                     crate::chatwidget::exec_tools::try_merge_completed_exec_at(chat, idx);
                 }
-                chat.invalidate_height_cache();
+                chat.invalidate_history_offsets();
                 chat.request_redraw();
                 return true;
             }
@@ -232,7 +233,7 @@ fn apply_exec_begin_metadata_to_finished_call(
                     && promote_exec_cell_to_explore(chat, idx) {
                         return true;
                     }
-                chat.invalidate_height_cache();
+                chat.invalidate_history_offsets();
                 chat.request_redraw();
                 return true;
             }
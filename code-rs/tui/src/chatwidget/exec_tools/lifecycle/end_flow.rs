@@ -6,6 +6,8 @@ pub(in super::super::super) fn handle_exec_end_now(
     order: &OrderMeta,
 ) {
     let call_id = super::ExecCallId(ev.call_id.clone());
+    // No more output follows an end; close any spill file for it.
+    chat.exec.output_spill.finish(&ev.call_id);
     let suppressing = chat.exec.should_suppress_exec_end(&call_id);
     if suppressing {
         chat.exec.unsuppress_exec_end(&call_id);
@@ -35,6 +37,7 @@ pub(in super::super::super) fn handle_exec_end_now(
         duration,
         stdout,
         stderr,
+        redacted_values,
     } = ev;
     let cmd = chat
         .exec
@@ -178,6 +181,9 @@ pub(in super::super::super) fn handle_exec_end_now(
             .any(|rc| rc.explore_entry.is_some())
         {
             chat.exec.running_explore_agg_index = None;
+            if let Some(actual_idx) = updated_index {
+                try_merge_explore_at(chat, actual_idx);
+            }
         } else if let Some(actual_idx) = updated_index {
             chat.exec.running_explore_agg_index = Some(actual_idx);
         }
@@ -231,6 +237,7 @@ pub(in super::super::super) fn handle_exec_end_now(
             wait_notes: wait_notes_record,
             stdout_tail: stdout_tail_event,
             stderr_tail: stderr_tail_event,
+            redacted_values,
         });
 
     let mut handled_via_state = false;
@@ -262,6 +269,7 @@ pub(in super::super::super) fn handle_exec_end_now(
             cell.set_wait_notes(&wait_notes_pairs);
             cell.set_waiting(false);
             cell.set_run_duration(Some(duration));
+            cell.record.redacted_values = redacted_values;
             if cell.record.call_id.as_deref().is_none() {
                 cell.record.call_id = Some(call_id.clone());
             }
@@ -42,6 +42,7 @@ use std::time::SystemTime;
 mod finalization;
 mod helpers;
 mod lifecycle;
+pub(super) mod output_spill;
 
 pub(super) use finalization::finalize_all_running_as_interrupted;
 pub(super) use finalization::finalize_all_running_due_to_answer;
@@ -0,0 +1,132 @@
+//! Full exec output kept on disk.
+//!
+//! History only retains the tail of each exec stream
+//! (`MAX_EXEC_STREAM_RETAINED_BYTES`). Output deltas are also buffered per
+//! call here; once a call produces more than history would keep, the buffer
+//! moves to a file in a session-scoped temporary directory and later deltas
+//! are appended to it, so `/output` can page through everything from disk.
+//! Smaller outputs never touch the disk. The directory is removed when the
+//! session ends.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use code_core::history::state::MAX_EXEC_STREAM_RETAINED_BYTES;
+
+#[derive(Default)]
+pub(crate) struct ExecOutputSpill {
+    dir: Option<tempfile::TempDir>,
+    /// Set after an I/O failure; spilling stays off for the session.
+    disabled: bool,
+    /// Output of running calls that has not outgrown history yet.
+    pending: HashMap<String, Vec<u8>>,
+    writers: HashMap<String, BufWriter<File>>,
+    files: HashMap<String, PathBuf>,
+}
+
+impl ExecOutputSpill {
+    /// Records a stdout or stderr delta for `call_id`, in arrival order.
+    pub(crate) fn append(&mut self, call_id: &str, chunk: &[u8]) {
+        if self.disabled || chunk.is_empty() {
+            return;
+        }
+        if let Some(writer) = self.writers.get_mut(call_id) {
+            if let Err(err) = writer.write_all(chunk) {
+                self.disable(&err);
+            }
+            return;
+        }
+        let pending = self.pending.entry(call_id.to_string()).or_default();
+        pending.extend_from_slice(chunk);
+        if pending.len() <= MAX_EXEC_STREAM_RETAINED_BYTES {
+            return;
+        }
+        let buffered = self.pending.remove(call_id).unwrap_or_default();
+        if let Err(err) = self.start_file(call_id, &buffered) {
+            self.disable(&err);
+        }
+    }
+
+    /// The call ended: flush its file, or drop its buffer when the output
+    /// was small enough for history to keep in full.
+    pub(crate) fn finish(&mut self, call_id: &str) {
+        self.pending.remove(call_id);
+        if let Some(mut writer) = self.writers.remove(call_id)
+            && let Err(err) = writer.flush()
+        {
+            self.disable(&err);
+        }
+    }
+
+    /// Path of the full output of `call_id`, if it was spilled.
+    pub(crate) fn path(&mut self, call_id: &str) -> Option<&Path> {
+        if let Some(writer) = self.writers.get_mut(call_id) {
+            // Still running: make everything so far visible to the pager.
+            let _ = writer.flush();
+        }
+        self.files.get(call_id).map(PathBuf::as_path)
+    }
+
+    fn start_file(&mut self, call_id: &str, buffered: &[u8]) -> std::io::Result<()> {
+        if self.dir.is_none() {
+            self.dir = Some(
+                tempfile::Builder::new()
+                    .prefix("code-exec-output-")
+                    .tempdir()?,
+            );
+        }
+        let Some(dir) = self.dir.as_ref() else {
+            return Ok(());
+        };
+        let path = dir.path().join(format!("{}.log", file_stem(call_id)));
+        let mut writer = BufWriter::new(File::create(&path)?);
+        writer.write_all(buffered)?;
+        self.writers.insert(call_id.to_string(), writer);
+        self.files.insert(call_id.to_string(), path);
+        Ok(())
+    }
+
+    fn disable(&mut self, err: &std::io::Error) {
+        tracing::warn!("exec output spill disabled: {err}");
+        self.disabled = true;
+        self.pending.clear();
+        self.writers.clear();
+    }
+}
+
+/// Call ids come from the model; keep only characters safe in a file name.
+fn file_stem(call_id: &str) -> String {
+    call_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn only_output_larger_than_history_retains_is_spilled() {
+        let mut spill = ExecOutputSpill::default();
+        spill.append("small", b"hello\n");
+        spill.finish("small");
+        assert_eq!(spill.path("small"), None);
+
+        let head = vec![b'a'; MAX_EXEC_STREAM_RETAINED_BYTES];
+        spill.append("call/1", &head);
+        spill.append("call/1", b"bc");
+        spill.append("call/1", b"\nerr\n");
+        spill.finish("call/1");
+        let path = spill.path("call/1").expect("spilled").to_path_buf();
+        assert_eq!(path.file_name().and_then(|n| n.to_str()), Some("call_1.log"));
+        let contents = std::fs::read(&path).expect("read spill");
+        assert_eq!(contents.len(), head.len() + 7);
+        assert!(contents.ends_with(b"abc\nerr\n"));
+    }
+}
@@ -0,0 +1,140 @@
+use super::*;
+
+impl ChatWidget<'_> {
+    /// Remembers what the session left `paths` as, so later edits made outside
+    /// the session (editor, `git pull`) can be told apart from its own.
+    pub(super) fn record_session_file_contents<'a>(
+        &mut self,
+        paths: impl IntoIterator<Item = &'a PathBuf>,
+    ) {
+        for path in paths {
+            let content = std::fs::read_to_string(path).unwrap_or_default();
+            self.diffs.session_file_contents.insert(path.clone(), content);
+        }
+    }
+
+    /// Re-reads every file the session has changed after a command ran, since
+    /// commands (formatters, `git checkout`) edit files on the session's behalf.
+    pub(super) fn resync_session_file_contents(&mut self) {
+        let paths: Vec<PathBuf> = self.diffs.session_file_contents.keys().cloned().collect();
+        self.record_session_file_contents(paths.iter());
+    }
+
+    /// Warns once about files the session changed that were modified on disk
+    /// since, because the session diff (baseline vs. disk) now folds those
+    /// edits in. Called before each patch and command and when the diff
+    /// viewer opens.
+    pub(super) fn check_external_edits(&mut self) {
+        let mut changed: Vec<PathBuf> = Vec::new();
+        for (path, expected) in self.diffs.session_file_contents.iter_mut() {
+            let current = std::fs::read_to_string(path).unwrap_or_default();
+            if current == *expected {
+                continue;
+            }
+            let expected = std::mem::replace(expected, current);
+            if !self.diffs.external_edits.contains_key(path) {
+                self.diffs.external_edits.insert(path.clone(), expected);
+                changed.push(path.clone());
+            }
+        }
+        if changed.is_empty() {
+            return;
+        }
+
+        changed.sort();
+        let count = changed.len();
+        let names: Vec<String> = changed
+            .iter()
+            .map(|path| self.diff_display_path(path))
+            .collect();
+        self.push_background_tail(format!(
+            "⚠ {count} file{} changed outside this session: {}. The session diff now includes those edits; run `/diff refresh` to rebase its baselines onto them. Pending patches are merged onto the new contents when applied.",
+            if count == 1 { "" } else { "s" },
+            names.join(", ")
+        ));
+        self.request_redraw();
+    }
+
+    /// `/diff refresh`: rebases the baselines of files edited outside the
+    /// session so the session diff shows only the session's own changes.
+    pub(crate) fn handle_diff_refresh(&mut self) {
+        self.check_external_edits();
+        if self.diffs.external_edits.is_empty() {
+            self.push_background_tail(
+                "`/diff refresh` — no files changed outside this session.".to_string(),
+            );
+            self.request_redraw();
+            return;
+        }
+
+        let mut external_edits: Vec<(PathBuf, String)> =
+            self.diffs.external_edits.drain().collect();
+        external_edits.sort();
+        let mut rebased = 0usize;
+        let mut reset: Vec<String> = Vec::new();
+        for (path, expected) in external_edits {
+            let current = std::fs::read_to_string(&path).unwrap_or_default();
+            let baseline = self
+                .diffs
+                .baseline_file_contents
+                .get(&path)
+                .cloned()
+                .unwrap_or_default();
+            let new_baseline = match rebase_baseline(&baseline, &expected, &current) {
+                Some(new_baseline) => {
+                    rebased += 1;
+                    new_baseline
+                }
+                None => {
+                    reset.push(self.diff_display_path(&path));
+                    current.clone()
+                }
+            };
+            self.diffs.baseline_file_contents.insert(path.clone(), new_baseline);
+            self.diffs.session_file_contents.insert(path, current);
+        }
+
+        let mut message = format!(
+            "Rebased session baselines onto outside edits in {rebased} file{}.",
+            if rebased == 1 { "" } else { "s" }
+        );
+        if !reset.is_empty() {
+            message.push_str(&format!(
+                " Outside edits overlap the session's changes in {}; their baselines now match the current contents.",
+                reset.join(", ")
+            ));
+        }
+        self.push_background_tail(message);
+        self.request_redraw();
+    }
+
+    fn diff_display_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.config.cwd)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+}
+
+/// Moves `baseline` forward by the outside edits that turned `expected` (what
+/// the session left the file as) into `current`, by undoing the session's own
+/// edits on top of `current`. `None` when the two sets of edits overlap.
+fn rebase_baseline(baseline: &str, expected: &str, current: &str) -> Option<String> {
+    diffy::merge(expected, current, baseline).ok()
+}
+
+#[cfg(test)]
+mod external_edits_tests {
+    use super::*;
+
+    #[test]
+    fn rebased_baseline_drops_only_the_session_edits() {
+        let baseline = "one\ntwo\nthree\nfour\nfive\n";
+        let expected = "one\ntwo\nthree\nfour\nFIVE\n";
+        let current = "ONE\ntwo\nthree\nfour\nFIVE\n";
+
+        let rebased = rebase_baseline(baseline, expected, current).expect("clean rebase");
+        assert_eq!(rebased, "ONE\ntwo\nthree\nfour\nfive\n");
+        assert_eq!(rebase_baseline("a\n", "b\n", "c\n"), None);
+    }
+}
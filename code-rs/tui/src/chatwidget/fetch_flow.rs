@@ -0,0 +1,50 @@
+use super::*;
+
+impl ChatWidget<'_> {
+    /// `/fetch <url>`: downloads the page without a browser and hands its
+    /// readable content to the model as a developer message.
+    pub(crate) fn handle_fetch_command(&mut self, args: String) {
+        let raw_url = args.trim().to_string();
+        if raw_url.is_empty() {
+            self.history_push_plain_state(crate::history_cell::new_error_event(
+                "`/fetch` — provide a URL, e.g. `/fetch https://docs.rs/serde`".to_string(),
+            ));
+            self.request_redraw();
+            return;
+        }
+
+        self.consume_pending_prompt_for_ui_only_turn();
+        let allowed_domains = self.config.tools_fetch_url_allowed_domains.clone();
+        let tx = self.app_event_tx.clone();
+        let ticket = self.make_background_tail_ticket();
+        self.push_background_tail(format!("Fetching {raw_url}…"));
+        self.request_redraw();
+
+        tokio::spawn(async move {
+            let page = match code_core::url_fetch::fetch_url(
+                &raw_url,
+                allowed_domains.as_deref(),
+                code_core::url_fetch::DEFAULT_FETCH_MAX_CHARS,
+            )
+            .await
+            {
+                Ok(page) => page,
+                Err(err) => {
+                    tx.send_background_event_with_ticket(&ticket, format!("`/fetch` — {err}"));
+                    return;
+                }
+            };
+
+            let mut summary = format!(
+                "Added {} to the conversation ({} characters of markdown",
+                page.title.as_deref().unwrap_or(&page.final_url),
+                page.markdown.chars().count()
+            );
+            summary.push_str(if page.truncated { ", truncated)." } else { ")." });
+            tx.send(AppEvent::CodexOp(Op::AddPendingInputDeveloper {
+                text: page.to_context_message(),
+            }));
+            tx.send_background_event_with_ticket(&ticket, summary);
+        });
+    }
+}
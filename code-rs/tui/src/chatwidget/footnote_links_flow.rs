@@ -0,0 +1,72 @@
+use super::*;
+use std::process::Command;
+use std::process::Stdio;
+
+/// The platform's command for opening `url` in the default browser.
+fn url_opener_command(url: &str) -> Command {
+    let program = if code_core::env::is_termux() {
+        "termux-open-url"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(windows) {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    let mut command = Command::new(program);
+    command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    command
+}
+
+impl ChatWidget<'_> {
+    /// Alt+1…9 with footnote links: opens link `number` of the last answer
+    /// with the platform opener, or copies it when nothing can open it.
+    pub(crate) fn open_footnote_link(&mut self, number: usize) {
+        let links = self
+            .history_cells
+            .iter()
+            .rev()
+            .filter_map(|cell| {
+                cell.as_any()
+                    .downcast_ref::<history_cell::AssistantMarkdownCell>()
+            })
+            .find(|cell| !cell.state().mid_turn)
+            .map(|cell| crate::markdown_links::footnote_links(cell.markdown()))
+            .unwrap_or_default();
+        let Some(url) = links.get(number.saturating_sub(1)).cloned() else {
+            let message = match links.len() {
+                0 => "The last answer has no links.".to_string(),
+                1 => "The last answer has only link [1].".to_string(),
+                count => format!("The last answer has links [1]–[{count}]."),
+            };
+            self.push_background_tail(message);
+            self.request_redraw();
+            return;
+        };
+
+        let tx = self.app_event_tx.clone();
+        let ticket = self.make_background_tail_ticket();
+        tokio::spawn(async move {
+            let target = url.clone();
+            let opened = tokio::task::spawn_blocking(move || url_opener_command(&target).status())
+                .await
+                .ok()
+                .and_then(Result::ok)
+                .is_some_and(|status| status.success());
+            let message = if opened {
+                format!("Opened [{number}] {url}")
+            } else {
+                match crate::clipboard_paste::copy_text_to_clipboard(&url) {
+                    Ok(()) => format!("Could not open [{number}]; copied the link instead: {url}"),
+                    Err(_) => format!("Could not open [{number}]: {url}"),
+                }
+            };
+            tx.send_background_event_with_ticket(&ticket, message);
+        });
+        self.request_redraw();
+    }
+}
@@ -3,12 +3,14 @@ use super::*;
 impl ChatWidget<'_> {
     /// Handle patch apply end immediately
     pub(in super::super) fn handle_patch_apply_end_now(&mut self, ev: PatchApplyEndEvent) {
+        let failed_patch = self.note_patch_apply_end(&ev.call_id, ev.success);
         if ev.success {
             let _ = self.update_latest_patch_summary_record(
                 HistoryPatchEventType::ApplySuccess,
                 None,
                 true,
             );
+            self.refresh_touched_files_summary();
             self.maybe_hide_spinner();
             return;
         }
@@ -21,6 +23,9 @@ impl ChatWidget<'_> {
         ) {
             self.insert_patch_failure_summary(failure_meta);
         }
+        if let Some(patch) = failed_patch.as_ref() {
+            self.show_patch_conflicts(patch);
+        }
         self.maybe_hide_spinner();
     }
 
@@ -3,6 +3,12 @@ use super::*;
 impl ChatWidget<'_> {
     /// Render a single recorded ResponseItem into history without executing tools
     pub(in super::super) fn render_replay_item(&mut self, item: ResponseItem) {
+        if let Some(item_id) = replay_item_id(&item)
+            && !self.history_state.note_replayed_item(item_id)
+        {
+            tracing::debug!("replay: skipping already rendered item {item_id}");
+            return;
+        }
         match item {
             ResponseItem::Message { id, role, content, .. } => {
                 let message_id = id;
@@ -37,21 +43,20 @@ impl ChatWidget<'_> {
                     return;
             }
             if role == "assistant" {
-                let normalized_new = Self::normalize_text(text);
-                if let Some(last_cell) = self.history_cells.last()
-                    && let Some(existing) = last_cell
-                        .as_any()
-                        .downcast_ref::<crate::history_cell::AssistantMarkdownCell>()
-                    {
-                        let normalized_existing =
-                            Self::normalize_text(existing.markdown());
-                        if normalized_existing == normalized_new {
-                            tracing::debug!(
-                                "replay: skipping duplicate assistant message"
-                            );
-                            return;
-                        }
-                    }
+                let previous = self
+                    .history_cell_ids
+                    .last()
+                    .copied()
+                    .flatten()
+                    .and_then(|id| self.history_state.record(id));
+                if self
+                    .history_state
+                    .merge_policy()
+                    .is_duplicate_assistant(previous, text)
+                {
+                    tracing::debug!("replay: skipping duplicate assistant message");
+                    return;
+                }
                 let mut lines: Vec<ratatui::text::Line<'static>> = Vec::new();
                 crate::markdown::append_markdown(text, &mut lines, &self.config);
                 self.insert_final_answer_with_id(message_id, lines, text.to_string());
@@ -209,7 +214,8 @@ impl ChatWidget<'_> {
             crate::colors::background()
         };
 
-        if is_assistant || is_auto_review {
+        let background_painted = is_assistant || is_auto_review;
+        if background_painted {
             let bg_style = Style::default()
                 .bg(cell_bg)
                 .fg(crate::colors::text());
@@ -225,11 +231,9 @@ impl ChatWidget<'_> {
         for (visible_offset, src_index) in (skip_rows as usize..skip_rows as usize + max_rows as usize)
             .enumerate()
         {
-            let src_row = layout
-                .rows
-                .get(src_index)
-                .map(std::convert::AsRef::as_ref)
-                .unwrap_or(&[]);
+            let Some(src_row) = layout.rows.get(src_index) else {
+                break;
+            };
 
             let dest_y = offset_y + visible_offset;
             if dest_y >= buf.area.height as usize {
@@ -244,27 +248,7 @@ impl ChatWidget<'_> {
             if end <= start {
                 continue;
             }
-            let dest_slice = &mut buf.content[start..end];
-
-            let copy_len = src_row.len().min(dest_slice.len());
-            if copy_len == dest_slice.len() {
-                if copy_len > 0 {
-                    dest_slice.clone_from_slice(&src_row[..copy_len]);
-                }
-            } else {
-                for (dst, src) in dest_slice.iter_mut().zip(src_row.iter()).take(copy_len) {
-                    dst.clone_from(src);
-                }
-                for cell in dest_slice.iter_mut().skip(copy_len) {
-                    cell.reset();
-                }
-            }
-
-            for cell in dest_slice.iter_mut() {
-                if cell.bg == ratatui::style::Color::Reset {
-                    cell.bg = cell_bg;
-                }
-            }
+            src_row.blit(&mut buf.content[start..end], cell_bg, background_painted);
         }
     }
     /// Trigger fade on the welcome cell when the composer expands (e.g., slash popup).
@@ -291,7 +275,7 @@ impl ChatWidget<'_> {
                     .map(|_| idx)
             });
 
-        let mut changed = false;
+        let mut changed = Vec::new();
         for (idx, cell) in self.history_cells.iter().enumerate() {
             if let Some(reasoning_cell) = cell
                 .as_any()
@@ -311,12 +295,12 @@ impl ChatWidget<'_> {
                 }
 
                 reasoning_cell.set_in_progress(false);
-                changed = true;
+                changed.push(idx);
             }
         }
 
-        if changed {
-            self.invalidate_height_cache();
+        for idx in changed {
+            self.invalidate_cell_height(idx);
         }
     }
 
@@ -347,14 +331,14 @@ impl ChatWidget<'_> {
 
     pub(in super::super) fn refresh_reasoning_collapsed_visibility(&mut self) {
         let show = self.config.tui.show_reasoning;
-        let mut needs_invalidate = false;
+        let mut changed = Vec::new();
         if show {
-            for cell in &self.history_cells {
+            for (i, cell) in self.history_cells.iter().enumerate() {
                 if let Some(reasoning_cell) = cell
                     .as_any()
                     .downcast_ref::<history_cell::CollapsibleReasoningCell>()
                     && reasoning_cell.set_hide_when_collapsed(false) {
-                        needs_invalidate = true;
+                        changed.push(i);
                     }
             }
         } else {
@@ -438,17 +422,32 @@ impl ChatWidget<'_> {
                 {
                     let hide = hide_indices.contains(&i);
                     if reasoning_cell.set_hide_when_collapsed(hide) {
-                        needs_invalidate = true;
+                        changed.push(i);
                     }
                 }
             }
         }
 
-        if needs_invalidate {
-            self.invalidate_height_cache();
+        if !changed.is_empty() {
+            for i in changed {
+                self.invalidate_cell_height(i);
+            }
             self.request_redraw();
         }
 
         self.refresh_explore_trailing_flags();
     }
 }
+
+fn replay_item_id(item: &ResponseItem) -> Option<&str> {
+    let id = match item {
+        ResponseItem::Message { id, .. }
+        | ResponseItem::LocalShellCall { id, .. }
+        | ResponseItem::FunctionCall { id, .. }
+        | ResponseItem::CustomToolCall { id, .. }
+        | ResponseItem::WebSearchCall { id, .. } => id.as_deref(),
+        ResponseItem::Reasoning { id, .. } => Some(id.as_str()),
+        _ => None,
+    };
+    id.filter(|id| !id.is_empty())
+}
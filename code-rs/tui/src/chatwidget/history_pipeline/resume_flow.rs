@@ -8,6 +8,7 @@ impl ChatWidget<'_> {
             return;
         }
         self.resume_picker_loading = true;
+        self.resume_picker_opened = false;
         self.bottom_pane.flash_footer_notice_for(
             "Loading past sessions…".to_string(),
             std::time::Duration::from_secs(30),
@@ -21,20 +22,32 @@ impl ChatWidget<'_> {
 
         tokio::spawn(async move {
             let fetch_cwd = cwd.clone();
-            let fetch_code_home = code_home.clone();
-            let fetch_exclude = exclude_path.clone();
-            let result = tokio::task::spawn_blocking(move || {
-                crate::resume::discovery::list_sessions_for_cwd(
+            let partial_tx = tx.clone();
+            let result = tokio::spawn(async move {
+                let partial_cwd = fetch_cwd.clone();
+                crate::resume::discovery::stream_sessions_for_cwd(
                     &fetch_cwd,
-                    &fetch_code_home,
-                    fetch_exclude.as_deref(),
+                    &code_home,
+                    exclude_path.as_deref(),
+                    move |candidates| {
+                        partial_tx.send(AppEvent::ResumePickerLoaded {
+                            cwd: partial_cwd.clone(),
+                            candidates,
+                            complete: false,
+                        });
+                    },
                 )
+                .await
             })
             .await;
 
             match result {
                 Ok(candidates) => {
-                    tx.send(AppEvent::ResumePickerLoaded { cwd, candidates });
+                    tx.send(AppEvent::ResumePickerLoaded {
+                        cwd,
+                        candidates,
+                        complete: true,
+                    });
                 }
                 Err(err) => {
                     tx.send(AppEvent::ResumePickerLoadFailed {
@@ -82,6 +95,7 @@ impl ChatWidget<'_> {
                 let created = human_ago(&c.created_ts.unwrap_or_default());
                 let user_message_count = c.user_message_count;
                 let user_msgs = format!("{user_message_count}");
+                let tokens = resume_tokens_label(c.total_tokens);
                 let branch = c.branch.unwrap_or_else(|| "-".to_string());
                 let nickname = c
                     .nickname
@@ -90,20 +104,17 @@ impl ChatWidget<'_> {
                         (!trimmed.is_empty()).then(|| trimmed.to_string())
                     });
                 let snippet = c.snippet.or(c.subtitle);
-                let mut summary = match (nickname, snippet) {
+                let summary = clip_resume_summary(match (nickname, snippet) {
                     (Some(name), Some(snippet)) => format!("{name} - {snippet}"),
                     (Some(name), None) => name,
                     (None, Some(snippet)) => snippet,
                     (None, None) => String::new(),
-                };
-                const SNIPPET_MAX: usize = 64;
-                if summary.chars().count() > SNIPPET_MAX {
-                    summary = summary.chars().take(SNIPPET_MAX).collect::<String>() + "…";
-                }
+                });
                 crate::bottom_pane::resume_selection_view::ResumeRow {
                     modified,
                     created,
                     user_msgs,
+                    tokens,
                     branch,
                     last_user_message: summary,
                     path: c.path,
@@ -116,21 +127,47 @@ impl ChatWidget<'_> {
         &mut self,
         cwd: std::path::PathBuf,
         candidates: Vec<crate::resume::discovery::ResumeCandidate>,
+        complete: bool,
     ) {
-        self.resume_picker_loading = false;
-        if candidates.is_empty() {
-            self.bottom_pane
-                .flash_footer_notice("No past sessions found for this folder".to_string());
+        if !self.resume_picker_loading {
+            return;
+        }
+        if complete {
+            self.resume_picker_loading = false;
+        }
+        if self.resume_picker_opened {
+            // Closed while still loading: leave it closed.
+            if !self.bottom_pane.resume_selection_active() {
+                return;
+            }
+            let rows = Self::resume_rows_from_candidates(candidates);
+            let count = rows.len();
+            self.bottom_pane.update_resume_selection(rows);
+            if complete {
+                self.bottom_pane
+                    .flash_footer_notice(format!("Loaded {count} past sessions."));
+            }
             self.request_redraw();
             return;
         }
+        if candidates.is_empty() {
+            if complete {
+                self.bottom_pane
+                    .flash_footer_notice("No past sessions found for this folder".to_string());
+                self.request_redraw();
+            }
+            return;
+        }
         let rows = Self::resume_rows_from_candidates(candidates);
         let count = rows.len();
         let title = format!("Resume Session — {}", cwd.display());
         self.bottom_pane
             .show_resume_selection(title, Some(String::new()), rows);
-        self.bottom_pane
-            .flash_footer_notice(format!("Loaded {count} past sessions."));
+        self.resume_picker_opened = true;
+        if complete {
+            self.bottom_pane
+                .flash_footer_notice(format!("Loaded {count} past sessions."));
+        }
         self.request_redraw();
     }
 
@@ -139,4 +176,44 @@ impl ChatWidget<'_> {
         self.bottom_pane.flash_footer_notice(message);
         self.request_redraw();
     }
+
+    /// Replaces a picker row's summary and token columns with facts read
+    /// from its rollout (requested with `s` in the picker).
+    pub(crate) fn apply_resume_summary(
+        &mut self,
+        path: std::path::PathBuf,
+        result: Result<code_core::SessionSummary, String>,
+    ) {
+        match result {
+            Ok(summary) => {
+                let mut headline = summary.headline();
+                if headline.is_empty() {
+                    headline = "(no activity recorded)".to_string();
+                }
+                self.bottom_pane.update_resume_summary(
+                    &path,
+                    clip_resume_summary(headline),
+                    resume_tokens_label(summary.tokens.total),
+                );
+            }
+            Err(message) => self.bottom_pane.flash_footer_notice(message),
+        }
+        self.request_redraw();
+    }
+}
+
+fn resume_tokens_label(total_tokens: u64) -> String {
+    match total_tokens {
+        0 => "-".to_string(),
+        total => code_protocol::num_format::format_si_suffix(total as i64),
+    }
+}
+
+fn clip_resume_summary(summary: String) -> String {
+    const SNIPPET_MAX: usize = 64;
+    if summary.chars().count() > SNIPPET_MAX {
+        summary.chars().take(SNIPPET_MAX).collect::<String>() + "…"
+    } else {
+        summary
+    }
 }
@@ -7,6 +7,12 @@ impl ChatWidget<'_> {
         // Use call_id as the approval correlation id so responses map to the
         // exact pending approval in core (supports multiple approvals per turn).
         let approval_id = ev.call_id.clone();
+        self.emit_feedback(self.config.tui.feedback.approval);
+        self.emit_approval_notification(
+            &approval_id,
+            "Code: approve command?",
+            &strip_bash_lc_and_escape(&ev.command),
+        );
         let ticket = self.make_background_before_next_output_ticket();
         self.bottom_pane
             .push_approval_request(ApprovalRequest::Exec {
@@ -24,6 +30,19 @@ impl ChatWidget<'_> {
             reason,
             grant_root,
         } = ev;
+        self.check_external_edits();
+
+        let mut changed_paths: Vec<String> = changes
+            .keys()
+            .map(|path| path.display().to_string())
+            .collect();
+        changed_paths.sort();
+        self.emit_feedback(self.config.tui.feedback.approval);
+        self.emit_approval_notification(
+            &call_id,
+            "Code: approve patch?",
+            &format!("{} file(s): {}", changed_paths.len(), changed_paths.join(", ")),
+        );
 
         // Clone for session storage before moving into history
         let changes_clone = changes.clone();
@@ -21,9 +21,9 @@ impl ChatWidget<'_> {
 
     /// Mark that the widget needs to be redrawn
     pub(in super::super::super) fn mark_needs_redraw(&mut self) {
-        // Clean up fully faded cells before redraw. If any are removed,
-        // invalidate the height cache since indices shift and our cache is
-        // keyed by (idx,width).
+        // Clean up fully faded cells before redraw. Cached heights are keyed
+        // by record id, so only the removed records' entries are dropped; the
+        // offsets of everything after them still have to be recomputed.
         let before_len = self.history_cells.len();
         if before_len > 0 {
             let old_cells = std::mem::take(&mut self.history_cells);
@@ -39,6 +39,9 @@ impl ChatWidget<'_> {
             for (cell, id) in old_cells.into_iter().zip(old_ids.into_iter()) {
                 if cell.should_remove() {
                     removed_any = true;
+                    if let Some(id) = id {
+                        self.history_render.invalidate_history_id(id);
+                    }
                     continue;
                 }
                 kept_ids.push(id);
@@ -47,7 +50,7 @@ impl ChatWidget<'_> {
             self.history_cells = kept_cells;
             self.history_cell_ids = kept_ids;
             if removed_any {
-                self.invalidate_height_cache();
+                self.invalidate_history_offsets();
             }
         } else if !self.history_cell_ids.is_empty() {
             self.history_cell_ids.clear();
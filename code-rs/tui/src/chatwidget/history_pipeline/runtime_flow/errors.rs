@@ -21,6 +21,7 @@ impl ChatWidget<'_> {
             self.mark_reconnecting(message);
             return;
         }
+        self.emit_feedback(self.config.tui.feedback.error);
 
         // Ensure reconnect banners are cleared once we pivot to a fatal error
         // without emitting the "Reconnected" toast (which would be misleading).
@@ -78,6 +79,9 @@ impl ChatWidget<'_> {
             self.reconnect_notice_active = true;
             self.push_background_tail(format!("Auto-retrying… ({message})"));
         }
+        if message.starts_with("Network unavailable") {
+            self.enter_offline_mode();
+        }
 
         // Do NOT clear running state or streams; the retry will resume them.
         self.request_redraw();
@@ -78,15 +78,9 @@ impl ChatWidget<'_> {
         self.exec
             .pending_exec_ends
             .insert(call_id, (ev, order.clone(), std::time::Instant::now()));
-        let tx = self.app_event_tx.clone();
-        let fallback_tx = tx.clone();
-        if thread_spawner::spawn_lightweight("exec-flush", move || {
-            std::thread::sleep(std::time::Duration::from_millis(120));
-            tx.send(crate::app_event::AppEvent::FlushPendingExecEnds);
-        })
-        .is_none()
-        {
-            fallback_tx.send(crate::app_event::AppEvent::FlushPendingExecEnds);
-        }
+        self.app_event_tx.send_after(
+            std::time::Duration::from_millis(120),
+            crate::app_event::AppEvent::FlushPendingExecEnds,
+        );
     }
 }
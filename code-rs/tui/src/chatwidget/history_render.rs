@@ -3,10 +3,11 @@ use std::collections::HashMap;
 use std::rc::Rc;
 use std::time::Instant;
 
-use ratatui::buffer::Cell as BufferCell;
 use ratatui::text::Line;
 
+use crate::cached_rows::CachedRow;
 use crate::history::state::{HistoryId, HistoryRecord, HistoryState};
+use super::perf::HeightCacheInvalidations;
 use crate::history_cell::{
     assistant_markdown_lines,
     compute_assistant_layout,
@@ -25,13 +26,11 @@ use code_core::config::Config;
 use crate::foundation::wrapping::word_wrap_lines;
 #[cfg(not(feature = "code-fork"))]
 use crate::insert_history::word_wrap_lines;
-use unicode_segmentation::UnicodeSegmentation;
-use unicode_width::UnicodeWidthStr;
 
 /// Memoized layout data for history rendering.
 pub(crate) struct HistoryRenderState {
-    pub(crate) layout_cache: RefCell<HashMap<CacheKey, Rc<CachedLayout>>>,
-    pub(crate) height_cache: RefCell<HashMap<CacheKey, u16>>,
+    pub(crate) layout_cache: RefCell<RecordCache<Rc<CachedLayout>>>,
+    pub(crate) height_cache: RefCell<RecordCache<u16>>,
     fallback_cache: RefCell<HashMap<HistoryId, Rc<Vec<Line<'static>>>>>,
     pub(crate) height_cache_last_width: Cell<u16>,
     pub(crate) prefix_sums: RefCell<Vec<u16>>,
@@ -46,13 +45,14 @@ pub(crate) struct HistoryRenderState {
     bottom_spacer_range: Cell<Option<(u16, u16)>>,
     bottom_spacer_lines: Cell<u16>,
     pending_bottom_spacer_lines: Cell<Option<u16>>,
+    invalidations: Cell<HeightCacheInvalidations>,
 }
 
 impl HistoryRenderState {
     pub(crate) fn new() -> Self {
         Self {
-            layout_cache: RefCell::new(HashMap::new()),
-            height_cache: RefCell::new(HashMap::new()),
+            layout_cache: RefCell::new(RecordCache::default()),
+            height_cache: RefCell::new(RecordCache::default()),
             fallback_cache: RefCell::new(HashMap::new()),
             height_cache_last_width: Cell::new(0),
             prefix_sums: RefCell::new(Vec::new()),
@@ -65,12 +65,19 @@ impl HistoryRenderState {
             bottom_spacer_range: Cell::new(None),
             bottom_spacer_lines: Cell::new(0),
             pending_bottom_spacer_lines: Cell::new(None),
+            invalidations: Cell::new(HeightCacheInvalidations::default()),
         }
     }
 
+    /// Drops every cached layout and height. Prefer
+    /// [`Self::invalidate_history_id`] when the changed records are known.
     pub(crate) fn invalidate_height_cache(&self) {
         self.layout_cache.borrow_mut().clear();
-        self.height_cache.borrow_mut().clear();
+        let evicted = self.height_cache.borrow_mut().clear();
+        self.count_invalidation(|stats| {
+            stats.full += 1;
+            stats.evicted_heights += evicted as u64;
+        });
         self.fallback_cache.borrow_mut().clear();
         self.prefix_sums.borrow_mut().clear();
         self.last_total_height.set(0);
@@ -82,14 +89,18 @@ impl HistoryRenderState {
         self.pending_bottom_spacer_lines.set(None);
     }
 
+    /// Drops offsets computed at another width. Layouts and heights at the
+    /// previous width are kept, so rotating back (or toggling a split pane)
+    /// reuses them instead of rewrapping the whole history again.
     pub(crate) fn handle_width_change(&self, width: u16) {
-        if self.height_cache_last_width.get() != width {
-            self.layout_cache
-                .borrow_mut()
-                .retain(|key, _| key.width == width);
-            self.height_cache
-                .borrow_mut()
-                .retain(|key, _| key.width == width);
+        let previous = self.height_cache_last_width.get();
+        if previous != width {
+            self.layout_cache.borrow_mut().retain_widths(&[width, previous]);
+            let evicted = self.height_cache.borrow_mut().retain_widths(&[width, previous]);
+            self.count_invalidation(|stats| {
+                stats.width_changes += 1;
+                stats.evicted_heights += evicted as u64;
+            });
             self.fallback_cache.borrow_mut().clear();
             self.prefix_sums.borrow_mut().clear();
             self.last_total_height.set(0);
@@ -107,12 +118,12 @@ impl HistoryRenderState {
         if id == HistoryId::ZERO {
             return;
         }
-        self.layout_cache
-            .borrow_mut()
-            .retain(|key, _| key.history_id != id);
-        self.height_cache
-            .borrow_mut()
-            .retain(|key, _| key.history_id != id);
+        self.layout_cache.borrow_mut().remove_record(id);
+        let evicted = self.height_cache.borrow_mut().remove_record(id);
+        self.count_invalidation(|stats| {
+            stats.record += 1;
+            stats.evicted_heights += evicted as u64;
+        });
         self.fallback_cache.borrow_mut().remove(&id);
         self.prefix_sums.borrow_mut().clear();
         self.last_total_height.set(0);
@@ -126,7 +137,11 @@ impl HistoryRenderState {
 
     pub(crate) fn invalidate_all(&self) {
         self.layout_cache.borrow_mut().clear();
-        self.height_cache.borrow_mut().clear();
+        let evicted = self.height_cache.borrow_mut().clear();
+        self.count_invalidation(|stats| {
+            stats.full += 1;
+            stats.evicted_heights += evicted as u64;
+        });
         self.fallback_cache.borrow_mut().clear();
         self.prefix_sums.borrow_mut().clear();
         self.last_total_height.set(0);
@@ -139,6 +154,7 @@ impl HistoryRenderState {
     }
 
     pub(crate) fn invalidate_prefix_only(&self) {
+        self.count_invalidation(|stats| stats.prefix_only += 1);
         self.prefix_sums.borrow_mut().clear();
         self.last_total_height.set(0);
         self.last_history_count.set(0);
@@ -149,6 +165,21 @@ impl HistoryRenderState {
         self.pending_bottom_spacer_lines.set(None);
     }
 
+    /// Invalidation counts since startup or the last `/perf reset`.
+    pub(crate) fn invalidations(&self) -> HeightCacheInvalidations {
+        self.invalidations.get()
+    }
+
+    pub(crate) fn reset_invalidations(&self) {
+        self.invalidations.set(HeightCacheInvalidations::default());
+    }
+
+    fn count_invalidation(&self, update: impl FnOnce(&mut HeightCacheInvalidations)) {
+        let mut stats = self.invalidations.get();
+        update(&mut stats);
+        self.invalidations.set(stats);
+    }
+
     pub(crate) fn should_rebuild_prefix(&self, width: u16, count: usize) -> bool {
         if !self.prefix_valid.get() {
             return true;
@@ -546,7 +577,7 @@ impl Default for HistoryRenderState {
 #[derive(Debug)]
 pub(crate) struct CachedLayout {
     pub(crate) lines: Vec<Line<'static>>,
-    pub(crate) rows: Vec<Box<[BufferCell]>>,
+    pub(crate) rows: Vec<CachedRow>,
 }
 
 fn build_cached_layout(lines: Vec<Line<'static>>, width: u16) -> CachedLayout {
@@ -559,80 +590,13 @@ fn build_cached_layout(lines: Vec<Line<'static>>, width: u16) -> CachedLayout {
     CachedLayout { lines: wrapped, rows }
 }
 
-fn build_cached_rows(lines: &[Line<'static>], width: u16) -> Vec<Box<[BufferCell]>> {
-    let target_width = width as usize;
+fn build_cached_rows(lines: &[Line<'static>], width: u16) -> Vec<CachedRow> {
     lines
         .iter()
-        .map(|line| build_cached_row(line, target_width))
+        .map(|line| CachedRow::from_line(line, width))
         .collect()
 }
 
-fn build_cached_row(line: &Line<'static>, target_width: usize) -> Box<[BufferCell]> {
-    if target_width == 0 {
-        return Box::new([]);
-    }
-
-    let mut cells = vec![BufferCell::default(); target_width];
-    let mut x: u16 = 0;
-    let mut remaining = target_width as u16;
-
-    for span in &line.spans {
-        if remaining == 0 {
-            break;
-        }
-        let span_style = line.style.patch(span.style);
-        for symbol in UnicodeSegmentation::graphemes(span.content.as_ref(), true) {
-            if symbol.chars().any(char::is_control) {
-                continue;
-            }
-            let symbol_width = UnicodeWidthStr::width(symbol) as u16;
-            if symbol_width == 0 {
-                continue;
-            }
-            if symbol_width > remaining {
-                remaining = 0;
-                break;
-            }
-
-            let idx = x as usize;
-            if idx >= target_width {
-                remaining = 0;
-                break;
-            }
-
-            cells[idx].set_symbol(symbol).set_style(span_style);
-
-            let next_symbol = x.saturating_add(symbol_width);
-            x = x.saturating_add(1);
-            while x < next_symbol {
-                let fill_idx = x as usize;
-                if fill_idx >= target_width {
-                    remaining = 0;
-                    break;
-                }
-                cells[fill_idx].reset();
-                x = x.saturating_add(1);
-            }
-            if remaining == 0 {
-                break;
-            }
-            if x >= target_width as u16 {
-                remaining = 0;
-                break;
-            }
-            remaining = target_width as u16 - x;
-            if remaining == 0 {
-                break;
-            }
-        }
-        if remaining == 0 {
-            break;
-        }
-    }
-
-    cells.into_boxed_slice()
-}
-
 /// Settings that affect layout caching. Any change to these fields invalidates
 /// the cached `CachedLayout` entries keyed by `(HistoryId, width, theme_epoch,
 /// reasoning_visible)`.
@@ -764,6 +728,12 @@ pub(crate) enum HeightSource {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub(crate) struct CacheKey {
     history_id: HistoryId,
+    variant: LayoutVariant,
+}
+
+/// The render settings a cached layout depends on besides the record itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct LayoutVariant {
     width: u16,
     theme_epoch: u64,
     reasoning_visible: bool,
@@ -773,9 +743,68 @@ impl CacheKey {
     fn new(history_id: HistoryId, settings: RenderSettings) -> Self {
         Self {
             history_id,
-            width: settings.width,
-            theme_epoch: settings.theme_epoch,
-            reasoning_visible: settings.reasoning_visible,
+            variant: LayoutVariant {
+                width: settings.width,
+                theme_epoch: settings.theme_epoch,
+                reasoning_visible: settings.reasoning_visible,
+            },
+        }
+    }
+}
+
+/// Cache entries grouped by record, so dropping one record's entries does
+/// not scan (or disturb) the rest of a long history.
+pub(crate) struct RecordCache<V> {
+    records: HashMap<HistoryId, HashMap<LayoutVariant, V>>,
+}
+
+impl<V> Default for RecordCache<V> {
+    fn default() -> Self {
+        Self {
+            records: HashMap::new(),
         }
     }
 }
+
+impl<V> RecordCache<V> {
+    pub(crate) fn get(&self, key: &CacheKey) -> Option<&V> {
+        self.records
+            .get(&key.history_id)
+            .and_then(|variants| variants.get(&key.variant))
+    }
+
+    pub(crate) fn insert(&mut self, key: CacheKey, value: V) {
+        self.records
+            .entry(key.history_id)
+            .or_default()
+            .insert(key.variant, value);
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.records.values().map(HashMap::len).sum()
+    }
+
+    /// Removes every entry for `id`, returning how many were dropped.
+    fn remove_record(&mut self, id: HistoryId) -> usize {
+        self.records.remove(&id).map_or(0, |variants| variants.len())
+    }
+
+    /// Keeps only entries laid out at one of `widths`, returning how many
+    /// were dropped.
+    fn retain_widths(&mut self, widths: &[u16]) -> usize {
+        let mut dropped = 0;
+        self.records.retain(|_, variants| {
+            let before = variants.len();
+            variants.retain(|variant, _| widths.contains(&variant.width));
+            dropped += before - variants.len();
+            !variants.is_empty()
+        });
+        dropped
+    }
+
+    fn clear(&mut self) -> usize {
+        let dropped = self.len();
+        self.records.clear();
+        dropped
+    }
+}
@@ -3,6 +3,28 @@ use super::*;
 impl ChatWidget<'_> {
     pub(super) fn invalidate_height_cache(&mut self) {
         self.history_render.invalidate_height_cache();
+        self.mark_history_layout_dirty();
+    }
+
+    /// Drops cached layout for the cell at `idx` after it changed in place,
+    /// keeping every other record's cached height.
+    pub(super) fn invalidate_cell_height(&mut self, idx: usize) {
+        match self.history_cell_ids.get(idx).copied().flatten() {
+            Some(id) if id != HistoryId::ZERO => self.history_render.invalidate_history_id(id),
+            // Cells without a record are never cached; only offsets move.
+            _ => self.history_render.invalidate_prefix_only(),
+        }
+        self.mark_history_layout_dirty();
+    }
+
+    /// Recomputes offsets after records were inserted, removed, or replaced
+    /// through helpers that already dropped those records' cached layouts.
+    pub(super) fn invalidate_history_offsets(&mut self) {
+        self.history_render.invalidate_prefix_only();
+        self.mark_history_layout_dirty();
+    }
+
+    fn mark_history_layout_dirty(&self) {
         self.mark_render_requests_dirty();
         self.history_prefix_append_only.set(false);
         self.history_virtualization_sync_pending.set(true);
@@ -323,6 +345,7 @@ impl ChatWidget<'_> {
         };
 
         let history_id = frozen.history_id();
+        let was_estimated = !frozen.is_measured();
         let Some(record) = self.history_state.record(history_id).cloned() else {
             return false;
         };
@@ -337,6 +360,9 @@ impl ChatWidget<'_> {
         }
         self.history_frozen_count = self.history_frozen_count.saturating_sub(1);
         self.update_render_request_seed(idx);
+        if was_estimated {
+            self.history_render.invalidate_prefix_only();
+        }
         true
     }
 
@@ -347,11 +373,16 @@ impl ChatWidget<'_> {
         }
 
         for idx in 0..self.history_cells.len() {
-            let (history_id, cached_width, cached_height) = match self.history_cells[idx]
+            let (history_id, cached_width, cached_height, measured) = match self.history_cells[idx]
                 .as_any()
                 .downcast_ref::<FrozenHistoryCell>()
             {
-                Some(frozen) => (frozen.history_id(), frozen.cached_width(), frozen.cached_height()),
+                Some(frozen) => (
+                    frozen.history_id(),
+                    frozen.cached_width(),
+                    frozen.cached_height(),
+                    frozen.is_measured(),
+                ),
                 None => continue,
             };
 
@@ -359,22 +390,40 @@ impl ChatWidget<'_> {
                 continue;
             }
 
-            let height = self
-                .history_render
-                .cached_height(history_id, render_settings)
-                .or_else(|| {
-                    self.history_state.record(history_id).cloned().and_then(|record| {
-                        self.build_cell_from_record(&record)
-                            .map(|cell| cell.desired_height(width))
-                    })
-                })
-                .unwrap_or(cached_height);
+            // Lazily hydrated placeholders keep a cheap estimate until they
+            // are materialized near the viewport; building them here would
+            // defeat the point on very long sessions.
+            let (height, exact) = match self.history_render.cached_height(history_id, render_settings) {
+                Some(height) => (height, true),
+                None if !measured => (
+                    self.history_state
+                        .record(history_id)
+                        .map(|record| estimate_record_height(record, width))
+                        .unwrap_or(cached_height),
+                    false,
+                ),
+                None => (
+                    self.history_state
+                        .record(history_id)
+                        .cloned()
+                        .and_then(|record| {
+                            self.build_cell_from_record(&record)
+                                .map(|cell| cell.desired_height(width))
+                        })
+                        .unwrap_or(cached_height),
+                    true,
+                ),
+            };
 
             if let Some(frozen) = self.history_cells[idx]
                 .as_any_mut()
                 .downcast_mut::<FrozenHistoryCell>()
             {
-                frozen.update_cached_height(width, height);
+                if exact {
+                    frozen.update_cached_height(width, height);
+                } else {
+                    frozen.set_estimated_height(width, height);
+                }
             }
         }
 
@@ -523,3 +572,40 @@ impl ChatWidget<'_> {
         );
     }
 }
+
+/// Rough row count for a record that has never been laid out, based on its
+/// text length at `width`. Only used for placeholders created by lazy
+/// hydration; the real height replaces it once the cell is materialized.
+pub(super) fn estimate_record_height(record: &HistoryRecord, width: u16) -> u16 {
+    let width = usize::from(width.max(1));
+    let wrapped = |chars: usize| chars.div_ceil(width).max(1);
+    let text_rows = |text: &str| text.lines().map(|line| wrapped(line.chars().count())).sum::<usize>();
+    let message_rows = |lines: &[MessageLine]| {
+        lines
+            .iter()
+            .map(|line| wrapped(line.spans.iter().map(|span| span.text.chars().count()).sum()))
+            .sum::<usize>()
+    };
+    let rows = match record {
+        HistoryRecord::PlainMessage(state) => {
+            usize::from(state.header.is_some()) + message_rows(&state.lines)
+        }
+        HistoryRecord::Notice(notice) => usize::from(notice.title.is_some()) + message_rows(&notice.body),
+        HistoryRecord::AssistantMessage(state) => text_rows(&state.markdown),
+        HistoryRecord::BackgroundEvent(event) => text_rows(&event.description),
+        HistoryRecord::Exec(exec) => {
+            let output_lines: usize = exec
+                .stdout_chunks
+                .iter()
+                .chain(exec.stderr_chunks.iter())
+                .map(|chunk| chunk.content.lines().count())
+                .sum();
+            1 + output_lines.min(ESTIMATED_EXEC_OUTPUT_ROWS)
+        }
+        _ => 2,
+    };
+    rows.clamp(1, usize::from(u16::MAX)) as u16
+}
+
+/// Exec cells collapse long output, so estimates cap the visible tail.
+const ESTIMATED_EXEC_OUTPUT_ROWS: usize = 6;
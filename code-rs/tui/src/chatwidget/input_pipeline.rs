@@ -334,6 +334,9 @@ impl ChatWidget<'_> {
                 layout_scroll::to_bottom(self);
                 return;
             }
+        if self.handle_table_scroll_key(&key_event) {
+            return;
+        }
 
         let composer_was_empty = self.bottom_pane.composer_is_empty();
         let input_result = self.bottom_pane.handle_key_event(key_event);
@@ -353,6 +356,7 @@ impl ChatWidget<'_> {
                     return;
                 }
                 self.pending_turn_origin = Some(TurnOrigin::User);
+                self.reset_table_scroll();
                 let cleaned = Self::strip_context_sections(&text);
                 self.last_user_message = (!cleaned.trim().is_empty()).then_some(cleaned);
                 if self.auto_state.should_show_goal_entry() {
@@ -491,16 +495,12 @@ impl ChatWidget<'_> {
                 .browser_last_key
                 .clone()
                 .or_else(|| self.tools_state.browser_sessions.keys().next().cloned());
-            self.browser_overlay_state.set_session_key(session_key.clone());
-            if let Some(key) = session_key
-                && let Some(tracker) = self.tools_state.browser_sessions.get(&key) {
-                    let history_len = tracker.cell.screenshot_history().len();
-                    if history_len > 0 {
-                        self
-                            .browser_overlay_state
-                            .set_screenshot_index(history_len.saturating_sub(1));
-                    }
-                }
+            self.browser_overlay_state.set_session_key(session_key);
+            let timeline_len = self.browser_overlay_timeline().len();
+            if timeline_len > 0 {
+                self.browser_overlay_state
+                    .set_screenshot_index(timeline_len.saturating_sub(1));
+            }
         } else {
             self.browser_overlay_state.reset();
         }
@@ -519,11 +519,26 @@ impl ChatWidget<'_> {
         let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
 
         match key_event.code {
+            KeyCode::Esc if self.browser_overlay_state.fullscreen() => {
+                self.browser_overlay_state.set_fullscreen(false);
+                self.request_redraw();
+            }
             KeyCode::Esc => {
                 self.browser_overlay_visible = false;
                 self.browser_overlay_state.reset();
                 self.request_redraw();
             }
+            KeyCode::Enter | KeyCode::Char('f') if !ctrl => {
+                let fullscreen = self.browser_overlay_state.fullscreen();
+                self.browser_overlay_state.set_fullscreen(!fullscreen);
+                self.request_redraw();
+            }
+            KeyCode::Char('r') if key_event.modifiers.is_empty() => {
+                self.recapture_browser_snapshot();
+            }
+            KeyCode::Char('c') if key_event.modifiers.is_empty() => {
+                self.copy_selected_browser_url();
+            }
             KeyCode::Up if shift => {
                 self.adjust_browser_overlay_action_scroll(-1);
                 self.request_redraw();
@@ -568,11 +583,9 @@ impl ChatWidget<'_> {
                 }
             }
             KeyCode::End => {
-                if let Some((_, tracker)) = self.browser_overlay_tracker() {
-                    let len = tracker.cell.screenshot_history().len();
-                    if len > 0 && self.set_browser_overlay_screenshot_index(len - 1) {
-                        self.request_redraw();
-                    }
+                let len = self.browser_overlay_timeline().len();
+                if len > 0 && self.set_browser_overlay_screenshot_index(len - 1) {
+                    self.request_redraw();
                 }
             }
             KeyCode::Char('j') if key_event.modifiers.is_empty() => {
@@ -589,11 +602,9 @@ impl ChatWidget<'_> {
                 }
             }
             KeyCode::Char('G') if key_event.modifiers.is_empty() => {
-                if let Some((_, tracker)) = self.browser_overlay_tracker() {
-                    let len = tracker.cell.screenshot_history().len();
-                    if len > 0 && self.set_browser_overlay_screenshot_index(len - 1) {
-                        self.request_redraw();
-                    }
+                let len = self.browser_overlay_timeline().len();
+                if len > 0 && self.set_browser_overlay_screenshot_index(len - 1) {
+                    self.request_redraw();
                 }
             }
             _ => {}
@@ -633,14 +644,11 @@ impl ChatWidget<'_> {
     }
 
     pub(super) fn set_browser_overlay_screenshot_index(&self, index: usize) -> bool {
-        let Some((_, tracker)) = self.browser_overlay_tracker() else {
-            return false;
-        };
-        let history = tracker.cell.screenshot_history();
-        if history.is_empty() {
+        let len = self.browser_overlay_timeline().len();
+        if len == 0 {
             return false;
         }
-        let clamped = index.min(history.len().saturating_sub(1));
+        let clamped = index.min(len.saturating_sub(1));
         if self.browser_overlay_state.screenshot_index() != clamped {
             self.browser_overlay_state.set_screenshot_index(clamped);
             return true;
@@ -649,14 +657,11 @@ impl ChatWidget<'_> {
     }
 
     pub(super) fn move_browser_overlay_screenshot(&self, delta: isize) -> bool {
-        let Some((_, tracker)) = self.browser_overlay_tracker() else {
-            return false;
-        };
-        let history = tracker.cell.screenshot_history();
-        if history.is_empty() {
+        let len = self.browser_overlay_timeline().len();
+        if len == 0 {
             return false;
         }
-        let last_index = history.len() as isize - 1;
+        let last_index = len as isize - 1;
         let mut current = self.browser_overlay_state.screenshot_index() as isize;
         if current > last_index {
             current = last_index;
@@ -2545,7 +2550,7 @@ impl ChatWidget<'_> {
             if idx < self.history_cell_ids.len() {
                 self.history_cell_ids[idx] = Some(id);
             }
-            self.invalidate_height_cache();
+            self.invalidate_history_offsets();
             self.request_redraw();
         } else {
             tracing::warn!(
@@ -2835,6 +2840,7 @@ impl ChatWidget<'_> {
         let record_idx = self
             .record_index_for_cell(idx)
             .unwrap_or_else(|| self.record_index_for_position(idx));
+        self.invalidate_cell_height(idx);
 
         let mutation = self.history_state.apply_domain_event(HistoryDomainEvent::Replace {
             index: record_idx,
@@ -2848,7 +2854,7 @@ impl ChatWidget<'_> {
 
         self.ensure_image_cell_picker(cell.as_ref());
         self.history_cells[idx] = cell;
-        self.invalidate_height_cache();
+        self.invalidate_cell_height(idx);
         self.request_redraw();
         self.refresh_explore_trailing_flags();
         self.mark_history_dirty();
@@ -3072,7 +3078,7 @@ impl ChatWidget<'_> {
         }
 
         if updated {
-            self.invalidate_height_cache();
+            self.invalidate_history_offsets();
             self.request_redraw();
         }
 
@@ -3384,6 +3390,10 @@ impl ChatWidget<'_> {
             return;
         }
         let original_text = message.display_text.clone();
+        if self.offline.active && !original_text.trim().starts_with('/') {
+            self.queue_offline_message(message);
+            return;
+        }
 
         let mut submitted_cli = false;
         let manual_edit_pending = self.auto_state.is_paused_manual()
@@ -3495,6 +3505,16 @@ impl ChatWidget<'_> {
             }
         }
 
+        // External command plugins run before prompt expansion; images cannot be passed to them.
+        if message
+            .ordered_items
+            .iter()
+            .all(|item| matches!(item, InputItem::Text { .. }))
+            && self.try_run_command_plugin(&text_only)
+        {
+            return;
+        }
+
         // Expand user-defined custom prompts, supporting both "/prompts:name" and "/name" forms.
         match prompt_args::expand_custom_prompt(&text_only, self.bottom_pane.custom_prompts()) {
             Ok(Some(expanded)) => {
@@ -3603,6 +3623,7 @@ impl ChatWidget<'_> {
                 };
 
                 if let Some(task) = args_opt {
+                    self.warn_if_power_constrained(&format!("/{cmd_name}"));
                     let res = code_core::slash_commands::format_subagent_command(
                         cmd_name,
                         &task,
@@ -3813,6 +3834,7 @@ impl ChatWidget<'_> {
             if should_capture_snapshot {
                 let _ = self.capture_ghost_snapshot(prompt_summary);
             }
+            self.note_unused_turn_model(&message);
             self.dispatch_queued_user_message_now(message);
             return;
         }
@@ -3979,14 +4001,35 @@ impl ChatWidget<'_> {
 
         if !combined_items.is_empty() {
             self.flush_pending_agent_notes();
+            self.reset_answer_variants();
+            if let Some(model) = messages
+                .iter()
+                .rev()
+                .find_map(|message| message.model_override.clone())
+            {
+                let session_model = self.config.model.clone();
+                match self.code_op_tx.send(Op::SetNextTurnModel {
+                    model: model.clone(),
+                }) {
+                    Ok(()) => self.push_background_tail(format!(
+                        "This turn runs on {model}; the session stays on {session_model}."
+                    )),
+                    Err(e) => tracing::error!("failed to send Op::SetNextTurnModel: {e}"),
+                }
+            }
+            let final_output_json_schema =
+                self.output_schema.as_ref().map(|(_, schema)| schema.clone());
+            let structured = final_output_json_schema.is_some();
             if let Err(e) = self
                 .code_op_tx
                 .send(Op::UserInput {
                     items: combined_items,
-                    final_output_json_schema: None,
+                    final_output_json_schema,
                 })
             {
                 tracing::error!("failed to send Op::UserInput: {e}");
+            } else if structured {
+                self.structured_turn_pending = true;
             }
         }
 
@@ -4014,6 +4057,17 @@ impl ChatWidget<'_> {
         }
     }
 
+    /// `@@model` only applies to a message that starts a turn; say so when
+    /// the message joins one that is already running.
+    fn note_unused_turn_model(&mut self, message: &UserMessage) {
+        if let Some(model) = message.model_override.as_deref() {
+            let session_model = self.config.model.clone();
+            self.push_background_tail(format!(
+                "@@{model} ignored: this message joins the current turn on {session_model}."
+            ));
+        }
+    }
+
     pub(super) fn dispatch_queued_batch_via_coordinator(&mut self, batch: Vec<UserMessage>) {
         if batch.is_empty() {
             return;
@@ -4030,6 +4084,7 @@ impl ChatWidget<'_> {
                 tracing::info!("[queue] Skipping queued user input removed before dispatch");
                 continue;
             };
+            self.note_unused_turn_model(&message);
 
             let items = message.ordered_items.clone();
             match self.code_op_tx.send(Op::QueueUserInput { items }) {
@@ -4880,7 +4935,24 @@ impl ChatWidget<'_> {
         self.cell_order_seq.clear();
         self.cell_order_dbg.clear();
 
-        for record in &self.history_state.records {
+        // Only the tail is materialized up front. Older records that can be
+        // frozen become placeholders and are built (and laid out) on demand
+        // as the live window reaches them.
+        let hydrate_from = self
+            .history_state
+            .records
+            .len()
+            .saturating_sub(RESTORE_HYDRATED_TAIL);
+        for (idx, record) in self.history_state.records.iter().enumerate() {
+            if idx < hydrate_from && self.freeze_eligible_record(record) {
+                let id = record.id();
+                let kind = history_cell::kind_from_record(record, &self.config);
+                self.history_cells
+                    .push(Box::new(FrozenHistoryCell::unmeasured(id, kind)));
+                self.history_cell_ids.push(Some(id));
+                self.history_frozen_count = self.history_frozen_count.saturating_add(1);
+                continue;
+            }
             if let Some(mut cell) = self.build_cell_from_record(record) {
                 let id = record.id();
                 Self::assign_history_id_inner(&mut cell, id);
@@ -5151,6 +5223,9 @@ impl ChatWidget<'_> {
             display_text,
             ordered_items,
             suppress_persistence,
+            attachment_bundles,
+            redacted_values,
+            model_override: _,
         } = message;
 
         let combined_message_text = {
@@ -5179,6 +5254,30 @@ impl ChatWidget<'_> {
                 self.pending_user_prompts_for_next_turn.saturating_add(1);
         }
 
+        for bundle in attachment_bundles {
+            let key = self.next_req_key_after_prompt();
+            let _ = self.history_insert_with_key_global(
+                Box::new(history_cell::AttachmentsCell::new(bundle)),
+                key,
+            );
+        }
+        if redacted_values > 0 {
+            self.push_background_tail(format!(
+                "Attached files: {}.",
+                history_cell::redacted_values_label(redacted_values)
+            ));
+        }
+        let images = ordered_items
+            .iter()
+            .filter(|item| matches!(item, InputItem::Image { .. } | InputItem::LocalImage { .. }))
+            .count();
+        if images > 0 && code_core::metered::is_metered() {
+            self.push_background_tail(format!(
+                "Metered connection: {images} image{} not sent to the model (`/metered off` to send images).",
+                if images == 1 { "" } else { "s" }
+            ));
+        }
+
         self.flush_pending_agent_notes();
 
         if let Some(model_echo) = combined_message_text {
@@ -5217,42 +5316,10 @@ impl ChatWidget<'_> {
             }
 
         if scheduled_watchdog {
-            let tx = self.app_event_tx.clone();
             // Fire a CommitTick after ~10s to ensure the watchdog runs even when
             // no streaming/animation is active.
-            if thread_spawner::spawn_lightweight("queue-watchdog", move || {
-                std::thread::sleep(Duration::from_secs(10));
-                tx.send(crate::app_event::AppEvent::CommitTick);
-            })
-            .is_none()
-            {
-                // If we cannot spawn another lightweight thread (e.g., thread cap reached),
-                // fall back to a non-threaded timer using tokio when available, or a best-effort
-                // regular thread; as a last resort mark the timer expired and send immediately so
-                // the queue cannot remain blocked.
-                if let Ok(handle) = tokio::runtime::Handle::try_current() {
-                    let tx = self.app_event_tx.clone();
-                    handle.spawn(async move {
-                        tokio::time::sleep(Duration::from_secs(10)).await;
-                        tx.send(crate::app_event::AppEvent::CommitTick);
-                    });
-                } else {
-                    let tx = self.app_event_tx.clone();
-                    if std::thread::Builder::new()
-                        .name("queue-watchdog-fallback".to_string())
-                        .spawn(move || {
-                            std::thread::sleep(Duration::from_secs(10));
-                            tx.send(crate::app_event::AppEvent::CommitTick);
-                        })
-                        .is_err()
-                    {
-                        // No way to schedule a delayed tick; force the timer to appear expired
-                        // and emit a tick now to avoid indefinite blocking.
-                        self.queue_block_started_at = Some(Instant::now() - Duration::from_secs(10));
-                        self.app_event_tx.send(crate::app_event::AppEvent::CommitTick);
-                    }
-                }
-            }
+            self.app_event_tx
+                .send_after(Duration::from_secs(10), crate::app_event::AppEvent::CommitTick);
         }
 
         self.request_redraw();
@@ -5369,6 +5436,7 @@ impl ChatWidget<'_> {
                     return;
                 }
                 self.pending_turn_origin = Some(TurnOrigin::User);
+                self.reset_table_scroll();
                 let cleaned = Self::strip_context_sections(&text);
                 self.last_user_message = (!cleaned.trim().is_empty()).then_some(cleaned);
                 let user_message = self.parse_message_with_images(text);
@@ -5390,8 +5458,9 @@ impl ChatWidget<'_> {
         // Check clickable regions from last render and find matching action
         let action_opt: Option<ClickableAction> = {
             let regions = self.clickable_regions.borrow();
+            let history_regions = self.history_click_regions.borrow();
             
-            regions.iter().find_map(|region| {
+            regions.iter().chain(history_regions.iter()).find_map(|region| {
                 // Check if click is inside this region
                 if x >= region.rect.x
                     && x < region.rect.x + region.rect.width
@@ -5436,6 +5505,39 @@ impl ChatWidget<'_> {
                         self.app_event_tx.send(AppEvent::DispatchCommand(slash_cmd, cmd));
                     }
                 }
+                ClickableAction::ShowDiffForFile(path) => {
+                    self.show_diff_for_file(&path);
+                }
+                ClickableAction::ToggleAttachments(idx) => {
+                    if let Some(cell) = self.history_cells.get(idx).and_then(|cell| {
+                        cell.as_any()
+                            .downcast_ref::<crate::history_cell::AttachmentsCell>()
+                    }) {
+                        cell.toggle_collapsed();
+                        self.invalidate_cell_height(idx);
+                        self.request_redraw();
+                    }
+                }
+                ClickableAction::ToggleReviewPass(idx, section) => {
+                    if let Some(cell) = self.history_cells.get(idx).and_then(|cell| {
+                        cell.as_any()
+                            .downcast_ref::<crate::history_cell::ReviewPassesCell>()
+                    }) {
+                        cell.toggle_section(section);
+                        self.invalidate_cell_height(idx);
+                        self.request_redraw();
+                    }
+                }
+                ClickableAction::ToggleTestFailure(idx, failure) => {
+                    if let Some(cell) = self.history_cells.get(idx).and_then(|cell| {
+                        cell.as_any()
+                            .downcast_ref::<crate::history_cell::TestResultsCell>()
+                    }) {
+                        cell.toggle_failure(failure);
+                        self.invalidate_cell_height(idx);
+                        self.request_redraw();
+                    }
+                }
             }
         }
     }
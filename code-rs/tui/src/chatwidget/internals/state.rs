@@ -147,6 +147,22 @@ pub(crate) struct ExecState {
     >,
     pub(crate) suppressed_exec_end_call_ids: HashSet<ExecCallId>,
     pub(crate) suppressed_exec_end_order: VecDeque<ExecCallId>,
+    // Reruns requested via `/rerun` that have not reported an ExecEnd yet.
+    pub(crate) pending_reruns: HashSet<ExecCallId>,
+    pub(crate) last_rerun: Option<RerunOutcome>,
+    pub(crate) rerun_seq: u64,
+    // Full output of commands too large for history, for `/output`.
+    pub(crate) output_spill: exec_tools::output_spill::ExecOutputSpill,
+}
+
+/// Captured result of the most recent `/rerun`, kept so it can be shared with
+/// the model on request.
+#[derive(Clone, Debug)]
+pub(crate) struct RerunOutcome {
+    pub(crate) command: Vec<String>,
+    pub(crate) exit_code: i32,
+    pub(crate) stdout: String,
+    pub(crate) stderr: String,
 }
 
 impl ExecState {
@@ -242,17 +258,50 @@ pub(crate) struct LayoutState {
     pub(crate) last_frame_width: std::cell::Cell<u16>,
     // Last bottom pane area for mouse hit testing
     pub(crate) last_bottom_pane_area: std::cell::Cell<Rect>,
+    // Content at the top of the history viewport in the last frame, restored
+    // after a resize rewraps history
+    pub(crate) history_anchor: Cell<Option<HistoryAnchor>>,
+}
+
+/// The history row at the top of the viewport, as a cell and the rows into
+/// it, so a resize that rewraps history can bring the same content back to
+/// the top.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct HistoryAnchor {
+    /// Content width the offsets were measured at.
+    pub(crate) width: u16,
+    pub(crate) cell: usize,
+    pub(crate) row_in_cell: u16,
+    pub(crate) cell_height: u16,
 }
 
 #[derive(Default)]
 pub(crate) struct DiffsState {
     pub(crate) session_patch_sets: Vec<HashMap<PathBuf, code_core::protocol::FileChange>>,
     pub(crate) baseline_file_contents: HashMap<PathBuf, String>,
+    // What the session last left each changed file as; a mismatch on disk means
+    // the file was edited outside the session.
+    pub(crate) session_file_contents: HashMap<PathBuf, String>,
+    // Files edited outside the session, with the contents the session expected;
+    // drained by `/diff refresh`.
+    pub(crate) external_edits: HashMap<PathBuf, String>,
+    // Patches whose PatchApplyBegin arrived but whose End has not, keyed by call_id.
+    pub(crate) pending_applies: HashMap<String, AppliedPatchSet>,
+    // Successfully applied patches, most recent last; popped by `/undo-patch`.
+    pub(crate) undo_stack: Vec<AppliedPatchSet>,
     pub(crate) overlay: Option<DiffOverlay>,
     pub(crate) confirm: Option<DiffConfirm>,
     pub(crate) body_visible_rows: std::cell::Cell<u16>,
 }
 
+/// A patch as applied, plus the pre-apply contents of every path it touched
+/// (`None` when the file did not exist).
+#[derive(Clone, Debug)]
+pub(crate) struct AppliedPatchSet {
+    pub(crate) changes: HashMap<PathBuf, code_core::protocol::FileChange>,
+    pub(crate) before: HashMap<PathBuf, Option<String>>,
+}
+
 #[derive(Default)]
 pub(crate) struct HelpState {
     pub(crate) overlay: Option<HelpOverlay>,
@@ -264,12 +313,27 @@ pub(crate) struct SettingsState {
     pub(crate) overlay: Option<SettingsOverlayView>,
 }
 
+/// Keeps the overlay timeline bounded over long browser sessions.
+const MAX_BROWSER_SNAPSHOTS: usize = 50;
+
+/// Screenshot delivered with a `BrowserSnapshotEvent` (the per-turn capture or
+/// a re-capture from the browser overlay).
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct BrowserSnapshotEntry {
+    pub(crate) path: PathBuf,
+    pub(crate) url: Option<String>,
+    pub(crate) title: Option<String>,
+    pub(crate) captured_at: Option<String>,
+}
+
 pub(crate) struct BrowserOverlayState {
     pub(crate) session_key: RefCell<Option<String>>,
     pub(crate) screenshot_index: Cell<usize>,
     pub(crate) action_scroll: Cell<u16>,
     pub(crate) last_action_view_height: Cell<u16>,
     pub(crate) max_action_scroll: Cell<u16>,
+    pub(crate) fullscreen: Cell<bool>,
+    pub(crate) snapshots: RefCell<Vec<BrowserSnapshotEntry>>,
 }
 
 impl Default for BrowserOverlayState {
@@ -280,6 +344,8 @@ impl Default for BrowserOverlayState {
             action_scroll: Cell::new(0),
             last_action_view_height: Cell::new(0),
             max_action_scroll: Cell::new(0),
+            fullscreen: Cell::new(false),
+            snapshots: RefCell::new(Vec::new()),
         }
     }
 }
@@ -290,6 +356,7 @@ impl BrowserOverlayState {
         self.action_scroll.set(0);
         self.last_action_view_height.set(0);
         self.max_action_scroll.set(0);
+        self.fullscreen.set(false);
     }
 
     pub(crate) fn session_key(&self) -> Option<String> {
@@ -331,6 +398,204 @@ impl BrowserOverlayState {
     pub(crate) fn max_action_scroll(&self) -> u16 {
         self.max_action_scroll.get()
     }
+
+    pub(crate) fn fullscreen(&self) -> bool {
+        self.fullscreen.get()
+    }
+
+    pub(crate) fn set_fullscreen(&self, fullscreen: bool) {
+        self.fullscreen.set(fullscreen);
+    }
+
+    /// Adds a snapshot to the timeline; a repeat of the latest screenshot
+    /// only refreshes its details.
+    pub(crate) fn record_snapshot(&self, entry: BrowserSnapshotEntry) {
+        let mut snapshots = self.snapshots.borrow_mut();
+        if let Some(last) = snapshots.last_mut()
+            && last.path == entry.path
+        {
+            *last = entry;
+            return;
+        }
+        snapshots.push(entry);
+        if snapshots.len() > MAX_BROWSER_SNAPSHOTS {
+            let overflow = snapshots.len() - MAX_BROWSER_SNAPSHOTS;
+            snapshots.drain(0..overflow);
+        }
+    }
+
+    pub(crate) fn snapshots(&self) -> Vec<BrowserSnapshotEntry> {
+        self.snapshots.borrow().clone()
+    }
+}
+
+/// The plan pinned above the composer while a plan is in progress (Alt+P
+/// collapses it to one line).
+#[derive(Default)]
+pub(crate) struct PlanPanelState {
+    /// Steps of the latest `PlanUpdate`.
+    pub(crate) steps: Vec<code_core::plan_tool::PlanItemArg>,
+    pub(crate) collapsed: bool,
+    /// Bumped on every change so a reused frame repaints the panel.
+    pub(crate) revision: u64,
+}
+
+/// What the Auto Drive guardrails (`[auto_drive]` `max_consecutive_turns`,
+/// `max_cost_usd`, `check_in_every_turns`, `stop_patterns`) measure.
+#[derive(Default)]
+pub(crate) struct AutoGuardrailState {
+    /// Turns since the user last stepped in.
+    pub(crate) unattended_turns: u32,
+    /// Latest session cost reported by the core.
+    pub(crate) session_cost_usd: f64,
+    /// Session cost the cost guardrail counts from: the cost at launch,
+    /// moved up each time the user continues past that guardrail.
+    pub(crate) cost_baseline_usd: f64,
+    /// Final output of the last CLI turn, checked against `stop_patterns`.
+    pub(crate) last_output: Option<String>,
+    /// Guardrail holding the pending prompt.
+    pub(crate) fired: Option<AutoGuardrail>,
+}
+
+/// Answers to the same turn from `/retry`, shown one at a time in the
+/// turn's answer cell; `/retry a`, `/retry b`, … switch between them.
+#[derive(Default)]
+pub(crate) struct AnswerVariantsState {
+    /// Answers in the order they were produced, each with how it was sampled.
+    pub(crate) answers: Vec<(String, crate::history::state::AssistantMessageState)>,
+    /// Index into `answers` of the one on screen.
+    pub(crate) shown: usize,
+    /// How the retry in flight was sampled; its answer becomes the next variant.
+    pub(crate) pending: Option<String>,
+}
+
+/// Wall-clock times in the history gutter (`tui.timestamps`, Alt+T), and how
+/// long finished turns took.
+#[derive(Default)]
+pub(crate) struct CellTimestampsState {
+    pub(crate) enabled: bool,
+    /// When the turn in flight started.
+    pub(crate) turn_started_at: Option<SystemTime>,
+    /// Turn durations, keyed by the history id of the turn's final answer.
+    pub(crate) turn_durations: HashMap<HistoryId, Duration>,
+}
+
+/// Messages held on this device while the network is unavailable, sent in
+/// order once a connectivity probe succeeds. Mirrored to
+/// `CODE_HOME/offline_queue.json` so a restart does not lose them.
+#[derive(Default)]
+pub(crate) struct OfflineQueueState {
+    pub(crate) active: bool,
+    pub(crate) messages: VecDeque<UserMessage>,
+    /// A connectivity probe is running.
+    pub(crate) probing: bool,
+    /// The queue saved by an earlier run was already loaded.
+    pub(crate) restored: bool,
+}
+
+/// A turn that a crash or killed process cut short, found after the session
+/// was configured.
+#[derive(Default)]
+pub(crate) struct TurnRecoveryState {
+    /// The rollouts were already checked for an interrupted turn.
+    pub(crate) checked: bool,
+    /// The current session's interrupted turn, until it is acted on.
+    pub(crate) interrupted: Option<code_core::InterruptedTurn>,
+    /// A choice made before the interrupted turn was found (when resuming
+    /// from the startup prompt).
+    pub(crate) pending: Option<crate::app_event::TurnRecovery>,
+}
+
+/// What one `/context history` entry records.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ContextTimelineChange {
+    /// A full environment snapshot, as `name value` facts.
+    Baseline(Vec<(String, String)>),
+    /// One field that changed since the previous snapshot.
+    Delta(crate::history::state::ContextDeltaRecord),
+}
+
+/// An entry of the full context timeline. The context cell keeps only the
+/// last few changes; this keeps all of them for `/context history`.
+#[derive(Clone, Debug)]
+pub(crate) struct ContextTimelineEntry {
+    pub(crate) at: chrono::DateTime<chrono::Local>,
+    pub(crate) sequence: Option<u64>,
+    pub(crate) change: ContextTimelineChange,
+}
+
+/// The guardrail that paused an Auto Drive run.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum AutoGuardrail {
+    MaxConsecutiveTurns(u32),
+    MaxCost { limit: f64, spent: f64 },
+    CheckIn(u32),
+    StopPattern(String),
+}
+
+/// A finding from the last `/review`, as listed by `/review findings`.
+#[derive(Clone, Debug)]
+pub(crate) struct ReviewFindingEntry {
+    pub(crate) finding: code_core::protocol::ReviewFinding,
+    /// Specialized pass that reported it; `None` for the main pass.
+    pub(crate) pass: Option<String>,
+    pub(crate) resolved: bool,
+}
+
+#[derive(Default)]
+pub(crate) struct ReviewFindingsState {
+    pub(crate) entries: Vec<ReviewFindingEntry>,
+    /// Checkout the review looked at; fix prompts take their diff hunks
+    /// from here.
+    pub(crate) root: Option<PathBuf>,
+    /// Finding whose fix prompt is running; it is checked off when that
+    /// task completes.
+    pub(crate) fixing: Option<usize>,
+}
+
+#[derive(Default)]
+pub(crate) struct TestResultsState {
+    /// The last `run_tests` report; `/tests` lists its failures.
+    pub(crate) report: Option<code_core::test_runner::TestReport>,
+    /// Failing tests' output waiting for the next prompt, when
+    /// `tools.run_tests_attach_failures` is on.
+    pub(crate) pending_attachment: Option<String>,
+}
+
+#[derive(Default)]
+pub(crate) struct LspState {
+    /// Errors introduced by this turn's patches that are still reported,
+    /// per file; a fix-up turn is started for them when `lsp.auto_fix` is on.
+    pub(crate) introduced: HashMap<PathBuf, Vec<code_core::protocol::LspDiagnostic>>,
+    /// The running turn is a fix-up turn, which never starts another.
+    pub(crate) fixup_turn: bool,
+}
+
+/// One agent of a `/spawn` fan-out.
+#[derive(Clone, Debug)]
+pub(crate) struct SpawnAgentEntry {
+    pub(crate) id: String,
+    pub(crate) label: String,
+    pub(crate) finished: bool,
+    /// Its changes against the batch snapshot, once collected; `Err` when the
+    /// agent failed or left no worktree.
+    pub(crate) diff: Option<Result<String, String>>,
+}
+
+/// A `/spawn` fan-out: agents working the same prompt in their own
+/// worktrees, all started from one snapshot of the workspace.
+#[derive(Clone, Debug)]
+pub(crate) struct SpawnBatchState {
+    pub(crate) batch_id: String,
+    pub(crate) prompt: String,
+    pub(crate) root: PathBuf,
+    /// Snapshot commit every worktree was created from.
+    pub(crate) base: String,
+    pub(crate) agents: Vec<SpawnAgentEntry>,
+    /// Set once every agent finished and their diffs are being gathered.
+    pub(crate) collecting: bool,
+    /// Agent whose changes were merged into the workspace.
+    pub(crate) merged: Option<usize>,
 }
 
 #[derive(Default)]
@@ -354,3 +619,34 @@ pub(crate) struct PerfState {
     pub(crate) stats: RefCell<PerfStats>,
     pub(crate) pending_scroll_rows: Cell<u64>,
 }
+
+/// The last composed frame, kept so a frame with narrow damage only has to
+/// re-render the regions that changed.
+pub(crate) struct ComposedFrame {
+    pub(crate) areas: [Rect; 3],
+    pub(crate) history: HistoryFingerprint,
+    pub(crate) buffer: Buffer,
+}
+
+/// Cheap summary of what the history viewport depends on. A change here
+/// forces history to re-render even when no request marked it dirty.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HistoryFingerprint {
+    pub(crate) cells: usize,
+    pub(crate) scroll_offset: u16,
+    pub(crate) queued_messages: usize,
+    pub(crate) streaming_rows: usize,
+    pub(crate) theme_epoch: u64,
+    pub(crate) invalidations: HeightCacheInvalidations,
+    pub(crate) plan_panel: u64,
+}
+
+#[derive(Default)]
+pub(crate) struct FrameRegionState {
+    /// Damage handed over by the app for the next render.
+    pub(crate) damage: Cell<Option<crate::dirty_regions::FrameDamage>>,
+    pub(crate) composed: RefCell<Option<ComposedFrame>>,
+    /// Debug HUD (`/perf hud`): frame rate and what the last frame redrew.
+    pub(crate) hud_enabled: bool,
+    pub(crate) recent_frames: RefCell<VecDeque<Instant>>,
+}
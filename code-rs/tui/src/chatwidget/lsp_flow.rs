@@ -0,0 +1,120 @@
+use super::*;
+use code_core::protocol::DiagnosticSeverity;
+use code_core::protocol::LspDiagnostic;
+use code_core::protocol::LspDiagnosticsEvent;
+
+/// Errors listed in a fix-up prompt before the rest are counted.
+const MAX_FIXUP_ERRORS: usize = 20;
+
+fn fixup_prompt(introduced: &HashMap<PathBuf, Vec<LspDiagnostic>>, cwd: &Path) -> String {
+    let mut files: Vec<_> = introduced.iter().collect();
+    files.sort_by(|a, b| a.0.cmp(b.0));
+    let errors: Vec<String> = files
+        .into_iter()
+        .flat_map(|(path, diagnostics)| {
+            let path = path.strip_prefix(cwd).unwrap_or(path).display().to_string();
+            diagnostics.iter().map(move |diagnostic| {
+                let source = diagnostic
+                    .source
+                    .as_deref()
+                    .map(|source| format!(" [{source}]"))
+                    .unwrap_or_default();
+                format!(
+                    "- {path}:{}:{}{source} {}",
+                    diagnostic.line, diagnostic.column, diagnostic.message
+                )
+            })
+        })
+        .collect();
+    let mut prompt = format!(
+        "The language server still reports {} error{} introduced by your last changes:\n\n",
+        errors.len(),
+        if errors.len() == 1 { "" } else { "s" }
+    );
+    prompt.push_str(&errors[..errors.len().min(MAX_FIXUP_ERRORS)].join("\n"));
+    if errors.len() > MAX_FIXUP_ERRORS {
+        prompt.push_str(&format!("\n… and {} more", errors.len() - MAX_FIXUP_ERRORS));
+    }
+    prompt.push_str("\n\nFix them without changing unrelated code.");
+    prompt
+}
+
+impl ChatWidget<'_> {
+    /// Shows the diagnostics of an applied patch and tracks the errors this
+    /// turn introduced: new ones are added, and ones the server no longer
+    /// reports drop out.
+    pub(super) fn handle_lsp_diagnostics_event(&mut self, event: LspDiagnosticsEvent) {
+        for file in &event.files {
+            let previous = self.lsp.introduced.remove(&file.path).unwrap_or_default();
+            let still_introduced: Vec<LspDiagnostic> = file
+                .diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error)
+                .filter(|diagnostic| {
+                    diagnostic.is_new || previous.iter().any(|old| old.same_problem(diagnostic))
+                })
+                .cloned()
+                .collect();
+            if !still_introduced.is_empty() {
+                self.lsp.introduced.insert(file.path.clone(), still_introduced);
+            }
+        }
+        let cell = history_cell::LspDiagnosticsCell::new(&event, &self.config.cwd);
+        self.history_push(cell);
+        self.request_redraw();
+    }
+
+    /// Called once a turn ends. With `lsp.auto_fix`, errors the turn
+    /// introduced and left behind start one fix-up turn; a fix-up turn never
+    /// starts another.
+    pub(super) fn maybe_start_lsp_fixup(&mut self) {
+        let introduced = std::mem::take(&mut self.lsp.introduced);
+        let was_fixup = std::mem::take(&mut self.lsp.fixup_turn);
+        if introduced.is_empty() || !self.config.lsp.auto_fix {
+            return;
+        }
+        if was_fixup {
+            let count: usize = introduced.values().map(Vec::len).sum();
+            self.push_background_tail(format!(
+                "The language server still reports {count} error{} after the fix-up turn.",
+                if count == 1 { "" } else { "s" }
+            ));
+            return;
+        }
+        let count: usize = introduced.values().map(Vec::len).sum();
+        let display = format!(
+            "Fix {count} language server error{}",
+            if count == 1 { "" } else { "s" }
+        );
+        let prompt = fixup_prompt(&introduced, &self.config.cwd);
+        self.lsp.fixup_turn = true;
+        self.submit_prompt_with_display(display, prompt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixup_prompt_lists_errors_relative_to_cwd() {
+        let error = |line: u32, message: &str| LspDiagnostic {
+            line,
+            column: 5,
+            severity: DiagnosticSeverity::Error,
+            message: message.to_string(),
+            source: Some("rustc".to_string()),
+            code: None,
+            is_new: true,
+        };
+        let mut introduced = HashMap::new();
+        introduced.insert(
+            PathBuf::from("/repo/src/lib.rs"),
+            vec![error(3, "mismatched types"), error(9, "cannot find value `y`")],
+        );
+        assert_eq!(
+            fixup_prompt(&introduced, Path::new("/repo")),
+            "The language server still reports 2 errors introduced by your last changes:\n\n- src/lib.rs:3:5 [rustc] mismatched types\n- src/lib.rs:9:5 [rustc] cannot find value `y`\n\nFix them without changing unrelated code."
+        );
+    }
+}
@@ -13,6 +13,39 @@ pub struct UserMessage {
     pub ordered_items: Vec<InputItem>,
     /// Skip adding this message to the persisted history when true.
     pub suppress_persistence: bool,
+    /// Directory and glob mentions expanded into `ordered_items`, listed in
+    /// history so the user can see exactly which files were sent.
+    #[serde(default)]
+    pub attachment_bundles: Vec<AttachmentBundle>,
+    /// Secret values masked by `[redaction]` in single-file `@path`
+    /// attachments; bundles count their own.
+    #[serde(default)]
+    pub redacted_values: usize,
+    /// Model for the turn this message starts, from a leading `@@model`.
+    #[serde(default)]
+    pub model_override: Option<String>,
+}
+
+/// Files sent for one `@dir/` or `@glob` mention.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AttachmentBundle {
+    /// The mention as typed, without the `@`.
+    pub pattern: String,
+    pub files: Vec<AttachedFile>,
+    /// Matching files left out once the size budget ran out.
+    pub omitted: usize,
+    /// Secret values masked in the attached files by `[redaction]`.
+    #[serde(default)]
+    pub redacted: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AttachedFile {
+    pub path: String,
+    /// Size of the file on disk.
+    pub bytes: usize,
+    /// Bytes included in the bundle; less than `bytes` when truncated.
+    pub sent_bytes: usize,
 }
 
 impl From<String> for UserMessage {
@@ -25,10 +58,26 @@ impl From<String> for UserMessage {
             display_text: text,
             ordered_items: ordered,
             suppress_persistence: false,
+            attachment_bundles: Vec::new(),
+            redacted_values: 0,
+            model_override: None,
         }
     }
 }
 
+/// Splits a leading `@@model` off a message: `@@gpt-5-mini why?` runs one
+/// turn on `gpt-5-mini`. Returns `None` unless both a model and some text
+/// follow the marker.
+pub(crate) fn split_turn_model_prefix(text: &str) -> Option<(String, String)> {
+    let rest = text.trim_start().strip_prefix("@@")?;
+    let (model, message) = rest.split_once(char::is_whitespace)?;
+    let message = message.trim_start();
+    if model.is_empty() || message.is_empty() {
+        return None;
+    }
+    Some((model.to_string(), message.to_string()))
+}
+
 pub fn create_initial_user_message(text: String, image_paths: Vec<PathBuf>) -> Option<UserMessage> {
     if text.is_empty() && image_paths.is_empty() {
         None
@@ -46,6 +95,25 @@ pub fn create_initial_user_message(text: String, image_paths: Vec<PathBuf>) -> O
             display_text: text,
             ordered_items: ordered,
             suppress_persistence: false,
+            attachment_bundles: Vec::new(),
+            redacted_values: 0,
+            model_override: None,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turn_model_prefix_needs_model_and_text() {
+        assert_eq!(
+            split_turn_model_prefix("  @@gpt-5-mini  what does this do?"),
+            Some(("gpt-5-mini".to_string(), "what does this do?".to_string()))
+        );
+        assert_eq!(split_turn_model_prefix("@@gpt-5-mini"), None);
+        assert_eq!(split_turn_model_prefix("@@ question"), None);
+        assert_eq!(split_turn_model_prefix("ask @@gpt-5-mini later"), None);
+    }
+}
@@ -0,0 +1,42 @@
+use super::*;
+
+use code_core::config_types::MeteredConnection;
+
+impl ChatWidget<'_> {
+    /// `/metered on|off|auto` switches data saving for the rest of the run;
+    /// `/metered` alone shows the current mode.
+    pub(crate) fn handle_metered_command(&mut self, args: String) {
+        let mode = match args.trim().to_ascii_lowercase().as_str() {
+            "" | "status" => None,
+            "on" => Some(MeteredConnection::On),
+            "off" => Some(MeteredConnection::Off),
+            "auto" => Some(MeteredConnection::Auto),
+            other => {
+                self.history_push_plain_state(crate::history_cell::new_error_event(format!(
+                    "`/metered` — unknown mode `{other}`. Use `/metered on`, `/metered off` or `/metered auto`."
+                )));
+                self.request_redraw();
+                return;
+            }
+        };
+        if let Some(mode) = mode {
+            code_core::metered::set_metered_connection(mode);
+            self.config.metered_connection = mode;
+        }
+        let mode = code_core::metered::metered_connection();
+        let message = match (mode, code_core::metered::is_metered()) {
+            (MeteredConnection::On, _) => {
+                "Metered connection: on. Images and browser screenshots are not sent and optional context is skipped.".to_string()
+            }
+            (MeteredConnection::Off, _) => "Metered connection: off. Images and screenshots are sent as usual.".to_string(),
+            (MeteredConnection::Auto, true) => {
+                "Metered connection: auto (no Wi-Fi, saving data). Images and browser screenshots are not sent.".to_string()
+            }
+            (MeteredConnection::Auto, false) => {
+                "Metered connection: auto. Data is saved when Termux reports no Wi-Fi; `/metered on` forces it.".to_string()
+            }
+        };
+        self.push_background_tail(message);
+        self.request_redraw();
+    }
+}
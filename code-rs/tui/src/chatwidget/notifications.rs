@@ -1,5 +1,7 @@
 use super::*;
 
+use code_core::config_types::FeedbackKind;
+
 impl ChatWidget<'_> {
     pub(super) fn clear_backgrounds_in(&self, buf: &mut Buffer, rect: Rect) {
         for y in rect.y..rect.y.saturating_add(rect.height) {
@@ -63,6 +65,48 @@ impl ChatWidget<'_> {
         });
     }
 
+    pub(super) fn emit_feedback(&self, kind: FeedbackKind) {
+        if kind == FeedbackKind::None || self.replay_history_depth > 0 {
+            return;
+        }
+        self.app_event_tx.send(AppEvent::EmitFeedback(kind));
+    }
+
+    pub(super) fn emit_approval_notification(&self, call_id: &str, title: &str, detail: &str) {
+        if !self.should_emit_tui_notification("approval-requested") {
+            return;
+        }
+
+        self.app_event_tx.send(AppEvent::EmitApprovalNotification {
+            call_id: call_id.to_string(),
+            title: title.to_string(),
+            detail: Self::notification_snippet(detail),
+        });
+    }
+
+    /// Apply an approve/deny decision that arrived from outside the TUI.
+    pub(crate) fn resolve_external_approval(&mut self, call_id: &str, approved: bool) {
+        let decision = if approved {
+            ReviewDecision::Approved
+        } else {
+            ReviewDecision::Denied
+        };
+        if !self.bottom_pane.resolve_approval(call_id, decision) {
+            tracing::debug!("external approval for unknown call {call_id} ignored");
+        }
+    }
+
+    /// Warn before a long multi-agent run when the device is low on battery
+    /// or running hot. The run still starts.
+    pub(super) fn warn_if_power_constrained(&mut self, activity: &str) {
+        let Some(constraint) = code_core::battery::cached_background_work_constraint() else {
+            return;
+        };
+        self.push_background_tail(format!(
+            "⚠️ {constraint}: {activity} can run for a long time and drain the battery. Consider plugging in."
+        ));
+    }
+
     pub(super) fn should_emit_tui_notification(&self, event: &str) -> bool {
         if self.replay_history_depth > 0 {
             return false;
@@ -0,0 +1,150 @@
+use super::*;
+
+use std::collections::BTreeMap;
+
+const OFFLINE_QUEUE_FILE: &str = "offline_queue.json";
+
+/// Queues saved in `CODE_HOME/offline_queue.json`, keyed by working directory.
+type StoredQueues = BTreeMap<String, Vec<UserMessage>>;
+
+fn read_stored_queues(code_home: &Path) -> StoredQueues {
+    std::fs::read(code_home.join(OFFLINE_QUEUE_FILE))
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+fn write_stored_queues(code_home: &Path, queues: &StoredQueues) -> std::io::Result<()> {
+    let path = code_home.join(OFFLINE_QUEUE_FILE);
+    if queues.is_empty() {
+        return match std::fs::remove_file(&path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        };
+    }
+    let data = serde_json::to_vec_pretty(queues).map_err(std::io::Error::other)?;
+    std::fs::create_dir_all(code_home)?;
+    std::fs::write(path, data)
+}
+
+impl ChatWidget<'_> {
+    /// The core is waiting for the network: hold new messages on this device
+    /// until a probe reaches the provider again.
+    pub(super) fn enter_offline_mode(&mut self) {
+        if self.offline.active {
+            return;
+        }
+        self.offline.active = true;
+        self.push_background_tail(
+            "Offline: messages you send are queued on this device and sent in order once the connection is back."
+                .to_string(),
+        );
+        self.start_connectivity_probe();
+    }
+
+    /// Holds `message` until the connection is back.
+    pub(super) fn queue_offline_message(&mut self, message: UserMessage) {
+        self.offline.messages.push_back(message);
+        self.persist_offline_queue();
+        self.bottom_pane.update_status_text(format!(
+            "Offline · {} queued",
+            self.offline.messages.len()
+        ));
+        self.request_redraw();
+    }
+
+    /// Sends messages queued while offline, oldest first.
+    pub(crate) fn on_connectivity_restored(&mut self) {
+        self.offline.probing = false;
+        if !self.offline.active {
+            return;
+        }
+        self.offline.active = false;
+        let messages: Vec<UserMessage> = self.offline.messages.drain(..).collect();
+        if !messages.is_empty() {
+            let count = messages.len();
+            self.push_background_tail(format!(
+                "Back online; sending {count} queued message{}.",
+                if count == 1 { "" } else { "s" }
+            ));
+        }
+        for message in messages {
+            self.submit_user_message(message);
+        }
+        self.persist_offline_queue();
+        self.request_redraw();
+    }
+
+    /// Loads messages an earlier run queued offline in this directory; they
+    /// are sent once a connectivity probe succeeds.
+    pub(super) fn restore_offline_queue(&mut self) {
+        if self.offline.restored {
+            return;
+        }
+        self.offline.restored = true;
+        let key = self.config.cwd.display().to_string();
+        let Some(messages) = read_stored_queues(&self.config.code_home).remove(&key) else {
+            return;
+        };
+        if messages.is_empty() {
+            return;
+        }
+        let count = messages.len();
+        self.offline.messages = messages.into();
+        self.offline.active = true;
+        self.push_background_tail(format!(
+            "{count} message{} queued offline in an earlier session; sending once the connection is back.",
+            if count == 1 { "" } else { "s" }
+        ));
+        self.start_connectivity_probe();
+    }
+
+    fn start_connectivity_probe(&mut self) {
+        if self.offline.probing {
+            return;
+        }
+        self.offline.probing = true;
+        let probe = self.config.model_provider.base_url_for_probe();
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            code_core::util::wait_for_connectivity(&probe).await;
+            tx.send(AppEvent::ConnectivityRestored);
+        });
+    }
+
+    fn persist_offline_queue(&self) {
+        let code_home = &self.config.code_home;
+        let key = self.config.cwd.display().to_string();
+        let mut queues = read_stored_queues(code_home);
+        if self.offline.messages.is_empty() {
+            if queues.remove(&key).is_none() {
+                return;
+            }
+        } else {
+            queues.insert(key, self.offline.messages.iter().cloned().collect());
+        }
+        if let Err(err) = write_stored_queues(code_home, &queues) {
+            tracing::warn!("failed to persist the offline queue: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stored_queues_round_trip_and_clear() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut queues = StoredQueues::new();
+        queues.insert(
+            "/work/app".to_string(),
+            vec![UserMessage::from("first".to_string()), UserMessage::from("second".to_string())],
+        );
+        write_stored_queues(dir.path(), &queues).expect("write");
+        assert_eq!(read_stored_queues(dir.path()), queues);
+
+        write_stored_queues(dir.path(), &StoredQueues::new()).expect("clear");
+        assert!(!dir.path().join(OFFLINE_QUEUE_FILE).exists());
+    }
+}
@@ -47,6 +47,23 @@ impl ChatWidget<'_> {
         format!("O:req={} out={} seq={}", ok.req, ok.out, ok.seq)
     }
 
+    /// Label for the order overlay: the cell's key as `req:out:seq` followed
+    /// by the tag it was inserted with.
+    pub(super) fn order_overlay_label(&self, idx: usize) -> Option<String> {
+        let key = self.cell_order_seq.get(idx)?;
+        let mut label = format!("{}:{}:{}", key.req, key.out, key.seq);
+        if let Some(tag) = self
+            .cell_order_dbg
+            .get(idx)
+            .and_then(Option::as_deref)
+            .and_then(|dbg| dbg.split_whitespace().find_map(|part| part.strip_prefix("tag=")))
+        {
+            label.push(' ');
+            label.push_str(tag);
+        }
+        Some(label)
+    }
+
     pub(super) fn order_key_successor(after: OrderKey) -> OrderKey {
         if after.seq != u64::MAX {
             return OrderKey {
@@ -0,0 +1,59 @@
+use super::*;
+
+impl ChatWidget<'_> {
+    /// `/output [n]`: opens the full output of the nth most recent command
+    /// (running or finished) in the pager when it was spilled to disk.
+    pub(crate) fn handle_output_command(&mut self, args: String) {
+        let args = args.trim();
+        let nth = if args.is_empty() {
+            1
+        } else {
+            match args.parse::<usize>() {
+                Ok(n) if n > 0 => n,
+                _ => {
+                    self.history_push_plain_state(crate::history_cell::new_error_event(
+                        "Usage: /output [n] to page through the nth most recent command's output"
+                            .to_string(),
+                    ));
+                    self.request_redraw();
+                    return;
+                }
+            }
+        };
+
+        let Some((call_id, clipped)) = self
+            .recent_exec_records()
+            .nth(nth.saturating_sub(1))
+            .map(|exec| {
+                let clipped = [&exec.stdout_chunks, &exec.stderr_chunks]
+                    .iter()
+                    .any(|chunks| chunks.first().is_some_and(|chunk| chunk.offset > 0));
+                (exec.call_id.clone(), clipped)
+            })
+        else {
+            self.history_push_plain_state(crate::history_cell::new_error_event(
+                "`/output` — no command found in history".to_string(),
+            ));
+            self.request_redraw();
+            return;
+        };
+
+        let path = call_id
+            .as_deref()
+            .and_then(|id| self.exec.output_spill.path(id))
+            .map(Path::to_path_buf);
+        match path {
+            Some(path) => self.app_event_tx.send(AppEvent::OpenOutputPager { path }),
+            None if clipped => {
+                self.history_push_plain_state(crate::history_cell::new_error_event(
+                    "`/output` — the full output of that command is no longer available"
+                        .to_string(),
+                ));
+            }
+            None => {
+                self.push_background_tail("That command's output is already shown in full.");
+            }
+        }
+        self.request_redraw();
+    }
+}
@@ -67,33 +67,19 @@ impl ChatWidget<'_> {
         let overlay_tracker = self.browser_overlay_tracker();
         let cell_opt = overlay_tracker.as_ref().map(|(_, tracker)| &tracker.cell);
 
-        let (screenshot_history, mut selected_index) = if let Some(cell) = cell_opt {
-            let history = cell.screenshot_history();
-            if history.is_empty() {
-                (None, 0usize)
-            } else {
-                let mut index = self.browser_overlay_state.screenshot_index();
-                if index >= history.len() {
-                    index = history.len().saturating_sub(1);
-                    self.browser_overlay_state.set_screenshot_index(index);
-                }
-                (Some(history), index)
-            }
-        } else {
-            (None, 0usize)
-        };
-
-        let screenshot_count = screenshot_history.map_or(0, <[_]>::len);
+        let timeline = self.browser_overlay_timeline();
+        let screenshot_count = timeline.len();
+        let mut selected_index = self.browser_overlay_state.screenshot_index();
         if screenshot_count == 0 {
             selected_index = 0;
+        } else if selected_index >= screenshot_count {
+            selected_index = screenshot_count - 1;
+            self.browser_overlay_state.set_screenshot_index(selected_index);
         }
+        let selected_shot = timeline.get(selected_index);
 
-        let mut screenshot_path = screenshot_history
-            .and_then(|history| history.get(selected_index))
-            .map(|record| record.path.clone());
-        let mut screenshot_url = screenshot_history
-            .and_then(|history| history.get(selected_index))
-            .and_then(|record| record.url.clone());
+        let mut screenshot_path = selected_shot.map(|shot| shot.path.clone());
+        let mut screenshot_url = selected_shot.and_then(|shot| shot.url.clone());
 
         if screenshot_path.is_none()
             && let Ok(latest) = self.latest_browser_screenshot.lock()
@@ -108,10 +94,47 @@ impl ChatWidget<'_> {
             .map(crate::history_cell::BrowserSessionCell::summary_label)
             .unwrap_or_else(|| self.browser_title().to_string());
         let summary_value = screenshot_url
-            
+            .clone()
             .filter(|value| !value.is_empty())
             .unwrap_or_else(|| summary_label.clone());
 
+        if self.browser_overlay_state.fullscreen() {
+            let header_area = Rect {
+                x: content.x,
+                y: content.y,
+                width: content.width,
+                height: 1,
+            };
+            let heading = selected_shot
+                .and_then(|shot| shot.title.clone())
+                .unwrap_or_else(|| summary_value.clone());
+            Paragraph::new(RLine::from(vec![
+                Span::styled(heading, Style::default().fg(crate::colors::text())),
+                Span::styled(
+                    "  Esc/f exit full screen",
+                    Style::default().fg(crate::colors::text_dim()),
+                ),
+            ]))
+            .render(header_area, buf);
+            let image_area = Rect {
+                x: content.x,
+                y: content.y.saturating_add(1),
+                width: content.width,
+                height: content.height.saturating_sub(1),
+            };
+            if image_area.height > 0 {
+                if let Some(path) = screenshot_path.as_ref() {
+                    self.render_screenshot_highlevel(path, image_area, buf);
+                } else {
+                    Paragraph::new("No browser screenshot captured yet.")
+                        .alignment(Alignment::Center)
+                        .style(Style::default().fg(crate::colors::text_dim()))
+                        .render(image_area, buf);
+                }
+            }
+            return;
+        }
+
         let screenshot_info = if screenshot_count > 0 {
             format!("Shot {}/{}", selected_index + 1, screenshot_count)
         } else {
@@ -235,18 +258,16 @@ impl ChatWidget<'_> {
             }
         }
 
-        let current_time = screenshot_history
-            .and_then(|history| history.get(selected_index))
-            .map(|record| record.timestamp)
-            .unwrap_or_else(|| Duration::ZERO);
+        let current_time = selected_shot
+            .and_then(|shot| shot.timestamp)
+            .unwrap_or(Duration::ZERO);
         let mut total_time = overlay_tracker
             .as_ref()
             .map(|(_, tracker)| tracker.elapsed)
             .unwrap_or_else(|| Duration::ZERO);
-        if let Some(history) = screenshot_history
-            && let Some(last) = history.last() {
-                total_time = total_time.max(last.timestamp);
-            }
+        if let Some(last) = timeline.iter().filter_map(|shot| shot.timestamp).max() {
+            total_time = total_time.max(last);
+        }
         if let Some(cell) = cell_opt {
             total_time = total_time.max(cell.total_duration());
         }
@@ -274,40 +295,40 @@ impl ChatWidget<'_> {
 
         info_lines.push(RLine::from(vec![Span::styled("Screenshots", header_style)]));
 
-        if let Some(history) = screenshot_history {
-            if history.is_empty() {
-                info_lines.push(RLine::from(vec![Span::styled(
-                    "No screenshots yet",
-                    secondary_style,
-                )]));
-            } else {
-                for (idx, entry) in history.iter().enumerate() {
-                    let mut spans: Vec<Span> = Vec::new();
-                    let marker = if idx == selected_index { "◉" } else { "•" };
-                    let marker_style = if idx == selected_index {
-                        Style::default().fg(crate::colors::primary())
-                    } else {
-                        secondary_style
-                    };
-                    spans.push(Span::styled(marker.to_string(), marker_style));
-                    spans.push(Span::raw(" "));
-                    spans.push(Span::styled(
-                        self.format_overlay_mm_ss(entry.timestamp),
-                        secondary_style,
-                    ));
-                    if let Some(url) = entry.url.as_ref()
-                        && !url.trim().is_empty() {
-                            spans.push(Span::raw("  "));
-                            spans.push(Span::styled(url.clone(), primary_style));
-                        }
-                    info_lines.push(RLine::from(spans));
-                }
-            }
-        } else {
+        if timeline.is_empty() {
             info_lines.push(RLine::from(vec![Span::styled(
-                "No browser session yet",
+                "No screenshots yet",
                 secondary_style,
             )]));
+        } else {
+            for (idx, shot) in timeline.iter().enumerate() {
+                let mut spans: Vec<Span> = Vec::new();
+                let marker = if idx == selected_index { "◉" } else { "•" };
+                let marker_style = if idx == selected_index {
+                    Style::default().fg(crate::colors::primary())
+                } else {
+                    secondary_style
+                };
+                spans.push(Span::styled(marker.to_string(), marker_style));
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(shot.time_label(self), secondary_style));
+                if let Some(title) = shot.title.as_ref()
+                    && !title.trim().is_empty() {
+                        spans.push(Span::raw("  "));
+                        spans.push(Span::styled(title.clone(), primary_style));
+                    }
+                if let Some(url) = shot.url.as_ref()
+                    && !url.trim().is_empty() {
+                        let url_style = if shot.title.is_some() {
+                            secondary_style
+                        } else {
+                            primary_style
+                        };
+                        spans.push(Span::raw("  "));
+                        spans.push(Span::styled(url.clone(), url_style));
+                    }
+                info_lines.push(RLine::from(spans));
+            }
         }
 
         info_lines.push(RLine::from(vec![Span::raw(String::new())]));
@@ -349,7 +370,7 @@ impl ChatWidget<'_> {
 
         info_lines.push(RLine::from(vec![Span::raw(String::new())]));
         info_lines.push(RLine::from(vec![Span::styled(
-            "Controls: ←/→ or ↑/↓ select screenshot • Shift+↑/↓ or j/k scroll actions",
+            "Controls: ←/→ or ↑/↓ select screenshot • Enter/f full screen • r re-capture • c copy URL • Shift+↑/↓ or j/k scroll actions",
             secondary_style,
         )]));
 
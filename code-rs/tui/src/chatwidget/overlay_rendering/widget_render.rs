@@ -1,5 +1,9 @@
 use super::*;
 
+use crate::dirty_regions::Regions;
+
+mod diff_tree;
+mod frame_regions;
 mod history_scroller;
 mod overlay_stack;
 mod pending_command;
@@ -15,15 +19,6 @@ impl ChatWidget<'_> {
             None
         };
 
-        // Safety clear for non-standard mode: keep a stable background even
-        // when downstream widgets intentionally skip unchanged regions.
-        if !self.standard_terminal_mode {
-            let bg_style = Style::default()
-                .bg(crate::colors::background())
-                .fg(crate::colors::text());
-            fill_rect(buf, area, None, bg_style);
-        }
-
         // Remember full frame size for layout and hit testing.
         self.layout.last_frame_height.set(area.height);
         self.layout.last_frame_width.set(area.width);
@@ -37,67 +32,109 @@ impl ChatWidget<'_> {
             .set(bottom_pane_area.height);
         self.layout.last_bottom_pane_area.set(bottom_pane_area);
 
-        if !self.standard_terminal_mode {
-            self.render_status_bar(status_bar_area, buf);
-        }
-
         if self.standard_terminal_mode {
             ratatui::widgets::WidgetRef::render_ref(&(&self.bottom_pane), bottom_pane_area, buf);
             self.clear_backgrounds_in(buf, bottom_pane_area);
             return;
         }
 
-        let padding = 1u16;
-        let content_area = Rect {
-            x: history_area.x + padding,
-            y: history_area.y,
-            width: history_area.width.saturating_sub(padding * 2),
-            height: history_area.height,
-        };
-
-        self.update_welcome_height_hint(content_area.width, content_area.height);
-
-        let base_style = Style::default()
-            .bg(crate::colors::background())
-            .fg(crate::colors::text());
-        // Clear the full history viewport once so reused rows/gutters do not
-        // retain stale paint from previous frames.
-        fill_rect(buf, history_area, Some(' '), base_style);
-
+        let cause = self
+            .frame_regions
+            .damage
+            .get()
+            .map_or("render", |damage| damage.cause);
         let streaming_lines = self
             .live_builder
             .display_rows()
             .into_iter()
             .map(|r| ratatui::text::Line::from(r.text))
             .collect::<Vec<_>>();
-        let streaming_cell = if !streaming_lines.is_empty() {
-            let state = self.synthesize_stream_state_from_lines(None, &streaming_lines, true);
-            Some(history_cell::new_streaming_content(state, &self.config))
-        } else {
-            None
-        };
+        let frame_areas = [status_bar_area, history_area, bottom_pane_area];
+        let history_fingerprint = self.history_fingerprint(streaming_lines.len());
+        let regions = self.begin_frame_regions(frame_areas, history_fingerprint, buf);
+        let reused = !regions.is_full();
+
+        // Safety clear: keep a stable background even when downstream widgets
+        // intentionally skip unchanged regions.
+        if !reused {
+            let bg_style = Style::default()
+                .bg(crate::colors::background())
+                .fg(crate::colors::text());
+            fill_rect(buf, area, None, bg_style);
+        }
 
-        let mut queued_preview_cells: Vec<crate::history_cell::PlainHistoryCell> =
-            Vec::with_capacity(self.queued_user_messages.len());
-        if !self.queued_user_messages.is_empty() {
-            for qm in &self.queued_user_messages {
-                let state = history_cell::new_queued_user_prompt(qm.display_text.clone());
-                queued_preview_cells.push(crate::history_cell::PlainHistoryCell::from_state(state));
+        if regions.contains(Regions::STATUS_BAR) {
+            if reused {
+                self.clear_region_for_repaint(buf, status_bar_area);
             }
+            self.render_status_bar(status_bar_area, buf);
         }
 
-        self.render_history_scroller(
-            history_area,
-            content_area,
-            base_style,
-            streaming_cell,
-            queued_preview_cells,
-            buf,
-        );
+        if regions.contains(Regions::HISTORY) {
+            let (scroll_area, plan_panel_area) = self.split_plan_panel(history_area);
+            let padding = 1u16;
+            let content_area = Rect {
+                x: scroll_area.x + padding,
+                y: scroll_area.y,
+                width: scroll_area.width.saturating_sub(padding * 2),
+                height: scroll_area.height,
+            };
+
+            self.update_welcome_height_hint(content_area.width, content_area.height);
+
+            let base_style = Style::default()
+                .bg(crate::colors::background())
+                .fg(crate::colors::text());
+            if reused {
+                self.clear_region_for_repaint(buf, history_area);
+            }
+            // Clear the full history viewport once so reused rows/gutters do not
+            // retain stale paint from previous frames.
+            fill_rect(buf, history_area, Some(' '), base_style);
 
-        self.render_terminal_overlay_and_bottom_pane(area, history_area, bottom_pane_area, buf);
+            let streaming_cell = if !streaming_lines.is_empty() {
+                let state = self.synthesize_stream_state_from_lines(None, &streaming_lines, true);
+                Some(history_cell::new_streaming_content(state, &self.config))
+            } else {
+                None
+            };
+
+            let mut queued_preview_cells: Vec<crate::history_cell::PlainHistoryCell> =
+                Vec::with_capacity(self.queued_user_messages.len());
+            if !self.queued_user_messages.is_empty() || !self.offline.messages.is_empty() {
+                for qm in self.queued_user_messages.iter().chain(&self.offline.messages) {
+                    let state = history_cell::new_queued_user_prompt(qm.display_text.clone());
+                    queued_preview_cells.push(crate::history_cell::PlainHistoryCell::from_state(state));
+                }
+            }
+
+            self.render_history_scroller(
+                scroll_area,
+                content_area,
+                base_style,
+                streaming_cell,
+                queued_preview_cells,
+                buf,
+            );
+            if let Some(plan_panel_area) = plan_panel_area {
+                self.render_plan_panel(plan_panel_area, buf);
+            }
+        }
+
+        if regions.contains(Regions::BOTTOM_PANE) {
+            if reused {
+                self.clear_region_for_repaint(buf, bottom_pane_area);
+            }
+            self.render_terminal_overlay_and_bottom_pane(area, history_area, bottom_pane_area, buf);
+        }
         self.render_overlay_stack(area, history_area, bottom_pane_area, buf);
 
+        // Rendering can change what history depends on (scroll clamping,
+        // height cache fills), so fingerprint again for the next frame.
+        let history_fingerprint = self.history_fingerprint(streaming_lines.len());
+        self.store_composed_frame(frame_areas, history_fingerprint, buf);
+        self.render_frame_hud(area, regions, cause, buf);
+
         if let Some(t0) = _perf_widget_start {
             let dt = t0.elapsed().as_nanos();
             let mut p = self.perf_state.stats.borrow_mut();
@@ -0,0 +1,80 @@
+use super::*;
+
+impl ChatWidget<'_> {
+    /// Left-hand file navigator for the diff viewer: a search row followed by
+    /// files grouped under their directories, each with an applied/pending badge.
+    pub(super) fn render_diff_file_tree(&self, overlay: &DiffOverlay, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+        let t_dim = Style::default().fg(crate::colors::text_dim());
+        let t_fg = Style::default().fg(crate::colors::text());
+
+        let search_line = if overlay.searching || !overlay.query.is_empty() {
+            let mut spans = vec![
+                Span::styled("/ ", t_fg),
+                Span::styled(overlay.query.clone(), t_fg),
+            ];
+            if overlay.searching {
+                spans.push(Span::styled("▏", Style::default().fg(crate::colors::primary())));
+            }
+            Line::from(spans)
+        } else {
+            Line::from(vec![
+                Span::styled(format!("{} files ", overlay.files.len()), t_fg),
+                Span::styled("· / search", t_dim),
+            ])
+        };
+
+        let rows = overlay.tree_rows();
+        let mut lines: Vec<Line<'static>> = Vec::with_capacity(rows.len());
+        let mut selected_row = 0usize;
+        for row in &rows {
+            match row {
+                DiffTreeRow::Dir(dir) => {
+                    lines.push(Line::from(Span::styled(dir.clone(), t_dim)));
+                }
+                DiffTreeRow::File {
+                    tab,
+                    name,
+                    nested,
+                    status,
+                } => {
+                    let selected = *tab == overlay.selected;
+                    if selected {
+                        selected_row = lines.len();
+                    }
+                    let (badge, badge_color) = match status {
+                        DiffFileStatus::Applied => ("✓", crate::colors::success()),
+                        DiffFileStatus::Pending => ("…", crate::colors::warning()),
+                    };
+                    let name_style = if selected {
+                        t_fg.add_modifier(Modifier::BOLD)
+                    } else {
+                        t_dim
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled(if selected { "› " } else { "  " }, t_fg),
+                        Span::raw(if *nested { "  " } else { "" }),
+                        Span::styled(badge, Style::default().fg(badge_color)),
+                        Span::raw(" "),
+                        Span::styled(name.clone(), name_style),
+                    ]));
+                }
+            }
+        }
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled("no matching files", t_dim)));
+        }
+
+        let [search_area, list_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(area);
+        Paragraph::new(search_line).render(search_area, buf);
+
+        // Keep the selected file in view.
+        let visible = list_area.height as usize;
+        let skip = selected_row.saturating_sub(visible.saturating_sub(1));
+        let end = (skip + visible).min(lines.len());
+        Paragraph::new(RtText::from(lines[skip..end].to_vec())).render(list_area, buf);
+    }
+}
@@ -0,0 +1,154 @@
+use super::*;
+
+use crate::dirty_regions::FrameDamage;
+use crate::dirty_regions::Regions;
+
+/// Width of the window the HUD averages frame rate over.
+const HUD_FPS_WINDOW: Duration = Duration::from_secs(1);
+
+impl ChatWidget<'_> {
+    /// Damage the app collected for the frame about to be rendered. Renders
+    /// without it (tests, snapshots) always repaint everything.
+    pub(crate) fn set_frame_damage(&self, damage: FrameDamage) {
+        self.frame_regions.damage.set(Some(damage));
+    }
+
+    pub(crate) fn toggle_frame_hud(&mut self) -> bool {
+        self.frame_regions.hud_enabled = !self.frame_regions.hud_enabled;
+        self.frame_regions.recent_frames.borrow_mut().clear();
+        self.frame_regions.hud_enabled
+    }
+
+    pub(super) fn history_fingerprint(&self, streaming_rows: usize) -> HistoryFingerprint {
+        HistoryFingerprint {
+            cells: self.history_cells.len(),
+            scroll_offset: self.layout.scroll_offset.get(),
+            queued_messages: self.queued_user_messages.len() + self.offline.messages.len(),
+            streaming_rows,
+            theme_epoch: self.render_theme_epoch,
+            invalidations: self.history_render.invalidations(),
+            plan_panel: self.plan_panel.revision,
+        }
+    }
+
+    fn frame_overlay_active(&self) -> bool {
+        self.terminal.overlay().is_some()
+            || self.agents_terminal.active
+            || self.browser_overlay_visible
+            || self.settings.overlay.is_some()
+            || self.diffs.overlay.is_some()
+            || self.help.overlay.is_some()
+    }
+
+    /// Picks the regions to render this frame and, when the previous frame
+    /// can be reused, copies it into `buf`. Returns `Regions::FULL` when
+    /// everything has to be painted from scratch.
+    pub(super) fn begin_frame_regions(
+        &self,
+        areas: [Rect; 3],
+        history: HistoryFingerprint,
+        buf: &mut Buffer,
+    ) -> Regions {
+        let Some(damage) = self.frame_regions.damage.take() else {
+            return Regions::FULL;
+        };
+        if damage.regions.is_full() || self.frame_overlay_active() {
+            return Regions::FULL;
+        }
+        let composed = self.frame_regions.composed.borrow();
+        let Some(previous) = composed.as_ref() else {
+            return Regions::FULL;
+        };
+        if previous.areas != areas || previous.buffer.area != buf.area {
+            return Regions::FULL;
+        }
+        let mut regions = damage.regions;
+        if previous.history != history {
+            regions = regions.union(Regions::HISTORY);
+        }
+        if regions.is_full() {
+            return Regions::FULL;
+        }
+        buf.content.clone_from(&previous.buffer.content);
+        regions
+    }
+
+    /// Resets a region of a reused frame to the blank, themed state a fresh
+    /// frame starts from.
+    pub(super) fn clear_region_for_repaint(&self, buf: &mut Buffer, area: Rect) {
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                if let Some(cell) = buf.cell_mut((x, y)) {
+                    cell.reset();
+                }
+            }
+        }
+        let bg_style = Style::default()
+            .bg(crate::colors::background())
+            .fg(crate::colors::text());
+        fill_rect(buf, area, None, bg_style);
+    }
+
+    pub(super) fn store_composed_frame(
+        &self,
+        areas: [Rect; 3],
+        history: HistoryFingerprint,
+        buf: &Buffer,
+    ) {
+        let mut composed = self.frame_regions.composed.borrow_mut();
+        match composed.as_mut() {
+            Some(previous) if previous.buffer.area == buf.area => {
+                previous.buffer.content.clone_from(&buf.content);
+                previous.areas = areas;
+                previous.history = history;
+            }
+            _ => {
+                *composed = Some(ComposedFrame {
+                    areas,
+                    history,
+                    buffer: buf.clone(),
+                });
+            }
+        }
+    }
+
+    /// Top-right readout of frame rate, the regions this frame redrew, and
+    /// the request that caused it.
+    pub(super) fn render_frame_hud(
+        &self,
+        area: Rect,
+        regions: Regions,
+        cause: &'static str,
+        buf: &mut Buffer,
+    ) {
+        if !self.frame_regions.hud_enabled || area.height == 0 {
+            return;
+        }
+        let now = Instant::now();
+        let fps = {
+            let mut frames = self.frame_regions.recent_frames.borrow_mut();
+            frames.push_back(now);
+            while frames
+                .front()
+                .is_some_and(|t| now.duration_since(*t) > HUD_FPS_WINDOW)
+            {
+                frames.pop_front();
+            }
+            frames.len()
+        };
+        let text = format!(" {fps} fps · {} · {cause} ", regions.label());
+        let width = (text.chars().count() as u16).min(area.width);
+        let hud_area = Rect {
+            x: area.right().saturating_sub(width),
+            y: area.y,
+            width,
+            height: 1,
+        };
+        let style = Style::default()
+            .bg(crate::colors::info())
+            .fg(crate::colors::background());
+        ratatui::widgets::Paragraph::new(text)
+            .style(style)
+            .render(hud_area, buf);
+    }
+}
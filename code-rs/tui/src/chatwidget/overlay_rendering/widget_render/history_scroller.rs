@@ -24,9 +24,9 @@ impl ChatWidget<'_> {
 
         // Calculate total content height using prefix sums; build if needed
         let spacing = 1u16; // Standard spacing between cells
-        const GUTTER_WIDTH: u16 = 2; // Same as in render loop
+        let gutter_width = self.history_gutter_width(); // Same as in render loop
         let reasoning_visible = self.is_reasoning_shown();
-        let cache_width = content_area.width.saturating_sub(GUTTER_WIDTH);
+        let cache_width = content_area.width.saturating_sub(gutter_width);
 
         // Opportunistically clear height cache if width changed
         self.history_render.handle_width_change(cache_width);
@@ -146,7 +146,7 @@ impl ChatWidget<'_> {
             let mut prefix: Vec<u16> = Vec::with_capacity(cells.len().saturating_add(1));
             prefix.push(0);
             let mut acc = 0u16;
-            let content_width = content_area.width.saturating_sub(GUTTER_WIDTH);
+            let content_width = content_area.width.saturating_sub(gutter_width);
             let mut spacing_ranges: Vec<(u16, u16)> = Vec::new();
 
             for (idx, vis) in cells.iter().enumerate() {
@@ -19,10 +19,15 @@ impl ChatWidget<'_> {
         let mut screen_y = start_y;
         let spacing = 1u16;
         let history_len = self.history_cells.len();
-        const GUTTER_WIDTH: u16 = 2;
+        let gutter_width = self.history_gutter_width();
         let viewport_bottom = content_area.y.saturating_add(content_area.height);
         let history_right = history_area.x.saturating_add(history_area.width);
         let logging_enabled = history_cell_logging_enabled();
+        let announce_cells = crate::accessibility::enabled();
+        // Row of the blank spacer painted after the previous cell; accessibility
+        // mode writes the next cell's label there.
+        let mut spacer_row: Option<u16> = None;
+        self.history_click_regions.borrow_mut().clear();
 
         let render_loop_start = if self.perf_state.enabled {
             Some(std::time::Instant::now())
@@ -64,7 +69,8 @@ impl ChatWidget<'_> {
             };
 
             let item_kind = item.kind();
-            let content_width = content_area.width.saturating_sub(GUTTER_WIDTH);
+            let content_width = content_area.width.saturating_sub(gutter_width);
+            let label_row = spacer_row.take();
 
             let mut layout_for_render: Option<Rc<CachedLayout>> = visible
                 .layout
@@ -126,14 +132,14 @@ impl ChatWidget<'_> {
                 let gutter_area = Rect {
                     x: content_area.x,
                     y: screen_y,
-                    width: GUTTER_WIDTH.min(content_area.width),
+                    width: gutter_width.min(content_area.width),
                     height: visible_height,
                 };
 
                 let item_area = Rect {
-                    x: content_area.x + GUTTER_WIDTH.min(content_area.width),
+                    x: content_area.x + gutter_width.min(content_area.width),
                     y: screen_y,
-                    width: content_area.width.saturating_sub(GUTTER_WIDTH),
+                    width: content_area.width.saturating_sub(gutter_width),
                     height: visible_height,
                 };
 
@@ -212,6 +218,14 @@ impl ChatWidget<'_> {
                     }
                 }
 
+                let anchor_offset: u16 = match item_kind {
+                    crate::history_cell::HistoryCellType::Assistant => 1,
+                    _ if is_auto_review => {
+                        crate::history_cell::PlainHistoryCell::auto_review_padding().0
+                    }
+                    _ => 0,
+                };
+
                 if let Some(symbol) = item.gutter_symbol() {
                     let color = if is_auto_review {
                         crate::colors::success()
@@ -290,27 +304,49 @@ impl ChatWidget<'_> {
                         }
                     };
 
-                    if gutter_area.width >= 2 {
-                        let anchor_offset: u16 = match item_kind {
-                            crate::history_cell::HistoryCellType::Assistant => 1,
-                            _ if is_auto_review => {
-                                crate::history_cell::PlainHistoryCell::auto_review_padding().0
-                            }
-                            _ => 0,
-                        };
+                    if gutter_area.width >= 2 && skip_top <= anchor_offset {
+                        let rel = anchor_offset - skip_top;
+                        let symbol_y = gutter_area.y.saturating_add(rel);
+                        if symbol_y < gutter_area.y.saturating_add(gutter_area.height) {
+                            let symbol_style = Style::default().fg(color).bg(gutter_bg);
+                            let symbol_x = gutter_area.x + gutter_area.width - 2;
+                            buf.set_string(symbol_x, symbol_y, symbol, symbol_style);
+                        }
+                    }
+                }
 
-                        if skip_top <= anchor_offset {
-                            let rel = anchor_offset - skip_top;
-                            let symbol_y = gutter_area.y.saturating_add(rel);
-                            if symbol_y < gutter_area.y.saturating_add(gutter_area.height) {
-                                let symbol_style = Style::default().fg(color).bg(gutter_bg);
-                                let symbol_x = gutter_area.x;
-                                buf.set_string(symbol_x, symbol_y, symbol, symbol_style);
-                            }
+                if let Some(req) = visible_requests_slice.get(offset)
+                    && let Some(stamp) = self.cell_gutter_stamp(req.history_id)
+                {
+                    let stamp_style = Style::default().fg(crate::colors::text_dim()).bg(gutter_bg);
+                    let rows = std::iter::once(stamp.time).chain(stamp.turn_took);
+                    for (row, text) in (anchor_offset..).zip(rows) {
+                        if row < skip_top {
+                            continue;
                         }
+                        let y = gutter_area.y.saturating_add(row - skip_top);
+                        if y >= gutter_area.y.saturating_add(gutter_area.height) {
+                            break;
+                        }
+                        buf.set_string(gutter_area.x, y, text, stamp_style);
                     }
                 }
 
+                if announce_cells
+                    && skip_top == 0
+                    && let Some(y) = label_row
+                    && let Some(label) = crate::accessibility::cell_label(item_kind)
+                {
+                    let label_style = Style::default().fg(crate::colors::text_dim());
+                    buf.set_stringn(
+                        content_area.x,
+                        y,
+                        label,
+                        content_area.width as usize,
+                        label_style,
+                    );
+                }
+
                 let skip_rows = skip_top;
                 let is_animating = item.is_animating();
                 let has_custom = item.has_custom_render();
@@ -350,10 +386,73 @@ impl ChatWidget<'_> {
                     }
                 }
 
+                if let Some(touched) = item
+                    .as_any()
+                    .downcast_ref::<crate::history_cell::TouchedFilesCell>()
+                {
+                    let mut regions = self.history_click_regions.borrow_mut();
+                    for offset in 0..item_area.height {
+                        if let Some(path) = touched.file_at_row(skip_rows.saturating_add(offset)) {
+                            regions.push(ClickableRegion {
+                                rect: Rect::new(item_area.x, item_area.y + offset, item_area.width, 1),
+                                action: ClickableAction::ShowDiffForFile(path.clone()),
+                            });
+                        }
+                    }
+                }
+
+                if let Some(review) = item
+                    .as_any()
+                    .downcast_ref::<crate::history_cell::ReviewPassesCell>()
+                {
+                    let mut regions = self.history_click_regions.borrow_mut();
+                    for (row, section) in review.section_header_rows(item_area.width) {
+                        let Some(offset) = row.checked_sub(skip_rows) else {
+                            continue;
+                        };
+                        if offset < item_area.height {
+                            regions.push(ClickableRegion {
+                                rect: Rect::new(item_area.x, item_area.y + offset, item_area.width, 1),
+                                action: ClickableAction::ToggleReviewPass(idx, section),
+                            });
+                        }
+                    }
+                }
+
+                if let Some(tests) = item
+                    .as_any()
+                    .downcast_ref::<crate::history_cell::TestResultsCell>()
+                {
+                    let mut regions = self.history_click_regions.borrow_mut();
+                    for (row, failure) in tests.failure_rows(item_area.width) {
+                        let Some(offset) = row.checked_sub(skip_rows) else {
+                            continue;
+                        };
+                        if offset < item_area.height {
+                            regions.push(ClickableRegion {
+                                rect: Rect::new(item_area.x, item_area.y + offset, item_area.width, 1),
+                                action: ClickableAction::ToggleTestFailure(idx, failure),
+                            });
+                        }
+                    }
+                }
+
+                if skip_rows == 0
+                    && item_area.height > 0
+                    && item
+                        .as_any()
+                        .is::<crate::history_cell::AttachmentsCell>()
+                {
+                    self.history_click_regions.borrow_mut().push(ClickableRegion {
+                        rect: Rect::new(item_area.x, item_area.y, item_area.width, 1),
+                        action: ClickableAction::ToggleAttachments(idx),
+                    });
+                }
+
                 if self.show_order_overlay
-                    && let Some(Some(info)) = self.cell_order_dbg.get(idx)
+                    && let Some(label) = self.order_overlay_label(idx)
                 {
-                    let mut text = format!("⟦{info}⟧");
+                    let mut text = format!("⟦{label}⟧");
                     if let Some(rc) = item
                         .as_any()
                         .downcast_ref::<crate::history_cell::CollapsibleReasoningCell>()
@@ -365,7 +464,7 @@ impl ChatWidget<'_> {
                     let style = Style::default().fg(crate::colors::text_dim());
                     let below_y = item_area.y.saturating_add(visible_height);
                     let bottom_y = viewport_bottom;
-                    let maxw = item_area.width as usize;
+                    let maxw = content_area.width as usize;
                     let draw_text = {
                         use unicode_width::UnicodeWidthStr as _;
                         if text.width() > maxw {
@@ -374,12 +473,12 @@ impl ChatWidget<'_> {
                             text.clone()
                         }
                     };
-                    if item_area.width > 0 {
+                    if content_area.width > 0 {
                         if below_y < bottom_y {
-                            buf.set_string(item_area.x, below_y, draw_text.clone(), style);
+                            buf.set_string(gutter_area.x, below_y, draw_text.clone(), style);
                         } else if item_area.y > content_area.y {
                             let above_y = item_area.y.saturating_sub(1);
-                            buf.set_string(item_area.x, above_y, draw_text.clone(), style);
+                            buf.set_string(gutter_area.x, above_y, draw_text.clone(), style);
                         }
                     }
                 }
@@ -429,6 +528,7 @@ impl ChatWidget<'_> {
             if should_add_spacing {
                 let bottom = viewport_bottom;
                 if screen_y < bottom {
+                    spacer_row = Some(screen_y);
                     let spacing_rows = spacing.min(bottom.saturating_sub(screen_y));
                     screen_y = screen_y.saturating_add(spacing_rows);
                 }
@@ -86,6 +86,15 @@ impl ChatWidget<'_> {
                 .last_history_viewport_height
                 .set(content_area.height);
         }
+        // A width change rewraps every cell, so row offsets from the last
+        // frame no longer point at the same content; follow the anchor cell.
+        let reflow_anchor = self
+            .layout
+            .history_anchor
+            .get()
+            .filter(|anchor| {
+                anchor.width != content_area.width && self.layout.scroll_offset.get() > 0
+            });
 
         let (start_y, scroll_pos) = if total_height <= content_area.height {
             // Content fits - always align to bottom so "Popular commands" stays at the bottom
@@ -98,7 +107,12 @@ impl ChatWidget<'_> {
             // scroll_offset is measured from the bottom (0 = bottom/newest)
             // Convert to distance from the top for rendering math.
             let max_scroll = total_height.saturating_sub(content_area.height);
-            if self.layout.scroll_offset.get() > 0 && max_scroll != prev_max_scroll {
+            let reflowed_row = reflow_anchor.and_then(|anchor| {
+                reflowed_anchor_row(&self.history_render.prefix_sums.borrow(), anchor)
+            });
+            if let Some(row) = reflowed_row {
+                self.layout.scroll_offset.set(max_scroll.saturating_sub(row));
+            } else if self.layout.scroll_offset.get() > 0 && max_scroll != prev_max_scroll {
                 // If the user has scrolled up and the history height changes (e.g. new output
                 // arrives while streaming), keep the same content anchored at the top of the
                 // viewport by adjusting our bottom-anchored scroll offset.
@@ -157,10 +171,20 @@ impl ChatWidget<'_> {
             (content_area.y, scroll_pos)
         };
 
-        // Record current viewport height for the next frame
+        // Record current viewport height and top-of-viewport content for the next frame
         self.layout
             .last_history_viewport_height
             .set(content_area.height);
+        let anchor = if total_height > content_area.height {
+            anchor_at_row(
+                &self.history_render.prefix_sums.borrow(),
+                scroll_pos,
+                content_area.width,
+            )
+        } else {
+            None
+        };
+        self.layout.history_anchor.set(anchor);
         HistoryScrollLayout {
             total_height,
             start_y,
@@ -168,3 +192,66 @@ impl ChatWidget<'_> {
         }
     }
 }
+
+/// The cell containing row `row`, given `prefix` (each cell's first row,
+/// then the total height).
+fn anchor_at_row(prefix: &[u16], row: u16, width: u16) -> Option<HistoryAnchor> {
+    let last = prefix.len().checked_sub(2)?;
+    let cell = prefix
+        .partition_point(|&start| start <= row)
+        .saturating_sub(1)
+        .min(last);
+    let start = prefix[cell];
+    Some(HistoryAnchor {
+        width,
+        cell,
+        row_in_cell: row.saturating_sub(start),
+        cell_height: prefix[cell + 1].saturating_sub(start),
+    })
+}
+
+/// Where `anchor` starts in the rewrapped `prefix`. Rows into the cell are
+/// scaled by how much the cell grew or shrank.
+fn reflowed_anchor_row(prefix: &[u16], anchor: HistoryAnchor) -> Option<u16> {
+    let cell = anchor.cell.min(prefix.len().checked_sub(2)?);
+    let start = prefix[cell];
+    let height = prefix[cell + 1].saturating_sub(start);
+    let row = if anchor.cell_height == 0 {
+        0
+    } else {
+        let scaled = u32::from(anchor.row_in_cell.min(anchor.cell_height)) * u32::from(height)
+            / u32::from(anchor.cell_height);
+        u16::try_from(scaled).unwrap_or(u16::MAX)
+    };
+    Some(start.saturating_add(row.min(height.saturating_sub(1))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchor_follows_its_cell_through_a_rewrap() {
+        // Three cells of 10, 20 and 5 rows; the viewport starts 5 rows into
+        // the second cell.
+        let wide = [0, 10, 30, 35];
+        let anchor = anchor_at_row(&wide, 15, 120).expect("anchor");
+        assert_eq!(
+            anchor,
+            HistoryAnchor {
+                width: 120,
+                cell: 1,
+                row_in_cell: 5,
+                cell_height: 20,
+            }
+        );
+
+        // At half the width every cell is twice as tall.
+        let narrow = [0, 20, 60, 70];
+        assert_eq!(reflowed_anchor_row(&narrow, anchor), Some(30));
+
+        // Past the end clamps to the last cell.
+        assert_eq!(anchor_at_row(&wide, 40, 120).map(|anchor| anchor.cell), Some(2));
+        assert_eq!(anchor_at_row(&[0], 0, 120), None);
+    }
+}
@@ -82,7 +82,16 @@ impl ChatWidget<'_> {
                     ratatui::text::Span::styled(" ", t_dim),
                     ratatui::text::Span::styled("Diff viewer", t_fg),
                 ];
-                if has_tabs {
+                if overlay.has_tree() {
+                    title_spans.extend_from_slice(&[
+                        ratatui::text::Span::styled(" ——— ", t_dim),
+                        ratatui::text::Span::styled("◂ ▸", t_fg),
+                        ratatui::text::Span::styled(" change file ", t_dim),
+                        ratatui::text::Span::styled("——— ", t_dim),
+                        ratatui::text::Span::styled("/", t_fg),
+                        ratatui::text::Span::styled(" search ", t_dim),
+                    ]);
+                } else if has_tabs {
                     title_spans.extend_from_slice(&[
                         ratatui::text::Span::styled(" ——— ", t_dim),
                         ratatui::text::Span::styled("◂ ▸", t_fg),
@@ -137,6 +146,23 @@ impl ChatWidget<'_> {
                 // Split into header tabs and body/footer
                 // Add one cell padding around the entire inside of the window
                 let padded_inner = inner.inner(ratatui::layout::Margin::new(1, 1));
+                // Many files: a tree navigator on the left replaces the tab strip.
+                let (tree_area, padded_inner) = if overlay.has_tree() && padded_inner.width >= 60 {
+                    let tree_width = (padded_inner.width / 3).clamp(24, 40);
+                    let [tree, _gap, rest] = Layout::horizontal([
+                        Constraint::Length(tree_width),
+                        Constraint::Length(2),
+                        Constraint::Fill(1),
+                    ])
+                    .areas(padded_inner);
+                    (Some(tree), rest)
+                } else {
+                    (None, padded_inner)
+                };
+                if let Some(tree_area) = tree_area {
+                    self.render_diff_file_tree(overlay, tree_area, buf);
+                }
+                let has_tabs = has_tabs && tree_area.is_none();
                 let [tabs_area, body_area] = if has_tabs {
                     Layout::vertical([Constraint::Length(2), Constraint::Fill(1)])
                         .areas(padded_inner)
@@ -232,6 +258,10 @@ impl ChatWidget<'_> {
                 } else {
                     // Single-file header: show full path with (+adds -dels)
                     if let Some((label, _)) = overlay.tabs.get(overlay.selected) {
+                        let label = overlay
+                            .files
+                            .get(overlay.selected)
+                            .map_or(label, |file| &file.display);
                         let header_line = ratatui::text::Line::from(ratatui::text::Span::styled(
                             label.clone(),
                             Style::default()
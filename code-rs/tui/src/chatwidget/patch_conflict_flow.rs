@@ -0,0 +1,119 @@
+use super::*;
+
+use code_core::protocol::FileChange;
+use ratatui::style::Color;
+
+impl ChatWidget<'_> {
+    /// After a failed apply, re-runs the 3-way merge for files that changed on
+    /// disk since the patch was prepared and opens the diff overlay on the
+    /// conflicting regions. Does nothing when no file conflicts.
+    pub(super) fn show_patch_conflicts(&mut self, patch: &AppliedPatchSet) {
+        let mut tabs: Vec<(String, Vec<DiffBlock>)> = Vec::new();
+        let mut paths: Vec<&PathBuf> = patch.changes.keys().collect();
+        paths.sort();
+        for path in paths {
+            let Some(FileChange::Update { new_content, .. }) = patch.changes.get(path) else {
+                continue;
+            };
+            let Some(base) = patch
+                .before
+                .get(path)
+                .cloned()
+                .flatten()
+                .or_else(|| self.diffs.baseline_file_contents.get(path).cloned())
+            else {
+                continue;
+            };
+            let Ok(current) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            if current == base {
+                continue;
+            }
+            let Err(conflicted) = diffy::merge(&base, &current, new_content) else {
+                continue;
+            };
+            let title = path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .map(std::string::ToString::to_string)
+                .unwrap_or_else(|| path.display().to_string());
+            tabs.push((title, conflict_blocks(path, &conflicted)));
+        }
+
+        if tabs.is_empty() {
+            return;
+        }
+        let count = tabs.len();
+        self.diffs.overlay = Some(DiffOverlay::new(tabs));
+        self.diffs.confirm = None;
+        self.push_background_tail(format!(
+            "Patch conflicts with edits made on disk in {count} file{}; showing the conflicting regions.",
+            if count == 1 { "" } else { "s" }
+        ));
+        self.request_redraw();
+    }
+}
+
+/// Renders conflict-marked merge output: a header, then only the conflicting
+/// regions with a little surrounding context.
+fn conflict_blocks(path: &Path, conflicted: &str) -> Vec<DiffBlock> {
+    const CONTEXT: usize = 3;
+    let header = Line::from(vec![
+        Span::styled(
+            path.display().to_string(),
+            Style::default()
+                .fg(crate::colors::text())
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            "  edits on disk (ours) vs. patch (theirs)",
+            Style::default().fg(crate::colors::text_dim()),
+        ),
+    ]);
+    let mut blocks = vec![DiffBlock { lines: vec![header] }];
+
+    let lines: Vec<&str> = conflicted.lines().collect();
+    let mut idx = 0;
+    while idx < lines.len() {
+        if !lines[idx].starts_with("<<<<<<<") {
+            idx += 1;
+            continue;
+        }
+        let start = idx.saturating_sub(CONTEXT);
+        let mut end = idx;
+        while end < lines.len() && !lines[end].starts_with(">>>>>>>") {
+            end += 1;
+        }
+        let end = (end + 1 + CONTEXT).min(lines.len());
+
+        let mut side: Option<Color> = None;
+        let block_lines = lines[start..end]
+            .iter()
+            .map(|line| {
+                let style = if line.starts_with("<<<<<<<") {
+                    side = Some(crate::colors::error());
+                    Style::default().fg(crate::colors::warning())
+                } else if line.starts_with("=======") {
+                    side = Some(crate::colors::success());
+                    Style::default().fg(crate::colors::warning())
+                } else if line.starts_with(">>>>>>>") {
+                    side = None;
+                    Style::default().fg(crate::colors::warning())
+                } else if line.starts_with("|||||||") {
+                    side = Some(crate::colors::text_dim());
+                    Style::default().fg(crate::colors::warning())
+                } else {
+                    side.map_or_else(
+                        || Style::default().fg(crate::colors::text_dim()),
+                        |color| Style::default().fg(color),
+                    )
+                };
+                Line::from(Span::styled((*line).to_string(), style))
+            })
+            .collect();
+        blocks.push(DiffBlock { lines: block_lines });
+        idx = end;
+    }
+    blocks
+}
@@ -0,0 +1,262 @@
+use super::*;
+
+use code_core::protocol::FileChange;
+
+impl ChatWidget<'_> {
+    /// Snapshots the pre-apply contents of every path a patch is about to touch
+    /// so it can be reverted once it applies successfully.
+    pub(super) fn note_patch_apply_begin(
+        &mut self,
+        call_id: &str,
+        changes: &HashMap<PathBuf, FileChange>,
+    ) {
+        let before = changes
+            .iter()
+            .map(|(path, change)| {
+                let content = match change {
+                    FileChange::Update {
+                        original_content, ..
+                    } => Some(original_content.clone()),
+                    FileChange::Add { .. } | FileChange::Delete => {
+                        std::fs::read_to_string(path).ok()
+                    }
+                };
+                (path.clone(), content)
+            })
+            .collect();
+        self.diffs.pending_applies.insert(
+            call_id.to_string(),
+            AppliedPatchSet {
+                changes: changes.clone(),
+                before,
+            },
+        );
+    }
+
+    /// Moves a successfully applied patch onto the undo stack. A failed patch
+    /// is handed back to the caller instead.
+    pub(super) fn note_patch_apply_end(
+        &mut self,
+        call_id: &str,
+        success: bool,
+    ) -> Option<AppliedPatchSet> {
+        let applied = self.diffs.pending_applies.remove(call_id)?;
+        if success {
+            let touched: Vec<PathBuf> = applied
+                .changes
+                .iter()
+                .map(|(path, change)| match change {
+                    FileChange::Update {
+                        move_path: Some(dest),
+                        ..
+                    } => dest.clone(),
+                    _ => path.clone(),
+                })
+                .collect();
+            self.record_session_file_contents(touched.iter());
+            self.diffs.undo_stack.push(applied);
+            None
+        } else {
+            Some(applied)
+        }
+    }
+
+    pub(crate) fn handle_undo_patch_command(&mut self, args: String) {
+        let force = match args.trim() {
+            "" => false,
+            "force" | "--force" => true,
+            _ => {
+                self.history_push_plain_state(crate::history_cell::new_error_event(
+                    "Usage: /undo-patch [force]".to_string(),
+                ));
+                self.request_redraw();
+                return;
+            }
+        };
+
+        let Some(applied) = self.diffs.undo_stack.pop() else {
+            self.history_push_plain_state(crate::history_cell::new_error_event(
+                "`/undo-patch` — no applied patch to revert in this session".to_string(),
+            ));
+            self.request_redraw();
+            return;
+        };
+
+        match revert_patch_set(&applied, force) {
+            Ok(reversal) => {
+                let count = reversal.len();
+                let restored: Vec<PathBuf> = applied.changes.keys().cloned().collect();
+                self.record_session_file_contents(restored.iter().chain(reversal.keys()));
+                self.history_push(crate::history_cell::new_patch_event(
+                    PatchEventType::ApplySuccess,
+                    reversal,
+                ));
+                self.push_background_tail(format!(
+                    "Reverted the last applied patch ({count} file{}).",
+                    if count == 1 { "" } else { "s" }
+                ));
+            }
+            Err(message) => {
+                // Keep the entry so the user can retry with `force` after inspecting.
+                self.diffs.undo_stack.push(applied);
+                self.history_push_plain_state(crate::history_cell::new_error_event(message));
+            }
+        }
+        self.request_redraw();
+    }
+}
+
+/// Restores every path in `applied` to its pre-apply contents and returns the
+/// reverse change set for display. Unless `force` is set, refuses to touch
+/// anything when a file no longer matches what the patch left behind.
+fn revert_patch_set(
+    applied: &AppliedPatchSet,
+    force: bool,
+) -> Result<HashMap<PathBuf, FileChange>, String> {
+    let read = |path: &Path| std::fs::read_to_string(path).ok();
+    let mut conflicts: Vec<String> = Vec::new();
+    for (path, change) in &applied.changes {
+        let (target, expected) = match change {
+            FileChange::Add { content } => (path, Some(content)),
+            FileChange::Delete => (path, None),
+            FileChange::Update {
+                move_path,
+                new_content,
+                ..
+            } => (move_path.as_ref().unwrap_or(path), Some(new_content)),
+        };
+        if read(target).as_ref() != expected {
+            conflicts.push(target.display().to_string());
+        }
+    }
+    if !conflicts.is_empty() && !force {
+        conflicts.sort();
+        return Err(format!(
+            "`/undo-patch` — these files changed after the patch was applied: {}. \
+             Run `/undo-patch force` to restore them anyway.",
+            conflicts.join(", ")
+        ));
+    }
+
+    let mut reversal = HashMap::with_capacity(applied.changes.len());
+    for (path, change) in &applied.changes {
+        let before = applied.before.get(path).cloned().flatten();
+        let (current_path, current) = match change {
+            FileChange::Update {
+                move_path: Some(dest),
+                ..
+            } if dest != path => {
+                let current = read(dest);
+                std::fs::remove_file(dest)
+                    .map_err(|err| format!("Failed to remove {}: {err}", dest.display()))?;
+                (dest.clone(), current)
+            }
+            _ => (path.clone(), read(path)),
+        };
+
+        match before.as_ref() {
+            Some(content) => {
+                if let Some(parent) = path.parent() {
+                    let _ = std::fs::create_dir_all(parent);
+                }
+                std::fs::write(path, content)
+                    .map_err(|err| format!("Failed to restore {}: {err}", path.display()))?;
+            }
+            None => {
+                if path.exists() {
+                    std::fs::remove_file(path)
+                        .map_err(|err| format!("Failed to remove {}: {err}", path.display()))?;
+                }
+            }
+        }
+
+        let reverse = match (current, before) {
+            (Some(_), None) => FileChange::Delete,
+            (None, Some(content)) => FileChange::Add { content },
+            (None, None) => continue,
+            (Some(current), Some(before)) => FileChange::Update {
+                unified_diff: diffy::create_patch(&current, &before).to_string(),
+                move_path: (current_path != *path).then(|| path.clone()),
+                original_content: current,
+                new_content: before,
+            },
+        };
+        reversal.insert(current_path, reverse);
+    }
+    Ok(reversal)
+}
+
+#[cfg(test)]
+mod patch_undo_tests {
+    use super::*;
+
+    fn applied(changes: Vec<(PathBuf, FileChange)>, before: Vec<(PathBuf, Option<&str>)>) -> AppliedPatchSet {
+        AppliedPatchSet {
+            changes: changes.into_iter().collect(),
+            before: before
+                .into_iter()
+                .map(|(path, content)| (path, content.map(str::to_string)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn reverts_update_and_add() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let edited = dir.path().join("edited.txt");
+        let added = dir.path().join("added.txt");
+        std::fs::write(&edited, "new\n").unwrap();
+        std::fs::write(&added, "fresh\n").unwrap();
+
+        let set = applied(
+            vec![
+                (
+                    edited.clone(),
+                    FileChange::Update {
+                        unified_diff: String::new(),
+                        move_path: None,
+                        original_content: "old\n".to_string(),
+                        new_content: "new\n".to_string(),
+                    },
+                ),
+                (
+                    added.clone(),
+                    FileChange::Add {
+                        content: "fresh\n".to_string(),
+                    },
+                ),
+            ],
+            vec![(edited.clone(), Some("old\n")), (added.clone(), None)],
+        );
+
+        let reversal = revert_patch_set(&set, false).expect("revert");
+        assert_eq!(std::fs::read_to_string(&edited).unwrap(), "old\n");
+        assert!(!added.exists());
+        assert!(matches!(reversal.get(&added), Some(FileChange::Delete)));
+    }
+
+    #[test]
+    fn refuses_when_file_changed_since_apply_unless_forced() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let edited = dir.path().join("edited.txt");
+        std::fs::write(&edited, "hand edit\n").unwrap();
+
+        let set = applied(
+            vec![(
+                edited.clone(),
+                FileChange::Update {
+                    unified_diff: String::new(),
+                    move_path: None,
+                    original_content: "old\n".to_string(),
+                    new_content: "new\n".to_string(),
+                },
+            )],
+            vec![(edited.clone(), Some("old\n"))],
+        );
+
+        assert!(revert_patch_set(&set, false).is_err());
+        assert_eq!(std::fs::read_to_string(&edited).unwrap(), "hand edit\n");
+        revert_patch_set(&set, true).expect("forced revert");
+        assert_eq!(std::fs::read_to_string(&edited).unwrap(), "old\n");
+    }
+}
@@ -42,6 +42,20 @@ pub struct PerfStats {
     // Undo/resume instrumentation
     pub undo_restore_events: u64,
     pub ns_undo_restore: u128,
+    // Filled from the history render state when a summary is requested
+    pub height_cache_invalidations: HeightCacheInvalidations,
+}
+
+/// How often the history height cache was invalidated, by scope. Full
+/// invalidations force every record to be laid out again, so they should
+/// stay rare next to per-record ones.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HeightCacheInvalidations {
+    pub full: u64,
+    pub record: u64,
+    pub prefix_only: u64,
+    pub width_changes: u64,
+    pub evicted_heights: u64,
 }
 
 #[derive(Default, Clone, Debug)]
@@ -85,6 +99,16 @@ impl PerfStats {
             self.cells_overlay_body_bg,
         ));
 
+        let inv = self.height_cache_invalidations;
+        out.push_str(&format!(
+            "\n  height_cache invalidations: full={} record={} prefix_only={} width_changes={} evicted_heights={}",
+            inv.full,
+            inv.record,
+            inv.prefix_only,
+            inv.width_changes,
+            inv.evicted_heights,
+        ));
+
         if self.scroll_events > 0 || self.scroll_render_frames > 0 {
             let ms_scroll = (self.ns_scroll_render as f64) / 1_000_000.0;
             out.push_str(&format!(
@@ -0,0 +1,174 @@
+use super::*;
+use code_core::plan_tool::PlanItemArg;
+use code_core::plan_tool::StepStatus;
+use ratatui::text::Span;
+use ratatui::widgets::Paragraph;
+
+/// Most step rows the expanded panel shows; longer plans scroll to keep the
+/// current step in view.
+const MAX_PLAN_PANEL_STEPS: usize = 8;
+
+/// History shorter than this keeps all its rows for scrollback.
+const MIN_HISTORY_ROWS_FOR_PANEL: u16 = 8;
+
+/// First step of the window of `rows` steps that keeps the first unfinished
+/// step visible with one finished step above it for context.
+fn plan_window_start(steps: &[PlanItemArg], rows: usize) -> usize {
+    if steps.len() <= rows {
+        return 0;
+    }
+    let current = steps
+        .iter()
+        .position(|step| !matches!(step.status, StepStatus::Completed))
+        .unwrap_or(steps.len() - 1);
+    current.saturating_sub(1).min(steps.len() - rows)
+}
+
+impl ChatWidget<'_> {
+    pub(super) fn sync_plan_panel(&mut self, plan: &[PlanItemArg]) {
+        self.plan_panel.steps = plan.to_vec();
+        self.plan_panel.revision = self.plan_panel.revision.wrapping_add(1);
+    }
+
+    /// Alt+P: collapses the plan panel to its summary line or expands it.
+    pub(crate) fn toggle_plan_panel(&mut self) {
+        if !self.plan_panel_visible() {
+            self.push_background_tail("No plan in progress.".to_string());
+            self.request_redraw();
+            return;
+        }
+        self.plan_panel.collapsed = !self.plan_panel.collapsed;
+        self.plan_panel.revision = self.plan_panel.revision.wrapping_add(1);
+        self.request_redraw();
+    }
+
+    /// The panel follows the terminal title: it shows while a plan has
+    /// unfinished steps.
+    fn plan_panel_visible(&self) -> bool {
+        self.active_plan_title.is_some() && !self.plan_panel.steps.is_empty()
+    }
+
+    /// Splits the panel's rows off the bottom of the history area.
+    pub(super) fn split_plan_panel(&self, history_area: Rect) -> (Rect, Option<Rect>) {
+        if !self.plan_panel_visible() || history_area.height < MIN_HISTORY_ROWS_FOR_PANEL {
+            return (history_area, None);
+        }
+        let rows = if self.plan_panel.collapsed {
+            1
+        } else {
+            let steps = self.plan_panel.steps.len().min(MAX_PLAN_PANEL_STEPS) as u16;
+            (1 + steps).min(history_area.height / 3).max(1)
+        };
+        let scroll_area = Rect {
+            height: history_area.height - rows,
+            ..history_area
+        };
+        let panel_area = Rect {
+            y: history_area.y + scroll_area.height,
+            height: rows,
+            ..history_area
+        };
+        (scroll_area, Some(panel_area))
+    }
+
+    pub(super) fn render_plan_panel(&self, area: Rect, buf: &mut Buffer) {
+        let steps = &self.plan_panel.steps;
+        let total = steps.len();
+        let done = steps
+            .iter()
+            .filter(|step| matches!(step.status, StepStatus::Completed))
+            .count();
+        let title = self.active_plan_title.as_deref().unwrap_or("Plan");
+        let dim = Style::default().fg(crate::colors::text_dim());
+        let collapsed = self.plan_panel.collapsed;
+
+        let mut header = vec![
+            Span::styled(if collapsed { "▸ " } else { "▾ " }, dim),
+            Span::styled(
+                title.to_string(),
+                Style::default()
+                    .fg(crate::colors::text())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  {done}/{total}"),
+                Style::default().fg(crate::colors::success()),
+            ),
+        ];
+        if collapsed
+            && let Some(current) = steps
+                .iter()
+                .find(|step| !matches!(step.status, StepStatus::Completed))
+        {
+            header.push(Span::styled(
+                format!(" · {}", current.step),
+                Style::default().fg(crate::colors::info()),
+            ));
+        }
+        header.push(Span::styled(
+            if collapsed { "  Alt+P expand" } else { "  Alt+P collapse" },
+            dim,
+        ));
+        let mut lines = vec![Line::from(header)];
+
+        if !collapsed {
+            let rows = usize::from(area.height.saturating_sub(1));
+            let start = plan_window_start(steps, rows);
+            for step in steps.iter().skip(start).take(rows) {
+                let (marker, style) = match step.status {
+                    StepStatus::Completed => (
+                        Span::styled("✔ ", Style::default().fg(crate::colors::success())),
+                        dim.add_modifier(Modifier::CROSSED_OUT),
+                    ),
+                    StepStatus::InProgress => (
+                        Span::raw("□ "),
+                        Style::default().fg(crate::colors::info()),
+                    ),
+                    StepStatus::Pending => (Span::raw("□ "), dim),
+                };
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    marker,
+                    Span::styled(step.step.clone(), style),
+                ]));
+            }
+        }
+
+        let background = Style::default()
+            .bg(crate::colors::background())
+            .fg(crate::colors::text());
+        fill_rect(buf, area, Some(' '), background);
+        let inner = Rect {
+            x: area.x.saturating_add(1),
+            width: area.width.saturating_sub(2),
+            ..area
+        };
+        Paragraph::new(lines).style(background).render(inner, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps(statuses: &[StepStatus]) -> Vec<PlanItemArg> {
+        statuses
+            .iter()
+            .enumerate()
+            .map(|(idx, status)| PlanItemArg {
+                step: format!("step {idx}"),
+                status: status.clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn window_keeps_current_step_in_view() {
+        use StepStatus::*;
+        let plan = steps(&[Completed, Completed, Completed, Completed, InProgress, Pending, Pending]);
+        assert_eq!(plan_window_start(&plan, 3), 3);
+        assert_eq!(plan_window_start(&plan, 7), 0);
+        let finishing = steps(&[Completed, Completed, Completed, Pending]);
+        assert_eq!(plan_window_start(&finishing, 2), 2);
+    }
+}
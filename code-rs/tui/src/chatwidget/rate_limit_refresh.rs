@@ -225,6 +225,7 @@ fn run_refresh(
                 msg: EventMsg::TokenCount(TokenCountEvent {
                     info: None,
                     rate_limits: Some(snapshot),
+                    session_cost_usd: None,
                 }),
                 order: None,
             };
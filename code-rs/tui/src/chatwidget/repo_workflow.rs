@@ -395,6 +395,198 @@ impl ChatWidget<'_> {
         });
     }
 
+    pub(crate) fn handle_pr_command(&mut self) {
+        self.consume_pending_prompt_for_ui_only_turn();
+        if self.ensure_git_repo_for_action(
+            GitInitResume::DispatchCommand {
+                command: SlashCommand::Pr,
+                command_text: "/pr".to_string(),
+            },
+            "Opening a pull request requires a git repository.",
+        ) {
+            return;
+        }
+        let worktree = match code_core::git_worktree::branch_worktree_root(&self.config.cwd) {
+            Some(root) => root,
+            None => match code_core::git_info::resolve_root_git_project_for_trust(&self.config.cwd) {
+                Some(root) => root,
+                None => {
+                    self.push_background_tail("`/pr` — run this command inside a git repository.".to_string());
+                    self.request_redraw();
+                    return;
+                }
+            },
+        };
+
+        self.push_background_tail("Branch, commit, push and open a pull request.".to_string());
+        self.request_redraw();
+
+        let tx = self.app_event_tx.clone();
+        let ticket = self.make_background_tail_ticket();
+        let configured_command = self.config.git.pr_command.clone();
+
+        tokio::spawn(async move {
+            use std::fmt::Write as _;
+            use tokio::process::Command;
+
+            async fn git_stdout(worktree: &std::path::Path, args: &[&str]) -> Option<String> {
+                tokio::process::Command::new("git")
+                    .current_dir(worktree)
+                    .args(args)
+                    .output()
+                    .await
+                    .ok()
+                    .filter(|out| out.status.success())
+                    .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+                    .filter(|text| !text.is_empty())
+            }
+
+            let short_status = match ChatWidget::git_short_status(&worktree).await {
+                Ok(output) => output,
+                Err(err) => {
+                    tx.send_background_event_with_ticket(
+                        &ticket,
+                        format!("`/pr` — failed to read git status: {err}"),
+                    );
+                    return;
+                }
+            };
+            let current_branch = git_stdout(&worktree, &["branch", "--show-current"]).await;
+            let default_branch = code_core::git_worktree::detect_default_branch(&worktree).await;
+            let remote_url = git_stdout(&worktree, &["remote", "get-url", "origin"]).await;
+
+            let pr_command = configured_command.unwrap_or_else(|| {
+                if remote_url.as_deref().is_some_and(|url| url.contains("gitlab")) {
+                    "glab mr create".to_string()
+                } else {
+                    "gh pr create".to_string()
+                }
+            });
+            let program = pr_command.split_whitespace().next().unwrap_or_default().to_string();
+            let program_available = Command::new(&program)
+                .arg("--version")
+                .output()
+                .await
+                .map(|out| out.status.success())
+                .unwrap_or(false);
+            if !program_available {
+                tx.send_background_event_with_ticket(
+                    &ticket,
+                    format!(
+                        "`/pr` — `{program}` is not available. Install it or set `[git] pr_command` in config.toml."
+                    ),
+                );
+                return;
+            }
+
+            let status_snippet = if short_status.trim().is_empty() {
+                "(clean working tree)".to_string()
+            } else {
+                short_status.trim_end().to_string()
+            };
+            let base = default_branch.clone().unwrap_or_else(|| "the default branch".to_string());
+            let on_default_branch = match (&current_branch, &default_branch) {
+                (None, _) => true,
+                (Some(current), Some(default)) => current == default,
+                (Some(_), None) => false,
+            };
+
+            let mut steps = Vec::new();
+            if on_default_branch {
+                steps.push(format!(
+                    "Create and switch to a new branch off the current HEAD, named after the changes in this session (short, kebab-case). Do not commit to {base}."
+                ));
+            }
+            if !short_status.trim().is_empty() {
+                steps.push(
+                    "Commit the changes made in this session. Leave out temporary files and secrets. Write the commit message from what was done in this session: an imperative subject under 72 characters, then a short body explaining why."
+                        .to_string(),
+                );
+            }
+            steps.push("Push the branch and set its upstream (`git push -u origin HEAD`).".to_string());
+            steps.push(format!(
+                "Open the pull request against {base} with `{pr_command}`, passing a title and a description written from the session summary: what changed, why, and how it was verified. Pass them as arguments so the command does not prompt."
+            ));
+            steps.push(
+                "If a pull request already exists for this branch, do not open another; use its URL."
+                    .to_string(),
+            );
+
+            let mut message = String::from("You have permission to create a branch, commit, push and open a pull request.\n");
+            message.push_str("\nRepository snapshot:\n");
+            let _ = writeln!(
+                message,
+                "Current branch: {}",
+                current_branch.as_deref().unwrap_or("(detached HEAD)")
+            );
+            let _ = writeln!(message, "Default branch: {base}");
+            message.push_str("`git status --short`:\n");
+            message.push_str(&status_snippet);
+            message.push_str("\n\n");
+            for (idx, step) in steps.iter().enumerate() {
+                let _ = writeln!(message, "{}. {}", idx + 1, step);
+            }
+            message.push_str("Finish with a one-line reply of the form `Pull request: <url>`.");
+
+            tx.send(AppEvent::SubmitHiddenTextWithPreface {
+                agent_text: message,
+                preface: String::new(),
+                surface_notice: false,
+            });
+        });
+    }
+
+    pub(crate) fn handle_map_command(&mut self) {
+        self.consume_pending_prompt_for_ui_only_turn();
+        let root = self.config.cwd.clone();
+        let max_tokens = self.config.repo_map.max_tokens();
+        let tx = self.app_event_tx.clone();
+        let ticket = self.make_background_tail_ticket();
+        self.push_background_tail("Mapping repository…".to_string());
+        self.request_redraw();
+
+        tokio::spawn(async move {
+            let map = match tokio::task::spawn_blocking(move || {
+                code_core::repo_map::build_repo_map(&root, max_tokens)
+            })
+            .await
+            {
+                Ok(map) => map,
+                Err(err) => {
+                    tx.send_background_event_with_ticket(
+                        &ticket,
+                        format!("`/map` — failed to build the repository map: {err}"),
+                    );
+                    return;
+                }
+            };
+            if map.files == 0 {
+                tx.send_background_event_with_ticket(
+                    &ticket,
+                    "`/map` — no files to map in this directory.".to_string(),
+                );
+                return;
+            }
+
+            let mut summary = format!(
+                "Repository map added to the conversation: {} files, {} symbols (~{} tokens).",
+                map.files,
+                map.symbols,
+                map.text.len() / 4
+            );
+            if map.omitted_files > 0 {
+                summary.push_str(&format!(
+                    " {} more files did not fit `[repo_map] max_tokens`.",
+                    map.omitted_files
+                ));
+            }
+            tx.send(AppEvent::CodexOp(Op::AddPendingInputDeveloper {
+                text: map.to_context_message(),
+            }));
+            tx.send_background_event_with_ticket(&ticket, summary);
+        });
+    }
+
     pub(crate) fn handle_project_command(&mut self, args: String) {
         let name = args.trim();
         if name.is_empty() {
@@ -459,6 +651,9 @@ impl ChatWidget<'_> {
         let previous_cwd = self.config.cwd.clone();
         self.config.cwd = new_cwd.clone();
         remember_cwd_history(&self.config.cwd);
+        self.set_context_cwd(&new_cwd);
+        // The scope was inside the old directory; the app clears its side.
+        self.scope = None;
         let ticket = self.make_background_tail_ticket();
 
         let msg = format!(
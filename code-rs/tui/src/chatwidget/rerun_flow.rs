@@ -0,0 +1,171 @@
+use super::*;
+
+/// Upper bound on how much captured output `/rerun send` forwards to the model.
+const RERUN_FEEDBACK_MAX_BYTES: usize = 16 * 1024;
+
+impl ChatWidget<'_> {
+    pub(crate) fn handle_rerun_command(&mut self, args: String) {
+        let args = args.trim();
+        if args.eq_ignore_ascii_case("send") {
+            self.send_last_rerun_to_model();
+            return;
+        }
+
+        let nth = if args.is_empty() {
+            1
+        } else {
+            match args.parse::<usize>() {
+                Ok(n) if n > 0 => n,
+                _ => {
+                    self.history_push_plain_state(crate::history_cell::new_error_event(
+                        "Usage: /rerun [n] to rerun the nth most recent command, or /rerun send"
+                            .to_string(),
+                    ));
+                    self.request_redraw();
+                    return;
+                }
+            }
+        };
+
+        let Some((command, cwd)) = self.nth_recent_exec_command(nth) else {
+            self.history_push_plain_state(crate::history_cell::new_error_event(
+                "`/rerun` — no completed command found in history".to_string(),
+            ));
+            self.request_redraw();
+            return;
+        };
+
+        self.exec.rerun_seq = self.exec.rerun_seq.saturating_add(1);
+        let call_id = format!("rerun_{}", self.exec.rerun_seq);
+        self.exec
+            .pending_reruns
+            .insert(ExecCallId(call_id.clone()));
+
+        let label = crate::exec_command::strip_bash_lc_and_escape(&command);
+        self.push_background_tail(format!("Rerunning `{label}`"));
+        self.submit_op(Op::RerunCommand {
+            call_id,
+            command,
+            cwd,
+        });
+        self.request_redraw();
+    }
+
+    /// Exec records in history, newest first, with merged cells expanded
+    /// into their segments.
+    pub(super) fn recent_exec_records(
+        &self,
+    ) -> impl Iterator<Item = &crate::history::state::ExecRecord> + '_ {
+        self.history_state
+            .records
+            .iter()
+            .rev()
+            .flat_map(|record| match record {
+                HistoryRecord::Exec(exec) => vec![exec],
+                HistoryRecord::MergedExec(merged) => merged.segments.iter().rev().collect(),
+                _ => Vec::new(),
+            })
+            .filter(|exec| !exec.command.is_empty())
+    }
+
+    /// Returns the command and working directory of the `nth` most recent
+    /// completed exec in history (1-based).
+    fn nth_recent_exec_command(&self, nth: usize) -> Option<(Vec<String>, Option<PathBuf>)> {
+        self.recent_exec_records()
+            .filter(|exec| exec.status != crate::history::state::ExecStatus::Running)
+            .nth(nth.saturating_sub(1))
+            .map(|exec| (exec.command.clone(), exec.working_dir.clone()))
+    }
+
+    /// Records the outcome of a `/rerun` when its ExecEnd arrives and offers
+    /// to forward the output to the model.
+    pub(super) fn note_rerun_exec_end(&mut self, ev: &ExecCommandEndEvent) {
+        let call_id = ExecCallId(ev.call_id.clone());
+        if !self.exec.pending_reruns.remove(&call_id) {
+            return;
+        }
+        let command = self
+            .history_state
+            .history_id_for_exec_call(&ev.call_id)
+            .and_then(|id| match self.history_state.record(id) {
+                Some(HistoryRecord::Exec(record)) => Some(record.command.clone()),
+                _ => None,
+            })
+            .unwrap_or_default();
+        self.exec.last_rerun = Some(RerunOutcome {
+            command,
+            exit_code: ev.exit_code,
+            stdout: ev.stdout.clone(),
+            stderr: ev.stderr.clone(),
+        });
+        self.push_background_tail(format!(
+            "Rerun finished with exit code {}. Use `/rerun send` to share the output with Code.",
+            ev.exit_code
+        ));
+    }
+
+    fn send_last_rerun_to_model(&mut self) {
+        let Some(outcome) = self.exec.last_rerun.take() else {
+            self.history_push_plain_state(crate::history_cell::new_error_event(
+                "`/rerun send` — no rerun output to share yet".to_string(),
+            ));
+            self.request_redraw();
+            return;
+        };
+        self.submit_text_message(format_rerun_feedback(&outcome));
+    }
+}
+
+fn format_rerun_feedback(outcome: &RerunOutcome) -> String {
+    let label = crate::exec_command::strip_bash_lc_and_escape(&outcome.command);
+    let mut output = outcome.stdout.trim_end().to_string();
+    let stderr = outcome.stderr.trim_end();
+    if !stderr.is_empty() {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output.push_str(stderr);
+    }
+    if output.len() > RERUN_FEEDBACK_MAX_BYTES {
+        let mut cut = output.len() - RERUN_FEEDBACK_MAX_BYTES;
+        while !output.is_char_boundary(cut) {
+            cut += 1;
+        }
+        output = format!("[… output truncated …]\n{}", &output[cut..]);
+    }
+    format!(
+        "I reran `{label}` (exit code {}). Output:\n```\n{output}\n```",
+        outcome.exit_code
+    )
+}
+
+#[cfg(test)]
+mod rerun_feedback_tests {
+    use super::*;
+
+    #[test]
+    fn feedback_includes_exit_code_and_both_streams() {
+        let outcome = RerunOutcome {
+            command: vec!["cargo".into(), "test".into()],
+            exit_code: 101,
+            stdout: "running 1 test\n".into(),
+            stderr: "error: test failed\n".into(),
+        };
+        let text = format_rerun_feedback(&outcome);
+        assert!(text.starts_with("I reran `cargo test` (exit code 101)"));
+        assert!(text.contains("running 1 test\nerror: test failed\n```"));
+    }
+
+    #[test]
+    fn feedback_keeps_tail_of_long_output() {
+        let outcome = RerunOutcome {
+            command: vec!["yes".into()],
+            exit_code: 0,
+            stdout: format!("{}END", "y\n".repeat(RERUN_FEEDBACK_MAX_BYTES)),
+            stderr: String::new(),
+        };
+        let text = format_rerun_feedback(&outcome);
+        assert!(text.contains("[… output truncated …]"));
+        assert!(text.contains("END\n```"));
+    }
+}
@@ -0,0 +1,208 @@
+use super::*;
+use crate::history::state::AssistantMessageState;
+
+/// What `/retry` was asked to do.
+#[derive(Debug, PartialEq)]
+enum RetryRequest {
+    /// Resend the last turn, optionally on another model or temperature.
+    Resend {
+        model: Option<String>,
+        temperature: Option<f64>,
+    },
+    /// Show the answer with this index (`a` is 0).
+    Show(usize),
+}
+
+fn parse_retry_args(args: &str) -> Result<RetryRequest, String> {
+    let mut model = None;
+    let mut temperature = None;
+    let tokens: Vec<&str> = args.split_whitespace().collect();
+    if let [token] = tokens.as_slice()
+        && token.len() == 1
+        && let Some(letter) = token.chars().next().filter(char::is_ascii_lowercase)
+    {
+        return Ok(RetryRequest::Show(usize::from(letter as u8 - b'a')));
+    }
+    for token in tokens {
+        if let Ok(value) = token.parse::<f64>() {
+            if !(0.0..=2.0).contains(&value) {
+                return Err(format!("temperature {value} is outside 0–2"));
+            }
+            temperature = Some(value);
+        } else if model.is_none() {
+            model = Some(token.to_string());
+        } else {
+            return Err(format!("unexpected argument `{token}`"));
+        }
+    }
+    Ok(RetryRequest::Resend { model, temperature })
+}
+
+/// `a`, `b`, … for the answer at `idx`.
+fn variant_letter(idx: usize) -> char {
+    u8::try_from(idx)
+        .ok()
+        .filter(|idx| *idx < 26)
+        .map_or('?', |idx| char::from(b'a' + idx))
+}
+
+impl ChatWidget<'_> {
+    /// `/retry [model] [temperature]` resends the last turn; `/retry a`,
+    /// `/retry b`, … switch between the answers it produced.
+    pub(crate) fn handle_retry_command(&mut self, args: String) {
+        match parse_retry_args(&args) {
+            Ok(RetryRequest::Show(idx)) => self.show_answer_variant(idx),
+            Ok(RetryRequest::Resend { model, temperature }) => self.retry_last_turn(model, temperature),
+            Err(err) => self.history_push_plain_state(history_cell::new_error_event(format!(
+                "`/retry` — {err}. Usage: `/retry [model] [temperature]` or `/retry a|b`."
+            ))),
+        }
+        self.request_redraw();
+    }
+
+    fn retry_last_turn(&mut self, model: Option<String>, temperature: Option<f64>) {
+        if !self.active_task_ids.is_empty() {
+            self.push_background_tail("Wait for the current turn to finish before retrying it.".to_string());
+            return;
+        }
+        let Some(idx) = self.last_answer_cell_index() else {
+            self.push_background_tail("There is no answer to retry yet.".to_string());
+            return;
+        };
+        let Some(current) = self.answer_state_at(idx) else {
+            return;
+        };
+        if self.answer_variants.answers.is_empty() {
+            let label = format!("{}, original", self.config.model);
+            self.answer_variants.answers.push((label, current));
+        }
+        let mut label = model.clone().unwrap_or_else(|| self.config.model.clone());
+        if let Some(temperature) = temperature {
+            label.push_str(&format!(", temperature {temperature}"));
+        }
+        self.answer_variants.pending = Some(label);
+        self.submit_op(Op::RetryLastTurn { model, temperature });
+    }
+
+    /// Called at turn end: a retried turn's answer becomes the next variant
+    /// and takes the place of the one on screen.
+    pub(super) fn record_retried_answer(&mut self) {
+        let Some(label) = self.answer_variants.pending.take() else {
+            return;
+        };
+        let Some(new_idx) = self.last_answer_cell_index() else {
+            return;
+        };
+        let Some(answer) = self.answer_state_at(new_idx) else {
+            return;
+        };
+        let Some((_, shown)) = self.answer_variants.answers.get(self.answer_variants.shown) else {
+            return;
+        };
+        if same_answer(shown, &answer) {
+            // The retry produced no answer of its own.
+            return;
+        }
+        if let Some(old_idx) = self.answer_cell_index_for(shown)
+            && old_idx < new_idx
+        {
+            self.history_remove_at(old_idx);
+        }
+        self.answer_variants.answers.push((label, answer));
+        let count = self.answer_variants.answers.len();
+        self.answer_variants.shown = count - 1;
+        let letters: Vec<String> = (0..count).map(|idx| format!("`/retry {}`", variant_letter(idx))).collect();
+        let letters = letters.join(", ");
+        let letter = variant_letter(count - 1).to_ascii_uppercase();
+        self.push_background_tail(format!(
+            "Showing answer {letter} of {count}. Switch with {letters}."
+        ));
+    }
+
+    fn show_answer_variant(&mut self, idx: usize) {
+        let count = self.answer_variants.answers.len();
+        if count < 2 {
+            self.push_background_tail("Only one answer to this turn; `/retry` makes another.".to_string());
+            return;
+        }
+        let Some((label, answer)) = self.answer_variants.answers.get(idx).cloned() else {
+            let last = variant_letter(count - 1);
+            self.push_background_tail(format!("There are answers a to {last} for this turn."));
+            return;
+        };
+        let Some((_, shown)) = self.answer_variants.answers.get(self.answer_variants.shown) else {
+            return;
+        };
+        let Some(cell_idx) = self.answer_cell_index_for(shown) else {
+            self.push_background_tail("The answer for this turn is no longer in the history.".to_string());
+            return;
+        };
+        self.last_assistant_message = Some(answer.markdown.clone());
+        let cell = history_cell::AssistantMarkdownCell::from_state(answer, &self.config);
+        self.history_replace_at(cell_idx, Box::new(cell));
+        self.answer_variants.shown = idx;
+        let letter = variant_letter(idx).to_ascii_uppercase();
+        self.push_background_tail(format!("Showing answer {letter} of {count} ({label})."));
+    }
+
+    /// A new turn starts a new set of answers.
+    pub(super) fn reset_answer_variants(&mut self) {
+        self.answer_variants = AnswerVariantsState::default();
+    }
+
+    fn last_answer_cell_index(&self) -> Option<usize> {
+        self.history_cells.iter().rposition(|cell| {
+            cell.as_any()
+                .downcast_ref::<history_cell::AssistantMarkdownCell>()
+                .is_some_and(|cell| !cell.state().mid_turn)
+        })
+    }
+
+    fn answer_state_at(&self, idx: usize) -> Option<AssistantMessageState> {
+        self.history_cells
+            .get(idx)?
+            .as_any()
+            .downcast_ref::<history_cell::AssistantMarkdownCell>()
+            .map(|cell| cell.state().clone())
+    }
+
+    fn answer_cell_index_for(&self, answer: &AssistantMessageState) -> Option<usize> {
+        self.history_cells.iter().rposition(|cell| {
+            cell.as_any()
+                .downcast_ref::<history_cell::AssistantMarkdownCell>()
+                .is_some_and(|cell| same_answer(cell.state(), answer))
+        })
+    }
+}
+
+/// Answer cells are matched by stream id, or by text when there is none.
+fn same_answer(a: &AssistantMessageState, b: &AssistantMessageState) -> bool {
+    match (a.stream_id.as_deref(), b.stream_id.as_deref()) {
+        (Some(a), Some(b)) => a == b,
+        _ => a.markdown == b.markdown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_resend_and_show_arguments() {
+        assert_eq!(
+            parse_retry_args(""),
+            Ok(RetryRequest::Resend { model: None, temperature: None })
+        );
+        assert_eq!(
+            parse_retry_args("gpt-5.1 0.9"),
+            Ok(RetryRequest::Resend {
+                model: Some("gpt-5.1".to_string()),
+                temperature: Some(0.9),
+            })
+        );
+        assert_eq!(parse_retry_args("b"), Ok(RetryRequest::Show(1)));
+        assert!(parse_retry_args("3.5").is_err());
+        assert!(parse_retry_args("one two").is_err());
+        assert_eq!(variant_letter(2), 'c');
+    }
+}
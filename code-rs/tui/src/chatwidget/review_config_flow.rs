@@ -0,0 +1,198 @@
+use super::*;
+use code_core::config_types::AutoReviewConfig;
+use code_core::config_types::ReviewSeverity;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+const REVIEW_CONFIG_USAGE: &str = "Usage: /review config [severity <info|warning|error|critical> | include <glob…>|none | exclude <glob…>|none | prompt <text>|reset | reset]";
+
+/// What a `/review config …` command changes for the current project.
+#[derive(Debug, PartialEq, Eq)]
+enum ReviewConfigCommand {
+    Show,
+    Severity(ReviewSeverity),
+    Include(Vec<String>),
+    Exclude(Vec<String>),
+    /// `None` restores the inherited prompt.
+    Prompt(Option<String>),
+    Reset,
+}
+
+fn parse_review_config_command(args: &str) -> Result<ReviewConfigCommand, String> {
+    let args = args.trim();
+    let (verb, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+    let rest = rest.trim();
+    let globs = |rest: &str| -> Vec<String> {
+        if rest == "none" {
+            Vec::new()
+        } else {
+            rest.split([',', ' '])
+                .map(str::trim)
+                .filter(|glob| !glob.is_empty())
+                .map(str::to_string)
+                .collect()
+        }
+    };
+    match verb {
+        "" | "show" if rest.is_empty() => Ok(ReviewConfigCommand::Show),
+        "severity" | "min_severity" => ReviewSeverity::parse(rest)
+            .map(ReviewConfigCommand::Severity)
+            .ok_or_else(|| format!("Unknown severity `{rest}`.\n{REVIEW_CONFIG_USAGE}")),
+        "include" if !rest.is_empty() => Ok(ReviewConfigCommand::Include(globs(rest))),
+        "exclude" if !rest.is_empty() => Ok(ReviewConfigCommand::Exclude(globs(rest))),
+        "prompt" if rest == "reset" => Ok(ReviewConfigCommand::Prompt(None)),
+        "prompt" if !rest.is_empty() => Ok(ReviewConfigCommand::Prompt(Some(rest.to_string()))),
+        "reset" if rest.is_empty() => Ok(ReviewConfigCommand::Reset),
+        _ => Err(REVIEW_CONFIG_USAGE.to_string()),
+    }
+}
+
+fn describe_globs(globs: &[String]) -> String {
+    if globs.is_empty() {
+        "—".to_string()
+    } else {
+        globs.join(", ")
+    }
+}
+
+impl ChatWidget<'_> {
+    /// `/review config`: shows the auto-review prompt, file scope and
+    /// severity gate in effect here, and edits the current project's
+    /// overrides under `[projects."<cwd>".auto_review]`.
+    pub(crate) fn handle_review_config_command(&mut self, args: &str) {
+        let command = match parse_review_config_command(args) {
+            Ok(command) => command,
+            Err(usage) => {
+                self.history_push_plain_state(history_cell::new_error_event(usage));
+                self.request_redraw();
+                return;
+            }
+        };
+        let code_home = self.config.code_home.clone();
+        let project = self.config.cwd.clone();
+        let (global, mut overrides) = match code_core::config::load_auto_review_settings(&code_home, &project) {
+            Ok(layers) => layers,
+            Err(err) => {
+                self.history_push_plain_state(history_cell::new_error_event(format!(
+                    "`/review config` — could not read config.toml: {err}"
+                )));
+                self.request_redraw();
+                return;
+            }
+        };
+
+        let changed = match command {
+            ReviewConfigCommand::Show => false,
+            ReviewConfigCommand::Severity(severity) => {
+                overrides.min_severity = Some(severity);
+                true
+            }
+            ReviewConfigCommand::Include(globs) => {
+                overrides.include = globs;
+                true
+            }
+            ReviewConfigCommand::Exclude(globs) => {
+                overrides.exclude = globs;
+                true
+            }
+            ReviewConfigCommand::Prompt(prompt) => {
+                overrides.prompt = prompt;
+                true
+            }
+            ReviewConfigCommand::Reset => {
+                overrides = AutoReviewConfig::default();
+                true
+            }
+        };
+        if changed {
+            if let Err(err) = code_core::config::set_project_auto_review(&code_home, &project, &overrides) {
+                self.history_push_plain_state(history_cell::new_error_event(format!(
+                    "`/review config` — could not save settings: {err}"
+                )));
+                self.request_redraw();
+                return;
+            }
+            self.config.auto_review = global.overridden_by(&overrides);
+        }
+        self.show_review_config(&overrides);
+    }
+
+    fn show_review_config(&mut self, overrides: &AutoReviewConfig) {
+        let effective = &self.config.auto_review;
+        let dim = Style::default().fg(crate::colors::text_dim());
+        let source = |is_project: bool| {
+            Span::styled(if is_project { "  (project)" } else { "" }, dim)
+        };
+        let severity = effective.min_severity();
+        let prompt = effective
+            .prompt
+            .as_deref()
+            .map(|prompt| prompt.trim().replace('\n', " "))
+            .unwrap_or_else(|| "default".to_string());
+        let lines: Vec<Line<'static>> = vec![
+            Line::from("auto review settings".dim()),
+            Line::from(vec![
+                Span::raw(format!(
+                    "Severity gate: {} and above ({}–P0)",
+                    severity.as_str(),
+                    severity.priority_label()
+                )),
+                source(overrides.min_severity.is_some()),
+            ]),
+            Line::from(vec![
+                Span::raw(format!("Include: {}", describe_globs(&effective.include))),
+                source(!overrides.include.is_empty()),
+            ]),
+            Line::from(vec![
+                Span::raw(format!("Exclude: {}", describe_globs(&effective.exclude))),
+                source(!overrides.exclude.is_empty()),
+            ]),
+            Line::from(vec![
+                Span::raw(format!("Prompt: {prompt}")),
+                source(overrides.prompt.is_some()),
+            ]),
+            Line::from(Span::styled(
+                format!("Project settings are saved for {}.", self.config.cwd.display()),
+                dim,
+            )),
+            Line::from(Span::styled(REVIEW_CONFIG_USAGE, dim)),
+        ];
+        self.history_push_plain_state(history_cell::plain_message_state_from_lines(
+            lines,
+            history_cell::HistoryCellType::Notice,
+        ));
+        self.request_redraw();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_review_config_subcommands() {
+        assert_eq!(parse_review_config_command(""), Ok(ReviewConfigCommand::Show));
+        assert_eq!(
+            parse_review_config_command("severity Warning"),
+            Ok(ReviewConfigCommand::Severity(ReviewSeverity::Warning))
+        );
+        assert_eq!(
+            parse_review_config_command("exclude *.lock, vendor/**"),
+            Ok(ReviewConfigCommand::Exclude(vec![
+                "*.lock".to_string(),
+                "vendor/**".to_string()
+            ]))
+        );
+        assert_eq!(
+            parse_review_config_command("include none"),
+            Ok(ReviewConfigCommand::Include(Vec::new()))
+        );
+        assert_eq!(
+            parse_review_config_command("prompt reset"),
+            Ok(ReviewConfigCommand::Prompt(None))
+        );
+        assert!(parse_review_config_command("severity loud").is_err());
+        assert!(parse_review_config_command("include").is_err());
+    }
+}
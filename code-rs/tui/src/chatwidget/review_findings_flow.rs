@@ -0,0 +1,288 @@
+use super::*;
+use code_core::config_types::ReviewSeverity;
+use code_core::protocol::ReviewOutputEvent;
+use std::path::Path;
+
+/// Keeps fix prompts small when a finding sits in a large rewritten region.
+const MAX_HUNK_LINES: usize = 120;
+
+/// New-file start and length from a `@@ -a,b +c,d @@` header.
+fn parse_hunk_header(line: &str) -> Option<(u32, u32)> {
+    let rest = line.strip_prefix("@@ -")?;
+    let (_, rest) = rest.split_once(" +")?;
+    let (range, _) = rest.split_once(" @@")?;
+    match range.split_once(',') {
+        Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+/// The hunks of a unified diff whose new-file lines overlap `start..=end`.
+fn diff_hunks_for_lines(diff: &str, start: u32, end: u32) -> Option<String> {
+    let mut hunks: Vec<&str> = Vec::new();
+    // The hunk being read, and whether it overlaps the finding.
+    let mut current: Option<(bool, Vec<&str>)> = None;
+    for line in diff.lines() {
+        let header = parse_hunk_header(line);
+        if header.is_some() || line.starts_with("diff --git") {
+            if let Some((true, lines)) = current.take() {
+                hunks.extend(lines);
+            }
+            current = header.map(|(new_start, new_len)| {
+                let new_end = new_start + new_len.max(1) - 1;
+                (new_start <= end && start <= new_end, vec![line])
+            });
+        } else if let Some((_, lines)) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+    if let Some((true, lines)) = current {
+        hunks.extend(lines);
+    }
+    if hunks.is_empty() {
+        return None;
+    }
+    if hunks.len() > MAX_HUNK_LINES {
+        hunks.truncate(MAX_HUNK_LINES);
+        hunks.push("…");
+    }
+    Some(hunks.join("\n"))
+}
+
+/// Looks for the finding's lines in the uncommitted changes, then in the
+/// last commit.
+async fn finding_diff_hunk(root: &Path, path: &Path, start: u32, end: u32) -> Option<String> {
+    let attempts: [&[&str]; 2] = [
+        &["diff", "HEAD", "--unified=3", "--"],
+        &["show", "--format=", "--unified=3", "HEAD", "--"],
+    ];
+    for args in attempts {
+        let Ok(output) = tokio::process::Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(args)
+            .arg(path)
+            .output()
+            .await
+        else {
+            return None;
+        };
+        if !output.status.success() {
+            continue;
+        }
+        if let Some(hunk) = diff_hunks_for_lines(&String::from_utf8_lossy(&output.stdout), start, end) {
+            return Some(hunk);
+        }
+    }
+    None
+}
+
+fn finding_location(entry: &ReviewFindingEntry, root: &Path) -> String {
+    let location = &entry.finding.code_location;
+    let path = location.absolute_file_path.as_path();
+    let path = path.strip_prefix(root).unwrap_or(path).display();
+    let (start, end) = (location.line_range.start, location.line_range.end);
+    if end > start {
+        format!("{path}:{start}-{end}")
+    } else {
+        format!("{path}:{start}")
+    }
+}
+
+fn fix_finding_prompt(entry: &ReviewFindingEntry, hunk: Option<&str>) -> String {
+    let finding = &entry.finding;
+    let location = &finding.code_location;
+    let severity = ReviewSeverity::from_priority(finding.priority);
+    let mut prompt = format!(
+        "Fix this code review finding.\n\n{}\nSeverity: {} · {}:{}-{}",
+        finding.title.trim(),
+        severity.as_str(),
+        location.absolute_file_path.display(),
+        location.line_range.start,
+        location.line_range.end,
+    );
+    let body = finding.body.trim();
+    if !body.is_empty() {
+        prompt.push_str(&format!("\n\n{body}"));
+    }
+    match hunk {
+        Some(hunk) => prompt.push_str(&format!("\n\nRelevant diff hunk:\n```diff\n{hunk}\n```")),
+        None => prompt.push_str("\n\nNo diff hunk covers these lines; read the file around them first."),
+    }
+    prompt.push_str("\n\nMake the smallest change that resolves the finding, then summarize what you changed.");
+    prompt
+}
+
+impl ChatWidget<'_> {
+    /// Keeps the findings of a finished `/review` for `/review findings`.
+    pub(crate) fn record_review_findings(&mut self, output: &ReviewOutputEvent, root: Option<PathBuf>) {
+        let entries = code_core::review_passes::split_review_passes(output)
+            .into_iter()
+            .flat_map(|section| {
+                let pass = section.name;
+                section.findings.into_iter().map(move |finding| ReviewFindingEntry {
+                    finding,
+                    pass: pass.clone(),
+                    resolved: false,
+                })
+            })
+            .collect();
+        self.review_findings = ReviewFindingsState {
+            entries,
+            root,
+            fixing: None,
+        };
+    }
+
+    /// `/review findings`: the last review's findings as a list. Enter sends
+    /// a fix prompt for the selected finding with its diff hunk attached.
+    pub(crate) fn show_review_findings(&mut self) {
+        if self.review_findings.entries.is_empty() {
+            self.push_background_tail("No review findings yet; run /review first.".to_string());
+            self.request_redraw();
+            return;
+        }
+        let root = self
+            .review_findings
+            .root
+            .clone()
+            .unwrap_or_else(|| self.config.cwd.clone());
+        let first_open = self
+            .review_findings
+            .entries
+            .iter()
+            .position(|entry| !entry.resolved)
+            .unwrap_or(0);
+        let items: Vec<SelectionItem> = self
+            .review_findings
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let marker = if entry.resolved {
+                    "[x]"
+                } else if self.review_findings.fixing == Some(index) {
+                    "[~]"
+                } else {
+                    "[ ]"
+                };
+                let mut details = vec![
+                    ReviewSeverity::from_priority(entry.finding.priority).as_str().to_string(),
+                    finding_location(entry, &root),
+                ];
+                if let Some(pass) = &entry.pass {
+                    details.push(pass.clone());
+                }
+                if let Some(suggestion) = entry.finding.body.lines().map(str::trim).find(|line| !line.is_empty()) {
+                    details.push(suggestion.to_string());
+                }
+                SelectionItem {
+                    name: format!("{marker} {}", entry.finding.title.trim()),
+                    description: Some(details.join(" · ")),
+                    is_current: index == first_open,
+                    actions: vec![Box::new(move |tx: &crate::app_event_sender::AppEventSender| {
+                        tx.send(crate::app_event::AppEvent::FixReviewFinding(index));
+                    })],
+                }
+            })
+            .collect();
+        let resolved = self
+            .review_findings
+            .entries
+            .iter()
+            .filter(|entry| entry.resolved)
+            .count();
+        let open = items.len() - resolved;
+        let view = ListSelectionView::new(
+            " Review findings ".to_string(),
+            Some(format!("{open} open · {resolved} resolved")),
+            Some("Enter fix · Esc close".to_string()),
+            items,
+            self.app_event_tx.clone(),
+            8,
+        );
+        self.bottom_pane
+            .show_list_selection("Review findings".to_string(), None, None, view);
+        self.request_redraw();
+    }
+
+    /// Looks up the diff hunk for finding `index`, then sends its fix prompt.
+    pub(crate) fn fix_review_finding(&mut self, index: usize) {
+        let Some(entry) = self.review_findings.entries.get(index) else {
+            return;
+        };
+        let root = self
+            .review_findings
+            .root
+            .clone()
+            .unwrap_or_else(|| self.config.cwd.clone());
+        let location = entry.finding.code_location.clone();
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let hunk = finding_diff_hunk(
+                &root,
+                &location.absolute_file_path,
+                location.line_range.start,
+                location.line_range.end,
+            )
+            .await;
+            tx.send(crate::app_event::AppEvent::SubmitReviewFindingFix { index, hunk });
+        });
+    }
+
+    pub(crate) fn submit_review_finding_fix(&mut self, index: usize, hunk: Option<String>) {
+        let Some(entry) = self.review_findings.entries.get(index) else {
+            return;
+        };
+        let display = format!("Fix review finding: {}", entry.finding.title.trim());
+        let prompt = fix_finding_prompt(entry, hunk.as_deref());
+        self.review_findings.fixing = Some(index);
+        self.submit_prompt_with_display(display, prompt);
+    }
+
+    /// Checks off the finding whose fix prompt just finished.
+    pub(crate) fn check_off_review_finding_fix(&mut self) {
+        let Some(index) = self.review_findings.fixing.take() else {
+            return;
+        };
+        let Some(entry) = self.review_findings.entries.get_mut(index) else {
+            return;
+        };
+        entry.resolved = true;
+        let title = entry.finding.title.trim().to_string();
+        let open = self
+            .review_findings
+            .entries
+            .iter()
+            .filter(|entry| !entry.resolved)
+            .count();
+        let message = if open == 0 {
+            format!("✓ Resolved {title} · all review findings resolved")
+        } else {
+            format!("✓ Resolved {title} · {open} open, /review findings for the rest")
+        };
+        self.push_background_tail(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "diff --git a/src/lib.rs b/src/lib.rs\nindex 1..2 100644\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,3 +1,4 @@\n fn a() {}\n+fn b() {}\n fn c() {}\n fn d() {}\n@@ -40,2 +41,3 @@ impl Foo {\n     let x = 1;\n+    let y = 2;\n }";
+
+    #[test]
+    fn picks_hunks_overlapping_the_finding() {
+        assert_eq!(parse_hunk_header("@@ -40,2 +41,3 @@ impl Foo {"), Some((41, 3)));
+        assert_eq!(parse_hunk_header("@@ -1 +1 @@"), Some((1, 1)));
+        assert_eq!(
+            diff_hunks_for_lines(DIFF, 42, 42).as_deref(),
+            Some("@@ -40,2 +41,3 @@ impl Foo {\n     let x = 1;\n+    let y = 2;\n }")
+        );
+        assert_eq!(
+            diff_hunks_for_lines(DIFF, 2, 2).as_deref(),
+            Some("@@ -1,3 +1,4 @@\n fn a() {}\n+fn b() {}\n fn c() {}\n fn d() {}")
+        );
+        assert_eq!(diff_hunks_for_lines(DIFF, 20, 30), None);
+    }
+}
@@ -1,5 +1,6 @@
 use super::*;
 use code_protocol::protocol::ReviewTarget;
+use code_core::config_types::AutoReviewConfig;
 
 impl ChatWidget<'_> {
     pub(super) fn auto_resolve_enabled(&self) -> bool {
@@ -891,6 +892,12 @@ impl ChatWidget<'_> {
         if !self.turn_had_code_edits && self.pending_auto_review_range.is_none() {
             return;
         }
+        if let Some(constraint) = code_core::battery::cached_background_work_constraint() {
+            self.push_background_tail(format!(
+                "Auto review skipped: {constraint}. Run /review when ready."
+            ));
+            return;
+        }
         if matches!(self.current_turn_origin, Some(TurnOrigin::Developer)) {
             return;
         }
@@ -1185,7 +1192,8 @@ impl ChatWidget<'_> {
                 (worktree_path, branch, None)
             };
 
-            let (has_findings, findings, summary) = Self::parse_agent_review_result(agent.result.as_deref());
+            let (has_findings, findings, summary) =
+                self.parse_auto_review_result(agent.result.as_deref(), Some(&worktree_path));
 
             self.processed_auto_review_agents.insert(agent.id.clone());
             self.on_background_review_finished(BackgroundReviewFinishedEvent {
@@ -1204,6 +1212,54 @@ impl ChatWidget<'_> {
     /// Parse the auto-review agent result to derive findings count and a concise summary.
     /// Tries to deserialize `ReviewOutputEvent` JSON (direct or fenced). Falls back to heuristics.
     pub(super) fn parse_agent_review_result(raw: Option<&str>) -> (bool, usize, Option<String>) {
+        Self::parse_agent_review_result_gated(raw, None)
+    }
+
+    /// Like [`Self::parse_agent_review_result`], but drops findings outside the
+    /// `[auto_review]` file scope or below its severity gate first. Paths are
+    /// matched relative to the review worktree or the session cwd.
+    pub(super) fn parse_auto_review_result(
+        &self,
+        raw: Option<&str>,
+        worktree_path: Option<&Path>,
+    ) -> (bool, usize, Option<String>) {
+        let mut roots: Vec<&Path> = worktree_path
+            .filter(|path| !path.as_os_str().is_empty())
+            .into_iter()
+            .collect();
+        roots.push(self.config.cwd.as_path());
+        Self::parse_agent_review_result_gated(raw, Some((&self.config.auto_review, &roots)))
+    }
+
+    fn parse_agent_review_result_gated(
+        raw: Option<&str>,
+        gate: Option<(&AutoReviewConfig, &[&Path])>,
+    ) -> (bool, usize, Option<String>) {
+        let mut hidden = 0;
+        let mut apply_gate = |output: &mut ReviewOutputEvent| {
+            if let Some((config, roots)) = gate {
+                hidden += code_core::auto_review::gate_findings(config, roots, &mut output.findings);
+            }
+        };
+        let result = Self::parse_agent_review_result_inner(raw, &mut apply_gate);
+        if hidden == 0 {
+            return result;
+        }
+        let (has_findings, findings, summary) = result;
+        let note = format!(
+            "{hidden} finding(s) below the severity gate or outside the file scope were hidden (see /review config)."
+        );
+        let summary = Some(match summary {
+            Some(existing) => format!("{existing} \n{note}"),
+            None => note,
+        });
+        (has_findings, findings, summary)
+    }
+
+    fn parse_agent_review_result_inner(
+        raw: Option<&str>,
+        apply_gate: &mut dyn FnMut(&mut ReviewOutputEvent),
+    ) -> (bool, usize, Option<String>) {
         let Some(text) = raw else { return (false, 0, None); };
         let trimmed = text.trim();
         if trimmed.is_empty() {
@@ -1224,11 +1280,13 @@ impl ChatWidget<'_> {
             if runs.is_empty() {
                 runs.push(wrapper.latest);
             }
+            runs.iter_mut().for_each(|run| apply_gate(run));
             return Self::review_result_from_runs(&runs);
         }
 
         // Try direct JSON first.
-        if let Ok(output) = serde_json::from_str::<ReviewOutputEvent>(trimmed) {
+        if let Ok(mut output) = serde_json::from_str::<ReviewOutputEvent>(trimmed) {
+            apply_gate(&mut output);
             return Self::review_result_from_output(&output);
         }
 
@@ -1236,7 +1294,8 @@ impl ChatWidget<'_> {
         if let Some(start) = trimmed.find("```")
             && let Some((body, _)) = trimmed[start + 3..].split_once("```") {
                 let candidate = body.trim_start_matches("json").trim();
-                if let Ok(output) = serde_json::from_str::<ReviewOutputEvent>(candidate) {
+                if let Ok(mut output) = serde_json::from_str::<ReviewOutputEvent>(candidate) {
+                    apply_gate(&mut output);
                     return Self::review_result_from_output(&output);
                 }
             }
@@ -2027,6 +2086,16 @@ impl ChatWidget<'_> {
 
     /// Handle `/review [focus]` command by starting a dedicated review session.
     pub(crate) fn handle_review_command(&mut self, args: String) {
+        if let Some(rest) = args.trim().strip_prefix("config")
+            && (rest.is_empty() || rest.starts_with(char::is_whitespace))
+        {
+            self.handle_review_config_command(rest);
+            return;
+        }
+        if args.trim() == "findings" {
+            self.show_review_findings();
+            return;
+        }
         if self.is_task_running() {
             self.history_push_plain_state(crate::history_cell::new_error_event(
                 "`/review` — complete or cancel the current task before starting a new review.".to_string(),
@@ -209,7 +209,7 @@ pub(super) fn finalize_all_due_to_answer(chat: &mut ChatWidget<'_>) {
 
     chat.tools_state.running_custom_tools = unresolved;
     if any_finalized {
-        chat.invalidate_height_cache();
+        chat.invalidate_history_offsets();
         chat.request_redraw();
     }
     chat.history_debug(format!(
@@ -0,0 +1,137 @@
+use super::*;
+use serde_json::Value;
+
+/// Top-level fields of a structured answer, one `name: value` line each.
+/// Strings are shown without quotes; nested values as compact JSON.
+fn extracted_fields(value: &Value) -> Vec<String> {
+    let Some(map) = value.as_object() else {
+        return Vec::new();
+    };
+    map.iter()
+        .map(|(key, field)| match field {
+            Value::String(text) => format!("{key}: {text}"),
+            Value::Array(items) if items.iter().all(Value::is_string) && !items.is_empty() => {
+                let items: Vec<&str> = items.iter().filter_map(Value::as_str).collect();
+                let items = items.join(", ");
+                format!("{key}: {items}")
+            }
+            other => format!("{key}: {other}"),
+        })
+        .collect()
+}
+
+impl ChatWidget<'_> {
+    /// `/schema set <file>` asks for every answer as JSON matching the schema
+    /// in `file`; `/schema clear` goes back to free-form answers, and `/schema`
+    /// alone shows which schema is active.
+    pub(crate) fn handle_schema_command(&mut self, args: String) {
+        let args = args.trim();
+        let (action, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+        match action {
+            "" | "status" => {
+                let message = match self.output_schema.as_ref() {
+                    Some((path, _)) => format!(
+                        "Answers are requested as JSON matching {}. `/schema clear` turns this off.",
+                        path.display()
+                    ),
+                    None => "No output schema set. `/schema set <file.json>` asks for answers as JSON matching it.".to_string(),
+                };
+                self.push_background_tail(message);
+            }
+            "set" => self.set_output_schema(rest.trim()),
+            "clear" | "off" => {
+                let message = if self.output_schema.take().is_some() {
+                    "Output schema cleared; answers are free-form again."
+                } else {
+                    "No output schema set."
+                };
+                self.push_background_tail(message.to_string());
+            }
+            other => {
+                self.history_push_plain_state(crate::history_cell::new_error_event(format!(
+                    "`/schema` — unknown action `{other}`. Use `/schema set <file.json>`, `/schema clear` or `/schema`."
+                )));
+            }
+        }
+        self.request_redraw();
+    }
+
+    fn set_output_schema(&mut self, raw_path: &str) {
+        if raw_path.is_empty() {
+            self.history_push_plain_state(crate::history_cell::new_error_event(
+                "`/schema set` — provide a JSON Schema file, e.g. `/schema set answer.schema.json`".to_string(),
+            ));
+            return;
+        }
+        let path = self.config.cwd.join(raw_path);
+        let schema = std::fs::read_to_string(&path)
+            .map_err(|err| format!("could not read {}: {err}", path.display()))
+            .and_then(|contents| {
+                serde_json::from_str::<Value>(&contents)
+                    .map_err(|err| format!("{} is not valid JSON: {err}", path.display()))
+            })
+            .and_then(|schema| {
+                if schema.is_object() {
+                    Ok(schema)
+                } else {
+                    Err(format!("{} does not contain a JSON Schema object", path.display()))
+                }
+            });
+        match schema {
+            Ok(schema) => {
+                self.push_background_tail(format!(
+                    "Answers will be requested as JSON matching {}. `/schema clear` turns this off.",
+                    path.display()
+                ));
+                self.output_schema = Some((path, schema));
+            }
+            Err(err) => {
+                self.history_push_plain_state(crate::history_cell::new_error_event(format!(
+                    "`/schema set` — {err}"
+                )));
+            }
+        }
+    }
+
+    /// Shows the final answer of a structured turn as pretty JSON followed by
+    /// its top-level fields. Schema mismatches arrive separately as a warning
+    /// from core.
+    pub(super) fn show_structured_answer(&mut self, answer: Option<&str>) {
+        let Some(Ok(value)) = answer.map(code_core::output_schema::parse_structured_answer) else {
+            return;
+        };
+        let pretty = serde_json::to_string_pretty(&value).unwrap_or_else(|_| value.to_string());
+        let mut lines = vec!["Structured answer".to_string()];
+        lines.extend(pretty.lines().map(str::to_string));
+        let fields = extracted_fields(&value);
+        if !fields.is_empty() {
+            lines.push(String::new());
+            lines.push("Fields".to_string());
+            lines.extend(fields);
+        }
+        self.history_push_plain_paragraphs(PlainMessageKind::Notice, lines);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn fields_are_listed_with_readable_values() {
+        let value = json!({ "status": "ok", "files": ["a.rs", "b.rs"], "count": 2, "meta": { "x": 1 } });
+        let mut fields = extracted_fields(&value);
+        fields.sort();
+        assert_eq!(
+            fields,
+            vec![
+                "count: 2".to_string(),
+                "files: a.rs, b.rs".to_string(),
+                "meta: {\"x\":1}".to_string(),
+                "status: ok".to_string(),
+            ]
+        );
+        assert!(extracted_fields(&json!([1, 2])).is_empty());
+    }
+}
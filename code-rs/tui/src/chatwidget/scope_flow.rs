@@ -0,0 +1,159 @@
+use super::*;
+
+use super::cwd_flow::resolve_cd_target;
+use code_core::monorepo::Workspace;
+
+/// Members listed by `/scope` before the rest are counted.
+const MAX_LISTED_MEMBERS: usize = 12;
+
+impl ChatWidget<'_> {
+    /// `/scope <dir>` focuses the session on one subproject of a monorepo:
+    /// the model is asked to work inside it, and `@` mentions, file search
+    /// and `/diff` stay inside it. `<dir>` is a path or a workspace member
+    /// name; `/scope clear` removes the scope and `/scope` alone lists the
+    /// workspace members.
+    pub(crate) fn handle_scope_command(&mut self, args: String) {
+        let args = args.trim();
+        match args {
+            "" | "status" => self.show_scope_status(),
+            "clear" | "off" => {
+                let message = if self.scope.is_some() {
+                    "Scope cleared; the whole session directory is in play again."
+                } else {
+                    "No scope set."
+                };
+                self.set_scope(None);
+                self.push_background_tail(message.to_string());
+            }
+            target => match self.resolve_scope_target(target) {
+                Ok(scope) => {
+                    let workspace = code_core::monorepo::detect_workspace(&scope);
+                    let member = workspace
+                        .as_ref()
+                        .and_then(|workspace| {
+                            workspace.member_containing(&scope).map(|member| (workspace, member))
+                        })
+                        .map(|(workspace, member)| {
+                            format!(" (`{}` in the {})", member.display(), workspace.kind)
+                        })
+                        .unwrap_or_default();
+                    self.push_background_tail(format!(
+                        "Scoped to {}{member}. `@` mentions, file search and `/diff` stay inside it, and the model is asked to run commands there.",
+                        self.scope_display(&scope)
+                    ));
+                    self.set_scope(Some(scope));
+                }
+                Err(err) => {
+                    self.history_push_plain_state(history_cell::new_error_event(format!(
+                        "`/scope` — {err}."
+                    )));
+                }
+            },
+        }
+        self.request_redraw();
+    }
+
+    /// Tells core and the app about the scope; cleared by `None`.
+    fn set_scope(&mut self, scope: Option<PathBuf>) {
+        self.submit_op(Op::SetScope { path: scope.clone() });
+        self.app_event_tx.send(AppEvent::ScopeChanged(scope.clone()));
+        self.scope = scope;
+    }
+
+    /// Sends the scope again after the session is reconfigured, which
+    /// starts core with none.
+    pub(super) fn restore_scope(&mut self) {
+        if let Some(scope) = self.scope.clone() {
+            self.submit_op(Op::SetScope { path: Some(scope) });
+        }
+    }
+
+    fn show_scope_status(&mut self) {
+        let mut lines = Vec::new();
+        match self.scope.as_ref() {
+            Some(scope) => lines.push(format!("Scope: {}", self.scope_display(scope))),
+            None => lines.push(
+                "No scope set; `/scope <dir>` focuses the session on a subproject.".to_string(),
+            ),
+        }
+        match code_core::monorepo::detect_workspace(&self.config.cwd) {
+            Some(workspace) => lines.push(format_workspace(&workspace)),
+            None => lines.push("No Cargo, pnpm or Bazel workspace found here.".to_string()),
+        }
+        self.history_push_plain_paragraphs(PlainMessageKind::Notice, lines);
+    }
+
+    /// A workspace member (by path or unique directory name) or a directory
+    /// inside the session directory.
+    fn resolve_scope_target(&self, target: &str) -> Result<PathBuf, String> {
+        let cwd = self.canonical_cwd();
+        let member = code_core::monorepo::detect_workspace(&cwd).and_then(|workspace| {
+            let name = target.trim_end_matches('/');
+            let mut matches = workspace.members.iter().filter(|member| {
+                member.as_path() == Path::new(name)
+                    || member.file_name().is_some_and(|file| file == name)
+            });
+            match (matches.next(), matches.next()) {
+                (Some(member), None) => Some(workspace.root.join(member)),
+                _ => None,
+            }
+        });
+        let scope = match member {
+            Some(member) => member.canonicalize().unwrap_or(member),
+            None => {
+                let home = std::env::var_os("HOME").map(PathBuf::from);
+                resolve_cd_target(target, &cwd, home.as_deref())?
+            }
+        };
+        if scope == cwd {
+            return Err(
+                "that is the whole session directory; `/scope clear` removes the scope"
+                    .to_string(),
+            );
+        }
+        if !scope.starts_with(&cwd) {
+            return Err(format!(
+                "{} is outside the session directory; `/cd` there first",
+                scope.display()
+            ));
+        }
+        Ok(scope)
+    }
+
+    fn scope_display(&self, scope: &Path) -> String {
+        scope
+            .strip_prefix(self.canonical_cwd())
+            .unwrap_or(scope)
+            .display()
+            .to_string()
+    }
+
+    /// Scopes are stored canonicalized, so comparisons use the resolved
+    /// session directory.
+    fn canonical_cwd(&self) -> PathBuf {
+        self.config
+            .cwd
+            .canonicalize()
+            .unwrap_or_else(|_| self.config.cwd.clone())
+    }
+}
+
+fn format_workspace(workspace: &Workspace) -> String {
+    let count = workspace.members.len();
+    let mut listed: Vec<String> = workspace
+        .members
+        .iter()
+        .take(MAX_LISTED_MEMBERS)
+        .map(|member| member.display().to_string())
+        .collect();
+    if count > MAX_LISTED_MEMBERS {
+        listed.push(format!("… {} more", count - MAX_LISTED_MEMBERS));
+    }
+    format!(
+        "{} at {} with {count} member{}: {}",
+        workspace.kind,
+        workspace.root.display(),
+        if count == 1 { "" } else { "s" },
+        listed.join(", ")
+    )
+}
@@ -88,6 +88,7 @@ impl ChatWidget<'_> {
             mcp_server_failures: HashMap::new(),
             mcp_auth_statuses: HashMap::new(),
             startup_mcp_error_summary: None,
+            startup_stages: Vec::new(),
             remote_model_presets: None,
             allow_remote_default_at_startup: !config.model_explicit,
             chat_model_selected_explicitly: false,
@@ -108,6 +109,11 @@ impl ChatWidget<'_> {
             ),
             total_token_usage: TokenUsage::default(),
             last_token_usage: TokenUsage::default(),
+            last_request_models: (None, None),
+            output_schema: None,
+            session_root: config.cwd.clone(),
+            scope: None,
+            structured_turn_pending: false,
             rate_limit_snapshot: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             rate_limit_fetch_inflight: false,
@@ -134,6 +140,10 @@ impl ChatWidget<'_> {
                 pending_exec_ends: HashMap::new(),
                 suppressed_exec_end_call_ids: HashSet::new(),
                 suppressed_exec_end_order: VecDeque::new(),
+                pending_reruns: HashSet::new(),
+                last_rerun: None,
+                rerun_seq: 0,
+                output_spill: Default::default(),
             },
             canceled_exec_call_ids: HashSet::new(),
             tools_state: ToolState::default(),
@@ -182,6 +192,9 @@ impl ChatWidget<'_> {
             turn_sequence: 0,
             review_guard: None,
             background_review_guard: None,
+            review_findings: ReviewFindingsState::default(),
+            test_results: TestResultsState::default(),
+            lsp: LspState::default(),
             processed_auto_review_agents: HashSet::new(),
             pending_turn_descriptor: None,
             render_request_cache: RefCell::new(Vec::new()),
@@ -190,6 +203,16 @@ impl ChatWidget<'_> {
             pending_auto_turn_config: None,
             overall_task_status: "preparing".to_string(),
             active_plan_title: None,
+            plan_panel: PlanPanelState::default(),
+            answer_variants: AnswerVariantsState::default(),
+            offline: OfflineQueueState::default(),
+            turn_recovery: TurnRecoveryState::default(),
+            cell_timestamps: CellTimestampsState {
+                enabled: config.tui.timestamps,
+                ..CellTimestampsState::default()
+            },
+            auto_guardrails: AutoGuardrailState::default(),
+            spawn_batch: None,
             agent_runtime: HashMap::new(),
             pending_agent_updates: HashMap::new(),
             sparkline_data: std::cell::RefCell::new(Vec::new()),
@@ -208,6 +231,10 @@ impl ChatWidget<'_> {
             diffs: DiffsState {
                 session_patch_sets: Vec::new(),
                 baseline_file_contents: HashMap::new(),
+                session_file_contents: HashMap::new(),
+                external_edits: HashMap::new(),
+                pending_applies: HashMap::new(),
+                undo_stack: Vec::new(),
                 overlay: None,
                 confirm: None,
                 body_visible_rows: std::cell::Cell::new(0),
@@ -230,11 +257,12 @@ impl ChatWidget<'_> {
             history_render: HistoryRenderState::new(),
             last_render_settings: Cell::new(RenderSettings::new(0, 0, false)),
             render_theme_epoch: 0,
-            history_state: HistoryState::new(),
+            history_state: HistoryState::with_merge_policy(config.history.merge.clone()),
             history_snapshot_dirty: false,
             history_snapshot_last_flush: None,
             context_cell_id: None,
             context_summary: None,
+            context_timeline: Vec::new(),
             context_last_sequence: None,
             context_browser_sequence: None,
             history_cell_ids: Vec::new(),
@@ -254,6 +282,7 @@ impl ChatWidget<'_> {
                 last_frame_height: std::cell::Cell::new(0),
                 last_frame_width: std::cell::Cell::new(0),
                 last_bottom_pane_area: std::cell::Cell::new(Rect::default()),
+                history_anchor: Cell::new(None),
             },
             last_theme: crate::theme::current_theme(),
             perf_state: PerfState {
@@ -261,6 +290,7 @@ impl ChatWidget<'_> {
                 stats: RefCell::new(PerfStats::default()),
                 pending_scroll_rows: Cell::new(0),
             },
+            frame_regions: FrameRegionState::default(),
             session_id: None,
             active_task_ids: HashSet::new(),
             queued_user_messages: std::collections::VecDeque::new(),
@@ -321,18 +351,17 @@ impl ChatWidget<'_> {
             replay_history_depth: 0,
             resume_placeholder_visible: false,
             resume_picker_loading: false,
+            resume_picker_opened: false,
             clickable_regions: RefCell::new(Vec::new()),
+            history_click_regions: RefCell::new(Vec::new()),
             hovered_clickable_action: RefCell::new(None),
         };
         new_widget.load_auto_review_baseline_marker();
+        if !test_mode {
+            new_widget.begin_startup_stage(crate::startup::StartupStage::Session);
+        }
         new_widget.spawn_conversation_runtime(config.clone(), auth_manager, code_op_rx);
-        if let Ok(Some(active_id)) = auth_accounts::get_active_account_id(&config.code_home)
-            && let Ok(records) = account_usage::list_rate_limit_snapshots(&config.code_home)
-                && let Some(record) = records.into_iter().find(|r| r.account_id == active_id) {
-                    new_widget.rate_limit_primary_next_reset_at = record.primary_next_reset_at;
-                    new_widget.rate_limit_secondary_next_reset_at = record.secondary_next_reset_at;
-                    new_widget.maybe_schedule_rate_limit_refresh();
-                }
+        new_widget.load_account_snapshot_in_background();
         // Seed footer access indicator based on current config
         new_widget.apply_access_mode_indicator_from_config();
         // Insert the welcome cell as top-of-first-request so future model output
@@ -438,6 +467,7 @@ impl ChatWidget<'_> {
             mcp_server_failures: HashMap::new(),
             mcp_auth_statuses: HashMap::new(),
             startup_mcp_error_summary: None,
+            startup_stages: Vec::new(),
             remote_model_presets: None,
             allow_remote_default_at_startup: !config.model_explicit,
             chat_model_selected_explicitly: false,
@@ -455,6 +485,11 @@ impl ChatWidget<'_> {
             initial_user_message: None,
             total_token_usage: TokenUsage::default(),
             last_token_usage: TokenUsage::default(),
+            last_request_models: (None, None),
+            output_schema: None,
+            session_root: config.cwd.clone(),
+            scope: None,
+            structured_turn_pending: false,
             rate_limit_snapshot: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             rate_limit_fetch_inflight: false,
@@ -481,6 +516,10 @@ impl ChatWidget<'_> {
                 pending_exec_ends: HashMap::new(),
                 suppressed_exec_end_call_ids: HashSet::new(),
                 suppressed_exec_end_order: VecDeque::new(),
+                pending_reruns: HashSet::new(),
+                last_rerun: None,
+                rerun_seq: 0,
+                output_spill: Default::default(),
             },
             canceled_exec_call_ids: HashSet::new(),
             tools_state: ToolState {
@@ -546,6 +585,9 @@ impl ChatWidget<'_> {
             turn_sequence: 0,
             review_guard: None,
             background_review_guard: None,
+            review_findings: ReviewFindingsState::default(),
+            test_results: TestResultsState::default(),
+            lsp: LspState::default(),
             processed_auto_review_agents: HashSet::new(),
             pending_turn_descriptor: None,
             render_request_cache: RefCell::new(Vec::new()),
@@ -554,6 +596,16 @@ impl ChatWidget<'_> {
             pending_auto_turn_config: None,
             overall_task_status: "preparing".to_string(),
             active_plan_title: None,
+            plan_panel: PlanPanelState::default(),
+            answer_variants: AnswerVariantsState::default(),
+            offline: OfflineQueueState::default(),
+            turn_recovery: TurnRecoveryState::default(),
+            cell_timestamps: CellTimestampsState {
+                enabled: config.tui.timestamps,
+                ..CellTimestampsState::default()
+            },
+            auto_guardrails: AutoGuardrailState::default(),
+            spawn_batch: None,
             agent_runtime: HashMap::new(),
             pending_agent_updates: HashMap::new(),
             sparkline_data: std::cell::RefCell::new(Vec::new()),
@@ -572,6 +624,10 @@ impl ChatWidget<'_> {
             diffs: DiffsState {
                 session_patch_sets: Vec::new(),
                 baseline_file_contents: HashMap::new(),
+                session_file_contents: HashMap::new(),
+                external_edits: HashMap::new(),
+                pending_applies: HashMap::new(),
+                undo_stack: Vec::new(),
                 overlay: None,
                 confirm: None,
                 body_visible_rows: std::cell::Cell::new(0),
@@ -594,11 +650,12 @@ impl ChatWidget<'_> {
             history_render: HistoryRenderState::new(),
             last_render_settings: Cell::new(RenderSettings::new(0, 0, false)),
             render_theme_epoch: 0,
-            history_state: HistoryState::new(),
+            history_state: HistoryState::with_merge_policy(config.history.merge.clone()),
             history_snapshot_dirty: false,
             history_snapshot_last_flush: None,
             context_cell_id: None,
             context_summary: None,
+            context_timeline: Vec::new(),
             context_last_sequence: None,
             context_browser_sequence: None,
             history_cell_ids: Vec::new(),
@@ -618,6 +675,7 @@ impl ChatWidget<'_> {
                 last_frame_height: std::cell::Cell::new(0),
                 last_frame_width: std::cell::Cell::new(0),
                 last_bottom_pane_area: std::cell::Cell::new(Rect::default()),
+                history_anchor: Cell::new(None),
             },
             last_theme: crate::theme::current_theme(),
             perf_state: PerfState {
@@ -625,6 +683,7 @@ impl ChatWidget<'_> {
                 stats: RefCell::new(PerfStats::default()),
                 pending_scroll_rows: Cell::new(0),
             },
+            frame_regions: FrameRegionState::default(),
             session_id: None,
             active_task_ids: HashSet::new(),
             queued_user_messages: std::collections::VecDeque::new(),
@@ -685,17 +744,13 @@ impl ChatWidget<'_> {
             replay_history_depth: 0,
             resume_placeholder_visible: false,
             resume_picker_loading: false,
+            resume_picker_opened: false,
             clickable_regions: RefCell::new(Vec::new()),
+            history_click_regions: RefCell::new(Vec::new()),
             hovered_clickable_action: RefCell::new(None),
         };
         w.load_auto_review_baseline_marker();
-        if let Ok(Some(active_id)) = auth_accounts::get_active_account_id(&config.code_home)
-            && let Ok(records) = account_usage::list_rate_limit_snapshots(&config.code_home)
-                && let Some(record) = records.into_iter().find(|r| r.account_id == active_id) {
-                    w.rate_limit_primary_next_reset_at = record.primary_next_reset_at;
-                    w.rate_limit_secondary_next_reset_at = record.secondary_next_reset_at;
-                    w.maybe_schedule_rate_limit_refresh();
-                }
+        w.load_account_snapshot_in_background();
         w.set_standard_terminal_mode(!config.tui.alternate_screen);
         let welcome_brand_title = w.config.tui.branding.title.as_deref();
         if show_welcome {
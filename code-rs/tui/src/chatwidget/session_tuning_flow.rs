@@ -173,6 +173,115 @@ impl ChatWidget<'_> {
         self.set_collaboration_mode(mode, true);
     }
 
+    pub(crate) fn handle_profile_command(&mut self, command_args: String) {
+        if self.is_task_running() {
+            let message = "'/profile' is disabled while a task is in progress.".to_string();
+            self.history_push_plain_state(history_cell::new_error_event(message));
+            return;
+        }
+
+        let profiles = match code_core::config::load_config_profiles(&self.config.code_home) {
+            Ok(profiles) => profiles,
+            Err(err) => {
+                let message = format!("Failed to read profiles from config.toml: {err}");
+                self.history_push_plain_state(history_cell::new_error_event(message));
+                return;
+            }
+        };
+
+        let trimmed = command_args.trim();
+        if trimmed.is_empty() {
+            if profiles.is_empty() {
+                self.push_background_tail(
+                    "No profiles defined. Add [profiles.<name>] tables to config.toml.".to_string(),
+                );
+                return;
+            }
+            let active = self.config.active_profile.as_deref();
+            let names: Vec<String> = profiles
+                .keys()
+                .map(|name| {
+                    if Some(name.as_str()) == active {
+                        format!("{name} (active)")
+                    } else {
+                        name.clone()
+                    }
+                })
+                .collect();
+            self.push_background_tail(format!(
+                "Profiles: {} (use /profile <name>)",
+                names.join(", ")
+            ));
+            return;
+        }
+
+        let Some(profile) = profiles.get(trimmed) else {
+            let message = format!("Unknown profile: '{trimmed}'. Run /profile to list profiles.");
+            self.history_push_plain_state(history_cell::new_error_event(message));
+            return;
+        };
+        self.apply_config_profile(trimmed, profile);
+    }
+
+    /// Applies a profile's model, reasoning effort, approval policy, and
+    /// sandbox mode to the running session. Settings the profile leaves unset
+    /// keep their current values.
+    fn apply_config_profile(
+        &mut self,
+        name: &str,
+        profile: &code_core::config_profile::ConfigProfile,
+    ) {
+        let mut access_changed = false;
+        if let Some(approval) = profile.approval_policy
+            && self.config.approval_policy != approval
+        {
+            self.config.approval_policy = approval;
+            access_changed = true;
+        }
+        if let Some(mode) = profile.sandbox_mode {
+            use code_protocol::config_types::SandboxMode;
+            let sandbox = match mode {
+                SandboxMode::ReadOnly => SandboxPolicy::ReadOnly,
+                SandboxMode::WorkspaceWrite => match &self.config.sandbox_policy {
+                    policy @ SandboxPolicy::WorkspaceWrite { .. } => policy.clone(),
+                    _ => SandboxPolicy::new_workspace_write_policy(),
+                },
+                SandboxMode::DangerFullAccess => SandboxPolicy::DangerFullAccess,
+            };
+            if self.config.sandbox_policy != sandbox {
+                self.config.sandbox_policy = sandbox;
+                access_changed = true;
+            }
+        }
+        self.config.active_profile = Some(name.to_string());
+
+        let model = profile
+            .model
+            .clone()
+            .unwrap_or_else(|| self.config.model.clone());
+        let model_changed = self.apply_model_selection_inner(
+            model,
+            profile.model_reasoning_effort,
+            profile.model.is_some(),
+            false,
+        );
+        if access_changed {
+            // A model change already resubmitted the session settings,
+            // including the new access policy.
+            if !model_changed {
+                self.submit_configure_session_for_current_settings();
+            }
+            self.apply_access_mode_indicator_from_config();
+        }
+
+        self.push_background_tail(format!(
+            "Profile '{name}' active: {} ({} reasoning)",
+            self.config.model, self.config.model_reasoning_effort
+        ));
+        self.refresh_settings_overview_rows();
+        self.request_redraw();
+    }
+
     pub(crate) fn set_collaboration_mode(
         &mut self,
         mode: CollaborationModeKind,
@@ -267,6 +376,7 @@ impl ChatWidget<'_> {
             self.config.model_reasoning_effort,
             false,
             ModelSelectionTarget::Session,
+            self.config.model_overrides.clone(),
         );
     }
 
@@ -289,6 +399,7 @@ impl ChatWidget<'_> {
             self.config.review_model_reasoning_effort,
             self.config.review_use_chat_model,
             ModelSelectionTarget::Review,
+            self.config.model_overrides.clone(),
         );
     }
 
@@ -320,6 +431,7 @@ impl ChatWidget<'_> {
             effort,
             self.config.review_resolve_use_chat_model,
             ModelSelectionTarget::ReviewResolve,
+            self.config.model_overrides.clone(),
         );
     }
 
@@ -351,6 +463,7 @@ impl ChatWidget<'_> {
             effort,
             self.config.auto_review_use_chat_model,
             ModelSelectionTarget::AutoReview,
+            self.config.model_overrides.clone(),
         );
     }
 
@@ -382,6 +495,7 @@ impl ChatWidget<'_> {
             effort,
             self.config.auto_review_resolve_use_chat_model,
             ModelSelectionTarget::AutoReviewResolve,
+            self.config.model_overrides.clone(),
         );
     }
 
@@ -411,6 +525,7 @@ impl ChatWidget<'_> {
                 effort,
                 self.config.planning_use_chat_model,
                 ModelSelectionTarget::Planning,
+                self.config.model_overrides.clone(),
             );
     }
 
@@ -433,6 +548,7 @@ impl ChatWidget<'_> {
             self.config.auto_drive.model_reasoning_effort,
             self.config.auto_drive_use_chat_model,
             ModelSelectionTarget::AutoDrive,
+            self.config.model_overrides.clone(),
         );
     }
 
@@ -523,10 +639,10 @@ impl ChatWidget<'_> {
         effort: Option<ReasoningEffort>,
         mark_explicit: bool,
         announce: bool,
-    ) {
+    ) -> bool {
         let trimmed = model.trim();
         if trimmed.is_empty() {
-            return;
+            return false;
         }
 
         if mark_explicit {
@@ -603,6 +719,7 @@ impl ChatWidget<'_> {
         }
 
         self.request_redraw();
+        updated
     }
 
     fn sync_follow_chat_models(&mut self) {
@@ -1382,6 +1499,7 @@ impl ChatWidget<'_> {
             self.config.model_reasoning_effort,
             false,
             ModelSelectionTarget::Session,
+            self.config.model_overrides.clone(),
         );
         }
     }
@@ -148,6 +148,7 @@ impl ChatWidget<'_> {
             current_effort,
             false,
             ModelSelectionTarget::Session,
+            self.config.model_overrides.clone(),
             self.app_event_tx.clone(),
         )
     }
@@ -931,6 +932,7 @@ impl ChatWidget<'_> {
         let presets = self.available_model_presets();
         let current_model = self.config.model.clone();
         let current_effort = self.config.model_reasoning_effort;
+        let model_overrides = self.config.model_overrides.clone();
         self.open_bottom_pane_settings(move |this| {
             this.bottom_pane.show_model_selection(
                 presets,
@@ -938,6 +940,7 @@ impl ChatWidget<'_> {
                 current_effort,
                 false,
                 ModelSelectionTarget::Session,
+                model_overrides,
             );
         })
     }
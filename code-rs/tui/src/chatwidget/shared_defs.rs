@@ -53,6 +53,9 @@ const STATUS_LABEL_TARGET_WIDTH: usize = 7;
 const STATUS_LABEL_GAP: usize = 2;
 const STATUS_CONTENT_PREFIX: &str = "    ";
 const RESUME_PLACEHOLDER_MESSAGE: &str = "Resuming previous session...";
+/// Records at the end of a restored snapshot that are built eagerly; older
+/// freezable records start as placeholders and hydrate around the viewport.
+const RESTORE_HYDRATED_TAIL: usize = 64;
 const RESUME_NO_HISTORY_NOTICE: &str =
     "No saved messages for this session. Start typing to continue.";
 const ENABLE_WARP_STRIPES: bool = false;
@@ -673,6 +676,15 @@ enum ClickableAction {
     ShowReasoningSelector,
     #[allow(dead_code)]
     ExecuteCommand(String),
+    ShowDiffForFile(PathBuf),
+    /// Expands or collapses the `AttachmentsCell` at this history index.
+    ToggleAttachments(usize),
+    /// Expands or collapses one pass section of the `ReviewPassesCell` at
+    /// this history index.
+    ToggleReviewPass(usize, usize),
+    /// Expands or collapses one failure of the `TestResultsCell` at this
+    /// history index.
+    ToggleTestFailure(usize, usize),
 }
 
 /// A clickable region with its screen coordinates and associated action
@@ -707,6 +719,8 @@ pub(crate) struct ChatWidget<'a> {
     history_snapshot_last_flush: Option<Instant>,
     context_cell_id: Option<HistoryId>,
     context_summary: Option<ContextSummary>,
+    /// Every baseline and change seen this session, for `/context history`.
+    context_timeline: Vec<ContextTimelineEntry>,
     context_last_sequence: Option<u64>,
     context_browser_sequence: Option<u64>,
     config: Config,
@@ -721,6 +735,8 @@ pub(crate) struct ChatWidget<'a> {
     /// Startup-only MCP init error summary. We keep this out of history so the
     /// welcome intro doesn't jump when MCP status changes.
     startup_mcp_error_summary: Option<String>,
+    /// Background startup work still in flight, listed in the footer.
+    startup_stages: Vec<crate::startup::StartupStage>,
 
     /// Optional remote-merged presets list delivered asynchronously.
     /// When absent, the TUI falls back to built-in presets.
@@ -739,6 +755,16 @@ pub(crate) struct ChatWidget<'a> {
     initial_user_message: Option<UserMessage>,
     total_token_usage: TokenUsage,
     last_token_usage: TokenUsage,
+    /// Model the last request asked for and the one that answered, for /status.
+    last_request_models: (Option<String>, Option<String>),
+    /// `/schema set`: the file and the schema every answer is asked to match.
+    output_schema: Option<(PathBuf, serde_json::Value)>,
+    /// Directory the session started in; `/cd` asks before leaving its repository.
+    session_root: PathBuf,
+    /// `/scope` subproject (absolute, inside `config.cwd`).
+    scope: Option<PathBuf>,
+    /// A turn was sent with the schema and its answer has not arrived yet.
+    structured_turn_pending: bool,
     rate_limit_snapshot: Option<RateLimitSnapshotEvent>,
     rate_limit_warnings: RateLimitWarningState,
     rate_limit_fetch_inflight: bool,
@@ -824,12 +850,26 @@ pub(crate) struct ChatWidget<'a> {
     turn_sequence: u64,
     review_guard: Option<ReviewGuard>,
     background_review_guard: Option<ReviewGuard>,
+    /// Findings of the last `/review`, for `/review findings`.
+    review_findings: ReviewFindingsState,
+    /// The last `run_tests` report, for `/tests`.
+    test_results: TestResultsState,
+    /// Language server errors introduced this turn, for `lsp.auto_fix`.
+    lsp: LspState,
     processed_auto_review_agents: HashSet<String>,
     // New: coordinator-provided hints for the next Auto turn
     pending_turn_descriptor: Option<TurnDescriptor>,
     pending_auto_turn_config: Option<TurnConfig>,
     overall_task_status: String,
     active_plan_title: Option<String>,
+    plan_panel: PlanPanelState,
+    auto_guardrails: AutoGuardrailState,
+    answer_variants: AnswerVariantsState,
+    cell_timestamps: CellTimestampsState,
+    offline: OfflineQueueState,
+    turn_recovery: TurnRecoveryState,
+    /// The latest `/spawn` fan-out.
+    spawn_batch: Option<SpawnBatchState>,
     /// Runtime timing per-agent (by id) to improve visibility in the HUD
     agent_runtime: HashMap<String, AgentRuntime>,
     pending_agent_updates: HashMap<String, PendingAgentUpdate>,
@@ -898,6 +938,8 @@ pub(crate) struct ChatWidget<'a> {
 
     // Performance tracing (opt-in via /perf)
     perf_state: PerfState,
+    // Composed-frame reuse for narrow redraws, plus the /perf hud overlay
+    frame_regions: FrameRegionState,
     // Current session id (from SessionConfigured)
     session_id: Option<uuid::Uuid>,
 
@@ -999,8 +1041,13 @@ pub(crate) struct ChatWidget<'a> {
     replay_history_depth: usize,
     resume_placeholder_visible: bool,
     resume_picker_loading: bool,
+    // Set once provisional rows have opened the picker for the current load,
+    // so later updates refresh it rather than reopening it after Esc.
+    resume_picker_opened: bool,
     // Clickable regions for mouse interaction (tracked during render, checked on click)
     clickable_regions: RefCell<Vec<ClickableRegion>>,
+    // Clickable rows inside history cells (rebuilt on every history paint).
+    history_click_regions: RefCell<Vec<ClickableRegion>>,
     // Current hovered header action (for hover styling on top status line).
     hovered_clickable_action: RefCell<Option<ClickableAction>>,
 }
@@ -1014,6 +1061,10 @@ struct ContextSummary {
     deltas: Vec<ContextDeltaRecord>,
     browser_snapshot: Option<ContextBrowserSnapshotRecord>,
     expanded: bool,
+    /// Facts from `/context pin`; they outlive environment baselines.
+    pinned: std::collections::BTreeMap<String, String>,
+    /// Facts from the environment providers (toolchains, compose, virtualenv).
+    providers: std::collections::BTreeMap<String, String>,
 }
 
 #[derive(Clone, Debug)]
@@ -1720,7 +1771,10 @@ fn last_existing_cwd(except: &Path) -> Option<PathBuf> {
 
 use self::diff_ui::DiffBlock;
 use self::diff_ui::DiffConfirm;
+use self::diff_ui::DiffFileMeta;
+use self::diff_ui::DiffFileStatus;
 use self::diff_ui::DiffOverlay;
+use self::diff_ui::DiffTreeRow;
 use self::settings_overlay::{
     AgentOverviewRow,
     AccountsSettingsContent,
@@ -1745,6 +1799,7 @@ use ratatui::text::Line as RtLine;
 use ratatui::text::Span as RtSpan;
 
 
+use self::perf::HeightCacheInvalidations;
 use self::perf::PerfStats;
 
 #[derive(Debug, Clone)]
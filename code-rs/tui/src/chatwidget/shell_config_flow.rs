@@ -154,7 +154,7 @@ impl ChatWidget<'_> {
         }
     }
 
-    fn submit_configure_session_for_current_settings(&self) {
+    pub(super) fn submit_configure_session_for_current_settings(&self) {
         let op = Op::ConfigureSession {
             provider: self.config.model_provider.clone(),
             model: self.config.model.clone(),
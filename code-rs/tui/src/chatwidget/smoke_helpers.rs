@@ -201,6 +201,8 @@ impl ChatWidgetHarness {
                 | AppEvent::ScheduleFrameIn(_)
                 | AppEvent::SetTerminalTitle { .. }
                 | AppEvent::EmitTuiNotification { .. }
+                | AppEvent::EmitApprovalNotification { .. }
+                | AppEvent::EmitFeedback(_)
                 | AppEvent::RequestRedraw
                 | AppEvent::Redraw
                 | AppEvent::PreviewTheme(_)
@@ -294,7 +296,9 @@ impl ChatWidgetHarness {
     }
 
     pub fn perf_stats_snapshot(&self) -> PerfStats {
-        self.chat.perf_state.stats.borrow().clone()
+        let mut stats = self.chat.perf_state.stats.borrow().clone();
+        stats.height_cache_invalidations = self.chat.history_render.invalidations();
+        stats
     }
 
     pub fn history_len(&self) -> usize {
@@ -0,0 +1,582 @@
+use super::*;
+
+/// Most agents one `/spawn` starts.
+const MAX_SPAWN_AGENTS: usize = 6;
+
+const SPAWN_USAGE: &str = "usage: /spawn <n> <task> | /spawn diffs | /spawn merge [n]";
+
+/// `<n> <task>` from the `/spawn` arguments.
+fn parse_spawn_args(args: &str) -> Result<(usize, String), String> {
+    let (count, task) = args
+        .trim()
+        .split_once(char::is_whitespace)
+        .ok_or_else(|| SPAWN_USAGE.to_string())?;
+    let count: usize = count.parse().map_err(|_| SPAWN_USAGE.to_string())?;
+    if !(2..=MAX_SPAWN_AGENTS).contains(&count) {
+        return Err(format!("pick between 2 and {MAX_SPAWN_AGENTS} agents"));
+    }
+    let task = task.trim();
+    if task.is_empty() {
+        return Err(SPAWN_USAGE.to_string());
+    }
+    Ok((count, task.to_string()))
+}
+
+/// Agents a fan-out rotates through: the enabled agents from config that may
+/// write, or the session model when none are configured.
+fn spawn_agent_configs(config: &Config) -> Vec<AgentConfig> {
+    let configured: Vec<AgentConfig> = config
+        .agents
+        .iter()
+        .filter(|agent| agent.enabled && !agent.read_only)
+        .cloned()
+        .collect();
+    if !configured.is_empty() {
+        return configured;
+    }
+    let model = if config.model.to_ascii_lowercase().starts_with("code-") {
+        config.model.clone()
+    } else {
+        format!("code-{}", config.model)
+    };
+    vec![AgentConfig {
+        name: model,
+        command: String::new(),
+        args: Vec::new(),
+        read_only: false,
+        enabled: true,
+        description: None,
+        env: None,
+        args_read_only: None,
+        args_write: None,
+        instructions: None,
+    }]
+}
+
+fn spawn_agent_prompt(task: &str, count: usize) -> String {
+    format!(
+        "{task}\n\nYou are one of {count} agents working on this task independently, each in its own git worktree. The user will compare the results and merge one. Make the changes in your worktree, do not push or merge anything, and finish with a short summary of your approach."
+    )
+}
+
+/// Files touched, lines added and lines removed by a multi-file git diff.
+fn diff_stats(diff: &str) -> (usize, usize, usize) {
+    let (mut files, mut added, mut removed) = (0, 0, 0);
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            files += 1;
+        } else if line.starts_with("+++ ") || line.starts_with("--- ") {
+            continue;
+        } else if line.starts_with('+') {
+            added += 1;
+        } else if line.starts_with('-') {
+            removed += 1;
+        }
+    }
+    (files, added, removed)
+}
+
+fn spawn_result_summary(diff: &Result<String, String>) -> String {
+    match diff {
+        Ok(diff) if diff.trim().is_empty() => "no changes".to_string(),
+        Ok(diff) => {
+            let (files, added, removed) = diff_stats(diff);
+            format!("{files} file{} · +{added} -{removed}", if files == 1 { "" } else { "s" })
+        }
+        Err(err) => format!("failed: {err}"),
+    }
+}
+
+/// One overlay block per file of the agent's diff, after a header block.
+fn spawn_diff_blocks(entry: &SpawnAgentEntry) -> Vec<DiffBlock> {
+    let Some(diff) = entry.diff.as_ref() else {
+        return Vec::new();
+    };
+    let header = Line::from(vec![
+        Span::styled(
+            entry.label.clone(),
+            Style::default()
+                .fg(crate::colors::text())
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("  {}", spawn_result_summary(diff)),
+            Style::default().fg(crate::colors::text_dim()),
+        ),
+    ]);
+    let mut blocks = vec![DiffBlock { lines: vec![header] }];
+    let Ok(diff) = diff else {
+        return blocks;
+    };
+    let mut current: Vec<Line<'static>> = Vec::new();
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") && !current.is_empty() {
+            blocks.push(DiffBlock {
+                lines: std::mem::take(&mut current),
+            });
+        }
+        let style = if line.starts_with("diff --git ") || line.starts_with("+++ ") || line.starts_with("--- ") {
+            Style::default()
+                .fg(crate::colors::text())
+                .add_modifier(Modifier::BOLD)
+        } else if line.starts_with("@@") {
+            Style::default().fg(crate::colors::info())
+        } else if line.starts_with('+') {
+            Style::default().fg(crate::colors::success())
+        } else if line.starts_with('-') {
+            Style::default().fg(crate::colors::error())
+        } else {
+            Style::default().fg(crate::colors::text_dim())
+        };
+        current.push(Line::from(Span::styled(line.to_string(), style)));
+    }
+    if !current.is_empty() {
+        blocks.push(DiffBlock { lines: current });
+    }
+    blocks
+}
+
+async fn run_git(dir: &Path, args: &[&str], stdin: Option<&str>) -> Result<String, String> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut child = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .stdin(if stdin.is_some() {
+            std::process::Stdio::piped()
+        } else {
+            std::process::Stdio::null()
+        })
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run git: {e}"))?;
+    if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        pipe.write_all(input.as_bytes())
+            .await
+            .map_err(|e| format!("failed to write to git: {e}"))?;
+    }
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("failed to run git: {e}"))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Everything agent `id` changed in its worktree since `base`, committed or
+/// not.
+async fn spawn_agent_diff(id: &str, base: &str) -> Result<String, String> {
+    let agent = {
+        let manager = code_core::AGENT_MANAGER.read().await;
+        manager
+            .get_agent(id)
+            .map(|agent| (agent.worktree_path, agent.error))
+    };
+    let Some((worktree, error)) = agent else {
+        return Err("agent is no longer tracked".to_string());
+    };
+    let Some(worktree) = worktree else {
+        return Err(error.unwrap_or_else(|| "no worktree was created".to_string()));
+    };
+    let worktree = PathBuf::from(worktree);
+    run_git(&worktree, &["add", "-A"], None).await?;
+    let diff = run_git(&worktree, &["diff", "--cached", "--binary", base], None).await?;
+    match error {
+        Some(error) if diff.trim().is_empty() => Err(error),
+        _ => Ok(diff),
+    }
+}
+
+impl ChatWidget<'_> {
+    /// `/spawn <n> <task>` starts `n` write-mode agents on the same task, each
+    /// in its own worktree created from a snapshot of the workspace.
+    /// `/spawn diffs` reopens the comparison and `/spawn merge [n]` applies
+    /// one agent's changes.
+    pub(crate) fn handle_spawn_command(&mut self, args: String) {
+        let args = args.trim();
+        let (sub, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+        match sub {
+            "" => {
+                self.show_spawn_status();
+                return;
+            }
+            "diffs" => {
+                self.show_spawn_diffs();
+                return;
+            }
+            "merge" => {
+                match rest.trim() {
+                    "" => self.show_spawn_merge_picker(),
+                    index => match index.parse::<usize>() {
+                        Ok(index) if index >= 1 => self.merge_spawn_result(index - 1),
+                        _ => self.push_background_tail(format!("`/spawn` — {SPAWN_USAGE}")),
+                    },
+                }
+                self.request_redraw();
+                return;
+            }
+            _ => {}
+        }
+
+        let (count, goal) = match parse_spawn_args(args) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                self.history_push_plain_state(crate::history_cell::new_error_event(format!(
+                    "`/spawn` — {err}"
+                )));
+                self.request_redraw();
+                return;
+            }
+        };
+        if self
+            .spawn_batch
+            .as_ref()
+            .is_some_and(|batch| batch.agents.iter().any(|agent| !agent.finished))
+        {
+            self.push_background_tail(
+                "`/spawn` — the previous fan-out is still running; watch it with Ctrl+A.".to_string(),
+            );
+            self.request_redraw();
+            return;
+        }
+        if self.ensure_git_repo_for_action(
+            GitInitResume::DispatchCommand {
+                command: SlashCommand::Spawn,
+                command_text: format!("/spawn {args}"),
+            },
+            "Spawning agents in separate worktrees requires a git repository.",
+        ) {
+            return;
+        }
+
+        self.consume_pending_prompt_for_ui_only_turn();
+        let config = self.config.clone();
+        let tx = self.app_event_tx.clone();
+        let ticket = self.make_background_tail_ticket();
+        self.push_background_tail(format!("Starting {count} agents on: {goal}"));
+        self.request_redraw();
+
+        tokio::spawn(async move {
+            let outcome = async {
+                let root = code_core::git_worktree::get_git_root_from(&config.cwd)
+                    .await
+                    .map_err(|e| format!("failed to detect git root: {e}"))?;
+                let snapshot = task::spawn_blocking({
+                    let repo_path = config.cwd.clone();
+                    move || {
+                        let options = CreateGhostCommitOptions::new(repo_path.as_path())
+                            .message("spawn snapshot");
+                        let hook_repo = repo_path.clone();
+                        let hook = move || bump_snapshot_epoch_for(&hook_repo);
+                        create_ghost_commit(&options.post_commit_hook(&hook))
+                    }
+                })
+                .await
+                .map_err(|e| format!("failed to spawn snapshot task: {e}"))
+                .and_then(|res| res.map_err(|e| format!("failed to capture snapshot: {e}")))?;
+                let base = snapshot.id().to_string();
+                let batch_id = format!("spawn-{}", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+                let prompt = spawn_agent_prompt(&goal, count);
+
+                let mut manager = code_core::AGENT_MANAGER.write().await;
+                let mut agents = Vec::with_capacity(count);
+                for (index, agent_config) in spawn_agent_configs(&config)
+                    .into_iter()
+                    .cycle()
+                    .take(count)
+                    .enumerate()
+                {
+                    let label = format!("#{} {}", index + 1, agent_config.name);
+                    let id = manager
+                        .create_agent_with_options(code_core::AgentCreateRequest {
+                            model: agent_config.name.clone(),
+                            name: Some(format!("Spawn {label}")),
+                            prompt: prompt.clone(),
+                            context: None,
+                            output_goal: None,
+                            files: Vec::new(),
+                            read_only: false,
+                            batch_id: Some(batch_id.clone()),
+                            config: Some(agent_config),
+                            worktree_branch: Some(format!("{batch_id}-{}", index + 1)),
+                            worktree_base: Some(base.clone()),
+                            source_kind: None,
+                            reasoning_effort: config.model_reasoning_effort.into(),
+                        })
+                        .await;
+                    agents.push((id, label));
+                }
+                drop(manager);
+                Ok::<_, String>((batch_id, root, base, agents))
+            }
+            .await;
+
+            match outcome {
+                Ok((batch_id, root, base, agents)) => tx.send(AppEvent::SpawnBatchStarted {
+                    batch_id,
+                    prompt: goal,
+                    root,
+                    base,
+                    agents,
+                }),
+                Err(err) => tx.send_background_event_with_ticket(&ticket, format!("`/spawn` — {err}")),
+            }
+        });
+    }
+
+    pub(crate) fn on_spawn_batch_started(
+        &mut self,
+        batch_id: String,
+        prompt: String,
+        root: PathBuf,
+        base: String,
+        agents: Vec<(String, String)>,
+    ) {
+        let count = agents.len();
+        self.spawn_batch = Some(SpawnBatchState {
+            batch_id,
+            prompt,
+            root,
+            base,
+            agents: agents
+                .into_iter()
+                .map(|(id, label)| SpawnAgentEntry {
+                    id,
+                    label,
+                    finished: false,
+                    diff: None,
+                })
+                .collect(),
+            collecting: false,
+            merged: None,
+        });
+        self.push_background_tail(format!(
+            "Spawned {count} agents in separate worktrees; Ctrl+A shows their progress. Their diffs open here when all finish."
+        ));
+        self.request_redraw();
+    }
+
+    /// Marks `/spawn` agents that reached a terminal state; once all have,
+    /// gathers their diffs.
+    pub(super) fn observe_spawn_status(&mut self, agents: &[code_core::protocol::AgentInfo]) {
+        let Some(batch) = self.spawn_batch.as_mut() else {
+            return;
+        };
+        if batch.collecting {
+            return;
+        }
+        for agent in agents {
+            if agent.batch_id.as_deref() != Some(batch.batch_id.as_str()) {
+                continue;
+            }
+            let terminal = matches!(
+                agent_status_from_str(agent.status.as_str()),
+                AgentStatus::Completed | AgentStatus::Failed | AgentStatus::Cancelled
+            );
+            if let Some(entry) = batch.agents.iter_mut().find(|entry| entry.id == agent.id) {
+                entry.finished |= terminal;
+            }
+        }
+        if !batch.agents.iter().all(|entry| entry.finished) {
+            return;
+        }
+        batch.collecting = true;
+        let batch_id = batch.batch_id.clone();
+        let base = batch.base.clone();
+        let ids: Vec<String> = batch.agents.iter().map(|entry| entry.id.clone()).collect();
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let mut diffs = Vec::with_capacity(ids.len());
+            for id in &ids {
+                diffs.push(spawn_agent_diff(id, &base).await);
+            }
+            tx.send(AppEvent::SpawnDiffsReady { batch_id, diffs });
+        });
+    }
+
+    pub(crate) fn show_spawn_results(&mut self, batch_id: String, diffs: Vec<Result<String, String>>) {
+        let Some(batch) = self.spawn_batch.as_mut() else {
+            return;
+        };
+        if batch.batch_id != batch_id {
+            return;
+        }
+        for (entry, diff) in batch.agents.iter_mut().zip(diffs) {
+            entry.diff = Some(diff);
+        }
+        let lines: Vec<String> = std::iter::once(format!("/spawn finished: {}", batch.prompt))
+            .chain(batch.agents.iter().map(|entry| {
+                let summary = entry
+                    .diff
+                    .as_ref()
+                    .map(spawn_result_summary)
+                    .unwrap_or_default();
+                format!("{} — {summary}", entry.label)
+            }))
+            .chain(std::iter::once(
+                "◂ ▸ switch between agents in the diff view; /spawn merge picks one to apply.".to_string(),
+            ))
+            .collect();
+        self.history_push_plain_paragraphs(PlainMessageKind::Notice, lines);
+        self.show_spawn_diffs();
+    }
+
+    /// Opens the diff overlay with one tab per `/spawn` agent.
+    fn show_spawn_diffs(&mut self) {
+        let Some(batch) = self.spawn_batch.as_ref() else {
+            self.push_background_tail("No /spawn results yet; start one with /spawn <n> <task>.".to_string());
+            self.request_redraw();
+            return;
+        };
+        if batch.agents.iter().any(|entry| entry.diff.is_none()) {
+            self.show_spawn_status();
+            return;
+        }
+        let tabs: Vec<(String, Vec<DiffBlock>)> = batch
+            .agents
+            .iter()
+            .map(|entry| (entry.label.clone(), spawn_diff_blocks(entry)))
+            .collect();
+        self.diffs.overlay = Some(DiffOverlay::new(tabs));
+        self.diffs.confirm = None;
+        self.request_redraw();
+    }
+
+    fn show_spawn_status(&mut self) {
+        let message = match self.spawn_batch.as_ref() {
+            None => format!("No /spawn fan-out yet; {SPAWN_USAGE}"),
+            Some(batch) => {
+                let done = batch.agents.iter().filter(|entry| entry.finished).count();
+                let total = batch.agents.len();
+                if done < total || batch.agents.iter().any(|entry| entry.diff.is_none()) {
+                    format!("/spawn: {done}/{total} agents finished on: {}", batch.prompt)
+                } else if let Some(index) = batch.merged {
+                    format!("/spawn: merged {} for: {}", batch.agents[index].label, batch.prompt)
+                } else {
+                    format!("/spawn: {total} results ready; /spawn diffs to compare, /spawn merge to pick one.")
+                }
+            }
+        };
+        self.push_background_tail(message);
+        self.request_redraw();
+    }
+
+    fn show_spawn_merge_picker(&mut self) {
+        let Some(batch) = self
+            .spawn_batch
+            .as_ref()
+            .filter(|batch| batch.agents.iter().all(|entry| entry.diff.is_some()))
+        else {
+            self.show_spawn_status();
+            return;
+        };
+        let items: Vec<SelectionItem> = batch
+            .agents
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| SelectionItem {
+                name: entry.label.clone(),
+                description: entry.diff.as_ref().map(spawn_result_summary),
+                is_current: batch.merged == Some(index),
+                actions: vec![Box::new(move |tx: &crate::app_event_sender::AppEventSender| {
+                    tx.send(AppEvent::MergeSpawnResult(index));
+                })],
+            })
+            .collect();
+        let view = ListSelectionView::new(
+            " Merge a /spawn result ".to_string(),
+            Some(batch.prompt.clone()),
+            Some("Enter merge · Esc close".to_string()),
+            items,
+            self.app_event_tx.clone(),
+            8,
+        );
+        self.bottom_pane
+            .show_list_selection("Merge a /spawn result".to_string(), None, None, view);
+    }
+
+    /// Applies agent `index`'s diff to the workspace, falling back to a
+    /// three-way merge when the workspace moved since the snapshot.
+    pub(crate) fn merge_spawn_result(&mut self, index: usize) {
+        let Some(batch) = self.spawn_batch.as_mut() else {
+            self.show_spawn_status();
+            return;
+        };
+        let Some(entry) = batch.agents.get(index) else {
+            let total = batch.agents.len();
+            self.push_background_tail(format!("`/spawn` — pick an agent between 1 and {total}."));
+            self.request_redraw();
+            return;
+        };
+        let diff = match entry.diff.as_ref() {
+            Some(Ok(diff)) if !diff.trim().is_empty() => diff.clone(),
+            Some(Ok(_)) => {
+                let label = entry.label.clone();
+                self.push_background_tail(format!("`/spawn` — {label} made no changes."));
+                self.request_redraw();
+                return;
+            }
+            Some(Err(err)) => {
+                let message = format!("`/spawn` — {} has nothing to merge: {err}", entry.label);
+                self.push_background_tail(message);
+                self.request_redraw();
+                return;
+            }
+            None => {
+                self.show_spawn_status();
+                return;
+            }
+        };
+        let label = entry.label.clone();
+        let root = batch.root.clone();
+        batch.merged = Some(index);
+        let tx = self.app_event_tx.clone();
+        let ticket = self.make_background_tail_ticket();
+        self.push_background_tail(format!("Merging {label} into the workspace…"));
+        self.request_redraw();
+
+        tokio::spawn(async move {
+            let applied = match run_git(&root, &["apply", "--whitespace=nowarn", "-"], Some(&diff)).await {
+                Ok(_) => Ok(()),
+                Err(_) => run_git(&root, &["apply", "--3way", "--whitespace=nowarn", "-"], Some(&diff))
+                    .await
+                    .map(|_| ()),
+            };
+            let message = match applied {
+                Ok(()) => format!("Merged {label}; review the result with /diff."),
+                Err(err) => format!("`/spawn` — could not merge {label}: {err}"),
+            };
+            tx.send_background_event_with_ticket(&ticket, message);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_spawn_count_and_task() {
+        assert_eq!(
+            parse_spawn_args("3 add a --json flag"),
+            Ok((3, "add a --json flag".to_string()))
+        );
+        assert!(parse_spawn_args("1 do it").is_err());
+        assert!(parse_spawn_args("9 do it").is_err());
+        assert!(parse_spawn_args("three do it").is_err());
+        assert!(parse_spawn_args("3").is_err());
+    }
+
+    #[test]
+    fn summarizes_agent_diffs() {
+        let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1 +1,2 @@\n fn a() {}\n+fn b() {}\n";
+        assert_eq!(diff_stats(diff), (1, 1, 0));
+        assert_eq!(spawn_result_summary(&Ok(diff.to_string())), "1 file · +1 -0");
+        assert_eq!(spawn_result_summary(&Ok(String::new())), "no changes");
+        assert_eq!(spawn_result_summary(&Err("boom".to_string())), "failed: boom");
+    }
+}
@@ -0,0 +1,70 @@
+use super::*;
+
+use crate::startup::StartupStage;
+
+impl ChatWidget<'_> {
+    /// Marks `stage` as still loading and lists it in the footer.
+    pub(super) fn begin_startup_stage(&mut self, stage: StartupStage) {
+        if !self.startup_stages.contains(&stage) {
+            self.startup_stages.push(stage);
+            self.refresh_startup_status();
+        }
+    }
+
+    pub(crate) fn finish_startup_stage(&mut self, stage: StartupStage) {
+        let before = self.startup_stages.len();
+        self.startup_stages.retain(|pending| *pending != stage);
+        if self.startup_stages.len() != before {
+            stage.mark_ready();
+            self.refresh_startup_status();
+        }
+    }
+
+    fn refresh_startup_status(&mut self) {
+        let status = (!self.startup_stages.is_empty()).then(|| {
+            let labels: Vec<&str> = self.startup_stages.iter().map(|stage| stage.label()).collect();
+            format!("Loading {}…", labels.join(", "))
+        });
+        self.bottom_pane.set_startup_status(status);
+    }
+
+    /// Reads the active account's stored rate-limit snapshot off the UI
+    /// thread; `on_startup_accounts_loaded` applies it.
+    pub(super) fn load_account_snapshot_in_background(&mut self) {
+        self.begin_startup_stage(StartupStage::Accounts);
+        let code_home = self.config.code_home.clone();
+        let tx = self.app_event_tx.clone();
+        let load = move || {
+            let record = auth_accounts::get_active_account_id(&code_home)
+                .ok()
+                .flatten()
+                .and_then(|active_id| {
+                    account_usage::list_rate_limit_snapshots(&code_home)
+                        .ok()?
+                        .into_iter()
+                        .find(|record| record.account_id == active_id)
+                });
+            tx.send(AppEvent::StartupAccountsLoaded {
+                primary_next_reset_at: record.as_ref().and_then(|r| r.primary_next_reset_at),
+                secondary_next_reset_at: record.and_then(|r| r.secondary_next_reset_at),
+            });
+        };
+        if thread_spawner::spawn_lightweight("startup-accounts", load).is_none() {
+            tracing::warn!("account snapshot load skipped: background thread limit reached");
+            self.finish_startup_stage(StartupStage::Accounts);
+        }
+    }
+
+    pub(crate) fn on_startup_accounts_loaded(
+        &mut self,
+        primary_next_reset_at: Option<DateTime<Utc>>,
+        secondary_next_reset_at: Option<DateTime<Utc>>,
+    ) {
+        if primary_next_reset_at.is_some() || secondary_next_reset_at.is_some() {
+            self.rate_limit_primary_next_reset_at = primary_next_reset_at;
+            self.rate_limit_secondary_next_reset_at = secondary_next_reset_at;
+            self.maybe_schedule_rate_limit_refresh();
+        }
+        self.finish_startup_stage(StartupStage::Accounts);
+    }
+}
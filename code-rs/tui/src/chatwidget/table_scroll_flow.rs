@@ -0,0 +1,53 @@
+use super::*;
+
+impl ChatWidget<'_> {
+    /// Alt+←/→ with an empty composer scrolls tables that are wider than the
+    /// screen by a column. Returns whether the key was handled.
+    pub(super) fn handle_table_scroll_key(&mut self, key_event: &KeyEvent) -> bool {
+        if !matches!(key_event.kind, KeyEventKind::Press | KeyEventKind::Repeat)
+            || key_event.modifiers != crossterm::event::KeyModifiers::ALT
+            || !self.composer_is_empty()
+            || !self.has_tables()
+        {
+            return false;
+        }
+        let delta = match key_event.code {
+            crossterm::event::KeyCode::Left => -1,
+            crossterm::event::KeyCode::Right => 1,
+            _ => return false,
+        };
+        if crate::markdown_table::scroll_columns(delta) {
+            self.relayout_tables();
+        }
+        true
+    }
+
+    /// Shows every table from its first column again, e.g. when a new
+    /// message is sent.
+    pub(super) fn reset_table_scroll(&mut self) {
+        if crate::markdown_table::reset_column_offset() {
+            self.relayout_tables();
+        }
+    }
+
+    fn has_tables(&self) -> bool {
+        self.history_cells.iter().any(|cell| {
+            cell.as_any()
+                .downcast_ref::<history_cell::AssistantMarkdownCell>()
+                .is_some_and(|cell| cell.markdown().contains('|'))
+        })
+    }
+
+    fn relayout_tables(&mut self) {
+        for cell in &self.history_cells {
+            if let Some(cell) = cell
+                .as_any()
+                .downcast_ref::<history_cell::AssistantMarkdownCell>()
+            {
+                cell.invalidate_layout();
+            }
+        }
+        self.history_render.invalidate_all();
+        self.request_redraw();
+    }
+}
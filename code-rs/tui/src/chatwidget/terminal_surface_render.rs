@@ -28,6 +28,9 @@ impl ChatWidget<'_> {
             for line in streaming_lines.iter_mut().skip(1) {
                 line.spans.insert(0, ratatui::text::Span::raw("   "));
             }
+            if crate::accessibility::enabled() {
+                streaming_lines.insert(0, ratatui::text::Line::from(" ASSISTANT:"));
+            }
             out.extend(streaming_lines);
             out.push(ratatui::text::Line::from(""));
         }
@@ -36,6 +39,7 @@ impl ChatWidget<'_> {
 
     /// Render a single history cell into terminal-friendly lines:
     /// - Prepend a gutter icon (symbol + space) to the first line when defined.
+    ///   In accessibility mode a text label line (`ASSISTANT:`) replaces it.
     /// - Add a single blank line after the cell as a separator.
     fn render_lines_for_terminal(
         &self,
@@ -44,7 +48,12 @@ impl ChatWidget<'_> {
     ) -> Vec<ratatui::text::Line<'static>> {
         let mut lines = self.cell_lines_for_terminal_index(idx, cell);
         let _has_icon = cell.gutter_symbol().is_some();
-        let first_prefix = if let Some(sym) = cell.gutter_symbol() {
+        let label = crate::accessibility::enabled()
+            .then(|| crate::accessibility::cell_label(cell.kind()))
+            .flatten();
+        let first_prefix = if label.is_some() {
+            "   ".to_string()
+        } else if let Some(sym) = cell.gutter_symbol() {
             format!(" {sym} ") // one space, icon, one space
         } else {
             "   ".to_string() // three spaces when no icon
@@ -61,6 +70,11 @@ impl ChatWidget<'_> {
                 line.spans.insert(0, ratatui::text::Span::raw("   "));
             }
         }
+        if let Some(label) = label
+            && !lines.is_empty()
+        {
+            lines.insert(0, ratatui::text::Line::from(format!(" {label}")));
+        }
         lines.push(ratatui::text::Line::from(""));
         lines
     }
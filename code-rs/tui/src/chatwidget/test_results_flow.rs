@@ -0,0 +1,156 @@
+use super::*;
+use code_core::test_runner::TestFailure;
+use code_core::test_runner::TestReport;
+
+fn fix_failure_prompt(report: &TestReport, failure: &TestFailure) -> String {
+    let mut prompt = format!("Fix this failing test.\n\n{}", failure.name);
+    if let Some(location) = failure.location() {
+        prompt.push_str(&format!(" ({location})"));
+    }
+    prompt.push_str(&format!("\nRun with: {} in {}", report.command, report.cwd.display()));
+    let output = failure.output.trim_end();
+    if !output.is_empty() {
+        prompt.push_str(&format!("\n\nOutput:\n```\n{output}\n```"));
+    } else if !failure.message.is_empty() {
+        prompt.push_str(&format!("\n\n{}", failure.message));
+    }
+    prompt.push_str(
+        "\n\nFind the cause, fix the code (or the test, if the test is wrong), then run this test again with run_tests.",
+    );
+    prompt
+}
+
+impl ChatWidget<'_> {
+    /// Keeps a finished `run_tests` report for `/tests`, and queues the
+    /// failing tests' output for the next prompt when
+    /// `tools.run_tests_attach_failures` is on.
+    pub(crate) fn record_test_report(&mut self, report: &TestReport) {
+        self.test_results.pending_attachment = if self.config.tools_run_tests_attach_failures {
+            report.failures_context()
+        } else {
+            None
+        };
+        self.test_results.report = Some(report.clone());
+    }
+
+    /// The queued failing-test output, once.
+    pub(super) fn take_failing_tests_attachment(&mut self) -> Option<InputItem> {
+        let text = self.test_results.pending_attachment.take()?;
+        let count = self
+            .test_results
+            .report
+            .as_ref()
+            .map_or(0, |report| report.failures.len());
+        self.push_background_tail(format!(
+            "Attached the output of {count} failing test{} from the last run.",
+            if count == 1 { "" } else { "s" }
+        ));
+        Some(InputItem::Text {
+            text: format!("\n{text}\n"),
+        })
+    }
+
+    /// `/tests`: the failures of the last `run_tests` call as a list. Enter
+    /// sends a fix prompt with the selected test's output.
+    pub(crate) fn show_test_failures(&mut self) {
+        let Some(report) = self.test_results.report.as_ref() else {
+            self.push_background_tail("No test runs yet; the agent runs tests with run_tests.".to_string());
+            self.request_redraw();
+            return;
+        };
+        if report.failures.is_empty() {
+            let message = if report.succeeded() {
+                format!("The last test run passed: {}", report.command)
+            } else {
+                format!(
+                    "The last test run failed without reporting failing tests (exit code {}): {}",
+                    report.exit_code, report.command
+                )
+            };
+            self.push_background_tail(message);
+            self.request_redraw();
+            return;
+        }
+        let items: Vec<SelectionItem> = report
+            .failures
+            .iter()
+            .enumerate()
+            .map(|(index, failure)| {
+                let mut details = Vec::new();
+                if let Some(location) = failure.location() {
+                    details.push(location);
+                }
+                if let Some(message) = failure.message.lines().map(str::trim).find(|line| !line.is_empty()) {
+                    details.push(message.to_string());
+                }
+                SelectionItem {
+                    name: failure.name.clone(),
+                    description: (!details.is_empty()).then(|| details.join(" · ")),
+                    is_current: index == 0,
+                    actions: vec![Box::new(move |tx: &crate::app_event_sender::AppEventSender| {
+                        tx.send(crate::app_event::AppEvent::FixTestFailure(index));
+                    })],
+                }
+            })
+            .collect();
+        let view = ListSelectionView::new(
+            " Failing tests ".to_string(),
+            Some(format!("{} failing · {}", items.len(), report.command)),
+            Some("Enter fix · Esc close".to_string()),
+            items,
+            self.app_event_tx.clone(),
+            8,
+        );
+        self.bottom_pane
+            .show_list_selection("Failing tests".to_string(), None, None, view);
+        self.request_redraw();
+    }
+
+    pub(crate) fn fix_test_failure(&mut self, index: usize) {
+        let Some(report) = self.test_results.report.as_ref() else {
+            return;
+        };
+        let Some(failure) = report.failures.get(index) else {
+            return;
+        };
+        let display = format!("Fix failing test: {}", failure.name);
+        let prompt = fix_failure_prompt(report, failure);
+        // The prompt already carries this test's output.
+        self.test_results.pending_attachment = None;
+        self.submit_prompt_with_display(display, prompt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_core::test_runner::TestRunner;
+    use std::path::PathBuf;
+
+    #[test]
+    fn fix_prompt_carries_the_failure_output() {
+        let failure = TestFailure {
+            name: "tests::parses".to_string(),
+            file: Some("src/lib.rs".to_string()),
+            line: Some(42),
+            message: "assertion failed".to_string(),
+            output: "thread 'tests::parses' panicked at src/lib.rs:42:9:\nassertion failed\n".to_string(),
+        };
+        let report = TestReport {
+            runner: TestRunner::Cargo,
+            command: "cargo test --no-fail-fast".to_string(),
+            cwd: PathBuf::from("/repo"),
+            exit_code: 101,
+            timed_out: false,
+            passed: Some(3),
+            failed: Some(1),
+            skipped: None,
+            failures: vec![failure.clone()],
+            output_tail: None,
+        };
+        assert_eq!(
+            fix_failure_prompt(&report, &failure),
+            "Fix this failing test.\n\ntests::parses (src/lib.rs:42)\nRun with: cargo test --no-fail-fast in /repo\n\nOutput:\n```\nthread 'tests::parses' panicked at src/lib.rs:42:9:\nassertion failed\n```\n\nFind the cause, fix the code (or the test, if the test is wrong), then run this test again with run_tests."
+        );
+    }
+}
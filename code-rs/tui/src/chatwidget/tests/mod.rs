@@ -3132,6 +3132,82 @@ fn reset_history(chat: &mut ChatWidget<'_>) {
     assert_eq!(text.trim(), "old-2");
     }
     
+    #[test]
+    fn inserting_history_keeps_other_records_cached() {
+    let mut harness = ChatWidgetHarness::new();
+    let chat = harness.chat();
+    reset_history(chat);
+    
+    insert_plain_cell(chat, &["first-1", "first-2"]);
+    insert_plain_cell(chat, &["second-1", "second-2"]);
+    
+    let mut terminal = Terminal::new(TestBackend::new(40, 12)).expect("terminal");
+    terminal
+        .draw(|frame| frame.render_widget_ref(&*chat, frame.area()))
+        .expect("draw history");
+    
+    let cached_before = chat.history_render.layout_cache.borrow().len();
+    assert!(cached_before > 0, "draw should populate the layout cache");
+    let full_before = chat.history_render.invalidations().full;
+    
+    insert_plain_cell(chat, &["third-1"]);
+    
+    assert_eq!(
+        chat.history_render.invalidations().full,
+        full_before,
+        "appending a record must not clear the whole cache"
+    );
+    assert_eq!(
+        chat.history_render.layout_cache.borrow().len(),
+        cached_before,
+        "existing records keep their cached layouts"
+    );
+    }
+    
+    #[test]
+    fn bottom_pane_damage_reuses_composed_history() {
+    use crate::dirty_regions::{FrameDamage, Regions};
+    
+    let mut harness = ChatWidgetHarness::new();
+    let chat = harness.chat();
+    reset_history(chat);
+    chat.perf_state.enabled = true;
+    insert_plain_cell(chat, &["kept-line"]);
+    
+    let bottom_only = FrameDamage {
+        regions: Regions::BOTTOM_PANE,
+        cause: "test",
+    };
+    let frame_text = |buffer: &Buffer| -> String {
+        buffer.content.iter().map(|cell| cell.symbol()).collect()
+    };
+    let mut terminal = Terminal::new(TestBackend::new(40, 12)).expect("terminal");
+    terminal
+        .draw(|frame| frame.render_widget_ref(&*chat, frame.area()))
+        .expect("first draw");
+    assert_eq!(chat.perf_state.stats.borrow().frames, 1);
+    
+    chat.set_frame_damage(bottom_only);
+    let completed = terminal
+        .draw(|frame| frame.render_widget_ref(&*chat, frame.area()))
+        .expect("bottom pane draw");
+    assert!(frame_text(completed.buffer).contains("kept-line"));
+    assert_eq!(
+        chat.perf_state.stats.borrow().frames,
+        1,
+        "history should come from the previous frame"
+    );
+    
+    // History changed without asking for a history redraw: it must still repaint.
+    insert_plain_cell(chat, &["fresh-line"]);
+    chat.set_frame_damage(bottom_only);
+    let completed = terminal
+        .draw(|frame| frame.render_widget_ref(&*chat, frame.area()))
+        .expect("draw after insert");
+    assert!(frame_text(completed.buffer).contains("fresh-line"));
+    assert_eq!(chat.perf_state.stats.borrow().frames, 2);
+    }
+    
     #[test]
     fn final_answer_without_task_complete_clears_spinner() {
     let _rt = enter_test_runtime_guard();
@@ -3565,5 +3641,167 @@ fn reset_history(chat: &mut ChatWidget<'_>) {
     );
     }
     
+    #[test]
+    fn restore_snapshot_hydrates_only_the_tail() {
+    let mut harness = ChatWidgetHarness::new();
+    let chat = harness.chat();
+    reset_history(chat);
+    
+    let total = RESTORE_HYDRATED_TAIL + 10;
+    let records: Vec<HistoryRecord> = (1..=total as u64)
+        .map(|id| {
+            HistoryRecord::PlainMessage(PlainMessageState {
+                id: HistoryId(id),
+                role: PlainMessageRole::Assistant,
+                kind: PlainMessageKind::Assistant,
+                header: None,
+                lines: vec![MessageLine {
+                    kind: MessageLineKind::Paragraph,
+                    spans: vec![InlineSpan {
+                        text: format!("message {id}"),
+                        tone: TextTone::Default,
+                        emphasis: TextEmphasis::default(),
+                        entity: None,
+                    }],
+                }],
+                metadata: None,
+            })
+        })
+        .collect();
+    let snapshot = HistorySnapshot {
+        records,
+        next_id: total as u64 + 1,
+        exec_call_lookup: HashMap::new(),
+        tool_call_lookup: HashMap::new(),
+        stream_lookup: HashMap::new(),
+        order: Vec::new(),
+        order_debug: Vec::new(),
+    };
+    
+    chat.restore_history_snapshot(&snapshot);
+    
+    assert_eq!(chat.history_cells.len(), total);
+    assert_eq!(chat.history_frozen_count, 10);
+    let first = chat.history_cells[0]
+        .as_any()
+        .downcast_ref::<FrozenHistoryCell>()
+        .expect("old records start as placeholders");
+    assert!(!first.is_measured());
+    assert_eq!(first.kind(), HistoryCellType::Assistant);
+    assert!(!ChatWidget::is_frozen_cell(chat.history_cells[10].as_ref()));
+    
+    assert!(chat.thaw_history_cell_at(0));
+    let text: String = chat.history_cells[0]
+        .display_lines_trimmed()
+        .iter()
+        .flat_map(|line| line.spans.iter().map(|span| span.content.as_ref()))
+        .collect();
+    assert!(text.contains("message 1"), "thawed placeholder should render its record");
+    assert_eq!(chat.history_frozen_count, 9);
+    }
+    
+    #[test]
+    fn history_golden_transcript_round_trips_restore() {
+    let mut harness = ChatWidgetHarness::new();
+    let chat = harness.chat();
+    reset_history(chat);
+    
+    let plain = |id: u64, text: &str, role: PlainMessageRole, kind: PlainMessageKind| {
+        HistoryRecord::PlainMessage(PlainMessageState {
+            id: HistoryId(id),
+            role,
+            kind,
+            header: None,
+            lines: vec![MessageLine {
+                kind: MessageLineKind::Paragraph,
+                spans: vec![InlineSpan {
+                    text: text.to_string(),
+                    tone: TextTone::Default,
+                    emphasis: TextEmphasis::default(),
+                    entity: None,
+                }],
+            }],
+            metadata: None,
+        })
+    };
+    let snapshot = HistorySnapshot {
+        records: vec![
+            plain(1, "user-turn", PlainMessageRole::User, PlainMessageKind::User),
+            plain(2, "assistant-turn", PlainMessageRole::Assistant, PlainMessageKind::Assistant),
+        ],
+        next_id: 3,
+        exec_call_lookup: HashMap::new(),
+        tool_call_lookup: HashMap::new(),
+        stream_lookup: HashMap::new(),
+        order: vec![
+            OrderKeySnapshot { req: 5, out: 0, seq: 0 },
+            OrderKeySnapshot { req: 5, out: 1, seq: 0 },
+        ],
+        order_debug: Vec::new(),
+    };
+    
+    chat.restore_history_snapshot(&snapshot);
+    let golden = code_core::history::golden::golden_transcript(
+        &chat.history_snapshot_for_persistence(),
+    );
+    assert_eq!(
+        golden,
+        "# code history golden v1\n\
+         \n[0000] order=5:0:0 plain kind=User role=User\n  | user-turn\n\
+         \n[0001] order=5:1:0 plain kind=Assistant role=Assistant\n  | assistant-turn\n"
+    );
+    }
+    
+    
+    
+    
+    #[test]
+    fn replay_skips_items_with_an_already_rendered_id() {
+    let mut harness = ChatWidgetHarness::new();
+    let chat = harness.chat();
+    reset_history(chat);
+    
+    let message = |text: &str| code_protocol::models::ResponseItem::Message {
+        id: Some("msg_1".to_string()),
+        role: "assistant".to_string(),
+        content: vec![code_protocol::models::ContentItem::OutputText {
+            text: text.to_string(),
+        }],
+        end_turn: None,
+        phase: None,
+    };
+    chat.render_replay_item(message("first answer"));
+    let after_first = chat.history_cells.len();
+    chat.render_replay_item(message("first answer, streamed again"));
+    
+    assert_eq!(chat.history_cells.len(), after_first);
+    let assistant_records = chat
+        .history_state
+        .records
+        .iter()
+        .filter(|record| matches!(record, HistoryRecord::AssistantMessage(_)))
+        .count();
+    assert_eq!(assistant_records, 1);
+    }
+    
+    #[test]
+    fn order_overlay_label_shows_key_and_tag() {
+    let mut harness = ChatWidgetHarness::new();
+    let chat = harness.chat();
+    reset_history(chat);
     
+    let key = OrderKey {
+        req: 2,
+        out: 1,
+        seq: 7,
+    };
+    let idx = chat.history_insert_with_key_global_tagged(
+        Box::new(history_cell::new_background_event("indexing".to_string())),
+        key,
+        "background",
+        None,
+    );
     
+    assert_eq!(chat.order_overlay_label(idx).as_deref(), Some("2:1:7 background"));
+    assert_eq!(chat.order_overlay_label(idx + 1), None);
+    }
@@ -0,0 +1,179 @@
+use super::*;
+
+use crate::history_cell::{TouchedFileEntry, TouchedFilesCell};
+use code_core::protocol::FileChange;
+use std::collections::HashSet;
+
+impl ChatWidget<'_> {
+    /// Latest-first unique list of files patched this session, keyed by the
+    /// destination path for renames. This is also the Ctrl+D tab order.
+    pub(super) fn session_diff_order(&self) -> Vec<PathBuf> {
+        let mut order: Vec<PathBuf> = Vec::new();
+        let mut seen: HashSet<PathBuf> = HashSet::new();
+        for changes in self.diffs.session_patch_sets.iter().rev() {
+            for (path, change) in changes {
+                let display_path = match change {
+                    FileChange::Update {
+                        move_path: Some(dest),
+                        ..
+                    } => dest.clone(),
+                    _ => path.clone(),
+                };
+                if seen.insert(display_path.clone()) {
+                    order.push(display_path);
+                }
+            }
+        }
+        order
+    }
+
+    /// Replaces the session's touched-files summary with a fresh one at the
+    /// bottom of history. Called after every successful patch apply.
+    pub(super) fn refresh_touched_files_summary(&mut self) {
+        let entries: Vec<TouchedFileEntry> = self
+            .session_diff_order()
+            .into_iter()
+            .map(|path| self.touched_file_entry(path))
+            .collect();
+        if let Some(idx) = self
+            .history_cells
+            .iter()
+            .rposition(|cell| cell.as_any().is::<TouchedFilesCell>())
+        {
+            self.history_remove_at(idx);
+        }
+        if entries.is_empty() {
+            return;
+        }
+        self.history_push(TouchedFilesCell::new(entries));
+    }
+
+    fn touched_file_entry(&self, path: PathBuf) -> TouchedFileEntry {
+        let baseline = self
+            .diffs
+            .baseline_file_contents
+            .get(&path)
+            .cloned()
+            .unwrap_or_default();
+        let current = std::fs::read_to_string(&path).unwrap_or_default();
+        let (added, removed) =
+            diff_line_counts(&diffy::create_patch(&baseline, &current).to_string());
+
+        let changes_per_apply = self
+            .diffs
+            .session_patch_sets
+            .iter()
+            .filter_map(|changes| {
+                changes.iter().find_map(|(src, change)| match change {
+                    FileChange::Update {
+                        move_path,
+                        unified_diff,
+                        ..
+                    } if *src == path || move_path.as_ref() == Some(&path) => {
+                        let (added, removed) = diff_line_counts(unified_diff);
+                        Some(added + removed)
+                    }
+                    FileChange::Add { content } if *src == path => Some(content.lines().count()),
+                    FileChange::Delete if *src == path => Some(baseline.lines().count().max(1)),
+                    _ => None,
+                })
+            })
+            .collect();
+
+        let display = path
+            .strip_prefix(&self.config.cwd)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        TouchedFileEntry {
+            path,
+            display,
+            added,
+            removed,
+            changes_per_apply,
+        }
+    }
+
+    /// Tree metadata for the Ctrl+D viewer. A file is pending while the disk
+    /// still shows the state from before the latest patch that touched it
+    /// (awaiting approval or apply); later edits such as formatting still
+    /// count as applied.
+    pub(super) fn diff_file_meta(&self, path: &Path) -> DiffFileMeta {
+        let on_disk = std::fs::read_to_string(path).ok();
+        let not_yet_applied = self.diffs.session_patch_sets.iter().rev().find_map(|changes| {
+            changes.iter().find_map(|(src, change)| match change {
+                FileChange::Update {
+                    move_path: Some(dest),
+                    ..
+                } if dest.as_path() == path => Some(on_disk.is_none()),
+                FileChange::Update {
+                    original_content,
+                    new_content,
+                    ..
+                } if src == path => {
+                    Some(original_content != new_content && on_disk.as_ref() == Some(original_content))
+                }
+                FileChange::Add { .. } if src == path => Some(on_disk.is_none()),
+                FileChange::Delete if src == path => Some(on_disk.is_some()),
+                _ => None,
+            })
+        });
+        let in_flight = self
+            .diffs
+            .pending_applies
+            .values()
+            .any(|pending| pending.changes.contains_key(path));
+        DiffFileMeta {
+            path: path.to_path_buf(),
+            display: path
+                .strip_prefix(&self.config.cwd)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .replace('\\', "/"),
+            status: if not_yet_applied.unwrap_or(false) || in_flight {
+                DiffFileStatus::Pending
+            } else {
+                DiffFileStatus::Applied
+            },
+        }
+    }
+
+    /// Opens the Ctrl+D diff overlay with `path`'s tab selected.
+    pub(super) fn show_diff_for_file(&mut self, path: &Path) {
+        self.show_diffs_popup();
+        if let Some(overlay) = self.diffs.overlay.as_mut()
+            && let Some(selected) = overlay.files.iter().position(|file| file.path == path)
+        {
+            overlay.selected = selected;
+        }
+    }
+}
+
+/// Counts inserted and deleted lines in a unified diff.
+pub(super) fn diff_line_counts(unified: &str) -> (usize, usize) {
+    let mut added = 0;
+    let mut removed = 0;
+    if let Ok(patch) = diffy::Patch::from_str(unified) {
+        for hunk in patch.hunks() {
+            for line in hunk.lines() {
+                match line {
+                    diffy::Line::Insert(_) => added += 1,
+                    diffy::Line::Delete(_) => removed += 1,
+                    diffy::Line::Context(_) => {}
+                }
+            }
+        }
+    } else {
+        for line in unified.lines() {
+            if line.starts_with("+++") || line.starts_with("---") || line.starts_with("@@") {
+                continue;
+            }
+            if line.starts_with('+') {
+                added += 1;
+            } else if line.starts_with('-') {
+                removed += 1;
+            }
+        }
+    }
+    (added, removed)
+}
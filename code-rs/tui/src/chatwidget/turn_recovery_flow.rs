@@ -0,0 +1,266 @@
+use super::*;
+
+use crate::app_event::TurnRecovery;
+use crate::bottom_pane::SelectionAction;
+use code_core::InterruptedTurn;
+use code_core::SessionQuery;
+use code_protocol::protocol::SessionSource;
+
+impl ChatWidget<'_> {
+    /// Once the session is configured, looks for a turn the previous run
+    /// never finished: in the resumed session, or else in the latest session
+    /// for this directory. Skipped when the user started with a prompt.
+    pub(super) fn check_interrupted_turn(&mut self) {
+        if self.turn_recovery.checked {
+            return;
+        }
+        self.turn_recovery.checked = true;
+        let tx = self.app_event_tx.clone();
+        if let Some(path) = self.config.experimental_resume.clone() {
+            tokio::task::spawn_blocking(move || {
+                if let Ok(Some(turn)) = code_core::find_interrupted_turn(&path) {
+                    tx.send(AppEvent::InterruptedTurnFound {
+                        path,
+                        turn,
+                        resumed: true,
+                    });
+                }
+            });
+            return;
+        }
+        if self.initial_user_message.is_some() {
+            return;
+        }
+
+        let code_home = self.config.code_home.clone();
+        let cwd = self.config.cwd.clone();
+        let current = self.session_id;
+        tokio::spawn(async move {
+            let query = SessionQuery {
+                cwd: Some(cwd),
+                sources: vec![SessionSource::Cli],
+                limit: Some(2),
+                ..SessionQuery::default()
+            };
+            let entries = match code_core::SessionCatalog::new(code_home.clone())
+                .query(&query)
+                .await
+            {
+                Ok(entries) => entries,
+                Err(err) => {
+                    tracing::debug!("cannot look for an interrupted session: {err}");
+                    return;
+                }
+            };
+            let Some(previous) = entries
+                .iter()
+                .find(|entry| Some(entry.session_id) != current)
+            else {
+                return;
+            };
+            let path = code_core::entry_to_rollout_path(&code_home, previous);
+            let found = tokio::task::spawn_blocking(move || {
+                code_core::find_interrupted_turn(&path).map(|turn| (path, turn))
+            })
+            .await;
+            if let Ok(Ok((path, Some(turn)))) = found {
+                tx.send(AppEvent::InterruptedTurnFound {
+                    path,
+                    turn,
+                    resumed: false,
+                });
+            }
+        });
+    }
+
+    pub(crate) fn on_interrupted_turn_found(
+        &mut self,
+        path: PathBuf,
+        turn: InterruptedTurn,
+        resumed: bool,
+    ) {
+        if !resumed {
+            self.show_interrupted_session_prompt(path, &turn);
+            return;
+        }
+
+        let mut lines = vec![
+            "The last turn of this session did not finish; Code exited while it was running."
+                .to_string(),
+        ];
+        if let Some(reasoning) = turn.partial_reasoning.as_deref() {
+            lines.push(format!("Reasoning before it stopped:\n{}", reasoning.trim_end()));
+        }
+        if let Some(reply) = turn.partial_reply.as_deref() {
+            lines.push(format!("Reply before it stopped:\n{}", reply.trim_end()));
+        }
+        self.history_push_plain_paragraphs(PlainMessageKind::Notice, lines);
+
+        let can_resubmit = turn.user_message.is_some();
+        self.turn_recovery.interrupted = Some(turn);
+        match self.turn_recovery.pending.take() {
+            Some(TurnRecovery::Resubmit) if !can_resubmit => self.show_turn_recovery_prompt(),
+            Some(action) => self.recover_interrupted_turn(action),
+            None => self.show_turn_recovery_prompt(),
+        }
+    }
+
+    /// Continues or resubmits the interrupted turn. A choice made while the
+    /// session is still resuming waits until the turn has been found.
+    pub(crate) fn recover_interrupted_turn(&mut self, action: TurnRecovery) {
+        let Some(turn) = self.turn_recovery.interrupted.take() else {
+            self.turn_recovery.pending = Some(action);
+            return;
+        };
+        let text = match action {
+            TurnRecovery::Continue => continue_prompt(&turn),
+            TurnRecovery::Resubmit => match turn.user_message {
+                Some(message) => message,
+                None => return,
+            },
+            TurnRecovery::Dismiss => return,
+        };
+        self.submit_user_message(UserMessage::from(text));
+    }
+
+    /// Offers to resume the previous session in this directory, which ended
+    /// mid-turn.
+    fn show_interrupted_session_prompt(&mut self, path: PathBuf, turn: &InterruptedTurn) {
+        let resume_with = |action: TurnRecovery| -> SelectionAction {
+            let path = path.clone();
+            Box::new(move |tx: &crate::app_event_sender::AppEventSender| {
+                tx.send(AppEvent::ResumeFrom(path.clone()));
+                tx.send(AppEvent::RecoverInterruptedTurn(action));
+            })
+        };
+        let mut items = vec![SelectionItem {
+            name: "Resume and continue".to_string(),
+            description: Some("Reopen that session and ask the model to pick up where it stopped".to_string()),
+            is_current: true,
+            actions: vec![resume_with(TurnRecovery::Continue)],
+        }];
+        if turn.user_message.is_some() {
+            items.push(SelectionItem {
+                name: "Resume and resubmit".to_string(),
+                description: Some("Reopen that session and send the interrupted message again".to_string()),
+                is_current: false,
+                actions: vec![resume_with(TurnRecovery::Resubmit)],
+            });
+        }
+        items.push(SelectionItem {
+            name: "Resume only".to_string(),
+            description: Some("Reopen that session without sending anything".to_string()),
+            is_current: false,
+            actions: vec![resume_with(TurnRecovery::Dismiss)],
+        });
+        items.push(SelectionItem {
+            name: "Start fresh".to_string(),
+            description: Some("Keep this new session".to_string()),
+            is_current: false,
+            actions: Vec::new(),
+        });
+        let view = ListSelectionView::new(
+            " Interrupted session ".to_string(),
+            Some(interrupted_subtitle("The last session here stopped mid-turn", turn)),
+            Some("Enter select · Esc start fresh".to_string()),
+            items,
+            self.app_event_tx.clone(),
+            4,
+        );
+        self.bottom_pane
+            .show_list_selection("Interrupted session".to_string(), None, None, view);
+        self.request_redraw();
+    }
+
+    fn show_turn_recovery_prompt(&mut self) {
+        let Some(turn) = self.turn_recovery.interrupted.as_ref() else {
+            return;
+        };
+        let recover = |action: TurnRecovery| -> SelectionAction {
+            Box::new(move |tx: &crate::app_event_sender::AppEventSender| {
+                tx.send(AppEvent::RecoverInterruptedTurn(action));
+            })
+        };
+        let mut items = vec![SelectionItem {
+            name: "Continue".to_string(),
+            description: Some("Ask the model to pick up where it stopped".to_string()),
+            is_current: true,
+            actions: vec![recover(TurnRecovery::Continue)],
+        }];
+        if turn.user_message.is_some() {
+            items.push(SelectionItem {
+                name: "Resubmit".to_string(),
+                description: Some("Send the interrupted message again".to_string()),
+                is_current: false,
+                actions: vec![recover(TurnRecovery::Resubmit)],
+            });
+        }
+        items.push(SelectionItem {
+            name: "Leave it".to_string(),
+            description: Some("Keep the session as it is".to_string()),
+            is_current: false,
+            actions: vec![recover(TurnRecovery::Dismiss)],
+        });
+        let view = ListSelectionView::new(
+            " Interrupted turn ".to_string(),
+            Some(interrupted_subtitle("The last turn stopped before it finished", turn)),
+            Some("Enter select · Esc leave it".to_string()),
+            items,
+            self.app_event_tx.clone(),
+            3,
+        );
+        self.bottom_pane
+            .show_list_selection("Interrupted turn".to_string(), None, None, view);
+        self.request_redraw();
+    }
+}
+
+fn interrupted_subtitle(lead: &str, turn: &InterruptedTurn) -> String {
+    match turn
+        .user_message
+        .as_deref()
+        .and_then(|message| message.lines().map(str::trim).find(|line| !line.is_empty()))
+    {
+        Some(line) => {
+            let snippet: String = line.chars().take(60).collect();
+            let ellipsis = if snippet.len() < line.len() { "…" } else { "" };
+            format!("{lead}: \"{snippet}{ellipsis}\"")
+        }
+        None => format!("{lead}."),
+    }
+}
+
+/// The message sent for [`TurnRecovery::Continue`]. The streamed reply is
+/// not in the model's history, so it is quoted back.
+fn continue_prompt(turn: &InterruptedTurn) -> String {
+    let lead = "Your previous turn was interrupted before it finished (the app exited).";
+    match turn.partial_reply.as_deref() {
+        Some(reply) => format!(
+            "{lead} This is what you had replied so far:\n\n{}\n\nContinue from where you left off.",
+            reply.trim_end()
+        ),
+        None => format!("{lead} Continue from where you left off."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn continue_prompt_quotes_the_partial_reply() {
+        let turn = InterruptedTurn {
+            user_message: Some("add tests".to_string()),
+            partial_reply: Some("Adding tests to lib.rs\n".to_string()),
+            partial_reasoning: None,
+        };
+        assert_eq!(
+            continue_prompt(&turn),
+            "Your previous turn was interrupted before it finished (the app exited). This is what you had replied so far:\n\nAdding tests to lib.rs\n\nContinue from where you left off."
+        );
+        assert_eq!(
+            continue_prompt(&InterruptedTurn::default()),
+            "Your previous turn was interrupted before it finished (the app exited). Continue from where you left off."
+        );
+    }
+}
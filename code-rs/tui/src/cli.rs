@@ -96,6 +96,24 @@ pub struct Cli {
     #[arg(long = "timing", default_value_t = false)]
     pub timing: bool,
 
+    /// Record how long each startup stage takes (config, migrations, terminal
+    /// setup, first frame, session and account readiness) and print the
+    /// spans to stderr on exit.
+    #[arg(long = "profile-startup", default_value_t = false)]
+    pub profile_startup: bool,
+
+    /// Run the session in a fresh git worktree of the current repository so
+    /// agent edits and commands never touch your checkout. Use `/merge` to
+    /// bring the results back.
+    #[arg(long = "worktree", default_value_t = false)]
+    pub worktree: bool,
+
+    /// Print the conversation to stdout as plain text as it happens: no
+    /// alternate screen, mouse capture, colors or hyperlinks. Suited to
+    /// `script`, tmux pipes and screen readers.
+    #[arg(long = "plain", default_value_t = false)]
+    pub plain: bool,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 
@@ -115,6 +133,11 @@ pub struct Cli {
     /// Resume a specific session id when provided.
     #[clap(skip)]
     pub resume_session_id: Option<String>,
+
+    /// Drive the session served by `code daemon` on this socket instead of
+    /// starting one (used by `code attach`).
+    #[clap(skip)]
+    pub attach_socket: Option<PathBuf>,
 }
 
 impl Cli {
@@ -41,8 +41,36 @@ pub struct PastedImageInfo {
 }
 
 /// Capture image from system clipboard, encode to PNG, and return bytes plus metadata.
-#[cfg(feature = "clipboard")]
+///
+/// On Termux the Android clipboard only holds text, so an image is read from
+/// the file whose path was copied.
 pub fn paste_image_as_png() -> Result<(Vec<u8>, PastedImageInfo), PasteImageError> {
+    if termux::available() {
+        return paste_image_from_termux();
+    }
+    paste_image_from_system_clipboard()
+}
+
+fn paste_image_from_termux() -> Result<(Vec<u8>, PastedImageInfo), PasteImageError> {
+    let text = termux::get_text().map_err(PasteImageError::ClipboardUnavailable)?;
+    let path = normalize_pasted_path(&text)
+        .filter(|path| path.is_file())
+        .ok_or_else(|| PasteImageError::NoImage("clipboard text is not an image path".into()))?;
+    let dyn_img = image::open(&path).map_err(|e| PasteImageError::DecodeFailed(e.to_string()))?;
+    let (w, h) = (dyn_img.width(), dyn_img.height());
+    let mut png: Vec<u8> = Vec::new();
+    {
+        let mut cursor = std::io::Cursor::new(&mut png);
+        dyn_img
+            .write_to(&mut cursor, image::ImageFormat::Png)
+            .map_err(|e| PasteImageError::EncodeFailed(e.to_string()))?;
+    }
+    tracing::debug!("termux clipboard image {} encoded to {w}x{h} PNG", path.display());
+    Ok((png, PastedImageInfo { width: w, height: h, encoded_format: EncodedImageFormat::Png }))
+}
+
+#[cfg(feature = "clipboard")]
+fn paste_image_from_system_clipboard() -> Result<(Vec<u8>, PastedImageInfo), PasteImageError> {
     tracing::debug!("attempting clipboard image read");
     let mut cb = arboard::Clipboard::new()
         .map_err(|e| PasteImageError::ClipboardUnavailable(e.to_string()))?;
@@ -78,12 +106,144 @@ pub fn paste_image_as_png() -> Result<(Vec<u8>, PastedImageInfo), PasteImageErro
 }
 
 #[cfg(not(feature = "clipboard"))]
-pub fn paste_image_as_png() -> Result<(Vec<u8>, PastedImageInfo), PasteImageError> {
+fn paste_image_from_system_clipboard() -> Result<(Vec<u8>, PastedImageInfo), PasteImageError> {
     Err(PasteImageError::ClipboardUnavailable(
         "clipboard feature not enabled for this platform".to_string(),
     ))
 }
 
+/// Read text from the system clipboard, preferring Termux:API when present.
+pub fn paste_text_from_clipboard() -> Result<String, String> {
+    if termux::available() {
+        return termux::get_text();
+    }
+    #[cfg(feature = "clipboard")]
+    {
+        arboard::Clipboard::new()
+            .and_then(|mut cb| cb.get_text())
+            .map_err(|e| e.to_string())
+    }
+    #[cfg(not(feature = "clipboard"))]
+    {
+        Err("clipboard feature not enabled for this platform".to_string())
+    }
+}
+
+/// Place text on the system clipboard. Over SSH this is the clipboard of the
+/// terminal the user is looking at (via OSC 52); otherwise Termux:API when
+/// present, then the local system clipboard.
+pub fn copy_text_to_clipboard(text: &str) -> Result<(), String> {
+    if code_core::env::is_ssh_session() {
+        return osc52::set_text(text);
+    }
+    if termux::available() {
+        return termux::set_text(text);
+    }
+    #[cfg(feature = "clipboard")]
+    {
+        arboard::Clipboard::new()
+            .and_then(|mut cb| cb.set_text(text.to_string()))
+            .map_err(|e| e.to_string())
+    }
+    #[cfg(not(feature = "clipboard"))]
+    {
+        let _ = text;
+        Err("clipboard feature not enabled for this platform".to_string())
+    }
+}
+
+/// Clipboard writes through the terminal itself (OSC 52), which reaches the
+/// local clipboard from an SSH session. tmux needs the sequence wrapped in a
+/// passthrough (and `set -g allow-passthrough on`).
+mod osc52 {
+    use base64::Engine;
+    use std::io::Write;
+
+    /// Terminals commonly cap OSC 52 payloads around 100 KB and silently drop
+    /// anything larger.
+    const MAX_ENCODED_LEN: usize = 100_000;
+
+    pub(super) fn set_text(text: &str) -> Result<(), String> {
+        let sequence = sequence(text, std::env::var_os("TMUX").is_some())?;
+        let mut stdout = std::io::stdout();
+        stdout
+            .write_all(sequence.as_bytes())
+            .and_then(|()| stdout.flush())
+            .map_err(|e| format!("OSC 52: {e}"))
+    }
+
+    fn sequence(text: &str, tmux: bool) -> Result<String, String> {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+        if encoded.len() > MAX_ENCODED_LEN {
+            return Err(format!(
+                "text is too large to copy over SSH ({} KB encoded; terminals accept about {} KB)",
+                encoded.len() / 1000,
+                MAX_ENCODED_LEN / 1000
+            ));
+        }
+        let osc = format!("\x1b]52;c;{encoded}\x07");
+        Ok(if tmux {
+            format!("\x1bPtmux;{}\x1b\\", osc.replace('\x1b', "\x1b\x1b"))
+        } else {
+            osc
+        })
+    }
+}
+
+/// Android clipboard access through the Termux:API package. arboard has no
+/// Android backend, so this is the only clipboard inside Termux.
+mod termux {
+    use std::io::Write;
+    use std::process::Command;
+    use std::process::Stdio;
+    use std::sync::OnceLock;
+
+    pub(super) fn available() -> bool {
+        static AVAILABLE: OnceLock<bool> = OnceLock::new();
+        *AVAILABLE.get_or_init(|| {
+            code_core::env::is_termux()
+                && which::which("termux-clipboard-get").is_ok()
+                && which::which("termux-clipboard-set").is_ok()
+        })
+    }
+
+    pub(super) fn get_text() -> Result<String, String> {
+        let output = Command::new("termux-clipboard-get")
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| format!("termux-clipboard-get: {e}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "termux-clipboard-get exited with {}; is the Termux:API app installed?",
+                output.status
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    pub(super) fn set_text(text: &str) -> Result<(), String> {
+        let mut child = Command::new("termux-clipboard-set")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("termux-clipboard-set: {e}"))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(text.as_bytes())
+                .map_err(|e| format!("termux-clipboard-set: {e}"))?;
+        }
+        let status = child.wait().map_err(|e| format!("termux-clipboard-set: {e}"))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "termux-clipboard-set exited with {status}; is the Termux:API app installed?"
+            ))
+        }
+    }
+}
+
 /// Write clipboard PNG to a temporary file and return the path.
 pub fn paste_image_to_temp_png() -> Result<(PathBuf, PastedImageInfo), PasteImageError> {
     let (png, info) = paste_image_as_png()?;
@@ -0,0 +1,96 @@
+//! Voice input for the composer.
+//!
+//! `/dictate` (or Alt+M) runs a speech-to-text command on a background thread
+//! and hands the transcription back as [`AppEvent::DictationFinished`]. The
+//! default is Termux:API's `termux-speech-to-text`, which streams partial
+//! guesses line by line before the final one; `tui.dictation_command`
+//! replaces it with any command that prints its transcription to stdout.
+
+use std::process::Command;
+use std::process::Stdio;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+
+const TERMUX_SPEECH_TO_TEXT: &str = "termux-speech-to-text";
+
+/// Starts recording with `command` (argv form), or `termux-speech-to-text`
+/// when `None`. Errors when the command cannot be found or started.
+pub(crate) fn start(command: Option<&[String]>, app_event_tx: AppEventSender) -> Result<(), String> {
+    let (program, args, streams_partials) = match command {
+        Some([program, args @ ..]) => (program.clone(), args.to_vec(), false),
+        Some([]) => return Err("`tui.dictation_command` is empty".to_string()),
+        None => (TERMUX_SPEECH_TO_TEXT.to_string(), Vec::new(), true),
+    };
+    if which::which(&program).is_err() {
+        return Err(if streams_partials {
+            format!("{TERMUX_SPEECH_TO_TEXT} not found; install `termux-api` or set `tui.dictation_command`")
+        } else {
+            format!("dictation command `{program}` not found")
+        });
+    }
+    let child = Command::new(&program)
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("failed to start `{program}`: {err}"))?;
+
+    let spawned = crate::thread_spawner::spawn_lightweight("dictation", move || {
+        let result = match child.wait_with_output() {
+            Ok(output) if output.status.success() => {
+                Ok(transcription_from_output(&output.stdout, streams_partials))
+            }
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+                Err(if stderr.is_empty() {
+                    format!("`{program}` exited with {}", output.status)
+                } else {
+                    format!("`{program}` failed: {stderr}")
+                })
+            }
+            Err(err) => Err(format!("`{program}` failed: {err}")),
+        };
+        app_event_tx.send(AppEvent::DictationFinished(result));
+    });
+    if spawned.is_none() {
+        return Err("too many background tasks; try again shortly".to_string());
+    }
+    Ok(())
+}
+
+/// Extracts the transcription. Streaming recognizers print each partial
+/// guess on its own line, so only the last one is kept.
+fn transcription_from_output(stdout: &[u8], streams_partials: bool) -> String {
+    let text = String::from_utf8_lossy(stdout);
+    if streams_partials {
+        text.lines()
+            .rev()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .to_string()
+    } else {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn keeps_final_guess_from_streaming_output() {
+        assert_eq!(
+            transcription_from_output(b"add\nadd a test\nadd a test for parsing\n\n", true),
+            "add a test for parsing"
+        );
+        assert_eq!(
+            transcription_from_output(b"  fix the\n  build  \n", false),
+            "fix the build"
+        );
+        assert_eq!(transcription_from_output(b"", true), "");
+    }
+}
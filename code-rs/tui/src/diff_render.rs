@@ -5,6 +5,8 @@ use ratatui::style::Style;
 use ratatui::text::Line as RtLine;
 use ratatui::text::Span as RtSpan;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ops::Range;
 use std::path::PathBuf;
 
 use code_core::protocol::FileChange;
@@ -181,12 +183,22 @@ const SPACES_AFTER_LINE_NUMBER: usize = 6;
 
 // Internal representation for diff line rendering
 #[allow(dead_code)]
+#[derive(Clone, Copy)]
 enum DiffLineType {
     Insert,
     Delete,
     Context,
 }
 
+/// Extra emphasis for a rendered diff line.
+#[derive(Clone, Default)]
+struct LineDecor {
+    /// Part of a hunk that only changes whitespace; rendered dimmed.
+    whitespace_only: bool,
+    /// Char ranges (in the sanitized text) of words changed within the line.
+    highlights: Vec<Range<usize>>,
+}
+
 #[allow(dead_code)]
 pub(super) fn create_diff_summary(
     title: &str,
@@ -283,8 +295,28 @@ pub(super) fn create_diff_summary_with_width(
         }
     };
 
+    let moves = detect_moves(changes);
+    let moved_sources: HashSet<&PathBuf> = moves.values().collect();
     let mut files: Vec<FileSummary> = Vec::new();
     for (path, change) in changes.iter() {
+        if moved_sources.contains(path) {
+            continue;
+        }
+        if let Some(source) = moves.get(path) {
+            files.push(FileSummary {
+                original_path: source.display().to_string(),
+                rename_target: Some(path.display().to_string()),
+                added: 0,
+                removed: 0,
+                change: FileSummaryKind::Update {
+                    rename_only: true,
+                    no_content_change: false,
+                    binary: false,
+                    metadata_only: false,
+                },
+            });
+            continue;
+        }
         match change {
             FileChange::Add { content } => {
                 let added = content.lines().count();
@@ -552,8 +584,14 @@ fn render_patch_details_with_width(
         full.saturating_sub(20).max(40)
     };
 
-    let total_files = changes.len();
-    for (index, (path, change)) in changes.iter().enumerate() {
+    let moves = detect_moves(changes);
+    let moved_sources: HashSet<&PathBuf> = moves.values().collect();
+    let entries: Vec<(&PathBuf, &FileChange)> = changes
+        .iter()
+        .filter(|(path, _)| !moved_sources.contains(path))
+        .collect();
+    let total_files = entries.len();
+    for (index, (path, change)) in entries.into_iter().enumerate() {
         let is_first_file = index == 0;
         // Add separator only between files (not at the very start)
         if !is_first_file {
@@ -563,6 +601,12 @@ fn render_patch_details_with_width(
             ]));
         }
         match change {
+            FileChange::Add { .. } if moves.contains_key(path) => {
+                out.push(rename_note_line(&format!(
+                    "moved from {}, content unchanged",
+                    moves[path].display()
+                )));
+            }
             FileChange::Add { content } => {
                 for (i, raw) in content.lines().enumerate() {
                     let ln = i + 1;
@@ -590,10 +634,18 @@ fn render_patch_details_with_width(
             }
             FileChange::Update {
                 unified_diff,
-                move_path: _,
+                move_path,
                 ..
             } => {
                 if let Ok(patch) = diffy::Patch::from_str(unified_diff) {
+                    if patch.hunks().is_empty()
+                        && let Some(dest) = move_path
+                    {
+                        out.push(rename_note_line(&format!(
+                            "renamed to {}, content unchanged",
+                            dest.display()
+                        )));
+                    }
                     let mut is_first_hunk = true;
                     for h in patch.hunks() {
                         // Render a simple separator between non-contiguous hunks
@@ -605,44 +657,7 @@ fn render_patch_details_with_width(
                             ]));
                         }
                         is_first_hunk = false;
-
-                        let mut old_ln = h.old_range().start();
-                        let mut new_ln = h.new_range().start();
-                        for l in h.lines() {
-                            match l {
-                                diffy::Line::Insert(text) => {
-                                    let s = sanitize_diff_text(text.trim_end_matches('\n'));
-                    out.extend(push_wrapped_diff_line_with_width(
-                        new_ln,
-                        DiffLineType::Insert,
-                        &s,
-                        term_cols,
-                    ));
-                                    new_ln += 1;
-                                }
-                                diffy::Line::Delete(text) => {
-                                    let s = sanitize_diff_text(text.trim_end_matches('\n'));
-                    out.extend(push_wrapped_diff_line_with_width(
-                        old_ln,
-                        DiffLineType::Delete,
-                        &s,
-                        term_cols,
-                    ));
-                                    old_ln += 1;
-                                }
-                                diffy::Line::Context(text) => {
-                                    let s = sanitize_diff_text(text.trim_end_matches('\n'));
-                    out.extend(push_wrapped_diff_line_with_width(
-                        new_ln,
-                        DiffLineType::Context,
-                        &s,
-                        term_cols,
-                    ));
-                                    old_ln += 1;
-                                    new_ln += 1;
-                                }
-                            }
-                        }
+                        out.extend(render_hunk(h, term_cols));
                     }
                 }
             }
@@ -658,6 +673,164 @@ fn render_patch_details_with_width(
     out
 }
 
+/// Pairs deleted files with added files that carry identical content so a move
+/// expressed as delete + add renders as a rename. Keys are the added paths.
+fn detect_moves(changes: &HashMap<PathBuf, FileChange>) -> HashMap<PathBuf, PathBuf> {
+    let mut deleted: Vec<(&PathBuf, String)> = changes
+        .iter()
+        .filter(|(_, change)| matches!(change, FileChange::Delete))
+        .filter_map(|(path, _)| std::fs::read_to_string(path).ok().map(|content| (path, content)))
+        .collect();
+    let mut moves = HashMap::new();
+    if deleted.is_empty() {
+        return moves;
+    }
+    deleted.sort_by(|a, b| a.0.cmp(b.0));
+    let mut added: Vec<(&PathBuf, &String)> = changes
+        .iter()
+        .filter_map(|(path, change)| match change {
+            FileChange::Add { content } if !content.is_empty() => Some((path, content)),
+            _ => None,
+        })
+        .collect();
+    added.sort_by(|a, b| a.0.cmp(b.0));
+    for (dest, content) in added {
+        if let Some(idx) = deleted.iter().position(|(_, old)| old == content) {
+            let (source, _) = deleted.remove(idx);
+            moves.insert(dest.clone(), source.clone());
+        }
+    }
+    moves
+}
+
+fn rename_note_line(text: &str) -> RtLine<'static> {
+    RtLine::from(vec![
+        RtSpan::raw("    "),
+        RtSpan::styled(text.to_string(), style_dim()),
+    ])
+}
+
+/// Renders one hunk. Hunks whose removed and added lines differ only in
+/// whitespace are dimmed; otherwise equal-sized runs of removed/added lines
+/// are paired up and the changed words within each pair are highlighted.
+fn render_hunk(hunk: &diffy::Hunk<'_, str>, term_cols: usize) -> Vec<RtLine<'static>> {
+    let lines: Vec<(DiffLineType, String)> = hunk
+        .lines()
+        .iter()
+        .map(|line| match line {
+            diffy::Line::Insert(text) => (DiffLineType::Insert, text),
+            diffy::Line::Delete(text) => (DiffLineType::Delete, text),
+            diffy::Line::Context(text) => (DiffLineType::Context, text),
+        })
+        .map(|(kind, text)| (kind, sanitize_diff_text(text.trim_end_matches('\n'))))
+        .collect();
+    let whitespace_only = hunk_is_whitespace_only(&lines);
+
+    let mut decors: Vec<LineDecor> = vec![LineDecor::default(); lines.len()];
+    if whitespace_only {
+        for (decor, (kind, _)) in decors.iter_mut().zip(&lines) {
+            decor.whitespace_only = !matches!(kind, DiffLineType::Context);
+        }
+    } else {
+        let mut idx = 0;
+        while idx < lines.len() {
+            let del_start = idx;
+            while idx < lines.len() && matches!(lines[idx].0, DiffLineType::Delete) {
+                idx += 1;
+            }
+            let ins_start = idx;
+            while idx < lines.len() && matches!(lines[idx].0, DiffLineType::Insert) {
+                idx += 1;
+            }
+            let deletes = ins_start - del_start;
+            if deletes > 0 && deletes == idx - ins_start {
+                for offset in 0..deletes {
+                    let (old_ranges, new_ranges) =
+                        word_diff_ranges(&lines[del_start + offset].1, &lines[ins_start + offset].1);
+                    decors[del_start + offset].highlights = old_ranges;
+                    decors[ins_start + offset].highlights = new_ranges;
+                }
+            }
+            if idx == del_start {
+                idx += 1;
+            }
+        }
+    }
+
+    let mut out: Vec<RtLine<'static>> = Vec::new();
+    let mut old_ln = hunk.old_range().start();
+    let mut new_ln = hunk.new_range().start();
+    for ((kind, text), decor) in lines.into_iter().zip(decors) {
+        let ln = match kind {
+            DiffLineType::Delete => old_ln,
+            DiffLineType::Insert | DiffLineType::Context => new_ln,
+        };
+        match kind {
+            DiffLineType::Insert => new_ln += 1,
+            DiffLineType::Delete => old_ln += 1,
+            DiffLineType::Context => {
+                old_ln += 1;
+                new_ln += 1;
+            }
+        }
+        out.extend(push_decorated_diff_line(ln, kind, &text, term_cols, &decor));
+    }
+    out
+}
+
+fn hunk_is_whitespace_only(lines: &[(DiffLineType, String)]) -> bool {
+    let squash = |kind: fn(&DiffLineType) -> bool| -> String {
+        lines
+            .iter()
+            .filter(|(line_kind, _)| kind(line_kind))
+            .flat_map(|(_, text)| text.chars().filter(|ch| !ch.is_whitespace()))
+            .collect()
+    };
+    let changed = lines
+        .iter()
+        .any(|(kind, _)| !matches!(kind, DiffLineType::Context));
+    changed
+        && squash(|kind| matches!(kind, DiffLineType::Delete))
+            == squash(|kind| matches!(kind, DiffLineType::Insert))
+}
+
+/// Char ranges of the words that differ between a removed line and the added
+/// line that replaced it. Lines that share too little are left unhighlighted
+/// since the whole line changed anyway.
+fn word_diff_ranges(old: &str, new: &str) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+    let diff = similar::TextDiff::from_words(old, new);
+    if diff.ratio() < 0.4 {
+        return (Vec::new(), Vec::new());
+    }
+    let mut old_ranges: Vec<Range<usize>> = Vec::new();
+    let mut new_ranges: Vec<Range<usize>> = Vec::new();
+    let (mut old_pos, mut new_pos) = (0usize, 0usize);
+    let push = |ranges: &mut Vec<Range<usize>>, start: usize, len: usize| {
+        match ranges.last_mut() {
+            Some(last) if last.end == start => last.end += len,
+            _ => ranges.push(start..start + len),
+        }
+    };
+    for change in diff.iter_all_changes() {
+        let len = change.value().chars().count();
+        match change.tag() {
+            similar::ChangeTag::Equal => {
+                old_pos += len;
+                new_pos += len;
+            }
+            similar::ChangeTag::Delete => {
+                push(&mut old_ranges, old_pos, len);
+                old_pos += len;
+            }
+            similar::ChangeTag::Insert => {
+                push(&mut new_ranges, new_pos, len);
+                new_pos += len;
+            }
+        }
+    }
+    (old_ranges, new_ranges)
+}
+
 /// Produce only the detailed diff lines without any file-level headers/summaries.
 /// Used by the Diff Viewer overlay where surrounding chrome already conveys context.
 #[allow(dead_code)]
@@ -673,11 +846,22 @@ fn push_wrapped_diff_line_with_width(
     kind: DiffLineType,
     text: &str,
     term_cols: usize,
+) -> Vec<RtLine<'static>> {
+    push_decorated_diff_line(line_number, kind, text, term_cols, &LineDecor::default())
+}
+
+fn push_decorated_diff_line(
+    line_number: usize,
+    kind: DiffLineType,
+    text: &str,
+    term_cols: usize,
+    decor: &LineDecor,
 ) -> Vec<RtLine<'static>> {
     // Slightly smaller left padding so line numbers sit a couple of spaces left
     let indent = "  ";
     let ln_str = line_number.to_string();
     let mut remaining_text: &str = text;
+    let mut consumed_chars = 0usize;
 
     // Reserve a fixed number of spaces after the line number so that content starts
     // at a consistent column. Always include a 1‑char diff sign ("+"/"-" or space)
@@ -691,10 +875,25 @@ fn push_wrapped_diff_line_with_width(
     // indentation.
     let continuation_indent: usize = text.chars().take_while(|c| *c == ' ').count();
     let (sign_opt, line_style) = match kind {
+        DiffLineType::Insert if decor.whitespace_only => (Some('+'), Some(style_dim())),
+        DiffLineType::Delete if decor.whitespace_only => (Some('-'), Some(style_dim())),
         DiffLineType::Insert => (Some('+'), Some(style_add())),
         DiffLineType::Delete => (Some('-'), Some(style_del())),
         DiffLineType::Context => (None, None),
     };
+    // Whitespace-only lines skip the tinted background so they read as noise.
+    let tint = match kind {
+        _ if decor.whitespace_only => None,
+        DiffLineType::Insert => Some(success_tint()),
+        DiffLineType::Delete => Some(error_tint()),
+        DiffLineType::Context => None,
+    };
+    let highlight_style = match kind {
+        DiffLineType::Insert => style_add().bg(word_highlight(crate::colors::success())),
+        DiffLineType::Delete => style_del().bg(word_highlight(crate::colors::error())),
+        DiffLineType::Context => Style::default(),
+    }
+    .add_modifier(Modifier::BOLD);
     let mut lines: Vec<RtLine<'static>> = Vec::new();
 
     loop {
@@ -717,65 +916,44 @@ fn push_wrapped_diff_line_with_width(
             .unwrap_or_else(|| remaining_text.len());
         let (chunk, rest) = remaining_text.split_at(split_at_byte_index);
         remaining_text = rest;
+        let content_spans =
+            chunk_spans(chunk, consumed_chars, &decor.highlights, line_style, highlight_style);
+        consumed_chars += chunk.chars().count();
 
+        let mut spans: Vec<RtSpan<'static>> = Vec::new();
         if first {
-            let mut spans: Vec<RtSpan<'static>> = Vec::new();
             spans.push(RtSpan::raw(indent));
             spans.push(RtSpan::styled(ln_str.clone(), style_dim()));
             spans.push(RtSpan::raw(" ".repeat(gap_after_ln)));
 
-            // Always prefix the content with a sign char for consistent gutters
-            let sign_char = sign_opt.unwrap_or(' ');
+            // Always prefix the content with a sign char for consistent gutters.
             // Add a space after the sign so it sits centered in the sign column
             // and content starts one cell to the right: "+ <content>".
-            let display_chunk = format!("{sign_char} {chunk}");
-
-            let content_span = match line_style {
-                Some(style) => RtSpan::styled(display_chunk, style),
-                None => RtSpan::raw(display_chunk),
-            };
-            spans.push(content_span);
-            let mut line = RtLine::from(spans);
-            if let Some(style) = line_style {
-                line.style = line.style.patch(style);
-            }
-            // Apply themed tinted background for added/removed lines
-            if matches!(kind, DiffLineType::Insert | DiffLineType::Delete) {
-                let tint = match kind {
-                    DiffLineType::Insert => success_tint(),
-                    DiffLineType::Delete => error_tint(),
-                    DiffLineType::Context => crate::colors::background(),
-                };
-                line.style = line.style.bg(tint);
-            }
-            lines.push(line);
+            let sign = format!("{} ", sign_opt.unwrap_or(' '));
+            spans.push(match line_style {
+                Some(style) => RtSpan::styled(sign, style),
+                None => RtSpan::raw(sign),
+            });
             first = false;
         } else {
             // Continuation lines keep a space for the sign column so content aligns
-            let hang_prefix = format!(
+            spans.push(RtSpan::raw(format!(
                 "{indent}{}{}  {}",
                 " ".repeat(ln_str.len()),
                 " ".repeat(gap_after_ln),
                 " ".repeat(continuation_indent)
-            );
-            let content_span = match line_style {
-                Some(style) => RtSpan::styled(chunk.to_string(), style),
-                None => RtSpan::raw(chunk.to_string()),
-            };
-            let mut line = RtLine::from(vec![RtSpan::raw(hang_prefix), content_span]);
-            if let Some(style) = line_style {
-                line.style = line.style.patch(style);
-            }
-            if matches!(kind, DiffLineType::Insert | DiffLineType::Delete) {
-                let tint = match kind {
-                    DiffLineType::Insert => success_tint(),
-                    DiffLineType::Delete => error_tint(),
-                    DiffLineType::Context => crate::colors::background(),
-                };
-                line.style = line.style.bg(tint);
-            }
-            lines.push(line);
+            )));
         }
+        spans.extend(content_spans);
+        let mut line = RtLine::from(spans);
+        if let Some(style) = line_style {
+            line.style = line.style.patch(style);
+        }
+        // Apply themed tinted background for added/removed lines
+        if let Some(tint) = tint {
+            line.style = line.style.bg(tint);
+        }
+        lines.push(line);
         if remaining_text.is_empty() {
             break;
         }
@@ -783,6 +961,41 @@ fn push_wrapped_diff_line_with_width(
     lines
 }
 
+/// Splits `chunk` (starting at char `start` of the full line) into spans,
+/// applying `highlight` to the parts covered by `highlights`.
+fn chunk_spans(
+    chunk: &str,
+    start: usize,
+    highlights: &[Range<usize>],
+    base: Option<Style>,
+    highlight: Style,
+) -> Vec<RtSpan<'static>> {
+    let plain = |text: String| match base {
+        Some(style) => RtSpan::styled(text, style),
+        None => RtSpan::raw(text),
+    };
+    if highlights.is_empty() {
+        return vec![plain(chunk.to_string())];
+    }
+    let mut spans: Vec<RtSpan<'static>> = Vec::new();
+    let mut current = String::new();
+    let mut current_hl = false;
+    for (offset, ch) in chunk.chars().enumerate() {
+        let pos = start + offset;
+        let hl = highlights.iter().any(|range| range.contains(&pos));
+        if hl != current_hl && !current.is_empty() {
+            let text = std::mem::take(&mut current);
+            spans.push(if current_hl { RtSpan::styled(text, highlight) } else { plain(text) });
+        }
+        current_hl = hl;
+        current.push(ch);
+    }
+    if !current.is_empty() || spans.is_empty() {
+        spans.push(if current_hl { RtSpan::styled(current, highlight) } else { plain(current) });
+    }
+    spans
+}
+
 #[allow(dead_code)]
 fn style_dim() -> Style {
     Style::default().add_modifier(Modifier::DIM)
@@ -811,4 +1024,36 @@ fn error_tint() -> Color {
     crate::colors::tint_background_toward(crate::colors::error())
 }
 
+/// Stronger tint used behind intraline word changes.
+fn word_highlight(accent: Color) -> Color {
+    crate::colors::mix_toward(crate::colors::background(), accent, 0.40)
+}
+
 // Removed per-line tinted backgrounds per design feedback
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_diff_highlights_only_changed_words() {
+        let (old, new) = word_diff_ranges("let total = a + b;", "let total = a - b;");
+        assert_eq!(old, vec![14..15]);
+        assert_eq!(new, vec![14..15]);
+    }
+
+    #[test]
+    fn reindented_hunk_is_whitespace_only() {
+        let lines = vec![
+            (DiffLineType::Delete, "if x {".to_string()),
+            (DiffLineType::Insert, "    if x {".to_string()),
+            (DiffLineType::Context, "}".to_string()),
+        ];
+        assert!(hunk_is_whitespace_only(&lines));
+        let changed = vec![
+            (DiffLineType::Delete, "if x {".to_string()),
+            (DiffLineType::Insert, "if y {".to_string()),
+        ];
+        assert!(!hunk_is_whitespace_only(&changed));
+    }
+}
@@ -0,0 +1,128 @@
+//! Which parts of the chat frame changed since the last draw.
+//!
+//! Redraw requests may name the region they touched: the composer spinner
+//! only dirties the bottom pane, a streaming delta only dirties history. When
+//! every request since the previous frame was narrow, `ChatWidget` starts
+//! from the previously composed buffer and re-renders just those regions, so
+//! a keystroke or spinner tick no longer lays out the whole history. Requests
+//! that do not name a region repaint everything.
+
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Regions(u8);
+
+impl Regions {
+    pub(crate) const STATUS_BAR: Self = Self(1);
+    pub(crate) const HISTORY: Self = Self(1 << 1);
+    pub(crate) const BOTTOM_PANE: Self = Self(1 << 2);
+    pub(crate) const FULL: Self = Self(0b111);
+
+    pub(crate) fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub(crate) fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub(crate) fn is_full(self) -> bool {
+        self == Self::FULL
+    }
+
+    /// Short label for the debug HUD, e.g. `history+bottom`.
+    pub(crate) fn label(self) -> String {
+        if self.is_full() {
+            return "full".to_string();
+        }
+        let names = [
+            (Self::STATUS_BAR, "status"),
+            (Self::HISTORY, "history"),
+            (Self::BOTTOM_PANE, "bottom"),
+        ];
+        let parts: Vec<&str> = names
+            .into_iter()
+            .filter(|(region, _)| self.contains(*region))
+            .map(|(_, name)| name)
+            .collect();
+        if parts.is_empty() {
+            "none".to_string()
+        } else {
+            parts.join("+")
+        }
+    }
+}
+
+/// Damage collected for one frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct FrameDamage {
+    pub(crate) regions: Regions,
+    /// The request that last widened the damage.
+    pub(crate) cause: &'static str,
+}
+
+impl FrameDamage {
+    pub(crate) fn full(cause: &'static str) -> Self {
+        Self {
+            regions: Regions::FULL,
+            cause,
+        }
+    }
+}
+
+/// Damage accumulated between frames. Shared by every clone of an
+/// `AppEventSender`, so any component can mark what it changed.
+#[derive(Debug, Default)]
+pub(crate) struct DirtyRegions {
+    pending: AtomicU8,
+    cause: Mutex<&'static str>,
+}
+
+impl DirtyRegions {
+    pub(crate) fn mark(&self, regions: Regions, cause: &'static str) {
+        let previous = self.pending.fetch_or(regions.0, Ordering::AcqRel);
+        let widened = previous | regions.0 != previous;
+        if widened && let Ok(mut last) = self.cause.lock() {
+            *last = cause;
+        }
+    }
+
+    /// Returns and clears the pending damage. A frame nobody asked for (a
+    /// timer, a resize) repaints everything.
+    pub(crate) fn take(&self) -> FrameDamage {
+        let bits = self.pending.swap(0, Ordering::AcqRel);
+        let cause = self.cause.lock().map(|cause| *cause).unwrap_or("redraw");
+        if bits == 0 {
+            return FrameDamage::full("timer");
+        }
+        FrameDamage {
+            regions: Regions(bits),
+            cause,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn narrow_marks_accumulate_until_taken() {
+        let dirty = DirtyRegions::default();
+        dirty.mark(Regions::BOTTOM_PANE, "spinner");
+        dirty.mark(Regions::BOTTOM_PANE, "composer");
+        dirty.mark(Regions::HISTORY, "stream");
+        dirty.mark(Regions::BOTTOM_PANE, "spinner");
+        assert_eq!(
+            dirty.take(),
+            FrameDamage {
+                regions: Regions::HISTORY.union(Regions::BOTTOM_PANE),
+                cause: "stream",
+            }
+        );
+        assert_eq!(dirty.take(), FrameDamage::full("timer"));
+    }
+}
@@ -1,6 +1,7 @@
 use std::env;
 use std::fs;
 use std::io::Write;
+use std::path::Path;
 use std::process::Command;
 
 use tempfile::Builder;
@@ -20,8 +21,13 @@ pub(crate) enum ExternalEditorError {
     NonZeroExit(String),
     #[error("Failed to read edited content: {0}")]
     ReadFailed(String),
+    #[error("Failed to launch pager: {0}. Set $PAGER to choose another.")]
+    PagerLaunchFailed(String),
 }
 
+/// Default pager; `-R` keeps colored command output readable.
+const DEFAULT_PAGER: &str = "less -R";
+
 pub(crate) fn run_editor(initial: &str) -> Result<String, ExternalEditorError> {
     let command = resolve_editor_command()?;
     let (program, args) = command
@@ -71,6 +77,26 @@ pub(crate) fn run_editor(initial: &str) -> Result<String, ExternalEditorError> {
         .map_err(|e| ExternalEditorError::ReadFailed(e.to_string()))
 }
 
+/// Shows `path` in `$PAGER` and waits for it to exit. The pager reads the
+/// file itself, so large files are never loaded into memory here.
+pub(crate) fn run_pager(path: &Path) -> Result<(), ExternalEditorError> {
+    let raw = env::var("PAGER")
+        .ok()
+        .filter(|val| !val.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    let command = parse_editor_command(&raw)
+        .map_err(|e| ExternalEditorError::PagerLaunchFailed(e.to_string()))?;
+    let (program, args) = command
+        .split_first()
+        .ok_or(ExternalEditorError::EmptyCommand)?;
+    Command::new(program)
+        .args(args)
+        .arg(path)
+        .status()
+        .map_err(|e| ExternalEditorError::PagerLaunchFailed(format!("{program}: {e}")))?;
+    Ok(())
+}
+
 fn resolve_editor_command() -> Result<Vec<String>, ExternalEditorError> {
     let visual = env::var("VISUAL").ok().filter(|val| !val.trim().is_empty());
     let editor = env::var("EDITOR").ok().filter(|val| !val.trim().is_empty());
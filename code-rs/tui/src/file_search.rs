@@ -20,6 +20,7 @@
 
 use code_file_search as file_search;
 use std::num::NonZeroUsize;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -47,6 +48,9 @@ pub(crate) struct FileSearchManager {
     state: Arc<Mutex<SearchState>>,
 
     search_dir: PathBuf,
+    /// Set when searching a `/scope` subtree: its path relative to the
+    /// session directory, prepended to every match.
+    path_prefix: Option<String>,
     app_tx: AppEventSender,
 }
 
@@ -75,10 +79,24 @@ impl FileSearchManager {
                 active_search: None,
             })),
             search_dir,
+            path_prefix: None,
             app_tx: tx,
         }
     }
 
+    /// Searches only `scope`, a directory inside `root`; matches stay
+    /// relative to `root` so mentions resolve the same way.
+    pub fn new_scoped(root: &Path, scope: &Path, tx: AppEventSender) -> Self {
+        let mut manager = Self::new(scope.to_path_buf(), tx);
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        manager.path_prefix = scope
+            .strip_prefix(&root)
+            .ok()
+            .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+            .filter(|relative| !relative.is_empty());
+        manager
+    }
+
     /// Call whenever the user edits the `@` token.
     pub fn on_user_query(&self, query: String) {
         {
@@ -116,6 +134,7 @@ impl FileSearchManager {
         // debounce timer.
         let state = self.state.clone();
         let search_dir = self.search_dir.clone();
+        let path_prefix = self.path_prefix.clone();
         let tx_clone = self.app_tx.clone();
         if let Err(err) = thread::Builder::new()
             .name("file-search-debounce".to_string())
@@ -150,6 +169,7 @@ impl FileSearchManager {
             FileSearchManager::spawn_file_search(
                 query,
                 search_dir,
+                path_prefix,
                 tx_clone,
                 cancellation_token,
                 state,
@@ -163,6 +183,7 @@ impl FileSearchManager {
     fn spawn_file_search(
         query: String,
         search_dir: PathBuf,
+        path_prefix: Option<String>,
         tx: AppEventSender,
         cancellation_token: Arc<AtomicBool>,
         search_state: Arc<Mutex<SearchState>>,
@@ -187,6 +208,13 @@ impl FileSearchManager {
                     if rx_cancel.load(Ordering::Relaxed) {
                         break;
                     }
+                    let matches = match path_prefix.as_deref() {
+                        Some(prefix) => matches
+                            .into_iter()
+                            .map(|file_match| prefixed_match(file_match, prefix))
+                            .collect(),
+                        None => matches,
+                    };
                     rx_tx.send(AppEvent::FileSearchResult { query: rx_query.clone(), matches });
                 }
                 })
@@ -232,3 +260,16 @@ impl FileSearchManager {
         }
     }
 }
+
+/// `file_match` with `prefix/` in front of its path and its highlighted
+/// character indices shifted to match.
+fn prefixed_match(file_match: file_search::FileMatch, prefix: &str) -> file_search::FileMatch {
+    let shift = prefix.chars().count() as u32 + 1;
+    file_search::FileMatch {
+        score: file_match.score,
+        path: format!("{prefix}/{}", file_match.path),
+        indices: file_match
+            .indices
+            .map(|indices| indices.into_iter().map(|index| index + shift).collect()),
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! The implementation mirrors the behaviour of the TypeScript version in
 //! `codex-cli`: it returns the diff for tracked changes as well as any
-//! untracked files. When the current directory is not inside a Git
+//! untracked files. When the session directory is not inside a Git
 //! repository, the function returns `Ok((false, String::new()))`.
 
 use std::io;
@@ -12,18 +12,27 @@ use tokio::process::Command;
 
 /// Return value of [`get_git_diff`].
 ///
-/// * `bool` – Whether the current working directory is inside a Git repo.
+/// * `bool` – Whether `cwd` is inside a Git repo.
 /// * `String` – The concatenated diff (may be empty).
-pub(crate) async fn get_git_diff() -> io::Result<(bool, String)> {
+///
+/// With a `/scope` subtree only changes under `scope` are included.
+pub(crate) async fn get_git_diff(cwd: &Path, scope: Option<&Path>) -> io::Result<(bool, String)> {
     // First check if we are inside a Git repository.
-    if !inside_git_repo().await? {
+    if !inside_git_repo(cwd).await? {
         return Ok((false, String::new()));
     }
 
+    let pathspec = scope.map(|scope| scope.to_string_lossy().into_owned());
+    let mut diff_args = vec!["diff", "--color"];
+    let mut untracked_args = vec!["ls-files", "--others", "--exclude-standard"];
+    if let Some(pathspec) = pathspec.as_deref() {
+        diff_args.extend(["--", pathspec]);
+        untracked_args.extend(["--", pathspec]);
+    }
     // Run tracked diff and untracked file listing in parallel.
     let (tracked_diff_res, untracked_output_res) = tokio::join!(
-        run_git_capture_diff(&["diff", "--color"]),
-        run_git_capture_stdout(&["ls-files", "--others", "--exclude-standard"]),
+        run_git_capture_diff(cwd, &diff_args),
+        run_git_capture_stdout(cwd, &untracked_args),
     );
     let tracked_diff = tracked_diff_res?;
     let untracked_output = untracked_output_res?;
@@ -44,9 +53,10 @@ pub(crate) async fn get_git_diff() -> io::Result<(bool, String)> {
     {
         let null_path = null_path.clone();
         let file = file.to_string();
+        let cwd = cwd.to_path_buf();
         join_set.spawn(async move {
             let args = ["diff", "--color", "--no-index", "--", &null_path, &file];
-            run_git_capture_diff(&args).await
+            run_git_capture_diff(&cwd, &args).await
         });
     }
     while let Some(res) = join_set.join_next().await {
@@ -63,8 +73,9 @@ pub(crate) async fn get_git_diff() -> io::Result<(bool, String)> {
 
 /// Helper that executes `git` with the given `args` and returns `stdout` as a
 /// UTF-8 string. Any non-zero exit status is considered an *error*.
-async fn run_git_capture_stdout(args: &[&str]) -> io::Result<String> {
+async fn run_git_capture_stdout(cwd: &Path, args: &[&str]) -> io::Result<String> {
     let output = Command::new("git")
+        .current_dir(cwd)
         .args(args)
         .stdout(Stdio::piped())
         .stderr(Stdio::null())
@@ -83,8 +94,9 @@ async fn run_git_capture_stdout(args: &[&str]) -> io::Result<String> {
 
 /// Like [`run_git_capture_stdout`] but treats exit status 1 as success and
 /// returns stdout. Git returns 1 for diffs when differences are present.
-async fn run_git_capture_diff(args: &[&str]) -> io::Result<String> {
+async fn run_git_capture_diff(cwd: &Path, args: &[&str]) -> io::Result<String> {
     let output = Command::new("git")
+        .current_dir(cwd)
         .args(args)
         .stdout(Stdio::piped())
         .stderr(Stdio::null())
@@ -101,9 +113,10 @@ async fn run_git_capture_diff(args: &[&str]) -> io::Result<String> {
     }
 }
 
-/// Determine if the current directory is inside a Git repository.
-async fn inside_git_repo() -> io::Result<bool> {
+/// Determine if `cwd` is inside a Git repository.
+async fn inside_git_repo(cwd: &Path) -> io::Result<bool> {
     let status = Command::new("git")
+        .current_dir(cwd)
         .args(["rev-parse", "--is-inside-work-tree"])
         .stdout(Stdio::null())
         .stderr(Stdio::null())
@@ -27,6 +27,7 @@ impl HeaderWaveEffect {
     }
 
     pub fn set_enabled(&self, enabled: bool, now: Instant) {
+        let enabled = enabled && !crate::accessibility::enabled();
         self.enabled.set(enabled);
         if enabled {
             self.started_at.set(Some(now));
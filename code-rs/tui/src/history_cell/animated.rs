@@ -50,6 +50,11 @@ impl AnimatedWelcomeCell {
     }
 
     pub(crate) fn begin_fade(&self) {
+        if crate::accessibility::enabled() {
+            // No fade in accessibility mode: the welcome text just goes.
+            self.faded_out.set(true);
+            return;
+        }
         self.set_fade_start();
     }
 
@@ -90,6 +95,9 @@ impl HistoryCell for AnimatedWelcomeCell {
     }
 
     fn desired_height(&self, width: u16) -> u16 {
+        if crate::accessibility::enabled() {
+            return self.display_lines().len() as u16;
+        }
         let variant = if let Some(available_height) = self.available_height.get() {
             crate::glitch_animation::intro_art_size_for_area(
                 width,
@@ -110,7 +118,8 @@ impl HistoryCell for AnimatedWelcomeCell {
     }
 
     fn has_custom_render(&self) -> bool {
-        true
+        // Accessibility mode shows the welcome text instead of the intro art.
+        !crate::accessibility::enabled()
     }
 
     fn custom_render(&self, area: Rect, buf: &mut Buffer) {
@@ -227,6 +236,9 @@ impl HistoryCell for AnimatedWelcomeCell {
     }
 
     fn is_animating(&self) -> bool {
+        if crate::accessibility::enabled() {
+            return false;
+        }
         let animation_duration = Duration::from_secs(2);
         if !self.completed.get() {
             if self.start_time.elapsed() < animation_duration {
@@ -0,0 +1,98 @@
+use super::*;
+use code_core::artifacts::ArtifactInfo;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use std::path::PathBuf;
+
+/// The session's artifacts folder: every file the agent saved for the user,
+/// with its size. The most recently saved artifact is highlighted.
+pub(crate) struct ArtifactsCell {
+    dir: PathBuf,
+    artifacts: Vec<ArtifactInfo>,
+    highlight: Option<String>,
+}
+
+impl ArtifactsCell {
+    pub(crate) fn new(dir: PathBuf, artifacts: Vec<ArtifactInfo>, highlight: Option<String>) -> Self {
+        Self {
+            dir,
+            artifacts,
+            highlight,
+        }
+    }
+}
+
+impl HistoryCell for ArtifactsCell {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn kind(&self) -> HistoryCellType {
+        HistoryCellType::Plain
+    }
+
+    fn display_lines(&self) -> Vec<Line<'static>> {
+        let dim = Style::default().fg(crate::colors::text_dim());
+        let total: u64 = self.artifacts.iter().map(|artifact| artifact.bytes).sum();
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(
+                    format!("Artifacts ({})", self.artifacts.len()),
+                    Style::default()
+                        .fg(crate::colors::text())
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("  {}", format_size(total)), dim),
+            ]),
+            Line::from(Span::styled(self.dir.display().to_string(), dim)),
+        ];
+
+        if self.artifacts.is_empty() {
+            lines.push(Line::from(Span::styled("No artifacts saved in this session yet.", dim)));
+        }
+        for artifact in &self.artifacts {
+            let highlighted = self.highlight.as_deref() == Some(artifact.name.as_str());
+            let mut name_style = Style::default().fg(crate::colors::primary());
+            if highlighted {
+                name_style = name_style.add_modifier(Modifier::BOLD);
+            }
+            let mut row = vec![
+                Span::styled(artifact.name.clone(), name_style),
+                Span::styled(format!("  {}", format_size(artifact.bytes)), dim),
+            ];
+            if highlighted {
+                row.push(Span::styled(
+                    "  saved",
+                    Style::default().fg(crate::colors::success()),
+                ));
+            }
+            lines.push(Line::from(row));
+        }
+        lines.push(Line::from(Span::styled(
+            "/artifacts open to reveal the folder · /artifacts copy <name> to copy a path",
+            dim,
+        )));
+        lines.push(Line::from(""));
+        lines
+    }
+
+    fn gutter_symbol(&self) -> Option<&'static str> {
+        Some("↓")
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    let value = bytes as f64;
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else if value < KIB * KIB {
+        format!("{:.1} KiB", value / KIB)
+    } else {
+        format!("{:.1} MiB", value / (KIB * KIB))
+    }
+}
@@ -49,6 +49,11 @@ impl AssistantMarkdownCell {
         self.layout_cache.borrow_mut().clear();
     }
 
+    /// Drops cached layouts, e.g. after the table column scroll moved.
+    pub(crate) fn invalidate_layout(&self) {
+        self.layout_cache.borrow_mut().clear();
+    }
+
     pub(crate) fn stream_id(&self) -> Option<&str> {
         self.state.stream_id.as_deref()
     }
@@ -324,8 +329,9 @@ pub(crate) fn assistant_markdown_lines_with_context(
 ) -> Vec<Line<'static>> {
     let mut out: Vec<Line<'static>> = Vec::new();
     out.push(Line::from("codex"));
+    let markdown = crate::markdown_links::collapse_links(&state.markdown);
     crate::markdown::append_markdown_with_opener_and_cwd_and_bold(
-        &state.markdown,
+        &markdown,
         &mut out,
         file_opener,
         cwd,
@@ -342,6 +348,81 @@ pub(crate) fn assistant_markdown_lines_with_context(
     out.into_iter().skip(1).collect()
 }
 
+/// Marks where a table goes in [`assistant_layout_lines`]; the table itself
+/// is laid out for the cell width.
+const TABLE_SENTINEL: &str = "⟦TABLE:";
+
+/// Like [`assistant_markdown_lines_with_context`], but each table is replaced
+/// by a sentinel line indexing into the returned tables.
+fn assistant_layout_lines(
+    state: &AssistantMessageState,
+    file_opener: UriBasedFileOpener,
+    cwd: &Path,
+) -> (Vec<Line<'static>>, Vec<crate::markdown_table::MarkdownTable>) {
+    use crate::markdown_table::MarkdownPiece;
+
+    let markdown = crate::markdown_links::collapse_links(&state.markdown);
+    let source = crate::markdown_math::rewrite_math(&markdown);
+    let pieces = crate::markdown_table::split_tables(&source);
+    if !pieces
+        .iter()
+        .any(|piece| matches!(piece, MarkdownPiece::Table(_)))
+    {
+        return (
+            assistant_markdown_lines_with_context(state, file_opener, cwd),
+            Vec::new(),
+        );
+    }
+
+    let mut out: Vec<Line<'static>> = Vec::new();
+    let mut tables = Vec::new();
+    let mut text_bolded = false;
+    for piece in pieces {
+        match piece {
+            MarkdownPiece::Markdown(text) => {
+                text_bolded = crate::markdown::append_markdown_continuing(
+                    &text,
+                    &mut out,
+                    file_opener,
+                    cwd,
+                    !state.mid_turn,
+                    text_bolded,
+                );
+            }
+            MarkdownPiece::Table(table) => {
+                out.push(Line::from(format!("{TABLE_SENTINEL}{}⟧", tables.len())));
+                tables.push(table);
+            }
+        }
+    }
+    let fg = if state.mid_turn {
+        crate::colors::text_mid()
+    } else {
+        crate::colors::text_bright()
+    };
+    for line in &mut out {
+        line.style = line.style.patch(Style::default().fg(fg));
+    }
+    (out, tables)
+}
+
+/// The table a sentinel line from [`assistant_layout_lines`] stands for.
+fn sentinel_table<'a>(
+    line: &Line<'_>,
+    tables: &'a [crate::markdown_table::MarkdownTable],
+) -> Option<&'a crate::markdown_table::MarkdownTable> {
+    let [span] = line.spans.as_slice() else {
+        return None;
+    };
+    let idx = span
+        .content
+        .strip_prefix(TABLE_SENTINEL)?
+        .strip_suffix('⟧')?
+        .parse::<usize>()
+        .ok()?;
+    tables.get(idx)
+}
+
 pub(crate) fn compute_assistant_layout(
     state: &AssistantMessageState,
     cfg: &Config,
@@ -359,9 +440,8 @@ pub(crate) fn compute_assistant_layout_with_context(
     let text_wrap_width = width;
     let mut segs: Vec<AssistantSeg> = Vec::new();
     let mut text_buf: Vec<Line<'static>> = Vec::new();
-    let mut iter = super::trim_empty_lines(assistant_markdown_lines_with_context(state, file_opener, cwd))
-        .into_iter()
-        .peekable();
+    let (lines, tables) = assistant_layout_lines(state, file_opener, cwd);
+    let mut iter = super::trim_empty_lines(lines).into_iter().peekable();
     let measure_line = |line: &Line<'_>| -> u16 {
         line.spans
             .iter()
@@ -446,6 +526,23 @@ pub(crate) fn compute_assistant_layout_with_context(
             continue;
         }
 
+        if let Some(table) = sentinel_table(&line, &tables) {
+            if !text_buf.is_empty() {
+                let wrapped = word_wrap_lines(&text_buf, text_wrap_width);
+                segs.push(AssistantSeg::Text(wrapped));
+                text_buf.clear();
+            }
+            let mut table_lines = table.layout(
+                usize::from(text_wrap_width),
+                crate::markdown_table::column_offset(),
+            );
+            for table_line in &mut table_lines {
+                table_line.style = line.style.patch(table_line.style);
+            }
+            segs.push(AssistantSeg::Bullet(table_lines));
+            continue;
+        }
+
         if text_wrap_width > 4 && is_horizontal_rule_line(&line) {
             if !text_buf.is_empty() {
                 let wrapped = word_wrap_lines(&text_buf, text_wrap_width);
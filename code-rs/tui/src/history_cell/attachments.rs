@@ -0,0 +1,123 @@
+use super::*;
+use crate::chatwidget::message::AttachmentBundle;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use std::cell::Cell;
+
+/// Files sent with a message for one `@dir/` or glob mention. Collapsed to its
+/// header by default; clicking the header lists every file and how much of it
+/// went to the model.
+pub(crate) struct AttachmentsCell {
+    bundle: AttachmentBundle,
+    collapsed: Cell<bool>,
+}
+
+impl AttachmentsCell {
+    pub(crate) fn new(bundle: AttachmentBundle) -> Self {
+        Self {
+            bundle,
+            collapsed: Cell::new(true),
+        }
+    }
+
+    pub(crate) fn toggle_collapsed(&self) {
+        self.collapsed.set(!self.collapsed.get());
+    }
+}
+
+impl HistoryCell for AttachmentsCell {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn kind(&self) -> HistoryCellType {
+        HistoryCellType::Plain
+    }
+
+    fn display_lines(&self) -> Vec<Line<'static>> {
+        let dim = Style::default().fg(crate::colors::text_dim());
+        let count = self.bundle.files.len();
+        let sent: usize = self.bundle.files.iter().map(|file| file.sent_bytes).sum();
+        let mut header = vec![
+            Span::styled(
+                format!("Attached {}", self.bundle.pattern),
+                Style::default()
+                    .fg(crate::colors::text())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    "  {count} file{} · {}",
+                    if count == 1 { "" } else { "s" },
+                    format_bytes(sent)
+                ),
+                dim,
+            ),
+        ];
+        if self.bundle.omitted > 0 {
+            header.push(Span::styled(
+                format!(" · {} omitted", self.bundle.omitted),
+                Style::default().fg(crate::colors::warning()),
+            ));
+        }
+        if self.bundle.redacted > 0 {
+            header.push(Span::styled(
+                format!(" · {}", redacted_values_label(self.bundle.redacted)),
+                dim,
+            ));
+        }
+        let hint = if self.collapsed.get() {
+            "  click to expand"
+        } else {
+            "  click to collapse"
+        };
+        header.push(Span::styled(hint, dim));
+        let mut lines = vec![Line::from(header)];
+
+        if !self.collapsed.get() {
+            for file in &self.bundle.files {
+                let mut row = vec![
+                    Span::styled(
+                        file.path.clone(),
+                        Style::default().fg(crate::colors::primary()),
+                    ),
+                    Span::styled(format!("  {}", format_bytes(file.bytes)), dim),
+                ];
+                if file.sent_bytes < file.bytes {
+                    row.push(Span::styled(
+                        format!(" (truncated to {})", format_bytes(file.sent_bytes)),
+                        Style::default().fg(crate::colors::warning()),
+                    ));
+                }
+                lines.push(Line::from(row));
+            }
+            if self.bundle.omitted > 0 {
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "{} more matching files omitted to stay within the attachment budget",
+                        self.bundle.omitted
+                    ),
+                    dim,
+                )));
+            }
+        }
+        lines.push(Line::from(""));
+        lines
+    }
+
+    fn gutter_symbol(&self) -> Option<&'static str> {
+        Some("@")
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    }
+}
@@ -736,6 +736,9 @@ impl AutoDriveCardCell {
     }
 
     fn celebration_reduced_motion() -> bool {
+        if crate::accessibility::enabled() {
+            return true;
+        }
         match env::var("CODE_TUI_REDUCED_MOTION") {
             Ok(value) => {
                 let normalized = value.trim().to_ascii_lowercase();
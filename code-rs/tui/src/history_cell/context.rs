@@ -1,6 +1,7 @@
 use super::*;
 use crate::history::compat::{
     ContextBrowserSnapshotRecord,
+    ContextBrowserTabRecord,
     ContextDeltaField,
     ContextDeltaRecord,
 };
@@ -106,8 +107,50 @@ fn build_lines(record: &ContextRecord) -> Vec<Line<'static>> {
         lines.push(Line::from(meta_spans));
     }
 
+    if !record.providers.is_empty() {
+        let mut spans = vec![Span::styled("🧰 ", accent)];
+        for (idx, (name, value)) in record.providers.iter().enumerate() {
+            if idx > 0 {
+                spans.push(Span::raw("  "));
+            }
+            spans.push(Span::styled(format!("{} ", provider_label(name)), dim));
+            spans.push(Span::styled(value.clone(), primary));
+        }
+        lines.push(Line::from(spans));
+    }
+
     if let Some(snapshot) = &record.browser_snapshot {
         lines.push(build_browser_line(snapshot, primary, dim, accent));
+        if record.expanded {
+            for tab in &snapshot.tabs {
+                lines.push(build_tab_line(tab, primary, dim));
+            }
+        }
+    }
+
+    if !record.pinned.is_empty() {
+        if record.expanded {
+            lines.push(Line::from(vec![Span::styled("Pinned:", accent)]));
+            for (key, value) in &record.pinned {
+                lines.push(Line::from(vec![
+                    Span::styled("• ", dim),
+                    Span::styled(key.clone(), primary),
+                    Span::styled(" = ", dim),
+                    Span::styled(value.clone(), primary),
+                ]));
+            }
+        } else {
+            let facts = record
+                .pinned
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<_>>()
+                .join("  ");
+            lines.push(Line::from(vec![
+                Span::styled("📌 ", accent),
+                Span::styled(facts, primary),
+            ]));
+        }
     }
 
     if record.expanded && !record.deltas.is_empty() {
@@ -139,6 +182,14 @@ fn build_browser_line(
         spans.push(Span::styled("Browser snapshot", primary));
     }
 
+    if let Some(active) = snapshot.tabs.iter().find(|tab| tab.active) {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            format!("tab {}/{}", active.index, snapshot.tabs.len()),
+            dim,
+        ));
+    }
+
     if snapshot.width.zip(snapshot.height).is_some() {
         spans.push(Span::raw("  "));
         spans.push(Span::styled(
@@ -171,13 +222,47 @@ fn build_browser_line(
     Line::from(spans)
 }
 
-fn build_delta_line(delta: &ContextDeltaRecord, primary: Style, dim: Style) -> Line<'static> {
-    let field = match delta.field {
+fn build_tab_line(tab: &ContextBrowserTabRecord, primary: Style, dim: Style) -> Line<'static> {
+    let marker = if tab.active { "▸ " } else { "  " };
+    let style = if tab.active { primary } else { dim };
+    let mut spans = vec![
+        Span::styled(format!("{marker}{}. ", tab.index), dim),
+        Span::styled(
+            tab.title
+                .clone()
+                .filter(|title| !title.is_empty())
+                .unwrap_or_else(|| tab.url.clone()),
+            style,
+        ),
+    ];
+    if tab.title.as_ref().is_some_and(|title| !title.is_empty()) {
+        spans.push(Span::styled(format!("  {}", tab.url), dim));
+    }
+    Line::from(spans)
+}
+
+/// Short label for an environment provider fact.
+fn provider_label(name: &str) -> &str {
+    match name {
+        "docker_compose" => "compose",
+        "virtualenv" => "venv",
+        other => other,
+    }
+}
+
+/// How a changed field is named in the context cell and `/context history`.
+pub(crate) fn context_delta_label(field: &ContextDeltaField) -> &str {
+    match field {
         ContextDeltaField::Cwd => "cwd",
         ContextDeltaField::GitBranch => "branch",
         ContextDeltaField::ReasoningEffort => "reasoning",
         ContextDeltaField::BrowserSnapshot => "browser",
-    };
+        ContextDeltaField::Environment(name) => provider_label(name),
+    }
+}
+
+fn build_delta_line(delta: &ContextDeltaRecord, primary: Style, dim: Style) -> Line<'static> {
+    let field = context_delta_label(&delta.field);
 
     let mut spans = Vec::new();
     spans.push(Span::styled("• ", dim));
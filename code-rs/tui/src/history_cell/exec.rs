@@ -30,6 +30,7 @@ use super::{
     format_inline_script_for_display,
     insert_line_breaks_after_double_ampersand,
     normalize_shell_command_display,
+    redacted_values_label,
     running_status_line,
     trim_empty_lines,
     exec_render_parts_parsed,
@@ -123,7 +124,7 @@ fn render_exec_stream(chunks: &[ExecStreamChunk], stream_name: &str) -> String {
         && first.offset > 0 {
             let mut notice = String::new();
             notice.push_str(&format!(
-                "… clipped {} from the start of {} (showing last {}).\n\n",
+                "… clipped {} from the start of {} (showing last {}; `/output` pages the full output).\n\n",
                 code_core::util::format_bytes(first.offset),
                 stream_name,
                 code_core::util::format_bytes(MAX_EXEC_STREAM_RETAINED_BYTES),
@@ -640,6 +641,9 @@ impl ExecCell {
                 out.splice(insert_at..insert_at, block);
             }
 
+            if let Some(line) = redaction_line(self.record.redacted_values) {
+                out.push(line);
+            }
         }
 
         (pre, out, if self.output.is_none() { status } else { None })
@@ -791,6 +795,15 @@ impl ExecCell {
     }
 }
 
+fn redaction_line(count: usize) -> Option<Line<'static>> {
+    (count > 0).then(|| {
+        Line::from(Span::styled(
+            redacted_values_label(count),
+            Style::default().fg(crate::colors::text_dim()),
+        ))
+    })
+}
+
 fn chunk_from_text(text: &str) -> Vec<ExecStreamChunk> {
     if text.is_empty() {
         Vec::new()
@@ -835,6 +848,7 @@ fn build_exec_record(
                 working_dir: None,
                 env: Vec::new(),
                 tags: Vec::new(),
+                redacted_values: 0,
             }
         }
         None => ExecRecord {
@@ -855,6 +869,7 @@ fn build_exec_record(
             working_dir: None,
             env: Vec::new(),
             tags: Vec::new(),
+            redacted_values: 0,
         },
     }
 }
@@ -4,7 +4,6 @@ use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{Block, Borders, Padding, Paragraph, Widget, Wrap};
 
 use crate::history::compat::{
-    ExecAction,
     ExecRecord,
     ExecStatus,
     HistoryId,
@@ -69,18 +68,6 @@ impl MergedExecCell {
         }
     }
 
-    pub(crate) fn from_records(
-        history_id: HistoryId,
-        action: ExecAction,
-        segments: Vec<ExecRecord>,
-    ) -> Self {
-        Self {
-            segments: segments.into_iter().map(MergedExecSegment::new).collect(),
-            kind: action.into(),
-            history_id,
-        }
-    }
-
     pub(crate) fn from_state(record: MergedExecRecord) -> Self {
         let history_id = record.id;
         let kind: ExecKind = record.action.into();
@@ -387,6 +387,16 @@ pub(crate) fn output_lines(
     lines
 }
 
+/// "N values redacted", for cells whose content had secrets masked by
+/// `[redaction]`.
+pub(crate) fn redacted_values_label(count: usize) -> String {
+    if count == 1 {
+        "1 value redacted".to_string()
+    } else {
+        format!("{count} values redacted")
+    }
+}
+
 pub(crate) fn pretty_provider_name(id: &str) -> String {
     // Special case common providers with human-friendly names
     match id {
@@ -13,6 +13,9 @@ pub(crate) struct FrozenHistoryCell {
     kind: HistoryCellType,
     cached_width: u16,
     cached_height: u16,
+    /// False for placeholders created during lazy hydration whose height is
+    /// only an estimate until the cell is built and laid out once.
+    measured: bool,
 }
 
 impl FrozenHistoryCell {
@@ -27,9 +30,25 @@ impl FrozenHistoryCell {
             kind,
             cached_width,
             cached_height,
+            measured: true,
         }
     }
 
+    /// Placeholder for a record that has never been materialized.
+    pub(crate) fn unmeasured(history_id: HistoryId, kind: HistoryCellType) -> Self {
+        Self {
+            history_id,
+            kind,
+            cached_width: 0,
+            cached_height: 1,
+            measured: false,
+        }
+    }
+
+    pub(crate) fn is_measured(&self) -> bool {
+        self.measured
+    }
+
     pub(crate) fn history_id(&self) -> HistoryId {
         self.history_id
     }
@@ -45,6 +64,12 @@ impl FrozenHistoryCell {
     pub(crate) fn update_cached_height(&mut self, width: u16, height: u16) {
         self.cached_width = width;
         self.cached_height = height;
+        self.measured = true;
+    }
+
+    pub(crate) fn set_estimated_height(&mut self, width: u16, height: u16) {
+        self.cached_width = width;
+        self.cached_height = height;
     }
 }
 
@@ -0,0 +1,151 @@
+use super::*;
+use code_core::protocol::DiagnosticSeverity;
+use code_core::protocol::LspDiagnosticsEvent;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use std::path::Path;
+
+/// Diagnostics listed per file before the rest are counted.
+const MAX_DIAGNOSTICS_PER_FILE: usize = 8;
+
+/// Language server diagnostics after a patch: a summary, then each file's
+/// problems, errors first, with the ones the patch introduced marked new.
+pub(crate) struct LspDiagnosticsCell {
+    lines: Vec<Line<'static>>,
+}
+
+impl LspDiagnosticsCell {
+    pub(crate) fn new(event: &LspDiagnosticsEvent, cwd: &Path) -> Self {
+        Self {
+            lines: render_lines(event, cwd),
+        }
+    }
+}
+
+fn render_lines(event: &LspDiagnosticsEvent, cwd: &Path) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(crate::colors::text_dim());
+    let count = |severity: DiagnosticSeverity| {
+        event
+            .files
+            .iter()
+            .flat_map(|file| &file.diagnostics)
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    };
+    let errors = count(DiagnosticSeverity::Error);
+    let warnings = count(DiagnosticSeverity::Warning);
+    let new_errors = event.new_errors().count();
+    let files = event.files.len();
+
+    let mut summary = Vec::new();
+    if errors > 0 {
+        let new = if new_errors > 0 {
+            format!(" ({new_errors} new)")
+        } else {
+            String::new()
+        };
+        summary.push(format!("{errors} error{}{new}", plural(errors)));
+    }
+    if warnings > 0 {
+        summary.push(format!("{warnings} warning{}", plural(warnings)));
+    }
+    let (symbol, color) = if new_errors > 0 {
+        ("✗", crate::colors::error())
+    } else if errors > 0 || warnings > 0 || !event.errors.is_empty() {
+        ("!", crate::colors::warning())
+    } else {
+        ("✓", crate::colors::success())
+    };
+    let headline = if summary.is_empty() {
+        format!("{symbol} Diagnostics: no problems in {files} file{}", plural(files))
+    } else {
+        format!(
+            "{symbol} Diagnostics: {} in {files} file{}",
+            summary.join(" · "),
+            plural(files)
+        )
+    };
+    let mut lines = vec![Line::from(Span::styled(
+        headline,
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    ))];
+
+    for file in &event.files {
+        if file.diagnostics.is_empty() {
+            continue;
+        }
+        let path = file.path.strip_prefix(cwd).unwrap_or(&file.path);
+        lines.push(Line::from(vec![
+            Span::styled(
+                path.display().to_string(),
+                Style::default()
+                    .fg(crate::colors::primary())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("  {}", file.server), dim),
+        ]));
+        let mut diagnostics: Vec<_> = file.diagnostics.iter().collect();
+        diagnostics.sort_by_key(|diagnostic| (diagnostic.severity, !diagnostic.is_new, diagnostic.line));
+        for diagnostic in diagnostics.iter().take(MAX_DIAGNOSTICS_PER_FILE) {
+            let (label, color) = match diagnostic.severity {
+                DiagnosticSeverity::Error => ("error", crate::colors::error()),
+                DiagnosticSeverity::Warning => ("warning", crate::colors::warning()),
+                DiagnosticSeverity::Information => ("info", crate::colors::text_dim()),
+                DiagnosticSeverity::Hint => ("hint", crate::colors::text_dim()),
+            };
+            let message = diagnostic.message.lines().next().unwrap_or_default().to_string();
+            let mut row = vec![
+                Span::styled(format!("  {}:{} ", diagnostic.line, diagnostic.column), dim),
+                Span::styled(label, Style::default().fg(color)),
+                Span::raw(format!(" {message}")),
+            ];
+            if diagnostic.is_new {
+                row.push(Span::styled(
+                    "  new",
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ));
+            }
+            lines.push(Line::from(row));
+        }
+        if diagnostics.len() > MAX_DIAGNOSTICS_PER_FILE {
+            lines.push(Line::from(Span::styled(
+                format!("  … {} more", diagnostics.len() - MAX_DIAGNOSTICS_PER_FILE),
+                dim,
+            )));
+        }
+    }
+    for error in &event.errors {
+        lines.push(Line::from(Span::styled(
+            format!("Language server unavailable: {error}"),
+            Style::default().fg(crate::colors::warning()),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
+
+impl HistoryCell for LspDiagnosticsCell {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn kind(&self) -> HistoryCellType {
+        HistoryCellType::Plain
+    }
+
+    fn display_lines(&self) -> Vec<Line<'static>> {
+        self.lines.clone()
+    }
+
+    fn gutter_symbol(&self) -> Option<&'static str> {
+        Some("•")
+    }
+}
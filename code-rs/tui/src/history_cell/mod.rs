@@ -1,6 +1,8 @@
 mod agent;
 mod animated;
+mod artifacts;
 mod assistant;
+mod attachments;
 mod auto_drive;
 mod background;
 mod browser;
@@ -16,16 +18,20 @@ mod formatting;
 mod frozen;
 mod image;
 mod loading;
+mod lsp_diagnostics;
 mod patch;
 mod plain;
 mod plan_update;
 mod rate_limits;
 mod reasoning;
 mod registry;
+mod review_passes;
 mod stream;
+mod test_results;
 mod text;
 mod tool;
 mod tool_factory;
+mod touched_files;
 mod upgrade;
 mod wait_status;
 mod web_search;
@@ -37,11 +43,14 @@ pub(crate) use assistant::{
     AssistantMarkdownCell,
 };
 pub(crate) use animated::{AnimatedWelcomeCell, new_animated_welcome};
+pub(crate) use artifacts::ArtifactsCell;
+pub(crate) use attachments::AttachmentsCell;
 pub(crate) use background::{
     new_background_event,
     BackgroundEventCell,
 };
 pub(crate) use context::ContextCell;
+pub(crate) use context::context_delta_label;
 pub(crate) use core::{
     CommandOutput,
     ExecKind,
@@ -92,6 +101,7 @@ pub(crate) use formatting::{
     normalize_overwrite_sequences,
     output_lines,
     pretty_provider_name,
+    redacted_values_label,
     trim_empty_lines,
 };
 #[allow(unused_imports)]
@@ -101,6 +111,7 @@ pub(crate) use image::ImageOutputCell;
 pub(crate) use loading::LoadingCell;
 #[allow(unused_imports)]
 pub(crate) use loading::new_loading_cell;
+pub(crate) use lsp_diagnostics::LspDiagnosticsCell;
 pub(crate) use patch::{new_patch_apply_failure, new_patch_event, PatchSummaryCell};
 pub(crate) use plain::{
     new_error_event,
@@ -123,7 +134,7 @@ pub(crate) use plain::new_text_line;
 pub(crate) use plan_update::{new_plan_update, PlanUpdateCell};
 pub(crate) use rate_limits::RateLimitsCell;
 pub(crate) use reasoning::CollapsibleReasoningCell;
-pub(crate) use registry::{cell_from_record, lines_from_record, record_from_cell};
+pub(crate) use registry::{cell_from_record, kind_from_record, lines_from_record, record_from_cell};
 pub(crate) use stream::{new_streaming_content, stream_lines_from_state, StreamingContentCell};
 pub(crate) use tool::{RunningToolCallCell, ToolCallCell};
 pub(crate) use tool_factory::{
@@ -141,6 +152,9 @@ pub(crate) use tool_factory::{
     new_active_mcp_tool_call,
     WebFetchToolCell,
 };
+pub(crate) use review_passes::ReviewPassesCell;
+pub(crate) use test_results::TestResultsCell;
+pub(crate) use touched_files::{TouchedFileEntry, TouchedFilesCell};
 pub(crate) use upgrade::{new_upgrade_prelude, UpgradeNoticeCell};
 pub(crate) use wait_status::{new_completed_wait_tool_call, WaitStatusCell};
 pub(crate) use auto_drive::{AutoDriveActionKind, AutoDriveCardCell, AutoDriveStatus};
@@ -24,6 +24,7 @@ use code_ansi_escape::ansi_escape_line;
 use code_common::create_config_summary_entries;
 use code_core::config::Config;
 use code_core::config_types::ReasoningEffort;
+use code_core::history::state::TokenUsageSummary;
 use code_core::protocol::{SessionConfiguredEvent, TokenUsage};
 use code_protocol::num_format::format_with_separators_u64;
 use ratatui::buffer::Buffer;
@@ -96,14 +97,7 @@ impl PlainHistoryCell {
             .unwrap_or(false)
     }
     pub(crate) fn from_state(state: PlainMessageState) -> Self {
-        let mut kind = history_cell_kind_from_plain(state.kind);
-        if kind == HistoryCellType::User
-            && let Some(first_line) = state.lines.first()
-                && first_line.spans.first().is_some_and(|span| {
-                    span.text.starts_with("[Compaction Summary]")
-                }) {
-                    kind = HistoryCellType::CompactionSummary;
-                }
+        let kind = plain_cell_kind(&state);
         Self {
             state: PlainCellState {
                 message: state,
@@ -463,6 +457,21 @@ where
     }
 }
 
+/// Cell kind a [`PlainHistoryCell`] built from `state` reports.
+pub(crate) fn plain_cell_kind(state: &PlainMessageState) -> HistoryCellType {
+    let kind = history_cell_kind_from_plain(state.kind);
+    if kind == HistoryCellType::User
+        && let Some(first_line) = state.lines.first()
+        && first_line
+            .spans
+            .first()
+            .is_some_and(|span| span.text.starts_with("[Compaction Summary]"))
+    {
+        return HistoryCellType::CompactionSummary;
+    }
+    kind
+}
+
 pub(crate) fn plain_role_for_kind(kind: PlainMessageKind) -> PlainMessageRole {
     match kind {
         PlainMessageKind::User => PlainMessageRole::User,
@@ -786,6 +795,7 @@ pub(crate) fn new_status_output(
     config: &Config,
     total_usage: &TokenUsage,
     last_usage: &TokenUsage,
+    turn_usage: &TokenUsageSummary,
     requested_model: Option<&str>,
     latest_response_model: Option<&str>,
 ) -> PlainMessageState {
@@ -968,6 +978,17 @@ pub(crate) fn new_status_output(
         "  • Session total: ".into(),
         format_with_separators_u64(total_usage.blended_total()).into(),
     ]));
+    if let Some((_, largest)) = &turn_usage.largest {
+        lines.push(Line::from(vec![
+            "  • Turns: ".into(),
+            format_with_separators_u64(turn_usage.turns as u64).into(),
+            format!(
+                " (largest {})",
+                format_with_separators_u64(largest.blended_total())
+            )
+            .dim(),
+        ]));
+    }
 
     // 📐 Model Limits
     let context_window = config.model_context_window;
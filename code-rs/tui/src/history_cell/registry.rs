@@ -13,14 +13,14 @@ use super::image::ImageOutputCell;
 use super::loading::LoadingCell;
 use super::patch::PatchSummaryCell;
 use super::plan_update::PlanUpdateCell;
-use super::plain::PlainHistoryCell;
+use super::plain::{plain_cell_kind, PlainHistoryCell};
 use super::rate_limits::RateLimitsCell;
 use super::reasoning::CollapsibleReasoningCell;
 use super::stream::StreamingContentCell;
 use super::tool::{RunningToolCallCell, ToolCallCell};
 use super::upgrade::UpgradeNoticeCell;
 use super::wait_status::WaitStatusCell;
-use super::HistoryCell;
+use super::{HistoryCell, HistoryCellType};
 
 pub(crate) fn cell_from_record(record: &HistoryRecord, cfg: &Config) -> Box<dyn HistoryCell> {
     match record {
@@ -59,6 +59,17 @@ pub(crate) fn cell_from_record(record: &HistoryRecord, cfg: &Config) -> Box<dyn
     }
 }
 
+/// Kind reported by the cell [`cell_from_record`] would build, without
+/// building it for the common message records.
+pub(crate) fn kind_from_record(record: &HistoryRecord, cfg: &Config) -> HistoryCellType {
+    match record {
+        HistoryRecord::PlainMessage(state) => plain_cell_kind(state),
+        HistoryRecord::AssistantMessage(_) => HistoryCellType::Assistant,
+        HistoryRecord::BackgroundEvent(_) => HistoryCellType::BackgroundEvent,
+        _ => cell_from_record(record, cfg).kind(),
+    }
+}
+
 pub(crate) fn lines_from_record(record: &HistoryRecord, cfg: &Config) -> Vec<Line<'static>> {
     if let HistoryRecord::Explore(state) = record { return explore_lines_from_record(state) }
     cell_from_record(record, cfg).display_lines_trimmed()
@@ -0,0 +1,172 @@
+use super::*;
+use code_core::protocol::ReviewOutputEvent;
+use code_core::review_passes::ReviewPassSection;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use std::cell::Cell;
+
+/// A `/review` that ran specialized passes (`[[review_passes]]`): the merged
+/// verdict plus one section per pass. Clicking a section header expands or
+/// collapses it; passes without findings start collapsed.
+pub(crate) struct ReviewPassesCell {
+    title: String,
+    sections: Vec<ReviewPassSection>,
+    collapsed: Vec<Cell<bool>>,
+    correctness: String,
+    confidence: f32,
+}
+
+impl ReviewPassesCell {
+    pub(crate) fn new(
+        hint: Option<&str>,
+        output: &ReviewOutputEvent,
+        sections: Vec<ReviewPassSection>,
+    ) -> Self {
+        let title = match hint.map(str::trim).filter(|hint| !hint.is_empty()) {
+            Some(hint) => format!("Review summary — {hint}"),
+            None => "Review summary".to_string(),
+        };
+        let collapsed = sections
+            .iter()
+            .map(|section| Cell::new(section.findings.is_empty()))
+            .collect();
+        Self {
+            title,
+            sections,
+            collapsed,
+            correctness: output.overall_correctness.trim().to_string(),
+            confidence: output.overall_confidence_score,
+        }
+    }
+
+    pub(crate) fn toggle_section(&self, section: usize) {
+        if let Some(collapsed) = self.collapsed.get(section) {
+            collapsed.set(!collapsed.get());
+        }
+    }
+
+    /// Rows (0-based within the cell, after wrapping to `width`) that hold a
+    /// section header, with the section each one toggles.
+    pub(crate) fn section_header_rows(&self, width: u16) -> Vec<(u16, usize)> {
+        let (lines, headers) = self.render_lines();
+        let mut rows = Vec::with_capacity(headers.len());
+        let mut top: u16 = 0;
+        for (idx, line) in lines.into_iter().enumerate() {
+            if let Some((_, section)) = headers.iter().find(|(line_idx, _)| *line_idx == idx) {
+                rows.push((top, *section));
+            }
+            let height: u16 = Paragraph::new(line)
+                .wrap(Wrap { trim: false })
+                .line_count(width)
+                .try_into()
+                .unwrap_or(u16::MAX);
+            top = top.saturating_add(height.max(1));
+        }
+        rows
+    }
+
+    /// The cell's lines, and which line holds each section header.
+    fn render_lines(&self) -> (Vec<Line<'static>>, Vec<(usize, usize)>) {
+        let dim = Style::default().fg(crate::colors::text_dim());
+        let bold = Style::default()
+            .fg(crate::colors::text())
+            .add_modifier(Modifier::BOLD);
+        let total: usize = self.sections.iter().map(|section| section.findings.len()).sum();
+        let mut lines = vec![Line::from(vec![
+            Span::styled(self.title.clone(), bold),
+            Span::styled(
+                format!(
+                    "  {total} finding{} · {} passes",
+                    if total == 1 { "" } else { "s" },
+                    self.sections.len()
+                ),
+                dim,
+            ),
+        ])];
+        let mut headers = Vec::new();
+
+        for (idx, section) in self.sections.iter().enumerate() {
+            let collapsed = self.collapsed[idx].get();
+            let count = section.findings.len();
+            headers.push((lines.len(), idx));
+            lines.push(Line::from(vec![
+                Span::styled(if collapsed { "▸ " } else { "▾ " }, dim),
+                Span::styled(
+                    section.name.clone().unwrap_or_else(|| "main".to_string()),
+                    Style::default()
+                        .fg(crate::colors::primary())
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(
+                        "  {count} finding{}{}",
+                        if count == 1 { "" } else { "s" },
+                        if collapsed { " · click to expand" } else { "" }
+                    ),
+                    dim,
+                ),
+            ]));
+            if collapsed {
+                continue;
+            }
+            for text in section.explanation.lines().filter(|text| !text.trim().is_empty()) {
+                lines.push(Line::from(format!("  {text}")));
+            }
+            for finding in &section.findings {
+                let location = &finding.code_location;
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  • {}", finding.title)),
+                    Span::styled(
+                        format!(
+                            " — {}:{}-{}",
+                            location.absolute_file_path.display(),
+                            location.line_range.start,
+                            location.line_range.end
+                        ),
+                        dim,
+                    ),
+                ]));
+                for body in finding.body.lines() {
+                    lines.push(Line::from(Span::styled(format!("    {body}"), dim)));
+                }
+            }
+        }
+
+        if !self.correctness.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled("Overall correctness: ", bold),
+                Span::raw(self.correctness.clone()),
+            ]));
+        }
+        if self.confidence > 0.0 {
+            lines.push(Line::from(Span::styled(
+                format!("Confidence score: {:.1}", self.confidence),
+                dim,
+            )));
+        }
+        lines.push(Line::from(""));
+        (lines, headers)
+    }
+}
+
+impl HistoryCell for ReviewPassesCell {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn kind(&self) -> HistoryCellType {
+        HistoryCellType::Plain
+    }
+
+    fn display_lines(&self) -> Vec<Line<'static>> {
+        self.render_lines().0
+    }
+
+    fn gutter_symbol(&self) -> Option<&'static str> {
+        Some("•")
+    }
+}
@@ -1,10 +1,12 @@
 use super::*;
-use crate::history::{AssistantMessageState, AssistantStreamState};
+use crate::history::AssistantStreamState;
+use crate::markdown_incremental::IncrementalMarkdown;
 use code_core::history::state::MAX_ASSISTANT_STREAM_RETAINED_BYTES;
 use code_core::config::Config;
 use code_core::config_types::UriBasedFileOpener;
 use ratatui::style::Style;
 use ratatui::text::Line;
+use std::cell::RefCell;
 use std::path::Path;
 use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -17,6 +19,8 @@ pub(crate) struct StreamingContentCell {
     state: AssistantStreamState,
     file_opener: UriBasedFileOpener,
     cwd: PathBuf,
+    // Each delta only lays out the blocks that are still growing.
+    layout: RefCell<IncrementalMarkdown>,
 }
 
 impl HistoryCell for StreamingContentCell {
@@ -33,12 +37,12 @@ impl HistoryCell for StreamingContentCell {
     }
 
     fn display_lines(&self) -> Vec<Line<'static>> {
-        stream_lines_from_state_with_context(
-            &self.state,
+        let rendered = self.layout.borrow_mut().render(
+            &stream_markdown_source(&self.state),
             self.file_opener,
             &self.cwd,
-            self.state.in_progress,
-        )
+        );
+        finish_stream_lines(rendered, self.state.in_progress)
     }
 }
 
@@ -53,6 +57,7 @@ impl StreamingContentCell {
             state,
             file_opener,
             cwd,
+            layout: RefCell::new(IncrementalMarkdown::new(true)),
         }
     }
 
@@ -97,47 +102,35 @@ pub(crate) fn stream_lines_from_state_with_context(
     cwd: &Path,
     show_ellipsis: bool,
 ) -> Vec<Line<'static>> {
-    let mut markdown = state.preview_markdown.clone();
-    if state.truncated_prefix_bytes > 0 {
-        let note = format!(
-            "… clipped {} from the start of assistant response (showing last {}).\n\n",
-            code_core::util::format_bytes(state.truncated_prefix_bytes),
-            code_core::util::format_bytes(MAX_ASSISTANT_STREAM_RETAINED_BYTES),
-        );
-        markdown = format!("{note}{markdown}");
-    }
-
-    let message_state = AssistantMessageState {
-        id: state.id,
-        stream_id: Some(state.stream_id.clone()),
-        markdown,
-        citations: state.citations.clone(),
-        metadata: state.metadata.clone(),
-        token_usage: state
-            .metadata
-            .as_ref()
-            .and_then(|meta| meta.token_usage.clone()),
-        mid_turn: false,
-        created_at: state.last_updated_at,
-    };
-
     let mut rendered: Vec<Line<'static>> = Vec::new();
-    // Insert a sentinel so downstream styling mirrors assistant message rendering.
-    rendered.push(Line::from("stream"));
     crate::markdown::append_markdown_with_opener_and_cwd_and_bold(
-        &message_state.markdown,
+        &stream_markdown_source(state),
         &mut rendered,
         file_opener,
         cwd,
         true,
     );
+    finish_stream_lines(rendered, show_ellipsis)
+}
 
+fn stream_markdown_source(state: &AssistantStreamState) -> String {
+    if state.truncated_prefix_bytes == 0 {
+        return state.preview_markdown.clone();
+    }
+    let note = format!(
+        "… clipped {} from the start of assistant response (showing last {}).\n\n",
+        code_core::util::format_bytes(state.truncated_prefix_bytes),
+        code_core::util::format_bytes(MAX_ASSISTANT_STREAM_RETAINED_BYTES),
+    );
+    format!("{note}{}", state.preview_markdown)
+}
+
+/// Applies assistant message styling to rendered stream markdown.
+fn finish_stream_lines(mut lines: Vec<Line<'static>>, show_ellipsis: bool) -> Vec<Line<'static>> {
     let bright = crate::colors::text_bright();
-    for line in rendered.iter_mut().skip(1) {
+    for line in &mut lines {
         line.style = line.style.patch(Style::default().fg(bright));
     }
-
-    let mut lines: Vec<Line<'static>> = rendered.into_iter().skip(1).collect();
     if show_ellipsis {
         lines.push(ellipsis_line());
     }
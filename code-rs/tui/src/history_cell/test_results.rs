@@ -0,0 +1,159 @@
+use super::*;
+use code_core::test_runner::TestReport;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use std::cell::Cell;
+
+/// Output lines shown for an expanded failure or a failed run.
+const MAX_EXPANDED_OUTPUT_LINES: usize = 40;
+
+/// A `run_tests` report: counts, then one row per failing test with its
+/// location and message. Clicking a failure shows its output.
+pub(crate) struct TestResultsCell {
+    report: TestReport,
+    expanded: Vec<Cell<bool>>,
+}
+
+impl TestResultsCell {
+    pub(crate) fn new(report: TestReport) -> Self {
+        let expanded = report.failures.iter().map(|_| Cell::new(false)).collect();
+        Self { report, expanded }
+    }
+
+    pub(crate) fn toggle_failure(&self, index: usize) {
+        if let Some(expanded) = self.expanded.get(index) {
+            expanded.set(!expanded.get());
+        }
+    }
+
+    /// Rows (0-based within the cell, after wrapping to `width`) that hold a
+    /// failure, with the failure each one toggles.
+    pub(crate) fn failure_rows(&self, width: u16) -> Vec<(u16, usize)> {
+        let (lines, headers) = self.render_lines();
+        let mut rows = Vec::with_capacity(headers.len());
+        let mut top: u16 = 0;
+        for (idx, line) in lines.into_iter().enumerate() {
+            if let Some((_, failure)) = headers.iter().find(|(line_idx, _)| *line_idx == idx) {
+                rows.push((top, *failure));
+            }
+            let height: u16 = Paragraph::new(line)
+                .wrap(Wrap { trim: false })
+                .line_count(width)
+                .try_into()
+                .unwrap_or(u16::MAX);
+            top = top.saturating_add(height.max(1));
+        }
+        rows
+    }
+
+    /// The cell's lines, and which line holds each failure.
+    fn render_lines(&self) -> (Vec<Line<'static>>, Vec<(usize, usize)>) {
+        let report = &self.report;
+        let dim = Style::default().fg(crate::colors::text_dim());
+        let (symbol, headline, color) = if report.timed_out {
+            ("✗", "Tests timed out", crate::colors::error())
+        } else if report.succeeded() {
+            ("✓", "Tests passed", crate::colors::success())
+        } else if report.failures.is_empty() {
+            ("✗", "Test run failed", crate::colors::error())
+        } else {
+            ("✗", "Tests failed", crate::colors::error())
+        };
+        let mut counts = Vec::new();
+        if let Some(failed) = report.failed.filter(|failed| *failed > 0) {
+            counts.push(format!("{failed} failed"));
+        }
+        if let Some(passed) = report.passed {
+            counts.push(format!("{passed} passed"));
+        }
+        if let Some(skipped) = report.skipped.filter(|skipped| *skipped > 0) {
+            counts.push(format!("{skipped} skipped"));
+        }
+        let mut header = vec![Span::styled(
+            format!("{symbol} {headline}"),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )];
+        if !counts.is_empty() {
+            header.push(Span::styled(format!("  {}", counts.join(" · ")), dim));
+        }
+        let mut lines = vec![
+            Line::from(header),
+            Line::from(Span::styled(format!("$ {}", report.command), dim)),
+        ];
+        let mut headers = Vec::new();
+
+        for (idx, failure) in report.failures.iter().enumerate() {
+            let expanded = self.expanded[idx].get();
+            headers.push((lines.len(), idx));
+            let mut row = vec![
+                Span::styled(if expanded { "▾ " } else { "▸ " }, dim),
+                Span::styled(
+                    failure.name.clone(),
+                    Style::default()
+                        .fg(crate::colors::primary())
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ];
+            if let Some(location) = failure.location() {
+                row.push(Span::styled(format!("  {location}"), dim));
+            }
+            lines.push(Line::from(row));
+            if let Some(message) = failure.message.lines().map(str::trim).find(|line| !line.is_empty()) {
+                lines.push(Line::from(Span::styled(
+                    format!("    {message}"),
+                    Style::default().fg(crate::colors::error()),
+                )));
+            }
+            if expanded {
+                let output: Vec<&str> = failure.output.lines().collect();
+                for text in output.iter().take(MAX_EXPANDED_OUTPUT_LINES) {
+                    lines.push(Line::from(Span::styled(format!("    {text}"), dim)));
+                }
+                if output.len() > MAX_EXPANDED_OUTPUT_LINES {
+                    lines.push(Line::from(Span::styled(
+                        format!("    … {} more lines", output.len() - MAX_EXPANDED_OUTPUT_LINES),
+                        dim,
+                    )));
+                }
+            }
+        }
+
+        if let Some(tail) = report.output_tail.as_deref() {
+            let tail_lines: Vec<&str> = tail.lines().collect();
+            let skip = tail_lines.len().saturating_sub(MAX_EXPANDED_OUTPUT_LINES);
+            for text in &tail_lines[skip..] {
+                lines.push(Line::from(Span::styled(format!("  {text}"), dim)));
+            }
+        }
+        if !report.failures.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "Click a test for its output · /tests to pick one to fix",
+                dim,
+            )));
+        }
+        lines.push(Line::from(""));
+        (lines, headers)
+    }
+}
+
+impl HistoryCell for TestResultsCell {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn kind(&self) -> HistoryCellType {
+        HistoryCellType::Plain
+    }
+
+    fn display_lines(&self) -> Vec<Line<'static>> {
+        self.render_lines().0
+    }
+
+    fn gutter_symbol(&self) -> Option<&'static str> {
+        Some("•")
+    }
+}
@@ -163,6 +163,9 @@ impl RunningToolCallCell {
 
     fn spinner_frame(&self) -> &'static str {
         const FRAMES: [&str; 4] = ["◐", "◓", "◑", "◒"];
+        if crate::accessibility::enabled() {
+            return FRAMES[0];
+        }
         let idx = ((self.start_clock.elapsed().as_millis() / 100) as usize) % FRAMES.len();
         FRAMES[idx]
     }
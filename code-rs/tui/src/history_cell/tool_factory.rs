@@ -98,6 +98,12 @@ fn browser_running_title(tool_name: &str) -> &'static str {
         "browser_inspect" => "Inspecting...",
         "browser_console" => "Reading console...",
         "browser_move" => "Moving...",
+        "browser_tabs" => "Listing tabs...",
+        "browser_new_tab" => "Opening tab...",
+        "browser_switch_tab" => "Switching tab...",
+        "browser_close_tab" => "Closing tab...",
+        "browser_snapshot" => "Capturing...",
+        "browser_read" => "Reading page...",
         _ => "Working...",
     }
 }
@@ -853,6 +859,12 @@ fn browser_tool_title(tool_name: &str) -> &'static str {
         "browser_console" => "Browser Console",
         "browser_cdp" => "Browser CDP",
         "browser_move" => "Browser Move",
+        "browser_tabs" => "Browser Tabs",
+        "browser_new_tab" => "Browser New Tab",
+        "browser_switch_tab" => "Browser Switch Tab",
+        "browser_close_tab" => "Browser Close Tab",
+        "browser_snapshot" => "Browser Snapshot",
+        "browser_read" => "Browser Read",
         _ => "Browser Tool",
     }
 }
@@ -0,0 +1,166 @@
+use super::*;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use std::path::PathBuf;
+
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Only the most recent applies are drawn so the row stays short.
+const MAX_SPARK_POINTS: usize = 16;
+const MAX_PATH_CHARS: usize = 48;
+
+/// One file touched this session: cumulative counts against its baseline plus
+/// the number of changed lines per patch apply, oldest first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TouchedFileEntry {
+    pub path: PathBuf,
+    pub display: String,
+    pub added: usize,
+    pub removed: usize,
+    pub changes_per_apply: Vec<usize>,
+}
+
+/// Summary of every file patched this session. Rows map 1:1 to entries after
+/// the header so the history scroller can make each file clickable.
+pub(crate) struct TouchedFilesCell {
+    entries: Vec<TouchedFileEntry>,
+}
+
+impl TouchedFilesCell {
+    pub(crate) fn new(entries: Vec<TouchedFileEntry>) -> Self {
+        Self { entries }
+    }
+
+    /// File rendered on `row` (0-based within the cell), if any.
+    pub(crate) fn file_at_row(&self, row: u16) -> Option<&PathBuf> {
+        let idx = usize::from(row).checked_sub(1)?;
+        self.entries.get(idx).map(|entry| &entry.path)
+    }
+}
+
+impl HistoryCell for TouchedFilesCell {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn kind(&self) -> HistoryCellType {
+        HistoryCellType::Plain
+    }
+
+    fn display_lines(&self) -> Vec<Line<'static>> {
+        let dim = Style::default().fg(crate::colors::text_dim());
+        let (added, removed) = self
+            .entries
+            .iter()
+            .fold((0, 0), |(a, r), entry| (a + entry.added, r + entry.removed));
+        let count = self.entries.len();
+        let mut lines = vec![Line::from(vec![
+            Span::styled(
+                format!("Files touched this session ({count})"),
+                Style::default()
+                    .fg(crate::colors::text())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" "),
+            Span::styled(format!("+{added}"), Style::default().fg(crate::colors::success())),
+            Span::raw(" "),
+            Span::styled(format!("-{removed}"), Style::default().fg(crate::colors::error())),
+            Span::styled("  click a file for its diff", dim),
+        ])];
+
+        let path_width = self
+            .entries
+            .iter()
+            .map(|entry| truncate_path(&entry.display).chars().count())
+            .max()
+            .unwrap_or(0);
+        for entry in &self.entries {
+            let path = truncate_path(&entry.display);
+            let pad = path_width.saturating_sub(path.chars().count());
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{path}{}", " ".repeat(pad)),
+                    Style::default().fg(crate::colors::primary()),
+                ),
+                Span::raw("  "),
+                Span::styled(
+                    format!("+{}", entry.added),
+                    Style::default().fg(crate::colors::success()),
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    format!("-{}", entry.removed),
+                    Style::default().fg(crate::colors::error()),
+                ),
+                Span::raw("  "),
+                Span::styled(sparkline(&entry.changes_per_apply), dim),
+            ]));
+        }
+        lines.push(Line::from(""));
+        lines
+    }
+
+    fn gutter_symbol(&self) -> Option<&'static str> {
+        Some("Δ")
+    }
+}
+
+/// Keeps the tail of long paths so rows do not wrap (wrapping would break the
+/// row-to-file mapping used for clicks).
+fn truncate_path(display: &str) -> String {
+    let count = display.chars().count();
+    if count <= MAX_PATH_CHARS {
+        return display.to_string();
+    }
+    let tail: String = display
+        .chars()
+        .skip(count - (MAX_PATH_CHARS - 1))
+        .collect();
+    format!("…{tail}")
+}
+
+fn sparkline(values: &[usize]) -> String {
+    let recent = &values[values.len().saturating_sub(MAX_SPARK_POINTS)..];
+    let max = recent.iter().copied().max().unwrap_or(0);
+    if max == 0 {
+        return String::new();
+    }
+    recent
+        .iter()
+        .map(|&value| {
+            if value == 0 {
+                ' '
+            } else {
+                SPARK_LEVELS[value * (SPARK_LEVELS.len() - 1) / max]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparkline_scales_to_the_busiest_apply() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[1, 0, 7, 4]), "▂ █▅");
+    }
+
+    #[test]
+    fn file_rows_start_after_header() {
+        let cell = TouchedFilesCell::new(vec![TouchedFileEntry {
+            path: PathBuf::from("/repo/src/lib.rs"),
+            display: "src/lib.rs".to_string(),
+            added: 3,
+            removed: 1,
+            changes_per_apply: vec![4],
+        }]);
+        assert_eq!(cell.file_at_row(0), None);
+        assert_eq!(cell.file_at_row(1), Some(&PathBuf::from("/repo/src/lib.rs")));
+        assert_eq!(cell.file_at_row(2), None);
+    }
+}
@@ -0,0 +1,164 @@
+//! OSC 8 hyperlinks for history written to terminal scrollback.
+//!
+//! Live frames never carry escape sequences (they confuse ratatui's cell diff;
+//! see the note above `autolink_spans` in `markdown_renderer.rs`). Lines
+//! mirrored into scrollback in standard terminal mode are written once and
+//! never redrawn, so there URLs and paths to existing files are wrapped in
+//! OSC 8 links. File links use `file://<host>/<path>` so a terminal attached
+//! over SSH can tell they point at the remote machine.
+
+use std::ops::Range;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+struct LinkContext {
+    cwd: PathBuf,
+    host: String,
+}
+
+static CONTEXT: RwLock<Option<LinkContext>> = RwLock::new(None);
+
+/// Enables (or disables) links; relative paths resolve against `cwd`.
+pub(crate) fn configure(enabled: bool, cwd: &Path) {
+    let Ok(mut context) = CONTEXT.write() else {
+        return;
+    };
+    *context = enabled.then(|| LinkContext {
+        cwd: cwd.to_path_buf(),
+        host: hostname(),
+    });
+}
+
+/// Follows `/branch`, `/merge`, and other working-directory switches.
+pub(crate) fn set_cwd(cwd: &Path) {
+    if let Ok(mut context) = CONTEXT.write()
+        && let Some(context) = context.as_mut()
+    {
+        context.cwd = cwd.to_path_buf();
+    }
+}
+
+/// Byte ranges of `text` to link, with their targets. Empty when disabled.
+pub(crate) fn link_targets(text: &str) -> Vec<(Range<usize>, String)> {
+    let Ok(context) = CONTEXT.read() else {
+        return Vec::new();
+    };
+    match context.as_ref() {
+        Some(context) => link_targets_with(text, |path| {
+            let path = context.cwd.join(path);
+            path.is_file().then(|| file_uri(&context.host, &path))
+        }),
+        None => Vec::new(),
+    }
+}
+
+pub(crate) fn open_sequence(target: &str) -> String {
+    format!("\x1b]8;;{target}\x1b\\")
+}
+
+pub(crate) const CLOSE_SEQUENCE: &str = "\x1b]8;;\x1b\\";
+
+/// Scans whitespace-separated tokens for URLs and file paths. `resolve_file`
+/// maps a path-like token to its link target, or `None` when no such file
+/// exists.
+fn link_targets_with(
+    text: &str,
+    resolve_file: impl Fn(&Path) -> Option<String>,
+) -> Vec<(Range<usize>, String)> {
+    if text.contains('\x1b') {
+        return Vec::new();
+    }
+    let mut links = Vec::new();
+    let mut offset = 0;
+    for token in text.split_inclusive(char::is_whitespace) {
+        let start = offset;
+        offset += token.len();
+        let trimmed_start = token.trim_start_matches(['(', '[', '<', '`', '\'', '"']);
+        let lead = token.len() - trimmed_start.len();
+        let core = trimmed_start
+            .trim_end()
+            .trim_end_matches([')', ']', '>', '`', '\'', '"', '.', ',', ';', ':', '!', '?']);
+        if core.is_empty() {
+            continue;
+        }
+        let range = start + lead..start + lead + core.len();
+        if core.starts_with("https://") || core.starts_with("http://") {
+            links.push((range, core.to_string()));
+            continue;
+        }
+        if core.starts_with('-') || !(core.contains('/') || core.contains('.')) {
+            continue;
+        }
+        if let Some(target) = resolve_file(Path::new(strip_line_suffix(core))) {
+            links.push((range, target));
+        }
+    }
+    links
+}
+
+/// Drops a trailing `:line` or `:line:col` location.
+fn strip_line_suffix(token: &str) -> &str {
+    let mut path = token;
+    for _ in 0..2 {
+        match path.rsplit_once(':') {
+            Some((head, tail)) if !tail.is_empty() && tail.bytes().all(|b| b.is_ascii_digit()) => {
+                path = head;
+            }
+            _ => break,
+        }
+    }
+    path
+}
+
+fn file_uri(host: &str, path: &Path) -> String {
+    let path = path.to_string_lossy();
+    let mut encoded = String::with_capacity(path.len());
+    for ch in path.chars() {
+        match ch {
+            ' ' => encoded.push_str("%20"),
+            '%' => encoded.push_str("%25"),
+            '#' => encoded.push_str("%23"),
+            '?' => encoded.push_str("%3F"),
+            '\\' => encoded.push('/'),
+            _ => encoded.push(ch),
+        }
+    }
+    if !encoded.starts_with('/') {
+        encoded.insert(0, '/');
+    }
+    format!("file://{host}{encoded}")
+}
+
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/proc/sys/kernel/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn finds_urls_and_existing_files() {
+        let text = "See https://example.com/docs. Edited (src/main.rs:12) and `missing.rs`.";
+        let links = link_targets_with(text, |path| {
+            (path == Path::new("src/main.rs")).then(|| file_uri("devbox", &Path::new("/repo").join(path)))
+        });
+        let spans: Vec<(&str, &str)> = links
+            .iter()
+            .map(|(range, target)| (&text[range.clone()], target.as_str()))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                ("https://example.com/docs", "https://example.com/docs"),
+                ("src/main.rs:12", "file://devbox/repo/src/main.rs"),
+            ]
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use std::fmt;
 use std::io;
 use std::io::Write;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 
 use crate::tui;
 use crossterm::Command;
@@ -22,6 +24,18 @@ use ratatui::text::Span;
 use textwrap::Options as TwOptions;
 use textwrap::WordSplitter;
 
+/// `--plain`: history is written as bare text, without colors, attributes or
+/// hyperlinks.
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+pub(crate) fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
 /// Insert `lines` above the viewport.
 #[allow(dead_code)]
 pub(crate) fn insert_history_lines(terminal: &mut tui::Tui, lines: Vec<Line>) {
@@ -356,6 +370,12 @@ fn write_spans<'a, I>(mut writer: &mut impl Write, content: I) -> io::Result<()>
 where
     I: Iterator<Item = &'a Span<'a>>,
 {
+    if is_plain() {
+        for span in content {
+            queue!(writer, Print(crate::accessibility::asciify(&span.content)))?;
+        }
+        return Ok(());
+    }
     let mut fg = Color::Reset;
     let mut bg = Color::Reset;
     let mut last_modifier = Modifier::empty();
@@ -382,7 +402,7 @@ where
             bg = next_bg;
         }
 
-        queue!(writer, Print(span.content.clone()))?;
+        write_linked_text(&mut writer, &crate::accessibility::asciify(&span.content))?;
     }
 
     queue!(
@@ -393,6 +413,22 @@ where
     )
 }
 
+/// Print `text`, wrapping URLs and file paths in OSC 8 hyperlinks when enabled.
+fn write_linked_text(writer: &mut impl Write, text: &str) -> io::Result<()> {
+    let mut cursor = 0;
+    for (range, target) in crate::hyperlinks::link_targets(text) {
+        queue!(
+            writer,
+            Print(&text[cursor..range.start]),
+            Print(crate::hyperlinks::open_sequence(&target)),
+            Print(&text[range.clone()]),
+            Print(crate::hyperlinks::CLOSE_SEQUENCE),
+        )?;
+        cursor = range.end;
+    }
+    queue!(writer, Print(&text[cursor..]))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(dead_code)]
 pub struct SetUnderlineColor(pub CColor);
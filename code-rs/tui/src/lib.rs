@@ -54,11 +54,13 @@ use tracing_subscriber::prelude::*;
 use tracing_subscriber::EnvFilter;
 use uuid::Uuid;
 
+mod accessibility;
 mod app;
 mod app_event;
 mod app_event_sender;
 mod account_label;
 mod bottom_pane;
+mod cached_rows;
 mod components;
 mod chrome_launch;
 mod chatwidget;
@@ -69,6 +71,8 @@ mod common;
 mod colors;
 pub mod card_theme;
 mod diff_render;
+mod dictation;
+mod dirty_regions;
 mod exec_command;
 mod external_editor;
 mod file_search;
@@ -80,11 +84,16 @@ mod auto_drive_style;
 mod header_wave;
 mod history_cell;
 mod history;
+mod hyperlinks;
 mod insert_history;
 pub mod live_wrap;
 mod markdown;
+mod markdown_incremental;
+mod markdown_links;
+mod markdown_math;
 mod markdown_render;
 mod markdown_renderer;
+mod markdown_table;
 mod ui_interaction;
 mod remote_model_presets;
 mod markdown_stream;
@@ -99,15 +108,18 @@ mod shimmer;
 mod slash_command;
 mod rate_limits_view;
 pub mod resume;
+mod startup;
 mod streaming;
 mod sanitize;
 mod layout_consts;
 mod terminal_info;
+mod termux_api;
 // mod text_block; // Orphaned after trait-based HistoryCell migration
 mod text_formatting;
 mod text_processing;
 mod theme;
 mod thread_spawner;
+mod timer_queue;
 mod util {
     pub mod buffer;
     pub mod list_window;
@@ -175,6 +187,7 @@ fn rotate_log_file(log_dir: &Path, file_name: &str) {
 
 #[cfg(feature = "test-helpers")]
 pub mod test_helpers {
+    pub use crate::cached_rows::CachedRow;
     pub use crate::chatwidget::smoke_helpers::AutoContinueModeFixture;
     pub use crate::chatwidget::smoke_helpers::ChatWidgetHarness;
     pub use crate::chatwidget::smoke_helpers::LayoutMetrics;
@@ -408,6 +421,7 @@ pub async fn run_main(
     code_linux_sandbox_exe: Option<PathBuf>,
 ) -> std::io::Result<ExitSummary> {
     cli.finalize_defaults();
+    startup::begin(cli.profile_startup);
 
     let (sandbox_mode, approval_policy) = if cli.full_auto {
         (
@@ -493,6 +507,7 @@ pub async fn run_main(
     };
 
     code_core::config::migrate_legacy_log_dirs(&code_home);
+    report_config_migration(&code_home);
 
     let housekeeping_home = code_home.clone();
     let housekeeping_stop = Arc::new(AtomicBool::new(false));
@@ -505,7 +520,9 @@ pub async fn run_main(
         while !housekeeping_stop_worker.load(Ordering::Relaxed) {
             let now = Instant::now();
             if now >= next_run_at {
-                if let Err(err) = code_core::run_housekeeping_if_due(&housekeeping_home) {
+                if let Some(constraint) = code_core::battery::background_work_constraint() {
+                    tracing::debug!("deferring code home housekeeping: {constraint}");
+                } else if let Err(err) = code_core::run_housekeeping_if_due(&housekeeping_home) {
                     tracing::warn!("code home housekeeping failed: {err}");
                 }
                 next_run_at = now + HOUSEKEEPING_INTERVAL;
@@ -533,6 +550,15 @@ pub async fn run_main(
         }
     };
 
+    let layers_cwd = match overrides.cwd.clone() {
+        Some(cwd) => Some(cwd),
+        None => std::env::current_dir().ok(),
+    };
+    if let Some(cwd) = layers_cwd {
+        maybe_prompt_for_project_config(&code_home, &cwd);
+    }
+
+    let config_span = startup::span("config");
     let mut config = {
         // Load configuration and support CLI overrides.
 
@@ -547,7 +573,17 @@ pub async fn run_main(
     };
 
     config.demo_developer_message = cli.demo_developer_message.clone();
+    code_core::battery::set_low_battery_threshold(config.low_battery_threshold);
+    code_core::metered::set_metered_connection(config.metered_connection);
+    code_core::http_client::set_network_config(&config.network);
+    #[cfg(unix)]
+    if let Some(socket) = cli.attach_socket.clone() {
+        chatwidget::set_attach_socket(socket);
+    }
+
+    drop(config_span);
 
+    let migrations_span = startup::span("migrations");
     let cli_personality_override = cli_kv_overrides.iter().any(|(path, _)| {
         matches!(path.as_str(), "model_personality" | "model-personality")
             || path.ends_with(".model_personality")
@@ -676,8 +712,11 @@ pub async fn run_main(
         }
     }
 
-    let startup_footer_notice = None;
+    drop(migrations_span);
 
+    let mut startup_footer_notice = None;
+
+    let trust_span = startup::span("project_trust");
     // we load config.toml here to determine project state.
     #[allow(clippy::print_stderr)]
     let (config_toml, theme_set_in_config_file) = {
@@ -703,6 +742,9 @@ pub async fn run_main(
         workspace_write_network_access_explicit,
     )?;
 
+    drop(trust_span);
+
+    let logging_span = startup::span("logging");
     let log_dir = code_core::config::log_dir(&config)?;
     std::fs::create_dir_all(&log_dir)?;
 
@@ -758,10 +800,31 @@ pub async fn run_main(
         .with_writer(critical_writer)
         .with_filter(LevelFilter::ERROR);
 
+    let otel = match code_core::otel_init::build_provider(&config, env!("CARGO_PKG_VERSION")) {
+        Ok(provider) => provider,
+        Err(err) => {
+            eprintln!("Could not start OpenTelemetry export: {err}");
+            None
+        }
+    };
+    let otel_log_layer = otel.as_ref().and_then(|provider| provider.log_layer()).map(|layer| {
+        layer.with_filter(tracing_subscriber::filter::filter_fn(
+            code_core::otel_init::code_export_filter,
+        ))
+    });
+    let otel_trace_layer = otel.as_ref().and_then(|provider| provider.trace_layer()).map(|layer| {
+        layer.with_filter(tracing_subscriber::filter::filter_fn(
+            code_core::otel_init::code_trace_filter,
+        ))
+    });
+
     let _ = tracing_subscriber::registry()
         .with(env_layer)
         .with(critical_layer)
+        .with(otel_log_layer)
+        .with(otel_trace_layer)
         .try_init();
+    drop(logging_span);
 
     if cli.oss {
         code_ollama::ensure_oss_ready(&config)
@@ -769,9 +832,17 @@ pub async fn run_main(
             .map_err(|e| std::io::Error::other(format!("OSS setup failed: {e}")))?;
     }
 
-    let _otel = code_core::otel_init::build_provider(&config, env!("CARGO_PKG_VERSION"));
+    if cli.worktree {
+        config.worktree_isolation = true;
+    }
+    // Resumed and attached sessions keep the directory they already run in.
+    let resuming = cli.resume_picker || cli.resume_last || cli.resume_session_id.is_some();
+    if config.worktree_isolation && !resuming && cli.attach_socket.is_none() {
+        startup_footer_notice = enter_isolated_worktree(&mut config).await;
+    }
 
     let latest_upgrade_version = if crate::updates::upgrade_ui_enabled() {
+        let _span = startup::span("upgrade_check");
         updates::get_upgrade_version(&config)
     } else {
         None
@@ -786,6 +857,7 @@ pub async fn run_main(
         theme_configured_explicitly,
     );
 
+    termux_api::release_wake_lock_blocking();
     housekeeping_stop.store(true, Ordering::Relaxed);
     if let Some(handle) = housekeeping_handle {
         if let Err(err) = handle.join() {
@@ -798,6 +870,32 @@ pub async fn run_main(
     run_result.map_err(|err| std::io::Error::other(err.to_string()))
 }
 
+/// Moves the session into its own git worktree (`--worktree` or
+/// `worktree_isolation`) and returns the notice announcing it. Sessions that
+/// already run inside a branch worktree stay where they are.
+async fn enter_isolated_worktree(config: &mut Config) -> Option<String> {
+    if code_core::git_worktree::is_branch_worktree_path(&config.cwd) {
+        return None;
+    }
+    match code_core::git_worktree::setup_isolated_session_worktree(&config.cwd).await {
+        Ok((cwd, branch)) => {
+            let notice = format!(
+                "Working in isolated worktree '{branch}' ({}). Your checkout is untouched; run /merge to fast-forward the results into it.",
+                cwd.display()
+            );
+            config.cwd = cwd;
+            Some(notice)
+        }
+        Err(err) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("Could not create an isolated worktree: {err}");
+            }
+            std::process::exit(1);
+        }
+    }
+}
+
 pub(crate) fn install_unified_panic_hook() {
     static PANIC_HOOK_ONCE: Once = Once::new();
 
@@ -849,6 +947,15 @@ fn run_ratatui_app(
     install_unified_panic_hook();
     maybe_apply_terminal_theme_detection(&mut config, theme_configured_explicitly);
 
+    if cli.plain {
+        // `--plain` is standard terminal mode with history written as bare
+        // text; it does not change the saved preference.
+        config.tui.alternate_screen = false;
+        config.tui.hyperlinks = false;
+        insert_history::set_plain(true);
+    }
+
+    let tui_span = startup::span("terminal_init");
     let (mut terminal, terminal_info) = tui::init(&config)?;
     if config.tui.alternate_screen {
         terminal.clear()?;
@@ -864,6 +971,8 @@ fn run_ratatui_app(
         );
     }
 
+    drop(tui_span);
+
     // Initialize high-fidelity session event logging if enabled.
     session_log::maybe_init(&config);
 
@@ -878,6 +987,7 @@ fn run_ratatui_app(
         resume_session_id: _,
         ..
     } = cli;
+    let app_span = startup::span("app_init");
     let mut app = App::new(app::AppInitArgs {
         config: config.clone(),
         initial_prompt: prompt,
@@ -891,6 +1001,7 @@ fn run_ratatui_app(
         startup_footer_notice,
         latest_upgrade_version,
     });
+    drop(app_span);
 
     let app_result = app.run(&mut terminal);
     let session_id = app.session_id();
@@ -908,6 +1019,9 @@ fn run_ratatui_app(
     if let Some(summary) = timing_summary {
         print_timing_summary(&summary);
     }
+    if let Some(report) = startup::report() {
+        print_startup_profile(&report);
+    }
 
     #[cfg(unix)]
     let sigterm_triggered = app.sigterm_triggered();
@@ -945,6 +1059,11 @@ fn print_timing_summary(summary: &str) {
     eprintln!("\n== Timing Summary ==\n{summary}");
 }
 
+#[allow(clippy::print_stderr)]
+fn print_startup_profile(report: &str) {
+    eprintln!("\n== Startup Profile ==\n{report}");
+}
+
 #[allow(clippy::print_stdout, clippy::print_stderr)]
 fn cleanup_session_worktrees_and_print() {
     let pid = std::process::id();
@@ -1328,6 +1447,69 @@ pub fn get_login_status(config: &Config) -> LoginStatus {
     }
 }
 
+/// Upgrades an older `config.toml` layout before the config is loaded and
+/// tells the user what changed.
+#[allow(clippy::print_stderr)]
+fn report_config_migration(code_home: &Path) {
+    match code_core::config::migrate_config_file(code_home, false) {
+        Ok(Some(report)) if !report.applied.is_empty() => {
+            eprintln!(
+                "Upgraded config.toml from version {} to {}:",
+                report.from_version, report.to_version
+            );
+            for summary in &report.applied {
+                eprintln!("  - {summary}");
+            }
+            if let Some(backup) = &report.backup_path {
+                eprintln!("  previous file saved as {}", backup.display());
+            }
+        }
+        Ok(_) => {}
+        Err(err) => {
+            tracing::warn!("config.toml migration failed: {err}");
+            eprintln!("Warning: could not upgrade config.toml: {err}");
+        }
+    }
+}
+
+/// Asks once per repository whether its `.code/config.toml` may be merged over
+/// the user config, and records the answer. Runs before the TUI takes over the
+/// terminal; without an interactive terminal the project config stays ignored.
+#[allow(clippy::print_stdout)]
+fn maybe_prompt_for_project_config(code_home: &Path, cwd: &Path) {
+    use std::io::IsTerminal;
+    use std::io::Write;
+
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return;
+    }
+    let files = match code_core::config_loader::pending_project_config_files(code_home, cwd) {
+        Ok(files) if !files.is_empty() => files,
+        Ok(_) => return,
+        Err(err) => {
+            tracing::warn!("failed to inspect project config layers: {err}");
+            return;
+        }
+    };
+
+    println!("This repository provides shared Code settings:");
+    for file in &files {
+        println!("  {}", file.display());
+    }
+    println!("Model selection and attempts to disable approvals or the sandbox are ignored.");
+    print!("Apply these settings whenever Code runs in this repository? [y/N] ");
+    let _ = std::io::stdout().flush();
+
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return;
+    }
+    let allowed = matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes");
+    if let Err(err) = code_core::config::set_project_config_allowed(code_home, cwd, allowed) {
+        tracing::warn!("failed to record project config decision: {err}");
+    }
+}
+
 /// Determine if user has configured a sandbox / approval policy,
 /// or if the current cwd project is trusted, and updates the config
 /// accordingly.
@@ -1430,6 +1612,9 @@ mod tests {
                 always_allow_commands: None,
                 hooks: vec![],
                 commands: vec![],
+                allow_project_config: None,
+                auto_review: None,
+                environment_context: None,
             },
         );
         config_toml.projects = Some(projects);
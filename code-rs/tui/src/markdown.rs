@@ -43,6 +43,25 @@ pub(crate) fn append_markdown_with_opener_and_cwd_and_bold(
     cwd: &Path,
     bold_first_sentence: bool,
 ) {
+    append_markdown_continuing(markdown_source, lines, file_opener, cwd, bold_first_sentence, false);
+}
+
+/// Renders `markdown_source` as the continuation of earlier source that was
+/// rendered separately. `leading_text_bolded` says whether the text block the
+/// source opens in already had its first sentence styled. Returns the same
+/// flag for the text block the source ends in.
+pub(crate) fn append_markdown_continuing(
+    markdown_source: &str,
+    lines: &mut Vec<Line<'static>>,
+    file_opener: UriBasedFileOpener,
+    cwd: &Path,
+    bold_first_sentence: bool,
+    leading_text_bolded: bool,
+) -> bool {
+    let mut text_bolded = leading_text_bolded;
+    // Turn `$…$` / `$$…$$` LaTeX into Unicode, or into code when it is too
+    // complex for that, before code blocks are split out.
+    let markdown_source = crate::markdown_math::rewrite_math(markdown_source);
     // Historically, we fed the entire `markdown_source` into the renderer in
     // one pass. However, fenced code blocks sometimes lost leading whitespace
     // when formatted by the markdown renderer/highlighter. To preserve code
@@ -50,7 +69,7 @@ pub(crate) fn append_markdown_with_opener_and_cwd_and_bold(
     // - Render non-code text through `tui_markdown` (with citation rewrite).
     // - Render code block content verbatim as plain lines without additional
     //   formatting, preserving leading spaces.
-    for seg in split_text_and_fences(markdown_source) {
+    for seg in split_text_and_fences(&markdown_source) {
         match seg {
             Segment::Text(s) => {
                 // Rewrite our special file citation tokens into markdown links
@@ -67,14 +86,19 @@ pub(crate) fn append_markdown_with_opener_and_cwd_and_bold(
                 // target in parentheses, which is much nicer than leaking the
                 // private-use delimiter glyphs.
                 let processed = rewrite_web_citations(&processed);
-                let rendered = if bold_first_sentence {
-                    MarkdownRenderer::render_with_bold_first_sentence(&processed)
+                let rendered = if bold_first_sentence && !text_bolded {
+                    let (rendered, bolded) =
+                        MarkdownRenderer::render_with_bold_first_sentence_tracked(&processed);
+                    text_bolded = bolded;
+                    rendered
                 } else {
                     MarkdownRenderer::render(&processed)
                 };
                 lines.extend(rendered);
             }
             Segment::Code { _lang, content, fenced } => {
+                // Text after a code block starts a fresh block.
+                text_bolded = false;
                 // Use syntect-based syntax highlighting when available, preserving exact text.
                 let lang = _lang.as_deref();
                 // Apply a solid background and pad trailing spaces so the block forms
@@ -127,6 +151,12 @@ pub(crate) fn append_markdown_with_opener_and_cwd_and_bold(
             }
         }
     }
+    text_bolded
+}
+
+/// True when `src` does not end inside a fenced or indented code block.
+pub(crate) fn ends_outside_code_block(src: &str) -> bool {
+    !matches!(split_text_and_fences(src).last(), Some(Segment::Code { .. }))
 }
 
 /// Rewrites file citations in `src` into markdown hyperlinks using the
@@ -0,0 +1,184 @@
+//! Incremental markdown layout for streaming answers.
+//!
+//! A streamed answer only ever grows at the end, and the renderer lays out
+//! each block (paragraph, list, table, code block) from its own lines. Once a
+//! blank line is followed by a new top-level block, everything before it can
+//! no longer change. `IncrementalMarkdown` keeps the rendered lines of that
+//! settled prefix and, on each delta, only lays out the trailing unsettled
+//! blocks.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use code_core::config_types::UriBasedFileOpener;
+use ratatui::text::Line;
+
+use crate::markdown;
+use crate::theme::Theme;
+
+/// How many candidate boundaries to try, newest first, before giving up and
+/// rendering the whole unsettled tail. Candidates inside an open code block
+/// fail, so this bounds the cost while a long block streams in.
+const MAX_BOUNDARY_PROBES: usize = 4;
+
+#[derive(Clone, PartialEq)]
+struct RenderContext {
+    file_opener: UriBasedFileOpener,
+    cwd: PathBuf,
+    theme: Theme,
+}
+
+pub(crate) struct IncrementalMarkdown {
+    bold_first_sentence: bool,
+    context: Option<RenderContext>,
+    settled_source: String,
+    settled_lines: Vec<Line<'static>>,
+    /// Whether the text block the settled source ends in already had its
+    /// first sentence styled.
+    settled_text_bolded: bool,
+}
+
+impl IncrementalMarkdown {
+    pub(crate) fn new(bold_first_sentence: bool) -> Self {
+        Self {
+            bold_first_sentence,
+            context: None,
+            settled_source: String::new(),
+            settled_lines: Vec::new(),
+            settled_text_bolded: false,
+        }
+    }
+
+    pub(crate) fn set_bold_first_sentence(&mut self, bold: bool) {
+        if self.bold_first_sentence != bold {
+            self.bold_first_sentence = bold;
+            self.reset();
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.settled_source.clear();
+        self.settled_lines.clear();
+        self.settled_text_bolded = false;
+    }
+
+    /// Renders `source` exactly as `append_markdown` would, reusing the lines
+    /// of any prefix settled by an earlier call.
+    pub(crate) fn render(
+        &mut self,
+        source: &str,
+        file_opener: UriBasedFileOpener,
+        cwd: &Path,
+    ) -> Vec<Line<'static>> {
+        let context = RenderContext {
+            file_opener,
+            cwd: cwd.to_path_buf(),
+            theme: crate::theme::current_theme(),
+        };
+        if self.context.as_ref() != Some(&context) || !source.starts_with(&self.settled_source) {
+            self.reset();
+        }
+        self.context = Some(context);
+
+        let settled_len = self.settled_source.len();
+        if let Some(boundary) = last_settled_boundary(&source[settled_len..]) {
+            let chunk = &source[settled_len..settled_len + boundary];
+            self.settled_text_bolded = markdown::append_markdown_continuing(
+                chunk,
+                &mut self.settled_lines,
+                file_opener,
+                cwd,
+                self.bold_first_sentence,
+                self.settled_text_bolded,
+            );
+            self.settled_source.push_str(chunk);
+        }
+
+        let mut lines = self.settled_lines.clone();
+        markdown::append_markdown_continuing(
+            &source[self.settled_source.len()..],
+            &mut lines,
+            file_opener,
+            cwd,
+            self.bold_first_sentence,
+            self.settled_text_bolded,
+        );
+        lines
+    }
+}
+
+/// Byte offset of the latest point in `src` where a new top-level block
+/// starts after a blank line, outside any code block. Text before that point
+/// renders the same no matter what follows it.
+fn last_settled_boundary(src: &str) -> Option<usize> {
+    let mut candidates = Vec::new();
+    let mut offset = 0;
+    let mut previous_blank = false;
+    for line in src.split_inclusive('\n') {
+        if !line.ends_with('\n') {
+            break;
+        }
+        if previous_blank && line.starts_with(|c: char| !c.is_whitespace()) {
+            candidates.push(offset);
+        }
+        previous_blank = line.trim().is_empty();
+        offset += line.len();
+    }
+    candidates
+        .into_iter()
+        .rev()
+        .take(MAX_BOUNDARY_PROBES)
+        .find(|&boundary| markdown::ends_outside_code_block(&src[..boundary]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn full_render(source: &str, bold: bool) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        markdown::append_markdown_with_opener_and_cwd_and_bold(
+            source,
+            &mut lines,
+            UriBasedFileOpener::None,
+            Path::new("/"),
+            bold,
+        );
+        lines
+    }
+
+    #[test]
+    fn streamed_prefixes_render_like_a_full_pass() {
+        let answer = "Intro without a stop\n\nThen a sentence. And more.\n\n- one\n- two\n\n| a | b |\n|---|---|\n| 1 | 2 |\n\n```rust\nfn main() {\n\nlet x = 1;\n}\n```\n\nAfter code. Done.\n\n    indented\n\nTail.";
+        for bold in [false, true] {
+            let mut incremental = IncrementalMarkdown::new(bold);
+            let mut end = 0;
+            while end < answer.len() {
+                end += 1;
+                while !answer.is_char_boundary(end) {
+                    end += 1;
+                }
+                let prefix = &answer[..end];
+                assert_eq!(
+                    incremental.render(prefix, UriBasedFileOpener::None, Path::new("/")),
+                    full_render(prefix, bold),
+                    "prefix {prefix:?}"
+                );
+            }
+            assert!(!incremental.settled_lines.is_empty());
+        }
+    }
+
+    #[test]
+    fn rewritten_source_starts_over() {
+        let mut incremental = IncrementalMarkdown::new(true);
+        let path = Path::new("/");
+        incremental.render("First. Para.\n\nSecond.\n", UriBasedFileOpener::None, path);
+        let rewritten = "Other. Para.\n\nSecond.\n";
+        assert_eq!(
+            incremental.render(rewritten, UriBasedFileOpener::None, path),
+            full_render(rewritten, true)
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Footnote-style links (`tui.link_style = "footnotes"`). Before an
+//! assistant answer is rendered, each web link is replaced by a numbered
+//! reference: `[docs](https://…)` reads `docs[1]` and a bare URL reads `[1]`.
+//! The URLs are listed once, in order, below a rule at the end of the answer,
+//! and Alt+1…9 opens the numbered link of the last answer. Links inside code
+//! blocks and code spans, images, and non-web links (file citations) are
+//! left as they are.
+
+use std::borrow::Cow;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use code_core::config_types::LinkStyle;
+
+static FOOTNOTES: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn configure(style: LinkStyle) {
+    FOOTNOTES.store(style == LinkStyle::Footnotes, Ordering::Relaxed);
+}
+
+pub(crate) fn footnotes_enabled() -> bool {
+    FOOTNOTES.load(Ordering::Relaxed)
+}
+
+/// `source` with its web links collapsed to numbered references and the
+/// footnote block appended. Unchanged unless footnotes are enabled.
+pub(crate) fn collapse_links(source: &str) -> Cow<'_, str> {
+    if !footnotes_enabled() || !source.contains("http") {
+        return Cow::Borrowed(source);
+    }
+    let (text, urls) = collapse(source);
+    if urls.is_empty() {
+        return Cow::Borrowed(source);
+    }
+    let footnotes: Vec<String> = urls
+        .iter()
+        .enumerate()
+        .map(|(idx, url)| format!("[{}] {url}", idx + 1))
+        .collect();
+    Cow::Owned(format!("{}\n\n---\n{}", text.trim_end(), footnotes.join("\n")))
+}
+
+/// The URLs of `source` in footnote order: `[1]` is the first.
+pub(crate) fn footnote_links(source: &str) -> Vec<String> {
+    collapse(source).1
+}
+
+fn collapse(source: &str) -> (String, Vec<String>) {
+    let mut urls: Vec<String> = Vec::new();
+    let mut out: Vec<String> = Vec::new();
+    let mut in_fence = false;
+    for line in source.split('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            out.push(line.to_string());
+        } else if in_fence {
+            out.push(line.to_string());
+        } else {
+            out.push(collapse_line(line, &mut urls));
+        }
+    }
+    (out.join("\n"), urls)
+}
+
+/// `[n]` for `url`, numbering it when it is new.
+fn reference(url: String, urls: &mut Vec<String>) -> String {
+    let number = match urls.iter().position(|known| *known == url) {
+        Some(idx) => idx + 1,
+        None => {
+            urls.push(url);
+            urls.len()
+        }
+    };
+    format!("[{number}]")
+}
+
+fn is_web_url(chars: &[char]) -> bool {
+    let prefix: String = chars.iter().take(8).collect();
+    prefix.starts_with("http://") || prefix.starts_with("https://")
+}
+
+fn collapse_line(line: &str, urls: &mut Vec<String>) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = String::with_capacity(line.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let ch = chars[i];
+        if ch == '`' {
+            // Copy code spans verbatim.
+            let run = chars[i..].iter().take_while(|c| **c == '`').count();
+            let close = (i + run..chars.len()).find(|&j| {
+                chars[j..].iter().take_while(|c| **c == '`').count() == run && chars[j - 1] != '`'
+            });
+            let end = close.map_or(chars.len(), |j| j + run);
+            out.extend(&chars[i..end]);
+            i = end;
+            continue;
+        }
+        if ch == '!'
+            && chars.get(i + 1) == Some(&'[')
+            && let Some(link) = markdown_link(&chars, i + 1)
+        {
+            // Images keep their target.
+            out.extend(&chars[i..link.end]);
+            i = link.end;
+            continue;
+        }
+        if ch == '['
+            && let Some(link) = markdown_link(&chars, i)
+        {
+            let label = &chars[i + 1..link.label_end];
+            let target = &chars[link.target.clone()];
+            if is_web_url(target) {
+                if !is_web_url(label) {
+                    out.extend(label);
+                }
+                out.push_str(&reference(target.iter().collect(), urls));
+            } else {
+                out.extend(&chars[i..link.end]);
+            }
+            i = link.end;
+            continue;
+        }
+        if ch == '<'
+            && is_web_url(&chars[i + 1..])
+            && let Some(close) = chars[i + 1..].iter().position(|c| *c == '>')
+            && !chars[i + 1..i + 1 + close].iter().any(|c| c.is_whitespace())
+        {
+            let url: String = chars[i + 1..i + 1 + close].iter().collect();
+            out.push_str(&reference(url, urls));
+            i += close + 2;
+            continue;
+        }
+        let at_word_start = i == 0 || !(chars[i - 1].is_alphanumeric() || chars[i - 1] == '/');
+        if at_word_start && is_web_url(&chars[i..]) {
+            let end = bare_url_end(&chars, i);
+            let url: String = chars[i..end].iter().collect();
+            out.push_str(&reference(url, urls));
+            i = end;
+            continue;
+        }
+        out.push(ch);
+        i += 1;
+    }
+    out
+}
+
+struct MarkdownLink {
+    /// Index of the `]` closing the label.
+    label_end: usize,
+    target: std::ops::Range<usize>,
+    /// Index just past the `)`.
+    end: usize,
+}
+
+/// A `[label](target)` link whose `[` is at `start`.
+fn markdown_link(chars: &[char], start: usize) -> Option<MarkdownLink> {
+    let label_end = (start + 1..chars.len()).find(|&j| chars[j] == ']')?;
+    if chars.get(label_end + 1) != Some(&'(') {
+        return None;
+    }
+    let target_start = label_end + 2;
+    let target_end = (target_start..chars.len()).find(|&j| chars[j] == ')')?;
+    if chars[target_start..target_end].iter().any(|c| c.is_whitespace()) {
+        return None;
+    }
+    Some(MarkdownLink {
+        label_end,
+        target: target_start..target_end,
+        end: target_end + 1,
+    })
+}
+
+/// End of a bare URL starting at `start`: up to whitespace or a delimiter,
+/// without trailing punctuation or an unbalanced closing parenthesis.
+fn bare_url_end(chars: &[char], start: usize) -> usize {
+    let mut end = (start..chars.len())
+        .find(|&j| chars[j].is_whitespace() || matches!(chars[j], '<' | '>' | '"' | '`'))
+        .unwrap_or(chars.len());
+    while end > start {
+        let last = chars[end - 1];
+        let unbalanced_paren = last == ')'
+            && chars[start..end].iter().filter(|c| **c == '(').count()
+                < chars[start..end].iter().filter(|c| **c == ')').count();
+        if matches!(last, '.' | ',' | ';' | ':' | '!' | '?' | '\'' | ']') || unbalanced_paren {
+            end -= 1;
+        } else {
+            break;
+        }
+    }
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_become_numbered_references() {
+        let (text, urls) = collapse(
+            "See [the docs](https://example.com/docs) and https://example.com/a.\n\
+             Again: <https://example.com/docs>, (https://example.com/b)",
+        );
+        assert_eq!(
+            text,
+            "See the docs[1] and [2].\nAgain: [1], ([3])"
+        );
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.com/docs",
+                "https://example.com/a",
+                "https://example.com/b",
+            ]
+        );
+    }
+
+    #[test]
+    fn code_images_and_file_links_are_kept() {
+        let source = "`curl https://example.com` ![logo](https://example.com/logo.png) \
+                      [main.rs](src/main.rs)\n```\nhttps://example.com/in-fence\n```";
+        let (text, urls) = collapse(source);
+        assert_eq!(text, source);
+        assert!(urls.is_empty());
+    }
+
+    #[test]
+    fn url_labels_are_not_repeated() {
+        let (text, urls) = collapse("[https://example.com](https://example.com/x)");
+        assert_eq!(text, "[1]");
+        assert_eq!(urls, vec!["https://example.com/x"]);
+    }
+}
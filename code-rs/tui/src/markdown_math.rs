@@ -0,0 +1,650 @@
+//! Math in assistant answers. Inline `$…$` / `\(…\)` and display
+//! `$$…$$` / `\[…\]` LaTeX is rewritten before the markdown is rendered:
+//! simple expressions become Unicode approximations (`$\alpha^2 \leq
+//! \frac{1}{2}$` reads `α² ≤ ½`), and anything the converter does not know
+//! is kept as LaTeX, in a `latex` fence for display math and in a code span
+//! for inline math.
+//!
+//! A `$` only opens inline math when it is followed by a non-space, and only
+//! closes it when it is preceded by a non-space and not followed by a digit,
+//! so prices such as "$5 and $10" stay as they are.
+
+use std::borrow::Cow;
+
+/// Rewrites the math in `source`, leaving fenced code blocks and inline code
+/// spans untouched.
+pub(crate) fn rewrite_math(source: &str) -> Cow<'_, str> {
+    if !source.contains('$') && !source.contains("\\(") && !source.contains("\\[") {
+        return Cow::Borrowed(source);
+    }
+    let lines: Vec<&str> = source.split('\n').collect();
+    let mut out: Vec<String> = Vec::with_capacity(lines.len());
+    let mut in_fence = false;
+    let mut idx = 0;
+    while idx < lines.len() {
+        let line = lines[idx];
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            out.push(line.to_string());
+            idx += 1;
+            continue;
+        }
+        if in_fence {
+            out.push(line.to_string());
+            idx += 1;
+            continue;
+        }
+        if let Some((consumed, rendered)) = display_math(&lines[idx..]) {
+            out.extend(rendered);
+            idx += consumed;
+            continue;
+        }
+        out.push(rewrite_inline_math(line));
+        idx += 1;
+    }
+    Cow::Owned(out.join("\n"))
+}
+
+/// A display math block starting at `lines[0]`: the number of lines it
+/// spans and the lines to render instead. `None` when the first line does
+/// not open one, or the block is not closed yet.
+fn display_math(lines: &[&str]) -> Option<(usize, Vec<String>)> {
+    let first = lines.first()?.trim();
+    let (open, close) = if first.starts_with("$$") {
+        ("$$", "$$")
+    } else if first.starts_with("\\[") {
+        ("\\[", "\\]")
+    } else {
+        return None;
+    };
+    let after_open = &first[open.len()..];
+    let mut body: Vec<&str> = Vec::new();
+    let mut consumed = 0;
+    if let Some(end) = after_open.find(close) {
+        if !after_open[end + close.len()..].trim().is_empty() {
+            // `$$a$$ and more` is inline math within a paragraph.
+            return None;
+        }
+        body.push(&after_open[..end]);
+        consumed = 1;
+    } else {
+        body.push(after_open);
+        for (offset, line) in lines.iter().enumerate().skip(1) {
+            let trimmed = line.trim();
+            if let Some(end) = trimmed.find(close) {
+                if !trimmed[end + close.len()..].trim().is_empty() {
+                    return None;
+                }
+                body.push(&trimmed[..end]);
+                consumed = offset + 1;
+                break;
+            }
+            body.push(line);
+        }
+        if consumed == 0 {
+            return None;
+        }
+    }
+    let body: Vec<&str> = body
+        .into_iter()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    if body.is_empty() {
+        return None;
+    }
+    let rendered = match latex_to_unicode(&body.join(" ")) {
+        Some(text) => vec![text],
+        None => {
+            let mut fence = vec!["```latex".to_string()];
+            fence.extend(body.iter().map(|line| (*line).to_string()));
+            fence.push("```".to_string());
+            fence
+        }
+    };
+    Some((consumed, rendered))
+}
+
+/// Rewrites `$…$`, `$$…$$` and `\(…\)` within one line of text.
+fn rewrite_inline_math(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = String::with_capacity(line.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let ch = chars[i];
+        if ch == '`' {
+            // Copy code spans verbatim.
+            let run = chars[i..].iter().take_while(|c| **c == '`').count();
+            let close = (i + run..chars.len()).find(|&j| {
+                chars[j..].iter().take_while(|c| **c == '`').count() == run && chars[j - 1] != '`'
+            });
+            let end = close.map_or(chars.len(), |j| j + run);
+            out.extend(&chars[i..end]);
+            i = end;
+            continue;
+        }
+        if ch == '\\' && chars.get(i + 1) == Some(&'(') {
+            if let Some(end) = find_sequence(&chars, i + 2, &['\\', ')']) {
+                let latex: String = chars[i + 2..end].iter().collect();
+                out.push_str(&inline_replacement(&latex, &chars[i..end + 2]));
+                i = end + 2;
+                continue;
+            }
+        } else if ch == '\\' && chars.get(i + 1) == Some(&'$') {
+            out.push_str("\\$");
+            i += 2;
+            continue;
+        } else if ch == '$' {
+            let delimiter = if chars.get(i + 1) == Some(&'$') { 2 } else { 1 };
+            if let Some(end) = closing_dollar(&chars, i + delimiter, delimiter) {
+                let latex: String = chars[i + delimiter..end].iter().collect();
+                out.push_str(&inline_replacement(&latex, &chars[i..end + delimiter]));
+                i = end + delimiter;
+                continue;
+            }
+        }
+        out.push(ch);
+        i += 1;
+    }
+    out
+}
+
+fn find_sequence(chars: &[char], from: usize, needle: &[char]) -> Option<usize> {
+    (from..chars.len()).find(|&j| chars[j..].starts_with(needle))
+}
+
+/// Position of the `$` (or `$$`) closing math that opens at `start`.
+fn closing_dollar(chars: &[char], start: usize, delimiter: usize) -> Option<usize> {
+    if chars.get(start).is_none_or(|c| c.is_whitespace()) {
+        return None;
+    }
+    let mut j = start + 1;
+    while j < chars.len() {
+        match chars[j] {
+            '\\' => j += 2,
+            '$' => {
+                let run = chars[j..].iter().take_while(|c| **c == '$').count();
+                let closes = run == delimiter
+                    && !chars[j - 1].is_whitespace()
+                    && !chars
+                        .get(j + delimiter)
+                        .is_some_and(char::is_ascii_digit);
+                if closes {
+                    return Some(j);
+                }
+                j += run;
+            }
+            _ => j += 1,
+        }
+    }
+    None
+}
+
+fn inline_replacement(latex: &str, original: &[char]) -> String {
+    if let Some(text) = latex_to_unicode(latex) {
+        return text;
+    }
+    let original: String = original.iter().collect();
+    if original.contains('`') {
+        original
+    } else {
+        format!("`{original}`")
+    }
+}
+
+/// Converts simple LaTeX math to Unicode. `None` when the input uses
+/// environments, alignment or commands without a Unicode approximation.
+fn latex_to_unicode(latex: &str) -> Option<String> {
+    let mut parser = Parser {
+        chars: latex.chars().collect(),
+        pos: 0,
+    };
+    let text = parser.sequence(false)?;
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    // Left-over markdown markers would be picked up by the renderer.
+    if text.is_empty() || text.contains(['*', '_', '`']) {
+        return None;
+    }
+    Some(text)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    /// Parses until the end of input, or the closing `}` when `in_group`.
+    fn sequence(&mut self, in_group: bool) -> Option<String> {
+        let mut out = String::new();
+        while let Some(ch) = self.peek() {
+            self.pos += 1;
+            match ch {
+                '}' if in_group => return Some(out),
+                '}' => return None,
+                '{' => out.push_str(&self.sequence(true)?),
+                '\\' => out.push_str(&self.command()?),
+                '^' => {
+                    let arg = self.argument()?;
+                    out.push_str(&script(&arg, superscript_char).unwrap_or_else(|| {
+                        if arg.chars().count() == 1 {
+                            format!("^{arg}")
+                        } else {
+                            format!("^({arg})")
+                        }
+                    }));
+                }
+                '_' => {
+                    let arg = self.argument()?;
+                    out.push_str(&script(&arg, subscript_char)?);
+                }
+                '&' | '#' | '%' => return None,
+                '\'' => out.push('′'),
+                '-' => out.push('−'),
+                '*' => out.push('∗'),
+                other if other.is_whitespace() => out.push(' '),
+                other => out.push(other),
+            }
+        }
+        if in_group { None } else { Some(out) }
+    }
+
+    /// A command argument: a `{group}`, a command or a single character.
+    fn argument(&mut self) -> Option<String> {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+        let ch = self.peek()?;
+        self.pos += 1;
+        match ch {
+            '{' => self.sequence(true),
+            '\\' => self.command(),
+            '}' | '^' | '_' => None,
+            other => Some(other.to_string()),
+        }
+    }
+
+    /// Raw text of a `{group}`, for `\text{…}` and friends.
+    fn text_argument(&mut self) -> Option<String> {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+        if self.peek()? != '{' {
+            return self.argument();
+        }
+        self.pos += 1;
+        let start = self.pos;
+        let end = start + self.chars[start..].iter().position(|c| *c == '}')?;
+        self.pos = end + 1;
+        Some(self.chars[start..end].iter().collect())
+    }
+
+    fn command(&mut self) -> Option<String> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            let ch = self.peek()?;
+            self.pos += 1;
+            return match ch {
+                ',' | ';' | ':' | ' ' => Some(" ".to_string()),
+                '!' => Some(String::new()),
+                '|' => Some("‖".to_string()),
+                '{' | '}' | '$' | '%' | '&' | '#' => Some(ch.to_string()),
+                _ => None,
+            };
+        }
+        let name: String = self.chars[start..self.pos].iter().collect();
+        match name.as_str() {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.argument()?;
+                let denominator = self.argument()?;
+                Some(fraction(&numerator, &denominator))
+            }
+            "sqrt" => {
+                let root = if self.peek() == Some('[') {
+                    let end = self.chars[self.pos..].iter().position(|c| *c == ']')?;
+                    let index: String = self.chars[self.pos + 1..self.pos + end].iter().collect();
+                    self.pos += end + 1;
+                    match index.trim() {
+                        "3" => '∛',
+                        "4" => '∜',
+                        _ => return None,
+                    }
+                } else {
+                    '√'
+                };
+                let arg = self.argument()?;
+                Some(format!("{root}{}", grouped(&arg)))
+            }
+            "text" | "textrm" | "textit" | "textbf" | "mathrm" | "mathit" | "mathbf"
+            | "mathsf" | "mathtt" | "operatorname" | "mbox" => self.text_argument(),
+            "hat" | "bar" | "overline" | "vec" | "dot" | "ddot" | "tilde" => {
+                let arg = self.argument()?;
+                let mark = match name.as_str() {
+                    "hat" => '\u{302}',
+                    "bar" | "overline" => '\u{304}',
+                    "vec" => '\u{20d7}',
+                    "dot" => '\u{307}',
+                    "ddot" => '\u{308}',
+                    _ => '\u{303}',
+                };
+                let mut chars = arg.chars();
+                let base = chars.next()?;
+                chars.next().is_none().then(|| format!("{base}{mark}"))
+            }
+            "left" | "right" | "bigl" | "bigr" | "Bigl" | "Bigr" | "big" | "Big" => {
+                // The delimiter that follows is rendered as is; `.` means none.
+                if self.peek() == Some('.') {
+                    self.pos += 1;
+                }
+                Some(String::new())
+            }
+            "displaystyle" | "textstyle" | "limits" | "nolimits" => Some(String::new()),
+            "quad" | "qquad" => Some(" ".to_string()),
+            "sin" | "cos" | "tan" | "cot" | "sec" | "csc" | "log" | "ln" | "exp" | "lim"
+            | "min" | "max" | "sup" | "inf" | "det" | "arg" | "deg" | "gcd" | "Pr" => {
+                Some(format!("{name} "))
+            }
+            other => symbol(other).map(str::to_string),
+        }
+    }
+}
+
+/// Wraps multi-term expressions in parentheses, e.g. for `√(x+1)`.
+fn grouped(expr: &str) -> String {
+    let simple = expr.chars().count() == 1
+        || expr.chars().all(|c| c.is_alphanumeric() || c == '.');
+    if simple {
+        expr.to_string()
+    } else {
+        format!("({expr})")
+    }
+}
+
+fn fraction(numerator: &str, denominator: &str) -> String {
+    let vulgar = match (numerator.trim(), denominator.trim()) {
+        ("1", "2") => Some('½'),
+        ("1", "3") => Some('⅓'),
+        ("2", "3") => Some('⅔'),
+        ("1", "4") => Some('¼'),
+        ("3", "4") => Some('¾'),
+        ("1", "8") => Some('⅛'),
+        _ => None,
+    };
+    match vulgar {
+        Some(ch) => ch.to_string(),
+        None => format!("{}/{}", grouped(numerator.trim()), grouped(denominator.trim())),
+    }
+}
+
+/// Maps every character of `text` with `map`, or `None` when one of them has
+/// no script form.
+fn script(text: &str, map: fn(char) -> Option<char>) -> Option<String> {
+    text.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(map)
+        .collect()
+}
+
+fn superscript_char(ch: char) -> Option<char> {
+    let mapped = match ch {
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4' => '⁴',
+        '5' => '⁵',
+        '6' => '⁶',
+        '7' => '⁷',
+        '8' => '⁸',
+        '9' => '⁹',
+        '+' => '⁺',
+        '-' | '−' => '⁻',
+        '=' => '⁼',
+        '(' => '⁽',
+        ')' => '⁾',
+        'a' => 'ᵃ',
+        'b' => 'ᵇ',
+        'c' => 'ᶜ',
+        'd' => 'ᵈ',
+        'e' => 'ᵉ',
+        'f' => 'ᶠ',
+        'g' => 'ᵍ',
+        'h' => 'ʰ',
+        'i' => 'ⁱ',
+        'j' => 'ʲ',
+        'k' => 'ᵏ',
+        'l' => 'ˡ',
+        'm' => 'ᵐ',
+        'n' => 'ⁿ',
+        'o' => 'ᵒ',
+        'p' => 'ᵖ',
+        'r' => 'ʳ',
+        's' => 'ˢ',
+        't' => 'ᵗ',
+        'u' => 'ᵘ',
+        'v' => 'ᵛ',
+        'w' => 'ʷ',
+        'x' => 'ˣ',
+        'y' => 'ʸ',
+        'z' => 'ᶻ',
+        'T' => 'ᵀ',
+        '′' => '′',
+        _ => return None,
+    };
+    Some(mapped)
+}
+
+fn subscript_char(ch: char) -> Option<char> {
+    let mapped = match ch {
+        '0' => '₀',
+        '1' => '₁',
+        '2' => '₂',
+        '3' => '₃',
+        '4' => '₄',
+        '5' => '₅',
+        '6' => '₆',
+        '7' => '₇',
+        '8' => '₈',
+        '9' => '₉',
+        '+' => '₊',
+        '-' | '−' => '₋',
+        '=' => '₌',
+        '(' => '₍',
+        ')' => '₎',
+        'a' => 'ₐ',
+        'e' => 'ₑ',
+        'h' => 'ₕ',
+        'i' => 'ᵢ',
+        'j' => 'ⱼ',
+        'k' => 'ₖ',
+        'l' => 'ₗ',
+        'm' => 'ₘ',
+        'n' => 'ₙ',
+        'o' => 'ₒ',
+        'p' => 'ₚ',
+        'r' => 'ᵣ',
+        's' => 'ₛ',
+        't' => 'ₜ',
+        'u' => 'ᵤ',
+        'v' => 'ᵥ',
+        'x' => 'ₓ',
+        _ => return None,
+    };
+    Some(mapped)
+}
+
+fn symbol(name: &str) -> Option<&'static str> {
+    let symbol = match name {
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" => "ϵ",
+        "varepsilon" => "ε",
+        "zeta" => "ζ",
+        "eta" => "η",
+        "theta" => "θ",
+        "vartheta" => "ϑ",
+        "iota" => "ι",
+        "kappa" => "κ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "nu" => "ν",
+        "xi" => "ξ",
+        "pi" => "π",
+        "rho" => "ρ",
+        "sigma" => "σ",
+        "tau" => "τ",
+        "upsilon" => "υ",
+        "phi" => "ϕ",
+        "varphi" => "φ",
+        "chi" => "χ",
+        "psi" => "ψ",
+        "omega" => "ω",
+        "Gamma" => "Γ",
+        "Delta" => "Δ",
+        "Theta" => "Θ",
+        "Lambda" => "Λ",
+        "Xi" => "Ξ",
+        "Pi" => "Π",
+        "Sigma" => "Σ",
+        "Phi" => "Φ",
+        "Psi" => "Ψ",
+        "Omega" => "Ω",
+        "times" => "×",
+        "cdot" => "·",
+        "div" => "÷",
+        "pm" => "±",
+        "mp" => "∓",
+        "ast" => "∗",
+        "leq" | "le" => "≤",
+        "geq" | "ge" => "≥",
+        "neq" | "ne" => "≠",
+        "approx" => "≈",
+        "equiv" => "≡",
+        "sim" => "∼",
+        "simeq" => "≃",
+        "propto" => "∝",
+        "ll" => "≪",
+        "gg" => "≫",
+        "infty" => "∞",
+        "sum" => "∑",
+        "prod" => "∏",
+        "int" => "∫",
+        "oint" => "∮",
+        "partial" => "∂",
+        "nabla" => "∇",
+        "to" | "rightarrow" => "→",
+        "leftarrow" | "gets" => "←",
+        "Rightarrow" | "implies" => "⇒",
+        "Leftarrow" => "⇐",
+        "Leftrightarrow" | "iff" => "⇔",
+        "leftrightarrow" => "↔",
+        "mapsto" => "↦",
+        "in" => "∈",
+        "notin" => "∉",
+        "ni" => "∋",
+        "subset" => "⊂",
+        "subseteq" => "⊆",
+        "supset" => "⊃",
+        "supseteq" => "⊇",
+        "cup" => "∪",
+        "cap" => "∩",
+        "setminus" => "∖",
+        "emptyset" | "varnothing" => "∅",
+        "forall" => "∀",
+        "exists" => "∃",
+        "neg" | "lnot" => "¬",
+        "land" | "wedge" => "∧",
+        "lor" | "vee" => "∨",
+        "oplus" => "⊕",
+        "otimes" => "⊗",
+        "circ" => "∘",
+        "bullet" => "•",
+        "ldots" | "dots" => "…",
+        "cdots" => "⋯",
+        "vdots" => "⋮",
+        "ddots" => "⋱",
+        "mid" => "|",
+        "vert" => "|",
+        "Vert" => "‖",
+        "langle" => "⟨",
+        "rangle" => "⟩",
+        "lfloor" => "⌊",
+        "rfloor" => "⌋",
+        "lceil" => "⌈",
+        "rceil" => "⌉",
+        "perp" => "⊥",
+        "parallel" => "∥",
+        "angle" => "∠",
+        "degree" => "°",
+        "prime" => "′",
+        "hbar" => "ℏ",
+        "ell" => "ℓ",
+        "Re" => "ℜ",
+        "Im" => "ℑ",
+        "aleph" => "ℵ",
+        _ => return None,
+    };
+    Some(symbol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simple_latex_becomes_unicode() {
+        assert_eq!(latex_to_unicode(r"\alpha^2 \leq \frac{1}{2}").as_deref(), Some("α² ≤ ½"));
+        assert_eq!(latex_to_unicode(r"x_i + y_{n+1}").as_deref(), Some("xᵢ + yₙ₊₁"));
+        assert_eq!(
+            latex_to_unicode(r"\sqrt{x+1} \cdot \frac{a+b}{c}").as_deref(),
+            Some("√(x+1) · (a+b)/c")
+        );
+        assert_eq!(
+            latex_to_unicode(r"\sum_{i=1}^{n} x_i").as_deref(),
+            Some("∑ᵢ₌₁ⁿ xᵢ")
+        );
+        assert_eq!(latex_to_unicode(r"\text{loss} = -\log p").as_deref(), Some("loss = −log p"));
+    }
+
+    #[test]
+    fn unknown_constructs_are_left_as_latex() {
+        assert_eq!(latex_to_unicode(r"\begin{pmatrix} a & b \end{pmatrix}"), None);
+        assert_eq!(latex_to_unicode(r"\mathbb{R}^n"), None);
+        assert_eq!(latex_to_unicode(r"x_{\beta}"), None);
+        assert_eq!(
+            rewrite_inline_math(r"where $\mathbb{R}$ is real"),
+            r"where `$\mathbb{R}$` is real"
+        );
+    }
+
+    #[test]
+    fn inline_math_skips_prices_and_code() {
+        assert_eq!(rewrite_inline_math("costs $5 and $10 today"), "costs $5 and $10 today");
+        assert_eq!(
+            rewrite_inline_math(r"with $\beta = 0.9$ and `$HOME`"),
+            "with β = 0.9 and `$HOME`"
+        );
+        assert_eq!(rewrite_inline_math(r"so \(e^{i\pi}\) holds"), "so e^(iπ) holds");
+    }
+
+    #[test]
+    fn display_math_is_converted_or_fenced() {
+        let source = "Before\n$$\n\\frac{3}{4} \\times \\pi\n$$\n\\[\n\\begin{cases} 1 & x > 0 \\end{cases}\n\\]\n```\n$x$\n```";
+        assert_eq!(
+            rewrite_math(source),
+            "Before\n¾ × π\n```latex\n\\begin{cases} 1 & x > 0 \\end{cases}\n```\n```\n$x$\n```"
+        );
+        // An unclosed block is left alone until the rest of it streams in.
+        assert_eq!(rewrite_math("$$\nx^2"), "$$\nx^2");
+    }
+}
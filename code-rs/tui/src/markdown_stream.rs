@@ -1,11 +1,12 @@
 #![allow(dead_code)]
 
+use std::cell::RefCell;
 use std::collections::VecDeque;
 
 use code_core::config::Config;
 use ratatui::text::Line;
 
-use crate::markdown;
+use crate::markdown_incremental::IncrementalMarkdown;
 use crate::render::markdown_utils::is_inside_unclosed_fence;
 use crate::render::markdown_utils::strip_empty_fenced_code_blocks;
 
@@ -21,6 +22,9 @@ pub(crate) struct MarkdownStreamCollector {
     // Tracks whether we've already evaluated the leading bullet prefix.
     // None => undecided, Some(true) => removed, Some(false) => left intact.
     leading_bullet_state: Option<bool>,
+    // Keeps the layout of settled blocks between renders; preview rendering
+    // only has `&self`.
+    renderer: RefCell<IncrementalMarkdown>,
 }
 
 
@@ -32,6 +36,7 @@ impl MarkdownStreamCollector {
             bold_first_sentence: false,
             pending_section_break: false,
             leading_bullet_state: None,
+            renderer: RefCell::new(IncrementalMarkdown::new(false)),
         }
     }
 
@@ -42,11 +47,13 @@ impl MarkdownStreamCollector {
             bold_first_sentence: true,
             pending_section_break: false,
             leading_bullet_state: None,
+            renderer: RefCell::new(IncrementalMarkdown::new(true)),
         }
     }
 
     pub fn set_bold_first_sentence(&mut self, bold: bool) {
         self.bold_first_sentence = bold;
+        self.renderer.get_mut().set_bold_first_sentence(bold);
     }
 
     /// Returns the number of logical lines that have already been committed
@@ -65,6 +72,7 @@ impl MarkdownStreamCollector {
         // Keep bold_first_sentence setting
         self.pending_section_break = false;
         self.leading_bullet_state = None;
+        self.renderer.get_mut().reset();
     }
 
     /// Replace the buffered content and mark that the first `committed_count`
@@ -168,12 +176,7 @@ impl MarkdownStreamCollector {
         let source = unwrap_markdown_language_fence_if_enabled(self.buffer.clone());
         let source = strip_empty_fenced_code_blocks(&source);
 
-        let mut rendered: Vec<Line<'static>> = Vec::new();
-        if self.bold_first_sentence {
-            markdown::append_markdown_with_bold_first(&source, &mut rendered, config);
-        } else {
-            markdown::append_markdown(&source, &mut rendered, config);
-        }
+        let rendered = self.render(&source, config);
 
         let mut complete_line_count = rendered.len();
         if complete_line_count > 0 {
@@ -291,12 +294,7 @@ impl MarkdownStreamCollector {
         let source = unwrap_markdown_language_fence_if_enabled(self.buffer.clone());
         let source = strip_empty_fenced_code_blocks(&source);
 
-        let mut rendered: Vec<Line<'static>> = Vec::new();
-        if self.bold_first_sentence {
-            markdown::append_markdown_with_bold_first(&source, &mut rendered, config);
-        } else {
-            markdown::append_markdown(&source, &mut rendered, config);
-        }
+        let rendered = self.render(&source, config);
         if self.committed_line_count >= rendered.len() {
             return Vec::new();
         }
@@ -341,12 +339,7 @@ impl MarkdownStreamCollector {
         let source = unwrap_markdown_language_fence_if_enabled(source);
         let source = strip_empty_fenced_code_blocks(&source);
 
-        let mut rendered: Vec<Line<'static>> = Vec::new();
-        if self.bold_first_sentence {
-            markdown::append_markdown_with_bold_first(&source, &mut rendered, config);
-        } else {
-            markdown::append_markdown(&source, &mut rendered, config);
-        }
+        let rendered = self.render(&source, config);
 
         let out = if self.committed_line_count >= rendered.len() {
             Vec::new()
@@ -382,13 +375,13 @@ impl MarkdownStreamCollector {
     pub fn render_preview_lines(&self, config: &Config) -> Vec<Line<'static>> {
         let source = unwrap_markdown_language_fence_if_enabled(self.buffer.clone());
         let source = strip_empty_fenced_code_blocks(&source);
-        let mut rendered: Vec<Line<'static>> = Vec::new();
-        if self.bold_first_sentence {
-            markdown::append_markdown_with_bold_first(&source, &mut rendered, config);
-        } else {
-            markdown::append_markdown(&source, &mut rendered, config);
-        }
-        rendered
+        self.render(&source, config)
+    }
+
+    fn render(&self, source: &str, config: &Config) -> Vec<Line<'static>> {
+        self.renderer
+            .borrow_mut()
+            .render(source, config.file_opener, &config.cwd)
     }
 }
 
@@ -0,0 +1,473 @@
+//! Markdown pipe tables. The markdown renderer lays a table out at its
+//! natural width; assistant answers are laid out again for the width they are
+//! drawn at ([`MarkdownTable::layout`]). Columns shrink towards their longest
+//! word and cells wrap. When even that does not fit, as on a phone screen,
+//! the table shows the columns that fit from the current horizontal scroll
+//! position, which Alt+←/→ moves, and says which columns are shown.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use unicode_width::UnicodeWidthStr;
+
+/// Space between two columns.
+const COLUMN_GAP: usize = 2;
+
+/// Narrowest a column is squeezed to, unless its content is narrower.
+const MIN_COLUMN_WIDTH: usize = 6;
+
+/// Words longer than this are broken rather than widening their column.
+const MAX_UNBROKEN_WORD: usize = 16;
+
+/// First column shown by tables too wide for the screen.
+static COLUMN_OFFSET: AtomicUsize = AtomicUsize::new(0);
+
+/// Furthest the column scroll is useful: the last column of the widest table
+/// laid out so far that did not fit.
+static MAX_COLUMN_OFFSET: AtomicUsize = AtomicUsize::new(0);
+
+pub(crate) fn column_offset() -> usize {
+    COLUMN_OFFSET.load(Ordering::Relaxed)
+}
+
+/// Moves the horizontal table scroll by `delta` columns. Returns whether it
+/// moved.
+pub(crate) fn scroll_columns(delta: isize) -> bool {
+    let current = column_offset();
+    let next = current
+        .saturating_add_signed(delta)
+        .min(MAX_COLUMN_OFFSET.load(Ordering::Relaxed));
+    COLUMN_OFFSET.store(next, Ordering::Relaxed);
+    next != current
+}
+
+/// Puts the horizontal table scroll back at the first column. Returns
+/// whether it moved.
+pub(crate) fn reset_column_offset() -> bool {
+    COLUMN_OFFSET.swap(0, Ordering::Relaxed) != 0
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Align {
+    Left,
+    Right,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct MarkdownTable {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+    aligns: Vec<Align>,
+}
+
+/// Markdown split around its tables.
+#[derive(Debug, PartialEq)]
+pub(crate) enum MarkdownPiece {
+    Markdown(String),
+    Table(MarkdownTable),
+}
+
+/// Splits `source` into tables and the markdown between them. Tables inside
+/// fenced code blocks are left alone.
+pub(crate) fn split_tables(source: &str) -> Vec<MarkdownPiece> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut pieces = Vec::new();
+    let mut text: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+    let mut idx = 0;
+    while idx < lines.len() {
+        let line = lines[idx];
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        } else if !in_fence
+            && let Some((consumed, table)) = parse_table(&lines[idx..])
+        {
+            if !text.is_empty() {
+                pieces.push(MarkdownPiece::Markdown(text.join("\n")));
+                text.clear();
+            }
+            pieces.push(MarkdownPiece::Table(table));
+            idx += consumed;
+            continue;
+        }
+        text.push(line);
+        idx += 1;
+    }
+    if !text.is_empty() {
+        pieces.push(MarkdownPiece::Markdown(text.join("\n")));
+    }
+    pieces
+}
+
+/// Split a row by '|' and trim spaces; drop empty edge cells from leading/trailing '|'.
+fn split_row(s: &str) -> Vec<String> {
+    let mut parts: Vec<String> = s.split('|').map(|x| x.trim().to_string()).collect();
+    if parts.first().is_some_and(String::is_empty) {
+        parts.remove(0);
+    }
+    if parts.last().is_some_and(String::is_empty) {
+        parts.pop();
+    }
+    parts
+}
+
+/// Separator segments split on runs of 2+ spaces, for tables whose
+/// separator row has no pipes.
+fn split_spaced_separator(sep_line: &str) -> Vec<String> {
+    let mut segs: Vec<String> = Vec::new();
+    let mut cur = String::new();
+    let mut space_run = 0;
+    for ch in sep_line.chars() {
+        if ch == ' ' {
+            space_run += 1;
+        } else {
+            space_run = 0;
+        }
+        if space_run >= 2 {
+            if !cur.trim().is_empty() {
+                segs.push(cur.trim().to_string());
+            }
+            cur.clear();
+            space_run = 0;
+        } else {
+            cur.push(ch);
+        }
+    }
+    if !cur.trim().is_empty() {
+        segs.push(cur.trim().to_string());
+    }
+    segs
+}
+
+/// Parses a markdown pipe table starting at `lines[0]`. Returns the number of
+/// lines it spans and the table.
+pub(crate) fn parse_table(lines: &[&str]) -> Option<(usize, MarkdownTable)> {
+    if lines.len() < 2 {
+        return None;
+    }
+    let header_line = lines[0].trim();
+    let sep_line = lines[1].trim();
+    if !header_line.contains('|') {
+        return None;
+    }
+
+    let header = split_row(header_line);
+    if header.is_empty() {
+        return None;
+    }
+
+    // The separator is either pipe-based or dashed segments separated by 2+ spaces.
+    let has_pipe_sep = sep_line.contains('|');
+    let sep_segments = if has_pipe_sep {
+        split_row(sep_line)
+    } else {
+        split_spaced_separator(sep_line)
+    };
+    if sep_segments.len() < header.len() {
+        return None;
+    }
+    let valid_sep = sep_segments.iter().take(header.len()).all(|c| {
+        let core = c.replace(':', "");
+        !core.is_empty() && core.chars().all(|ch| ch == '-')
+    });
+    if !valid_sep {
+        return None;
+    }
+
+    // Collect body rows until a non-table line.
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut idx = 2usize;
+    while idx < lines.len() {
+        let raw = lines[idx];
+        if !raw.contains('|') {
+            break;
+        }
+        let row = split_row(raw);
+        if row.is_empty() {
+            break;
+        }
+        rows.push(row);
+        idx += 1;
+    }
+
+    let cols = header.len().max(rows.iter().map(Vec::len).max().unwrap_or(0));
+    // Column alignment comes from colons in a pipe separator; otherwise
+    // numeric-only columns are right-aligned.
+    let aligns = (0..cols)
+        .map(|i| {
+            if has_pipe_sep {
+                let seg = sep_segments.get(i).map(String::as_str).unwrap_or("");
+                if seg.ends_with(':') && !seg.starts_with(':') {
+                    Align::Right
+                } else {
+                    Align::Left
+                }
+            } else if rows
+                .iter()
+                .all(|r| r.get(i).is_none_or(|c| is_numeric(c)))
+            {
+                Align::Right
+            } else {
+                Align::Left
+            }
+        })
+        .collect();
+
+    Some((idx, MarkdownTable { header, rows, aligns }))
+}
+
+fn is_numeric(s: &str) -> bool {
+    let t = s.trim();
+    if t.is_empty() {
+        return true;
+    }
+    let mut has_digit = false;
+    for ch in t.chars() {
+        if ch.is_ascii_digit() {
+            has_digit = true;
+            continue;
+        }
+        if matches!(ch, '+' | '-' | '.' | ',') {
+            continue;
+        }
+        return false;
+    }
+    has_digit
+}
+
+fn pad_cell(s: &str, w: usize, align: Align) -> String {
+    let len = UnicodeWidthStr::width(s);
+    if len >= w {
+        return s.to_string();
+    }
+    let pad = w - len;
+    match align {
+        Align::Left => format!("{s}{}", " ".repeat(pad)),
+        Align::Right => format!("{}{s}", " ".repeat(pad)),
+    }
+}
+
+impl MarkdownTable {
+    fn columns(&self) -> usize {
+        self.aligns.len()
+    }
+
+    fn cell(&self, row: Option<usize>, col: usize) -> &str {
+        let cells = match row {
+            Some(row) => &self.rows[row],
+            None => &self.header,
+        };
+        cells.get(col).map(String::as_str).unwrap_or("")
+    }
+
+    fn row_indices(&self) -> impl Iterator<Item = Option<usize>> + '_ {
+        std::iter::once(None).chain((0..self.rows.len()).map(Some))
+    }
+
+    fn natural_widths(&self) -> Vec<usize> {
+        (0..self.columns())
+            .map(|col| {
+                self.row_indices()
+                    .map(|row| UnicodeWidthStr::width(self.cell(row, col)))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
+    /// Width a column can shrink to: its longest word, within limits.
+    fn min_widths(&self, natural: &[usize]) -> Vec<usize> {
+        (0..self.columns())
+            .map(|col| {
+                let longest_word = self
+                    .row_indices()
+                    .flat_map(|row| self.cell(row, col).split_whitespace())
+                    .map(UnicodeWidthStr::width)
+                    .max()
+                    .unwrap_or(0);
+                natural[col].min(longest_word.clamp(MIN_COLUMN_WIDTH, MAX_UNBROKEN_WORD))
+            })
+            .collect()
+    }
+
+    /// The table at its natural width, one line per row.
+    pub(crate) fn natural_lines(&self) -> Vec<Line<'static>> {
+        let widths = self.natural_widths();
+        let columns: Vec<usize> = (0..self.columns()).collect();
+        self.render(&columns, &widths, false)
+    }
+
+    /// The table laid out for `width` cells, starting at column `offset`
+    /// when not every column fits.
+    pub(crate) fn layout(&self, width: usize, offset: usize) -> Vec<Line<'static>> {
+        let cols = self.columns();
+        let natural = self.natural_widths();
+        let gaps = COLUMN_GAP * cols.saturating_sub(1);
+        if cols == 0 || natural.iter().sum::<usize>() + gaps <= width {
+            return self.natural_lines();
+        }
+
+        let min = self.min_widths(&natural);
+        let total_min: usize = min.iter().sum::<usize>() + gaps;
+        let (start, end) = if total_min <= width {
+            (0, cols)
+        } else {
+            MAX_COLUMN_OFFSET.fetch_max(cols - 1, Ordering::Relaxed);
+            let start = offset.min(cols - 1);
+            let mut end = start + 1;
+            let mut used = min[start];
+            while end < cols && used + COLUMN_GAP + min[end] <= width {
+                used += COLUMN_GAP + min[end];
+                end += 1;
+            }
+            (start, end)
+        };
+
+        let shown: Vec<usize> = (start..end).collect();
+        let mut widths: Vec<usize> = shown.iter().map(|&col| min[col].min(width)).collect();
+        let used: usize = widths.iter().sum::<usize>() + COLUMN_GAP * (shown.len() - 1);
+        // Hand the spare width to the columns that were squeezed the most.
+        for _ in used..width {
+            let Some((slot, _)) = shown
+                .iter()
+                .enumerate()
+                .map(|(slot, &col)| (slot, natural[col].saturating_sub(widths[slot])))
+                .filter(|(_, deficit)| *deficit > 0)
+                .max_by_key(|(_, deficit)| *deficit)
+            else {
+                break;
+            };
+            widths[slot] += 1;
+        }
+
+        let mut lines = self.render(&shown, &widths, true);
+        if shown.len() < cols {
+            let dim = Style::default().fg(crate::colors::text_dim());
+            let before = if start > 0 { "◂ " } else { "" };
+            let after = if end < cols { " ▸" } else { "" };
+            let range = if shown.len() == 1 {
+                format!("column {}", start + 1)
+            } else {
+                format!("columns {}–{end}", start + 1)
+            };
+            lines.push(Line::from(Span::styled(
+                format!("{before}{range} of {cols}{after} · Alt+←/→ scrolls"),
+                dim,
+            )));
+        }
+        lines
+    }
+
+    /// Header, separator and body rows for the `shown` columns at `widths`.
+    /// With `wrap`, cells wider than their column wrap onto more lines.
+    fn render(&self, shown: &[usize], widths: &[usize], wrap: bool) -> Vec<Line<'static>> {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let mut out: Vec<Line<'static>> = Vec::new();
+        for row in self.row_indices() {
+            let cells: Vec<Vec<String>> = shown
+                .iter()
+                .zip(widths)
+                .map(|(&col, &w)| {
+                    let text = self.cell(row, col);
+                    if wrap && UnicodeWidthStr::width(text) > w {
+                        textwrap::wrap(text, w.max(1))
+                            .into_iter()
+                            .map(std::borrow::Cow::into_owned)
+                            .collect()
+                    } else {
+                        vec![text.to_string()]
+                    }
+                })
+                .collect();
+            let height = cells.iter().map(Vec::len).max().unwrap_or(1).max(1);
+            for line_idx in 0..height {
+                let mut spans: Vec<Span<'static>> = Vec::new();
+                for (slot, &col) in shown.iter().enumerate() {
+                    if slot > 0 {
+                        spans.push(Span::raw(" ".repeat(COLUMN_GAP)));
+                    }
+                    let part = cells[slot].get(line_idx).map(String::as_str).unwrap_or("");
+                    let text = pad_cell(part, widths[slot], self.aligns[col]);
+                    if row.is_none() {
+                        spans.push(Span::styled(text, bold));
+                    } else {
+                        spans.push(Span::raw(text));
+                    }
+                }
+                out.push(Line::from(spans));
+            }
+            if row.is_none() {
+                // Box-drawing separator so it is not mistaken for a horizontal rule.
+                let mut spans: Vec<Span<'static>> = Vec::new();
+                for (slot, width) in widths.iter().enumerate() {
+                    if slot > 0 {
+                        spans.push(Span::raw(" ".repeat(COLUMN_GAP)));
+                    }
+                    spans.push(Span::raw("─".repeat(*width)));
+                }
+                out.push(Line::from(spans));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(source: &str) -> MarkdownTable {
+        let lines: Vec<&str> = source.lines().collect();
+        parse_table(&lines).expect("table parses").1
+    }
+
+    fn text(lines: &[Line<'static>]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect::<String>())
+            .collect()
+    }
+
+    #[test]
+    fn narrow_width_wraps_cells() {
+        let table = table(
+            "| Name | Notes | Size |\n|---|---|---:|\n| alpha | runs the nightly batch import | 12 |",
+        );
+        assert_eq!(
+            text(&table.layout(30, 0)),
+            vec![
+                "Name   Notes              Size",
+                "─────  ─────────────────  ────",
+                "alpha  runs the nightly     12",
+                "       batch import           ",
+            ]
+        );
+        // Wide enough: the natural layout.
+        assert_eq!(text(&table.layout(80, 0)), text(&table.natural_lines()));
+    }
+
+    #[test]
+    fn tables_wider_than_the_screen_scroll_by_column() {
+        let table = table(
+            "| Column one | Column two | Column three |\n|---|---|---|\n| first value | second value | third value |",
+        );
+        let first = text(&table.layout(13, 0));
+        assert_eq!(first[0], "Column one ");
+        assert_eq!(first.last().map(String::as_str), Some("column 1 of 3 ▸ · Alt+←/→ scrolls"));
+        let last = text(&table.layout(13, 9));
+        assert_eq!(last.last().map(String::as_str), Some("◂ column 3 of 3 · Alt+←/→ scrolls"));
+    }
+
+    #[test]
+    fn split_keeps_tables_in_code_fences() {
+        let pieces = split_tables("Intro\n| a | b |\n|---|---|\n| 1 | 2 |\n```\n| x | y |\n|---|---|\n```");
+        assert_eq!(pieces.len(), 3);
+        assert!(matches!(&pieces[0], MarkdownPiece::Markdown(text) if text == "Intro"));
+        assert!(matches!(&pieces[1], MarkdownPiece::Table(_)));
+        assert!(matches!(&pieces[2], MarkdownPiece::Markdown(text) if text.starts_with("```")));
+    }
+}
@@ -13,10 +13,14 @@ pub struct ResumeCandidate {
     pub created_ts: Option<String>,
     pub modified_ts: Option<String>,
     pub user_message_count: usize,
+    /// Provider-reported tokens summed over the session.
+    pub total_tokens: u64,
     pub branch: Option<String>,
     pub snippet: Option<String>,
 }
 
+const MAX_RESULTS: usize = 200;
+
 /// Return sessions matching the provided cwd using the SessionCatalog.
 /// Includes CLI, VSCode, Exec/model sessions, etc.
 pub fn list_sessions_for_cwd(
@@ -24,62 +28,12 @@ pub fn list_sessions_for_cwd(
     code_home: &Path,
     exclude_path: Option<&Path>,
 ) -> Vec<ResumeCandidate> {
-    const MAX_RESULTS: usize = 200;
-
     let code_home = code_home.to_path_buf();
     let cwd = cwd.to_path_buf();
     let exclude_path = exclude_path.map(std::path::Path::to_path_buf);
 
     let fetch = async move {
-        let catalog = SessionCatalog::new(code_home.clone());
-        let query = SessionQuery {
-            cwd: Some(cwd),
-            git_root: None,
-            sources: vec![SessionSource::Cli, SessionSource::VSCode, SessionSource::Exec],
-            // Keep broad retrieval and apply a richer eligibility check below:
-            // sessions with explicit nicknames should remain resumable even when
-            // user-message counting misses newer rollout formats.
-            min_user_messages: 0,
-            include_archived: false,
-            include_deleted: false,
-            limit: Some(MAX_RESULTS),
-        };
-
-        match catalog.query(&query).await {
-            Ok(entries) => entries
-                .into_iter()
-                .filter(|entry| {
-                    if entry.session_source == SessionSource::Mcp {
-                        return false;
-                    }
-                    let has_nickname = entry
-                        .nickname
-                        .as_deref()
-                        .map(str::trim)
-                        .is_some_and(|value| !value.is_empty());
-                    let has_snippet = entry
-                        .last_user_snippet
-                        .as_deref()
-                        .map(str::trim)
-                        .is_some_and(|value| !value.is_empty());
-                    if entry.user_message_count == 0 && !has_nickname && !has_snippet {
-                        // Preserve the existing "event-only noise" suppression,
-                        // but allow explicitly renamed sessions through.
-                        return false;
-                    }
-                    if let Some(exclude) = exclude_path.as_deref()
-                        && entry_to_rollout_path(&code_home, entry) == exclude {
-                            return false;
-                        }
-                    true
-                })
-                .map(|entry| entry_to_candidate(&code_home, entry))
-                .collect(),
-            Err(err) => {
-                tracing::warn!("failed to query session catalog: {err}");
-                Vec::new()
-            }
-        }
+        stream_sessions_for_cwd(&cwd, &code_home, exclude_path.as_deref(), |_| {}).await
     };
 
     // Execute the async fetch, reusing an existing runtime when available.
@@ -113,6 +67,79 @@ pub fn list_sessions_for_cwd(
     }
 }
 
+/// Async form of [`list_sessions_for_cwd`] that also reports provisional
+/// candidates through `on_partial` while session files are still being
+/// scanned, so the picker can show rows before the scan completes.
+pub async fn stream_sessions_for_cwd(
+    cwd: &Path,
+    code_home: &Path,
+    exclude_path: Option<&Path>,
+    mut on_partial: impl FnMut(Vec<ResumeCandidate>) + Send,
+) -> Vec<ResumeCandidate> {
+    let catalog = SessionCatalog::new(code_home.to_path_buf());
+    let query = SessionQuery {
+        cwd: Some(cwd.to_path_buf()),
+        git_root: None,
+        sources: vec![SessionSource::Cli, SessionSource::VSCode, SessionSource::Exec],
+        // Keep broad retrieval and apply a richer eligibility check below:
+        // sessions with explicit nicknames should remain resumable even when
+        // user-message counting misses newer rollout formats.
+        min_user_messages: 0,
+        include_archived: false,
+        include_deleted: false,
+        limit: Some(MAX_RESULTS),
+    };
+    let to_candidates = |entries: Vec<SessionIndexEntry>| -> Vec<ResumeCandidate> {
+        entries
+            .into_iter()
+            .filter(|entry| is_resumable(code_home, exclude_path, entry))
+            .map(|entry| entry_to_candidate(code_home, entry))
+            .collect()
+    };
+
+    match catalog
+        .query_streaming(&query, |entries| {
+            let candidates = to_candidates(entries);
+            if !candidates.is_empty() {
+                on_partial(candidates);
+            }
+        })
+        .await
+    {
+        Ok(entries) => to_candidates(entries),
+        Err(err) => {
+            tracing::warn!("failed to query session catalog: {err}");
+            Vec::new()
+        }
+    }
+}
+
+fn is_resumable(code_home: &Path, exclude_path: Option<&Path>, entry: &SessionIndexEntry) -> bool {
+    if entry.session_source == SessionSource::Mcp {
+        return false;
+    }
+    let has_nickname = entry
+        .nickname
+        .as_deref()
+        .map(str::trim)
+        .is_some_and(|value| !value.is_empty());
+    let has_snippet = entry
+        .last_user_snippet
+        .as_deref()
+        .map(str::trim)
+        .is_some_and(|value| !value.is_empty());
+    if entry.user_message_count == 0 && !has_nickname && !has_snippet {
+        // Preserve the existing "event-only noise" suppression,
+        // but allow explicitly renamed sessions through.
+        return false;
+    }
+    if let Some(exclude) = exclude_path
+        && entry_to_rollout_path(code_home, entry) == exclude {
+            return false;
+        }
+    true
+}
+
 fn entry_to_candidate(code_home: &Path, entry: SessionIndexEntry) -> ResumeCandidate {
     let path = entry_to_rollout_path(code_home, &entry);
 
@@ -123,6 +150,7 @@ fn entry_to_candidate(code_home: &Path, entry: SessionIndexEntry) -> ResumeCandi
         created_ts: Some(entry.created_at.clone()),
         modified_ts: Some(entry.last_event_at.clone()),
         user_message_count: entry.user_message_count,
+        total_tokens: entry.total_tokens,
         branch: entry.git_branch.clone(),
         snippet: entry.last_user_snippet,
     }
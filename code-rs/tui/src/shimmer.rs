@@ -19,6 +19,9 @@ pub(crate) fn shimmer_spans(text: &str) -> Vec<Span<'static>> {
     if chars.is_empty() {
         return Vec::new();
     }
+    if crate::accessibility::enabled() {
+        return vec![Span::styled(text.to_string(), Style::default().add_modifier(Modifier::BOLD))];
+    }
     // Use time-based sweep synchronized to process start.
     let padding = 10usize;
     let period = chars.len() + padding * 2;
@@ -55,11 +55,18 @@ pub enum SlashCommand {
     Init,
     Compact,
     Undo,
+    UndoPatch,
+    Retry,
     Review,
+    Tests,
     Cloud,
     Diff,
+    Copy,
+    Dictate,
     Mention,
     Cmd,
+    Rerun,
+    Output,
     Status,
     Statusline,
     Limits,
@@ -71,17 +78,29 @@ pub enum SlashCommand {
     Shell,
     Mode,
     Model,
+    Profile,
     Reasoning,
     Verbosity,
+    Schema,
+    Metered,
     Prompts,
     Skills,
     Perf,
+    Debug,
     Demo,
     Agents,
     Auto,
+    Cd,
+    Scope,
     Branch,
     Merge,
+    Spawn,
     Push,
+    Pr,
+    Map,
+    Fetch,
+    Context,
+    Artifacts,
     Validation,
     Mcp,
     Resume,
@@ -112,16 +131,27 @@ impl SlashCommand {
             SlashCommand::Code => "perform a coding task (multiple agents)",
             SlashCommand::Reasoning => "change reasoning effort (minimal/low/medium/high)",
             SlashCommand::Verbosity => "change text verbosity (high/medium/low)",
+            SlashCommand::Schema => "request answers as JSON matching a schema (/schema set <file> | clear)",
+            SlashCommand::Metered => "save data on a metered connection (/metered [on|off|auto])",
             SlashCommand::New => "start a new chat during a conversation",
             SlashCommand::Init => "create an AGENTS.md file with instructions for Code",
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
             SlashCommand::Undo => "restore the workspace to the last Code snapshot",
+            SlashCommand::UndoPatch => "revert the most recently applied patch (/undo-patch [force])",
+            SlashCommand::Retry => "regenerate the last answer (/retry [model] [temperature] | /retry a|b)",
             SlashCommand::Review => "review your changes for potential issues",
+            SlashCommand::Tests => "list the failing tests of the last run and pick one to fix",
             SlashCommand::Cloud => "browse, apply, and create cloud tasks",
             SlashCommand::Quit => "exit Code",
-            SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::Diff => {
+                "show git diff (including untracked files) (/diff export | /diff commit | /diff refresh)"
+            }
+            SlashCommand::Copy => "copy the last response to the clipboard",
+            SlashCommand::Dictate => "dictate into the composer with speech-to-text",
             SlashCommand::Mention => "mention a file",
             SlashCommand::Cmd => "run a project command",
+            SlashCommand::Rerun => "rerun a recent command (/rerun [n] | /rerun send)",
+            SlashCommand::Output => "page through a recent command's full output (/output [n])",
             SlashCommand::Status => "show current session configuration and token usage",
             SlashCommand::Statusline => {
                 "configure status line fields (/statusline [primary|secondary|top|bottom])"
@@ -135,17 +165,29 @@ impl SlashCommand {
             SlashCommand::Prompts => "manage custom prompts",
             SlashCommand::Skills => "manage skills",
             SlashCommand::Model => "choose your default model",
+            SlashCommand::Profile => "switch to a config profile (/profile [name])",
             SlashCommand::Mode => "set collaboration mode (default/plan)",
             SlashCommand::Agents => "configure agents",
             SlashCommand::Auto => "work autonomously on long tasks with Auto Drive",
+            SlashCommand::Cd => "change the session's working directory (/cd <path> | /cd -)",
+            SlashCommand::Scope => "focus the session on a subproject (/scope <dir> | /scope clear)",
             SlashCommand::Branch => {
                 "work in an isolated /branch then /merge when done (great for parallel work)"
             }
             SlashCommand::Merge => "merge current worktree branch back to default",
+            SlashCommand::Spawn => {
+                "run N agents on one task in separate worktrees, then pick one to merge (/spawn <n> <task>)"
+            }
             SlashCommand::Push => "commit, push, and monitor workflows",
+            SlashCommand::Pr => "branch, commit, push, and open a pull request",
+            SlashCommand::Map => "add a map of the repository's files and symbols to the context",
+            SlashCommand::Fetch => "fetch a web page and add its readable content to the context",
+            SlashCommand::Context => "pin facts the model always sees (/context pin key=value | unpin | edit | history)",
+            SlashCommand::Artifacts => "list files the agent saved for you (open/copy)",
             SlashCommand::Validation => "control validation harness (status/on/off)",
             SlashCommand::Mcp => "manage MCP servers",
-            SlashCommand::Perf => "performance tracing (on/off/show/reset)",
+            SlashCommand::Perf => "performance tracing (on/off/show/reset/order/hud)",
+            SlashCommand::Debug => "inspect the wire log (/debug last-request)",
             SlashCommand::Demo => "populate history with demo cells (dev/perf only)",
             SlashCommand::Login => "manage Code sign-ins (add/select/disconnect)",
             SlashCommand::Accounts => "manage account switching settings",
@@ -167,6 +167,9 @@ pub fn spinner_group_for(name: &str) -> &'static str {
 
 pub fn frame_at_time(def: &Spinner, now_ms: u128) -> String {
     if def.frames.is_empty() { return String::new(); }
+    // Accessibility mode holds the first frame so screen readers are not
+    // re-reading the status line on every tick.
+    if crate::accessibility::enabled() { return def.frames[0].clone(); }
     let idx = ((now_ms as u64 / def.interval_ms) as usize) % def.frames.len();
     def.frames[idx].clone()
 }
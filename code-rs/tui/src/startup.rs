@@ -0,0 +1,180 @@
+//! Staged startup.
+//!
+//! Only configuration and terminal setup run before the first frame. Work
+//! that the composer does not need (skills and MCP servers in the core
+//! session, account snapshots) finishes in the background; the chat widget
+//! lists the `StartupStage`s still pending in the footer.
+//!
+//! `--profile-startup` records how long each stage took, relative to the
+//! start of `run_main`, and prints the spans to stderr on exit. Each span is
+//! also logged through `tracing` so it lands in the debug log.
+
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Startup work that may still be running after the first frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StartupStage {
+    /// Core session bootstrap: skills, MCP connections, SessionConfigured.
+    Session,
+    /// Active account and its stored rate-limit snapshot.
+    Accounts,
+}
+
+impl StartupStage {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            StartupStage::Session => "skills & MCP",
+            StartupStage::Accounts => "accounts",
+        }
+    }
+
+    fn span_name(self) -> &'static str {
+        match self {
+            StartupStage::Session => "session_ready",
+            StartupStage::Accounts => "accounts_ready",
+        }
+    }
+
+    /// Records that this stage finished, as a point on the startup timeline.
+    pub(crate) fn mark_ready(self) {
+        mark(self.span_name());
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ORIGIN: OnceLock<Instant> = OnceLock::new();
+static SPANS: Mutex<Vec<SpanRecord>> = Mutex::new(Vec::new());
+
+struct SpanRecord {
+    name: &'static str,
+    start: Duration,
+    duration: Duration,
+}
+
+/// Starts the startup clock. Spans are only kept when `profile` is set.
+pub(crate) fn begin(profile: bool) {
+    ORIGIN.get_or_init(Instant::now);
+    ENABLED.store(profile, Ordering::Relaxed);
+}
+
+fn origin() -> Instant {
+    *ORIGIN.get_or_init(Instant::now)
+}
+
+/// Times the enclosing scope as a startup span named `name`.
+pub(crate) fn span(name: &'static str) -> StartupSpan {
+    StartupSpan {
+        name,
+        started: Instant::now(),
+    }
+}
+
+/// Records a zero-length span the first time `name` is reached, e.g. the
+/// first painted frame.
+pub(crate) fn mark(name: &'static str) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut spans = lock_spans();
+    if spans.iter().any(|span| span.name == name) {
+        return;
+    }
+    let start = origin().elapsed();
+    tracing::info!(target: "startup", stage = name, at_ms = start.as_millis() as u64);
+    spans.push(SpanRecord {
+        name,
+        start,
+        duration: Duration::ZERO,
+    });
+}
+
+pub(crate) struct StartupSpan {
+    name: &'static str,
+    started: Instant,
+}
+
+impl Drop for StartupSpan {
+    fn drop(&mut self) {
+        if !ENABLED.load(Ordering::Relaxed) {
+            return;
+        }
+        let duration = self.started.elapsed();
+        let start = self.started.saturating_duration_since(origin());
+        tracing::info!(
+            target: "startup",
+            stage = self.name,
+            at_ms = start.as_millis() as u64,
+            duration_ms = duration.as_millis() as u64
+        );
+        lock_spans().push(SpanRecord {
+            name: self.name,
+            start,
+            duration,
+        });
+    }
+}
+
+fn lock_spans() -> std::sync::MutexGuard<'static, Vec<SpanRecord>> {
+    SPANS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Recorded spans in start order, one per line, or `None` when profiling
+/// is off.
+pub(crate) fn report() -> Option<String> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return None;
+    }
+    let mut spans = lock_spans();
+    spans.sort_by_key(|span| span.start);
+    Some(format_report(&spans))
+}
+
+fn format_report(spans: &[SpanRecord]) -> String {
+    let width = spans.iter().map(|span| span.name.len()).max().unwrap_or(0);
+    spans
+        .iter()
+        .map(|span| {
+            let at = span.start.as_secs_f64() * 1000.0;
+            if span.duration.is_zero() {
+                format!("{:<width$}  at {at:>8.1}ms", span.name)
+            } else {
+                let took = span.duration.as_secs_f64() * 1000.0;
+                format!("{:<width$}  at {at:>8.1}ms  took {took:>8.1}ms", span.name)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn report_aligns_spans_and_marks() {
+        let spans = [
+            SpanRecord {
+                name: "config",
+                start: Duration::from_millis(2),
+                duration: Duration::from_micros(12_500),
+            },
+            SpanRecord {
+                name: "first_paint",
+                start: Duration::from_millis(40),
+                duration: Duration::ZERO,
+            },
+        ];
+        assert_eq!(
+            format_report(&spans),
+            "config       at      2.0ms  took     12.5ms\nfirst_paint  at     40.0ms"
+        );
+    }
+}
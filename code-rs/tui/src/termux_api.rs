@@ -0,0 +1,381 @@
+//! Android notifications and wake locks through Termux.
+//!
+//! Inside Termux with the `termux-api` package installed, TUI notifications go
+//! to the Android shade via `termux-notification`; tapping one brings Termux
+//! back to the front. Approval notifications also carry Approve / Deny /
+//! Review buttons. Each button runs a one-line shell command that writes
+//! `<verb> <key>` into a FIFO owned by this process, and a listener thread
+//! turns that line into an [`AppEvent::ExternalApprovalDecision`]. Review opens
+//! `termux-dialog confirm` with the full request before deciding.
+//!
+//! While a turn runs the process also holds `termux-wake-lock` so Android
+//! does not doze it mid-turn.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+
+/// Shell command that brings the Termux activity to the foreground.
+const FOCUS_ACTION: &str = "am start --user 0 -n com.termux/com.termux.app.TermuxActivity";
+
+struct PendingApproval {
+    call_id: String,
+    title: String,
+    detail: String,
+}
+
+pub(crate) struct TermuxNotifier {
+    /// Callback FIFO; `None` when it could not be created, in which case
+    /// approval notifications are posted without buttons.
+    fifo: Option<PathBuf>,
+    app_event_tx: AppEventSender,
+    pending: Mutex<HashMap<u64, PendingApproval>>,
+    next_key: AtomicU64,
+}
+
+static NOTIFIER: OnceLock<TermuxNotifier> = OnceLock::new();
+
+/// Whether this process currently holds `termux-wake-lock`.
+static WAKE_LOCK_HELD: AtomicBool = AtomicBool::new(false);
+
+/// True when running inside Termux with `termux-notification` on PATH.
+fn termux_api_installed() -> bool {
+    code_core::env::is_termux() && which::which("termux-notification").is_ok()
+}
+
+/// Sets up the notifier and its callback listener once per process. Returns
+/// false when Termux:API is unavailable.
+pub(crate) fn init(app_event_tx: AppEventSender) -> bool {
+    if NOTIFIER.get().is_some() {
+        return true;
+    }
+    if !termux_api_installed() {
+        return false;
+    }
+    let fifo = match create_callback_fifo() {
+        Ok(path) => Some(path),
+        Err(err) => {
+            tracing::warn!("termux approval callbacks disabled: {err}");
+            None
+        }
+    };
+    let notifier = TermuxNotifier {
+        fifo: fifo.clone(),
+        app_event_tx,
+        pending: Mutex::new(HashMap::new()),
+        next_key: AtomicU64::new(1),
+    };
+    if NOTIFIER.set(notifier).is_err() {
+        return true;
+    }
+    if let Some(path) = fifo {
+        spawn_listener(path);
+    }
+    true
+}
+
+pub(crate) fn notifier() -> Option<&'static TermuxNotifier> {
+    NOTIFIER.get()
+}
+
+fn wake_lock_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        code_core::env::is_termux()
+            && which::which("termux-wake-lock").is_ok()
+            && which::which("termux-wake-unlock").is_ok()
+    })
+}
+
+/// Keeps Android from dozing Termux while a turn runs. Returns true when the
+/// lock is held afterwards; false outside Termux.
+pub(crate) fn acquire_wake_lock() -> bool {
+    if WAKE_LOCK_HELD.load(Ordering::Acquire) {
+        return true;
+    }
+    if !wake_lock_available() {
+        return false;
+    }
+    spawn_termux("termux-wake-lock", Vec::new());
+    WAKE_LOCK_HELD.store(true, Ordering::Release);
+    true
+}
+
+/// Releases a wake lock taken by [`acquire_wake_lock`]. Returns true when one
+/// was held. A lock the user took by hand is never released here.
+pub(crate) fn release_wake_lock() -> bool {
+    if !WAKE_LOCK_HELD.swap(false, Ordering::AcqRel) {
+        return false;
+    }
+    spawn_termux("termux-wake-unlock", Vec::new());
+    true
+}
+
+/// Like [`release_wake_lock`] but waits for the command, for use on exit.
+pub(crate) fn release_wake_lock_blocking() {
+    if WAKE_LOCK_HELD.swap(false, Ordering::AcqRel) {
+        let _ = Command::new("termux-wake-unlock")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+}
+
+/// Short vibration via `termux-vibrate`; a no-op outside Termux.
+pub(crate) fn vibrate() {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    let available = *AVAILABLE
+        .get_or_init(|| code_core::env::is_termux() && which::which("termux-vibrate").is_ok());
+    if available {
+        spawn_termux("termux-vibrate", vec!["-d".to_string(), "300".to_string()]);
+    }
+}
+
+impl TermuxNotifier {
+    /// Posts (or replaces) the single turn-status notification.
+    pub(crate) fn notify(&self, title: &str, body: Option<&str>) {
+        let mut args = base_args(&turn_notification_id(), title, body.unwrap_or(""));
+        args.extend(["--priority".to_string(), "default".to_string()]);
+        spawn_termux("termux-notification", args);
+    }
+
+    /// Posts an approval notification with Approve / Deny / Review buttons.
+    pub(crate) fn notify_approval(&self, call_id: &str, title: &str, detail: &str) {
+        let key = self.next_key.fetch_add(1, Ordering::Relaxed);
+        let mut args = base_args(&approval_notification_id(key), title, detail);
+        args.extend(["--priority".to_string(), "high".to_string()]);
+        if let Some(fifo) = &self.fifo {
+            for (idx, (label, verb)) in [("Approve", "approve"), ("Deny", "deny"), ("Review", "review")]
+                .into_iter()
+                .enumerate()
+            {
+                let button = idx + 1;
+                args.push(format!("--button{button}"));
+                args.push(label.to_string());
+                args.push(format!("--button{button}-action"));
+                args.push(callback_command(fifo, verb, key));
+            }
+            if let Ok(mut pending) = self.pending.lock() {
+                pending.insert(
+                    key,
+                    PendingApproval {
+                        call_id: call_id.to_string(),
+                        title: title.to_string(),
+                        detail: detail.to_string(),
+                    },
+                );
+            }
+        }
+        spawn_termux("termux-notification", args);
+    }
+
+    /// Removes the notification for `call_id` once it was answered elsewhere.
+    pub(crate) fn dismiss_approval(&self, call_id: &str) {
+        let key = self.pending.lock().ok().and_then(|mut pending| {
+            let key = pending
+                .iter()
+                .find(|(_, approval)| approval.call_id == call_id)
+                .map(|(key, _)| *key)?;
+            pending.remove(&key);
+            Some(key)
+        });
+        if let Some(key) = key {
+            spawn_termux("termux-notification-remove", vec![approval_notification_id(key)]);
+        }
+    }
+
+    fn handle_callback(&self, line: &str) {
+        let Some((verb, key)) = parse_callback(line) else {
+            tracing::debug!("ignoring malformed termux callback: {line}");
+            return;
+        };
+        let Some(approval) = self.pending.lock().ok().and_then(|mut pending| pending.remove(&key))
+        else {
+            return;
+        };
+        let approved = match verb {
+            CallbackVerb::Approve => true,
+            CallbackVerb::Deny => false,
+            CallbackVerb::Review => match confirm_dialog(&approval.title, &approval.detail) {
+                Some(approved) => approved,
+                None => {
+                    // Dialog dismissed: keep the approval pending.
+                    if let Ok(mut pending) = self.pending.lock() {
+                        pending.insert(key, approval);
+                    }
+                    return;
+                }
+            },
+        };
+        spawn_termux("termux-notification-remove", vec![approval_notification_id(key)]);
+        self.app_event_tx.send(AppEvent::ExternalApprovalDecision {
+            call_id: approval.call_id,
+            approved,
+        });
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CallbackVerb {
+    Approve,
+    Deny,
+    Review,
+}
+
+fn parse_callback(line: &str) -> Option<(CallbackVerb, u64)> {
+    let mut parts = line.split_whitespace();
+    let verb = match parts.next()? {
+        "approve" => CallbackVerb::Approve,
+        "deny" => CallbackVerb::Deny,
+        "review" => CallbackVerb::Review,
+        _ => return None,
+    };
+    let key = parts.next()?.parse().ok()?;
+    parts.next().is_none().then_some((verb, key))
+}
+
+fn turn_notification_id() -> String {
+    format!("code-{}-turn", std::process::id())
+}
+
+fn approval_notification_id(key: u64) -> String {
+    format!("code-{}-approval-{key}", std::process::id())
+}
+
+fn base_args(id: &str, title: &str, content: &str) -> Vec<String> {
+    vec![
+        "--id".to_string(),
+        id.to_string(),
+        "--title".to_string(),
+        title.to_string(),
+        "--content".to_string(),
+        content.to_string(),
+        "--group".to_string(),
+        "code".to_string(),
+        "--action".to_string(),
+        FOCUS_ACTION.to_string(),
+    ]
+}
+
+fn callback_command(fifo: &std::path::Path, verb: &str, key: u64) -> String {
+    let path = fifo.to_string_lossy();
+    let quoted = shlex::try_quote(&path).unwrap_or(path.clone());
+    format!("echo {verb} {key} > {quoted}")
+}
+
+/// Runs a Termux:API command without waiting; the API round-trips through an
+/// Android service and can take a second or more.
+fn spawn_termux(program: &'static str, args: Vec<String>) {
+    let spawned = Command::new(program)
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    match spawned {
+        Ok(mut child) => {
+            crate::thread_spawner::spawn_lightweight("termux-api", move || {
+                let _ = child.wait();
+            });
+        }
+        Err(err) => tracing::warn!("failed to run {program}: {err}"),
+    }
+}
+
+/// Shows a yes/no dialog; `None` when it was dismissed or failed.
+fn confirm_dialog(title: &str, detail: &str) -> Option<bool> {
+    let output = Command::new("termux-dialog")
+        .args(["confirm", "-t", title, "-i", detail])
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    match value.get("text").and_then(serde_json::Value::as_str)? {
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(unix)]
+fn create_callback_fifo() -> std::io::Result<PathBuf> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = std::env::var_os("TMPDIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir);
+    let path = dir.join(format!("code-termux-{}.fifo", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())?;
+    // SAFETY: `c_path` is a valid NUL-terminated path for the duration of the call.
+    if unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(path)
+}
+
+#[cfg(not(unix))]
+fn create_callback_fifo() -> std::io::Result<PathBuf> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "callback FIFOs require a unix platform",
+    ))
+}
+
+fn spawn_listener(path: PathBuf) {
+    use std::io::BufRead;
+
+    let spawned = std::thread::Builder::new()
+        .name("termux-callbacks".to_string())
+        .spawn(move || {
+            loop {
+                // Opening blocks until a button action opens the FIFO for
+                // writing; each action writes one line and closes it.
+                let file = match std::fs::File::open(&path) {
+                    Ok(file) => file,
+                    Err(err) => {
+                        tracing::warn!("termux callback listener stopped: {err}");
+                        return;
+                    }
+                };
+                for line in std::io::BufReader::new(file).lines().map_while(Result::ok) {
+                    if let Some(notifier) = notifier() {
+                        notifier.handle_callback(line.trim());
+                    }
+                }
+            }
+        });
+    if let Err(err) = spawned {
+        tracing::warn!("failed to start termux callback listener: {err}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn callbacks_parse_only_known_verbs_and_numeric_keys() {
+        assert_eq!(parse_callback("approve 3"), Some((CallbackVerb::Approve, 3)));
+        assert_eq!(parse_callback("review 12"), Some((CallbackVerb::Review, 12)));
+        assert_eq!(parse_callback("deny x"), None);
+        assert_eq!(parse_callback("approve 3 extra"), None);
+        assert_eq!(parse_callback("run 3"), None);
+    }
+
+    #[test]
+    fn callback_command_quotes_fifo_path() {
+        let command = callback_command(std::path::Path::new("/tmp/a b/code.fifo"), "deny", 7);
+        assert_eq!(command, "echo deny 7 > '/tmp/a b/code.fifo'");
+    }
+}
@@ -0,0 +1,182 @@
+//! One shared thread for delayed callbacks.
+//!
+//! Short deferred work (flushing pending exec ends, clearing a debounce flag,
+//! a redraw after a hint expires) used to spawn a thread per request that
+//! slept and then sent one event. Under a burst of tool calls that meant
+//! hundreds of sleeping OS threads, and once `thread_spawner`'s cap was hit
+//! the work ran immediately instead of after its delay. `run_after` queues the
+//! callback on a single timer thread instead.
+//!
+//! Callbacks run on the timer thread, so they must be quick (typically an
+//! `AppEventSender::send`). Anything slow should spawn its own worker.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::Duration;
+use std::time::Instant;
+
+use once_cell::sync::Lazy;
+
+use crate::thread_spawner;
+
+type Task = Box<dyn FnOnce() + Send + 'static>;
+
+static TIMER_QUEUE: Lazy<TimerQueue> = Lazy::new(TimerQueue::new);
+
+/// Runs `task` on the shared timer thread once `delay` has elapsed. Tasks
+/// with equal deadlines run in the order they were queued. If the timer
+/// thread cannot be started, `task` runs immediately on the caller's thread.
+pub(crate) fn run_after<F>(delay: Duration, task: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    TIMER_QUEUE.push(Instant::now() + delay, Box::new(task));
+}
+
+struct Entry {
+    deadline: Instant,
+    seq: u64,
+    task: Task,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    // Reversed so the max-heap pops the earliest deadline first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.deadline, other.seq).cmp(&(self.deadline, self.seq))
+    }
+}
+
+struct QueueState {
+    entries: BinaryHeap<Entry>,
+    next_seq: u64,
+    worker_running: bool,
+}
+
+struct TimerQueue {
+    state: Mutex<QueueState>,
+    cv: Condvar,
+}
+
+impl TimerQueue {
+    fn new() -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                entries: BinaryHeap::new(),
+                next_seq: 0,
+                worker_running: false,
+            }),
+            cv: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn push(&'static self, deadline: Instant, task: Task) {
+        let mut state = self.lock();
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.entries.push(Entry {
+            deadline,
+            seq,
+            task,
+        });
+        let should_spawn = !state.worker_running;
+        state.worker_running = true;
+        drop(state);
+        self.cv.notify_one();
+
+        if should_spawn && thread_spawner::spawn_lightweight("timers", move || self.run()).is_none() {
+            // Same fallback the per-request threads had: do the work now
+            // rather than never.
+            let drained = {
+                let mut state = self.lock();
+                state.worker_running = false;
+                std::mem::take(&mut state.entries).into_sorted_vec()
+            };
+            // `into_sorted_vec` is ascending by `Ord`, i.e. latest first.
+            for entry in drained.into_iter().rev() {
+                (entry.task)();
+            }
+        }
+    }
+
+    /// Worker loop. Stays alive for the rest of the process once started so
+    /// a steady trickle of short timers does not keep respawning it.
+    fn run(&self) {
+        let mut state = self.lock();
+        loop {
+            let Some(deadline) = state.entries.peek().map(|entry| entry.deadline) else {
+                state = self
+                    .cv
+                    .wait(state)
+                    .unwrap_or_else(std::sync::PoisonError::into_inner);
+                continue;
+            };
+            let now = Instant::now();
+            if deadline <= now {
+                let Some(entry) = state.entries.pop() else {
+                    continue;
+                };
+                drop(state);
+                (entry.task)();
+                state = self.lock();
+                continue;
+            }
+            let (new_state, _) = self
+                .cv
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            state = new_state;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::Arc;
+
+    #[test]
+    fn entries_pop_by_deadline_then_queue_order() {
+        let start = Instant::now();
+        let fired = Arc::new(Mutex::new(Vec::new()));
+        let mut heap = BinaryHeap::new();
+        for (seq, (label, delay_ms)) in [("late", 60), ("early", 10), ("middle", 30), ("early-2", 10)]
+            .into_iter()
+            .enumerate()
+        {
+            let fired = Arc::clone(&fired);
+            heap.push(Entry {
+                deadline: start + Duration::from_millis(delay_ms),
+                seq: seq as u64,
+                task: Box::new(move || fired.lock().unwrap().push(label)),
+            });
+        }
+        while let Some(entry) = heap.pop() {
+            (entry.task)();
+        }
+        assert_eq!(*fired.lock().unwrap(), vec!["early", "early-2", "middle", "late"]);
+    }
+}
@@ -10,7 +10,9 @@ use std::path::PathBuf;
 use code_core::command_canonicalization::{
     canonical_approval_command_kind,
     canonicalize_command_for_approval,
+    powershell_preview_statements,
     CanonicalApprovalCommandKind,
+    PowerShellPreviewStage,
 };
 use code_core::protocol::Op;
 use code_core::protocol::ReviewDecision;
@@ -94,7 +96,15 @@ impl UserApprovalWidget<'_> {
         before_ticket: BackgroundOrderTicket,
         app_event_tx: AppEventSender,
     ) -> Self {
+        let powershell_preview = match &approval_request {
+            ApprovalRequest::Exec { command, .. } => powershell_preview_statements(command),
+            _ => None,
+        };
         let confirmation_prompt = match &approval_request {
+            ApprovalRequest::Exec { reason, .. } if powershell_preview.is_some() => {
+                let statements = powershell_preview.as_deref().unwrap_or_default();
+                powershell_confirmation_prompt(statements, reason.as_deref())
+            }
             ApprovalRequest::Exec {
                 command, reason, ..
             } => {
@@ -123,7 +133,8 @@ impl UserApprovalWidget<'_> {
                 let mut contents: Vec<Line> = vec![];
 
                 if let Some(r) = reason {
-                    contents.push(Line::from(r.clone().italic()));
+                    // Lint gate notes list findings on their own lines.
+                    contents.extend(r.lines().map(|line| Line::from(line.to_string().italic())));
                     contents.push(Line::from(""));
                 }
 
@@ -350,13 +361,86 @@ impl UserApprovalWidget<'_> {
         }
     }
 
+    /// Id of the agent call this widget is asking about; `None` for terminal
+    /// commands, which are not routed through core.
+    pub(crate) fn request_id(&self) -> Option<&str> {
+        match &self.approval_request {
+            ApprovalRequest::Exec { id, .. } | ApprovalRequest::ApplyPatch { id, .. } => Some(id),
+            ApprovalRequest::TerminalCommand { .. } => None,
+        }
+    }
+
+    /// Apply a decision made outside the modal (e.g. from a notification).
+    pub(crate) fn resolve_external(&mut self, decision: ReviewDecision) {
+        if !self.done {
+            self.send_decision(decision);
+        }
+    }
+
     /// Returns `true` once the user has made a decision and the widget no
     /// longer needs to be displayed.
     pub(crate) fn is_complete(&self) -> bool {
         self.done
     }
 
+    /// The request and its choices as plain text, one line each, for
+    /// accessibility mode: no glyphs, no border, and the selection spelled
+    /// out rather than shown by color.
+    fn linear_lines(&self) -> Vec<Line<'static>> {
+        let mut lines: Vec<Line<'static>> = Vec::new();
+        let (request, reason, grant_root) = match &self.approval_request {
+            ApprovalRequest::Exec {
+                command, reason, ..
+            } => (
+                format!("Code wants to run: {}", strip_bash_lc_and_escape(command)),
+                reason.as_deref(),
+                None,
+            ),
+            ApprovalRequest::ApplyPatch {
+                reason, grant_root, ..
+            } => (
+                "Code wants to apply a patch.".to_string(),
+                reason.as_deref(),
+                grant_root.as_ref(),
+            ),
+            ApprovalRequest::TerminalCommand { command, .. } => {
+                (format!("Run shell command now: {command}"), None, None)
+            }
+        };
+        lines.push(Line::from(format!("APPROVAL NEEDED: {request}")));
+        if let Some(reason) = reason {
+            for (idx, line) in reason.lines().enumerate() {
+                if idx == 0 {
+                    lines.push(Line::from(format!("Reason: {line}")));
+                } else {
+                    lines.push(Line::from(line.to_string()));
+                }
+            }
+        }
+        if let Some(root) = grant_root {
+            lines.push(Line::from(format!(
+                "This will grant write access to {} for the remainder of this session.",
+                root.display()
+            )));
+        }
+        for (idx, option) in self.select_options.iter().enumerate() {
+            let selected = if idx == self.selected_option { " [selected]" } else { "" };
+            lines.push(Line::from(format!(
+                "{}{}: {}{selected}",
+                option.label,
+                hotkey_suffix(option.hotkey),
+                option.description
+            )));
+        }
+        lines
+    }
+
     pub(crate) fn desired_height(&self, width: u16) -> u16 {
+        if crate::accessibility::enabled() {
+            return Paragraph::new(self.linear_lines())
+                .wrap(Wrap { trim: false })
+                .line_count(width) as u16;
+        }
         let prompt = self.get_confirmation_prompt_height(width);
         let option_lines = (self.select_options.len() as u16).saturating_mul(2);
         prompt + option_lines + 2
@@ -365,6 +449,12 @@ impl UserApprovalWidget<'_> {
 
 impl WidgetRef for &UserApprovalWidget<'_> {
     fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        if crate::accessibility::enabled() {
+            Paragraph::new(self.linear_lines())
+                .wrap(Wrap { trim: false })
+                .render(area, buf);
+            return;
+        }
         let prompt_height = self.get_confirmation_prompt_height(area.width);
         let [prompt_chunk, options_chunk] = Layout::vertical([
             Constraint::Length(prompt_height),
@@ -414,6 +504,50 @@ impl WidgetRef for &UserApprovalWidget<'_> {
     }
 }
 
+fn powershell_confirmation_prompt(
+    statements: &[Vec<PowerShellPreviewStage>],
+    reason: Option<&str>,
+) -> Paragraph<'static> {
+    let mut contents: Vec<Line> = vec![
+        Line::from(""),
+        Line::from(vec![
+            "? ".fg(crate::colors::info()),
+            "Code wants to run PowerShell".bold(),
+        ]),
+    ];
+    contents.extend(statements.iter().map(|stages| powershell_statement_line(stages)));
+    contents.push(Line::from(""));
+    if let Some(reason) = reason {
+        contents.push(Line::from(reason.to_string().italic()));
+        contents.push(Line::from(""));
+    }
+    Paragraph::new(contents).wrap(Wrap { trim: false })
+}
+
+/// Renders one PowerShell statement with its pipeline stages joined by `|`,
+/// calling out cmdlets that delete or overwrite data.
+fn powershell_statement_line(stages: &[PowerShellPreviewStage]) -> Line<'static> {
+    let mut spans: Vec<Span<'static>> = vec!["  ".into()];
+    for (idx, stage) in stages.iter().enumerate() {
+        if idx > 0 {
+            spans.push(" | ".fg(crate::colors::text_dim()));
+        }
+        if stage.destructive {
+            spans.push(Span::styled(
+                stage.text.clone(),
+                Style::default()
+                    .fg(crate::colors::error())
+                    .add_modifier(Modifier::BOLD),
+            ));
+        } else {
+            let mut span: Span = stage.text.clone().into();
+            span.style = span.style.add_modifier(Modifier::DIM);
+            spans.push(span);
+        }
+    }
+    Line::from(spans)
+}
+
 fn build_exec_select_options(command: &[String]) -> Vec<SelectOption> {
     let mut options = Vec::new();
 
@@ -53,6 +53,7 @@ fn exec_command_completes_properly() {
             stderr: String::new(),
             exit_code: 0,
             duration: Duration::from_millis(50),
+            redacted_values: 0,
         }),
         order: Some(next_order_meta(1, &mut seq)),
     });
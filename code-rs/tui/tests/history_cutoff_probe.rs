@@ -175,6 +175,7 @@ fn collapsed_reasoning_before_exec_keeps_last_line() {
             stderr: String::new(),
             exit_code: 0,
             duration: Duration::from_millis(120),
+            redacted_values: 0,
         }),
         order: Some(order(&mut seq)),
     });
@@ -407,6 +408,7 @@ fn exec_history_small_viewport_keeps_last_line() {
             stderr: String::new(),
             exit_code: 0,
             duration: Duration::from_millis(120),
+            redacted_values: 0,
         }),
         order: Some(order(&mut seq)),
     });
@@ -441,6 +443,7 @@ fn browser_history_small_viewport_keeps_last_line() {
             snapshot,
             url: Some("https://example.com".into()),
             captured_at: Some("2025-11-05T12:00:00Z".into()),
+            screenshot_path: None,
         }),
         order: Some(OrderMeta {
             request_ordinal: 4,
@@ -12,7 +12,7 @@ use filetime::{set_file_mtime, FileTime};
 use tempfile::TempDir;
 use uuid::Uuid;
 
-use code_tui::resume::discovery::list_sessions_for_cwd;
+use code_tui::resume::discovery::{list_sessions_for_cwd, stream_sessions_for_cwd};
 
 fn must<T, E: std::fmt::Display>(result: Result<T, E>, context: &str) -> T {
     result.unwrap_or_else(|err| panic!("{context}: {err}"))
@@ -297,3 +297,41 @@ fn resume_picker_excludes_current_path_and_empty_sessions() {
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].path, visible);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn resume_picker_streams_partial_results_before_final_list() {
+    let temp = must(TempDir::new(), "failed to create tempdir");
+    let cwd = std::path::PathBuf::from("/project");
+
+    let mut expected = Vec::new();
+    for (idx, id) in [
+        "11111111-1111-4111-8111-111111111111",
+        "22222222-2222-4222-8222-222222222222",
+        "33333333-3333-4333-8333-333333333333",
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        expected.push(write_rollout(
+            temp.path(),
+            must(Uuid::parse_str(id), "invalid UUID for streamed session"),
+            &format!("2025-11-1{idx}T10:00:00Z"),
+            &format!("2025-11-1{idx}T10:05:00Z"),
+            &cwd,
+            SessionSource::Cli,
+            "streamed",
+        ));
+    }
+    expected.reverse();
+
+    let mut partials = Vec::new();
+    let results = stream_sessions_for_cwd(&cwd, temp.path(), None, |candidates| {
+        partials.push(candidates.len());
+    })
+    .await;
+
+    assert!(!partials.is_empty(), "expected at least one partial update");
+    assert!(partials.iter().all(|&len| (1..=3).contains(&len)));
+    let paths: Vec<_> = results.into_iter().map(|cand| cand.path).collect();
+    assert_eq!(paths, expected);
+}
@@ -72,6 +72,7 @@ fn exec_cell_clears_after_patch_flow() {
             stderr: String::new(),
             exit_code: 0,
             duration: Duration::from_millis(50),
+            redacted_values: 0,
         }),
         order: Some(next_order_meta(1, &mut seq)),
     });
@@ -215,6 +216,7 @@ fn synthetic_end_clears_cancelled_exec_spinner() {
             stderr: "Command cancelled by user.".to_string(),
             exit_code: 130,
             duration: Duration::ZERO,
+            redacted_values: 0,
         }),
         order: Some(next_order_meta(1, &mut seq)),
     });
@@ -327,6 +329,7 @@ fn wait_interrupt_after_exec_end_does_not_mutate_exec() {
             stderr: String::new(),
             exit_code: 0,
             duration: Duration::from_millis(5),
+            redacted_values: 0,
         }),
         order: Some(next_order_meta(1, &mut seq)),
     });
@@ -403,6 +406,7 @@ fn wait_missing_job_skips_merged_exec() {
             stderr: String::new(),
             exit_code: 0,
             duration: Duration::from_millis(5),
+            redacted_values: 0,
         }),
         order: Some(next_order_meta(1, &mut seq)),
     });
@@ -428,6 +432,7 @@ fn wait_missing_job_skips_merged_exec() {
             stderr: String::new(),
             exit_code: 0,
             duration: Duration::from_millis(5),
+            redacted_values: 0,
         }),
         order: Some(next_order_meta(1, &mut seq)),
     });
@@ -501,6 +506,7 @@ fn exec_begin_upgrades_running_tool_cell() {
             stderr: String::new(),
             exit_code: 0,
             duration: Duration::from_millis(5),
+            redacted_values: 0,
         }),
         order: Some(next_order_meta(1, &mut seq)),
     });
@@ -645,6 +651,7 @@ fn queued_exec_end_flushes_after_stream_clears() {
             stderr: String::new(),
             exit_code: 0,
             duration: Duration::from_millis(5),
+            redacted_values: 0,
         }),
         order: Some(next_order_meta(1, &mut seq)),
     });
@@ -715,6 +722,7 @@ fn background_style_exec_end_with_zero_seq_does_not_get_stuck() {
             stderr: String::new(),
             exit_code: 0,
             duration: Duration::from_millis(5),
+            redacted_values: 0,
         }),
         order: Some(next_order_meta(1, &mut seq)),
     });
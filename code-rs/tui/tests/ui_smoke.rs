@@ -88,6 +88,7 @@ fn cli_web_search_flag_defaults() {
         resume_picker: false,
         resume_last: false,
         resume_session_id: None,
+        attach_socket: None,
         compact_prompt_override: None,
         compact_prompt_file: None,
     };
@@ -121,6 +122,7 @@ fn cli_web_search_flag_explicit_enable() {
         resume_picker: false,
         resume_last: false,
         resume_session_id: None,
+        attach_socket: None,
         compact_prompt_override: None,
         compact_prompt_file: None,
     };
@@ -154,6 +156,7 @@ fn cli_web_search_flag_disable() {
         resume_picker: false,
         resume_last: false,
         resume_session_id: None,
+        attach_socket: None,
         compact_prompt_override: None,
         compact_prompt_file: None,
     };
@@ -489,6 +492,7 @@ fn smoke_exec_begin_end_consistency() {
         stderr: String::new(),
         exit_code: 0,
         duration: Duration::from_secs(2),
+        redacted_values: 0,
     };
 
     assert_eq!(end.call_id, begin.call_id);
@@ -674,6 +678,7 @@ fn smoke_exec_command_stream() {
             stderr: String::new(),
             exit_code: 0,
             duration: Duration::from_millis(20),
+            redacted_values: 0,
         }),
         order: Some(OrderMeta {
             request_ordinal: 1,
@@ -717,6 +717,7 @@ fn context_cell_shows_browser_badge() {
             snapshot,
             url: Some("https://example.com".into()),
             captured_at: Some("2025-11-05T12:00:00Z".into()),
+            screenshot_path: None,
         }),
         order: Some(OrderMeta {
             request_ordinal: 1,
@@ -2497,6 +2498,8 @@ fn compaction_checkpoint_warning_notice() {
         event_seq: 0,
         msg: EventMsg::CompactionCheckpointWarning(CompactionCheckpointWarningEvent {
             message: COMPACTION_CHECKPOINT_MESSAGE.to_string(),
+            compacted_messages: None,
+            rollout_path: None,
         }),
         order: None,
     });